@@ -1,8 +1,16 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod path;
+mod spans;
 mod syntax;
+#[cfg(feature = "std")]
+pub use path::glob_match_path;
+pub use path::normalize_path;
+pub use spans::{MatchSpan, MatchSpans, glob_match_spans};
 pub use syntax::{skip_braces, skip_char_class};
 
 use alloc::vec::Vec;
@@ -44,8 +52,14 @@ enum BraceState {
     EndBrace,
 }
 
+/// Brace nesting levels kept inline; deeper levels spill to the heap.
+const INLINE_BRACE_DEPTH: usize = 10;
+
 struct BraceStack {
-    stack: [State; 10],
+    stack: [State; INLINE_BRACE_DEPTH],
+    /// Levels past [`INLINE_BRACE_DEPTH`]. Only pathological patterns
+    /// allocate.
+    overflow: Vec<State>,
     length: u32,
     longest_brace_match: u32,
 }
@@ -142,9 +156,6 @@ impl<'a> Matcher<'a> {
                         }
                     }
                     b'{' => {
-                        if self.brace_stack.length as usize >= self.brace_stack.stack.len() {
-                            return false;
-                        }
                         self.state.end_capture(&mut captures);
                         self.state.begin_capture(
                             &mut captures,
@@ -560,7 +571,8 @@ impl Default for BraceStack {
     fn default() -> Self {
         // Manual implementation is faster than the automatically derived one.
         BraceStack {
-            stack: [State::default(); 10],
+            stack: [State::default(); INLINE_BRACE_DEPTH],
+            overflow: Vec::new(),
             length: 0,
             longest_brace_match: 0,
         }
@@ -572,7 +584,14 @@ impl BraceStack {
     #[inline(always)]
     fn push(&mut self, state: &State) -> State {
         // Push old state to the stack, and reset current state.
-        self.stack[self.length as usize] = *state;
+        let index = self.length as usize;
+        if index < INLINE_BRACE_DEPTH {
+            self.stack[index] = *state;
+        } else if let Some(slot) = self.overflow.get_mut(index - INLINE_BRACE_DEPTH) {
+            *slot = *state;
+        } else {
+            self.overflow.push(*state);
+        }
         self.length += 1;
         State {
             path_index: state.path_index,
@@ -585,14 +604,15 @@ impl BraceStack {
     #[allow(clippy::inline_always)]
     #[inline(always)]
     fn pop(&mut self, state: &State, captures: &mut Option<&mut Vec<Capture>>) -> State {
+        let saved = *self.last();
         self.length -= 1;
         let mut state = State {
             path_index: (self.longest_brace_match - 1) as usize,
             glob_index: state.glob_index,
             // But restore star state if needed later.
-            wildcard: self.stack[self.length as usize].wildcard,
-            globstar: self.stack[self.length as usize].globstar,
-            capture_index: self.stack[self.length as usize].capture_index,
+            wildcard: saved.wildcard,
+            globstar: saved.globstar,
+            capture_index: saved.capture_index,
         };
         if self.length == 0 {
             self.longest_brace_match = 0;
@@ -608,7 +628,12 @@ impl BraceStack {
     #[allow(clippy::inline_always)]
     #[inline(always)]
     fn last(&self) -> &State {
-        &self.stack[self.length as usize - 1]
+        let index = self.length as usize - 1;
+        if index < INLINE_BRACE_DEPTH {
+            &self.stack[index]
+        } else {
+            &self.overflow[index - INLINE_BRACE_DEPTH]
+        }
     }
 }
 
@@ -2220,6 +2245,73 @@ mod tests {
         assert!(glob_match("{,*.{foo,db},\\(bar\\)}", "a.db"));
     }
 
+    /// `{x0,{x1,{x2,...{xN}}}}`: braces nested `depth` levels deep.
+    fn nested_braces(depth: usize) -> alloc::string::String {
+        use core::fmt::Write;
+
+        let mut glob = alloc::string::String::new();
+        for level in 0..depth {
+            let _ = write!(glob, "{{x{level},");
+        }
+        glob.push_str("leaf");
+        glob.push_str(&"}".repeat(depth));
+        glob
+    }
+
+    #[test]
+    fn braces_nested_past_inline_depth() {
+        for depth in [9, 10, 11, 12, 64] {
+            let glob = nested_braces(depth);
+            assert!(glob_match(&glob, "leaf"), "{glob}");
+            assert!(glob_match(&glob, "x0"), "{glob}");
+            assert!(
+                glob_match(&glob, &alloc::format!("x{}", depth - 1)),
+                "{glob}"
+            );
+            assert!(!glob_match(&glob, "x"), "{glob}");
+            assert!(!glob_match(&glob, "leafx"), "{glob}");
+        }
+        let glob = alloc::format!("src/{}/*.rs", nested_braces(20));
+        assert!(glob_match(&glob, "src/x15/main.rs"));
+        assert!(!glob_match(&glob, "src/x20/main.rs"));
+    }
+
+    #[test]
+    fn braces_nested_past_inline_depth_capture() {
+        let glob = alloc::format!("{}/*.rs", nested_braces(16));
+        assert_eq!(
+            glob_match_with_captures(&glob, "x12/main.rs").map(|c| c.len()),
+            Some(17)
+        );
+    }
+
+    #[test]
+    fn braces_pathological() {
+        // Unbalanced braces behave the same past the inline depth as within it.
+        for (glob, path) in [("{a", "a"), ("{a,b", "b"), ("{a,b", "c")] {
+            let deep = alloc::format!("{}{glob}", "{".repeat(100));
+            let shallow = alloc::format!("{}{glob}", "{".repeat(3));
+            assert_eq!(
+                glob_match(&deep, path),
+                glob_match(&shallow, path),
+                "{glob}"
+            );
+        }
+        assert!(glob_match(
+            &alloc::format!("a{}", "}".repeat(100)),
+            &alloc::format!("a{}", "}".repeat(100))
+        ));
+        // Many sibling groups and a deep chain of `**` alternatives.
+        let siblings = "{a,b}".repeat(32);
+        assert!(glob_match(&siblings, &"ab".repeat(16)));
+        assert!(!glob_match(&siblings, &"ab".repeat(15)));
+        let mut globstars = alloc::string::String::from("leaf");
+        for _ in 0..12 {
+            globstars = alloc::format!("{{**/{globstars},x}}");
+        }
+        assert!(glob_match(&globstars, "a/b/leaf"));
+    }
+
     #[test]
     fn braces_globstar_paths() {
         assert!(!glob_match("a/b/**/c{d,e}/**/xyz.md", "a/b/c/xyz.md"));
@@ -0,0 +1,125 @@
+//! Matching Windows paths against globs written with `/`.
+//!
+//! `\` escapes the next character in a glob, so a glob can only spell
+//! separators as `/`. The matcher accepts either separator in a path, but
+//! that is not enough for Windows paths: a lowercase drive letter or a
+//! verbatim prefix (`\\?\C:\...`) keeps `C:/work/**` from matching, and
+//! code that drops a leading `./` before matching misses `.\`.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// `path` with Windows separators and prefixes rewritten for matching:
+///
+/// - `\` separators become `/`.
+/// - A lowercase drive letter becomes uppercase: `c:\src` is `C:/src`.
+/// - A UNC path keeps its leading `//`: `\\server\share` is
+///   `//server/share`.
+/// - Verbatim and device prefixes are dropped: `\\?\C:\src` is `C:/src`
+///   and `\\?\UNC\server\share` is `//server/share`.
+///
+/// Borrows `path` when it needs no change.
+pub fn normalize_path(path: &str) -> Cow<'_, str> {
+    let (unc, rest) = match path
+        .strip_prefix(r"\\?\")
+        .or_else(|| path.strip_prefix(r"\\.\"))
+    {
+        Some(rest) => match rest.strip_prefix(r"UNC\") {
+            Some(share) => (true, share),
+            None => (false, rest),
+        },
+        None => (false, path),
+    };
+    let bytes = rest.as_bytes();
+    let lowercase_drive = bytes.len() >= 2 && bytes[0].is_ascii_lowercase() && bytes[1] == b':';
+    if rest.len() == path.len() && !lowercase_drive && !rest.contains('\\') {
+        return Cow::Borrowed(path);
+    }
+
+    let mut normalized = String::with_capacity(rest.len() + 2);
+    if unc {
+        normalized.push_str("//");
+    }
+    for (i, c) in rest.char_indices() {
+        normalized.push(match c {
+            '\\' => '/',
+            c if i == 0 && lowercase_drive => c.to_ascii_uppercase(),
+            c => c,
+        });
+    }
+    Cow::Owned(normalized)
+}
+
+/// Like [`glob_match`](crate::glob_match) for a [`Path`](std::path::Path).
+/// On Windows the path is normalized with [`normalize_path`] first, so
+/// `src/**/*.rs` matches `src\bin\main.rs`. A path that is not valid UTF-8
+/// matches nothing.
+#[cfg(feature = "std")]
+pub fn glob_match_path(glob: &str, path: &std::path::Path) -> bool {
+    let Some(path) = path.to_str() else {
+        return false;
+    };
+    if cfg!(windows) {
+        crate::glob_match(glob, &normalize_path(path))
+    } else {
+        crate::glob_match(glob, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_separators_drives_and_prefixes() {
+        let cases = [
+            (r"src\bin\main.rs", "src/bin/main.rs"),
+            (r"c:\work\app.yaml", "C:/work/app.yaml"),
+            (r"D:\work\app.yaml", "D:/work/app.yaml"),
+            (r"\\server\share\app.yaml", "//server/share/app.yaml"),
+            (r"\\?\c:\work\app.yaml", "C:/work/app.yaml"),
+            (r"\\?\UNC\server\share\app.yaml", "//server/share/app.yaml"),
+            (r"\\.\C:\work", "C:/work"),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(normalize_path(path), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn borrows_paths_that_need_no_change() {
+        assert!(matches!(
+            normalize_path("src/main.rs"),
+            Cow::Borrowed("src/main.rs")
+        ));
+        assert!(matches!(normalize_path("C:/src/main.rs"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn normalized_paths_match_slash_globs() {
+        let verbatim = r"\\?\c:\work\k8s\app.yaml";
+        assert!(!crate::glob_match("C:/work/**/*.yaml", verbatim));
+        assert!(crate::glob_match(
+            "C:/work/**/*.yaml",
+            &normalize_path(verbatim)
+        ));
+        assert_eq!(normalize_path(r".\src\main.rs"), "./src/main.rs");
+        assert!(crate::glob_match(
+            "//server/share/*.yaml",
+            &normalize_path(r"\\server\share\app.yaml")
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_paths() {
+        assert!(glob_match_path(
+            "src/**/*.rs",
+            std::path::Path::new("src/bin/main.rs")
+        ));
+        assert!(!glob_match_path(
+            "*.rs",
+            std::path::Path::new("src/main.rs")
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+//! Which part of a path each part of a glob matched.
+//!
+//! [`glob_match_with_captures`](crate::glob_match_with_captures) only
+//! reports what the wildcards matched, and repeated `**/` or unused brace
+//! alternatives make its captures hard to line up with the glob. A config
+//! debugging UI wants every token of the glob, literals included, paired
+//! with the part of the path it consumed.
+//!
+//! [`glob_match_spans`] splits the glob into tokens and asks the matcher
+//! itself where each token can end so that the rest of the glob still
+//! matches the rest of the path, so the spans always agree with
+//! [`glob_match`].
+
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::ops::Range;
+
+use crate::glob_match;
+use crate::syntax::{skip_braces, skip_char_class};
+
+/// One token of a glob and the part of the path it matched, as byte
+/// ranges. A token that matched nothing has an empty `path` range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchSpan {
+    pub glob: Range<usize>,
+    pub path: Range<usize>,
+}
+
+/// The [`MatchSpan`]s of a match, in glob order.
+#[derive(Clone, Debug)]
+pub struct MatchSpans {
+    spans: vec::IntoIter<MatchSpan>,
+}
+
+impl Iterator for MatchSpans {
+    type Item = MatchSpan;
+
+    fn next(&mut self) -> Option<MatchSpan> {
+        self.spans.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for MatchSpans {
+    fn next_back(&mut self) -> Option<MatchSpan> {
+        self.spans.next_back()
+    }
+}
+
+impl ExactSizeIterator for MatchSpans {}
+
+/// The part of `path` each token of `glob` matched, or `None` if `glob`
+/// does not match `path`. Also `None` in the rare cases where the matcher
+/// accepts a path that no split between the tokens matches, such as
+/// `{a,b/**}{,a/}{a,b/**}` and `b/`.
+///
+/// The tokens are runs of literal characters, `?`, `*`, `**` (with the
+/// `/` that follows it, which it also matches), `[...]` classes and whole
+/// `{...}` groups. Their spans cover `glob` and `path` from start to end.
+/// Wildcards match as little as they can. A negated glob has no spans, as
+/// it matches paths that nothing in it matched.
+///
+/// ```
+/// use glob_matcher::glob_match_spans;
+///
+/// let spans: Vec<_> = glob_match_spans("src/**/*.rs", "src/bin/main.rs")
+///     .unwrap()
+///     .map(|span| span.path)
+///     .collect();
+/// assert_eq!(spans, [0..4, 4..8, 8..12, 12..15]);
+/// ```
+pub fn glob_match_spans(glob: &str, path: &str) -> Option<MatchSpans> {
+    let negations = glob.bytes().take_while(|&b| b == b'!').count();
+    if negations % 2 == 1 || !glob_match(glob, path) {
+        return None;
+    }
+
+    let tokens = tokenize(glob.as_bytes(), negations);
+    // `rests[i]` matches what is left of the path after token `i`.
+    let mut rests = Vec::with_capacity(tokens.len());
+    let mut rest = String::new();
+    for token in tokens.iter().rev() {
+        rests.push(rest.clone());
+        rest.insert_str(0, token.pattern(glob));
+    }
+    rests.reverse();
+
+    let mut spans = Vec::with_capacity(tokens.len());
+    let mut start = 0;
+    for (token, rest) in tokens.iter().zip(&rests) {
+        let end = (start..=path.len()).find(|&end| {
+            path.is_char_boundary(end)
+                && matches(token.pattern(glob), &path[start..end])
+                && matches(rest, &path[end..])
+        })?;
+        spans.push(MatchSpan {
+            glob: token.glob.clone(),
+            path: start..end,
+        });
+        start = end;
+    }
+    Some(MatchSpans {
+        spans: spans.into_iter(),
+    })
+}
+
+/// A token of a glob.
+struct Token {
+    glob: Range<usize>,
+    /// A `**` that is not a whole path component, which matches like `*`.
+    star: bool,
+}
+
+impl Token {
+    /// A glob that matches what this token matches on its own.
+    fn pattern<'a>(&self, glob: &'a str) -> &'a str {
+        if self.star {
+            "*"
+        } else {
+            &glob[self.glob.clone()]
+        }
+    }
+}
+
+/// [`glob_match`] for a part of a glob, where a leading `!` is a literal.
+fn matches(glob: &str, path: &str) -> bool {
+    if glob.starts_with('!') {
+        let mut escaped = String::with_capacity(glob.len() + 1);
+        escaped.push('\\');
+        escaped.push_str(glob);
+        glob_match(&escaped, path)
+    } else {
+        glob_match(glob, path)
+    }
+}
+
+/// The tokens of `glob` from `start`.
+fn tokenize(glob: &[u8], start: usize) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut literal: Option<usize> = None;
+    let mut i = start;
+    while i < glob.len() {
+        let end = match glob[i] {
+            b'?' => i + 1,
+            b'[' => skip_char_class(glob, i),
+            b'{' => skip_braces(glob, i),
+            b'*' => glob[i..].iter().take_while(|&&b| b == b'*').count() + i,
+            b'\\' => {
+                literal.get_or_insert(i);
+                i = (i + 2).min(glob.len());
+                continue;
+            }
+            _ => {
+                literal.get_or_insert(i);
+                i += 1;
+                continue;
+            }
+        };
+        if let Some(literal) = literal.take() {
+            tokens.push(Token {
+                glob: literal..i,
+                star: false,
+            });
+        }
+
+        let mut token = Token {
+            glob: i..end,
+            star: false,
+        };
+        if end - i == 2 && glob[i] == b'*' {
+            let whole_component =
+                (i == start || glob[i - 1] == b'/') && (end == glob.len() || glob[end] == b'/');
+            if !whole_component {
+                token.star = true;
+            } else if end < glob.len() {
+                token.glob.end += 1;
+            }
+        }
+        i = token.glob.end;
+        tokens.push(token);
+    }
+    if let Some(literal) = literal {
+        tokens.push(Token {
+            glob: literal..glob.len(),
+            star: false,
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::vec;
+
+    fn spans<'a>(glob: &'a str, path: &'a str) -> Option<Vec<(&'a str, &'a str)>> {
+        Some(
+            glob_match_spans(glob, path)?
+                .map(|span| (&glob[span.glob], &path[span.path]))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn spans_cover_every_token() {
+        assert_eq!(
+            spans("src/**/*.rs", "src/bin/main.rs"),
+            Some(vec![
+                ("src/", "src/"),
+                ("**/", "bin/"),
+                ("*", "main"),
+                (".rs", ".rs")
+            ])
+        );
+        assert_eq!(
+            spans("test/**/*.js", "test/a.js"),
+            Some(vec![
+                ("test/", "test/"),
+                ("**/", ""),
+                ("*", "a"),
+                (".js", ".js")
+            ])
+        );
+        assert_eq!(
+            spans("a?[0-9]{x,yz}\\*", "ab7yz*"),
+            Some(vec![
+                ("a", "a"),
+                ("?", "b"),
+                ("[0-9]", "7"),
+                ("{x,yz}", "yz"),
+                ("\\*", "*")
+            ])
+        );
+        assert_eq!(
+            spans("docs/**", "docs/a/b.md"),
+            Some(vec![("docs/", "docs/"), ("**", "a/b.md")])
+        );
+        assert_eq!(
+            spans("a**b", "axxb"),
+            Some(vec![("a", "a"), ("**", "xx"), ("b", "b")])
+        );
+        assert_eq!(spans("src/*.rs", "src/bin/main.rs"), None);
+    }
+
+    #[test]
+    fn negated_globs_have_no_spans() {
+        assert!(glob_match("!*.md", "a.rs"));
+        assert_eq!(spans("!*.md", "a.rs"), None);
+        assert_eq!(
+            spans("!!*.rs", "a.rs"),
+            Some(vec![("*", "a"), (".rs", ".rs")])
+        );
+        assert_eq!(spans("a/!b", "a/!b"), Some(vec![("a/!b", "a/!b")]));
+        assert_eq!(spans("*!b", "a!b"), Some(vec![("*", "a"), ("!b", "!b")]));
+    }
+
+    #[test]
+    fn spans_agree_with_glob_match() {
+        const PARTS: [&str; 9] = ["a", "/", "*", "**", "?", "[ab]", "{a,b/**}", "{,a/}", "!"];
+        const CHARS: [char; 3] = ['a', 'b', '/'];
+
+        let mut globs = vec![String::new()];
+        for _ in 0..3 {
+            let longer: Vec<String> = globs
+                .iter()
+                .flat_map(|glob| PARTS.iter().map(move |part| format!("{glob}{part}")))
+                .collect();
+            globs.extend(longer);
+        }
+        let mut paths = vec![String::new()];
+        for _ in 0..4 {
+            let longer: Vec<String> = paths
+                .iter()
+                .flat_map(|path| CHARS.iter().map(move |c| format!("{path}{c}")))
+                .collect();
+            paths.extend(longer);
+        }
+        // See the `None` case of `glob_match_spans`.
+        globs.retain(|glob| glob.matches('{').count() < 2);
+        globs.sort();
+        globs.dedup();
+        paths.sort();
+        paths.dedup();
+
+        for glob in &globs {
+            let negated = glob.bytes().take_while(|&b| b == b'!').count() % 2 == 1;
+            for path in &paths {
+                let Some(spans) = glob_match_spans(glob, path) else {
+                    assert!(
+                        negated || !glob_match(glob, path),
+                        "{glob:?} matches {path:?} but has no spans"
+                    );
+                    continue;
+                };
+                assert!(glob_match(glob, path), "{glob:?} {path:?}");
+                let mut glob_end = glob.bytes().take_while(|&b| b == b'!').count();
+                let mut path_end = 0;
+                for span in spans {
+                    assert_eq!(span.glob.start, glob_end, "{glob:?} {path:?}");
+                    assert_eq!(span.path.start, path_end, "{glob:?} {path:?}");
+                    glob_end = span.glob.end;
+                    path_end = span.path.end;
+                }
+                assert_eq!(
+                    (glob_end, path_end),
+                    (glob.len(), path.len()),
+                    "{glob:?} {path:?}"
+                );
+            }
+        }
+    }
+}
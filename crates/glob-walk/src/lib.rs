@@ -0,0 +1,235 @@
+#![doc = include_str!("../README.md")]
+extern crate alloc;
+
+mod prune;
+
+pub use prune::base_dir;
+
+use alloc::collections::VecDeque;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use glob_matcher::glob_match_path;
+
+/// Include and exclude globs to walk the matching files of.
+#[derive(Clone, Debug, Default)]
+pub struct GlobWalker {
+    includes: Vec<String>,
+    excludes: Vec<String>,
+}
+
+impl GlobWalker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Walk the files matching `glob`.
+    pub fn include(&mut self, glob: impl Into<String>) -> &mut Self {
+        self.includes.push(glob.into());
+        self
+    }
+
+    /// Leave out the files matching `glob`, and skip the directories it
+    /// matches everything below.
+    pub fn exclude(&mut self, glob: impl Into<String>) -> &mut Self {
+        self.excludes.push(glob.into());
+        self
+    }
+
+    /// Whether `path` matches an exclude glob. A leading `./` is ignored.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        self.excludes.iter().any(|glob| glob_match_path(glob, path))
+    }
+
+    /// Whether an exclude glob ending in `/**` matches everything below
+    /// `dir`, so a walk need not read it. A leading `./` is ignored.
+    pub fn is_excluded_dir(&self, dir: &Path) -> bool {
+        let dir = dir.strip_prefix(".").unwrap_or(dir);
+        self.excludes.iter().any(|glob| {
+            glob.strip_suffix("/**")
+                .is_some_and(|parent| glob_match_path(parent, dir))
+        })
+    }
+
+    /// The files matching an include glob and no exclude glob, each include
+    /// walked from its [`base_dir`] in turn.
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            walker: self,
+            include: 0,
+            glob: "",
+            dirs: Vec::new(),
+            files: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+/// The iterator returned by [`GlobWalker::walk`].
+///
+/// A base directory that does not exist yields nothing; other I/O errors
+/// are yielded and the walk goes on.
+#[derive(Debug)]
+pub struct Walk<'a> {
+    walker: &'a GlobWalker,
+    /// The next include glob to walk.
+    include: usize,
+    /// The include glob being walked.
+    glob: &'a str,
+    dirs: Vec<PathBuf>,
+    files: VecDeque<io::Result<PathBuf>>,
+    seen: HashSet<PathBuf>,
+}
+
+impl Iterator for Walk<'_> {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(file) = self.files.pop_front() {
+                return Some(file);
+            }
+            if let Some(dir) = self.dirs.pop() {
+                self.read_dir(&dir);
+                continue;
+            }
+            self.glob = self.walker.includes.get(self.include)?;
+            self.include += 1;
+            self.dirs.push(PathBuf::from(base_dir(self.glob)));
+        }
+    }
+}
+
+impl Walk<'_> {
+    /// Queue the matching files of `dir` and the subdirectories to walk,
+    /// in name order.
+    fn read_dir(&mut self, dir: &Path) {
+        let read = if dir.as_os_str().is_empty() {
+            fs::read_dir(".")
+        } else {
+            fs::read_dir(dir)
+        };
+        let entries = match read {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return,
+            Err(e) => {
+                self.files.push_back(Err(e));
+                return;
+            }
+        };
+        let mut entries: Vec<fs::DirEntry> = entries
+            .filter_map(|entry| entry.map_err(|e| self.files.push_back(Err(e))).ok())
+            .collect();
+        entries.sort_by_key(fs::DirEntry::file_name);
+
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let path = dir.join(entry.file_name());
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(e) => {
+                    self.files.push_back(Err(e));
+                    continue;
+                }
+            };
+            if file_type.is_dir() {
+                if !self.walker.is_excluded_dir(&path) && prune::could_match_below(self.glob, &path)
+                {
+                    subdirs.push(path);
+                }
+            } else if (file_type.is_file() || path.is_file())
+                && glob_match_path(self.glob, &path)
+                && !self.walker.is_excluded(&path)
+                && self.seen.insert(path.clone())
+            {
+                self.files.push_back(Ok(path));
+            }
+        }
+        self.dirs.extend(subdirs.into_iter().rev());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(root: &Path, files: &[&str]) -> io::Result<()> {
+        for file in files {
+            let path = root.join(file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, "{}")?;
+        }
+        Ok(())
+    }
+
+    fn walk(walker: &GlobWalker, root: &Path) -> io::Result<Vec<String>> {
+        walker
+            .walk()
+            .map(|path| {
+                Ok(path?
+                    .strip_prefix(root)
+                    .expect("paths are under the root")
+                    .to_string_lossy()
+                    .replace('\\', "/"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walks_matching_files_once() -> io::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let root = tmp.path();
+        tree(
+            root,
+            &[
+                "package.json",
+                "app/package.json",
+                "app/src/config.json",
+                "app/src/deep/x.json",
+                "app/node_modules/dep/package.json",
+                "docs/readme.md",
+            ],
+        )?;
+        let glob = |glob: &str| format!("{}/{glob}", root.display());
+
+        let mut walker = GlobWalker::new();
+        walker
+            .include(glob("**/*.json"))
+            .include(glob("app/src/*.json"))
+            .exclude(glob("**/node_modules/**"))
+            .exclude(glob("**/deep/*"));
+        assert_eq!(
+            walk(&walker, root)?,
+            ["package.json", "app/package.json", "app/src/config.json"]
+        );
+
+        let mut walker = GlobWalker::new();
+        walker.include(glob("*/package.json"));
+        assert_eq!(walk(&walker, root)?, ["app/package.json"]);
+
+        let mut walker = GlobWalker::new();
+        walker.include(glob("missing/**/*.json"));
+        assert_eq!(walk(&walker, root)?, Vec::<String>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_dirs() {
+        let mut walker = GlobWalker::new();
+        walker
+            .exclude("**/node_modules/**")
+            .exclude("target/**")
+            .exclude("dist/*");
+        assert!(walker.is_excluded_dir(Path::new("node_modules")));
+        assert!(walker.is_excluded_dir(Path::new("./web/node_modules")));
+        assert!(walker.is_excluded_dir(Path::new("target")));
+        assert!(!walker.is_excluded_dir(Path::new("dist")));
+        assert!(walker.is_excluded(Path::new("./dist/app.json")));
+        assert!(!walker.is_excluded(Path::new("dist/app/app.json")));
+    }
+}
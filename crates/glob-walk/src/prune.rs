@@ -0,0 +1,81 @@
+//! Which directories a walk for a glob can skip.
+
+use std::path::Path;
+
+/// The directory every match of `glob` is under: its leading components
+/// without glob syntax, not counting the last, which names the files.
+/// Empty when the first component already has glob syntax.
+///
+/// ```
+/// use glob_walk::base_dir;
+///
+/// assert_eq!(base_dir("src/**/*.rs"), "src");
+/// assert_eq!(base_dir("config/app.json"), "config");
+/// assert_eq!(base_dir("*.json"), "");
+/// assert_eq!(base_dir("/etc/*.conf"), "/etc");
+/// assert_eq!(base_dir("/*.conf"), "/");
+/// ```
+pub fn base_dir(glob: &str) -> &str {
+    let literal = glob
+        .find(['*', '?', '[', '{', '\\'])
+        .map_or(glob, |i| &glob[..i]);
+    match literal.rfind('/') {
+        Some(0) => "/",
+        Some(i) => &glob[..i],
+        None => "",
+    }
+}
+
+/// Whether a file below `dir` can match `glob`: each component of `dir`
+/// matches the glob's component at the same depth, and the glob has
+/// components left for what is below. A `**` or a `{...}` group spanning
+/// components ends the comparison, as it can match any depth.
+pub(crate) fn could_match_below(glob: &str, dir: &Path) -> bool {
+    let Some(dir) = dir.to_str() else {
+        return true;
+    };
+    let components: Vec<&str> = glob.split('/').collect();
+    for (depth, name) in dir.split(['/', '\\']).enumerate() {
+        let Some(&component) = components.get(depth) else {
+            return false;
+        };
+        if component.contains("**")
+            || component.matches('{').count() != component.matches('}').count()
+        {
+            return true;
+        }
+        if depth + 1 == components.len() || !glob_matcher::glob_match(component, name) {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_dir_stops_at_glob_syntax() {
+        assert_eq!(base_dir("./src/*.rs"), "./src");
+        assert_eq!(base_dir("a/b/c.json"), "a/b");
+        assert_eq!(base_dir("a/b{c,d}/e.json"), "a");
+        assert_eq!(base_dir("a/\\*/e.json"), "a");
+        assert_eq!(base_dir("package.json"), "");
+    }
+
+    #[test]
+    fn directories_below_the_glob_depth_are_skipped() {
+        let below = |glob, dir| could_match_below(glob, Path::new(dir));
+        assert!(below("*/package.json", "app"));
+        assert!(!below("*/package.json", "app/node_modules"));
+        assert!(below("src/*/mod.rs", "src/parser"));
+        assert!(!below("src/*/mod.rs", "docs/parser"));
+        assert!(!below("src/*.rs", "src/parser"));
+        assert!(below("src/**/*.rs", "src/a/b/c"));
+        assert!(below("{a/b,c}/*.rs", "a/b"));
+        assert!(below("{**,x}/*.rs", "a/b/c"));
+        assert!(below("./src/*/x", "./src/a"));
+        assert!(!below("./src/*/x", "./src/a/b"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Structured completion candidates for editor integrations.
+//!
+//! Given a schema and a pointer to the node being edited, lists the property
+//! names and values that are valid there. `$ref`s are resolved, `allOf` is
+//! merged, and `oneOf`/`anyOf` branches are unioned so a plugin does not need
+//! to understand composition to offer suggestions.
+
+use indexmap::IndexMap;
+use jsonschema_schema::{Schema, SchemaValue};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::{get_description, resolve_ref};
+
+/// What a [`Completion`] inserts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompletionKind {
+    /// An object key.
+    Property,
+    /// A value, from `enum`, `const` or a boolean type.
+    Value,
+}
+
+/// A single completion candidate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Completion {
+    /// Whether this is a property name or a value.
+    pub kind: CompletionKind,
+    /// Text shown in the completion list: the property name, or the value
+    /// as JSON (`"dev"`, `true`, `3`).
+    pub label: String,
+    /// The value to insert, for [`CompletionKind::Value`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// Short type label, e.g. `string` or `string | null`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Documentation, preferring `markdownDescription`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Default value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// The property is listed in `required`.
+    pub required: bool,
+    /// The property or value is marked deprecated.
+    pub deprecated: bool,
+}
+
+/// List the completions valid at a schema JSON Pointer.
+///
+/// `pointer` addresses a sub-schema (e.g. `/properties/server`), the same
+/// form [`navigate_pointer`](crate::navigate_pointer) accepts; use `""` for
+/// the root. Properties come first in declaration order, followed by values.
+///
+/// # Errors
+///
+/// Returns an error if the pointer cannot be resolved within the schema.
+pub fn completions(schema: &SchemaValue, pointer: &str) -> Result<Vec<Completion>, String> {
+    let node = crate::schema::navigate_pointer(schema, schema, pointer)?;
+    let Some(node) = node.as_schema() else {
+        return Ok(vec![]);
+    };
+    let branches = branches(node, schema);
+
+    let mut properties: IndexMap<&str, Completion> = IndexMap::new();
+    let mut values: Vec<Completion> = Vec::new();
+    for branch in &branches {
+        let required = branch.required_set();
+        for (name, prop_sv) in &branch.properties {
+            properties
+                .entry(name.as_str())
+                .or_insert_with(|| property_completion(name, prop_sv, schema, required));
+        }
+        for completion in value_completions(branch) {
+            if !values.iter().any(|v| v.value == completion.value) {
+                values.push(completion);
+            }
+        }
+    }
+
+    Ok(properties.into_values().chain(values).collect())
+}
+
+/// The node with `allOf` merged, followed by each `oneOf`/`anyOf` branch.
+fn branches(node: &Schema, root: &SchemaValue) -> Vec<Schema> {
+    let merged = node.flatten(root);
+    let variants: Vec<Schema> = [merged.one_of.as_deref(), merged.any_of.as_deref()]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|v| resolve_ref(v, root).as_schema())
+        .map(|v| v.flatten(root))
+        .collect();
+    core::iter::once(merged).chain(variants).collect()
+}
+
+fn property_completion(
+    name: &str,
+    prop_sv: &SchemaValue,
+    root: &SchemaValue,
+    required: &[String],
+) -> Completion {
+    let prop = resolve_ref(prop_sv, root).as_schema();
+    Completion {
+        kind: CompletionKind::Property,
+        label: name.to_string(),
+        value: None,
+        type_: prop.and_then(Schema::type_str),
+        description: prop.and_then(get_description).map(String::from),
+        default: prop.and_then(|p| p.default.clone()),
+        required: required.iter().any(|r| r == name),
+        deprecated: prop.is_some_and(Schema::is_deprecated),
+    }
+}
+
+fn value_completions(schema: &Schema) -> Vec<Completion> {
+    let descriptions = schema
+        .markdown_enum_descriptions
+        .as_deref()
+        .unwrap_or_default();
+    let mut out: Vec<Completion> = schema
+        .enum_
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, v)| value_completion(v, descriptions.get(i).cloned().flatten()))
+        .collect();
+    if let Some(c) = &schema.const_ {
+        out.push(value_completion(
+            c,
+            get_description(schema).map(String::from),
+        ));
+    }
+    if out.is_empty() && schema.type_str().as_deref() == Some("boolean") {
+        out.push(value_completion(&Value::Bool(true), None));
+        out.push(value_completion(&Value::Bool(false), None));
+    }
+    out
+}
+
+fn value_completion(value: &Value, description: Option<String>) -> Completion {
+    Completion {
+        kind: CompletionKind::Value,
+        label: value.to_string(),
+        value: Some(value.clone()),
+        type_: None,
+        description,
+        default: None,
+        required: false,
+        deprecated: false,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sv(val: serde_json::Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(jsonschema_migrate::migrate(val).unwrap()))
+    }
+
+    fn labels(completions: &[Completion]) -> Vec<&str> {
+        completions.iter().map(|c| c.label.as_str()).collect()
+    }
+
+    #[test]
+    fn lists_properties_with_docs() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": {
+                "port": { "type": "integer", "description": "Port to bind", "default": 8080 },
+                "host": { "type": "string", "deprecated": true }
+            },
+            "required": ["port"]
+        }));
+        let completions = completions(&schema, "").unwrap();
+        assert_eq!(labels(&completions), ["port", "host"]);
+        let port = &completions[0];
+        assert_eq!(port.kind, CompletionKind::Property);
+        assert_eq!(port.type_.as_deref(), Some("integer"));
+        assert_eq!(port.description.as_deref(), Some("Port to bind"));
+        assert_eq!(port.default, Some(json!(8080)));
+        assert!(port.required);
+        assert!(completions[1].deprecated);
+    }
+
+    #[test]
+    fn resolves_refs_and_unions_variants() {
+        let schema = sv(json!({
+            "properties": { "step": { "$ref": "#/$defs/Step" } },
+            "$defs": {
+                "Step": {
+                    "allOf": [{ "properties": { "name": { "type": "string" } } }],
+                    "oneOf": [
+                        { "properties": { "run": { "type": "string" } }, "required": ["run"] },
+                        { "$ref": "#/$defs/Uses" }
+                    ]
+                },
+                "Uses": { "properties": { "uses": { "type": "string" }, "name": {} } }
+            }
+        }));
+        let completions = completions(&schema, "/properties/step").unwrap();
+        assert_eq!(labels(&completions), ["name", "run", "uses"]);
+        assert!(completions[1].required);
+    }
+
+    #[test]
+    fn lists_enum_const_and_boolean_values() {
+        let schema = sv(json!({
+            "properties": {
+                "mode": {
+                    "enum": ["dev", "prod"],
+                    "markdownEnumDescriptions": ["Development", "Production"]
+                },
+                "kind": { "anyOf": [{ "const": "a" }, { "const": "b" }] },
+                "debug": { "type": "boolean" }
+            }
+        }));
+        let mode = completions(&schema, "/properties/mode").unwrap();
+        assert_eq!(labels(&mode), ["\"dev\"", "\"prod\""]);
+        assert_eq!(mode[0].kind, CompletionKind::Value);
+        assert_eq!(mode[1].description.as_deref(), Some("Production"));
+        let kind = completions(&schema, "/properties/kind").unwrap();
+        assert_eq!(labels(&kind), ["\"a\"", "\"b\""]);
+        let debug = completions(&schema, "/properties/debug").unwrap();
+        assert_eq!(labels(&debug), ["true", "false"]);
+    }
+
+    #[test]
+    fn serializes_for_editors() {
+        let schema = sv(json!({ "properties": { "name": { "type": "string" } } }));
+        let completions = completions(&schema, "").unwrap();
+        assert_eq!(
+            serde_json::to_value(&completions).unwrap(),
+            json!([{
+                "kind": "property",
+                "label": "name",
+                "type": "string",
+                "required": false,
+                "deprecated": false
+            }])
+        );
+    }
+
+    #[test]
+    fn unknown_pointer_is_an_error() {
+        let schema = sv(json!({ "properties": {} }));
+        assert!(completions(&schema, "/properties/missing").is_err());
+    }
+}
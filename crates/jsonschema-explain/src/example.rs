@@ -0,0 +1,574 @@
+//! A minimal instance of a schema, as a starting point for a new document.
+//!
+//! Each value is the first of: `const`, `default`, the first of `examples`,
+//! the first `enum` value, or a placeholder for its type. Objects get only
+//! their required properties and arrays only `minItems` items. Placeholder
+//! strings follow `format` and `minLength`/`maxLength`, and a `pattern` is
+//! used when it is plain text; numbers keep to their bounds and
+//! `multipleOf`. A schema without a type takes its first `oneOf` or `anyOf`
+//! variant. Other constraints are not considered, so the instance may still
+//! need editing to validate.
+
+use alloc::borrow::Cow;
+use core::fmt::Write;
+
+use jsonschema_schema::{Schema, SchemaValue, SimpleType, TypeValue};
+use serde_json::{Map, Number, Value};
+
+use crate::ExampleFormat;
+use crate::schema::resolve_ref;
+use crate::sections::format_example;
+
+/// A minimal instance of `schema`, a sub-schema of `root` or `root` itself.
+pub fn example_instance(schema: &SchemaValue, root: &SchemaValue) -> Value {
+    Example {
+        root,
+        refs: Vec::new(),
+    }
+    .value(schema)
+}
+
+/// [`example_instance`] as a document in `format`. Instances TOML cannot
+/// represent are written as JSON.
+pub fn example_document(schema: &SchemaValue, root: &SchemaValue, format: ExampleFormat) -> String {
+    let (mut document, _) = format_example(&example_instance(schema, root), format);
+    document.push('\n');
+    document
+}
+
+/// [`example_document`] with the description of the schema and of each
+/// property as comments in YAML and TOML. JSON has no comments, so it is
+/// written as by [`example_document`], as are instances that are not
+/// objects.
+pub fn commented_example_document(
+    schema: &SchemaValue,
+    root: &SchemaValue,
+    format: ExampleFormat,
+) -> String {
+    let instance = example_instance(schema, root);
+    let Value::Object(object) = &instance else {
+        return example_document(schema, root, format);
+    };
+    let mut out = String::new();
+    let header = description_lines(schema, root);
+    for line in &header {
+        let _ = writeln!(out, "# {line}");
+    }
+    if !header.is_empty() {
+        out.push('\n');
+    }
+    match format {
+        ExampleFormat::Json => return example_document(schema, root, format),
+        ExampleFormat::Yaml => write_yaml(&mut out, object, schema, root, 0),
+        ExampleFormat::Toml => write_toml(&mut out, object, schema, root, &[]),
+    }
+    out
+}
+
+struct Example<'a> {
+    root: &'a SchemaValue,
+    /// `$ref`s being expanded, so recursive schemas end.
+    refs: Vec<String>,
+}
+
+impl Example<'_> {
+    fn value(&mut self, sv: &SchemaValue) -> Value {
+        let ref_ = sv.as_schema().and_then(|s| s.ref_.clone());
+        if let Some(ref_) = &ref_ {
+            if self.refs.contains(ref_) {
+                return Value::Null;
+            }
+            self.refs.push(ref_.clone());
+        }
+        let value = match resolve_ref(sv, self.root) {
+            SchemaValue::Schema(schema) => self.schema(schema),
+            SchemaValue::Bool(_) | SchemaValue::Other(_) => Value::Null,
+        };
+        if ref_.is_some() {
+            self.refs.pop();
+        }
+        value
+    }
+
+    fn schema(&mut self, schema: &Schema) -> Value {
+        let schema = if schema.all_of.is_some() {
+            Cow::Owned(schema.flatten(self.root))
+        } else {
+            Cow::Borrowed(schema)
+        };
+        let given = schema
+            .const_
+            .as_ref()
+            .or(schema.default.as_ref())
+            .or(schema.examples.as_deref().and_then(<[Value]>::first))
+            .or(schema.enum_.as_deref().and_then(<[Value]>::first));
+        if let Some(value) = given {
+            return value.clone();
+        }
+
+        let variant = schema
+            .one_of
+            .as_deref()
+            .or(schema.any_of.as_deref())
+            .and_then(<[SchemaValue]>::first);
+        match instance_type(&schema) {
+            Some(SimpleType::Object) => {
+                let mut object = self.object(&schema);
+                // The properties the variant requires as well
+                if let Some(Value::Object(required)) = variant.map(|v| self.value(v)) {
+                    for (name, value) in required {
+                        object.entry(name).or_insert(value);
+                    }
+                }
+                Value::Object(object)
+            }
+            Some(SimpleType::Array) => self.array(&schema),
+            Some(SimpleType::String) => Value::String(string(&schema)),
+            Some(SimpleType::Integer) => number(&schema, true),
+            Some(SimpleType::Number) => number(&schema, false),
+            Some(SimpleType::Boolean) => Value::Bool(false),
+            Some(SimpleType::Null) => Value::Null,
+            None => variant.map_or(Value::Null, |v| self.value(v)),
+        }
+    }
+
+    fn object(&mut self, schema: &Schema) -> Map<String, Value> {
+        schema
+            .required_set()
+            .iter()
+            .map(|name| {
+                let property = schema
+                    .properties
+                    .get(name)
+                    .or(schema.additional_properties.as_deref());
+                (
+                    name.clone(),
+                    property.map_or(Value::Null, |p| self.value(p)),
+                )
+            })
+            .collect()
+    }
+
+    fn array(&mut self, schema: &Schema) -> Value {
+        let prefix = schema.prefix_items.as_deref().unwrap_or_default();
+        let len = usize::try_from(schema.min_items.unwrap_or(0)).unwrap_or_default();
+        (0..len)
+            .map(|i| {
+                prefix
+                    .get(i)
+                    .or(schema.items.as_deref())
+                    .map_or(Value::Null, |item| self.value(item))
+            })
+            .collect()
+    }
+}
+
+/// The type of instance to make: the schema's type (other than `null` if it
+/// allows several), else the type its keywords imply.
+fn instance_type(schema: &Schema) -> Option<SimpleType> {
+    match &schema.type_ {
+        Some(TypeValue::Single(ty)) => Some(*ty),
+        Some(TypeValue::Union(types)) => types
+            .iter()
+            .find(|ty| **ty != SimpleType::Null)
+            .or(types.first())
+            .copied(),
+        None if !schema.properties.is_empty() || schema.required.is_some() => {
+            Some(SimpleType::Object)
+        }
+        None if schema.items.is_some() || schema.prefix_items.is_some() => Some(SimpleType::Array),
+        None => None,
+    }
+}
+
+/// A placeholder string for `format`, or `"string"` padded or cut to the
+/// length limits.
+fn string(schema: &Schema) -> String {
+    if let Some(text) = schema.pattern.as_deref().and_then(plain_pattern) {
+        return text.to_string();
+    }
+    let placeholder = match schema.format.as_deref() {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "00:00:00Z",
+        Some("duration") => "P1D",
+        Some("email" | "idn-email") => "user@example.com",
+        Some("hostname" | "idn-hostname") => "example.com",
+        Some("ipv4") => "127.0.0.1",
+        Some("ipv6") => "::1",
+        Some("uri" | "iri" | "uri-reference" | "iri-reference" | "uri-template") => {
+            "https://example.com"
+        }
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("json-pointer") => "/",
+        Some("regex") => ".*",
+        _ => {
+            let min = usize::try_from(schema.min_length.unwrap_or(0)).unwrap_or_default();
+            let max = schema
+                .max_length
+                .and_then(|max| usize::try_from(max).ok())
+                .unwrap_or(usize::MAX);
+            let mut text: String = "string".chars().take(max).collect();
+            while text.len() < min {
+                text.push('x');
+            }
+            return text;
+        }
+    };
+    placeholder.to_string()
+}
+
+/// The text a `pattern` matches when it is plain text, optionally anchored,
+/// or a choice of plain texts (`^(a|b)$`), taking the first.
+fn plain_pattern(pattern: &str) -> Option<&str> {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let pattern = pattern.strip_suffix('$').unwrap_or(pattern);
+    let pattern = pattern
+        .strip_prefix('(')
+        .and_then(|p| p.strip_suffix(')'))
+        .unwrap_or(pattern);
+    let first = pattern.split('|').next().unwrap_or(pattern);
+    let plain = !pattern.contains(|c| "\\.^$?*+()[]{}".contains(c));
+    (plain && !first.is_empty()).then_some(first)
+}
+
+/// The number nearest zero within the bounds, rounded up to `multipleOf`.
+#[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+fn number(schema: &Schema, integer: bool) -> Value {
+    let bound = |n: &Option<Number>| n.as_ref().and_then(Number::as_f64);
+    let lower = bound(&schema.minimum).or(bound(&schema.exclusive_minimum).map(|n| n + 1.0));
+    let upper = bound(&schema.maximum).or(bound(&schema.exclusive_maximum).map(|n| n - 1.0));
+    let mut n = 0.0_f64;
+    if let Some(lower) = lower {
+        n = n.max(lower);
+    }
+    if let Some(upper) = upper {
+        n = n.min(upper);
+    }
+    if let Some(step) = bound(&schema.multiple_of).filter(|step| *step > 0.0) {
+        n = (n / step).ceil() * step;
+    }
+    if integer || n.fract() == 0.0 {
+        Value::from(n.ceil() as i64)
+    } else {
+        Number::from_f64(n).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// The schema of property `name` of an instance of `schema`, from its
+/// `properties`, first `oneOf` or `anyOf` variant or `additionalProperties`,
+/// as [`example_instance`] fills them in.
+fn property_schema(schema: &SchemaValue, root: &SchemaValue, name: &str) -> Option<SchemaValue> {
+    let resolved = resolve_ref(schema, root).as_schema()?;
+    let flattened;
+    let resolved = if resolved.all_of.is_some() {
+        flattened = resolved.flatten(root);
+        &flattened
+    } else {
+        resolved
+    };
+    if let Some(property) = resolved.properties.get(name) {
+        return Some(property.clone());
+    }
+    resolved
+        .one_of
+        .as_deref()
+        .or(resolved.any_of.as_deref())
+        .and_then(<[SchemaValue]>::first)
+        .and_then(|variant| property_schema(variant, root, name))
+        .or_else(|| resolved.additional_properties.as_deref().cloned())
+}
+
+/// The first paragraph of the description of `schema`, or of the schema its
+/// `$ref` points to, by line.
+fn description_lines(schema: &SchemaValue, root: &SchemaValue) -> Vec<String> {
+    fn description(schema: &SchemaValue) -> Option<&str> {
+        let schema = schema.as_schema()?;
+        schema
+            .description
+            .as_deref()
+            .or(schema.markdown_description.as_deref())
+    }
+    let Some(text) = description(schema).or_else(|| description(resolve_ref(schema, root))) else {
+        return Vec::new();
+    };
+    text.trim()
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+fn write_comments(
+    out: &mut String,
+    schema: Option<&SchemaValue>,
+    root: &SchemaValue,
+    indent: &str,
+) {
+    for line in schema
+        .map(|s| description_lines(s, root))
+        .unwrap_or_default()
+    {
+        let _ = writeln!(out, "{indent}# {line}");
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_yaml(
+    out: &mut String,
+    object: &Map<String, Value>,
+    schema: &SchemaValue,
+    root: &SchemaValue,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    for (name, value) in object {
+        let property = property_schema(schema, root, name);
+        write_comments(out, property.as_ref(), root, &indent);
+        let key = yaml(&Value::String(name.clone()));
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                let _ = writeln!(out, "{indent}{key}:");
+                let any = SchemaValue::Bool(true);
+                write_yaml(
+                    out,
+                    nested,
+                    property.as_ref().unwrap_or(&any),
+                    root,
+                    depth + 1,
+                );
+            }
+            Value::Array(items) if !items.is_empty() => {
+                let _ = writeln!(out, "{indent}{key}:");
+                for line in yaml(value).lines() {
+                    let _ = writeln!(out, "{indent}{line}");
+                }
+            }
+            _ => {
+                let _ = writeln!(out, "{indent}{key}: {}", yaml(value));
+            }
+        }
+    }
+}
+
+fn yaml(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .unwrap_or_default()
+        .trim_end()
+        .to_string()
+}
+
+/// Write `object` as the table at `path`: its values first, then its
+/// non-empty objects as tables of their own. TOML has no `null`, so a
+/// property without a value is left commented out.
+#[allow(clippy::too_many_arguments)]
+fn write_toml(
+    out: &mut String,
+    object: &Map<String, Value>,
+    schema: &SchemaValue,
+    root: &SchemaValue,
+    path: &[String],
+) {
+    let is_table = |value: &Value| value.as_object().is_some_and(|o| !o.is_empty());
+    for (name, value) in object.iter().filter(|(_, value)| !is_table(value)) {
+        write_comments(out, property_schema(schema, root, name).as_ref(), root, "");
+        let key = toml_key(name);
+        match toml::Value::try_from(value) {
+            Ok(value) => {
+                let _ = writeln!(out, "{key} = {value}");
+            }
+            Err(_) => {
+                let _ = writeln!(out, "# {key} =");
+            }
+        }
+    }
+    for (name, value) in object {
+        let Value::Object(nested) = value else {
+            continue;
+        };
+        if nested.is_empty() {
+            continue;
+        }
+        let property = property_schema(schema, root, name);
+        let mut path = path.to_vec();
+        path.push(toml_key(name));
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        write_comments(out, property.as_ref(), root, "");
+        let _ = writeln!(out, "[{}]", path.join("."));
+        let any = SchemaValue::Bool(true);
+        write_toml(out, nested, property.as_ref().unwrap_or(&any), root, &path);
+    }
+}
+
+/// `name` as a TOML key, quoted unless it is a bare key.
+fn toml_key(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if bare {
+        name.to_string()
+    } else {
+        toml::Value::String(name.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sv(val: Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(
+            jsonschema_migrate::migrate(val).expect("schema migrates"),
+        ))
+    }
+
+    fn example(schema: Value) -> Value {
+        let schema = sv(schema);
+        example_instance(&schema, &schema)
+    }
+
+    #[test]
+    fn objects_get_required_properties() {
+        let instance = example(json!({
+            "type": "object",
+            "required": ["name", "on", "jobs"],
+            "properties": {
+                "name": { "type": "string", "default": "CI" },
+                "on": { "enum": ["push", "pull_request"] },
+                "jobs": {
+                    "type": "object",
+                    "required": ["build"],
+                    "properties": { "build": { "$ref": "#/$defs/job" } }
+                },
+                "env": { "type": "object" }
+            },
+            "$defs": {
+                "job": {
+                    "type": "object",
+                    "required": ["runs-on", "steps"],
+                    "properties": {
+                        "runs-on": { "type": "string", "examples": ["ubuntu-latest"] },
+                        "steps": { "type": "array", "minItems": 1, "items": { "type": "object" } },
+                        "timeout": { "type": "integer" }
+                    }
+                }
+            }
+        }));
+        assert_eq!(
+            instance,
+            json!({
+                "name": "CI",
+                "on": "push",
+                "jobs": { "build": { "runs-on": "ubuntu-latest", "steps": [{}] } }
+            })
+        );
+    }
+
+    #[test]
+    fn placeholders_follow_constraints() {
+        assert_eq!(
+            example(json!({ "type": "string", "format": "email" })),
+            "user@example.com"
+        );
+        assert_eq!(example(json!({ "type": "string", "maxLength": 3 })), "str");
+        assert_eq!(
+            example(json!({ "type": "string", "minLength": 8 })),
+            "stringxx"
+        );
+        assert_eq!(
+            example(json!({ "type": "string", "pattern": "^(v1|v2)$" })),
+            "v1"
+        );
+        assert_eq!(
+            example(json!({ "type": "string", "pattern": "^v[0-9]+$" })),
+            "string"
+        );
+        assert_eq!(
+            example(json!({ "type": "integer", "minimum": 3, "multipleOf": 2 })),
+            4
+        );
+        assert_eq!(
+            example(json!({ "type": "integer", "exclusiveMaximum": 0 })),
+            -1
+        );
+        assert_eq!(example(json!({ "type": "number", "minimum": 0.5 })), 0.5);
+        assert_eq!(example(json!({ "type": ["null", "boolean"] })), false);
+        assert_eq!(
+            example(json!({ "oneOf": [{ "type": "array", "minItems": 2 }, { "type": "string" }] })),
+            json!([null, null])
+        );
+    }
+
+    #[test]
+    fn recursive_refs_end() {
+        let instance = example(json!({
+            "type": "object",
+            "required": ["root"],
+            "properties": { "root": { "$ref": "#/$defs/node" } },
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "required": ["child"],
+                    "properties": { "child": { "$ref": "#/$defs/node" } }
+                }
+            }
+        }));
+        assert_eq!(instance, json!({ "root": { "child": null } }));
+    }
+
+    #[test]
+    fn commented_documents() {
+        let schema = sv(json!({
+            "description": "Deployment settings",
+            "type": "object",
+            "required": ["name", "server"],
+            "properties": {
+                "name": { "type": "string", "description": "Service name" },
+                "server": {
+                    "description": "Where to listen\n\nMore detail",
+                    "type": "object",
+                    "required": ["port", "tls"],
+                    "properties": {
+                        "port": { "type": "integer", "default": 8080, "description": "TCP port" },
+                        "tls": { "type": "null" }
+                    }
+                }
+            }
+        }));
+        let document = |format| commented_example_document(&schema, &schema, format);
+        assert_eq!(
+            document(ExampleFormat::Yaml),
+            "# Deployment settings\n\n# Service name\nname: string\n# Where to listen\nserver:\n  # TCP port\n  port: 8080\n  tls: null\n"
+        );
+        assert_eq!(
+            document(ExampleFormat::Toml),
+            "# Deployment settings\n\n# Service name\nname = \"string\"\n\n# Where to listen\n[server]\n# TCP port\nport = 8080\n# tls =\n"
+        );
+        assert_eq!(
+            document(ExampleFormat::Json),
+            example_document(&schema, &schema, ExampleFormat::Json)
+        );
+    }
+
+    #[test]
+    fn documents_in_each_format() {
+        let schema = sv(json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": { "name": { "type": "string" } }
+        }));
+        let document = |format| example_document(&schema, &schema, format);
+        assert_eq!(
+            document(ExampleFormat::Json),
+            "{\n  \"name\": \"string\"\n}\n"
+        );
+        assert_eq!(document(ExampleFormat::Yaml), "name: string\n");
+        assert_eq!(document(ExampleFormat::Toml), "name = \"string\"\n");
+    }
+}
@@ -1,8 +1,15 @@
+use core::cell::{Cell, RefCell};
+
 #[cfg(test)]
 pub(crate) use ansi_term_styles::BLUE;
 pub(crate) use ansi_term_styles::{BOLD, CYAN, DIM, GREEN, MAGENTA, RED, RESET, YELLOW};
+use jsonschema_schema::SchemaValue;
+
+use crate::ExampleFormat;
+use crate::render::DEFAULT_MAX_DEPTH;
 
 /// Formatting context passed through the rendering functions.
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Fmt<'a> {
     pub bold: &'a str,
     pub dim: &'a str,
@@ -15,6 +22,22 @@ pub(crate) struct Fmt<'a> {
     pub syntax_highlight: bool,
     pub width: usize,
     pub extended: bool,
+    /// Nesting limit for expanded properties; `None` is unlimited.
+    pub max_depth: Option<usize>,
+    pub example_format: ExampleFormat,
+    /// Custom annotation keywords to show with each schema.
+    pub annotations: Vec<String>,
+    /// List only required properties.
+    pub required_only: bool,
+    /// Glob property names must match to be listed.
+    pub property_filter: Option<String>,
+    /// Leave out deprecated properties.
+    pub exclude_deprecated: bool,
+    /// How many properties matching `property_filter` are being rendered;
+    /// below one, names are not filtered.
+    pub filter_matched: Cell<usize>,
+    /// `$ref`s currently being expanded, outermost first.
+    ref_stack: RefCell<Vec<String>>,
 }
 
 impl Fmt<'_> {
@@ -27,6 +50,12 @@ impl Fmt<'_> {
         };
         f.syntax_highlight = opts.syntax_highlight;
         f.extended = opts.extended;
+        f.max_depth = opts.max_depth;
+        f.example_format = opts.example_format;
+        f.annotations.clone_from(&opts.annotations);
+        f.required_only = opts.required_only;
+        f.property_filter.clone_from(&opts.property_filter);
+        f.exclude_deprecated = opts.exclude_deprecated;
         f
     }
 
@@ -43,6 +72,14 @@ impl Fmt<'_> {
             syntax_highlight: true,
             width,
             extended: false,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            example_format: ExampleFormat::Json,
+            annotations: Vec::new(),
+            required_only: false,
+            property_filter: None,
+            exclude_deprecated: false,
+            filter_matched: Cell::new(0),
+            ref_stack: RefCell::new(Vec::new()),
         }
     }
 
@@ -59,7 +96,39 @@ impl Fmt<'_> {
             syntax_highlight: false,
             width,
             extended: false,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            example_format: ExampleFormat::Json,
+            annotations: Vec::new(),
+            required_only: false,
+            property_filter: None,
+            exclude_deprecated: false,
+            filter_matched: Cell::new(0),
+            ref_stack: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Whether properties at `depth` may expand their nested properties.
+    pub(crate) fn can_nest(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max| depth < max)
+    }
+
+    /// Run `render` with the `$ref` of `sv` (if any) marked as being expanded.
+    ///
+    /// Returns `false` without calling `render` when that `$ref` is already
+    /// being expanded further up, so recursive schemas terminate even
+    /// without a depth limit.
+    pub(crate) fn expand_ref(&self, sv: &SchemaValue, render: impl FnOnce()) -> bool {
+        let Some(ref_str) = sv.as_schema().and_then(|s| s.ref_.as_deref()) else {
+            render();
+            return true;
+        };
+        if self.ref_stack.borrow().iter().any(|r| r == ref_str) {
+            return false;
         }
+        self.ref_stack.borrow_mut().push(ref_str.to_string());
+        render();
+        self.ref_stack.borrow_mut().pop();
+        true
     }
 
     /// Whether color output is enabled.
@@ -0,0 +1,487 @@
+//! HTML output for hosting schema documentation.
+//!
+//! Produces an embeddable `<article>` fragment: every property gets a stable
+//! `id` anchor, `$ref`s link to their definition, and object-valued
+//! properties are wrapped in `<details>` so they can be collapsed.
+
+use alloc::collections::BTreeMap;
+use core::fmt::Write;
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+use jsonschema_schema::{Schema, SchemaValue, ref_name};
+
+use crate::fmt::{Fmt, format_value};
+use crate::render::constraint_parts;
+use crate::schema::{get_description, is_local_ref, required_set, resolve_ref, schema_type_str};
+
+/// Render a JSON Schema as an HTML fragment.
+///
+/// `name` is used as the heading when the schema has no `title`. Property
+/// anchors are `prop-<dotted.path>`, definition anchors are `def-<name>` and
+/// the anchors of a definition's properties are
+/// `def-<name>-prop-<dotted.path>`, so links into the page stay stable
+/// across regenerations. An anchor that would repeat an earlier one gets a
+/// `-2`, `-3`, … suffix.
+/// `max_depth` limits how many levels of nested properties are expanded, or
+/// `None` for no limit, as in
+/// [`ExplainOptions::max_depth`](crate::ExplainOptions::max_depth).
+pub fn explain_html(schema: &SchemaValue, name: &str, max_depth: Option<usize>) -> String {
+    render_document(schema, schema, name, max_depth)
+}
+
+/// Render the sub-schema at a JSON Pointer path as an HTML fragment.
+///
+/// # Errors
+///
+/// Returns an error if the pointer cannot be resolved within the schema.
+pub fn explain_html_at_path(
+    schema: &SchemaValue,
+    pointer: &str,
+    name: &str,
+    max_depth: Option<usize>,
+) -> Result<String, String> {
+    let sub = crate::schema::navigate_pointer(schema, schema, pointer)?;
+    Ok(render_document(sub, schema, name, max_depth))
+}
+
+fn render_document(
+    sv: &SchemaValue,
+    root: &SchemaValue,
+    name: &str,
+    max_depth: Option<usize>,
+) -> String {
+    let mut out = String::from("<article class=\"jsonschema-explain\">\n");
+    let Some(s) = sv.as_schema() else {
+        let _ = writeln!(out, "<h1>{}</h1>\n</article>", escape(name));
+        return out;
+    };
+    let s = s.flatten(root);
+    let mut anchors = Anchors::default();
+    for def_name in s.defs.iter().flat_map(BTreeMap::keys) {
+        let anchor = anchors.unique("def-", def_name);
+        anchors.defs.insert(def_name.clone(), anchor);
+    }
+
+    let heading = s.title.as_deref().unwrap_or(name);
+    let _ = writeln!(out, "<h1>{}</h1>", escape(heading));
+    if let Some(desc) = get_description(&s) {
+        write_description(&mut out, desc);
+    }
+    if let Some(id) = s.id.as_deref() {
+        let text = escape(id);
+        if is_web_url(id) {
+            let _ = writeln!(
+                out,
+                "<p class=\"schema-url\">Schema: <a href=\"{text}\">{text}</a></p>"
+            );
+        } else {
+            let _ = writeln!(out, "<p class=\"schema-url\">Schema: {text}</p>");
+        }
+    }
+    if let Some(ty) = schema_type_str(&s) {
+        let _ = writeln!(
+            out,
+            "<p class=\"type\">Type: <code>{}</code></p>",
+            escape(&ty)
+        );
+    }
+
+    if !s.properties.is_empty() {
+        out.push_str("<section class=\"properties\">\n<h2>Properties</h2>\n");
+        render_properties(
+            &mut out,
+            &mut anchors,
+            &s.properties,
+            &required_set(&s),
+            root,
+            "prop-",
+            "",
+            1,
+            max_depth,
+        );
+        out.push_str("</section>\n");
+    }
+
+    render_definitions(&mut out, &mut anchors, &s, root, max_depth);
+    out.push_str("</article>\n");
+    out
+}
+
+/// Unique `id`s for the anchors of one page.
+#[derive(Default)]
+struct Anchors {
+    used: HashSet<String>,
+    /// The anchor of each definition, for `$ref` links to it.
+    defs: HashMap<String, String>,
+}
+
+impl Anchors {
+    /// The anchor `<section><text>`, with a counter appended if the page
+    /// already has it.
+    fn unique(&mut self, section: &str, text: &str) -> String {
+        let base = format!("{section}{}", anchor_id(text));
+        let mut anchor = base.clone();
+        let mut count = 1;
+        while !self.used.insert(anchor.clone()) {
+            count += 1;
+            anchor = format!("{base}-{count}");
+        }
+        anchor
+    }
+
+    /// The anchor of definition `name`.
+    fn def(&self, name: &str) -> String {
+        self.defs
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| format!("def-{}", anchor_id(name)))
+    }
+}
+
+/// Render `props`, anchored at `<section><dotted.path>`.
+#[allow(clippy::too_many_arguments)]
+fn render_properties(
+    out: &mut String,
+    anchors: &mut Anchors,
+    props: &IndexMap<String, SchemaValue>,
+    required: &[String],
+    root: &SchemaValue,
+    section: &str,
+    prefix: &str,
+    depth: usize,
+    max_depth: Option<usize>,
+) {
+    out.push_str("<ul>\n");
+    for (prop_name, prop_sv) in props {
+        let path = if prefix.is_empty() {
+            prop_name.clone()
+        } else {
+            format!("{prefix}.{prop_name}")
+        };
+        let anchor = anchors.unique(section, &path);
+        let resolved = resolve_ref(prop_sv, root).as_schema();
+        let nested = resolved.filter(|p| {
+            max_depth.is_none_or(|max| depth < max)
+                && !p.properties.is_empty()
+                && !is_local_ref(prop_sv)
+        });
+
+        let mut header = format!(
+            "<a class=\"anchor\" href=\"#{anchor}\">#</a> <code class=\"name\">{}</code>",
+            escape(prop_name)
+        );
+        let ty = type_html(anchors, prop_sv, resolved);
+        if !ty.is_empty() {
+            let _ = write!(header, " <span class=\"type\">{ty}</span>");
+        }
+        if required.contains(prop_name) {
+            header.push_str(" <span class=\"required\">required</span>");
+        }
+        if resolved.is_some_and(Schema::is_deprecated) {
+            header.push_str(" <span class=\"deprecated\">deprecated</span>");
+        }
+
+        let _ = writeln!(out, "<li id=\"{anchor}\">");
+        if let Some(prop) = nested {
+            let _ = writeln!(out, "<details open>\n<summary>{header}</summary>");
+            write_details(out, prop);
+            render_properties(
+                out,
+                anchors,
+                &prop.properties,
+                &required_set(prop),
+                root,
+                section,
+                &path,
+                depth + 1,
+                max_depth,
+            );
+            out.push_str("</details>\n");
+        } else {
+            let _ = writeln!(out, "{header}");
+            if let Some(prop) = resolved {
+                write_details(out, prop);
+            }
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_definitions(
+    out: &mut String,
+    anchors: &mut Anchors,
+    schema: &Schema,
+    root: &SchemaValue,
+    max_depth: Option<usize>,
+) {
+    let Some(defs) = schema.defs.as_ref().filter(|d| !d.is_empty()) else {
+        return;
+    };
+    out.push_str("<section class=\"definitions\">\n<h2>Definitions</h2>\n");
+    for (def_name, def_sv) in defs {
+        let anchor = anchors.def(def_name);
+        let _ = writeln!(
+            out,
+            "<div class=\"definition\" id=\"{anchor}\">\n<h3>{}</h3>",
+            escape(def_name)
+        );
+        if let Some(def) = def_sv.as_schema() {
+            if let Some(ty) = schema_type_str(def) {
+                let _ = writeln!(
+                    out,
+                    "<p class=\"type\">Type: <code>{}</code></p>",
+                    escape(&ty)
+                );
+            }
+            write_details(out, def);
+            if !def.properties.is_empty() {
+                render_properties(
+                    out,
+                    anchors,
+                    &def.properties,
+                    &required_set(def),
+                    root,
+                    &format!("{anchor}-prop-"),
+                    "",
+                    2,
+                    max_depth,
+                );
+            }
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</section>\n");
+}
+
+/// Description, allowed values, default and constraints for a schema node.
+fn write_details(out: &mut String, schema: &Schema) {
+    if let Some(desc) = get_description(schema) {
+        write_description(out, desc);
+    }
+    if let Some(values) = schema.enum_.as_ref().filter(|v| !v.is_empty()) {
+        let joined = values
+            .iter()
+            .map(|v| format!("<code>{}</code>", escape(&format_value(v))))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "<p class=\"enum\">Allowed: {joined}</p>");
+    }
+    if let Some(default) = schema.default.as_ref() {
+        let _ = writeln!(
+            out,
+            "<p class=\"default\">Default: <code>{}</code></p>",
+            escape(&format_value(default))
+        );
+    }
+    let constraints = constraint_parts(schema, &Fmt::plain(0));
+    if !constraints.is_empty() {
+        let joined = constraints
+            .iter()
+            .map(|c| format!("<code>{}</code>", escape(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "<p class=\"constraints\">Constraints: {joined}</p>");
+    }
+}
+
+/// Write a description as one `<p>` per blank-line-separated paragraph.
+fn write_description(out: &mut String, desc: &str) {
+    for para in desc.trim().split("\n\n") {
+        let para = para.trim();
+        if !para.is_empty() {
+            let _ = writeln!(out, "<p class=\"description\">{}</p>", escape(para));
+        }
+    }
+}
+
+/// Type label: a link for local `$ref`s, otherwise the escaped type name.
+fn type_html(anchors: &Anchors, original: &SchemaValue, resolved: Option<&Schema>) -> String {
+    if let Some(r) = original.as_schema().and_then(|s| s.ref_.as_deref())
+        && r.starts_with("#/")
+    {
+        let name = ref_name(r);
+        return format!(
+            "<a href=\"#{}\"><code>{}</code></a>",
+            anchors.def(name),
+            escape(name)
+        );
+    }
+    resolved
+        .and_then(schema_type_str)
+        .map(|ty| format!("<code>{}</code>", escape(&ty)))
+        .unwrap_or_default()
+}
+
+/// Whether `url` is safe to link: an `http` or `https` URL. Other schemes,
+/// such as `javascript:`, are shown as text.
+fn is_web_url(url: &str) -> bool {
+    let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+    scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+}
+
+/// Escape text for HTML element content and double-quoted attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Turn a property path or definition name into a safe `id` value.
+fn anchor_id(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_MAX_DEPTH;
+    use serde_json::json;
+
+    fn sv(val: serde_json::Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(jsonschema_migrate::migrate(val).unwrap()))
+    }
+
+    #[test]
+    fn renders_article_with_heading_and_properties() {
+        let schema = sv(json!({
+            "title": "Server",
+            "description": "First paragraph.\n\nSecond <paragraph>.",
+            "type": "object",
+            "properties": {
+                "port": { "type": "integer", "default": 8080 }
+            },
+            "required": ["port"]
+        }));
+        let html = explain_html(&schema, "server.json", Some(DEFAULT_MAX_DEPTH));
+        assert!(html.starts_with("<article class=\"jsonschema-explain\">\n<h1>Server</h1>\n"));
+        assert!(html.contains("<p class=\"description\">First paragraph.</p>"));
+        assert!(html.contains("<p class=\"description\">Second &lt;paragraph&gt;.</p>"));
+        assert!(html.contains("<li id=\"prop-port\">"));
+        assert!(html.contains("<a class=\"anchor\" href=\"#prop-port\">#</a>"));
+        assert!(html.contains("<span class=\"required\">required</span>"));
+        assert!(html.contains("Default: <code>8080</code>"));
+        assert!(html.trim_end().ends_with("</article>"));
+    }
+
+    fn deep() -> SchemaValue {
+        sv(json!({
+            "properties": { "a": { "properties": { "b": { "properties": {
+                "c": { "properties": { "d": { "properties": { "e": { "type": "string" } } } } }
+            } } } } }
+        }))
+    }
+
+    #[test]
+    fn nesting_follows_max_depth() {
+        let html = explain_html(&deep(), "doc", Some(1));
+        assert!(html.contains("id=\"prop-a\""));
+        assert!(!html.contains("id=\"prop-a.b\""));
+        let html = explain_html(&deep(), "doc", None);
+        assert!(html.contains("id=\"prop-a.b.c.d.e\""));
+    }
+
+    #[test]
+    fn nested_objects_are_collapsible() {
+        let schema = sv(json!({
+            "properties": {
+                "server": {
+                    "type": "object",
+                    "properties": { "host": { "type": "string" } }
+                }
+            }
+        }));
+        let html = explain_html(&schema, "config", Some(DEFAULT_MAX_DEPTH));
+        assert!(html.contains("<li id=\"prop-server\">\n<details open>\n<summary>"));
+        assert!(html.contains("<li id=\"prop-server.host\">"));
+    }
+
+    #[test]
+    fn refs_link_to_definitions() {
+        let schema = sv(json!({
+            "properties": { "owner": { "$ref": "#/$defs/Person" } },
+            "$defs": {
+                "Person": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } }
+                }
+            }
+        }));
+        let html = explain_html(&schema, "doc", Some(DEFAULT_MAX_DEPTH));
+        assert!(html.contains("<a href=\"#def-Person\"><code>Person</code></a>"));
+        assert!(html.contains("<div class=\"definition\" id=\"def-Person\">"));
+        assert!(html.contains("<li id=\"def-Person-prop-name\">"));
+    }
+
+    #[test]
+    fn anchors_are_unique() {
+        let schema = sv(json!({
+            "properties": {
+                "a b": { "type": "string" },
+                "a-b": { "type": "string" },
+                "Person.name": { "type": "string" }
+            },
+            "$defs": {
+                "Person": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } }
+                },
+                "Person-prop-name": { "type": "string" }
+            }
+        }));
+        let html = explain_html(&schema, "doc", Some(DEFAULT_MAX_DEPTH));
+        assert!(html.contains("<li id=\"prop-a-b\">"));
+        assert!(html.contains("<li id=\"prop-a-b-2\">"));
+        assert!(html.contains("<li id=\"prop-Person.name\">"));
+        assert!(html.contains("id=\"def-Person-prop-name\">"));
+        assert!(html.contains("<li id=\"def-Person-prop-name-2\">"));
+    }
+
+    #[test]
+    fn only_web_ids_are_linked() {
+        let html = explain_html(
+            &sv(json!({ "$id": "https://example.com/s.json" })),
+            "s",
+            Some(DEFAULT_MAX_DEPTH),
+        );
+        assert!(html.contains(
+            "Schema: <a href=\"https://example.com/s.json\">https://example.com/s.json</a>"
+        ));
+        let html = explain_html(
+            &sv(json!({ "$id": "javascript:alert(1)" })),
+            "s",
+            Some(DEFAULT_MAX_DEPTH),
+        );
+        assert!(html.contains("<p class=\"schema-url\">Schema: javascript:alert(1)</p>"));
+        assert!(!html.contains("href=\"javascript"));
+    }
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            escape("<a href=\"x\">&'"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+        assert_eq!(anchor_id("a b/c"), "a-b-c");
+    }
+}
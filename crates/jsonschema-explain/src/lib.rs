@@ -1,9 +1,16 @@
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
+mod completion;
+mod example;
 mod fmt;
+mod html;
 mod man;
+mod markdown;
 mod render;
 mod schema;
+mod search;
 mod sections;
 
 use core::fmt::Write;
@@ -13,15 +20,22 @@ use jsonschema_schema::{Schema, SchemaValue};
 use fmt::{Fmt, format_header, format_type};
 use man::{write_description, write_section};
 use render::{
-    render_additional_properties, render_pattern_properties, render_properties, render_subschema,
+    constraint_parts, render_additional_properties, render_annotations, render_pattern_properties,
+    render_properties, render_subschema,
 };
 use schema::{get_description, required_set, schema_type_str};
 use sections::{
-    render_definitions_section, render_examples_section, render_schema_section,
-    render_variants_section,
+    render_conditional_sections, render_definitions_section, render_examples_section,
+    render_schema_section, render_variants_section,
 };
 
+pub use completion::{Completion, CompletionKind, completions};
+pub use example::{commented_example_document, example_document, example_instance};
+pub use html::{explain_html, explain_html_at_path};
+pub use markdown::{explain_markdown, explain_markdown_at_path};
+pub use render::DEFAULT_MAX_DEPTH;
 pub use schema::{navigate_pointer, resolve_ref as resolve_schema_ref};
+pub use search::{SearchHit, search};
 
 /// A validation error to display in the VALIDATION ERRORS section.
 pub struct ExplainError {
@@ -31,7 +45,52 @@ pub struct ExplainError {
     pub message: String,
 }
 
+/// Data format used for object and array snippets in the EXAMPLES section.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExampleFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// YAML, for `.yaml`/`.yml` data files.
+    Yaml,
+    /// TOML, for `.toml` data files. Values TOML cannot represent fall back
+    /// to JSON.
+    Toml,
+}
+
+impl ExampleFormat {
+    /// The format of a data file from its extension, so examples can be
+    /// pasted straight into the file being explained.
+    pub fn for_path(path: &str) -> Self {
+        let ext = std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "yaml" | "yml" => Self::Yaml,
+            "toml" => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl core::str::FromStr for ExampleFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            _ => Err(format!(
+                "unknown example format '{s}', expected: json, yaml, toml"
+            )),
+        }
+    }
+}
+
 /// Display options for rendering schema documentation.
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExplainOptions {
     /// Use ANSI color codes in output.
     pub color: bool,
@@ -43,6 +102,23 @@ pub struct ExplainOptions {
     pub validation_errors: Vec<ExplainError>,
     /// Show extended details like `$comment` annotations.
     pub extended: bool,
+    /// How many levels of nested properties to expand, or `None` for no
+    /// limit. Recursive `$ref`s are expanded once per path either way.
+    /// [`DEFAULT_MAX_DEPTH`] is the usual default.
+    pub max_depth: Option<usize>,
+    /// Format for example snippets, usually matching the data file.
+    pub example_format: ExampleFormat,
+    /// Custom annotation keywords, like `x-kubernetes-int-or-string`, whose
+    /// values are shown with each schema.
+    pub annotations: Vec<String>,
+    /// List only required properties.
+    pub required_only: bool,
+    /// List only properties whose name matches this glob, and the
+    /// properties leading to them. A matching property is listed with all
+    /// of its nested properties.
+    pub property_filter: Option<String>,
+    /// Leave out deprecated properties.
+    pub exclude_deprecated: bool,
 }
 
 /// Render a JSON Schema as human-readable terminal documentation.
@@ -130,6 +206,23 @@ fn explain_schema(s: &Schema, root: &SchemaValue, name: &str, opts: &ExplainOpti
         out.push('\n');
     }
 
+    let constraints = constraint_parts(&s, &f);
+    if !constraints.is_empty() {
+        write_section(&mut out, "CONSTRAINTS", &f);
+        for part in &constraints {
+            let _ = writeln!(out, "    {part}");
+        }
+        out.push('\n');
+    }
+
+    let mut annotations = String::new();
+    render_annotations(&mut annotations, &s, &f, "    ");
+    if !annotations.is_empty() {
+        write_section(&mut out, "ANNOTATIONS", &f);
+        out.push_str(&annotations);
+        out.push('\n');
+    }
+
     let required = required_set(&s);
     if !s.properties.is_empty() {
         write_section(&mut out, "PROPERTIES", &f);
@@ -140,24 +233,7 @@ fn explain_schema(s: &Schema, root: &SchemaValue, name: &str, opts: &ExplainOpti
     render_pattern_properties(&mut out, &s, root, &f, 0, "    ");
     render_additional_properties(&mut out, &s, root, &f, 0, "    ");
 
-    // Root-level if/then/else
-    if s.if_.is_some() {
-        use crate::schema::variant_summary;
-        write_section(&mut out, "CONDITIONAL", &f);
-        if let Some(ref if_sv) = s.if_ {
-            let summary = variant_summary(if_sv, root, &f);
-            let _ = writeln!(out, "    If: {summary}");
-        }
-        if let Some(ref then_sv) = s.then_ {
-            let summary = variant_summary(then_sv, root, &f);
-            let _ = writeln!(out, "    Then: {summary}");
-        }
-        if let Some(ref else_sv) = s.else_ {
-            let summary = variant_summary(else_sv, root, &f);
-            let _ = writeln!(out, "    Else: {summary}");
-        }
-        out.push('\n');
-    }
+    render_conditional_sections(&mut out, &s, &render_root, &f);
 
     if type_str.as_deref() == Some("array")
         && let Some(ref items) = s.items
@@ -214,6 +290,12 @@ mod tests {
             width: 80,
             validation_errors: vec![],
             extended: false,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            example_format: ExampleFormat::Json,
+            annotations: Vec::new(),
+            required_only: false,
+            property_filter: None,
+            exclude_deprecated: false,
         }
     }
 
@@ -224,6 +306,12 @@ mod tests {
             width: 80,
             validation_errors: vec![],
             extended: false,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            example_format: ExampleFormat::Json,
+            annotations: Vec::new(),
+            required_only: false,
+            property_filter: None,
+            exclude_deprecated: false,
         }
     }
 
@@ -281,6 +369,154 @@ mod tests {
         assert!(output.contains("Enable debug mode"));
     }
 
+    fn deep_chain() -> SchemaValue {
+        sv(json!({
+            "type": "object",
+            "properties": { "a": { "type": "object", "properties": {
+                "b": { "type": "object", "properties": {
+                    "c": { "type": "object", "properties": {
+                        "d": { "type": "object", "properties": {
+                            "e": { "type": "string" }
+                        } }
+                    } }
+                } }
+            } } }
+        }))
+    }
+
+    #[test]
+    fn declared_annotations_are_shown() {
+        let schema = sv(json!({
+            "type": "object",
+            "x-order": 3,
+            "properties": {
+                "port": {
+                    "description": "The port",
+                    "x-kubernetes-int-or-string": true,
+                    "x-internal": "hidden"
+                }
+            }
+        }));
+        let output = explain(&schema, "svc", &plain());
+        assert!(!output.contains("x-kubernetes-int-or-string"));
+
+        let opts = ExplainOptions {
+            annotations: vec!["x-order".into(), "x-kubernetes-int-or-string".into()],
+            ..plain()
+        };
+        let output = explain(&schema, "svc", &opts);
+        assert!(output.contains("ANNOTATIONS\n    x-order: 3\n"));
+        assert!(output.contains("        x-kubernetes-int-or-string: true\n"));
+        assert!(!output.contains("x-internal"));
+    }
+
+    #[test]
+    fn default_depth_stops_nesting() {
+        let output = explain(&deep_chain(), "deep", &plain());
+        assert!(output.contains("c (object)"));
+        assert!(!output.contains("d (object)"));
+    }
+
+    #[test]
+    fn configurable_depth() {
+        let shallow = ExplainOptions {
+            max_depth: Some(1),
+            ..plain()
+        };
+        let output = explain(&deep_chain(), "deep", &shallow);
+        assert!(output.contains("a (object)"));
+        assert!(!output.contains("b (object)"));
+
+        let unlimited = ExplainOptions {
+            max_depth: None,
+            ..plain()
+        };
+        let output = explain(&deep_chain(), "deep", &unlimited);
+        assert!(output.contains("e (string)"));
+    }
+
+    #[test]
+    fn unlimited_depth_stops_at_recursive_refs() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": { "root": { "$ref": "#/$defs/Node" } },
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "description": "A tree node",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "child": { "$ref": "#/$defs/Node" }
+                    }
+                }
+            }
+        }));
+        let unlimited = ExplainOptions {
+            max_depth: None,
+            ..plain()
+        };
+        let output = explain(&schema, "tree", &unlimited);
+        assert!(output.contains("child (object)"));
+        assert!(output.contains("Recursive: see Node above"));
+    }
+
+    #[test]
+    fn property_filters() {
+        let schema = sv(json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string" },
+                "legacy": { "type": "string", "deprecated": true },
+                "options": {
+                    "type": "object",
+                    "properties": {
+                        "strict": { "type": "boolean" },
+                        "target": { "type": "string" }
+                    }
+                },
+                "server": {
+                    "type": "object",
+                    "properties": { "port": { "type": "integer" } }
+                }
+            }
+        }));
+        let with = |opts: ExplainOptions| explain(&schema, "cfg", &opts);
+
+        let output = with(ExplainOptions {
+            required_only: true,
+            ..plain()
+        });
+        assert!(output.contains("    name (string, *required)"));
+        assert!(!output.contains("legacy"));
+        assert!(!output.contains("options"));
+
+        let output = with(ExplainOptions {
+            exclude_deprecated: true,
+            ..plain()
+        });
+        assert!(!output.contains("legacy"));
+        assert!(output.contains("    options (object)"));
+
+        let output = with(ExplainOptions {
+            property_filter: Some("str*".into()),
+            ..plain()
+        });
+        assert!(output.contains("    options (object)"));
+        assert!(output.contains("        strict (boolean)"));
+        assert!(!output.contains("target"));
+        assert!(!output.contains("name ("));
+        assert!(!output.contains("server"));
+
+        let output = with(ExplainOptions {
+            property_filter: Some("options".into()),
+            ..plain()
+        });
+        assert!(output.contains("        strict (boolean)"));
+        assert!(output.contains("        target (string)"));
+        assert!(!output.contains("port"));
+    }
+
     #[test]
     fn enum_values_listed() {
         let schema = sv(json!({
@@ -749,6 +985,35 @@ mod tests {
         assert!(output.contains("multipleOf=0.5"));
     }
 
+    #[test]
+    fn item_constraints_shown() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": {
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": "^[a-z]+$", "maxLength": 20 }
+                }
+            }
+        }));
+
+        let output = explain(&schema, "items", &plain());
+        assert!(output.contains("Item constraints: maxLength=20, pattern=^[a-z]+$"));
+    }
+
+    #[test]
+    fn root_constraints_section() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1, "format": "hostname" }
+            }
+        }));
+
+        let output = explain_at_path(&schema, "/properties/name", "name", &plain()).unwrap();
+        assert!(output.contains("CONSTRAINTS\n    format=hostname\n    minLength=1\n"));
+    }
+
     #[test]
     fn no_constraints_line_when_none() {
         let schema = sv(json!({
@@ -942,6 +1207,68 @@ mod tests {
         assert!(output.contains("Else:"));
     }
 
+    #[test]
+    fn conditional_branches_render_properties() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": { "kind": { "enum": ["service", "job"] } },
+            "if": {
+                "properties": { "kind": { "const": "service" } },
+                "required": ["kind"]
+            },
+            "then": {
+                "properties": { "port": { "type": "integer", "description": "Port to expose" } },
+                "required": ["port"]
+            },
+            "else": {
+                "properties": { "schedule": { "type": "string" } }
+            }
+        }));
+
+        let output = explain(&schema, "cond-test", &plain());
+        assert!(output.contains("If: kind = \"service\""), "{output}");
+        assert!(output.contains("    Then:\n        Requires: port\n"));
+        assert!(output.contains("port (integer, *required)"));
+        assert!(output.contains("Port to expose"));
+        assert!(output.contains("schedule (string)"));
+    }
+
+    #[test]
+    fn root_not_and_dependent_schemas_sections() {
+        let schema = sv(json!({
+            "type": "object",
+            "not": { "required": ["legacy"] },
+            "dependentSchemas": {
+                "credit_card": {
+                    "properties": { "billing_address": { "type": "string" } },
+                    "required": ["billing_address"]
+                }
+            }
+        }));
+
+        let output = explain(&schema, "dep-test", &plain());
+        assert!(output.contains("NOT\n"));
+        assert!(output.contains("DEPENDENT SCHEMAS\n    When \"credit_card\" is present:\n"));
+        assert!(output.contains("billing_address (string, *required)"));
+    }
+
+    #[test]
+    fn property_condition_is_described() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": {
+                "db": {
+                    "type": "object",
+                    "if": { "required": ["url"] },
+                    "then": { "properties": { "pool": { "type": "integer" } } }
+                }
+            }
+        }));
+
+        let output = explain(&schema, "prop-cond", &plain());
+        assert!(output.contains("If: url is present"));
+    }
+
     #[test]
     fn not_shown() {
         let schema = sv(json!({
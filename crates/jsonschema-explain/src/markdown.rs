@@ -0,0 +1,379 @@
+//! GitHub-flavored markdown output for schema reference docs.
+//!
+//! Mirrors the terminal renderer's structure — description, type, properties
+//! and definitions — but emits property tables and nested headings instead
+//! of man-page sections.
+
+use core::fmt::Write;
+
+use indexmap::IndexMap;
+use jsonschema_schema::{Schema, SchemaValue, ref_name};
+
+use crate::fmt::{Fmt, format_value};
+use crate::render::constraint_parts;
+use crate::schema::{get_description, is_local_ref, required_set, resolve_ref, schema_type_str};
+
+/// Render a JSON Schema as GitHub-flavored markdown.
+///
+/// `name` is used as the document heading when the schema has no `title`.
+/// Object-valued properties get their own nested heading with a property
+/// table, and `$ref`s to local definitions link to the matching heading
+/// under `## Definitions`. `max_depth` limits how many levels of nested
+/// properties get a section, or `None` for no limit, as in
+/// [`ExplainOptions::max_depth`](crate::ExplainOptions::max_depth).
+pub fn explain_markdown(schema: &SchemaValue, name: &str, max_depth: Option<usize>) -> String {
+    render_document(schema, schema, name, max_depth)
+}
+
+/// Render the sub-schema at a JSON Pointer path as markdown.
+///
+/// `$ref`s are resolved against the full `schema`, so definition links keep
+/// working for sub-schemas.
+///
+/// # Errors
+///
+/// Returns an error if the pointer cannot be resolved within the schema.
+pub fn explain_markdown_at_path(
+    schema: &SchemaValue,
+    pointer: &str,
+    name: &str,
+    max_depth: Option<usize>,
+) -> Result<String, String> {
+    let sub = crate::schema::navigate_pointer(schema, schema, pointer)?;
+    Ok(render_document(sub, schema, name, max_depth))
+}
+
+fn render_document(
+    sv: &SchemaValue,
+    root: &SchemaValue,
+    name: &str,
+    max_depth: Option<usize>,
+) -> String {
+    let mut out = String::new();
+    let Some(s) = sv.as_schema() else {
+        let _ = writeln!(out, "# {name}");
+        return out;
+    };
+    let s = s.flatten(root);
+
+    let heading = s.title.as_deref().unwrap_or(name);
+    let _ = writeln!(out, "# {heading}\n");
+
+    if let Some(desc) = get_description(&s) {
+        let _ = writeln!(out, "{}\n", desc.trim());
+    }
+
+    if let Some(id) = s.id.as_deref() {
+        let _ = writeln!(out, "**Schema:** <{id}>\n");
+    }
+    if let Some(ty) = schema_type_str(&s) {
+        let _ = writeln!(out, "**Type:** `{ty}`\n");
+    }
+
+    if !s.properties.is_empty() {
+        out.push_str("## Properties\n\n");
+        render_object(&mut out, &s, root, "", 1, max_depth);
+    }
+
+    render_definitions(&mut out, &s, root, max_depth);
+    out
+}
+
+/// Render a property table followed by a nested section for each
+/// object-valued property.
+#[allow(clippy::too_many_arguments)]
+fn render_object(
+    out: &mut String,
+    schema: &Schema,
+    root: &SchemaValue,
+    prefix: &str,
+    depth: usize,
+    max_depth: Option<usize>,
+) {
+    render_property_table(out, &schema.properties, &required_set(schema), root);
+
+    if max_depth.is_some_and(|max| depth >= max) {
+        return;
+    }
+    for (prop_name, prop_sv) in &schema.properties {
+        // Referenced definitions are documented once under `## Definitions`.
+        if is_local_ref(prop_sv) {
+            continue;
+        }
+        let Some(prop) = prop_sv.as_schema() else {
+            continue;
+        };
+        if prop.properties.is_empty() {
+            continue;
+        }
+        let path = if prefix.is_empty() {
+            prop_name.clone()
+        } else {
+            format!("{prefix}.{prop_name}")
+        };
+        let level = "#".repeat((depth + 2).min(6));
+        let _ = writeln!(out, "{level} `{path}`\n");
+        if let Some(desc) = get_description(prop) {
+            let _ = writeln!(out, "{}\n", desc.trim());
+        }
+        render_object(out, prop, root, &path, depth + 1, max_depth);
+    }
+}
+
+fn render_property_table(
+    out: &mut String,
+    props: &IndexMap<String, SchemaValue>,
+    required: &[String],
+    root: &SchemaValue,
+) {
+    out.push_str("| Property | Type | Required | Description |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for (prop_name, prop_sv) in props {
+        let resolved = resolve_ref(prop_sv, root).as_schema();
+        let ty = type_cell(prop_sv, resolved);
+        let req = if required.contains(prop_name) {
+            "yes"
+        } else {
+            ""
+        };
+        let desc = resolved.map(description_cell).unwrap_or_default();
+        let deprecated = if resolved.is_some_and(Schema::is_deprecated) {
+            " *(deprecated)*"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "| `{}`{deprecated} | {ty} | {req} | {desc} |",
+            escape_cell(prop_name)
+        );
+    }
+    out.push('\n');
+}
+
+fn render_definitions(
+    out: &mut String,
+    schema: &Schema,
+    root: &SchemaValue,
+    max_depth: Option<usize>,
+) {
+    let Some(defs) = schema.defs.as_ref().filter(|d| !d.is_empty()) else {
+        return;
+    };
+    out.push_str("## Definitions\n\n");
+    for (def_name, def_sv) in defs {
+        let _ = writeln!(out, "### `{def_name}`\n");
+        let Some(def) = def_sv.as_schema() else {
+            continue;
+        };
+        if let Some(desc) = get_description(def) {
+            let _ = writeln!(out, "{}\n", desc.trim());
+        }
+        if let Some(ty) = schema_type_str(def) {
+            let _ = writeln!(out, "**Type:** `{ty}`\n");
+        }
+        if !def.properties.is_empty() {
+            render_object(out, def, root, def_name, 2, max_depth);
+        }
+    }
+}
+
+/// Type column: a link for local `$ref`s, otherwise the type in backticks.
+fn type_cell(original: &SchemaValue, resolved: Option<&Schema>) -> String {
+    if let Some(r) = original.as_schema().and_then(|s| s.ref_.as_deref())
+        && r.starts_with("#/")
+    {
+        let name = ref_name(r);
+        return format!("[`{}`](#{})", escape_cell(name), anchor(name));
+    }
+    resolved
+        .and_then(schema_type_str)
+        .map(|ty| format!("`{}`", escape_cell(&ty)))
+        .unwrap_or_default()
+}
+
+/// Description column: the description plus allowed values and default.
+fn description_cell(schema: &Schema) -> String {
+    let mut parts = Vec::new();
+    if let Some(desc) = get_description(schema) {
+        parts.push(escape_cell(desc.trim()));
+    }
+    if let Some(values) = schema.enum_.as_ref().filter(|v| !v.is_empty()) {
+        let joined = values
+            .iter()
+            .map(|v| format!("`{}`", escape_cell(&format_value(v))))
+            .collect::<Vec<_>>()
+            .join(", ");
+        parts.push(format!("Allowed: {joined}"));
+    }
+    if let Some(default) = schema.default.as_ref() {
+        parts.push(format!(
+            "Default: `{}`",
+            escape_cell(&format_value(default))
+        ));
+    }
+    let constraints = constraint_parts(schema, &Fmt::plain(0));
+    if !constraints.is_empty() {
+        let joined = constraints
+            .iter()
+            .map(|c| format!("`{}`", escape_cell(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        parts.push(format!("Constraints: {joined}"));
+    }
+    parts.join("<br>")
+}
+
+/// Make text safe for a single table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "\n")
+        .replace("\n\n", "<br>")
+        .replace('\n', " ")
+}
+
+/// GitHub heading anchor for a plain heading text.
+fn anchor(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c.to_ascii_lowercase())
+            } else if c == ' ' {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_MAX_DEPTH;
+    use serde_json::json;
+
+    fn sv(val: serde_json::Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(jsonschema_migrate::migrate(val).unwrap()))
+    }
+
+    #[test]
+    fn renders_heading_description_and_table() {
+        let schema = sv(json!({
+            "title": "Server config",
+            "description": "Settings for the server.",
+            "type": "object",
+            "properties": {
+                "port": { "type": "integer", "description": "Port to bind", "default": 8080 },
+                "mode": { "enum": ["dev", "prod"] }
+            },
+            "required": ["port"]
+        }));
+        let md = explain_markdown(&schema, "server.json", Some(DEFAULT_MAX_DEPTH));
+        assert!(md.starts_with("# Server config\n\nSettings for the server.\n"));
+        assert!(md.contains("**Type:** `object`"));
+        assert!(md.contains("| Property | Type | Required | Description |"));
+        assert!(md.contains("| `port` | `integer` | yes | Port to bind<br>Default: `8080` |"));
+        assert!(md.contains("Allowed: `\"dev\"`, `\"prod\"`"));
+    }
+
+    #[test]
+    fn nested_objects_get_headings() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": {
+                "server": {
+                    "type": "object",
+                    "description": "Server block",
+                    "properties": {
+                        "tls": {
+                            "type": "object",
+                            "properties": { "cert": { "type": "string" } }
+                        }
+                    }
+                }
+            }
+        }));
+        let md = explain_markdown(&schema, "config", Some(DEFAULT_MAX_DEPTH));
+        assert!(md.starts_with("# config\n"));
+        assert!(md.contains("### `server`\n\nServer block\n"));
+        assert!(md.contains("#### `server.tls`\n"));
+        assert!(md.contains("| `cert` | `string` |  |  |"));
+    }
+
+    fn deep() -> SchemaValue {
+        sv(json!({
+            "properties": { "a": { "properties": { "b": { "properties": {
+                "c": { "properties": { "d": { "properties": { "e": { "type": "string" } } } } }
+            } } } } }
+        }))
+    }
+
+    #[test]
+    fn nesting_follows_max_depth() {
+        let md = explain_markdown(&deep(), "doc", Some(1));
+        assert!(!md.contains("### `a`"));
+        let md = explain_markdown(&deep(), "doc", Some(DEFAULT_MAX_DEPTH));
+        assert!(md.contains("#### `a.b`"));
+        assert!(!md.contains("`a.b.c`"));
+        let md = explain_markdown(&deep(), "doc", None);
+        assert!(md.contains("###### `a.b.c.d`"));
+        assert!(md.contains("| `e` | `string` |"));
+    }
+
+    #[test]
+    fn refs_link_to_definitions() {
+        let schema = sv(json!({
+            "type": "object",
+            "properties": { "owner": { "$ref": "#/$defs/Person" } },
+            "$defs": {
+                "Person": {
+                    "type": "object",
+                    "description": "A person",
+                    "properties": { "name": { "type": "string" } }
+                }
+            }
+        }));
+        let md = explain_markdown(&schema, "doc", Some(DEFAULT_MAX_DEPTH));
+        assert!(md.contains("| `owner` | [`Person`](#person) |"));
+        assert!(md.contains("## Definitions\n\n### `Person`\n\nA person\n"));
+        assert!(md.contains("| `name` | `string` |"));
+    }
+
+    #[test]
+    fn constraints_in_description_cell() {
+        let schema = sv(json!({
+            "properties": { "name": { "type": "string", "minLength": 1 } }
+        }));
+        let md = explain_markdown(&schema, "doc", Some(DEFAULT_MAX_DEPTH));
+        assert!(md.contains("| `name` | `string` |  | Constraints: `minLength=1` |"));
+    }
+
+    #[test]
+    fn table_cells_are_escaped() {
+        assert_eq!(escape_cell("a|b\nc\n\nd"), "a\\|b c<br>d");
+    }
+
+    #[test]
+    fn anchors_follow_github_rules() {
+        assert_eq!(anchor("Person"), "person");
+        assert_eq!(anchor("My Type.v2"), "my-typev2");
+    }
+
+    #[test]
+    fn at_path_renders_sub_schema() {
+        let schema = sv(json!({
+            "properties": {
+                "db": {
+                    "title": "Database",
+                    "properties": { "url": { "type": "string" } }
+                }
+            }
+        }));
+        let md = explain_markdown_at_path(&schema, "/properties/db", "db", Some(DEFAULT_MAX_DEPTH))
+            .unwrap();
+        assert!(md.starts_with("# Database\n"));
+        assert!(md.contains("| `url` | `string` |"));
+    }
+}
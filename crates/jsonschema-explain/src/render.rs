@@ -5,10 +5,13 @@ use jsonschema_schema::{Schema, SchemaValue, ref_name};
 
 use crate::fmt::{COMPOSITION_KEYWORDS, Fmt, format_type, format_type_suffix, format_value};
 use crate::man::{write_description, write_label, write_label_wrapped};
-use crate::schema::{get_description, required_set, resolve_ref, schema_type_str, variant_summary};
+use crate::schema::{
+    describe_condition, get_description, required_set, resolve_ref, schema_type_str,
+    variant_summary,
+};
 
-/// Maximum nesting depth for recursive property rendering.
-pub(crate) const MAX_DEPTH: usize = 3;
+/// Default nesting depth for recursive property rendering.
+pub const DEFAULT_MAX_DEPTH: usize = 3;
 
 /// Render a variant block for `oneOf`/`anyOf`/`allOf`.
 ///
@@ -164,6 +167,8 @@ pub(crate) fn render_properties(
         // 0 = required, 1 = normal, 2 = deprecated
         i32::from(deprecated) * 2 + i32::from(!required.contains(name))
     });
+    sorted_props
+        .retain(|(name, sv)| shows_property(name, sv, required.contains(name), root, f, depth));
 
     for (prop_name, prop_sv) in sorted_props {
         let resolved_sv = resolve_ref(prop_sv, root);
@@ -203,11 +208,86 @@ pub(crate) fn render_properties(
             f.green, f.reset
         );
 
-        render_property_details(out, prop_schema, root, f, depth, &desc_indent);
+        // Below a property the filter matches, every property is listed
+        let matched = f
+            .property_filter
+            .as_deref()
+            .is_some_and(|glob| glob_matcher::glob_match(glob, prop_name));
+        f.filter_matched
+            .set(f.filter_matched.get() + usize::from(matched));
+        let expanded = f.expand_ref(prop_sv, || {
+            render_property_details(out, prop_schema, root, f, depth, &desc_indent);
+        });
+        f.filter_matched
+            .set(f.filter_matched.get() - usize::from(matched));
+        if !expanded {
+            render_recursive_ref(out, prop_sv, prop_schema, f, &desc_indent);
+        }
         out.push('\n');
     }
 }
 
+/// Whether the property `name` at `depth` is listed under the property
+/// filters of `f`. A property the name filter does not match is listed when
+/// one of its nested properties, within the depth limit, is.
+#[allow(clippy::too_many_arguments)]
+fn shows_property(
+    name: &str,
+    sv: &SchemaValue,
+    required: bool,
+    root: &SchemaValue,
+    f: &Fmt<'_>,
+    depth: usize,
+) -> bool {
+    let resolved = resolve_ref(sv, root);
+    let schema = resolved.as_schema();
+    if f.required_only && !required {
+        return false;
+    }
+    if f.exclude_deprecated && schema.is_some_and(Schema::is_deprecated) {
+        return false;
+    }
+    let Some(glob) = f.property_filter.as_deref() else {
+        return true;
+    };
+    if f.filter_matched.get() > 0 || glob_matcher::glob_match(glob, name) {
+        return true;
+    }
+    let Some(schema) = schema.filter(|_| f.can_nest(depth)) else {
+        return false;
+    };
+    let nested_required = required_set(schema);
+    let mut shown = false;
+    f.expand_ref(sv, || {
+        shown = schema.properties.iter().any(|(nested, nested_sv)| {
+            let required = nested_required.contains(nested);
+            shows_property(nested, nested_sv, required, root, f, depth + 1)
+        });
+    });
+    shown
+}
+
+/// Render a property whose `$ref` is already being expanded further up: its
+/// description and a pointer back to the definition instead of the details.
+#[allow(clippy::too_many_arguments)]
+fn render_recursive_ref(
+    out: &mut String,
+    original: &SchemaValue,
+    resolved: &Schema,
+    f: &Fmt<'_>,
+    desc_indent: &str,
+) {
+    if let Some(desc) = get_description(resolved) {
+        write_description(out, desc, f, desc_indent);
+    }
+    let name = original
+        .as_schema()
+        .and_then(|s| s.ref_.as_deref())
+        .map(ref_name)
+        .unwrap_or_default();
+    write_label(out, desc_indent, "Recursive", &format!("see {name} above"));
+}
+
 /// Render details for a single property: description, default, enum, const,
 /// variant lists, nested properties, and array item types.
 #[allow(clippy::too_many_arguments)]
@@ -252,10 +332,13 @@ fn render_property_details(
     }
 
     render_constraints(out, prop_schema, f, desc_indent);
+    render_item_constraints(out, prop_schema, root, f, desc_indent);
 
     // Content type/encoding
     render_content_info(out, prop_schema, f, desc_indent);
 
+    render_annotations(out, prop_schema, f, desc_indent);
+
     // Composition keywords (oneOf/anyOf/allOf)
     render_composition(out, prop_schema, root, f, depth, desc_indent);
 
@@ -275,7 +358,7 @@ fn render_property_details(
     render_dependent_schemas(out, prop_schema, root, f, depth, desc_indent);
 
     // Nested properties
-    if depth < MAX_DEPTH && !prop_schema.properties.is_empty() {
+    if f.can_nest(depth) && !prop_schema.properties.is_empty() {
         let nested_required = required_set(prop_schema);
         out.push('\n');
         render_properties(
@@ -316,7 +399,7 @@ fn render_inline_variant(
     let is_ref = original.as_schema().is_some_and(|s| s.ref_.is_some());
     let has_properties = !resolved.properties.is_empty();
 
-    if !is_ref && has_properties && depth < MAX_DEPTH {
+    if !is_ref && has_properties && f.can_nest(depth) {
         let deprecated_tag = deprecated_tag(resolved, f);
         let (label, label_is_type) = if let Some(ref title) = resolved.title {
             (title.clone(), false)
@@ -423,6 +506,49 @@ fn deprecated_tag(schema: &Schema, f: &Fmt<'_>) -> String {
 
 /// Render JSON Schema validation constraints as a compact annotation line.
 fn render_constraints(out: &mut String, schema: &Schema, f: &Fmt<'_>, indent: &str) {
+    let parts = constraint_parts(schema, f);
+    if !parts.is_empty() {
+        let _ = writeln!(
+            out,
+            "{indent}{}Constraints:{} {}",
+            f.dim,
+            f.reset,
+            parts.join(", ")
+        );
+    }
+}
+
+/// Render the constraints of an array's item schema, which would otherwise
+/// be hidden behind the `T[]` type label.
+#[allow(clippy::too_many_arguments)]
+fn render_item_constraints(
+    out: &mut String,
+    schema: &Schema,
+    root: &SchemaValue,
+    f: &Fmt<'_>,
+    indent: &str,
+) {
+    let Some(ref items) = schema.items else {
+        return;
+    };
+    let Some(item_schema) = resolve_ref(items, root).as_schema() else {
+        return;
+    };
+    let parts = constraint_parts(item_schema, f);
+    if !parts.is_empty() {
+        let _ = writeln!(
+            out,
+            "{indent}{}Item constraints:{} {}",
+            f.dim,
+            f.reset,
+            parts.join(", ")
+        );
+    }
+}
+
+/// Validation keywords of a schema as `keyword=value` parts: format, string
+/// length and pattern, numeric bounds, and item/property counts.
+pub(crate) fn constraint_parts(schema: &Schema, f: &Fmt<'_>) -> Vec<String> {
     let mut parts: Vec<String> = Vec::new();
 
     if let Some(ref fmt_val) = schema.format {
@@ -473,16 +599,7 @@ fn render_constraints(out: &mut String, schema: &Schema, f: &Fmt<'_>, indent: &s
     if let Some(v) = schema.max_properties {
         parts.push(format!("maxProperties={}{v}{}", f.magenta, f.reset));
     }
-
-    if !parts.is_empty() {
-        let _ = writeln!(
-            out,
-            "{indent}{}Constraints:{} {}",
-            f.dim,
-            f.reset,
-            parts.join(", ")
-        );
-    }
+    parts
 }
 
 /// Render enum values, optionally with `markdownEnumDescriptions`.
@@ -518,6 +635,15 @@ fn render_enum_with_descriptions(out: &mut String, schema: &Schema, f: &Fmt<'_>,
     }
 }
 
+/// Values of the custom annotation keywords in [`Fmt::annotations`].
+pub(crate) fn render_annotations(out: &mut String, schema: &Schema, f: &Fmt<'_>, indent: &str) {
+    for keyword in &f.annotations {
+        if let Some(value) = schema.extra.get(keyword) {
+            write_label_wrapped(out, indent, keyword, &format_value(value), f);
+        }
+    }
+}
+
 /// Render `contentMediaType` / `contentEncoding`.
 fn render_content_info(out: &mut String, schema: &Schema, f: &Fmt<'_>, indent: &str) {
     let media = schema.content_media_type.as_deref();
@@ -588,23 +714,23 @@ fn render_conditional(
     }
     let _ = writeln!(out, "{indent}{}Conditional:{}", f.dim, f.reset);
     if let Some(ref if_sv) = schema.if_ {
-        let summary = variant_summary(if_sv, root, f);
-        let _ = writeln!(out, "{indent}  If: {summary}");
-        if depth < MAX_DEPTH {
+        let condition = describe_condition(if_sv, root, f);
+        let _ = writeln!(out, "{indent}  If: {condition}");
+        if f.can_nest(depth) {
             render_conditional_subschema(out, if_sv, root, f, depth);
         }
     }
     if let Some(ref then_sv) = schema.then_ {
         let summary = variant_summary(then_sv, root, f);
         let _ = writeln!(out, "{indent}  Then: {summary}");
-        if depth < MAX_DEPTH {
+        if f.can_nest(depth) {
             render_conditional_subschema(out, then_sv, root, f, depth);
         }
     }
     if let Some(ref else_sv) = schema.else_ {
         let summary = variant_summary(else_sv, root, f);
         let _ = writeln!(out, "{indent}  Else: {summary}");
-        if depth < MAX_DEPTH {
+        if f.can_nest(depth) {
             render_conditional_subschema(out, else_sv, root, f, depth);
         }
     }
@@ -624,7 +750,9 @@ fn render_conditional_subschema(
         && !resolved.properties.is_empty()
     {
         let req = required_set(resolved);
-        render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+        f.expand_ref(sv, || {
+            render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+        });
     }
 }
 
@@ -668,13 +796,15 @@ fn render_dependent_schemas(
     for (key, dep_sv) in &schema.dependent_schemas {
         let summary = variant_summary(dep_sv, root, f);
         let _ = writeln!(out, "{indent}  {}\"{key}\"{}: {summary}", f.green, f.reset);
-        if depth < MAX_DEPTH {
+        if f.can_nest(depth) {
             let resolved_sv = resolve_ref(dep_sv, root);
             if let Some(resolved) = resolved_sv.as_schema()
                 && !resolved.properties.is_empty()
             {
                 let req = required_set(resolved);
-                render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+                f.expand_ref(dep_sv, || {
+                    render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+                });
             }
         }
     }
@@ -715,9 +845,11 @@ pub(crate) fn render_pattern_properties(
                 let nested_indent = format!("{indent}      ");
                 write_description(out, desc, f, &nested_indent);
             }
-            if depth < MAX_DEPTH && !resolved.properties.is_empty() {
+            if f.can_nest(depth) && !resolved.properties.is_empty() {
                 let req = required_set(resolved);
-                render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+                f.expand_ref(sv, || {
+                    render_properties(out, &resolved.properties, &req, root, f, depth + 2);
+                });
             }
         }
     }
@@ -853,7 +985,7 @@ pub(crate) fn render_additional_properties(
                 let nested_indent = format!("{indent}    ");
                 write_description(out, desc, f, &nested_indent);
             }
-            if depth < MAX_DEPTH && !s.properties.is_empty() {
+            if f.can_nest(depth) && !s.properties.is_empty() {
                 let req = required_set(s);
                 render_properties(out, &s.properties, &req, root, f, depth + 2);
             }
@@ -885,10 +1017,14 @@ pub(crate) fn render_subschema(
         write_description(out, desc, f, &indent);
     }
 
-    if depth < MAX_DEPTH {
+    render_constraints(out, resolved, f, &indent);
+
+    if f.can_nest(depth) {
         let required = required_set(resolved);
         if !resolved.properties.is_empty() {
-            render_properties(out, &resolved.properties, &required, root, f, depth + 1);
+            f.expand_ref(schema, || {
+                render_properties(out, &resolved.properties, &required, root, f, depth + 1);
+            });
         }
     }
 }
@@ -1,6 +1,6 @@
 use jsonschema_schema::{Schema, SchemaValue, ref_name};
 
-use crate::fmt::{Fmt, format_type};
+use crate::fmt::{Fmt, format_type, format_value};
 
 /// Resolve a `$ref` within the same schema document.
 ///
@@ -49,6 +49,13 @@ pub(crate) fn schema_type_str(schema: &Schema) -> Option<String> {
     schema.type_str()
 }
 
+/// Whether a schema is a `$ref` into the same document.
+pub(crate) fn is_local_ref(sv: &SchemaValue) -> bool {
+    sv.as_schema()
+        .and_then(|s| s.ref_.as_deref())
+        .is_some_and(|r| r.starts_with("#/"))
+}
+
 /// Get the best description text from a schema, preferring `markdownDescription`.
 pub(crate) fn get_description(schema: &Schema) -> Option<&str> {
     schema.description()
@@ -117,8 +124,55 @@ pub(crate) fn variant_summary(variant: &SchemaValue, root: &SchemaValue, f: &Fmt
     format!("{}(schema){}{dep}", f.dim, f.reset)
 }
 
+/// Describe an `if` schema as a readable condition, e.g.
+/// `kind = "service" and port is present`.
+///
+/// Property `const`/`enum`/`pattern`/type checks and extra `required` entries
+/// are spelled out; anything else falls back to [`variant_summary`].
+pub(crate) fn describe_condition(cond: &SchemaValue, root: &SchemaValue, f: &Fmt<'_>) -> String {
+    let Some(schema) = resolve_ref(cond, root).as_schema() else {
+        return variant_summary(cond, root, f);
+    };
+    let mut parts = Vec::new();
+    for (name, prop_sv) in &schema.properties {
+        let Some(prop) = resolve_ref(prop_sv, root).as_schema() else {
+            continue;
+        };
+        let name = format!("{}{name}{}", f.green, f.reset);
+        if let Some(ref c) = prop.const_ {
+            parts.push(format!(
+                "{name} = {}{}{}",
+                f.magenta,
+                format_value(c),
+                f.reset
+            ));
+        } else if let Some(values) = prop.enum_.as_ref().filter(|v| !v.is_empty()) {
+            let joined = values
+                .iter()
+                .map(|v| format!("{}{}{}", f.magenta, format_value(v), f.reset))
+                .collect::<Vec<_>>()
+                .join(", ");
+            parts.push(format!("{name} is one of {joined}"));
+        } else if let Some(ref pat) = prop.pattern {
+            parts.push(format!("{name} matches {}{pat}{}", f.magenta, f.reset));
+        } else if let Some(ty) = schema_type_str(prop) {
+            parts.push(format!("{name} is {}", format_type(&ty, f)));
+        }
+    }
+    for req in schema.required_set() {
+        if !schema.properties.contains_key(req) {
+            parts.push(format!("{}{req}{} is present", f.green, f.reset));
+        }
+    }
+    if parts.is_empty() {
+        variant_summary(cond, root, f)
+    } else {
+        parts.join(" and ")
+    }
+}
+
 /// Extract the first sentence or line from a description for one-line summaries.
-fn first_sentence(desc: &str) -> &str {
+pub(crate) fn first_sentence(desc: &str) -> &str {
     // Use the first line break (paragraph boundary) if present.
     let trimmed = desc.trim();
     if let Some(pos) = trimmed.find("\n\n") {
@@ -0,0 +1,268 @@
+//! Fuzzy search over property names, titles and descriptions.
+//!
+//! Walks every property, item schema, composition branch and definition in a
+//! schema and ranks the nodes whose name or title fuzzy-matches the query,
+//! followed by nodes that only mention it in their description. `$ref`s are
+//! not followed — definitions are searched where they are declared — so the
+//! walk always terminates.
+
+use jsonschema_schema::{Schema, SchemaValue};
+
+use crate::schema::{first_sentence, get_description};
+
+/// A schema node matching a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    /// JSON Pointer to the matching sub-schema, e.g.
+    /// `/properties/server/properties/port` or `/$defs/Port`.
+    pub pointer: String,
+    /// Property or definition name.
+    pub name: String,
+    /// First sentence of the node's description, if any.
+    pub summary: Option<String>,
+    /// Relevance score; higher is better.
+    pub score: u32,
+}
+
+const EXACT: u32 = 1000;
+const PREFIX: u32 = 800;
+const SUBSTRING: u32 = 600;
+const SUBSEQUENCE: u32 = 200;
+const DESCRIPTION: u32 = 100;
+
+/// Fuzzy-search a schema for `query`.
+///
+/// Names are matched exactly, by prefix, by substring and finally as a
+/// subsequence (`srvprt` finds `serverPort`); titles score slightly lower
+/// than names, and descriptions only count when they contain the query as
+/// written (ignoring case). Hits are sorted best first, with shallower
+/// pointers winning ties.
+pub fn search(schema: &SchemaValue, query: &str) -> Vec<SearchHit> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+    let mut hits = Vec::new();
+    if let Some(s) = schema.as_schema() {
+        walk(s, "", &query, &mut hits);
+    }
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.pointer.len().cmp(&b.pointer.len()))
+            .then_with(|| a.pointer.cmp(&b.pointer))
+    });
+    hits
+}
+
+fn walk(schema: &Schema, pointer: &str, query: &str, hits: &mut Vec<SearchHit>) {
+    for (name, sv) in &schema.properties {
+        visit(sv, &child(pointer, "properties", name), name, query, hits);
+    }
+    for (pattern, sv) in &schema.pattern_properties {
+        visit(
+            sv,
+            &child(pointer, "patternProperties", pattern),
+            pattern,
+            query,
+            hits,
+        );
+    }
+    if let Some(defs) = &schema.defs {
+        for (name, sv) in defs {
+            visit(sv, &child(pointer, "$defs", name), name, query, hits);
+        }
+    }
+
+    let unnamed = [
+        ("items", schema.items.as_deref()),
+        (
+            "additionalProperties",
+            schema.additional_properties.as_deref(),
+        ),
+    ];
+    for (keyword, sv) in unnamed {
+        if let Some(s) = sv.and_then(SchemaValue::as_schema) {
+            walk(s, &format!("{pointer}/{keyword}"), query, hits);
+        }
+    }
+    let branches = [
+        ("allOf", schema.all_of.as_deref()),
+        ("anyOf", schema.any_of.as_deref()),
+        ("oneOf", schema.one_of.as_deref()),
+        ("prefixItems", schema.prefix_items.as_deref()),
+    ];
+    for (keyword, variants) in branches {
+        for (i, sv) in variants.unwrap_or_default().iter().enumerate() {
+            if let Some(s) = sv.as_schema() {
+                walk(s, &format!("{pointer}/{keyword}/{i}"), query, hits);
+            }
+        }
+    }
+}
+
+/// Score a named node, record it if it matches, then search inside it.
+#[allow(clippy::too_many_arguments)]
+fn visit(sv: &SchemaValue, pointer: &str, name: &str, query: &str, hits: &mut Vec<SearchHit>) {
+    let schema = sv.as_schema();
+    let description = schema.and_then(get_description);
+    let title_score = schema
+        .and_then(|s| s.title.as_deref())
+        .and_then(|t| fuzzy_score(query, t))
+        .map(|s| s * 4 / 5);
+    let description_score = description
+        .filter(|d| d.to_lowercase().contains(query))
+        .map(|_| DESCRIPTION);
+    let score = [fuzzy_score(query, name), title_score, description_score]
+        .into_iter()
+        .flatten()
+        .max();
+    if let Some(score) = score {
+        hits.push(SearchHit {
+            pointer: pointer.to_string(),
+            name: name.to_string(),
+            summary: description.map(|d| first_sentence(d).to_string()),
+            score,
+        });
+    }
+    if let Some(s) = schema {
+        walk(s, pointer, query, hits);
+    }
+}
+
+fn child(pointer: &str, keyword: &str, name: &str) -> String {
+    format!(
+        "{pointer}/{keyword}/{}",
+        name.replace('~', "~0").replace('/', "~1")
+    )
+}
+
+/// Score `text` against an already-lowercased `query`, or `None` if the
+/// query's characters do not all appear in order.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == query {
+        return Some(EXACT);
+    }
+    if text.starts_with(query) {
+        return Some(PREFIX);
+    }
+    if let Some(pos) = text.find(query) {
+        return Some(SUBSTRING.saturating_sub(u32::try_from(pos).unwrap_or(u32::MAX)));
+    }
+
+    // Subsequence: reward runs of consecutive characters, penalize gaps.
+    let mut chars = text.chars().enumerate();
+    let mut score = SUBSEQUENCE;
+    let mut last: Option<usize> = None;
+    for qc in query.chars() {
+        let (i, _) = chars.by_ref().find(|&(_, c)| c == qc)?;
+        match last {
+            Some(prev) if i == prev + 1 => score += 10,
+            Some(prev) => {
+                score = score.saturating_sub(u32::try_from(i - prev - 1).unwrap_or(u32::MAX));
+            }
+            None => {}
+        }
+        last = Some(i);
+    }
+    Some(score.max(1))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sv(val: serde_json::Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(jsonschema_migrate::migrate(val).unwrap()))
+    }
+
+    fn pointers(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.pointer.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_exact_before_fuzzy() {
+        let schema = sv(json!({
+            "properties": {
+                "serverPort": { "type": "integer" },
+                "port": { "type": "integer", "description": "Port to bind." },
+                "server": {
+                    "properties": { "portRange": { "type": "string" } }
+                }
+            }
+        }));
+        let hits = search(&schema, "port");
+        assert_eq!(
+            pointers(&hits),
+            [
+                "/properties/port",
+                "/properties/server/properties/portRange",
+                "/properties/serverPort",
+            ]
+        );
+        assert_eq!(hits[0].summary.as_deref(), Some("Port to bind."));
+    }
+
+    #[test]
+    fn subsequence_matches() {
+        let schema = sv(json!({
+            "properties": { "serverPort": {}, "name": {} }
+        }));
+        assert_eq!(
+            pointers(&search(&schema, "srvprt")),
+            ["/properties/serverPort"]
+        );
+    }
+
+    #[test]
+    fn searches_titles_descriptions_and_defs() {
+        let schema = sv(json!({
+            "properties": {
+                "tls": { "$ref": "#/$defs/Tls" },
+                "cert": { "description": "Path to the TLS certificate." }
+            },
+            "$defs": {
+                "Tls": { "title": "Transport security", "properties": { "key": {} } }
+            }
+        }));
+        let hits = search(&schema, "transport");
+        assert_eq!(pointers(&hits), ["/$defs/Tls"]);
+        let hits = search(&schema, "certificate");
+        assert_eq!(pointers(&hits), ["/properties/cert"]);
+        assert_eq!(hits[0].score, DESCRIPTION);
+        assert_eq!(
+            pointers(&search(&schema, "key")),
+            ["/$defs/Tls/properties/key"]
+        );
+    }
+
+    #[test]
+    fn walks_items_and_variants() {
+        let schema = sv(json!({
+            "properties": {
+                "steps": {
+                    "type": "array",
+                    "items": {
+                        "oneOf": [
+                            { "properties": { "run": {} } },
+                            { "properties": { "uses": {} } }
+                        ]
+                    }
+                }
+            }
+        }));
+        assert_eq!(
+            pointers(&search(&schema, "uses")),
+            ["/properties/steps/items/oneOf/1/properties/uses"]
+        );
+    }
+
+    #[test]
+    fn empty_query_has_no_hits() {
+        let schema = sv(json!({ "properties": { "a": {} } }));
+        assert!(search(&schema, "  ").is_empty());
+    }
+}
@@ -3,10 +3,14 @@ use core::fmt::Write;
 use jsonschema_schema::{Schema, SchemaValue};
 use serde_json::Value;
 
+use crate::ExampleFormat;
 use crate::fmt::{COMPOSITION_KEYWORDS, Fmt, format_type_suffix, format_value};
 use crate::man::{write_description, write_label, write_section};
 use crate::render::{render_properties, render_variant_block};
-use crate::schema::{get_description, required_set, resolve_ref, schema_type_str};
+use crate::schema::{
+    describe_condition, get_description, required_set, resolve_ref, schema_type_str,
+    variant_summary,
+};
 
 /// Render the SCHEMA section with URL and source information.
 pub(crate) fn render_schema_section(out: &mut String, schema: &Schema, f: &Fmt<'_>) {
@@ -64,33 +68,138 @@ pub(crate) fn render_variants_section(
     }
 }
 
-/// Render an EXAMPLES section when the schema has top-level `examples`.
-pub(crate) fn render_examples_section(out: &mut String, schema: &Schema, f: &Fmt<'_>) {
-    let examples = match schema.examples.as_ref() {
-        Some(arr) if !arr.is_empty() => arr,
-        _ => return,
+/// Render the CONDITIONAL, NOT and DEPENDENT SCHEMAS sections for root-level
+/// `if`/`then`/`else`, `not` and `dependentSchemas`.
+///
+/// Branches that add properties have them rendered in full, so readers can
+/// see which fields apply when the condition holds.
+pub(crate) fn render_conditional_sections(
+    out: &mut String,
+    schema: &Schema,
+    root: &SchemaValue,
+    f: &Fmt<'_>,
+) {
+    if let Some(ref if_sv) = schema.if_ {
+        write_section(out, "CONDITIONAL", f);
+        let condition = describe_condition(if_sv, root, f);
+        let _ = writeln!(out, "    If: {condition}");
+        if let Some(ref then_sv) = schema.then_ {
+            render_branch(out, "Then", then_sv, root, f);
+        }
+        if let Some(ref else_sv) = schema.else_ {
+            render_branch(out, "Else", else_sv, root, f);
+        }
+        out.push('\n');
+    }
+
+    if let Some(ref not_sv) = schema.not {
+        write_section(out, "NOT", f);
+        let _ = writeln!(out, "    {}", variant_summary(not_sv, root, f));
+        out.push('\n');
+    }
+
+    if !schema.dependent_schemas.is_empty() {
+        write_section(out, "DEPENDENT SCHEMAS", f);
+        for (key, dep_sv) in &schema.dependent_schemas {
+            let label = format!("When {}\"{key}\"{} is present", f.green, f.reset);
+            render_branch(out, &label, dep_sv, root, f);
+        }
+        out.push('\n');
+    }
+}
+
+/// Render one conditional branch: a summary line, then the properties and
+/// required fields the branch adds.
+#[allow(clippy::too_many_arguments)]
+fn render_branch(out: &mut String, label: &str, sv: &SchemaValue, root: &SchemaValue, f: &Fmt<'_>) {
+    let Some(resolved) = resolve_ref(sv, root).as_schema() else {
+        let _ = writeln!(out, "    {label}: {}", variant_summary(sv, root, f));
+        return;
     };
+    let required = required_set(resolved);
+    if resolved.properties.is_empty() && required.is_empty() {
+        let _ = writeln!(out, "    {label}: {}", variant_summary(sv, root, f));
+        return;
+    }
+    let _ = writeln!(out, "    {label}:");
+    if !required.is_empty() {
+        let joined = required
+            .iter()
+            .map(|r| format!("{}{r}{}", f.green, f.reset))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write_label(out, "        ", "Requires", &joined);
+    }
+    if !resolved.properties.is_empty() && f.can_nest(1) {
+        f.expand_ref(sv, || {
+            render_properties(out, &resolved.properties, &required, root, f, 2);
+        });
+    }
+}
+
+/// Render an EXAMPLES section from the schema's top-level `default` and
+/// `examples`.
+///
+/// Objects and arrays are shown as code blocks in [`Fmt::example_format`];
+/// scalars are shown inline.
+pub(crate) fn render_examples_section(out: &mut String, schema: &Schema, f: &Fmt<'_>) {
+    let examples = schema.examples.as_deref().unwrap_or_default();
+    if examples.is_empty() && schema.default.is_none() {
+        return;
+    }
 
     write_section(out, "EXAMPLES", f);
+    if let Some(ref default) = schema.default {
+        let _ = writeln!(out, "    {}(default){}:", f.dim, f.reset);
+        render_example(out, default, f);
+    }
+    let numbered = examples.len() > 1 || schema.default.is_some();
     for (i, example) in examples.iter().enumerate() {
-        if examples.len() > 1 {
+        if numbered {
             let _ = writeln!(out, "    {}({}){}:", f.dim, i + 1, f.reset);
         }
-        match example {
-            Value::Object(_) | Value::Array(_) => {
-                let json = serde_json::to_string_pretty(example).unwrap_or_default();
-                let lang_hint = if f.syntax_highlight { "json" } else { "" };
-                let block = format!("```{lang_hint}\n{json}\n```");
-                write_description(out, &block, f, "    ");
-            }
-            _ => {
-                let _ = writeln!(out, "    {}{}{}", f.magenta, format_value(example), f.reset);
-            }
-        }
+        render_example(out, example, f);
     }
     out.push('\n');
 }
 
+fn render_example(out: &mut String, example: &Value, f: &Fmt<'_>) {
+    match example {
+        Value::Object(_) | Value::Array(_) => {
+            let (code, lang) = format_example(example, f.example_format);
+            let lang_hint = if f.syntax_highlight { lang } else { "" };
+            let block = format!("```{lang_hint}\n{code}\n```");
+            write_description(out, &block, f, "    ");
+        }
+        _ => {
+            let _ = writeln!(out, "    {}{}{}", f.magenta, format_value(example), f.reset);
+        }
+    }
+}
+
+/// Serialize an example in the requested format, returning the text and its
+/// code-fence language. Falls back to JSON when the value cannot be
+/// represented (e.g. TOML has no `null` or top-level arrays).
+pub(crate) fn format_example(value: &Value, format: ExampleFormat) -> (String, &'static str) {
+    let converted = match format {
+        ExampleFormat::Json => None,
+        ExampleFormat::Yaml => serde_yaml::to_string(value)
+            .ok()
+            .map(|s| (s.trim_end().to_string(), "yaml")),
+        ExampleFormat::Toml => value
+            .is_object()
+            .then(|| toml::to_string_pretty(value).ok())
+            .flatten()
+            .map(|s| (s.trim_end().to_string(), "toml")),
+    };
+    converted.unwrap_or_else(|| {
+        (
+            serde_json::to_string_pretty(value).unwrap_or_default(),
+            "json",
+        )
+    })
+}
+
 /// Render the DEFINITIONS section (`$defs`/`definitions`).
 pub(crate) fn render_definitions_section(
     out: &mut String,
@@ -252,6 +361,38 @@ mod tests {
         assert!(out.contains("\"name\": \"test\""));
     }
 
+    #[test]
+    fn root_default_shown_in_examples() {
+        let mut out = String::new();
+        let f = Fmt::plain(80);
+        let schema = parse_schema(json!({
+            "type": "object",
+            "default": { "port": 8080 },
+            "examples": [{ "port": 80 }]
+        }));
+
+        render_examples_section(&mut out, &schema, &f);
+        assert!(out.contains("(default):"));
+        assert!(out.contains("\"port\": 8080"));
+        assert!(out.contains("(1):"));
+    }
+
+    #[test]
+    fn examples_converted_to_yaml_and_toml() {
+        let value = json!({ "server": { "port": 80 }, "tags": ["a"] });
+        let (yaml, lang) = format_example(&value, ExampleFormat::Yaml);
+        assert_eq!(lang, "yaml");
+        assert!(yaml.contains("server:\n  port: 80"));
+
+        let (toml, lang) = format_example(&value, ExampleFormat::Toml);
+        assert_eq!(lang, "toml");
+        assert!(toml.contains("[server]\nport = 80"));
+
+        // TOML cannot represent a top-level array; fall back to JSON.
+        let (_, lang) = format_example(&json!([1, 2]), ExampleFormat::Toml);
+        assert_eq!(lang, "json");
+    }
+
     #[test]
     fn empty_examples_not_shown() {
         let mut out = String::new();
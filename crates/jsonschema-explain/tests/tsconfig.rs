@@ -1,4 +1,6 @@
-use jsonschema_explain::{ExplainOptions, explain, explain_at_path};
+use jsonschema_explain::{
+    DEFAULT_MAX_DEPTH, ExampleFormat, ExplainOptions, explain, explain_at_path,
+};
 use jsonschema_schema::SchemaValue;
 
 fn plain() -> ExplainOptions {
@@ -8,6 +10,12 @@ fn plain() -> ExplainOptions {
         width: 80,
         validation_errors: vec![],
         extended: false,
+        max_depth: Some(DEFAULT_MAX_DEPTH),
+        example_format: ExampleFormat::Json,
+        annotations: Vec::new(),
+        required_only: false,
+        property_filter: None,
+        exclude_deprecated: false,
     }
 }
 
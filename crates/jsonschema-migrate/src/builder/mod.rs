@@ -3,6 +3,7 @@ mod cleanup;
 mod deps;
 mod id;
 mod items;
+mod openapi;
 
 use serde_json::{Map, Value};
 
@@ -63,6 +64,10 @@ pub fn migrate_in_place(obj: &mut Map<String, Value>, draft: Option<Draft>) {
     let needs_migration = draft != Some(Draft::Draft2020_12);
     let looks_like_schema = is_schema_like(obj);
 
+    if draft == Some(Draft::OpenApi30) {
+        openapi::migrate_nullable(obj);
+        openapi::migrate_example(obj);
+    }
     if needs_migration {
         id::migrate_id(obj, looks_like_schema);
         id::rewrite_ref_value(obj, "$id");
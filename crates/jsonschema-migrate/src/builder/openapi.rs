@@ -0,0 +1,139 @@
+use serde_json::{Map, Value};
+
+/// Keys that stay on the outer schema when `nullable` wraps it in `anyOf`.
+const ANNOTATION_KEYS: &[&str] = &[
+    "title",
+    "description",
+    "default",
+    "example",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Keys that make a schema without `type` accept values of several types.
+const COMPOSITION_KEYS: &[&str] = &["$ref", "allOf", "anyOf", "oneOf"];
+
+/// Replace OpenAPI 3.0 `nullable: true` with a `null` type.
+///
+/// - `type: "string"` becomes `type: ["string", "null"]`, and `null` is added
+///   to an `enum`
+/// - a schema with no `type` but a `$ref` or composition becomes
+///   `anyOf: [<schema>, {"type": "null"}]`, keeping annotations outside
+/// - otherwise `nullable` has no effect and is dropped
+pub fn migrate_nullable(obj: &mut Map<String, Value>) {
+    if obj.remove("nullable") != Some(Value::Bool(true)) {
+        return;
+    }
+    match obj.get_mut("type") {
+        Some(Value::String(ty)) => {
+            let ty = core::mem::take(ty);
+            obj.insert(
+                "type".to_string(),
+                Value::Array(vec![Value::String(ty), Value::String("null".to_string())]),
+            );
+        }
+        Some(Value::Array(types)) => {
+            if !types.iter().any(|t| t == "null") {
+                types.push(Value::String("null".to_string()));
+            }
+        }
+        _ => {
+            if COMPOSITION_KEYS.iter().any(|k| obj.contains_key(*k)) {
+                wrap_nullable(obj);
+            }
+            return;
+        }
+    }
+    if let Some(Value::Array(values)) = obj.get_mut("enum")
+        && !values.contains(&Value::Null)
+    {
+        values.push(Value::Null);
+    }
+}
+
+fn wrap_nullable(obj: &mut Map<String, Value>) {
+    let mut inner = core::mem::take(obj);
+    for key in ANNOTATION_KEYS {
+        if let Some(v) = inner.remove(*key) {
+            obj.insert((*key).to_string(), v);
+        }
+    }
+    let mut null = Map::new();
+    null.insert("type".to_string(), Value::String("null".to_string()));
+    obj.insert(
+        "anyOf".to_string(),
+        Value::Array(vec![Value::Object(inner), Value::Object(null)]),
+    );
+}
+
+/// Convert OpenAPI 3.0 `example` to a 2020-12 `examples` array.
+pub fn migrate_example(obj: &mut Map<String, Value>) {
+    let Some(example) = obj.remove("example") else {
+        return;
+    };
+    match obj.get_mut("examples") {
+        Some(Value::Array(examples)) => examples.push(example),
+        _ => {
+            obj.insert("examples".to_string(), Value::Array(vec![example]));
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::needless_pass_by_value)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn obj(value: serde_json::Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn nullable_adds_null_type_and_enum_value() {
+        let mut m = obj(json!({"type": "string", "enum": ["a", "b"], "nullable": true}));
+        migrate_nullable(&mut m);
+        assert_eq!(
+            Value::Object(m),
+            json!({"type": ["string", "null"], "enum": ["a", "b", null]})
+        );
+    }
+
+    #[test]
+    fn nullable_ref_becomes_any_of() {
+        let mut m = obj(json!({
+            "description": "The owner",
+            "allOf": [{"$ref": "#/components/schemas/User"}],
+            "nullable": true
+        }));
+        migrate_nullable(&mut m);
+        assert_eq!(
+            Value::Object(m),
+            json!({
+                "description": "The owner",
+                "anyOf": [
+                    {"allOf": [{"$ref": "#/components/schemas/User"}]},
+                    {"type": "null"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn nullable_false_is_dropped() {
+        let mut m = obj(json!({"type": "integer", "nullable": false}));
+        migrate_nullable(&mut m);
+        assert_eq!(Value::Object(m), json!({"type": "integer"}));
+    }
+
+    #[test]
+    fn example_becomes_examples() {
+        let mut m = obj(json!({"type": "integer", "example": 3}));
+        migrate_example(&mut m);
+        assert_eq!(
+            Value::Object(m),
+            json!({"type": "integer", "examples": [3]})
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Draft 2020-12 → draft-07, for tools that do not support 2020-12 yet.
+//!
+//! Keywords with no draft-07 equivalent (`unevaluatedProperties`,
+//! `$dynamicRef`, ...) are left in place; draft-07 validators ignore them.
+
+use serde_json::{Map, Value};
+
+/// Keys that hold a single schema (after the downgrade).
+const SINGLE_SCHEMA_KEYS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "propertyNames",
+    "not",
+    "if",
+    "then",
+    "else",
+];
+
+/// Keys that hold a map of named schemas (after the downgrade).
+const MAP_SCHEMA_KEYS: &[&str] = &["properties", "patternProperties", "definitions"];
+
+/// Keys that hold an array of schemas.
+const ARRAY_SCHEMA_KEYS: &[&str] = &["allOf", "anyOf", "oneOf"];
+
+/// Downgrade a draft 2020-12 schema map to draft-07 in place.
+pub fn downgrade_in_place(obj: &mut Map<String, Value>) {
+    if let Some(defs) = obj.remove("$defs") {
+        obj.insert("definitions".to_string(), defs);
+    }
+    if let Some(Value::String(r)) = obj.get_mut("$ref")
+        && r.contains("#/$defs/")
+    {
+        *r = r.replace("#/$defs/", "#/definitions/");
+    }
+    if let Some(prefix) = obj.remove("prefixItems") {
+        if let Some(items) = obj.remove("items") {
+            obj.insert("additionalItems".to_string(), items);
+        }
+        obj.insert("items".to_string(), prefix);
+    }
+    merge_dependencies(obj);
+
+    // Recurse into nested schema positions
+    for key in SINGLE_SCHEMA_KEYS {
+        if let Some(Value::Object(v)) = obj.get_mut(*key) {
+            downgrade_in_place(v);
+        }
+    }
+    for key in MAP_SCHEMA_KEYS.iter().chain(&["dependencies"]) {
+        if let Some(Value::Object(map)) = obj.get_mut(*key) {
+            for v in map.values_mut() {
+                if let Value::Object(v) = v {
+                    downgrade_in_place(v);
+                }
+            }
+        }
+    }
+    for key in ARRAY_SCHEMA_KEYS.iter().chain(&["items"]) {
+        match obj.get_mut(*key) {
+            Some(Value::Array(arr)) => {
+                for v in arr.iter_mut() {
+                    if let Value::Object(v) = v {
+                        downgrade_in_place(v);
+                    }
+                }
+            }
+            Some(Value::Object(v)) => downgrade_in_place(v),
+            _ => {}
+        }
+    }
+}
+
+/// Merge `dependentSchemas` and `dependentRequired` into `dependencies`.
+fn merge_dependencies(obj: &mut Map<String, Value>) {
+    let schemas = obj.remove("dependentSchemas");
+    let required = obj.remove("dependentRequired");
+    if schemas.is_none() && required.is_none() {
+        return;
+    }
+    let mut dependencies = Map::new();
+    for source in [schemas, required].into_iter().flatten() {
+        if let Value::Object(map) = source {
+            for (k, v) in map {
+                dependencies.entry(k).or_insert(v);
+            }
+        }
+    }
+    obj.insert("dependencies".to_string(), Value::Object(dependencies));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn downgrade(value: &Value) -> Value {
+        let mut obj = value.as_object().expect("test schemas are objects").clone();
+        downgrade_in_place(&mut obj);
+        Value::Object(obj)
+    }
+
+    #[test]
+    fn renames_defs_and_refs() {
+        let schema = downgrade(&json!({
+            "$defs": {"name": {"type": "string"}},
+            "properties": {"name": {"$ref": "#/$defs/name"}}
+        }));
+        assert_eq!(
+            schema,
+            json!({
+                "definitions": {"name": {"type": "string"}},
+                "properties": {"name": {"$ref": "#/definitions/name"}}
+            })
+        );
+    }
+
+    #[test]
+    fn prefix_items_become_array_items() {
+        let schema = downgrade(&json!({
+            "prefixItems": [{"type": "string"}],
+            "items": {"$ref": "#/$defs/rest"}
+        }));
+        assert_eq!(
+            schema,
+            json!({
+                "items": [{"type": "string"}],
+                "additionalItems": {"$ref": "#/definitions/rest"}
+            })
+        );
+    }
+
+    #[test]
+    fn dependent_keywords_merge_into_dependencies() {
+        let schema = downgrade(&json!({
+            "dependentSchemas": {"a": {"required": ["b"]}},
+            "dependentRequired": {"c": ["d"]}
+        }));
+        assert_eq!(
+            schema,
+            json!({"dependencies": {"a": {"required": ["b"]}, "c": ["d"]}})
+        );
+    }
+}
@@ -11,6 +11,9 @@ pub enum Draft {
     Draft2019_09,
     /// `https://json-schema.org/draft/2020-12/schema`
     Draft2020_12,
+    /// OpenAPI 3.0 Schema Object: draft 04 with `nullable` and `example`.
+    /// Never detected, as OpenAPI schemas carry no `$schema`.
+    OpenApi30,
 }
 
 /// Detect the JSON Schema draft from the root `$schema` keyword.
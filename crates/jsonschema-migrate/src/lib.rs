@@ -1,11 +1,12 @@
 #![doc = include_str!("../README.md")]
 
 mod builder;
+mod downgrade;
 mod draft;
 mod regex;
 
 pub use crate::draft::{Draft, detect_draft};
-pub use crate::regex::normalize_ecma_regex;
+pub use crate::regex::{Translation, normalize_ecma_regex, translate_ecma_regex};
 pub use jsonschema_schema::Schema;
 
 /// Migrate a JSON Schema document to draft 2020-12, returning a typed [`Schema`].
@@ -28,6 +29,76 @@ pub fn migrate(mut schema: serde_json::Value) -> Result<Schema, serde_json::Erro
 /// Safe to call on schemas that are already 2020-12 (idempotent).
 pub fn migrate_to_2020_12(schema: &mut serde_json::Value) {
     let draft = draft::detect_draft(schema);
+    migrate_from(schema, draft);
+}
+
+/// Migrate a JSON Schema document to draft-07 in place, for consumers that
+/// do not support 2020-12.
+///
+/// The schema is first migrated to 2020-12, then `$defs`, `prefixItems` and
+/// `dependentSchemas`/`dependentRequired` are converted back to their
+/// draft-07 forms. Keywords with no draft-07 equivalent are kept.
+pub fn migrate_to_draft_07(schema: &mut serde_json::Value) {
+    migrate_to_2020_12(schema);
+    let serde_json::Value::Object(obj) = schema else {
+        return;
+    };
+    obj.insert(
+        "$schema".to_string(),
+        serde_json::Value::String("http://json-schema.org/draft-07/schema#".to_string()),
+    );
+    downgrade::downgrade_in_place(obj);
+}
+
+/// Migrate an OpenAPI 3.0 Schema Object to draft 2020-12 in place.
+///
+/// On top of the draft 04 transformations, `nullable: true` becomes a `null`
+/// type and `example` becomes `examples`. `$ref`s are left as they are; use
+/// [`openapi_component_schema`] to extract a component with the schemas it
+/// refers to.
+pub fn migrate_openapi_30(schema: &mut serde_json::Value) {
+    migrate_from(schema, Some(Draft::OpenApi30));
+}
+
+/// Extract `#/components/schemas/{name}` from an OpenAPI 3.0 document as a
+/// standalone draft 2020-12 schema.
+///
+/// All component schemas are copied into `$defs` and `$ref`s to
+/// `#/components/schemas/...` are rewritten to point there, so the result
+/// can be validated against and explained on its own. Returns `None` if the
+/// document has no such component.
+pub fn openapi_component_schema(doc: &serde_json::Value, name: &str) -> Option<serde_json::Value> {
+    let components = doc.pointer("/components/schemas")?.as_object()?;
+    let mut schema = components.get(name)?.clone();
+    let serde_json::Value::Object(obj) = &mut schema else {
+        return Some(schema);
+    };
+    obj.insert(
+        "$defs".to_string(),
+        serde_json::Value::Object(components.clone()),
+    );
+    rewrite_component_refs(&mut schema);
+    migrate_openapi_30(&mut schema);
+    Some(schema)
+}
+
+/// Point `#/components/schemas/...` refs at `#/$defs/...`.
+fn rewrite_component_refs(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if let Some(serde_json::Value::String(r)) = obj.get_mut("$ref")
+                && let Some(rest) = r.strip_prefix("#/components/schemas/")
+            {
+                *r = format!("#/$defs/{rest}");
+            }
+            obj.values_mut().for_each(rewrite_component_refs);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(rewrite_component_refs),
+        _ => {}
+    }
+}
+
+fn migrate_from(schema: &mut serde_json::Value, draft: Option<Draft>) {
     let serde_json::Value::Object(obj) = schema else {
         return;
     };
@@ -305,6 +376,63 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn extracts_openapi_component_schemas() {
+        let doc = json!({
+            "openapi": "3.0.3",
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string", "example": "Rex"},
+                            "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                            "owner": {
+                                "allOf": [{"$ref": "#/components/schemas/User"}],
+                                "nullable": true
+                            }
+                        }
+                    },
+                    "User": {"type": "string", "nullable": true}
+                }
+            }
+        });
+        let schema = openapi_component_schema(&doc, "Pet").unwrap();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        let properties = &schema["properties"];
+        assert_eq!(properties["name"]["examples"], json!(["Rex"]));
+        assert_eq!(properties["age"]["exclusiveMinimum"], 0);
+        assert_eq!(
+            properties["owner"]["anyOf"],
+            json!([{"allOf": [{"$ref": "#/$defs/User"}]}, {"type": "null"}])
+        );
+        assert_eq!(schema["$defs"]["User"]["type"], json!(["string", "null"]));
+        assert!(migrate(schema).is_ok());
+        assert!(openapi_component_schema(&doc, "Missing").is_none());
+    }
+
+    #[test]
+    fn migrates_draft_04_to_draft_07() {
+        let mut schema = json!({
+            "$schema": "http://json-schema.org/draft-04/schema#",
+            "definitions": {
+                "port": {"type": "integer", "minimum": 0, "exclusiveMinimum": true}
+            },
+            "properties": {"port": {"$ref": "#/definitions/port"}}
+        });
+        migrate_to_draft_07(&mut schema);
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert!(schema.get("$defs").is_none());
+        assert_eq!(
+            schema["definitions"]["port"],
+            json!({"type": "integer", "exclusiveMinimum": 0})
+        );
+        assert_eq!(schema["properties"]["port"]["$ref"], "#/definitions/port");
+    }
+
     #[test]
     fn sets_schema_at_root() {
         let mut schema = json!({"type": "object"});
@@ -1,5 +1,23 @@
+/// An ECMA 262 pattern translated to Rust regex syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    /// The pattern, accepted by `regex_syntax` (or by `fancy_regex`, when it
+    /// uses lookaround or backreferences).
+    pub pattern: String,
+    /// Constructs that could not be carried over with their ECMA 262 meaning.
+    pub warnings: Vec<String>,
+}
+
 /// Normalize an ECMA 262 regex pattern for compatibility with Rust's `regex_syntax`.
 ///
+/// This is [`translate_ecma_regex`] without the warnings.
+pub fn normalize_ecma_regex(pattern: &str) -> String {
+    translate_ecma_regex(pattern).pattern
+}
+
+/// Translate an ECMA 262 regex pattern to the syntax of Rust's `regex_syntax`
+/// and `fancy_regex`.
+///
 /// Several ECMA 262 / PCRE incompatibilities are fixed:
 ///
 /// 1. **Bare braces**: Unescaped `{` and `}` that do not form valid quantifiers
@@ -17,122 +35,289 @@
 ///    shorthand class like `\w` is escaped to `\-` because `regex_syntax`
 ///    doesn't allow class shorthands as range endpoints.
 ///
-/// 5. **Bare `[` in character classes**: Escaped to `\[`. ECMA 262 treats `[`
-///    as a literal inside `[…]`, but `regex_syntax` tries to parse nested classes.
+/// 5. **Character classes**: a bare `[` is escaped to `\[`, and the second
+///    character of `&&`, `--` and `~~` is escaped, since `regex_syntax` reads
+///    those as nested classes and set operations. `[]` never matches and `[^]`
+///    matches any character, as in ECMA 262. `\b` is a backspace and `\0`–`\7`
+///    start an octal escape.
+///
+/// 6. **Escapes**: `\cX` control escapes become `\xHH`, `\0` becomes `\x00`,
+///    UTF-16 surrogate pairs (`\uD83D\uDE00`) become `\u{1F600}`, and identity
+///    escapes that `regex_syntax` rejects (`\e`, `\<`, `\é`, …) become the
+///    literal character.
+///
+/// 7. **Named groups**: `(?<name>…)` becomes `(?P<name>…)`.
+///
+/// Lookahead and lookbehind are kept for `fancy_regex`. Lone surrogates, group
+/// names that Rust does not accept and lookbehinds of variable length (such as
+/// `(?<=a+)`) cannot be preserved; each one adds a warning.
 #[allow(clippy::missing_panics_doc)] // from_utf8 cannot panic on our output
-pub fn normalize_ecma_regex(pattern: &str) -> String {
+pub fn translate_ecma_regex(pattern: &str) -> Translation {
     let b = pattern.as_bytes();
-    let valid_braces = find_valid_quantifier_braces(b);
-    let mut out = Vec::with_capacity(b.len() + 16);
-    let mut i = 0;
-    let mut in_class = false;
+    let mut translator = Translator {
+        b,
+        valid_braces: find_valid_quantifier_braces(b),
+        out: Vec::with_capacity(b.len() + 16),
+        warnings: Vec::new(),
+        i: 0,
+        in_class: false,
+    };
+    translator.run();
 
-    while i < b.len() {
-        // Handle escape sequences
-        if b[i] == b'\\' && i + 1 < b.len() {
-            let next = b[i + 1];
+    check_lookbehinds(&translator.out, &mut translator.warnings);
+    // Safety: input is valid UTF-8 and we only replace/insert ASCII bytes.
+    // Non-ASCII bytes (≥ 128) never match our ASCII comparisons, so multi-byte
+    // UTF-8 sequences pass through unchanged.
+    Translation {
+        pattern: String::from_utf8(translator.out).expect("normalization preserves UTF-8"),
+        warnings: translator.warnings,
+    }
+}
 
-            // Expand \d → 0-9 inside character classes
-            if in_class && next == b'd' {
-                out.extend_from_slice(b"0-9");
-                i += 2;
-                continue;
-            }
+struct Translator<'a> {
+    b: &'a [u8],
+    valid_braces: Vec<bool>,
+    out: Vec<u8>,
+    warnings: Vec<String>,
+    i: usize,
+    in_class: bool,
+}
 
-            // Inside character classes: shorthand classes (\w, \s, \W, \D, \S)
-            // can't be range endpoints. If followed by `-`, escape the `-`.
-            if in_class && matches!(next, b'w' | b's' | b'W' | b'D' | b'S') {
-                out.push(b'\\');
-                out.push(next);
-                i += 2;
-                // Escape a following `-` that would form an invalid range
-                if i < b.len() && b[i] == b'-' && i + 1 < b.len() && b[i + 1] != b']' {
-                    out.extend_from_slice(b"\\-");
-                    i += 1;
-                }
-                continue;
+impl Translator<'_> {
+    fn peek(&self, ahead: usize) -> Option<u8> {
+        self.b.get(self.i + ahead).copied()
+    }
+
+    /// Copy the next `len` bytes unchanged.
+    fn copy(&mut self, len: usize) {
+        let end = (self.i + len).min(self.b.len());
+        self.out.extend_from_slice(&self.b[self.i..end]);
+        self.i = end;
+    }
+
+    /// Replace the next `len` bytes with `s`.
+    fn replace(&mut self, len: usize, s: &[u8]) {
+        self.out.extend_from_slice(s);
+        self.i += len;
+    }
+
+    fn run(&mut self) {
+        while let Some(c) = self.peek(0) {
+            match c {
+                b'\\' if self.i + 1 < self.b.len() => self.escape(),
+                b'[' => self.class(),
+                b'(' => self.group(),
+                // Escape bare braces outside character class
+                b'{' if !self.valid_braces[self.i] => self.replace(1, b"\\{"),
+                b'}' if !self.valid_braces[self.i] => self.replace(1, b"\\}"),
+                _ => self.copy(1),
             }
+        }
+    }
 
+    /// An escape outside a character class.
+    fn escape(&mut self) {
+        match self.b[self.i + 1] {
             // Convert PCRE anchors: \A → ^, \Z → $
-            if !in_class && (next == b'A' || next == b'Z') {
-                out.push(if next == b'A' { b'^' } else { b'$' });
-                i += 2;
-                continue;
+            b'A' => self.replace(2, b"^"),
+            b'Z' => self.replace(2, b"$"),
+            b'0' => self.octal_escape(),
+            // Backreferences and escapes with the same meaning in Rust
+            b'1'..=b'9' | b'b' | b'B' | b'd' | b'D' | b's' | b'S' | b'w' | b'W' | b'z' => {
+                self.copy(2);
             }
+            b'k' if self.peek(2) == Some(b'<') => self.copy(2),
+            _ => self.common_escape(),
+        }
+    }
 
-            // Pass through Unicode escapes: \p{...}, \P{...}, \u{...}
-            if matches!(next, b'p' | b'P' | b'u') && i + 2 < b.len() && b[i + 2] == b'{' {
-                out.push(b'\\');
-                out.push(next);
-                i += 2;
-                if let Some(close) = b[i..].iter().position(|&c| c == b'}') {
-                    out.extend_from_slice(&b[i..=i + close]);
-                    i += close + 1;
-                }
-                continue;
+    /// Escapes that mean the same inside and outside character classes.
+    fn common_escape(&mut self) {
+        let next = self.b[self.i + 1];
+        match next {
+            b'f' | b'n' | b'r' | b't' | b'v' => self.copy(2),
+            b'c' => self.control_escape(),
+            // Pass through Unicode escapes: \p{...}, \P{...}, \u{...}, \x{...}
+            b'p' | b'P' | b'u' | b'x' if self.peek(2) == Some(b'{') => {
+                let len = self.b[self.i..]
+                    .iter()
+                    .position(|&c| c == b'}')
+                    .map_or(2, |close| close + 1);
+                self.copy(len);
             }
+            b'u' if self.hex_digits(2, 4).is_some() => self.unicode_escape(),
+            b'x' if self.hex_digits(2, 2).is_some() => self.copy(4),
+            // Identity escapes: ECMA 262 (Annex B) reads `\e` as `e`, but
+            // `regex_syntax` only accepts escaped ASCII punctuation.
+            b'<' | b'>' => self.replace(2, &[next]),
+            c if c.is_ascii_alphanumeric() => self.replace(2, &[c]),
+            c if c.is_ascii() => self.copy(2),
+            // `\é`: drop the backslash, the character follows unchanged
+            _ => self.i += 1,
+        }
+    }
 
-            out.push(b[i]);
-            out.push(next);
-            i += 2;
-            continue;
+    /// The value of `len` hex digits starting `ahead` bytes from here.
+    fn hex_digits(&self, ahead: usize, len: usize) -> Option<u32> {
+        let start = self.i + ahead;
+        let digits = self.b.get(start..start + len)?;
+        if !digits.iter().all(u8::is_ascii_hexdigit) {
+            return None;
         }
+        u32::from_str_radix(core::str::from_utf8(digits).ok()?, 16).ok()
+    }
 
-        // Character class start
-        if b[i] == b'[' && !in_class {
-            in_class = true;
-            out.push(b'[');
-            i += 1;
-            // Skip negation and literal ] at class start
-            if i < b.len() && b[i] == b'^' {
-                out.push(b'^');
-                i += 1;
-            }
-            if i < b.len() && b[i] == b']' {
-                out.push(b']');
-                i += 1;
+    /// `\uHHHH`, joining surrogate pairs into one code point.
+    fn unicode_escape(&mut self) {
+        let Some(unit) = self.hex_digits(2, 4) else {
+            return;
+        };
+        if !(0xD800..=0xDFFF).contains(&unit) {
+            self.copy(6);
+            return;
+        }
+        if unit < 0xDC00
+            && self.peek(6) == Some(b'\\')
+            && self.peek(7) == Some(b'u')
+            && let Some(low) = self.hex_digits(8, 4)
+            && (0xDC00..=0xDFFF).contains(&low)
+        {
+            let code_point = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+            self.replace(12, format!("\\u{{{code_point:X}}}").as_bytes());
+            return;
+        }
+
+        let escape = String::from_utf8_lossy(&self.b[self.i..self.i + 6]).into_owned();
+        self.i += 6;
+        if self.in_class {
+            // Drop the surrogate, and the range it starts
+            if self.peek(0) == Some(b'-')
+                && self.peek(1) == Some(b'\\')
+                && self.peek(2) == Some(b'u')
+                && self.hex_digits(3, 4).is_some()
+            {
+                self.i += 7;
             }
-            continue;
+            self.warnings.push(format!(
+                "lone surrogate `{escape}` cannot be matched in Rust; removed from the class"
+            ));
+        } else {
+            self.out.extend_from_slice(br"[^\s\S]");
+            self.warnings.push(format!(
+                "lone surrogate `{escape}` cannot be matched in Rust; replaced by a class that never matches"
+            ));
         }
-        if b[i] == b']' && in_class {
-            in_class = false;
-            out.push(b']');
-            i += 1;
-            continue;
+    }
+
+    /// `\cX` → the control character `X % 32`.
+    fn control_escape(&mut self) {
+        match self.peek(2) {
+            Some(letter) if letter.is_ascii_alphabetic() => {
+                self.replace(3, format!("\\x{:02X}", letter % 32).as_bytes());
+            }
+            // ECMA 262 (Annex B) reads `\c` without a letter as a backslash
+            _ => self.replace(1, br"\\"),
         }
+    }
 
-        // Inside character class: escape bare `[` (ECMA 262 treats as literal,
-        // but regex_syntax attempts to parse nested character classes)
-        if in_class {
-            if b[i] == b'[' {
-                out.extend_from_slice(b"\\[");
-            } else {
-                out.push(b[i]);
+    /// Legacy octal escape (`\0`, `\12`, `\377`) → `\xHH`.
+    fn octal_escape(&mut self) {
+        let mut value = 0;
+        let mut len = 0;
+        while len < 3
+            && let Some(digit @ b'0'..=b'7') = self.peek(1 + len)
+        {
+            let next = value * 8 + u32::from(digit - b'0');
+            if next > 0o377 {
+                break;
             }
-            i += 1;
-            continue;
+            value = next;
+            len += 1;
         }
+        self.replace(1 + len, format!("\\x{value:02X}").as_bytes());
+    }
 
-        // Escape bare braces outside character class
-        if b[i] == b'{' && !valid_braces[i] {
-            out.extend_from_slice(b"\\{");
-            i += 1;
-            continue;
+    fn class(&mut self) {
+        self.in_class = true;
+        self.copy(1);
+        let negated = self.peek(0) == Some(b'^');
+        if negated {
+            self.copy(1);
         }
-        if b[i] == b'}' && !valid_braces[i] {
-            out.extend_from_slice(b"\\}");
-            i += 1;
-            continue;
+        let start = self.out.len();
+        while let Some(c) = self.peek(0) {
+            match c {
+                b']' => {
+                    // `[]` never matches and `[^]` matches anything
+                    if self.out.len() == start {
+                        if negated {
+                            self.out.pop();
+                            self.out.extend_from_slice(br"\s\S");
+                        } else {
+                            self.out.extend_from_slice(br"^\s\S");
+                        }
+                    }
+                    self.copy(1);
+                    break;
+                }
+                b'\\' if self.i + 1 < self.b.len() => self.class_escape(),
+                // ECMA 262 treats `[` as a literal inside `[…]`, but
+                // regex_syntax attempts to parse nested character classes
+                b'[' => self.replace(1, br"\["),
+                // `&&`, `--` and `~~` are set operations in regex_syntax
+                b'&' | b'-' | b'~' if self.peek(1) == Some(c) => {
+                    self.replace(2, &[c, b'\\', c]);
+                }
+                _ => self.copy(1),
+            }
         }
+        self.in_class = false;
+    }
 
-        out.push(b[i]);
-        i += 1;
+    /// An escape inside a character class.
+    fn class_escape(&mut self) {
+        let next = self.b[self.i + 1];
+        match next {
+            // Expand \d → 0-9 inside character classes
+            b'd' => self.replace(2, b"0-9"),
+            // Shorthand classes (\w, \s, \W, \D, \S) can't be range
+            // endpoints. If followed by `-`, escape the `-`.
+            b'w' | b's' | b'W' | b'D' | b'S' => {
+                self.copy(2);
+                if self.peek(0) == Some(b'-') && self.peek(1).is_some_and(|c| c != b']') {
+                    self.replace(1, br"\-");
+                }
+            }
+            b'b' => self.replace(2, br"\x08"),
+            b'0'..=b'7' => self.octal_escape(),
+            _ => self.common_escape(),
+        }
     }
 
-    // Safety: input is valid UTF-8 and we only replace/insert ASCII bytes.
-    // Non-ASCII bytes (≥ 128) never match our ASCII comparisons, so multi-byte
-    // UTF-8 sequences pass through unchanged.
-    String::from_utf8(out).expect("normalization preserves UTF-8")
+    /// `(?<name>` → `(?P<name>`; other groups are copied.
+    fn group(&mut self) {
+        let rest = &self.b[self.i..];
+        if rest.starts_with(b"(?<")
+            && !rest.starts_with(b"(?<=")
+            && !rest.starts_with(b"(?<!")
+            && let Some(len) = rest[3..].iter().position(|&c| c == b'>')
+        {
+            let name = String::from_utf8_lossy(&rest[3..3 + len]).into_owned();
+            let mut chars = name.chars();
+            let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_');
+            if valid {
+                self.replace(4 + len, format!("(?P<{name}>").as_bytes());
+            } else {
+                self.replace(4 + len, b"(");
+                self.warnings.push(format!(
+                    "group name `{name}` is not valid in Rust; kept as an unnamed group"
+                ));
+            }
+            return;
+        }
+        self.copy(1);
+    }
 }
 
 /// Identify positions of `{` and `}` that form valid quantifiers.
@@ -220,6 +405,171 @@ fn parse_quantifier(b: &[u8], start: usize) -> Option<usize> {
     None
 }
 
+/// Warn about lookbehinds in a translated pattern with an alternative whose
+/// length varies. `fancy_regex` splits `(?<=a|bc)` into one lookbehind per
+/// alternative, but each of them needs a fixed length.
+fn check_lookbehinds(b: &[u8], warnings: &mut Vec<String>) {
+    let mut i = 0;
+    while i < b.len() {
+        if (b[i..].starts_with(b"(?<=") || b[i..].starts_with(b"(?<!"))
+            && let Some(close) = group_end(b, i)
+            && split_alternatives(&b[i + 4..close])
+                .into_iter()
+                .any(|alt| fixed_width(alt).is_none())
+        {
+            warnings.push(format!(
+                "lookbehind `{}` has no fixed length, which Rust regex engines require",
+                String::from_utf8_lossy(&b[i..=close])
+            ));
+        }
+        i = token_end(b, i);
+    }
+}
+
+/// End of the token starting at `b[i]`: an escape, a whole character class
+/// or a single byte.
+fn token_end(b: &[u8], i: usize) -> usize {
+    let until = |close: u8| {
+        b[i..]
+            .iter()
+            .position(|&c| c == close)
+            .map_or(b.len(), |p| i + p + 1)
+    };
+    match b[i] {
+        b'\\' if i + 1 < b.len() => match b[i + 1] {
+            b'p' | b'P' | b'u' | b'x' if b.get(i + 2) == Some(&b'{') => until(b'}'),
+            b'k' if b.get(i + 2) == Some(&b'<') => until(b'>'),
+            b'x' => (i + 4).min(b.len()),
+            b'u' => (i + 6).min(b.len()),
+            _ => i + 2,
+        },
+        b'[' => {
+            let mut j = i + 1;
+            if b.get(j) == Some(&b'^') {
+                j += 1;
+            }
+            while j < b.len() {
+                match b[j] {
+                    b'\\' => j += 2,
+                    b']' => return j + 1,
+                    _ => j += 1,
+                }
+            }
+            b.len()
+        }
+        _ => i + 1,
+    }
+}
+
+/// Index of the `)` closing the group opened at `b[open]`.
+fn group_end(b: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < b.len() {
+        match b[i] {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i = token_end(b, i);
+    }
+    None
+}
+
+/// Split `b` on `|` outside groups.
+fn split_alternatives(b: &[u8]) -> Vec<&[u8]> {
+    let mut alternatives = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b'|' if depth == 0 => {
+                alternatives.push(&b[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i = token_end(b, i);
+    }
+    alternatives.push(&b[start..]);
+    alternatives
+}
+
+/// Number of characters matched by a pattern without top-level `|`, or
+/// `None` if it varies.
+fn fixed_width(b: &[u8]) -> Option<usize> {
+    let mut width = 0;
+    let mut last = 0;
+    let mut i = 0;
+    while i < b.len() {
+        let mut end = token_end(b, i);
+        let w = match b[i] {
+            b'*' | b'+' | b'?' => return None,
+            b'{' => {
+                let close = parse_quantifier(b, i)?;
+                let bounds = core::str::from_utf8(&b[i + 1..close]).ok()?;
+                let (min, max) = bounds.split_once(',').unwrap_or((bounds, bounds));
+                if min != max {
+                    return None;
+                }
+                let count: usize = min.parse().ok()?;
+                width = width - last + last * count;
+                i = close + 1;
+                last = 0;
+                continue;
+            }
+            b'(' => {
+                end = group_end(b, i)? + 1;
+                group_width(&b[i + 1..end - 1])?
+            }
+            b'^' | b'$' => 0,
+            b'\\' => match b.get(i + 1) {
+                Some(b'b' | b'B' | b'A' | b'z') => 0,
+                Some(b'1'..=b'9' | b'k') => return None,
+                _ => 1,
+            },
+            // UTF-8 continuation bytes belong to the previous character
+            c if c & 0xC0 == 0x80 => 0,
+            _ => 1,
+        };
+        width += w;
+        last = w;
+        i = end;
+    }
+    Some(width)
+}
+
+/// Width of a group, given the bytes between its parentheses.
+fn group_width(inner: &[u8]) -> Option<usize> {
+    let body = match inner {
+        [b'?', b'=' | b'!', ..] | [b'?', b'<', b'=' | b'!', ..] => return Some(0),
+        [b'?', b'P', b'<', ..] => &inner[inner.iter().position(|&c| c == b'>')? + 1..],
+        [b'?', rest @ ..] => {
+            // Inline flags: `(?i)` or `(?i:…)`
+            let flags = rest
+                .iter()
+                .position(|c| !(c.is_ascii_alphabetic() || *c == b'-'))
+                .unwrap_or(rest.len());
+            if rest.get(flags) != Some(&b':') {
+                return Some(0);
+            }
+            &rest[flags + 1..]
+        }
+        _ => inner,
+    };
+    let mut widths = split_alternatives(body).into_iter().map(fixed_width);
+    let first = widths.next()??;
+    widths.all(|w| w == Some(first)).then_some(first)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,8 +649,9 @@ mod tests {
 
     #[test]
     fn pcre_anchors_preserved_in_class() {
-        // \A and \Z inside character classes should NOT be converted
-        assert_eq!(normalize_ecma_regex(r"[\A\Z]"), r"[\A\Z]");
+        // \A and \Z inside character classes are not anchors: ECMA 262 reads
+        // them as the letters A and Z
+        assert_eq!(normalize_ecma_regex(r"[\A\Z]"), r"[AZ]");
     }
 
     #[test]
@@ -335,6 +686,12 @@ mod tests {
             r"^(\p{L}|_)(\p{L}|\p{N}|[.\-_])*$",
             // PCRE anchors
             r"\A[0-9a-zA-Z-_]+\Z",
+            // ECMA class semantics and escapes
+            r"^[^]*$",
+            r"[]|a",
+            r"[\b\0\12&&+--~~]",
+            r"^\cJ\e\<\u00e9\uD83D\uDE00$",
+            r"^(?<year>\d{4})-(?<month>\d{2})$",
         ];
         // Note: patterns with look-ahead (?!...) or (?=...) are not tested here
         // because regex_syntax doesn't support look-around. The jsonschema crate
@@ -349,4 +706,87 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn empty_and_negated_empty_classes() {
+        assert_eq!(normalize_ecma_regex(r"^[^]*$"), r"^[\s\S]*$");
+        assert_eq!(normalize_ecma_regex(r"a[]"), r"a[^\s\S]");
+        assert_eq!(normalize_ecma_regex(r"[\s\S]"), r"[\s\S]");
+    }
+
+    #[test]
+    fn class_escapes_and_set_operators() {
+        assert_eq!(normalize_ecma_regex(r"[\b]"), r"[\x08]");
+        assert_eq!(normalize_ecma_regex(r"[\0\12\377\8]"), r"[\x00\x0A\xFF8]");
+        assert_eq!(normalize_ecma_regex(r"[a&&b]"), r"[a&\&b]");
+        assert_eq!(normalize_ecma_regex(r"[+--]"), r"[+-\-]");
+        assert_eq!(normalize_ecma_regex(r"[~~]"), r"[~\~]");
+    }
+
+    #[test]
+    fn control_and_identity_escapes() {
+        assert_eq!(normalize_ecma_regex(r"\cJ\cm"), r"\x0A\x0D");
+        assert_eq!(normalize_ecma_regex(r"\c1"), r"\\c1");
+        assert_eq!(normalize_ecma_regex(r"\e\<\>\_"), r"e<>\_");
+        assert_eq!(normalize_ecma_regex("\\é"), "é");
+        assert_eq!(normalize_ecma_regex(r"a\0b"), r"a\x00b");
+        assert_eq!(normalize_ecma_regex(r"(a)\1"), r"(a)\1");
+    }
+
+    #[test]
+    fn surrogate_pairs_joined() {
+        let t = translate_ecma_regex(r"^\uD83D\uDE00+é$");
+        assert_eq!(t.pattern, r"^\u{1F600}+é$");
+        assert!(t.warnings.is_empty());
+    }
+
+    #[test]
+    fn lone_surrogates_warn() {
+        let t = translate_ecma_regex(r"\uD83D");
+        assert_eq!(t.pattern, r"[^\s\S]");
+        assert_eq!(t.warnings.len(), 1);
+
+        let t = translate_ecma_regex(r"[a\uD800-\uDBFFb]");
+        assert_eq!(t.pattern, r"[ab]");
+        assert_eq!(t.warnings.len(), 1);
+    }
+
+    #[test]
+    fn named_groups_converted() {
+        let t = translate_ecma_regex(r"(?<year>\d{4})-\k<year>");
+        assert_eq!(t.pattern, r"(?P<year>\d{4})-\k<year>");
+        assert!(t.warnings.is_empty());
+
+        let t = translate_ecma_regex(r"(?<a$b>x)");
+        assert_eq!(t.pattern, r"(x)");
+        assert_eq!(t.warnings.len(), 1);
+    }
+
+    #[test]
+    fn lookaround_preserved() {
+        for pat in [r"^(?!-)[a-z-]+(?<!-)$", r"(?=\w{3})\w+", r"(?<=\$)\d{2}"] {
+            let t = translate_ecma_regex(pat);
+            assert_eq!(t.pattern, pat);
+            assert!(t.warnings.is_empty(), "{pat}: {:?}", t.warnings);
+        }
+    }
+
+    #[test]
+    fn lookbehind_alternatives_of_different_lengths_accepted() {
+        for pat in [r"(?<=a|bc)x", r"(?<!\d|[a-z]{2}|(?:ab|cd)e)x"] {
+            assert!(translate_ecma_regex(pat).warnings.is_empty(), "{pat}");
+        }
+    }
+
+    #[test]
+    fn variable_lookbehind_warns() {
+        let t = translate_ecma_regex(r"(?<=a+)b");
+        assert_eq!(t.pattern, r"(?<=a+)b");
+        assert_eq!(
+            t.warnings,
+            ["lookbehind `(?<=a+)` has no fixed length, which Rust regex engines require"]
+        );
+        let t = translate_ecma_regex(r"(?<!https?://|www\.)x");
+        assert_eq!(t.warnings.len(), 1);
+    }
 }
@@ -7,6 +7,7 @@ use anyhow::Result;
 use bpaf::{Bpaf, ShellComp};
 
 use lintel_cli_common::CliCacheOptions;
+use lintel_config::MappingPriority;
 use lintel_schema_cache::SchemaCache;
 use lintel_validate::parsers;
 use lintel_validate::validate;
@@ -101,15 +102,21 @@ fn process_file(
         return FileOutcome::Skipped;
     }
 
-    let schema_url = config
-        .find_schema_mapping(&path_str, file_name)
-        .map(str::to_string)
-        .or_else(|| {
-            catalogs
-                .iter()
-                .find_map(|cat| cat.find_schema(&path_str, file_name))
-                .map(str::to_string)
-        });
+    // A low-priority mapping only applies when no catalog matches.
+    let mapping = config.find_schema_mapping_entry(&path_str, file_name);
+    let is_low = mapping.is_some_and(|(_, m)| m.priority() == MappingPriority::Low);
+    let mapped = || mapping.map(|(_, m)| m.url().to_string());
+    let from_catalog = || {
+        catalogs
+            .iter()
+            .find_map(|cat| cat.find_schema(&path_str, file_name))
+            .map(str::to_string)
+    };
+    let schema_url = if is_low {
+        from_catalog().or_else(mapped)
+    } else {
+        mapped().or_else(from_catalog)
+    };
 
     let Some(schema_url) = schema_url else {
         return FileOutcome::Skipped;
@@ -167,6 +174,8 @@ pub async fn run(args: &AnnotateArgs) -> Result<AnnotateResult> {
         .find(|g| Path::new(g).is_dir())
         .map(PathBuf::from);
 
+    let (mut config, config_root, _) = validate::load_config(config_dir.as_deref());
+
     let mut builder = SchemaCache::builder();
     if let Some(dir) = &args.cache.cache_dir {
         builder = builder.cache_dir(PathBuf::from(dir));
@@ -174,16 +183,31 @@ pub async fn run(args: &AnnotateArgs) -> Result<AnnotateResult> {
     if let Some(ttl) = args.cache.schema_cache_ttl {
         builder = builder.ttl(ttl);
     }
+    if let Some(max_size) = config.cache_max_size {
+        builder = builder.max_size(max_size);
+    }
+    if let Some(timeout) = args.cache.fetch_timeout.or(config.network.timeout) {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(retries) = args.cache.fetch_retries.or(config.network.retries) {
+        builder = builder.retries(retries);
+    }
+    if let Some(backoff) = args
+        .cache
+        .fetch_retry_backoff
+        .or(config.network.retry_backoff)
+    {
+        builder = builder.retry_backoff(backoff);
+    }
     let retriever = builder.build();
-
-    let (mut config, _, _) = validate::load_config(config_dir.as_deref());
     config.exclude.extend(args.exclude.clone());
 
     let files = validate::collect_files(&args.globs, &config.exclude)?;
     tracing::info!(file_count = files.len(), "collected files");
 
     let catalogs =
-        validate::fetch_compiled_catalogs(&retriever, &config, args.cache.no_catalog).await;
+        validate::fetch_compiled_catalogs(&retriever, &config, &config_root, args.cache.no_catalog)
+            .await;
 
     let mut result = AnnotateResult {
         annotated: Vec::new(),
@@ -157,6 +157,10 @@ fn parsers_from_file_match(patterns: &[String]) -> Vec<FileFormat> {
                 ".yaml" | ".yml" => Some(FileFormat::Yaml),
                 ".toml" => Some(FileFormat::Toml),
                 ".md" | ".mdx" => Some(FileFormat::Markdown),
+                ".xml" => Some(FileFormat::Xml),
+                ".tf" | ".hcl" => Some(FileFormat::Hcl),
+                ".jsonnet" => Some(FileFormat::Jsonnet),
+                ".cue" => Some(FileFormat::Cue),
                 _ => None,
             };
             if let Some(f) = format {
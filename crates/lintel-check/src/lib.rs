@@ -5,7 +5,9 @@ use std::time::Instant;
 use anyhow::Result;
 use bpaf::Bpaf;
 
-use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter};
+use lintel_diagnostics::reporter::{
+    CheckResult, CheckedFile, ExitStatus, Reporter, format_summary, format_unknown_files,
+};
 
 // -----------------------------------------------------------------------
 // CheckArgs — CLI struct for the `lintel check` command
@@ -39,6 +41,16 @@ pub async fn check(
     args: &mut CheckArgs,
     on_file_checked: impl FnMut(&CheckedFile),
 ) -> Result<CheckResult> {
+    if args.fix && args.validate.stdin {
+        anyhow::bail!("--fix cannot be used with --stdin");
+    }
+    if args.fix && args.validate.since.is_some() {
+        anyhow::bail!("--fix cannot be used with --since");
+    }
+    if args.validate.compose_merge {
+        anyhow::bail!("--compose-merge is only supported by `lintel validate` and `lintel ci`");
+    }
+
     // Save original args before validate's merge_config modifies them.
     let original_globs = args.validate.globs.clone();
     let original_exclude = args.validate.exclude.clone();
@@ -48,9 +60,14 @@ pub async fn check(
     let lib_args = lintel_validate::validate::ValidateArgs::from(&args.validate);
 
     // Collect and read files once.
-    let files = lintel_validate::validate::collect_files(&lib_args.globs, &lib_args.exclude)?;
     let mut read_errors = Vec::new();
-    let file_contents = lintel_validate::validate::read_files(&files, &mut read_errors).await;
+    let file_contents = if let Some(document) = lintel_validate::read_stdin(&args.validate)? {
+        vec![document]
+    } else if let Some(files) = lintel_validate::changed_files(&args.validate, &lib_args)? {
+        lintel_validate::validate::read_files(&files, &mut read_errors).await
+    } else {
+        lintel_validate::validate::read_documents(&lib_args, &mut read_errors).await?
+    };
 
     if args.fix {
         let fixed = lintel_format::fix_format(&original_globs, &original_exclude)?;
@@ -70,11 +87,16 @@ pub async fn check(
         Ok(result)
     } else {
         // Check formatting using pre-read contents (borrows, no extra I/O).
-        let format_errors = lintel_format::check_format_contents(
+        let mut format_errors = lintel_format::check_format_contents(
             &file_contents,
             &original_globs,
             &original_exclude,
         );
+        // Documents downloaded from URLs or unpacked from archives cannot be
+        // reformatted, so only files on disk (or stdin) get format errors.
+        if !args.validate.stdin {
+            format_errors.retain(|e| std::path::Path::new(e.path()).is_file());
+        }
 
         // Run validation (takes ownership of file contents).
         let mut result = lintel_validate::validate::run_with_contents(
@@ -99,18 +121,31 @@ pub async fn check(
 /// Discovers files once and shares the file list between validation and
 /// formatting. Config is loaded once.
 ///
-/// Returns `Ok(true)` if any errors were found, `Ok(false)` if clean.
+/// Returns the [`ExitStatus`] for the worst diagnostic.
 ///
 /// # Errors
 ///
 /// Returns an error if schema validation fails to run (e.g. network or I/O issues).
-pub async fn run(args: &mut CheckArgs, reporter: &mut dyn Reporter) -> Result<bool> {
+pub async fn run(args: &mut CheckArgs, reporter: &mut dyn Reporter) -> Result<ExitStatus> {
     let start = Instant::now();
     let result = check(args, |file| reporter.on_file_checked(file)).await?;
-    let had_errors = result.has_errors();
+    let status = result.exit_status(args.validate.error_on_warnings);
     let elapsed = start.elapsed();
+    let summary = args.validate.summary.then(|| format_summary(&result));
+    let unknown_files = args
+        .validate
+        .unknown_files
+        .then(|| format_unknown_files(&result));
+    let outputs = lintel_validate::run_outputs(&args.validate, &result, elapsed, status);
     reporter.report(result, elapsed);
-    Ok(had_errors)
+    if let Some(summary) = summary {
+        eprint!("{summary}");
+    }
+    if let Some(unknown_files) = unknown_files {
+        eprint!("{unknown_files}");
+    }
+    lintel_validate::write_outputs(outputs)?;
+    Ok(status)
 }
 
 fn sort_errors(errors: &mut [lintel_diagnostics::LintelDiagnostic]) {
@@ -20,6 +20,10 @@ pub struct CLIGlobalOptions {
     #[bpaf(short('v'), long("verbose"), switch, fallback(false))]
     pub verbose: bool,
 
+    /// Only print errors: no warnings, summary line or progress.
+    #[bpaf(short('q'), long("quiet"), switch, fallback(false))]
+    pub quiet: bool,
+
     /// The level of logging. In order, from the most verbose to the least verbose:
     /// debug, info, warn, error.
     #[bpaf(
@@ -90,8 +94,14 @@ impl core::fmt::Display for LogLevel {
 // Shared cache options
 // ---------------------------------------------------------------------------
 
+/// Parse a human-readable duration such as `"30s"` or `"12h"`, for bpaf's
+/// `parse()`.
+///
+/// # Errors
+///
+/// Returns an error message if `s` is not a valid duration.
 #[allow(clippy::needless_pass_by_value)] // bpaf parse() requires owned String
-fn parse_duration(s: String) -> Result<Duration, String> {
+pub fn parse_duration(s: String) -> Result<Duration, String> {
     humantime::parse_duration(&s).map_err(|e| format!("invalid duration '{s}': {e}"))
 }
 
@@ -121,6 +131,18 @@ pub struct CliCacheOptions {
 
     #[bpaf(long("no-catalog"), switch)]
     pub no_catalog: bool,
+
+    /// Time limit for each schema download (e.g. "30s", "2m"); default 30s
+    #[bpaf(long("fetch-timeout"), argument::<String>("DURATION"), parse(parse_duration), optional)]
+    pub fetch_timeout: Option<Duration>,
+
+    /// Retries for schema downloads that time out or fail transiently; default 2
+    #[bpaf(long("fetch-retries"), argument("N"), optional)]
+    pub fetch_retries: Option<u32>,
+
+    /// Delay before the first retry, doubled for each further one; default 500ms
+    #[bpaf(long("fetch-retry-backoff"), argument::<String>("DURATION"), parse(parse_duration), optional)]
+    pub fetch_retry_backoff: Option<Duration>,
 }
 
 impl CLIGlobalOptions {
@@ -287,6 +309,9 @@ mod tests {
         assert!(!parsed.force_validation);
         assert!(!parsed.force);
         assert!(!parsed.no_catalog);
+        assert!(parsed.fetch_timeout.is_none());
+        assert!(parsed.fetch_retries.is_none());
+        assert!(parsed.fetch_retry_backoff.is_none());
     }
 
     #[test]
@@ -341,6 +366,24 @@ mod tests {
         assert!(parsed.no_catalog);
     }
 
+    #[test]
+    fn fetch_policy_flags() {
+        let parsed = cache_opts()
+            .run_inner(&[
+                "--fetch-timeout",
+                "10s",
+                "--fetch-retries",
+                "4",
+                "--fetch-retry-backoff",
+                "250ms",
+            ])
+            .unwrap();
+        assert_eq!(parsed.fetch_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(parsed.fetch_retries, Some(4));
+        assert_eq!(parsed.fetch_retry_backoff, Some(Duration::from_millis(250)));
+        assert!(cache_opts().run_inner(&["--fetch-retries", "-1"]).is_err());
+    }
+
     #[test]
     fn cache_combined_flags() {
         let parsed = cache_opts()
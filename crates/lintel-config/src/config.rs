@@ -1,3 +1,4 @@
+use core::time::Duration;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -24,6 +25,34 @@ fn example_schema_glob() -> Vec<String> {
     vec!["https://json.schemastore.org/*.json".into()]
 }
 
+fn example_ignore_keywords() -> Vec<String> {
+    vec!["additionalProperties".into(), "format".into()]
+}
+
+fn example_messages() -> HashMap<String, String> {
+    HashMap::from([
+        (
+            "pattern".into(),
+            "use lowercase letters and dashes, like `my-service`".into(),
+        ),
+        (
+            "/properties/image/pattern".into(),
+            "pin images by digest: {message}".into(),
+        ),
+    ])
+}
+
+fn example_extract() -> String {
+    "$.spec.template".into()
+}
+
+fn example_extends() -> Vec<String> {
+    vec![
+        "github:my-org/lintel-config/lintel.toml".into(),
+        "./shared/lintel-base.toml".into(),
+    ]
+}
+
 fn example_exclude() -> Vec<String> {
     vec![
         "vendor/**".into(),
@@ -51,6 +80,268 @@ pub struct Format {
     /// apply to all formatters. Per-plugin sections (`json`, `toml`,
     /// `markdown`) override the global defaults for that plugin.
     pub dprint: Option<dprint_config::DprintConfig>,
+
+    /// How `lintel format` lays out tables in TOML files.
+    ///
+    /// `preserve` keeps inline tables, arrays and table headers as written.
+    /// `auto` uses the file's JSON Schema to choose: arrays of objects become
+    /// `[[table]]` entries and deeply nested single-key tables become dotted
+    /// keys.
+    #[schemars(title = "TOML Style")]
+    #[serde(default)]
+    pub toml_style: TomlStyle,
+}
+
+/// Table layout strategy for TOML files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TomlStyle {
+    /// Keep the layout as written.
+    #[default]
+    Preserve,
+    /// Choose inline tables, `[[table]]` entries or dotted keys from the
+    /// file's schema.
+    Auto,
+}
+
+/// Network settings for downloading schemas and catalogs.
+///
+/// Command-line flags (`--fetch-timeout`, `--fetch-retries`,
+/// `--fetch-retry-backoff`) take priority over these values.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[schemars(title = "Network")]
+pub struct Network {
+    /// Time limit for a single request, e.g. `"30s"` or `"2m"`. Defaults to
+    /// 30 seconds.
+    #[schemars(title = "Timeout", with = "Option<String>")]
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub timeout: Option<Duration>,
+
+    /// How many times a request that timed out, could not connect, or got a
+    /// 5xx or 429 response is retried. Defaults to 2.
+    #[schemars(title = "Retries")]
+    #[serde(default)]
+    pub retries: Option<u32>,
+
+    /// Delay before the first retry, doubled for every further retry, e.g.
+    /// `"500ms"`. Defaults to 500 milliseconds.
+    #[schemars(title = "Retry Backoff", with = "Option<String>")]
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub retry_backoff: Option<Duration>,
+}
+
+/// Resolve schemas for Kubernetes manifests from their `apiVersion` and
+/// `kind`.
+///
+/// Built-in resources use the `kubernetes-json-schema` layout
+/// (`<base-url>/<version>-standalone-strict/deployment-apps-v1.json`); custom
+/// resources use the `crds` URL templates. This applies after inline
+/// `$schema` and `[schemas]` mappings, before catalogs.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[schemars(title = "Kubernetes")]
+pub struct Kubernetes {
+    /// Look up schemas by `apiVersion` and `kind`. Defaults to `false`.
+    #[schemars(title = "Enabled")]
+    #[serde(default)]
+    pub enabled: Option<bool>,
+
+    /// Kubernetes version whose schemas are used, e.g. `"1.31.0"`. Defaults
+    /// to `master`.
+    #[schemars(title = "Version")]
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// Base URL of the built-in resource schemas. Defaults to
+    /// `https://raw.githubusercontent.com/yannh/kubernetes-json-schema/master`.
+    #[schemars(title = "Base URL")]
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Schema URL templates for custom resources, keyed by a glob matched
+    /// against the API group. `{group}`, `{kind}` (lowercase) and
+    /// `{version}` are substituted; the longest matching pattern wins.
+    /// Defaults to the datreeio `CRDs-catalog` for every group.
+    ///
+    /// Example:
+    /// ```toml
+    /// [kubernetes.crds]
+    /// "*.example.com" = "https://schemas.example.com/{group}/{kind}_{version}.json"
+    /// ```
+    #[schemars(title = "CRD Catalogs")]
+    #[serde(default)]
+    pub crds: HashMap<String, String>,
+}
+
+/// Checks for GitHub Actions workflows and actions beyond their schemas.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[schemars(title = "GitHub Actions")]
+pub struct GithubActions {
+    /// Check `${{ }}` expressions and `if:` conditions for unknown contexts
+    /// and function names. Defaults to `false`.
+    #[schemars(title = "Expressions")]
+    #[serde(default)]
+    pub expressions: Option<bool>,
+}
+
+/// An external program or WebAssembly module that resolves schemas or
+/// validates documents.
+///
+/// Lintel runs the plugin once for each hook it enables, with a JSON request
+/// listing the files matching `files`, and reads a JSON response. A command
+/// reads the request on stdin and writes the response to stdout.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(title = "Plugin")]
+pub struct Plugin {
+    /// The program and its arguments. A relative program path such as
+    /// `./tools/crd-schemas` is resolved from the directory of `lintel.toml`.
+    #[schemars(title = "Command")]
+    #[serde(default)]
+    pub command: Vec<String>,
+
+    /// A WebAssembly module to run instead of a command, relative to the
+    /// directory of `lintel.toml`. The module runs sandboxed, without access
+    /// to files, the network or the environment. Needs a Lintel built with
+    /// the `wasm-plugins` feature.
+    #[schemars(title = "WebAssembly Module")]
+    #[serde(default)]
+    pub wasm: Option<String>,
+
+    /// Globs of the files sent to the plugin. Defaults to every file.
+    #[schemars(title = "Files")]
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    /// Ask the plugin for the schema of each file. Its answer is used after
+    /// inline `$schema` and `[schemas]` mappings, before everything else.
+    #[schemars(title = "Resolve")]
+    #[serde(default)]
+    pub resolve: bool,
+
+    /// Send each validated file, with its schema, to the plugin for checks
+    /// of its own.
+    #[schemars(title = "Validate")]
+    #[serde(default)]
+    pub validate: bool,
+    /// Time limit for each run of the plugin, e.g. `"30s"` or `"2m"`. A
+    /// plugin still running then is stopped and fails the run; a `wasm`
+    /// module also gets a fuel budget in proportion to it. Defaults to 60
+    /// seconds.
+    #[schemars(title = "Timeout", with = "Option<String>")]
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub timeout: Option<Duration>,
+}
+
+impl Plugin {
+    /// Whether `path` is sent to the plugin.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.files.is_empty()
+            || self
+                .files
+                .iter()
+                .any(|pattern| glob_matcher::glob_match(pattern, path))
+    }
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    humantime::parse_duration(&s)
+        .map(Some)
+        .map_err(|e| serde::de::Error::custom(format!("invalid duration '{s}': {e}")))
+}
+
+fn deserialize_size<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_size(&s)
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid size '{s}'")))
+}
+
+/// Parse a size such as `"500MB"`, `"2GiB"` or `"1024"` into bytes. Units
+/// are case-insensitive; `KB`, `MB` and `GB` are powers of 1000 and `KiB`,
+/// `MiB` and `GiB` powers of 1024.
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+fn deserialize_extract<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    jsonpath_to_pointer(&s)
+        .map_err(|e| serde::de::Error::custom(format!("invalid extract path '{s}': {e}")))?;
+    Ok(Some(s))
+}
+
+/// Convert a plain JSONPath (`$.spec.containers[0]['app.kubernetes.io/name']`)
+/// to a JSON pointer (`/spec/containers/0/app.kubernetes.io~1name`).
+///
+/// # Errors
+///
+/// Returns an error for malformed paths and for wildcards, recursive descent
+/// and filters, which select more than one fragment.
+pub fn jsonpath_to_pointer(expr: &str) -> Result<String, String> {
+    let mut rest = expr.trim().strip_prefix('$').ok_or("must start with `$`")?;
+    let mut pointer = String::new();
+    let mut push = |segment: &str| {
+        pointer.push('/');
+        pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+    };
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            if after.starts_with(['.', '*']) {
+                return Err("wildcards and recursive descent are not supported".into());
+            }
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err("empty name after `.`".into());
+            }
+            push(&after[..end]);
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or("unclosed `[`")?;
+            let inner = after[..end].trim();
+            let quoted = inner
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+            match quoted {
+                Some(name) => push(name),
+                None if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_digit()) => {
+                    push(inner);
+                }
+                None => return Err(format!("unsupported selector `[{inner}]`")),
+            }
+            rest = &after[end + 1..];
+        } else {
+            return Err(format!("unexpected `{rest}`"));
+        }
+    }
+    Ok(pointer)
 }
 
 /// Conditional settings applied to files or schemas matching specific patterns.
@@ -105,6 +396,156 @@ pub struct Override {
     #[schemars(title = "Validate Formats")]
     #[serde(default)]
     pub validate_formats: Option<bool>,
+
+    /// Severity of schema validation errors in matching files.
+    ///
+    /// `error` (the default) fails the run. `warning` still reports the
+    /// errors but does not fail the run, which is useful for legacy files
+    /// that cannot be fixed yet. `off` hides them entirely. When omitted,
+    /// the next matching override (or `error`) applies.
+    #[schemars(title = "Severity")]
+    #[serde(default)]
+    pub severity: Option<Severity>,
+
+    /// Schema keywords that are not checked in matching files.
+    ///
+    /// Matching files are validated as if the listed keywords (e.g.
+    /// `additionalProperties`, `format` or `required`) were absent from the
+    /// schema, including inside `anyOf` and `oneOf` branches. Plugin
+    /// diagnostics are ignored when their rule is listed. Keywords from
+    /// every matching override are combined.
+    #[schemars(
+        title = "Ignore Keywords",
+        example = example_ignore_keywords(),
+    )]
+    #[serde(default)]
+    pub ignore_keywords: Vec<String>,
+
+    /// Custom error messages for matching files, keyed by schema keyword
+    /// (`pattern`) or by the schema path of one rule
+    /// (`/properties/image/pattern`).
+    ///
+    /// The message replaces the one lintel would print. `{message}` in it is
+    /// replaced by that message and `{instance_path}` by the JSON pointer of
+    /// the failing value. A schema path beats a keyword, and the first
+    /// matching override with a message for an error wins.
+    #[schemars(title = "Error Messages", example = example_messages())]
+    #[serde(default)]
+    pub messages: HashMap<String, String>,
+
+    /// JSONPath of the fragment of matching files to validate, instead of the
+    /// whole document.
+    ///
+    /// Only plain paths are supported: `$`, then `.name`, `['name']` or
+    /// `[0]` steps. Files that do not contain the fragment are skipped. When
+    /// omitted, the next matching override (or the whole document) applies.
+    #[schemars(title = "Extract", example = example_extract())]
+    #[serde(default, deserialize_with = "deserialize_extract")]
+    pub extract: Option<String>,
+
+    /// Evaluation priority relative to other overrides.
+    ///
+    /// Overrides with a higher priority are consulted first, so their
+    /// `validate_formats` and `severity` win over lower-priority overrides
+    /// regardless of where they are declared (including in configs pulled in
+    /// through `extends`). Overrides with equal priority keep their declared
+    /// order. Defaults to `0`.
+    #[schemars(title = "Priority")]
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl Override {
+    /// Whether this override matches every file and schema `later` matches
+    /// and already decides every option `later` sets.
+    fn shadows(&self, later: &Override) -> bool {
+        let covers = |ours: &[String], theirs: &[String]| {
+            ours.iter().any(|p| p == "**" || p == "*") || theirs.iter().all(|p| ours.contains(p))
+        };
+        covers(&self.files, &later.files)
+            && covers(&self.schemas, &later.schemas)
+            && (later.validate_formats.is_none() || self.validate_formats.is_some())
+            && (later.severity.is_none() || self.severity.is_some())
+            && (later.extract.is_none() || self.extract.is_some())
+            && later
+                .ignore_keywords
+                .iter()
+                .all(|k| self.ignore_keywords.contains(k))
+            && later.messages.keys().all(|k| self.messages.contains_key(k))
+    }
+}
+
+/// A `[schemas]` entry: a schema URL, or a table with the URL and its
+/// priority.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum SchemaMapping {
+    /// Schema URL (or `//`-prefixed local path).
+    Url(String),
+    /// Schema URL with an explicit priority.
+    Detailed(DetailedSchemaMapping),
+}
+
+/// The table form of a `[schemas]` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DetailedSchemaMapping {
+    /// Schema URL (or `//`-prefixed local path).
+    pub url: String,
+
+    /// Where this mapping ranks against inline `$schema` declarations and
+    /// catalog matches.
+    #[serde(default)]
+    pub priority: MappingPriority,
+}
+
+impl SchemaMapping {
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Url(url) => url,
+            Self::Detailed(d) => &d.url,
+        }
+    }
+
+    pub fn priority(&self) -> MappingPriority {
+        match self {
+            Self::Url(_) => MappingPriority::default(),
+            Self::Detailed(d) => d.priority,
+        }
+    }
+}
+
+impl From<&str> for SchemaMapping {
+    fn from(url: &str) -> Self {
+        Self::Url(url.to_string())
+    }
+}
+
+/// Where a `[schemas]` mapping ranks during schema resolution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingPriority {
+    /// Beats inline `$schema` declarations and YAML modelines, for files
+    /// whose declared schema is wrong and cannot be edited.
+    High,
+    /// Loses to inline declarations, beats catalog matches.
+    #[default]
+    Normal,
+    /// Only used when neither an inline declaration nor a catalog matches.
+    Low,
+}
+
+/// How schema validation errors are reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Report and fail the run.
+    #[default]
+    Error,
+    /// Report without failing the run.
+    Warning,
+    /// Do not report.
+    Off,
 }
 
 /// Configuration file for the Lintel JSON/YAML schema validator.
@@ -129,6 +570,18 @@ pub struct Config {
     #[serde(default)]
     pub root: bool,
 
+    /// Shared configs to inherit settings from.
+    ///
+    /// Each entry is a path relative to this file, an `https://` URL, or a
+    /// `github:org/repo/path/to/lintel.toml` shorthand (append `@ref` to pin
+    /// a branch, tag or commit). Extended configs are merged like parent
+    /// directories: settings in this file take priority, and later entries
+    /// take priority over earlier ones. Remote configs are cached for 12
+    /// hours.
+    #[schemars(title = "Extends", example = example_extends())]
+    #[serde(default)]
+    pub extends: Vec<String>,
+
     /// Glob patterns for files to exclude from validation.
     ///
     /// Matched against file paths relative to the working directory. Standard
@@ -141,19 +594,21 @@ pub struct Config {
     /// Custom schema-to-file mappings.
     ///
     /// Keys are glob patterns matched against file paths; values are schema
-    /// URLs (or `//`-prefixed local paths) to apply. These mappings take
-    /// priority over catalog auto-detection but are overridden by inline
-    /// `$schema` properties and YAML modeline comments.
+    /// URLs (or `//`-prefixed local paths) to apply. By default these
+    /// mappings take priority over catalog auto-detection but are overridden
+    /// by inline `$schema` properties and YAML modeline comments; use the
+    /// table form with a `priority` to change that.
     ///
     /// Example:
     /// ```toml
     /// [schemas]
     /// "config/*.yaml" = "https://json.schemastore.org/github-workflow.json"
     /// "myschema.json" = "//schemas/custom.json"
+    /// "vendor/*.json" = { url = "//schemas/fixed.json", priority = "high" }
     /// ```
     #[schemars(title = "Schema Mappings")]
     #[serde(default)]
-    pub schemas: HashMap<String, String>,
+    pub schemas: HashMap<String, SchemaMapping>,
 
     /// Disable the built-in Lintel catalog.
     ///
@@ -164,6 +619,17 @@ pub struct Config {
     #[serde(default, rename = "no-default-catalog")]
     pub no_default_catalog: bool,
 
+    /// Disable the workspace schema index.
+    ///
+    /// By default, Lintel scans the `*.schema.json` files beneath this
+    /// file's directory and serves schemas and `$ref` targets whose URI
+    /// matches one of their `$id`s from the local file instead of fetching
+    /// the published copy. The scan runs on the first remote schema URI a
+    /// run meets. Set to `true` to skip the scan.
+    #[schemars(title = "No Schema Index")]
+    #[serde(default, rename = "no-schema-index")]
+    pub no_schema_index: bool,
+
     /// Additional schema catalog URLs to fetch alongside `SchemaStore`.
     ///
     /// Each entry should be a URL pointing to a JSON file in `SchemaStore`
@@ -180,7 +646,9 @@ pub struct Config {
     ///
     /// Keys are URI prefixes to match; values are replacement prefixes. The
     /// longest matching prefix wins. Use `//` as a value prefix to reference
-    /// paths relative to the directory containing `lintel.toml`.
+    /// paths relative to the directory containing `lintel.toml`. Rules also
+    /// apply to remote `$ref` targets, so a schema's references can be
+    /// served from local copies (see `lintel vendor`).
     ///
     /// Example:
     /// ```toml
@@ -206,6 +674,110 @@ pub struct Config {
     #[schemars(title = "Format")]
     #[serde(default)]
     pub format: Option<Format>,
+
+    /// Size limit of the schema cache, e.g. `"500MB"` or `"2GiB"`.
+    ///
+    /// Once a download takes the cache past it, the least recently used
+    /// schemas are deleted. Defaults to 500 MB.
+    #[schemars(title = "Cache Max Size", with = "Option<String>")]
+    #[serde(
+        default,
+        rename = "cache-max-size",
+        alias = "cache_max_size",
+        deserialize_with = "deserialize_size"
+    )]
+    pub cache_max_size: Option<u64>,
+
+    /// Timeout and retry settings for schema and catalog downloads.
+    #[schemars(title = "Network")]
+    #[serde(default)]
+    pub network: Network,
+
+    /// Schema resolution for Kubernetes manifests by `apiVersion` and
+    /// `kind`.
+    #[schemars(title = "Kubernetes")]
+    #[serde(default)]
+    pub kubernetes: Kubernetes,
+
+    /// Checks for GitHub Actions workflows and actions beyond their
+    /// schemas.
+    #[schemars(title = "GitHub Actions")]
+    #[serde(default, rename = "github-actions")]
+    pub github_actions: GithubActions,
+
+    /// External programs that resolve schemas or validate documents, by
+    /// name. Plugins run in name order; the first to resolve a file's schema
+    /// wins. Only local config files may declare plugins, not configs
+    /// fetched through `extends`.
+    ///
+    /// Example:
+    /// ```toml
+    /// [plugins.cluster-crds]
+    /// command = ["./tools/crd-schemas", "--context", "prod"]
+    /// files = ["k8s/**/*.yaml"]
+    /// resolve = true
+    /// ```
+    #[schemars(title = "Plugins")]
+    #[serde(default)]
+    pub plugins: HashMap<String, Plugin>,
+
+    /// Severity of each `lintel schema-lint` rule, by rule ID.
+    ///
+    /// `"off"` disables a rule; rules that are not listed use their default
+    /// severity. Only `error` findings fail the run.
+    ///
+    /// Example:
+    /// ```toml
+    /// [schema-lint]
+    /// missing-description = "off"
+    /// unreachable-def = "error"
+    /// ```
+    #[schemars(title = "Schema Lint Rules")]
+    #[serde(default, rename = "schema-lint")]
+    pub schema_lint: HashMap<String, Severity>,
+
+    /// Validators for custom `format` values, by format name.
+    ///
+    /// Each value names a built-in validator: `cron`, `k8s-quantity`,
+    /// `semver` or `semver-range`. Formats are only checked where format
+    /// validation is enabled (see `validate_formats` in overrides).
+    ///
+    /// Example:
+    /// ```toml
+    /// [formats]
+    /// quantity = "k8s-quantity"
+    /// version-range = "semver-range"
+    /// ```
+    #[schemars(title = "Custom Formats")]
+    #[serde(default)]
+    pub formats: HashMap<String, String>,
+
+    /// Custom annotation keywords accepted in every schema.
+    ///
+    /// Keywords listed here are not reported by the `unknown-keyword` rule
+    /// of `lintel schema-lint`, and `lintel explain` shows their values.
+    ///
+    /// Example:
+    /// ```toml
+    /// annotations = ["markdownDescription", "x-kubernetes-int-or-string"]
+    /// ```
+    #[schemars(title = "Custom Annotations")]
+    #[serde(default)]
+    pub annotations: Vec<String>,
+
+    /// Keywords of custom vocabularies, by vocabulary URI.
+    ///
+    /// A vocabulary's keywords are treated like `annotations` in schemas
+    /// whose `$vocabulary` lists its URI.
+    ///
+    /// Example:
+    /// ```toml
+    /// [vocabularies]
+    /// "https://example.com/vocab/kubernetes" = ["x-kubernetes-int-or-string"]
+    /// ```
+    #[schemars(title = "Custom Vocabularies")]
+    #[serde(default)]
+    pub vocabularies: HashMap<String, Vec<String>>,
 }
 
 impl Config {
@@ -213,7 +785,12 @@ impl Config {
     /// - `exclude`: parent entries are appended (child entries come first)
     /// - `schemas`: parent entries are added only if the key is not already present
     /// - `registries`: parent entries are appended (deduped)
-    /// - `rewrite`: parent entries are added only if the key is not already present
+    /// - `annotations`: parent entries are appended (deduped)
+    /// - `rewrite`, `schema-lint`, `formats`, `vocabularies`, `plugins`:
+    ///   parent entries are added only if the key is not already present
+    /// - `cache-max-size`, `network`, `kubernetes`, `github-actions`: parent
+    ///   values fill in settings the child leaves unset (`kubernetes.crds` as
+    ///   a whole)
     /// - `root` is not inherited
     pub(crate) fn merge_parent(&mut self, parent: Config) {
         self.exclude.extend(parent.exclude);
@@ -228,12 +805,62 @@ impl Config {
         for (k, v) in parent.rewrite {
             self.rewrite.entry(k).or_insert(v);
         }
+        for (k, v) in parent.schema_lint {
+            self.schema_lint.entry(k).or_insert(v);
+        }
+        for (k, v) in parent.formats {
+            self.formats.entry(k).or_insert(v);
+        }
+        for keyword in parent.annotations {
+            if !self.annotations.contains(&keyword) {
+                self.annotations.push(keyword);
+            }
+        }
+        for (k, v) in parent.vocabularies {
+            self.vocabularies.entry(k).or_insert(v);
+        }
+        for (k, v) in parent.plugins {
+            self.plugins.entry(k).or_insert(v);
+        }
         // Child overrides come first (higher priority), then parent overrides.
         self.overrides.extend(parent.overrides);
         // Child format takes priority; fall back to parent's.
         if self.format.is_none() {
             self.format = parent.format;
         }
+        self.cache_max_size = self.cache_max_size.or(parent.cache_max_size);
+        self.network.timeout = self.network.timeout.or(parent.network.timeout);
+        self.network.retries = self.network.retries.or(parent.network.retries);
+        self.network.retry_backoff = self.network.retry_backoff.or(parent.network.retry_backoff);
+        let k8s = &mut self.kubernetes;
+        k8s.enabled = k8s.enabled.or(parent.kubernetes.enabled);
+        k8s.version = k8s.version.take().or(parent.kubernetes.version);
+        k8s.base_url = k8s.base_url.take().or(parent.kubernetes.base_url);
+        if k8s.crds.is_empty() {
+            k8s.crds = parent.kubernetes.crds;
+        }
+        self.github_actions.expressions = self
+            .github_actions
+            .expressions
+            .or(parent.github_actions.expressions);
+    }
+
+    /// The custom annotation keywords of a schema whose `$vocabulary` lists
+    /// `vocabularies`: `annotations` plus the keywords of each listed
+    /// vocabulary in `[vocabularies]`.
+    pub fn annotation_keywords<'a>(
+        &'a self,
+        vocabularies: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<&'a str> {
+        let mut keywords: Vec<&str> = self.annotations.iter().map(String::as_str).collect();
+        for uri in vocabularies {
+            for keyword in self.vocabularies.get(uri).into_iter().flatten() {
+                if !keywords.contains(&keyword.as_str()) {
+                    keywords.push(keyword);
+                }
+            }
+        }
+        keywords
     }
 
     /// Find a custom schema mapping for the given file path.
@@ -241,15 +868,25 @@ impl Config {
     /// Matches against the `[schemas]` table using glob patterns.
     /// Returns the schema URL if a match is found.
     pub fn find_schema_mapping(&self, path: &str, file_name: &str) -> Option<&str> {
+        self.find_schema_mapping_entry(path, file_name)
+            .map(|(_, mapping)| mapping.url())
+    }
+
+    /// Like [`find_schema_mapping`](Self::find_schema_mapping), returning
+    /// the matching pattern and the full mapping including its priority.
+    pub fn find_schema_mapping_entry(
+        &self,
+        path: &str,
+        file_name: &str,
+    ) -> Option<(&str, &SchemaMapping)> {
         let path = path.strip_prefix("./").unwrap_or(path);
-        for (pattern, url) in &self.schemas {
-            if glob_matcher::glob_match(pattern, path)
-                || glob_matcher::glob_match(pattern, file_name)
-            {
-                return Some(url);
-            }
-        }
-        None
+        self.schemas
+            .iter()
+            .find(|(pattern, _)| {
+                glob_matcher::glob_match(pattern, path)
+                    || glob_matcher::glob_match(pattern, file_name)
+            })
+            .map(|(pattern, mapping)| (pattern.as_str(), mapping))
     }
 
     /// Check whether format validation should be enabled for a given file.
@@ -261,26 +898,139 @@ impl Config {
     /// Returns `false` if any matching `[[override]]` sets
     /// `validate_formats = false`.  Defaults to `true` when no override matches.
     pub fn should_validate_formats(&self, path: &str, schema_uris: &[&str]) -> bool {
-        let path = path.strip_prefix("./").unwrap_or(path);
-        for ov in &self.overrides {
-            let file_match = !ov.files.is_empty()
-                && ov
-                    .files
-                    .iter()
-                    .any(|pat| glob_matcher::glob_match(pat, path));
-            let schema_match = !ov.schemas.is_empty()
-                && schema_uris.iter().any(|uri| {
-                    ov.schemas
-                        .iter()
-                        .any(|pat| glob_matcher::glob_match(pat, uri))
-                });
-            if (file_match || schema_match)
-                && let Some(val) = ov.validate_formats
-            {
-                return val;
+        self.matching_overrides(path, schema_uris)
+            .find_map(|ov| ov.validate_formats)
+            .unwrap_or(true)
+    }
+
+    /// Severity of validation errors for a given file.
+    ///
+    /// `path` and `schema_uris` are matched the same way as in
+    /// [`should_validate_formats`](Self::should_validate_formats). The first
+    /// matching `[[override]]` that sets `severity` wins; defaults to
+    /// [`Severity::Error`].
+    pub fn severity_for(&self, path: &str, schema_uris: &[&str]) -> Severity {
+        self.matching_overrides(path, schema_uris)
+            .find_map(|ov| ov.severity)
+            .unwrap_or_default()
+    }
+
+    /// Schema keywords to ignore for a given file: the union of
+    /// `ignore_keywords` across all matching `[[override]]` blocks.
+    pub fn ignored_keywords<'a>(
+        &'a self,
+        path: &'a str,
+        schema_uris: &'a [&'a str],
+    ) -> Vec<&'a str> {
+        let mut keywords: Vec<&str> = Vec::new();
+        for keyword in self
+            .matching_overrides(path, schema_uris)
+            .flat_map(|ov| &ov.ignore_keywords)
+        {
+            if !keywords.contains(&keyword.as_str()) {
+                keywords.push(keyword);
             }
         }
-        true
+        keywords
+    }
+
+    /// Custom message for an error from the rule at `schema_path` in a given
+    /// file, from `messages` of the first matching `[[override]]` that has
+    /// one for its schema path or, failing that, its keyword.
+    ///
+    /// `path` and `schema_uris` are matched the same way as in
+    /// [`should_validate_formats`](Self::should_validate_formats).
+    pub fn error_message<'a>(
+        &'a self,
+        path: &'a str,
+        schema_uris: &'a [&'a str],
+        schema_path: &str,
+    ) -> Option<&'a str> {
+        let keyword = schema_path.rsplit('/').next().unwrap_or(schema_path);
+        self.matching_overrides(path, schema_uris)
+            .find_map(|ov| {
+                ov.messages
+                    .get(schema_path)
+                    .or_else(|| ov.messages.get(keyword))
+            })
+            .map(String::as_str)
+    }
+
+    /// JSON pointer of the fragment to validate in a given file, from the
+    /// first matching `[[override]]` that sets `extract`.
+    ///
+    /// `path` and `schema_uris` are matched the same way as in
+    /// [`should_validate_formats`](Self::should_validate_formats).
+    pub fn extract_pointer(&self, path: &str, schema_uris: &[&str]) -> Option<String> {
+        self.matching_overrides(path, schema_uris)
+            .find_map(|ov| ov.extract.as_deref())
+            .and_then(|expr| jsonpath_to_pointer(expr).ok())
+    }
+
+    /// Overrides that can never take effect, with the reason, by index into
+    /// [`overrides`](Self::overrides).
+    ///
+    /// An override is unreachable when it has no `files` or `schemas`
+    /// patterns, sets no options, or is shadowed by an override evaluated
+    /// before it (see [`Override::priority`]) that matches at least the same
+    /// files and schemas and already sets everything it sets.
+    pub fn unreachable_overrides(&self) -> Vec<(usize, String)> {
+        let ordered = self.ordered_overrides();
+        let mut unreachable = Vec::new();
+        for (n, &(i, ov)) in ordered.iter().enumerate() {
+            let reason = if ov.files.is_empty() && ov.schemas.is_empty() {
+                "has no `files` or `schemas` patterns".to_string()
+            } else if ov.validate_formats.is_none()
+                && ov.severity.is_none()
+                && ov.ignore_keywords.is_empty()
+                && ov.messages.is_empty()
+                && ov.extract.is_none()
+            {
+                "sets no options".to_string()
+            } else if let Some(&(j, _)) = ordered[..n].iter().find(|(_, e)| e.shadows(ov)) {
+                format!("is shadowed by override #{}", j + 1)
+            } else {
+                continue;
+            };
+            unreachable.push((i, reason));
+        }
+        unreachable.sort_by_key(|(i, _)| *i);
+        unreachable
+    }
+
+    /// Overrides with their index, highest `priority` first and declaration
+    /// order otherwise.
+    fn ordered_overrides(&self) -> Vec<(usize, &Override)> {
+        let mut ordered: Vec<(usize, &Override)> = self.overrides.iter().enumerate().collect();
+        ordered.sort_by_key(|(_, ov)| core::cmp::Reverse(ov.priority));
+        ordered
+    }
+
+    /// Overrides whose `files` match `path` or whose `schemas` match any of
+    /// `schema_uris`, in priority order.
+    fn matching_overrides<'a>(
+        &'a self,
+        path: &'a str,
+        schema_uris: &'a [&'a str],
+    ) -> impl Iterator<Item = &'a Override> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.ordered_overrides()
+            .into_iter()
+            .map(|(_, ov)| ov)
+            .filter(move |ov| {
+                let file_match = !ov.files.is_empty()
+                    && ov
+                        .files
+                        .iter()
+                        .any(|pat| glob_matcher::glob_match(pat, path));
+                let schema_match = !ov.schemas.is_empty()
+                    && schema_uris.iter().any(|uri| {
+                        ov.schemas
+                            .iter()
+                            .any(|pat| glob_matcher::glob_match(pat, uri))
+                    });
+                file_match || schema_match
+            })
     }
 
     /// Collect files matching the given globs, merging config excludes with CLI excludes.
@@ -1,13 +1,16 @@
 //! Shared file discovery for Lintel.
 //!
 //! Provides directory walking that respects `.gitignore`, exclude glob patterns,
-//! and caller-provided file filters.
+//! and caller-provided file filters, and lists the files git reports as
+//! changed since a revision.
 
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use anyhow::{Context, Result};
 
 /// Walk a directory tree, respecting `.gitignore`, filtering by predicate, applying excludes.
+/// Directories an exclude pattern ending in `/**` covers are not read.
 ///
 /// Hidden files (e.g. `.eslintrc.json`) are included, but the `.git` directory is skipped.
 ///
@@ -19,12 +22,17 @@ pub fn discover_files(
     excludes: &[String],
     filter: impl Fn(&Path) -> bool,
 ) -> Result<Vec<PathBuf>> {
+    let excluded_dirs = exclude_walker(excludes);
     let walker = ignore::WalkBuilder::new(root)
         .hidden(false) // don't skip dotfiles (e.g. .eslintrc.json)
         .git_ignore(true)
         .git_global(true)
         .git_exclude(true)
-        .filter_entry(|entry| entry.file_name() != ".git")
+        .filter_entry(move |entry| {
+            entry.file_name() != ".git"
+                && !(entry.file_type().is_some_and(|t| t.is_dir())
+                    && excluded_dirs.is_excluded_dir(entry.path()))
+        })
         .build();
 
     let mut files = Vec::new();
@@ -48,6 +56,11 @@ pub fn discover_files(
 }
 
 /// Resolve globs/directories into file lists. Empty globs = auto-discover from `"."`.
+/// A glob written `!glob` is an exclude pattern, like an `exclude` entry.
+///
+/// Globs are walked from their literal prefix, skipping directories they
+/// cannot match or an exclude pattern covers, so `src/**/*.json` never
+/// reads `node_modules`.
 ///
 /// # Errors
 ///
@@ -57,40 +70,156 @@ pub fn collect_files(
     excludes: &[String],
     filter: impl Fn(&Path) -> bool,
 ) -> Result<Vec<PathBuf>> {
+    let (globs, excludes) = split_negated(globs, excludes);
+    let excludes = &excludes[..];
     if globs.is_empty() {
         return discover_files(".", excludes, filter);
     }
 
     let mut result = Vec::new();
-    for pattern in globs {
+    let mut walker = exclude_walker(excludes);
+    for pattern in &globs {
         let path = Path::new(pattern);
         if path.is_dir() {
             result.extend(discover_files(pattern, excludes, &filter)?);
         } else {
-            for entry in
-                glob::glob(pattern).with_context(|| format!("invalid glob pattern: {pattern}"))?
-            {
-                let path = entry?;
-                if path.is_file() && !is_excluded(&path, excludes) {
-                    result.push(path);
-                }
-            }
+            // The matcher makes the best of a malformed glob; keep rejecting
+            // them.
+            glob::Pattern::new(pattern)
+                .with_context(|| format!("invalid glob pattern: {pattern}"))?;
+            walker.include(pattern.as_str());
         }
     }
+    for path in walker.walk() {
+        result.push(path?);
+    }
     result.sort();
     result.dedup();
     Ok(result)
 }
 
+/// List the files under `root` that changed since the git revision `since`:
+/// the difference between the merge base of `since` and `HEAD` and the
+/// working tree, so uncommitted edits count, plus untracked files that are
+/// not ignored. Files that no longer exist are left out.
+///
+/// `diff_filter` is passed to `git diff --diff-filter` (e.g. `AM`, or `d` to
+/// leave out deletions); untracked files count as added (`A`).
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run, `root` is not in a git repository,
+/// or `since` is not a known revision.
+pub fn changed_files(root: &str, since: &str, diff_filter: Option<&str>) -> Result<Vec<PathBuf>> {
+    let merge_base = git(root, &["merge-base", since, "HEAD"])?;
+    let mut diff = vec!["diff", "--name-only", "--relative", "-z"];
+    let filter_arg = diff_filter.map(|f| format!("--diff-filter={f}"));
+    diff.extend(filter_arg.as_deref());
+    diff.push(merge_base.trim());
+    let mut names = git(root, &diff)?;
+
+    let untracked_added = diff_filter.is_none_or(|f| {
+        f.contains('A') || (!f.contains('a') && !f.chars().any(|c| c.is_ascii_uppercase()))
+    });
+    if untracked_added {
+        names.push_str(&git(
+            root,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )?);
+    }
+
+    let mut files: Vec<PathBuf> = names
+        .split('\0')
+        .filter(|name| !name.is_empty())
+        .map(|name| Path::new(root).join(name))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Run git in `dir` and return its standard output.
+fn git(dir: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).context("git printed a non-UTF-8 path")
+}
+
+/// Keep the files that match `globs` (a directory matches the files below
+/// it; empty means everything) and `filter`, and no exclude pattern. As for
+/// [`collect_files`], a glob written `!glob` is an exclude pattern.
+///
+/// Use this to narrow a file list that did not come from walking the globs,
+/// such as [`changed_files`].
+pub fn filter_files(
+    files: Vec<PathBuf>,
+    globs: &[String],
+    excludes: &[String],
+    filter: impl Fn(&Path) -> bool,
+) -> Vec<PathBuf> {
+    let (globs, excludes) = split_negated(globs, excludes);
+    let strip = |s: &str| s.strip_prefix("./").unwrap_or(s).to_string();
+    let globs: Vec<String> = globs.iter().map(|g| strip(g)).collect();
+    files
+        .into_iter()
+        .filter(|path| {
+            let relative = path.strip_prefix(".").unwrap_or(path);
+            globs.is_empty()
+                || globs.iter().any(|glob| {
+                    if Path::new(glob).is_dir() {
+                        relative.starts_with(glob)
+                    } else {
+                        glob_matcher::glob_match_path(glob, relative)
+                    }
+                })
+        })
+        .filter(|path| filter(path) && !is_excluded(path, &excludes))
+        .collect()
+}
+
+/// Split the `!glob` entries out of `globs` and add them, without the `!`,
+/// to `excludes`, so `'configs/**' '!configs/generated/**'` on the command
+/// line works like an `exclude` entry. Returns the remaining globs and all
+/// excludes.
+fn split_negated(globs: &[String], excludes: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut included = Vec::new();
+    let mut excludes = excludes.to_vec();
+    for glob in globs {
+        match glob.strip_prefix('!') {
+            Some(negated) => excludes.push(negated.to_string()),
+            None => included.push(glob.clone()),
+        }
+    }
+    (included, excludes)
+}
+
+/// A walker that leaves out `excludes`, to include globs in or to ask which
+/// directories are excluded.
+fn exclude_walker(excludes: &[String]) -> glob_walk::GlobWalker {
+    let mut walker = glob_walk::GlobWalker::new();
+    for exclude in excludes {
+        walker.exclude(exclude.as_str());
+    }
+    walker
+}
+
 /// Check if a path matches any exclude glob pattern.
 pub fn is_excluded(path: &Path, excludes: &[String]) -> bool {
-    let path_str = match path.to_str() {
-        Some(s) => s.strip_prefix("./").unwrap_or(s),
-        None => return false,
-    };
+    let path = path.strip_prefix(".").unwrap_or(path);
     excludes
         .iter()
-        .any(|pattern| glob_matcher::glob_match(pattern, path_str))
+        .any(|pattern| glob_matcher::glob_match_path(pattern, path))
 }
 
 #[cfg(test)]
@@ -173,6 +302,127 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn filter_files_applies_globs_directories_and_excludes() {
+        let files = vec![
+            PathBuf::from("./src/a.json"),
+            PathBuf::from("./src/vendor/b.json"),
+            PathBuf::from("./docs/c.json"),
+            PathBuf::from("./docs/d.txt"),
+        ];
+        let kept = filter_files(files.clone(), &["docs/*.*".to_string()], &[], json_only);
+        assert_eq!(kept, vec![PathBuf::from("./docs/c.json")]);
+
+        let kept = filter_files(files, &[], &["**/vendor/**".to_string()], json_only);
+        assert_eq!(
+            kept,
+            vec![
+                PathBuf::from("./src/a.json"),
+                PathBuf::from("./docs/c.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_files_include_edits_and_untracked_files() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let root = tmp.path().to_str().expect("temp dir should be valid UTF-8");
+        let run = |args: &[&str]| git(root, args).map(drop);
+        run(&["init", "-q"])?;
+        fs::write(tmp.path().join("same.json"), "{}")?;
+        fs::write(tmp.path().join("edited.json"), "{}")?;
+        fs::write(tmp.path().join("deleted.json"), "{}")?;
+        run(&["add", "."])?;
+        run(&[
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "base",
+        ])?;
+
+        fs::write(tmp.path().join("edited.json"), r#"{"a":1}"#)?;
+        fs::remove_file(tmp.path().join("deleted.json"))?;
+        fs::write(tmp.path().join("new.json"), "{}")?;
+
+        let names = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .filter_map(|f| f.file_name()?.to_str().map(String::from))
+                .collect()
+        };
+        assert_eq!(
+            names(changed_files(root, "HEAD", None)?),
+            ["edited.json", "new.json"]
+        );
+        assert_eq!(
+            names(changed_files(root, "HEAD", Some("M"))?),
+            ["edited.json"]
+        );
+        assert!(changed_files(root, "no-such-rev", None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn collect_files_skips_excluded_directories() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let root = tmp.path().to_str().expect("temp dir should be valid UTF-8");
+        let modules = tmp.path().join("web/node_modules/dep");
+        fs::create_dir_all(&modules)?;
+        fs::write(modules.join("package.json"), "{}")?;
+        fs::write(tmp.path().join("web/package.json"), "{}")?;
+        fs::write(tmp.path().join("web/app.yaml"), "a: 1")?;
+
+        let files = collect_files(
+            &[format!("{root}/**/*.{{json,yaml}}")],
+            &["**/node_modules/**".to_string()],
+            always_true,
+        )?;
+        assert_eq!(
+            files,
+            [
+                tmp.path().join("web/app.yaml"),
+                tmp.path().join("web/package.json")
+            ]
+        );
+        assert!(collect_files(&[format!("{root}/[a.json")], &[], always_true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn negated_globs_are_excludes() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let root = tmp.path().to_str().expect("temp dir should be valid UTF-8");
+        fs::create_dir_all(tmp.path().join("configs/generated"))?;
+        fs::write(tmp.path().join("configs/app.yaml"), "a: 1")?;
+        fs::write(tmp.path().join("configs/generated/app.yaml"), "a: 1")?;
+
+        let files = collect_files(
+            &[
+                format!("{root}/configs/**/*.yaml"),
+                format!("!{root}/configs/generated/**"),
+            ],
+            &[],
+            always_true,
+        )?;
+        assert_eq!(files, [tmp.path().join("configs/app.yaml")]);
+
+        let files = vec![
+            PathBuf::from("./configs/app.yaml"),
+            PathBuf::from("./configs/generated/app.yaml"),
+        ];
+        let kept = filter_files(
+            files,
+            &["!configs/generated/**".to_string()],
+            &[],
+            always_true,
+        );
+        assert_eq!(kept, [PathBuf::from("./configs/app.yaml")]);
+        Ok(())
+    }
+
     #[test]
     fn is_excluded_strips_dot_slash() {
         let path = Path::new("./vendor/file.json");
@@ -0,0 +1,394 @@
+//! Loading a `lintel.toml` together with the configs it `extends`.
+//!
+//! Entries are local paths (relative to the extending file), `https://`
+//! URLs, or `github:org/repo/path/to/lintel.toml[@ref]` shorthands. Remote
+//! configs are cached on disk like schemas and refreshed after
+//! [`REMOTE_CACHE_TTL`]; a stale copy is used when the network is down.
+
+use core::time::Duration;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::Config;
+
+/// How long a fetched remote config is used before it is fetched again.
+const REMOTE_CACHE_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// How many `extends` levels are followed before giving up.
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Where a config was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    File(PathBuf),
+    Url(String),
+}
+
+impl core::fmt::Display for Source {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+/// One config file in a merged chain, with the text it was loaded from.
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    /// File path or URL the config was loaded from.
+    pub origin: String,
+    /// The raw TOML content.
+    pub content: String,
+    /// Whether the config was fetched from a URL rather than read from a
+    /// local file.
+    pub remote: bool,
+}
+
+impl ConfigLayer {
+    /// Parse the layer on its own, without merging anything into it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is not a valid `lintel.toml`, or if
+    /// a remote config declares `[plugins]`: those run programs on this
+    /// machine, so only local configs may add them.
+    pub fn parse(&self) -> anyhow::Result<Config> {
+        let config: Config = toml::from_str(&self.content)
+            .map_err(|e| anyhow::anyhow!("failed to parse {}: {e}", self.origin))?;
+        if self.remote && !config.plugins.is_empty() {
+            anyhow::bail!(
+                "{}: [plugins] are only allowed in local config files",
+                self.origin
+            );
+        }
+        Ok(config)
+    }
+}
+
+/// The config at `path` followed by everything it extends, in priority
+/// order, so that merging each layer into the ones before it as a parent
+/// gives the effective config.
+///
+/// # Errors
+///
+/// Returns an error if this file or any config it extends cannot be read,
+/// fetched or parsed as TOML, or if the `extends` chain is cyclic.
+pub(crate) fn load_layers(path: &Path) -> anyhow::Result<Vec<ConfigLayer>> {
+    let mut layers = Vec::new();
+    Loader {
+        cache_dir: ensure_cache_dir(),
+        stack: Vec::new(),
+    }
+    .collect(Source::File(path.to_path_buf()), &mut layers)?;
+    Ok(layers)
+}
+
+struct Loader {
+    cache_dir: PathBuf,
+    /// Configs currently being loaded, to detect cycles.
+    stack: Vec<Source>,
+}
+
+impl Loader {
+    fn collect(&mut self, source: Source, out: &mut Vec<ConfigLayer>) -> anyhow::Result<()> {
+        if self.stack.contains(&source) {
+            anyhow::bail!("cyclic `extends`: {source} extends itself");
+        }
+        if self.stack.len() >= MAX_EXTENDS_DEPTH {
+            anyhow::bail!("`extends` is nested too deeply at {source}");
+        }
+
+        let content = match &source {
+            Source::File(path) => fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", path.display()))?,
+            Source::Url(url) => fetch_cached(url, &self.cache_dir)?,
+        };
+        // Only `extends` is read here; the layer is parsed as a `Config`
+        // later, so a chain with an invalid file can still be listed.
+        let table: toml::Table = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("failed to parse {source}: {e}"))?;
+        let parents: Vec<Source> = table
+            .get("extends")
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+            .map(|entry| resolve(entry, &source))
+            .collect();
+
+        out.push(ConfigLayer {
+            origin: source.to_string(),
+            content,
+            remote: matches!(source, Source::Url(_)),
+        });
+        self.stack.push(source);
+        // Later entries take priority over earlier ones.
+        for parent in parents.into_iter().rev() {
+            self.collect(parent, out)?;
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// Resolve an `extends` entry against the config that lists it.
+fn resolve(entry: &str, base: &Source) -> Source {
+    if let Some(url) = remote_url(entry) {
+        return Source::Url(url);
+    }
+    match base {
+        Source::File(path) => {
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            Source::File(dir.join(entry))
+        }
+        Source::Url(url) => {
+            let dir = url.rfind('/').map_or(url.as_str(), |i| &url[..=i]);
+            Source::Url(format!(
+                "{dir}{}",
+                entry.strip_prefix("./").unwrap_or(entry)
+            ))
+        }
+    }
+}
+
+/// The URL to fetch for a remote `extends` entry, or `None` for a local
+/// path.
+///
+/// - `https://…` / `http://…` → used as-is
+/// - `github:org/repo/path`     → the file on the default branch
+/// - `github:org/repo/path@ref` → the file at a branch, tag or commit
+fn remote_url(entry: &str) -> Option<String> {
+    if entry.starts_with("https://") || entry.starts_with("http://") {
+        return Some(entry.to_string());
+    }
+    let rest = entry.strip_prefix("github:")?;
+    let (rest, git_ref) = rest.rsplit_once('@').unwrap_or((rest, "HEAD"));
+    let mut parts = rest.splitn(3, '/');
+    let (org, repo) = (parts.next()?, parts.next()?);
+    let path = parts.next().unwrap_or("lintel.toml");
+    Some(format!(
+        "https://raw.githubusercontent.com/{org}/{repo}/{git_ref}/{path}"
+    ))
+}
+
+/// Read a remote config from the disk cache, fetching it when the cached
+/// copy is missing or older than [`REMOTE_CACHE_TTL`].
+fn fetch_cached(url: &str, cache_dir: &Path) -> anyhow::Result<String> {
+    let cache_path = cache_dir.join(format!("{}.toml", hash_url(url)));
+    let cached = fs::read_to_string(&cache_path).ok();
+    if let Some(content) = &cached
+        && !is_expired(&cache_path)
+    {
+        return Ok(content.clone());
+    }
+
+    match fetch(url) {
+        Ok(body) => {
+            let _ = fs::write(&cache_path, &body);
+            Ok(body)
+        }
+        Err(e) => match cached {
+            Some(content) => {
+                eprintln!("warning: failed to refresh {url}, using cached copy: {e}");
+                Ok(content)
+            }
+            None => Err(anyhow::anyhow!("failed to fetch {url}: {e}")),
+        },
+    }
+}
+
+/// Fetch `url` with a blocking client. The request runs on its own thread
+/// so config loading also works from inside an async runtime.
+#[cfg(feature = "remote")]
+fn fetch(url: &str) -> anyhow::Result<String> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| -> anyhow::Result<String> {
+                let body = reqwest::blocking::get(url)?.error_for_status()?.text()?;
+                Ok(body)
+            })
+            .join()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("fetch thread panicked")))
+    })
+}
+
+#[cfg(not(feature = "remote"))]
+fn fetch(_url: &str) -> anyhow::Result<String> {
+    anyhow::bail!("remote configs are not supported without the `remote` feature")
+}
+
+fn is_expired(path: &Path) -> bool {
+    fs::metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|mtime| mtime.elapsed().ok())
+        .is_none_or(|age| age > REMOTE_CACHE_TTL)
+}
+
+fn hash_url(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+}
+
+/// Return a usable cache directory for remote configs, creating it if
+/// necessary.
+fn ensure_cache_dir() -> PathBuf {
+    let candidates = [
+        dirs::cache_dir().map(|d| d.join("lintel").join("configs")),
+        Some(std::env::temp_dir().join("lintel").join("configs")),
+    ];
+    for candidate in candidates.into_iter().flatten() {
+        if fs::create_dir_all(&candidate).is_ok() {
+            return candidate;
+        }
+    }
+    std::env::temp_dir().join("lintel").join("configs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers(cache_dir: &Path, path: &Path) -> anyhow::Result<Vec<ConfigLayer>> {
+        let mut layers = Vec::new();
+        Loader {
+            cache_dir: cache_dir.to_path_buf(),
+            stack: Vec::new(),
+        }
+        .collect(Source::File(path.to_path_buf()), &mut layers)?;
+        Ok(layers)
+    }
+
+    fn merge(layers: &[ConfigLayer]) -> anyhow::Result<Config> {
+        let mut merged = Config::default();
+        for layer in layers {
+            merged.merge_parent(layer.parse()?);
+        }
+        Ok(merged)
+    }
+
+    #[test]
+    fn github_shorthand_resolves_to_raw_url() {
+        assert_eq!(
+            remote_url("github:acme/configs/lintel/base.toml").as_deref(),
+            Some("https://raw.githubusercontent.com/acme/configs/HEAD/lintel/base.toml")
+        );
+        assert_eq!(
+            remote_url("github:acme/configs@v2").as_deref(),
+            Some("https://raw.githubusercontent.com/acme/configs/v2/lintel.toml")
+        );
+        assert_eq!(
+            remote_url("https://example.com/lintel.toml").as_deref(),
+            Some("https://example.com/lintel.toml")
+        );
+        assert_eq!(remote_url("./shared/lintel-base.toml"), None);
+    }
+
+    #[test]
+    fn relative_entries_resolve_against_the_extending_config() {
+        assert_eq!(
+            resolve(
+                "base.toml",
+                &Source::File(PathBuf::from("/repo/lintel.toml"))
+            ),
+            Source::File(PathBuf::from("/repo/base.toml"))
+        );
+        assert_eq!(
+            resolve(
+                "./common.toml",
+                &Source::Url("https://example.com/org/lintel.toml".into())
+            ),
+            Source::Url("https://example.com/org/common.toml".into())
+        );
+    }
+
+    #[test]
+    fn extended_configs_merge_below_the_extending_one() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::create_dir(tmp.path().join("shared"))?;
+        fs::write(
+            tmp.path().join("shared/base.toml"),
+            "exclude = [\"base/**\"]\n[schemas]\n\"a.json\" = \"base-a\"\n",
+        )?;
+        fs::write(
+            tmp.path().join("shared/strict.toml"),
+            "extends = [\"base.toml\"]\n[schemas]\n\"b.json\" = \"strict-b\"\n",
+        )?;
+        fs::write(
+            tmp.path().join("shared/extra.toml"),
+            "[schemas]\n\"b.json\" = \"extra-b\"\n\"c.json\" = \"extra-c\"\n",
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "extends = [\"./shared/extra.toml\", \"./shared/strict.toml\"]\nexclude = [\"own/**\"]\n[schemas]\n\"a.json\" = \"own-a\"\n",
+        )?;
+
+        let layers = layers(tmp.path(), &tmp.path().join("lintel.toml"))?;
+        let origins: Vec<&str> = layers
+            .iter()
+            .map(|l| l.origin.rsplit('/').next().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            origins,
+            ["lintel.toml", "strict.toml", "base.toml", "extra.toml"]
+        );
+        let config = merge(&layers)?;
+        assert_eq!(config.exclude, ["own/**", "base/**"]);
+        assert_eq!(config.schemas["a.json"].url(), "own-a");
+        // The later entry wins over the earlier one.
+        assert_eq!(config.schemas["b.json"].url(), "strict-b");
+        assert_eq!(config.schemas["c.json"].url(), "extra-c");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_cyclic_extends() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("a.toml"), "extends = [\"b.toml\"]\n")?;
+        fs::write(tmp.path().join("b.toml"), "extends = [\"a.toml\"]\n")?;
+
+        let err =
+            layers(tmp.path(), &tmp.path().join("a.toml")).expect_err("cycle should be rejected");
+        assert!(err.to_string().contains("cyclic"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn remote_configs_are_served_from_a_fresh_cache() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let url = "https://example.invalid/org/lintel.toml";
+        fs::write(
+            tmp.path().join(format!("{}.toml", hash_url(url))),
+            "registries = [\"https://example.invalid/catalog.json\"]\n",
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            format!("extends = [\"{url}\"]\n"),
+        )?;
+
+        let config = merge(&layers(tmp.path(), &tmp.path().join("lintel.toml"))?)?;
+        assert_eq!(config.registries, ["https://example.invalid/catalog.json"]);
+        Ok(())
+    }
+
+    #[test]
+    fn remote_configs_cannot_add_plugins() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let url = "https://example.invalid/org/lintel.toml";
+        fs::write(
+            tmp.path().join(format!("{}.toml", hash_url(url))),
+            "[plugins.evil]\ncommand = [\"sh\", \"-c\", \"true\"]\nresolve = true\n",
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            format!("extends = [\"{url}\"]\n"),
+        )?;
+
+        let err = merge(&layers(tmp.path(), &tmp.path().join("lintel.toml"))?)
+            .expect_err("a remote config must not add plugins");
+        assert!(err.to_string().contains("only allowed in local"), "{err}");
+        Ok(())
+    }
+}
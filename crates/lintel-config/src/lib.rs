@@ -2,6 +2,9 @@
 
 mod config;
 pub mod discover;
+mod extends;
+pub mod schema_index;
+pub mod sidecar;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -9,7 +12,11 @@ use std::path::{Path, PathBuf};
 use schemars::schema_for;
 use serde_json::Value;
 
-pub use config::{Config, Format, Override};
+pub use config::{
+    Config, DetailedSchemaMapping, Format, GithubActions, Kubernetes, MappingPriority, Network,
+    Override, Plugin, SchemaMapping, Severity, TomlStyle, jsonpath_to_pointer,
+};
+pub use extends::ConfigLayer;
 
 const CONFIG_FILENAME: &str = "lintel.toml";
 
@@ -71,23 +78,47 @@ pub fn find_config_path(start_dir: &Path) -> Option<PathBuf> {
 
 /// Search for `lintel.toml` files starting from `start_dir`, walking up.
 /// Merges all configs found until one with `root = true` is hit (inclusive).
+/// Each file's `extends` entries are merged in right below it.
 /// Returns the merged config, or `None` if no config file was found.
 ///
 /// # Errors
 ///
 /// Returns an error if a config file exists but cannot be read or parsed.
 pub fn find_and_load(start_dir: &Path) -> Result<Option<Config>, anyhow::Error> {
-    let mut configs: Vec<Config> = Vec::new();
+    let layers = find_layers(start_dir)?;
+    let Some((child, parents)) = layers.split_first() else {
+        return Ok(None);
+    };
+
+    let mut merged = child.parse()?;
+    for parent in parents {
+        merged.merge_parent(parent.parse()?);
+    }
+    Ok(Some(merged))
+}
+
+/// Every config file that [`find_and_load`] merges, closest first: each
+/// `lintel.toml` from `start_dir` upward (until `root = true`), each
+/// followed by the configs it extends.
+///
+/// # Errors
+///
+/// Returns an error if a config file cannot be read, fetched or parsed as
+/// TOML.
+pub fn find_layers(start_dir: &Path) -> Result<Vec<ConfigLayer>, anyhow::Error> {
+    let mut layers: Vec<ConfigLayer> = Vec::new();
     let mut dir = start_dir.to_path_buf();
 
     loop {
         let candidate = dir.join(CONFIG_FILENAME);
         if candidate.is_file() {
-            let content = std::fs::read_to_string(&candidate)?;
-            let cfg: Config = toml::from_str(&content)
-                .map_err(|e| anyhow::anyhow!("failed to parse {}: {e}", candidate.display()))?;
-            let is_root = cfg.root;
-            configs.push(cfg);
+            let file_layers = extends::load_layers(&candidate)?;
+            let is_root = file_layers
+                .first()
+                .and_then(|layer| layer.content.parse::<toml::Table>().ok())
+                .and_then(|table| table.get("root").and_then(toml::Value::as_bool))
+                .unwrap_or(false);
+            layers.extend(file_layers);
             if is_root {
                 break;
             }
@@ -96,17 +127,7 @@ pub fn find_and_load(start_dir: &Path) -> Result<Option<Config>, anyhow::Error>
             break;
         }
     }
-
-    if configs.is_empty() {
-        return Ok(None);
-    }
-
-    // configs[0] is the closest (child), last is the farthest (root-most parent)
-    let mut merged = configs.remove(0);
-    for parent in configs {
-        merged.merge_parent(parent);
-    }
-    Ok(Some(merged))
+    Ok(layers)
 }
 
 /// Load config from the current working directory (walking upward).
@@ -525,8 +546,264 @@ files = ["schemas/vector.json"]
         assert!(config.should_validate_formats("schemas/other.json", &[]));
     }
 
+    // --- severity_for ---
+
+    #[test]
+    fn severity_defaults_to_error() {
+        let config = Config::default();
+        assert_eq!(config.severity_for("anything.json", &[]), Severity::Error);
+    }
+
+    #[test]
+    fn severity_from_file_and_schema_overrides() {
+        let config = Config {
+            overrides: vec![
+                Override {
+                    files: vec!["legacy/**".to_string()],
+                    severity: Some(Severity::Warning),
+                    ..Default::default()
+                },
+                Override {
+                    schemas: vec!["https://example.com/strict.json".to_string()],
+                    severity: Some(Severity::Off),
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["**".to_string()],
+                    validate_formats: Some(false),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.severity_for("./legacy/app.yaml", &[]),
+            Severity::Warning
+        );
+        assert_eq!(
+            config.severity_for("app.yaml", &["https://example.com/strict.json"]),
+            Severity::Off
+        );
+        // The third override matches but does not set a severity.
+        assert_eq!(config.severity_for("app.yaml", &[]), Severity::Error);
+    }
+
+    #[test]
+    fn parses_override_severity() -> anyhow::Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[[override]]
+files = ["legacy/**"]
+severity = "warning"
+"#,
+        )?;
+        assert_eq!(config.overrides[0].severity, Some(Severity::Warning));
+        assert!(toml::from_str::<Config>("[[override]]\nseverity = \"fatal\"\n").is_err());
+        Ok(())
+    }
+
+    // --- ignored_keywords ---
+
+    #[test]
+    fn ignored_keywords_combine_matching_overrides() {
+        let config = Config {
+            overrides: vec![
+                Override {
+                    files: vec!["deploy/**".to_string()],
+                    ignore_keywords: vec!["additionalProperties".to_string()],
+                    ..Default::default()
+                },
+                Override {
+                    schemas: vec!["https://json.schemastore.org/*".to_string()],
+                    ignore_keywords: vec!["format".to_string(), "additionalProperties".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.ignored_keywords(
+                "./deploy/app.yaml",
+                &["https://json.schemastore.org/app.json"]
+            ),
+            ["additionalProperties", "format"]
+        );
+        assert_eq!(
+            config.ignored_keywords("deploy/app.yaml", &[]),
+            ["additionalProperties"]
+        );
+        assert!(config.ignored_keywords("src/app.yaml", &[]).is_empty());
+    }
+
+    // --- error_message ---
+
+    #[test]
+    fn error_messages_prefer_schema_paths() -> anyhow::Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[[override]]
+files = ["deploy/**"]
+messages = { "/properties/name/pattern" = "names are kebab-case" }
+
+[[override]]
+files = ["**"]
+messages = { pattern = "does not match: {message}", required = "missing" }
+"#,
+        )?;
+        let message = |path, schema_path| config.error_message(path, &[], schema_path);
+        assert_eq!(
+            message("deploy/app.yaml", "/properties/name/pattern"),
+            Some("names are kebab-case")
+        );
+        assert_eq!(
+            message("src/app.yaml", "/properties/name/pattern"),
+            Some("does not match: {message}")
+        );
+        assert_eq!(message("deploy/app.yaml", "/required"), Some("missing"));
+        assert_eq!(message("deploy/app.yaml", "/properties/port/type"), None);
+        Ok(())
+    }
+
+    // --- extract ---
+
+    #[test]
+    fn jsonpath_converts_to_pointer() {
+        assert_eq!(jsonpath_to_pointer("$").as_deref(), Ok(""));
+        assert_eq!(
+            jsonpath_to_pointer("$.spec.containers[0]['app.kubernetes.io/name']").as_deref(),
+            Ok("/spec/containers/0/app.kubernetes.io~1name")
+        );
+        assert_eq!(
+            jsonpath_to_pointer("$[\"a~b\"].c").as_deref(),
+            Ok("/a~0b/c")
+        );
+        for bad in [
+            "spec",
+            "$..spec",
+            "$.spec[*]",
+            "$.items[?(@.a)]",
+            "$.a[0",
+            "$.",
+        ] {
+            assert!(jsonpath_to_pointer(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn extract_from_first_matching_override() -> anyhow::Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[[override]]
+files = ["charts/**/values.yaml"]
+extract = "$.app"
+
+[[override]]
+files = ["**/*.yaml"]
+extract = "$.spec.template"
+"#,
+        )?;
+        assert_eq!(
+            config
+                .extract_pointer("charts/web/values.yaml", &[])
+                .as_deref(),
+            Some("/app")
+        );
+        assert_eq!(
+            config.extract_pointer("deploy/job.yaml", &[]).as_deref(),
+            Some("/spec/template")
+        );
+        assert_eq!(config.extract_pointer("a.json", &[]), None);
+        assert!(
+            toml::from_str::<Config>("[[override]]\nfiles = [\"*\"]\nextract = \"$.*\"\n").is_err()
+        );
+        Ok(())
+    }
+
+    // --- unreachable_overrides ---
+
+    #[test]
+    fn flags_unreachable_overrides() {
+        let config = Config {
+            overrides: vec![
+                Override {
+                    files: vec!["**".to_string()],
+                    severity: Some(Severity::Warning),
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["legacy/**".to_string()],
+                    severity: Some(Severity::Off),
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["legacy/**".to_string()],
+                    validate_formats: Some(false),
+                    ..Default::default()
+                },
+                Override {
+                    severity: Some(Severity::Off),
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["docs/**".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let unreachable = config.unreachable_overrides();
+        let indices: Vec<usize> = unreachable.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, [1, 3, 4]);
+        assert_eq!(unreachable[0].1, "is shadowed by override #1");
+        assert!(unreachable[1].1.contains("no `files` or `schemas`"));
+        assert_eq!(unreachable[2].1, "sets no options");
+    }
+
+    #[test]
+    fn find_layers_lists_configs_closest_first() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("sub");
+        fs::create_dir_all(&sub)?;
+        fs::write(tmp.path().join("lintel.toml"), "root = true\n")?;
+        fs::write(tmp.path().join("base.toml"), "exclude = [\"base/**\"]\n")?;
+        fs::write(sub.join("lintel.toml"), "extends = [\"../base.toml\"]\n")?;
+
+        let layers = find_layers(&sub)?;
+        let origins: Vec<&str> = layers.iter().map(|l| l.origin.as_str()).collect();
+        let expected = [
+            sub.join("lintel.toml"),
+            sub.join("../base.toml"),
+            tmp.path().join("lintel.toml"),
+        ];
+        assert_eq!(
+            origins,
+            expected
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.exclude, ["base/**"]);
+        Ok(())
+    }
+
     // --- Format / dprint config ---
 
+    #[test]
+    fn parses_format_toml_style() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "root = true\n\n[format]\ntoml_style = \"auto\"\n",
+        )?;
+
+        let config = find_and_load(tmp.path())?.expect("config should exist");
+        let fmt = config.format.expect("format section should exist");
+        assert_eq!(fmt.toml_style, TomlStyle::Auto);
+        assert!(fmt.dprint.is_none());
+        Ok(())
+    }
+
     #[test]
     fn parses_format_dprint_config_kebab_case() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
@@ -696,4 +973,253 @@ validate_formats = false
         assert_eq!(config.overrides[1].validate_formats, Some(true));
         Ok(())
     }
+
+    #[test]
+    fn network_settings_merge_per_field() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[network]\ntimeout = \"1m\"\nretries = 5\n",
+        )?;
+        fs::write(sub.join("lintel.toml"), "[network]\nretries = 0\n")?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(
+            config.network.timeout,
+            Some(core::time::Duration::from_secs(60))
+        );
+        assert_eq!(config.network.retries, Some(0));
+        assert_eq!(config.network.retry_backoff, None);
+        assert!(toml::from_str::<Config>("[network]\ntimeout = \"soon\"\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cache_max_size_parses_units_and_merges() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "cache-max-size = \"2GiB\"\n",
+        )?;
+        fs::write(sub.join("lintel.toml"), "exclude = []\n")?;
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.cache_max_size, Some(2 << 30));
+
+        let size = |toml: &str| toml::from_str::<Config>(toml).map(|c| c.cache_max_size);
+        assert_eq!(size("cache_max_size = \"500MB\"")?, Some(500_000_000));
+        assert_eq!(size("cache-max-size = \"64 kb\"")?, Some(64_000));
+        assert_eq!(size("cache-max-size = \"1024\"")?, Some(1024));
+        assert!(size("cache-max-size = \"lots\"").is_err());
+        assert!(size("cache-max-size = \"5TB\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn kubernetes_settings_merge_from_parent() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[kubernetes]\nenabled = true\nversion = \"1.30.0\"\n[kubernetes.crds]\n\"*\" = \"https://crds/{group}/{kind}.json\"\n",
+        )?;
+        fs::write(
+            sub.join("lintel.toml"),
+            "[kubernetes]\nversion = \"1.31.0\"\n",
+        )?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.kubernetes.enabled, Some(true));
+        assert_eq!(config.kubernetes.version.as_deref(), Some("1.31.0"));
+        assert_eq!(
+            config.kubernetes.crds["*"],
+            "https://crds/{group}/{kind}.json"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn github_actions_expressions_merge_from_parent() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[github-actions]\nexpressions = true\n",
+        )?;
+        fs::write(sub.join("lintel.toml"), "exclude = [\"dist/**\"]\n")?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.github_actions.expressions, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn plugins_merge_by_name_and_match_files() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[plugins.crds]\ncommand = [\"crds\"]\nresolve = true\n\
+             [plugins.policy]\nwasm = \"policy.wasm\"\nvalidate = true\n",
+        )?;
+        fs::write(
+            sub.join("lintel.toml"),
+            "[plugins.crds]\ncommand = [\"./crds\", \"--local\"]\nfiles = [\"k8s/**\"]\nresolve = true\n",
+        )?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        let crds = &config.plugins["crds"];
+        assert_eq!(crds.command, ["./crds", "--local"]);
+        assert!(crds.matches("./k8s/app/deploy.yaml"));
+        assert!(!crds.matches("values.yaml"));
+        assert!(config.plugins["policy"].validate);
+        assert_eq!(
+            config.plugins["policy"].wasm.as_deref(),
+            Some("policy.wasm")
+        );
+        assert!(config.plugins["policy"].matches("values.yaml"));
+        Ok(())
+    }
+
+    #[test]
+    fn schema_lint_rules_merge_per_rule() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schema-lint]\nmissing-description = \"off\"\nunreachable-def = \"warning\"\n",
+        )?;
+        fs::write(
+            sub.join("lintel.toml"),
+            "[schema-lint]\nunreachable-def = \"error\"\n",
+        )?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.schema_lint["missing-description"], Severity::Off);
+        assert_eq!(config.schema_lint["unreachable-def"], Severity::Error);
+        Ok(())
+    }
+
+    #[test]
+    fn formats_merge_per_format() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[formats]\nquantity = \"k8s-quantity\"\nversion = \"semver\"\n",
+        )?;
+        fs::write(
+            sub.join("lintel.toml"),
+            "[formats]\nversion = \"semver-range\"\n",
+        )?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.formats["quantity"], "k8s-quantity");
+        assert_eq!(config.formats["version"], "semver-range");
+        Ok(())
+    }
+
+    #[test]
+    fn annotations_and_vocabularies_merge() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let sub = tmp.path().join("child");
+        fs::create_dir_all(&sub)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "annotations = [\"markdownDescription\", \"x-order\"]\n[vocabularies]\n\"https://example.com/k8s\" = [\"x-kubernetes-int-or-string\"]\n",
+        )?;
+        fs::write(sub.join("lintel.toml"), "annotations = [\"x-order\"]\n")?;
+
+        let config = find_and_load(&sub)?.expect("config should exist");
+        assert_eq!(config.annotations, ["x-order", "markdownDescription"]);
+        assert_eq!(
+            config.annotation_keywords([]),
+            ["x-order", "markdownDescription"]
+        );
+        assert_eq!(
+            config.annotation_keywords(["https://example.com/k8s", "https://example.com/other"]),
+            [
+                "x-order",
+                "markdownDescription",
+                "x-kubernetes-int-or-string"
+            ]
+        );
+        Ok(())
+    }
+
+    // --- Priority ---
+
+    #[test]
+    fn parses_schema_mapping_priority() -> anyhow::Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[schemas]
+"a.json" = "https://example.com/a.json"
+"vendor/*.json" = { url = "//schemas/fixed.json", priority = "high" }
+"#,
+        )?;
+        let (pattern, mapping) = config
+            .find_schema_mapping_entry("./vendor/x.json", "x.json")
+            .expect("mapping should match");
+        assert_eq!(pattern, "vendor/*.json");
+        assert_eq!(mapping.url(), "//schemas/fixed.json");
+        assert_eq!(mapping.priority(), MappingPriority::High);
+        assert_eq!(
+            config.find_schema_mapping("a.json", "a.json"),
+            Some("https://example.com/a.json")
+        );
+        assert_eq!(config.schemas["a.json"].priority(), MappingPriority::Normal);
+        assert!(
+            toml::from_str::<Config>(
+                "[schemas]\n\"a.json\" = { url = \"x\", priority = \"top\" }\n"
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn higher_priority_overrides_are_consulted_first() {
+        let config = Config {
+            overrides: vec![
+                Override {
+                    files: vec!["legacy/**".to_string()],
+                    severity: Some(Severity::Warning),
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["legacy/critical/**".to_string()],
+                    severity: Some(Severity::Error),
+                    priority: 10,
+                    ..Default::default()
+                },
+                Override {
+                    files: vec!["**".to_string()],
+                    severity: Some(Severity::Off),
+                    priority: 20,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.severity_for("legacy/critical/a.json", &[]),
+            Severity::Off
+        );
+        // The catch-all is evaluated first, so every other override is shadowed.
+        let unreachable: Vec<usize> = config
+            .unreachable_overrides()
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(unreachable, [0, 1]);
+    }
 }
@@ -0,0 +1,193 @@
+//! An index of the schemas kept in a workspace, by `$id`.
+//!
+//! Projects that keep their authoritative schemas in-repo usually publish
+//! them under the URL in their `$id`, and their documents and `$ref`s point
+//! at that URL. The index maps each `$id` to the local `*.schema.json` file
+//! declaring it, so those references are served from the working tree
+//! instead of the published copy.
+//!
+//! Scanning walks the whole workspace, so [`LazySchemaIndex`] only does it
+//! for the first URI that could be an `$id`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+/// Suffix of the files the index scans.
+pub const SCHEMA_FILE_SUFFIX: &str = ".schema.json";
+
+/// Local schema files by `$id`.
+#[derive(Debug, Default, Clone)]
+pub struct SchemaIndex {
+    ids: HashMap<String, PathBuf>,
+}
+
+impl SchemaIndex {
+    /// Scan `root` for `*.schema.json` files, respecting `.gitignore` and
+    /// `excludes`, and record their `$id`s.
+    ///
+    /// Files that cannot be read or parsed, or that have no `$id`, are left
+    /// out. When two files declare the same `$id`, the first in path order
+    /// wins.
+    pub fn build(root: &Path, excludes: &[String]) -> Self {
+        let files = crate::discover::discover_files(&root.to_string_lossy(), excludes, |path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(SCHEMA_FILE_SUFFIX))
+        })
+        .unwrap_or_default();
+
+        let mut index = Self::default();
+        for path in files {
+            let Some(id) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .and_then(|schema| schema.get("$id")?.as_str().map(str::to_string))
+            else {
+                continue;
+            };
+            let path = std::path::absolute(&path).unwrap_or(path);
+            index
+                .ids
+                .entry(without_empty_fragment(&id).to_string())
+                .or_insert(path);
+        }
+        index
+    }
+
+    /// The local file whose `$id` is `uri`. A trailing empty fragment (`#`)
+    /// is ignored.
+    pub fn resolve(&self, uri: &str) -> Option<&Path> {
+        self.ids
+            .get(without_empty_fragment(uri))
+            .map(PathBuf::as_path)
+    }
+
+    /// Number of indexed schemas.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether no schemas were indexed.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// A [`SchemaIndex`] of `root` built on the first lookup of a URI that
+/// could be an `$id`. Local paths and `file://` URIs never are, so runs that
+/// only use local schemas never scan the workspace.
+#[derive(Debug)]
+pub struct LazySchemaIndex {
+    root: PathBuf,
+    excludes: Vec<String>,
+    index: OnceLock<SchemaIndex>,
+}
+
+impl LazySchemaIndex {
+    /// An index of `root`, scanned like [`SchemaIndex::build`] when first
+    /// needed.
+    pub fn new(root: &Path, excludes: &[String]) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            excludes: excludes.to_vec(),
+            index: OnceLock::new(),
+        }
+    }
+
+    /// An index that never scans and resolves nothing.
+    pub fn disabled() -> Self {
+        Self {
+            root: PathBuf::new(),
+            excludes: Vec::new(),
+            index: OnceLock::from(SchemaIndex::default()),
+        }
+    }
+
+    /// The local file whose `$id` is `uri`, scanning the workspace first if
+    /// `uri` could be an `$id` and it has not been scanned yet.
+    pub fn resolve(&self, uri: &str) -> Option<&Path> {
+        if !could_be_id(uri) {
+            return None;
+        }
+        self.index
+            .get_or_init(|| SchemaIndex::build(&self.root, &self.excludes))
+            .resolve(uri)
+    }
+}
+
+/// Whether `uri` is an absolute URI other than `file://`, as every `$id`
+/// that names a published schema is. A Windows drive letter is not a scheme.
+fn could_be_id(uri: &str) -> bool {
+    uri.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme != "file"
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+fn without_empty_fragment(uri: &str) -> &str {
+    uri.strip_suffix('#').unwrap_or(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_schema_files_by_id() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas = tmp.path().join("schemas");
+        std::fs::create_dir_all(schemas.join("vendor"))?;
+        std::fs::write(
+            schemas.join("app.schema.json"),
+            r#"{"$id": "https://example.com/app.json#", "type": "object"}"#,
+        )?;
+        std::fs::write(
+            schemas.join("vendor/other.schema.json"),
+            r#"{"$id": "https://example.com/other.json"}"#,
+        )?;
+        std::fs::write(schemas.join("no-id.schema.json"), r#"{"type": "object"}"#)?;
+        std::fs::write(
+            schemas.join("plain.json"),
+            r#"{"$id": "https://example.com/plain.json"}"#,
+        )?;
+
+        let index = SchemaIndex::build(tmp.path(), &["**/vendor/**".to_string()]);
+        assert_eq!(index.len(), 1);
+        assert_eq!(
+            index.resolve("https://example.com/app.json"),
+            Some(std::path::absolute(schemas.join("app.schema.json"))?.as_path())
+        );
+        assert_eq!(index.resolve("https://example.com/other.json"), None);
+        assert_eq!(index.resolve("https://example.com/plain.json"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn lazy_index_scans_only_for_uris_that_could_be_ids() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema = tmp.path().join("app.schema.json");
+        std::fs::write(&schema, r#"{"$id": "https://example.com/app.json"}"#)?;
+
+        let index = LazySchemaIndex::new(tmp.path(), &[]);
+        assert_eq!(index.resolve(&schema.to_string_lossy()), None);
+        assert_eq!(index.resolve("file:///schemas/app.json"), None);
+        assert_eq!(index.resolve(r"C:\schemas\app.json"), None);
+        assert!(index.index.get().is_none());
+
+        assert_eq!(
+            index.resolve("https://example.com/app.json"),
+            Some(std::path::absolute(&schema)?.as_path())
+        );
+        assert_eq!(
+            LazySchemaIndex::disabled().resolve("https://example.com/app.json"),
+            None
+        );
+        Ok(())
+    }
+}
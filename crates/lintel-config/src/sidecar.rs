@@ -0,0 +1,129 @@
+//! `.lintel-schema` sidecar files.
+//!
+//! A `.lintel-schema` file assigns a schema to the files beneath its
+//! directory, for directories of many homogeneous files where a glob in the
+//! root `lintel.toml` is awkward:
+//!
+//! ```toml
+//! schema = "../schemas/resource.json"
+//! files = ["*.yaml", "**/*.yml"]
+//! ```
+//!
+//! `files` are globs relative to the sidecar's directory and default to every
+//! file. The nearest sidecar whose `files` match wins. Its schema acts like
+//! an inline `$schema` for files that do not declare one.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// File name of a sidecar.
+pub const SIDECAR_FILENAME: &str = ".lintel-schema";
+
+/// The contents of a `.lintel-schema` file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Sidecar {
+    /// Schema URL, or a path relative to the sidecar's directory.
+    schema: String,
+    /// Globs relative to the sidecar's directory; empty matches every file.
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+/// A schema assigned by a sidecar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SidecarSchema {
+    /// The schema URI, with local paths made absolute.
+    pub uri: String,
+    /// The `.lintel-schema` file that assigned it.
+    pub sidecar: PathBuf,
+}
+
+/// Find the schema the nearest matching `.lintel-schema` above `path`
+/// assigns to it.
+///
+/// Sidecars that cannot be read or parsed are skipped with a warning.
+pub fn find_sidecar_schema(path: &Path) -> Option<SidecarSchema> {
+    for dir in path.ancestors().skip(1) {
+        let sidecar_path = dir.join(SIDECAR_FILENAME);
+        if !sidecar_path.is_file() {
+            continue;
+        }
+        let sidecar = match std::fs::read_to_string(&sidecar_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(toml::from_str::<Sidecar>(&content)?))
+        {
+            Ok(sidecar) => sidecar,
+            Err(e) => {
+                eprintln!("warning: ignoring {}: {e}", sidecar_path.display());
+                continue;
+            }
+        };
+        let Ok(relative) = path.strip_prefix(dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if !sidecar.files.is_empty()
+            && !sidecar
+                .files
+                .iter()
+                .any(|pattern| glob_matcher::glob_match(pattern, &relative))
+        {
+            continue;
+        }
+        return Some(SidecarSchema {
+            uri: resolve_uri(&sidecar.schema, dir),
+            sidecar: sidecar_path,
+        });
+    }
+    None
+}
+
+/// Make a local schema path absolute against the sidecar's directory. URLs
+/// and `//` paths (relative to `lintel.toml`) are kept.
+fn resolve_uri(uri: &str, dir: &Path) -> String {
+    if uri.contains("://") || uri.starts_with("//") {
+        return uri.to_string();
+    }
+    let path = dir.join(uri);
+    std::path::absolute(&path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_matching_sidecar_wins() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let nested = tmp.path().join("resources/nested");
+        std::fs::create_dir_all(&nested)?;
+        std::fs::write(
+            tmp.path().join("resources").join(SIDECAR_FILENAME),
+            "schema = \"https://example.com/resource.json\"\n",
+        )?;
+        std::fs::write(
+            nested.join(SIDECAR_FILENAME),
+            "schema = \"../schema.json\"\nfiles = [\"*.yaml\"]\n",
+        )?;
+
+        let yaml = find_sidecar_schema(&nested.join("a.yaml"));
+        assert_eq!(
+            yaml,
+            Some(SidecarSchema {
+                uri: std::path::absolute(nested.join("../schema.json"))?
+                    .to_string_lossy()
+                    .to_string(),
+                sidecar: nested.join(SIDECAR_FILENAME),
+            })
+        );
+        let json = find_sidecar_schema(&nested.join("a.json")).map(|s| s.uri);
+        assert_eq!(json.as_deref(), Some("https://example.com/resource.json"));
+        assert_eq!(find_sidecar_schema(&tmp.path().join("a.json")), None);
+        Ok(())
+    }
+}
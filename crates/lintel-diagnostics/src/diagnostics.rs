@@ -1,4 +1,5 @@
-use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+use miette::{Diagnostic, LabeledSpan, NamedSource, Severity, SourceSpan};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 /// Default label text used for span annotations when no specific instance path
@@ -26,6 +27,18 @@ pub struct ValidationDiagnostic {
     pub schema_path: String,
     /// Granular diagnostic code (e.g. `validation(required)`).
     pub validation_code: String,
+    /// `description` of the schema node that failed, shown as a help note
+    /// under the code frame.
+    pub schema_description: Option<String>,
+    /// Property name to use instead of the unexpected key at `span`, for a
+    /// "did you mean" quick fix.
+    pub suggestion: Option<String>,
+    /// Values the schema allows here, for `enum` and `const` errors; empty
+    /// otherwise.
+    pub allowed_values: Vec<serde_json::Value>,
+    /// `Some(Severity::Warning)` when a config override downgrades this
+    /// error to a warning; `None` for a regular error.
+    pub severity: Option<Severity>,
 }
 
 impl Diagnostic for ValidationDiagnostic {
@@ -33,15 +46,23 @@ impl Diagnostic for ValidationDiagnostic {
         Some(Box::new(&self.validation_code))
     }
 
+    fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
     fn url<'a>(&'a self) -> Option<Box<dyn core::fmt::Display + 'a>> {
         Some(Box::new(&self.schema_url))
     }
 
     fn help<'a>(&'a self) -> Option<Box<dyn core::fmt::Display + 'a>> {
-        Some(Box::new(format!(
+        let explain = format!(
             "run `lintel explain --file {}` to see the full schema definition",
             self.path
-        )))
+        );
+        Some(Box::new(match &self.schema_description {
+            Some(desc) => format!("{desc}\n{explain}"),
+            None => explain,
+        }))
     }
 
     fn source_code(&self) -> Option<&dyn miette::SourceCode> {
@@ -68,6 +89,9 @@ impl Diagnostic for ValidationDiagnostic {
 }
 
 /// A single diagnostic produced during validation, formatting, or parsing.
+// Validation diagnostics are by far the most common variant, so boxing
+// them would cost an allocation per error for no gain.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Error, Diagnostic)]
 pub enum LintelDiagnostic {
     #[error("{message}")]
@@ -104,6 +128,37 @@ pub enum LintelDiagnostic {
     #[diagnostic(code(schema::compile))]
     SchemaCompile { path: String, message: String },
 
+    /// A remote schema whose content differs from the hash recorded in the
+    /// lockfile at `path`.
+    #[error("{path}: {message}")]
+    #[diagnostic(code(schema::changed), severity(Warning))]
+    SchemaChanged { path: String, message: String },
+
+    /// Validation of `path` given up on after `--timeout-per-file`.
+    #[error("{path}: {message}")]
+    #[diagnostic(code(timeout))]
+    Timeout { path: String, message: String },
+
+    #[error("{message}")]
+    #[diagnostic(code(unused_suppression), severity(Warning))]
+    UnusedSuppression {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("unused")]
+        span: SourceSpan,
+        message: String,
+    },
+
+    /// Diagnostics of `path` left out by `--max-errors`.
+    #[error("{path}: {message}")]
+    #[diagnostic(code(truncated))]
+    Truncated {
+        path: String,
+        message: String,
+        /// Whether every left-out diagnostic is a warning.
+        warning: bool,
+    },
+
     #[error("Formatter would have printed the following content:\n\n{styled_path}\n\n{diff}")]
     #[diagnostic(
         code(format),
@@ -116,42 +171,180 @@ pub enum LintelDiagnostic {
     },
 }
 
+impl ValidationDiagnostic {
+    /// The edit that applies [`suggestion`](Self::suggestion): the span of
+    /// the unexpected key and the suggested name, quoted like the key.
+    /// `None` without a suggestion or when `span` is not the key.
+    pub fn suggested_fix(&self) -> Option<(SourceSpan, String)> {
+        let suggestion = self.suggestion.as_deref()?;
+        let start = self.span.offset();
+        let key = self.src.inner().get(start..start + self.span.len())?;
+        let property = self.instance_path.rsplit('/').next()?;
+        let property = property.replace("~1", "/").replace("~0", "~");
+        let quote = key.chars().next().filter(|c| matches!(c, '"' | '\''));
+        let unquoted = match quote {
+            Some(q) => key.strip_prefix(q)?.strip_suffix(q)?,
+            None => key,
+        };
+        if unquoted != property {
+            return None;
+        }
+        let replacement = match quote {
+            Some(q) => format!("{q}{suggestion}{q}"),
+            None => suggestion.to_string(),
+        };
+        Some((self.span, replacement))
+    }
+
+    /// 1-based `(line, column)` of the offending value in the source file.
+    pub fn line_col(&self) -> (usize, usize) {
+        offset_to_line_col(self.src.inner(), self.span.offset())
+    }
+
+    /// Stable identifier for tracking this error across runs.
+    ///
+    /// Hashes the schema URL, the failing keyword, the schema path and the
+    /// instance path with array indices replaced by `*`. The file name and
+    /// the error's position are left out, so the fingerprint survives
+    /// renames, line shifts and items inserted into arrays.
+    pub fn fingerprint(&self) -> String {
+        let keyword = self.schema_path.rsplit('/').next().unwrap_or_default();
+        let instance_path = if self.instance_path == DEFAULT_LABEL {
+            String::new()
+        } else {
+            self.instance_path
+                .split('/')
+                .map(|segment| {
+                    if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                        "*"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        let mut hasher = Sha256::new();
+        for part in [
+            self.schema_url.as_str(),
+            keyword,
+            &self.schema_path,
+            &instance_path,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        let mut hex = format!("{:x}", hasher.finalize());
+        hex.truncate(32);
+        hex
+    }
+}
+
 impl LintelDiagnostic {
     /// File path associated with this error.
     pub fn path(&self) -> &str {
         match self {
-            LintelDiagnostic::Parse { src, .. } => src.name(),
+            LintelDiagnostic::Parse { src, .. }
+            | LintelDiagnostic::UnusedSuppression { src, .. } => src.name(),
             LintelDiagnostic::Validation(v) => &v.path,
             LintelDiagnostic::SchemaMismatch { path, .. }
             | LintelDiagnostic::Io { path, .. }
             | LintelDiagnostic::SchemaFetch { path, .. }
             | LintelDiagnostic::SchemaCompile { path, .. }
+            | LintelDiagnostic::SchemaChanged { path, .. }
+            | LintelDiagnostic::Timeout { path, .. }
+            | LintelDiagnostic::Truncated { path, .. }
             | LintelDiagnostic::Format { path, .. } => path,
         }
     }
 
+    /// The file [`line_col`](Self::line_col) points into. Differs from
+    /// [`path`](Self::path) for documents embedded in a file, such as JSON
+    /// Lines lines and Markdown code fences, which report as `file:line`.
+    pub fn file(&self) -> &str {
+        match self {
+            LintelDiagnostic::Validation(v) => v.src.name(),
+            _ => self.path(),
+        }
+    }
+
+    /// Whether this diagnostic is a warning, such as an error downgraded by
+    /// config, which is reported but does not fail the run.
+    pub fn is_warning(&self) -> bool {
+        match self {
+            LintelDiagnostic::Validation(v) => v.severity == Some(Severity::Warning),
+            LintelDiagnostic::UnusedSuppression { .. } | LintelDiagnostic::SchemaChanged { .. } => {
+                true
+            }
+            LintelDiagnostic::Truncated { warning, .. } => *warning,
+            _ => false,
+        }
+    }
+
+    /// [`ValidationDiagnostic::fingerprint`] of validation errors; `None` for
+    /// other diagnostics.
+    pub fn fingerprint(&self) -> Option<String> {
+        match self {
+            LintelDiagnostic::Validation(v) => Some(v.fingerprint()),
+            _ => None,
+        }
+    }
+
     /// Human-readable error message.
     pub fn message(&self) -> &str {
         match self {
             LintelDiagnostic::Parse { message, .. }
+            | LintelDiagnostic::UnusedSuppression { message, .. }
             | LintelDiagnostic::SchemaMismatch { message, .. }
             | LintelDiagnostic::Io { message, .. }
             | LintelDiagnostic::SchemaFetch { message, .. }
-            | LintelDiagnostic::SchemaCompile { message, .. } => message,
+            | LintelDiagnostic::SchemaCompile { message, .. }
+            | LintelDiagnostic::SchemaChanged { message, .. }
+            | LintelDiagnostic::Timeout { message, .. }
+            | LintelDiagnostic::Truncated { message, .. } => message,
             LintelDiagnostic::Validation(v) => &v.message,
             LintelDiagnostic::Format { .. } => "file is not properly formatted",
         }
     }
 
-    /// Byte offset in the source file (for sorting).
+    /// 1-based `(line, column)` of the error in its source file.
+    ///
+    /// Returns `None` for diagnostics that are not tied to a source location
+    /// (I/O, schema fetch/compile, timeout and format errors).
+    pub fn line_col(&self) -> Option<(usize, usize)> {
+        match self {
+            LintelDiagnostic::Parse { src, span, .. }
+            | LintelDiagnostic::UnusedSuppression { src, span, .. } => {
+                Some(offset_to_line_col(src.inner(), span.offset()))
+            }
+            LintelDiagnostic::Validation(v) => Some(v.line_col()),
+            LintelDiagnostic::SchemaMismatch { line_number, .. } => Some((*line_number, 1)),
+            LintelDiagnostic::Io { .. }
+            | LintelDiagnostic::SchemaFetch { .. }
+            | LintelDiagnostic::SchemaCompile { .. }
+            | LintelDiagnostic::SchemaChanged { .. }
+            | LintelDiagnostic::Timeout { .. }
+            | LintelDiagnostic::Truncated { .. }
+            | LintelDiagnostic::Format { .. } => None,
+        }
+    }
+
+    /// Byte offset in the source file (for sorting). [`Truncated`] sorts
+    /// after the other diagnostics of its file.
+    ///
+    /// [`Truncated`]: LintelDiagnostic::Truncated
     pub fn offset(&self) -> usize {
         match self {
-            LintelDiagnostic::Parse { span, .. } => span.offset(),
+            LintelDiagnostic::Truncated { .. } => usize::MAX,
+            LintelDiagnostic::Parse { span, .. }
+            | LintelDiagnostic::UnusedSuppression { span, .. } => span.offset(),
             LintelDiagnostic::Validation(v) => v.span.offset(),
             LintelDiagnostic::SchemaMismatch { .. }
             | LintelDiagnostic::Io { .. }
             | LintelDiagnostic::SchemaFetch { .. }
             | LintelDiagnostic::SchemaCompile { .. }
+            | LintelDiagnostic::SchemaChanged { .. }
+            | LintelDiagnostic::Timeout { .. }
             | LintelDiagnostic::Format { .. } => 0,
         }
     }
@@ -195,12 +388,22 @@ fn first_content_offset(content: &str) -> usize {
     0
 }
 
-/// Find the byte span `(offset, length)` of a JSON pointer path segment in the
-/// source text, suitable for converting directly into a [`SourceSpan`].
+/// Find the byte span `(offset, length)` of a JSON pointer path in the source
+/// text, suitable for converting directly into a [`SourceSpan`].
+///
+/// For an `instance_path` like `/jobs/build/steps`, walks the segments in order
+/// and searches for each key within the value of the previous one, so nested
+/// keys resolve inside their parent rather than at a same-named key elsewhere
+/// in the file. Keys are matched as JSON/JSONC keys (`"name":`), YAML keys
+/// (`name:`, also after a `- ` sequence marker), TOML keys (`name =`) and TOML
+/// table headers (`[name]`, `[parent.name]`); keys inside strings and comments
+/// are skipped. A value ends at its closing bracket, at the next line indented
+/// no deeper than its key, or at the next TOML table header outside it. Array
+/// indices are skipped, so an error on `/items/2` points at the `items` key.
 ///
-/// For an `instance_path` like `/properties/name`, searches for the last segment
-/// `name` as a JSON key (`"name"`) or YAML key (`name:`), and returns a span
-/// covering the matched token.
+/// If a key is not found within its parent, the span of the parent is
+/// returned; if not even the first key is found, falls back to searching for
+/// the last segment anywhere in the file.
 ///
 /// For root-level errors (empty or "/" path), skips past leading comment and blank
 /// lines so the error arrow points at actual content rather than modeline comments.
@@ -212,38 +415,333 @@ pub fn find_instance_path_span(content: &str, instance_path: &str) -> (usize, us
         return (first_content_offset(content), 0);
     }
 
-    // Get the last path segment (e.g., "/foo/bar/baz" -> "baz")
-    let segment = instance_path.rsplit('/').next().unwrap_or("");
-    if segment.is_empty() {
-        return (0, 0);
+    let segments: Vec<String> = instance_path
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+
+    let (mut from, mut to) = (0, content.len());
+    let mut found = None;
+    for segment in &segments {
+        // `#text` is the text of an XML element, found at the element.
+        if segment.is_empty() || segment == "#text" || segment.parse::<usize>().is_ok() {
+            continue;
+        }
+        let Some(span) = find_key_span(&content[..to], segment, from) else {
+            break;
+        };
+        from = span.0 + span.1;
+        to = value_end(&content[..to], span, segment);
+        found = Some(span);
+    }
+    if let Some(span) = found {
+        return span;
     }
 
-    // Try JSON-style key: "segment" — highlight including quotes
-    let json_key = format!("\"{segment}\"");
-    if let Some(pos) = content.find(&json_key) {
-        return (pos, json_key.len());
+    // Fall back to the last segment anywhere in the file
+    segments
+        .last()
+        .filter(|s| !s.is_empty())
+        .and_then(|s| find_key_span(content, s, 0))
+        .unwrap_or((0, 0))
+}
+
+/// Find the span of `segment` used as an object key at or after byte `from`.
+fn find_key_span(content: &str, segment: &str, from: usize) -> Option<(usize, usize)> {
+    let rest = content.get(from..)?;
+
+    // JSON-style key: "segment" followed by `:` (or `=` for quoted TOML keys)
+    // — highlight including quotes
+    let quoted = format!("\"{segment}\"");
+    let mut pos = 0;
+    while pos < rest.len() {
+        if rest[pos..].starts_with(&quoted) {
+            let after = rest[pos + quoted.len()..].trim_start_matches([' ', '\t']);
+            if after.starts_with(':') || after.starts_with('=') {
+                return Some((from + pos, quoted.len()));
+            }
+        }
+        pos = skip_string_or_comment(rest, pos).unwrap_or_else(|| next_char(rest, pos));
     }
 
-    // Try YAML-style key: segment: (at line start or after whitespace)
-    let yaml_key = format!("{segment}:");
-    let quoted_yaml_key = format!("\"{segment}\":");
-    let mut offset = 0;
-    for line in content.lines() {
-        let trimmed = line.trim_start();
-        if trimmed.starts_with(&quoted_yaml_key) {
-            let key_start = line.len() - trimmed.len();
-            // Highlight the quoted key without the trailing colon
-            return (offset + key_start, quoted_yaml_key.len() - 1);
+    // Line-oriented keys: YAML `segment:` and TOML `segment =` / `[segment]`
+    let mut offset = from;
+    for line in rest.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let trimmed = text.trim_start();
+        let mut key_start = offset + (text.len() - trimmed.len());
+
+        // YAML sequence of mappings: `- segment: value`
+        let trimmed = match trimmed.strip_prefix("- ") {
+            Some(item) => {
+                let item_trimmed = item.trim_start();
+                key_start += trimmed.len() - item_trimmed.len();
+                item_trimmed
+            }
+            None => trimmed,
+        };
+
+        if let Some(after) = trimmed.strip_prefix(segment) {
+            let after_ws = after.trim_start_matches([' ', '\t']);
+            if after.starts_with(':') || after_ws.starts_with('=') {
+                return Some((key_start, segment.len()));
+            }
         }
-        if trimmed.starts_with(&yaml_key) {
-            let key_start = line.len() - trimmed.len();
-            // Highlight just the key without the trailing colon
-            return (offset + key_start, segment.len());
+
+        if let Some(span) = toml_header_span(trimmed, segment) {
+            return Some((key_start + span, segment.len()));
         }
-        offset += line.len() + 1; // +1 for newline
+
+        if let Some(span) = hcl_block_span(trimmed, segment) {
+            return Some((key_start + span, segment.len()));
+        }
+
+        offset += line.len();
+    }
+
+    xml_name_span(rest, segment).map(|(start, len)| (from + start, len))
+}
+
+/// The offset of the character after the one at `pos`.
+fn next_char(text: &str, pos: usize) -> usize {
+    pos + text[pos..].chars().next().map_or(1, char::len_utf8)
+}
+
+/// If a double-quoted string or a comment (`//`, `/* */`, `#`) starts at
+/// `pos`, the offset just past it. Strings end at the end of their line at
+/// the latest, so a stray quote in a YAML plain scalar does not hide the rest
+/// of the file.
+fn skip_string_or_comment(text: &str, pos: usize) -> Option<usize> {
+    let rest = &text[pos..];
+    if rest.starts_with("//") || rest.starts_with('#') {
+        return Some(rest.find('\n').map_or(text.len(), |end| pos + end));
+    }
+    if let Some(body) = rest.strip_prefix("/*") {
+        return Some(body.find("*/").map_or(text.len(), |end| pos + end + 4));
+    }
+    if !rest.starts_with('"') {
+        return None;
+    }
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(pos + i + 1),
+            '\n' => return Some(pos + i),
+            _ => escaped = false,
+        }
+    }
+    Some(text.len())
+}
+
+/// The end of the value of the key at `key`, a span found by
+/// [`find_key_span`], within `content`.
+fn value_end(content: &str, key: (usize, usize), segment: &str) -> usize {
+    let (start, len) = key;
+    let line_end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |end| start + end);
+    // HCL labels are found without their closing quote.
+    let after = content[start + len..line_end].trim_start_matches('"');
+    let after = after.trim_start_matches([' ', '\t']);
+    if let Some(value) = after.strip_prefix([':', '=']) {
+        let value_start = line_end - value.len();
+        let mut pos = value_start;
+        while pos < content.len() {
+            if let Some(end) = skip_string_or_comment(content, pos) {
+                pos = end;
+            } else if content[pos..].starts_with(char::is_whitespace) {
+                pos = next_char(content, pos);
+            } else {
+                break;
+            }
+        }
+        return if content[pos..].starts_with(['{', '[']) {
+            closing_bracket(content, pos)
+        } else {
+            indented_block_end(content, start)
+        };
+    }
+    if after.starts_with(']') {
+        return toml_table_end(content, line_end);
+    }
+    if content[..start].ends_with('<') {
+        return xml_element_end(content, start, segment);
+    }
+    // The last label of an HCL block, `resource "type" "name" {`. A block
+    // type or a label before the last one names every block that has it.
+    if after.starts_with('{') {
+        return closing_bracket(content, line_end - after.len());
+    }
+    content.len()
+}
+
+/// The offset just past the bracket closing the one at `open`.
+fn closing_bracket(content: &str, open: usize) -> usize {
+    let mut depth = 0usize;
+    let mut pos = open;
+    while pos < content.len() {
+        if let Some(end) = skip_string_or_comment(content, pos) {
+            pos = end;
+            continue;
+        }
+        match content.as_bytes()[pos] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return pos + 1;
+                }
+            }
+            _ => {}
+        }
+        pos = next_char(content, pos);
     }
+    content.len()
+}
+
+/// The start of the first line after the key at `key_start` that is indented
+/// no deeper than the key: the end of an indented (YAML block) value. A
+/// YAML sequence may sit at the key's own indentation.
+fn indented_block_end(content: &str, key_start: usize) -> usize {
+    let line_start = content[..key_start].rfind('\n').map_or(0, |i| i + 1);
+    let column = key_start - line_start;
+    let mut offset = content[key_start..]
+        .find('\n')
+        .map_or(content.len(), |end| key_start + end + 1);
+    for line in content[offset..].split_inclusive('\n') {
+        let text = line.trim_end();
+        let trimmed = text.trim_start();
+        let indent = text.len() - trimmed.len();
+        let is_item = trimmed == "-" || trimmed.starts_with("- ");
+        if !trimmed.is_empty()
+            && !trimmed.starts_with('#')
+            && (indent < column || (indent == column && !is_item))
+        {
+            return offset;
+        }
+        offset += line.len();
+    }
+    content.len()
+}
+
+/// The start of the next TOML table header, after the header line ending at
+/// `line_end`, that is not a subtable of it.
+fn toml_table_end(content: &str, line_end: usize) -> usize {
+    let header_name = |line: &str| {
+        let inner = line.trim().strip_prefix('[')?;
+        let inner = inner.strip_prefix('[').unwrap_or(inner);
+        let end = inner.find(']')?;
+        let rest = inner[end..].trim_start_matches(']').trim_start();
+        (rest.is_empty() || rest.starts_with('#')).then(|| inner[..end].replace([' ', '\t'], ""))
+    };
+    let line_start = content[..line_end].rfind('\n').map_or(0, |i| i + 1);
+    let Some(table) = header_name(&content[line_start..line_end]) else {
+        return content.len();
+    };
+    let prefix = format!("{table}.");
+    let mut offset = line_end;
+    for line in content[line_end..].split_inclusive('\n') {
+        if header_name(line).is_some_and(|name| !name.starts_with(&prefix)) {
+            return offset;
+        }
+        offset += line.len();
+    }
+    content.len()
+}
+
+/// The end of the XML element whose name starts at `name_start`.
+fn xml_element_end(content: &str, name_start: usize, name: &str) -> usize {
+    let open = format!("<{name}");
+    let close = format!("</{name}");
+    let bounded = |pos: usize| {
+        content[pos..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+    };
+    let mut depth = 0usize;
+    let mut pos = name_start - 1;
+    while let Some(found) = content[pos..].find('<') {
+        let tag = pos + found;
+        let tag_end = content[tag..]
+            .find('>')
+            .map_or(content.len(), |end| tag + end + 1);
+        if content[tag..].starts_with(&close) && bounded(tag + close.len()) {
+            depth = depth.saturating_sub(1);
+        } else if content[tag..].starts_with(&open)
+            && bounded(tag + open.len())
+            && !content[..tag_end].ends_with("/>")
+        {
+            depth += 1;
+        }
+        if depth == 0 {
+            return tag_end;
+        }
+        pos = tag_end;
+    }
+    content.len()
+}
+
+/// If `line` opens an HCL block (`resource "aws_s3_bucket" "logs" {`) whose
+/// type or one of whose labels is `segment`, return the byte offset of it
+/// within the line.
+fn hcl_block_span(line: &str, segment: &str) -> Option<usize> {
+    let header = line.trim_end().strip_suffix('{')?;
+    let mut pos = 0;
+    for token in header.split_whitespace() {
+        let start = pos + header[pos..].find(token)?;
+        pos = start + token.len();
+        if token.trim_matches('"') == segment {
+            return Some(if token.starts_with('"') {
+                start + 1
+            } else {
+                start
+            });
+        }
+    }
+    None
+}
+
+/// Find an XML element `<segment`, or an attribute `name=` for an `@name`
+/// segment, in `rest`.
+fn xml_name_span(rest: &str, segment: &str) -> Option<(usize, usize)> {
+    let (name, needle) = match segment.strip_prefix('@') {
+        Some(attr) => (attr, format!("{attr}=")),
+        None => (segment, format!("<{segment}")),
+    };
+    let is_attr = name.len() < segment.len();
+    let mut search = 0;
+    while let Some(pos) = rest[search..].find(&needle) {
+        let start = search + pos;
+        let end = start + needle.len();
+        let bounded = if is_attr {
+            rest[..start].ends_with(char::is_whitespace)
+        } else {
+            rest[end..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        };
+        if bounded {
+            let name_start = if is_attr { start } else { start + 1 };
+            return Some((name_start, name.len()));
+        }
+        search = end;
+    }
+    None
+}
 
-    (0, 0)
+/// If `line` is a TOML table header whose last key is `segment`, return the
+/// byte offset of that key within the line.
+fn toml_header_span(line: &str, segment: &str) -> Option<usize> {
+    let inner = line.strip_prefix("[[").or_else(|| line.strip_prefix('['))?;
+    let end = inner.find(']')?;
+    let last = inner[..end].rsplit('.').next()?.trim();
+    let last = last
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .unwrap_or(last);
+    if last != segment {
+        return None;
+    }
+    let header_end = line.len() - inner.len() + end;
+    line[..header_end].rfind(segment)
 }
 
 /// Build a label string combining the instance path and the schema path.
@@ -352,6 +850,240 @@ mod tests {
         assert_eq!(find_instance_path_span(content, "/on"), (0, 4)); // "on"
     }
 
+    #[test]
+    fn span_walks_nested_json_keys() {
+        let content = r#"{"a": {"name": 1}, "b": {"name": 2}}"#;
+        assert_eq!(find_instance_path_span(content, "/b/name"), (25, 6));
+    }
+
+    #[test]
+    fn span_walks_nested_yaml_keys() {
+        let content = "build:\n  image: a\ntest:\n  image: b\n";
+        let (offset, len) = find_instance_path_span(content, "/test/image");
+        assert_eq!(len, 5);
+        assert_eq!(offset_to_line_col(content, offset), (4, 3));
+    }
+
+    #[test]
+    fn span_skips_array_indices_in_yaml_sequences() {
+        let content = "steps:\n  - name: one\n  - name: two\n    run: echo\n";
+        let (offset, len) = find_instance_path_span(content, "/steps/1/run");
+        assert_eq!(len, 3);
+        assert_eq!(offset_to_line_col(content, offset), (4, 5));
+    }
+
+    #[test]
+    fn span_highlights_toml_keys_and_tables() {
+        let content = "[package]\nname = \"demo\"\n\n[dependencies]\nserde = \"1\"\n";
+        let (offset, len) = find_instance_path_span(content, "/dependencies/serde");
+        assert_eq!(len, 5);
+        assert_eq!(offset_to_line_col(content, offset), (5, 1));
+        let (offset, len) = find_instance_path_span(content, "/package");
+        assert_eq!((offset, len), (1, 7));
+    }
+
+    #[test]
+    fn span_highlights_hcl_blocks_and_labels() {
+        let content = "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n}\n";
+        let (offset, len) = find_instance_path_span(content, "/resource/aws_s3_bucket/logs");
+        assert_eq!((&content[offset..offset + len], offset), ("logs", 26));
+        let (offset, len) = find_instance_path_span(content, "/resource/aws_s3_bucket/logs/bucket");
+        assert_eq!(len, 6);
+        assert_eq!(offset_to_line_col(content, offset), (2, 3));
+        let content =
+            "variable \"a\" {\n  type = string\n}\n\nvariable \"b\" {\n  default = 1\n}\n";
+        let (offset, _) = find_instance_path_span(content, "/variable/b/default");
+        assert_eq!(offset_to_line_col(content, offset), (6, 3));
+    }
+
+    #[test]
+    fn span_highlights_xml_elements_and_attributes() {
+        let content = "<project>\n  <names/>\n  <name lang=\"en\">demo</name>\n</project>\n";
+        let (offset, len) = find_instance_path_span(content, "/project/name");
+        assert_eq!(len, 4);
+        assert_eq!(offset_to_line_col(content, offset), (3, 4));
+        let (offset, len) = find_instance_path_span(content, "/project/name/@lang");
+        assert_eq!((&content[offset..offset + len], len), ("lang", 4));
+        let (offset, _) = find_instance_path_span(content, "/project/name/#text");
+        assert_eq!(offset_to_line_col(content, offset), (3, 4));
+    }
+
+    #[test]
+    fn span_ignores_string_values_matching_key() {
+        let content = r#"{"title": "name", "name": "x"}"#;
+        assert_eq!(find_instance_path_span(content, "/name"), (18, 6));
+    }
+
+    #[test]
+    fn span_stays_within_the_parent_value() {
+        let json = r#"{"a": {"title": 1}, "b": {"name": 2}}"#;
+        assert_eq!(find_instance_path_span(json, "/a/name"), (1, 3));
+        let yaml = "a:\n  title: 1\nb:\n  name: 2\n";
+        assert_eq!(find_instance_path_span(yaml, "/a/name"), (0, 1));
+        let toml = "[a]\ntitle = 1\n\n[a.c]\nname = 3\n\n[b]\nname = 2\n";
+        let (offset, _) = find_instance_path_span(toml, "/a/c/name");
+        assert_eq!(offset_to_line_col(toml, offset), (5, 1));
+        let (offset, _) = find_instance_path_span(toml, "/b/name");
+        assert_eq!(offset_to_line_col(toml, offset), (8, 1));
+    }
+
+    #[test]
+    fn span_skips_keys_in_comments_and_strings() {
+        let content =
+            "{\n  // \"name\": \"old\"\n  \"note\": \"\\\"name\\\": x\",\n  \"name\": \"x\"\n}\n";
+        let (offset, len) = find_instance_path_span(content, "/name");
+        assert_eq!(len, 6);
+        assert_eq!(offset_to_line_col(content, offset), (4, 3));
+    }
+
+    #[test]
+    fn span_falls_back_to_last_segment() {
+        let content = "name: hello\n";
+        assert_eq!(find_instance_path_span(content, "/missing/name"), (0, 4));
+    }
+
+    #[test]
+    fn validation_line_col() {
+        let content = "a: 1\nb: 2\n";
+        let d = LintelDiagnostic::Validation(ValidationDiagnostic {
+            src: NamedSource::new("f.yaml", content.to_string()),
+            span: 5.into(),
+            schema_span: 5.into(),
+            path: "f.yaml".into(),
+            instance_path: "/b".into(),
+            label: "/b".into(),
+            message: "m".into(),
+            schema_url: "s".into(),
+            schema_path: "/properties/b".into(),
+            validation_code: "validation(type)".into(),
+            schema_description: None,
+            suggestion: None,
+            allowed_values: Vec::new(),
+            severity: None,
+        });
+        assert_eq!(d.line_col(), Some((2, 1)));
+    }
+
+    #[test]
+    fn fingerprint_ignores_file_position_and_array_index() {
+        let diagnostic = |path: &str, offset: usize, instance_path: &str| ValidationDiagnostic {
+            src: NamedSource::new(path, "x".repeat(64)),
+            span: offset.into(),
+            schema_span: offset.into(),
+            path: path.into(),
+            instance_path: instance_path.into(),
+            label: instance_path.into(),
+            message: "m".into(),
+            schema_url: "https://example.com/s.json".into(),
+            schema_path: "/properties/ports/items/type".into(),
+            validation_code: "validation(type)".into(),
+            schema_description: None,
+            suggestion: None,
+            allowed_values: Vec::new(),
+            severity: None,
+        };
+        let original = diagnostic("a.yaml", 3, "/ports/0");
+        assert_eq!(original.fingerprint().len(), 32);
+        assert_eq!(
+            original.fingerprint(),
+            diagnostic("renamed.yaml", 40, "/ports/2").fingerprint()
+        );
+        assert_ne!(
+            original.fingerprint(),
+            diagnostic("a.yaml", 3, "/hosts/0").fingerprint()
+        );
+    }
+
+    #[test]
+    fn suggested_fix_replaces_the_key() {
+        let diagnostic = |content: &str, span: (usize, usize)| ValidationDiagnostic {
+            src: NamedSource::new("f", content.to_string()),
+            span: span.into(),
+            schema_span: span.into(),
+            path: "f".into(),
+            instance_path: "/spec/replicass".into(),
+            label: "/spec/replicass".into(),
+            message: "m".into(),
+            schema_url: "s".into(),
+            schema_path: "/properties/spec/additionalProperties".into(),
+            validation_code: "validation(additional_property)".into(),
+            schema_description: None,
+            suggestion: Some("replicas".into()),
+            allowed_values: Vec::new(),
+            severity: None,
+        };
+        let json = r#"{"spec": {"replicass": 3}}"#;
+        assert_eq!(
+            diagnostic(json, (10, 11)).suggested_fix(),
+            Some(((10, 11).into(), "\"replicas\"".to_string()))
+        );
+        let yaml = "spec:\n  replicass: 3\n";
+        assert_eq!(
+            diagnostic(yaml, (8, 9)).suggested_fix(),
+            Some(((8, 9).into(), "replicas".to_string()))
+        );
+        // A fallback span that is not the key has no fix
+        assert_eq!(diagnostic(yaml, (0, 0)).suggested_fix(), None);
+    }
+
+    #[test]
+    fn validation_help_includes_schema_description() {
+        use miette::Diagnostic;
+
+        let d = ValidationDiagnostic {
+            src: NamedSource::new("f.yaml", String::new()),
+            span: 0.into(),
+            schema_span: 0.into(),
+            path: "f.yaml".into(),
+            instance_path: "/port".into(),
+            label: "/port".into(),
+            message: "m".into(),
+            schema_url: "s".into(),
+            schema_path: "/properties/port/type".into(),
+            validation_code: "validation(type)".into(),
+            schema_description: Some("Port to listen on".into()),
+            suggestion: None,
+            allowed_values: Vec::new(),
+            severity: None,
+        };
+        let help = d.help().map(|h| h.to_string()).unwrap_or_default();
+        assert!(help.starts_with("Port to listen on\n"));
+        assert!(help.contains("lintel explain --file f.yaml"));
+    }
+
+    #[test]
+    fn downgraded_validation_is_a_warning() {
+        use miette::Diagnostic;
+
+        let mut v = ValidationDiagnostic {
+            src: NamedSource::new("f.yaml", String::new()),
+            span: 0.into(),
+            schema_span: 0.into(),
+            path: "f.yaml".into(),
+            instance_path: "/port".into(),
+            label: "/port".into(),
+            message: "m".into(),
+            schema_url: "s".into(),
+            schema_path: "/properties/port/type".into(),
+            validation_code: "validation(type)".into(),
+            schema_description: None,
+            suggestion: None,
+            allowed_values: Vec::new(),
+            severity: None,
+        };
+        assert_eq!(v.severity(), None);
+        v.severity = Some(Severity::Warning);
+        assert_eq!(v.severity(), Some(Severity::Warning));
+        assert!(LintelDiagnostic::Validation(v).is_warning());
+        assert!(
+            !LintelDiagnostic::Io {
+                path: String::new(),
+                message: String::new(),
+            }
+            .is_warning()
+        );
+    }
+
     // --- Error code tests ---
 
     #[test]
@@ -379,6 +1111,10 @@ mod tests {
                     schema_url: String::new(),
                     schema_path: String::new(),
                     validation_code: "validation(required)".to_string(),
+                    schema_description: None,
+                    suggestion: None,
+                    allowed_values: Vec::new(),
+                    severity: None,
                 }),
                 "validation(required)",
             ),
@@ -411,6 +1147,20 @@ mod tests {
                 },
                 "schema::compile",
             ),
+            (
+                LintelDiagnostic::SchemaChanged {
+                    path: String::new(),
+                    message: String::new(),
+                },
+                "schema::changed",
+            ),
+            (
+                LintelDiagnostic::Timeout {
+                    path: String::new(),
+                    message: String::new(),
+                },
+                "timeout",
+            ),
             (
                 LintelDiagnostic::Format {
                     path: String::new(),
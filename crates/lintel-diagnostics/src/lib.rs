@@ -1,6 +1,8 @@
 #![doc = include_str!("../README.md")]
 #![allow(unused_assignments)] // thiserror/miette derive macros trigger false positives
 
+extern crate alloc;
+
 pub mod diagnostics;
 pub mod reporter;
 
@@ -1,7 +1,11 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::Write;
 use core::time::Duration;
 
 use lintel_schema_cache::CacheStatus;
 use lintel_validation_cache::ValidationCacheStatus;
+use miette::Diagnostic;
+use serde_json::{Map, Value, json};
 
 use crate::diagnostics::LintelDiagnostic;
 
@@ -13,17 +17,87 @@ pub struct CheckedFile {
     pub cache_status: Option<CacheStatus>,
     /// `None` when validation caching is not applicable; `Some` for validation cache hits/misses.
     pub validation_cache_status: Option<ValidationCacheStatus>,
+    /// Time spent validating the file; `None` when it was not validated,
+    /// such as on a validation cache hit.
+    pub duration: Option<Duration>,
+}
+
+/// Time a check run spent in each phase.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Timings {
+    /// Fetching and reading schemas.
+    pub fetch: Duration,
+    /// Compiling schemas into validators.
+    pub compile: Duration,
+    /// Validating files, including validation cache lookups.
+    pub validate: Duration,
+}
+
+/// How a check run ended. The discriminant is the process exit code, and
+/// when several apply the highest wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExitStatus {
+    /// Nothing failed the run.
+    Success = 0,
+    /// Files failed validation, failed to parse or are not formatted.
+    Invalid = 1,
+    /// Configuration or schema errors, such as a schema that does not compile.
+    Config = 2,
+    /// Files or schemas could not be read or downloaded.
+    Io = 3,
+}
+
+impl From<ExitStatus> for u8 {
+    fn from(status: ExitStatus) -> Self {
+        status as u8
+    }
 }
 
 /// Result of a check run (validation + optional format checking).
 pub struct CheckResult {
+    /// All diagnostics, including ones downgraded to warnings (see
+    /// [`LintelDiagnostic::is_warning`]).
     pub errors: Vec<LintelDiagnostic>,
     pub checked: Vec<CheckedFile>,
+    pub timings: Timings,
+    /// SHA-256 of each schema files were validated against, by URI, telling
+    /// apart the versions of a schema served at one URI.
+    pub schema_hashes: BTreeMap<String, String>,
+    /// Files no schema was found for, sorted.
+    pub without_schema: Vec<String>,
 }
 
 impl CheckResult {
+    /// Whether any diagnostic should fail the run. Warnings do not.
     pub fn has_errors(&self) -> bool {
-        !self.errors.is_empty()
+        self.errors.iter().any(|e| !e.is_warning())
+    }
+
+    /// The [`ExitStatus`] of the run. With `error_on_warnings`, warnings fail
+    /// it like validation errors.
+    pub fn exit_status(&self, error_on_warnings: bool) -> ExitStatus {
+        self.errors
+            .iter()
+            .map(|error| match error {
+                _ if error.is_warning() && !error_on_warnings => ExitStatus::Success,
+                LintelDiagnostic::SchemaCompile { .. } => ExitStatus::Config,
+                LintelDiagnostic::Io { .. } | LintelDiagnostic::SchemaFetch { .. } => {
+                    ExitStatus::Io
+                }
+                _ => ExitStatus::Invalid,
+            })
+            .max()
+            .unwrap_or(ExitStatus::Success)
+    }
+
+    /// Number of diagnostics that fail the run.
+    pub fn error_count(&self) -> usize {
+        self.errors.iter().filter(|e| !e.is_warning()).count()
+    }
+
+    /// Number of diagnostics downgraded to warnings.
+    pub fn warning_count(&self) -> usize {
+        self.errors.iter().filter(|e| e.is_warning()).count()
     }
 
     pub fn files_checked(&self) -> usize {
@@ -49,6 +123,240 @@ pub fn format_checked_verbose(file: &CheckedFile) -> String {
     )
 }
 
+/// The warning count appended to a reporter's summary line, e.g.
+/// `" 2 warnings."`, or an empty string when there are none.
+pub fn format_warnings_note(warning_count: usize) -> String {
+    match warning_count {
+        0 => String::new(),
+        1 => " 1 warning.".to_string(),
+        n => format!(" {n} warnings."),
+    }
+}
+
+/// Files in the `--summary` list of slowest files.
+const SLOWEST_FILES: usize = 10;
+
+/// The `--summary` report: time per phase, files and diagnostics per schema,
+/// validation diagnostics per schema keyword, and the slowest files.
+pub fn format_summary(result: &CheckResult) -> String {
+    let mut schemas: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+    for file in &result.checked {
+        schemas.entry(&file.schema).or_default().0 += 1;
+    }
+    let mut keywords: BTreeMap<&str, usize> = BTreeMap::new();
+    for error in &result.errors {
+        let LintelDiagnostic::Validation(v) = error else {
+            continue;
+        };
+        let counts = schemas.entry(&v.schema_url).or_default();
+        if error.is_warning() {
+            counts.2 += 1;
+        } else {
+            counts.1 += 1;
+        }
+        let keyword = v.schema_path.rsplit('/').next().unwrap_or_default();
+        *keywords.entry(keyword).or_default() += 1;
+    }
+
+    let Timings {
+        fetch,
+        compile,
+        validate,
+    } = result.timings;
+    let mut out = format!(
+        "Summary:\n  time: fetching {fetch:.1?}, compiling {compile:.1?}, validating {validate:.1?}\n"
+    );
+    out.push_str("  by schema:\n");
+    let mut schemas: Vec<_> = schemas.into_iter().collect();
+    schemas.sort_by_key(|(_, (files, errors, _))| core::cmp::Reverse((*errors, *files)));
+    for (schema, (files, errors, warnings)) in schemas {
+        let _ = writeln!(
+            out,
+            "    {files:>5} files {errors:>5} errors {warnings:>5} warnings  {schema}"
+        );
+    }
+    if !keywords.is_empty() {
+        out.push_str("  by error keyword:\n");
+        let mut keywords: Vec<_> = keywords.into_iter().collect();
+        keywords.sort_by_key(|(_, count)| core::cmp::Reverse(*count));
+        for (keyword, count) in keywords {
+            let _ = writeln!(out, "    {count:>5}  {keyword}");
+        }
+    }
+    let mut timed: Vec<(&CheckedFile, Duration)> = result
+        .checked
+        .iter()
+        .filter_map(|file| Some((file, file.duration?)))
+        .collect();
+    if !timed.is_empty() {
+        out.push_str("  slowest files:\n");
+        timed.sort_by_key(|(_, duration)| core::cmp::Reverse(*duration));
+        for (file, duration) in timed.into_iter().take(SLOWEST_FILES) {
+            let _ = writeln!(out, "    {:>9}  {}", format!("{duration:.1?}"), file.path);
+        }
+    }
+    out
+}
+
+/// The `--unknown-files` report: the files no schema was found for,
+/// grouped by extension (or by name, for files without one), largest group
+/// first.
+pub fn format_unknown_files(result: &CheckResult) -> String {
+    let mut groups: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for path in &result.without_schema {
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+        let key = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => format!("*.{extension}"),
+            _ => name.to_string(),
+        };
+        groups.entry(key).or_default().push(path);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, paths)| core::cmp::Reverse(paths.len()));
+
+    let mut out = format!("Files without a schema: {}\n", result.without_schema.len());
+    for (pattern, paths) in groups {
+        let _ = writeln!(out, "  {:>5}  {pattern}", paths.len());
+        for path in paths {
+            let _ = writeln!(out, "           {path}");
+        }
+    }
+    out
+}
+
+/// Version of the `--report-file` format.
+pub const REPORT_VERSION: u32 = 1;
+
+/// The `--report-file` report: counts and timings of a run, for dashboards.
+/// It holds no file paths, contents or messages.
+pub fn run_report(result: &CheckResult, elapsed: Duration, status: ExitStatus) -> Value {
+    let mut schemas: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+    for file in &result.checked {
+        schemas.entry(&file.schema).or_default().0 += 1;
+    }
+    let mut rules: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut failed: BTreeSet<&str> = BTreeSet::new();
+    for error in &result.errors {
+        let code = error.code().map(|c| c.to_string()).unwrap_or_default();
+        let counts = rules.entry(code).or_default();
+        if error.is_warning() {
+            counts.1 += 1;
+        } else {
+            counts.0 += 1;
+            failed.insert(error.path());
+        }
+        if let LintelDiagnostic::Validation(v) = error {
+            let counts = schemas.entry(&v.schema_url).or_default();
+            if error.is_warning() {
+                counts.2 += 1;
+            } else {
+                counts.1 += 1;
+            }
+        }
+    }
+
+    let millis = |duration: Duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    let cached = result
+        .checked
+        .iter()
+        .filter(|file| file.validation_cache_status == Some(ValidationCacheStatus::Hit))
+        .count();
+    json!({
+        "version": REPORT_VERSION,
+        "exit_code": u8::from(status),
+        "files": {
+            "checked": result.files_checked(),
+            "failed": failed.len(),
+            "cached": cached,
+            "without_schema": result.without_schema.len(),
+        },
+        "diagnostics": {
+            "errors": result.error_count(),
+            "warnings": result.warning_count(),
+        },
+        "rules": rules
+            .into_iter()
+            .map(|(code, (errors, warnings))| {
+                (code, json!({"errors": errors, "warnings": warnings}))
+            })
+            .collect::<Map<_, _>>(),
+        "schemas": schemas
+            .into_iter()
+            .map(|(uri, (files, errors, warnings))| {
+                json!({
+                    "uri": uri,
+                    "sha256": result.schema_hashes.get(uri),
+                    "files": files,
+                    "errors": errors,
+                    "warnings": warnings,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "duration_ms": {
+            "total": millis(elapsed),
+            "fetch": millis(result.timings.fetch),
+            "compile": millis(result.timings.compile),
+            "validate": millis(result.timings.validate),
+        },
+    })
+}
+
+const BADGE_GREEN: &str = "#4c1";
+const BADGE_YELLOW: &str = "#dfb317";
+const BADGE_RED: &str = "#e05d44";
+
+/// The `--badge` report: a shields.io-style SVG badge reading `passing`, or
+/// the number of errors when `status` fails the run.
+pub fn format_badge(result: &CheckResult, status: ExitStatus) -> String {
+    let count = |n: usize, what: &str| {
+        if n == 1 {
+            format!("1 {what}")
+        } else {
+            format!("{n} {what}s")
+        }
+    };
+    let errors = result.error_count();
+    let warnings = result.warning_count();
+    let (message, color) = match (status, errors) {
+        (ExitStatus::Success, _) if warnings > 0 => (count(warnings, "warning"), BADGE_YELLOW),
+        (ExitStatus::Success, _) => ("passing".to_string(), BADGE_GREEN),
+        // Only `--error-on-warnings` fails a run without errors.
+        (_, 0) if warnings > 0 => (count(warnings, "warning"), BADGE_RED),
+        (_, 0) => ("failing".to_string(), BADGE_RED),
+        (_, errors) => (count(errors, "error"), BADGE_RED),
+    };
+    badge_svg("lintel", &message, color)
+}
+
+/// A flat two-part badge. Positions are in tenths of a pixel, as shields.io
+/// draws them, and `textLength` fits each text to its estimated width.
+fn badge_svg(label: &str, message: &str, color: &str) -> String {
+    let label_width = badge_text_width(label) + 10;
+    let message_width = badge_text_width(message) + 10;
+    let width = label_width + message_width;
+    let label_x = label_width * 5;
+    let message_x = label_width * 10 + message_width * 5;
+    let label_length = (label_width - 10) * 10;
+    let message_length = (message_width - 10) * 10;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" text-rendering="geometricPrecision" font-size="110"><text aria-hidden="true" x="{label_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)" textLength="{label_length}">{label}</text><text x="{label_x}" y="140" transform="scale(.1)" textLength="{label_length}">{label}</text><text aria-hidden="true" x="{message_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)" textLength="{message_length}">{message}</text><text x="{message_x}" y="140" transform="scale(.1)" textLength="{message_length}">{message}</text></g></svg>
+"##
+    )
+}
+
+/// Estimated width in pixels of `text` in 11px Verdana.
+fn badge_text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | '\'' | '!' | '|' => 3,
+            ' ' | 'f' | 'r' | 't' | '(' | ')' | '[' | ']' => 4,
+            'm' | 'w' | 'M' | 'W' => 10,
+            'A'..='Z' | '0'..='9' => 7,
+            _ => 6,
+        })
+        .sum()
+}
+
 /// Trait for formatting and outputting check results.
 pub trait Reporter {
     /// Called after all checks complete with the full result and elapsed time.
@@ -57,3 +365,230 @@ pub trait Reporter {
     /// Called each time a file is checked (for streaming progress).
     fn on_file_checked(&mut self, file: &CheckedFile);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, schema: &str, millis: Option<u64>) -> CheckedFile {
+        CheckedFile {
+            path: path.into(),
+            schema: schema.into(),
+            cache_status: None,
+            validation_cache_status: None,
+            duration: millis.map(Duration::from_millis),
+        }
+    }
+
+    fn validation_error(schema_path: &str) -> LintelDiagnostic {
+        LintelDiagnostic::Validation(crate::ValidationDiagnostic {
+            src: miette::NamedSource::new("a.json", String::new()),
+            span: 0.into(),
+            schema_span: 0.into(),
+            path: "a.json".into(),
+            instance_path: "/x".into(),
+            label: "/x".into(),
+            message: "m".into(),
+            schema_url: "b.json".into(),
+            schema_path: schema_path.into(),
+            validation_code: "validation(type)".into(),
+            schema_description: None,
+            suggestion: None,
+            allowed_values: Vec::new(),
+            severity: None,
+        })
+    }
+
+    #[test]
+    fn summary_counts_schemas_keywords_and_slowest_files() {
+        let result = CheckResult {
+            errors: vec![
+                validation_error("/properties/x/type"),
+                validation_error("/properties/y/type"),
+                validation_error("/required"),
+            ],
+            checked: vec![
+                file("a.json", "b.json", Some(3)),
+                file("c.json", "d.json", Some(120)),
+                file("e.json", "d.json", None),
+            ],
+            timings: Timings {
+                fetch: Duration::from_millis(250),
+                compile: Duration::from_millis(40),
+                validate: Duration::from_millis(123),
+            },
+            schema_hashes: BTreeMap::new(),
+            without_schema: Vec::new(),
+        };
+        assert_eq!(
+            format_summary(&result),
+            "Summary:
+  time: fetching 250.0ms, compiling 40.0ms, validating 123.0ms
+  by schema:
+        1 files     3 errors     0 warnings  b.json
+        2 files     0 errors     0 warnings  d.json
+  by error keyword:
+        2  type
+        1  required
+  slowest files:
+      120.0ms  c.json
+        3.0ms  a.json
+"
+        );
+    }
+
+    #[test]
+    fn unknown_files_are_grouped_by_extension() {
+        let result = CheckResult {
+            errors: vec![],
+            checked: vec![file("a.json", "b.json", None)],
+            timings: Timings::default(),
+            schema_hashes: BTreeMap::new(),
+            without_schema: [
+                "Jenkinsfile",
+                "deploy/a.yaml",
+                "deploy/b.yaml",
+                "ci/.env.toml",
+            ]
+            .map(String::from)
+            .to_vec(),
+        };
+        assert_eq!(
+            format_unknown_files(&result),
+            "Files without a schema: 4
+      2  *.yaml
+           deploy/a.yaml
+           deploy/b.yaml
+      1  *.toml
+           ci/.env.toml
+      1  Jenkinsfile
+           Jenkinsfile
+"
+        );
+    }
+
+    #[test]
+    fn report_counts_files_rules_and_schemas() {
+        let mut cached = file("c.json", "d.json", None);
+        cached.validation_cache_status = Some(ValidationCacheStatus::Hit);
+        let result = CheckResult {
+            errors: vec![
+                validation_error("/properties/x/type"),
+                validation_error("/properties/y/type"),
+                LintelDiagnostic::SchemaFetch {
+                    path: "e.json".into(),
+                    message: "m".into(),
+                },
+            ],
+            checked: vec![file("a.json", "b.json", Some(3)), cached],
+            timings: Timings {
+                fetch: Duration::from_millis(250),
+                compile: Duration::from_millis(40),
+                validate: Duration::from_millis(123),
+            },
+            schema_hashes: BTreeMap::from([("b.json".to_string(), "abc".to_string())]),
+            without_schema: vec!["f.yaml".to_string()],
+        };
+        assert_eq!(
+            run_report(&result, Duration::from_millis(500), ExitStatus::Io),
+            json!({
+                "version": 1,
+                "exit_code": 3,
+                "files": {"checked": 2, "failed": 2, "cached": 1, "without_schema": 1},
+                "diagnostics": {"errors": 3, "warnings": 0},
+                "rules": {
+                    "schema::fetch": {"errors": 1, "warnings": 0},
+                    "validation(type)": {"errors": 2, "warnings": 0},
+                },
+                "schemas": [
+                    {"uri": "b.json", "sha256": "abc", "files": 1, "errors": 2, "warnings": 0},
+                    {"uri": "d.json", "sha256": null, "files": 1, "errors": 0, "warnings": 0},
+                ],
+                "duration_ms": {"total": 500, "fetch": 250, "compile": 40, "validate": 123},
+            })
+        );
+    }
+
+    #[test]
+    fn badge_shows_passing_or_the_error_count() {
+        let result = |errors| CheckResult {
+            errors,
+            checked: vec![],
+            timings: Timings::default(),
+            schema_hashes: BTreeMap::new(),
+            without_schema: Vec::new(),
+        };
+        let unused = || LintelDiagnostic::UnusedSuppression {
+            src: miette::NamedSource::new("a.yaml", "# lintel-ignore\n".to_string()),
+            span: 0.into(),
+            message: "unused".into(),
+        };
+
+        let passing = format_badge(&result(vec![]), ExitStatus::Success);
+        assert!(passing.starts_with("<svg "));
+        assert!(passing.contains("<title>lintel: passing</title>"));
+        assert!(passing.contains(BADGE_GREEN));
+
+        let warned = format_badge(&result(vec![unused()]), ExitStatus::Success);
+        assert!(warned.contains("<title>lintel: 1 warning</title>"));
+        assert!(warned.contains(BADGE_YELLOW));
+
+        let failing = result(vec![
+            validation_error("/type"),
+            validation_error("/required"),
+            unused(),
+        ]);
+        let failing = format_badge(&failing, ExitStatus::Invalid);
+        assert!(failing.contains("<title>lintel: 2 errors</title>"));
+        assert!(failing.contains(BADGE_RED));
+    }
+
+    #[test]
+    fn warnings_note_pluralizes() {
+        assert_eq!(format_warnings_note(0), "");
+        assert_eq!(format_warnings_note(1), " 1 warning.");
+        assert_eq!(format_warnings_note(3), " 3 warnings.");
+    }
+
+    #[test]
+    fn exit_status_takes_the_worst_diagnostic() {
+        let result = |errors| CheckResult {
+            errors,
+            checked: vec![],
+            timings: Timings::default(),
+            schema_hashes: BTreeMap::new(),
+            without_schema: Vec::new(),
+        };
+        let unused = || LintelDiagnostic::UnusedSuppression {
+            src: miette::NamedSource::new("a.yaml", "# lintel-ignore\n".to_string()),
+            span: 0.into(),
+            message: "unused".into(),
+        };
+        let compile = || LintelDiagnostic::SchemaCompile {
+            path: "a.json".into(),
+            message: "m".into(),
+        };
+        assert_eq!(result(vec![]).exit_status(false), ExitStatus::Success);
+        assert_eq!(
+            result(vec![unused()]).exit_status(false),
+            ExitStatus::Success
+        );
+        assert_eq!(
+            result(vec![unused()]).exit_status(true),
+            ExitStatus::Invalid
+        );
+        let fetch = LintelDiagnostic::SchemaFetch {
+            path: "b.json".into(),
+            message: "m".into(),
+        };
+        assert_eq!(
+            result(vec![compile(), fetch, unused()]).exit_status(false),
+            ExitStatus::Io
+        );
+        assert_eq!(
+            result(vec![unused(), compile()]).exit_status(false),
+            ExitStatus::Config
+        );
+        assert_eq!(u8::from(ExitStatus::Io), 3);
+    }
+}
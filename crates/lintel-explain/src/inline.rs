@@ -1,10 +1,13 @@
 //! Inline external `$ref` URIs into root `$defs`.
 //!
 //! When a schema uses relative or absolute `$ref` URIs pointing to external
-//! schemas (e.g. `"meta/core"`), this module fetches those schemas and bundles
-//! them into the root `$defs` object. The original `$ref` values are rewritten
-//! to local `#/$defs/<name>` pointers so the existing rendering code can
-//! resolve them.
+//! schemas (e.g. `"meta/core"` or `"https://…/definitions.json#/Foo"`), this
+//! module fetches those documents and bundles the referenced sub-schemas into
+//! the root `$defs` object. The original `$ref` values are rewritten to local
+//! `#/$defs/<name>` pointers so the existing rendering code can resolve them.
+//!
+//! `$ref`s inside a bundled sub-schema are made absolute against the document
+//! it came from, so the next pass picks them up like any other external ref.
 
 use alloc::collections::BTreeMap;
 use std::collections::HashSet;
@@ -16,7 +19,9 @@ use url::Url;
 use lintel_schema_cache::SchemaCache;
 
 /// Maximum number of resolution passes (to handle transitive external refs).
-const MAX_PASSES: usize = 2;
+///
+/// Each document is fetched at most once, so extra passes are cheap.
+const MAX_PASSES: usize = 8;
 
 /// Inline all external `$ref` URIs in `value` by fetching them and adding
 /// them to root `$defs`. Rewrites the `$ref` values to `#/$defs/<name>`.
@@ -28,6 +33,8 @@ pub async fn inline_external_refs(
     cache: &SchemaCache,
 ) -> Result<()> {
     let mut seen: HashSet<String> = HashSet::new();
+    // Fetched documents by URI (without fragment); `None` if the fetch failed.
+    let mut documents: BTreeMap<String, Option<Value>> = BTreeMap::new();
 
     for _ in 0..MAX_PASSES {
         let Some(base) = extract_base_uri(value, schema_uri) else {
@@ -45,17 +52,30 @@ pub async fn inline_external_refs(
             break;
         }
 
-        // Fetch all external schemas.
+        // Fetch all external documents and pick out the referenced sub-schemas.
         let mut fetched: BTreeMap<String, Value> = BTreeMap::new();
         for uri in &new_uris {
-            match cache.fetch(uri).await {
-                Ok((val, _)) => {
-                    fetched.insert(uri.clone(), val);
-                }
-                Err(e) => {
-                    tracing::warn!(uri, error = %e, "failed to fetch external $ref");
-                }
+            let (doc_uri, fragment) = split_fragment(uri);
+            if !documents.contains_key(doc_uri) {
+                let doc = match cache.fetch(doc_uri).await {
+                    Ok((val, _)) => Some(val),
+                    Err(e) => {
+                        tracing::warn!(uri = doc_uri, error = %e, "failed to fetch external $ref");
+                        None
+                    }
+                };
+                documents.insert(doc_uri.to_string(), doc);
             }
+            let Some(Some(doc)) = documents.get(doc_uri) else {
+                continue;
+            };
+            let Some(target) = doc.pointer(fragment) else {
+                tracing::warn!(uri, "external $ref fragment not found");
+                continue;
+            };
+            let mut target = target.clone();
+            absolutize_refs(&mut target, doc, doc_uri);
+            fetched.insert(uri.clone(), target);
         }
 
         if fetched.is_empty() {
@@ -86,6 +106,49 @@ fn extract_base_uri(value: &Value, schema_uri: &str) -> Option<Url> {
     Url::parse(base_str).ok()
 }
 
+/// Split a URI into the document URI and its JSON Pointer fragment.
+fn split_fragment(uri: &str) -> (&str, &str) {
+    uri.split_once('#').unwrap_or((uri, ""))
+}
+
+/// Make every `$ref` in a sub-schema taken from `doc` absolute.
+///
+/// Fragment-only refs point back into the fetched document; other relative
+/// refs resolve against the document's `$id` (or its URI).
+fn absolutize_refs(value: &mut Value, doc: &Value, doc_uri: &str) {
+    let Ok(fetched) = Url::parse(doc_uri) else {
+        return;
+    };
+    let base = extract_base_uri(doc, doc_uri).unwrap_or_else(|| fetched.clone());
+    absolutize_recursive(value, &fetched, &base);
+}
+
+fn absolutize_recursive(value: &mut Value, doc_url: &Url, base: &Url) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(ref_val)) = map.get_mut("$ref") {
+                let resolved = if ref_val.starts_with('#') {
+                    doc_url.join(ref_val)
+                } else {
+                    base.join(ref_val)
+                };
+                if let Ok(resolved) = resolved {
+                    *ref_val = resolved.to_string();
+                }
+            }
+            for v in map.values_mut() {
+                absolutize_recursive(v, doc_url, base);
+            }
+        }
+        Value::Array(arr) => {
+            for v in arr.iter_mut() {
+                absolutize_recursive(v, doc_url, base);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Resolve a `$ref` string against a base URL.
 ///
 /// Returns `None` for local refs (starting with `#`).
@@ -191,7 +254,7 @@ fn bundle_into_defs(root: &mut Value, fetched: &BTreeMap<String, Value>, base: &
     };
 
     for (uri, fetched_val) in fetched {
-        let name = def_name_for_schema(fetched_val, uri);
+        let name = unique_def_name(root_defs_map, def_name_for_schema(fetched_val, uri), uri);
         // Add x-lintel.source to the fetched schema.
         let mut schema_copy = fetched_val.clone();
         if let Some(obj) = schema_copy.as_object_mut() {
@@ -215,10 +278,32 @@ fn bundle_into_defs(root: &mut Value, fetched: &BTreeMap<String, Value>, base: &
     rewrite_refs(root, &uri_to_def_name, base);
 }
 
+/// Return `name`, or `name-2`, `name-3`, … if a different source already
+/// uses it, so two external schemas never share one definition.
+fn unique_def_name(defs: &serde_json::Map<String, Value>, name: String, uri: &str) -> String {
+    let is_free = |candidate: &str| {
+        defs.get(candidate).is_none_or(|existing| {
+            existing.pointer("/x-lintel/source").and_then(Value::as_str) == Some(uri)
+        })
+    };
+    if is_free(&name) {
+        return name;
+    }
+    let mut n = 2;
+    loop {
+        let candidate = format!("{name}-{n}");
+        if is_free(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
 /// Derive a definition name from a fetched schema.
 ///
-/// Uses the schema's `title` if available, otherwise the last path segment
-/// of the URL.
+/// Uses the schema's `title` if available, otherwise the last segment of the
+/// URL's fragment (e.g. `Foo` for `defs.json#/definitions/Foo`), otherwise
+/// the last path segment of the URL.
 fn def_name_for_schema(fetched_value: &Value, url: &str) -> String {
     if let Some(title) = fetched_value
         .as_object()
@@ -229,6 +314,15 @@ fn def_name_for_schema(fetched_value: &Value, url: &str) -> String {
         return title.to_string();
     }
 
+    if let Some(last) = split_fragment(url)
+        .1
+        .rsplit('/')
+        .next()
+        .filter(|s| !s.is_empty())
+    {
+        return last.replace("~1", "/").replace("~0", "~");
+    }
+
     // Fall back to last path segment of URL.
     Url::parse(url)
         .ok()
@@ -247,7 +341,11 @@ fn rewrite_refs(value: &mut Value, uri_to_name: &BTreeMap<String, String>, base:
                 && let Some(resolved) = resolve_ref_uri(&ref_val, base)
                 && let Some(name) = uri_to_name.get(&resolved)
             {
-                map.insert("$ref".to_string(), Value::String(format!("#/$defs/{name}")));
+                let escaped = name.replace('~', "~0").replace('/', "~1");
+                map.insert(
+                    "$ref".to_string(),
+                    Value::String(format!("#/$defs/{escaped}")),
+                );
             }
             for v in map.values_mut() {
                 rewrite_refs(v, uri_to_name, base);
@@ -468,6 +566,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn def_name_uses_fragment_segment() {
+        let schema = json!({ "type": "object" });
+        assert_eq!(
+            def_name_for_schema(&schema, "https://example.com/defs.json#/definitions/Foo"),
+            "Foo"
+        );
+    }
+
+    #[test]
+    fn unique_def_name_suffixes_other_sources() {
+        let defs = json!({
+            "Foo": { "x-lintel": { "source": "https://a.example/defs.json#/Foo" } },
+            "Foo-2": {}
+        });
+        let defs = defs.as_object().unwrap();
+        assert_eq!(
+            unique_def_name(defs, "Foo".into(), "https://a.example/defs.json#/Foo"),
+            "Foo"
+        );
+        assert_eq!(
+            unique_def_name(defs, "Foo".into(), "https://b.example/defs.json#/Foo"),
+            "Foo-3"
+        );
+        assert_eq!(unique_def_name(defs, "Bar".into(), "x"), "Bar");
+    }
+
+    #[test]
+    fn absolutize_refs_in_fetched_document() {
+        let doc = json!({ "$id": "https://example.com/v1/defs.json" });
+        let mut target = json!({
+            "properties": {
+                "a": { "$ref": "#/definitions/A" },
+                "b": { "$ref": "other.json#/B" },
+                "c": { "$ref": "https://elsewhere.example/c.json" }
+            }
+        });
+        absolutize_refs(&mut target, &doc, "https://cdn.example.com/defs.json");
+        assert_eq!(
+            target["properties"]["a"]["$ref"],
+            "https://cdn.example.com/defs.json#/definitions/A"
+        );
+        assert_eq!(
+            target["properties"]["b"]["$ref"],
+            "https://example.com/v1/other.json#/B"
+        );
+        assert_eq!(
+            target["properties"]["c"]["$ref"],
+            "https://elsewhere.example/c.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn inline_fragment_refs_into_shared_document() {
+        let cache = SchemaCache::memory();
+        cache.insert(
+            "https://example.com/definitions.json",
+            json!({
+                "definitions": {
+                    "Foo": {
+                        "type": "object",
+                        "properties": { "bar": { "$ref": "#/definitions/Bar" } }
+                    },
+                    "Bar": { "type": "string", "description": "A bar" }
+                }
+            }),
+        );
+
+        let mut root = json!({
+            "$id": "https://example.com/schema.json",
+            "properties": {
+                "foo": { "$ref": "definitions.json#/definitions/Foo" }
+            }
+        });
+
+        inline_external_refs(&mut root, "https://example.com/schema.json", &cache)
+            .await
+            .unwrap();
+
+        assert_eq!(root["properties"]["foo"]["$ref"], "#/$defs/Foo");
+        let defs = root["$defs"].as_object().unwrap();
+        assert_eq!(defs["Foo"]["type"], "object");
+        assert_eq!(defs["Foo"]["properties"]["bar"]["$ref"], "#/$defs/Bar");
+        assert_eq!(defs["Bar"]["description"], "A bar");
+        assert_eq!(
+            defs["Bar"]["x-lintel"]["source"],
+            "https://example.com/definitions.json#/definitions/Bar"
+        );
+    }
+
     #[tokio::test]
     async fn inline_no_external_refs_is_noop() {
         let cache = SchemaCache::memory();
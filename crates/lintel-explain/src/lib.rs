@@ -4,10 +4,16 @@ extern crate alloc;
 
 mod inline;
 mod path;
+mod position;
 pub mod resolve;
+#[cfg(feature = "tui")]
+mod tui;
 
-pub use resolve::{ResolvedFileSchema, SchemaSource, build_retriever};
+pub use position::FilePosition;
+pub use resolve::{FileSchemaResolver, ResolvedFileSchema, SchemaSource, build_retriever};
 
+use alloc::sync::Arc;
+use std::collections::HashMap;
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
@@ -20,8 +26,73 @@ use lintel_cli_common::{CLIGlobalOptions, CliCacheOptions};
 // CLI args
 // ---------------------------------------------------------------------------
 
+/// Output format for `lintel explain`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExplainFormat {
+    /// Man-page-style terminal output.
+    #[default]
+    Terminal,
+    /// GitHub-flavored markdown for generated docs.
+    Markdown,
+    /// HTML fragment for hosted docs sites.
+    Html,
+}
+
+impl core::str::FromStr for ExplainFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Self::Terminal),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!(
+                "unknown output format '{s}', expected: terminal, markdown, html"
+            )),
+        }
+    }
+}
+
+/// How many levels of nested properties `lintel explain` expands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplainDepth {
+    /// Expand up to this many levels.
+    Levels(usize),
+    /// Expand everything; recursive `$ref`s are shown once per path.
+    Unlimited,
+}
+
+impl Default for ExplainDepth {
+    fn default() -> Self {
+        Self::Levels(jsonschema_explain::DEFAULT_MAX_DEPTH)
+    }
+}
+
+impl ExplainDepth {
+    /// The depth limit in the form [`jsonschema_explain::ExplainOptions`] expects.
+    pub fn max_depth(self) -> Option<usize> {
+        match self {
+            Self::Levels(n) => Some(n),
+            Self::Unlimited => None,
+        }
+    }
+}
+
+impl core::str::FromStr for ExplainDepth {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unlimited" => Ok(Self::Unlimited),
+            _ => s
+                .parse()
+                .map(Self::Levels)
+                .map_err(|_| format!("invalid depth '{s}', expected: a number, unlimited")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Bpaf)]
 #[bpaf(generate(explain_args_inner))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExplainArgs {
     /// Schema URL or local file path to explain.
     /// Can be combined with `--file` or `--path` to override schema resolution
@@ -56,6 +127,58 @@ pub struct ExplainArgs {
     #[bpaf(long("extended"), switch)]
     pub extended: bool,
 
+    /// Output format
+    #[bpaf(
+        long("output"),
+        argument("terminal|markdown|html"),
+        fallback(ExplainFormat::Terminal)
+    )]
+    pub output: ExplainFormat,
+
+    /// Levels of nested properties to expand (default: 3)
+    #[bpaf(
+        long("depth"),
+        argument("N|unlimited"),
+        fallback(ExplainDepth::default())
+    )]
+    pub depth: ExplainDepth,
+
+    /// List only required properties
+    #[bpaf(long("required-only"), switch)]
+    pub required_only: bool,
+
+    /// List only properties whose name matches GLOB, and the properties
+    /// leading to them
+    #[bpaf(long("filter"), argument("GLOB"))]
+    pub filter: Option<String>,
+
+    /// Leave out deprecated properties
+    #[bpaf(long("exclude-deprecated"), switch)]
+    pub exclude_deprecated: bool,
+
+    /// Print a minimal document for the schema instead of its documentation:
+    /// required properties with their defaults, examples or placeholders
+    #[bpaf(long("example"), switch)]
+    pub example: bool,
+
+    /// Format of the `--example` document (default: from the data file's
+    /// extension, else JSON)
+    #[bpaf(long("format"), argument("json|yaml|toml"))]
+    pub format: Option<jsonschema_explain::ExampleFormat>,
+
+    /// Explain the property at a `LINE:COL` position in the data file
+    #[bpaf(long("at"), argument("LINE:COL"))]
+    pub at: Option<FilePosition>,
+
+    /// Fuzzy-search property names, titles and descriptions and list the
+    /// matching JSON pointers instead of rendering documentation
+    #[bpaf(long("search"), argument("TERM"))]
+    pub search: Option<String>,
+
+    /// Browse the schema as an interactive tree (search with `/`)
+    #[bpaf(long("interactive"), switch)]
+    pub interactive: bool,
+
     /// First positional argument. When no `--file`, `--path`, or `--schema`
     /// flag is given this is treated as a file path (equivalent to `--path`).
     /// Otherwise it is a JSON Pointer or `JSONPath` to a sub-schema.
@@ -87,6 +210,12 @@ fn is_url(s: &str) -> bool {
     s.starts_with("http://") || s.starts_with("https://")
 }
 
+/// Pick the EXAMPLES snippet format from a data file's extension, so
+/// examples can be pasted straight into the file being explained.
+pub fn example_format_for(path: &str) -> jsonschema_explain::ExampleFormat {
+    jsonschema_explain::ExampleFormat::for_path(path)
+}
+
 /// Split a schema URI into the base URL and an optional fragment pointer.
 ///
 /// Uses the `url` crate for proper URL parsing when the input is a URL.
@@ -186,7 +315,7 @@ impl TempDataFile {
 ///
 /// Returns an error if the schema cannot be fetched, parsed, or the pointer
 /// cannot be resolved.
-#[allow(clippy::missing_panics_doc)]
+#[allow(clippy::missing_panics_doc, clippy::too_many_lines)]
 pub async fn run(args: ExplainArgs, global: &CLIGlobalOptions) -> Result<bool> {
     // Normalize positional args: when no flag is given, the first positional
     // is a file path (equivalent to --path) and the second is the pointer.
@@ -229,14 +358,52 @@ pub async fn run(args: ExplainArgs, global: &CLIGlobalOptions) -> Result<bool> {
         resolve_schema_info(&args, data_source_str, is_file_flag, fetched.as_ref()).await?;
 
     let schema = fetch_schema(&schema_uri, is_remote, &args.cache).await?;
+    let annotations = annotation_keywords(&schema);
     let schema_value = jsonschema_schema::SchemaValue::Schema(Box::new(schema));
 
+    if let Some(query) = args.search.as_deref() {
+        if pointer_str.is_some() {
+            anyhow::bail!("--search cannot be combined with a pointer");
+        }
+        print_search_hits(&schema_value, query, global)?;
+        return Ok(false);
+    }
+
+    let pointer_str = match args.at {
+        Some(pos) => Some(pointer_at_position(
+            pos,
+            pointer_str.as_deref(),
+            data_source_str,
+            fetched.as_ref(),
+        )?),
+        None => pointer_str,
+    };
+
     let pointer = pointer_str
         .as_deref()
         .map(path::to_schema_pointer)
         .transpose()
         .map_err(|e| anyhow::anyhow!("{e}"))?;
 
+    if args.example {
+        let schema = match pointer.as_deref() {
+            Some(ptr) => jsonschema_explain::navigate_pointer(&schema_value, &schema_value, ptr)
+                .map_err(|e| anyhow::anyhow!("{e}"))?,
+            None => &schema_value,
+        };
+        let format = args
+            .format
+            .unwrap_or_else(|| data_source_str.map(example_format_for).unwrap_or_default());
+        print!(
+            "{}",
+            jsonschema_explain::example_document(schema, &schema_value, format)
+        );
+        return Ok(false);
+    }
+    if args.format.is_some() {
+        anyhow::bail!("--format applies only to --example");
+    }
+
     let instance_prefix = pointer
         .as_deref()
         .map(schema_pointer_to_instance_prefix)
@@ -258,6 +425,12 @@ pub async fn run(args: ExplainArgs, global: &CLIGlobalOptions) -> Result<bool> {
         width: lintel_cli_common::terminal_width(),
         validation_errors,
         extended: args.extended,
+        max_depth: args.depth.max_depth(),
+        example_format: data_source_str.map(example_format_for).unwrap_or_default(),
+        annotations,
+        required_only: args.required_only,
+        property_filter: args.filter.clone(),
+        exclude_deprecated: args.exclude_deprecated,
     };
 
     // When navigating via pointer, use the last path segment as the display
@@ -272,10 +445,53 @@ pub async fn run(args: ExplainArgs, global: &CLIGlobalOptions) -> Result<bool> {
         display_name
     };
 
-    let output = match pointer.as_deref() {
-        Some(ptr) => jsonschema_explain::explain_at_path(&schema_value, ptr, &display_name, &opts)
-            .map_err(|e| anyhow::anyhow!("{e}"))?,
-        None => jsonschema_explain::explain(&schema_value, &display_name, &opts),
+    if args.interactive {
+        if !is_tty {
+            anyhow::bail!("--interactive requires a terminal");
+        }
+        browse(
+            &schema_value,
+            pointer.as_deref().unwrap_or(""),
+            &display_name,
+        )?;
+        return Ok(false);
+    }
+
+    let filtered = args.required_only || args.filter.is_some() || args.exclude_deprecated;
+    if filtered && args.output != ExplainFormat::Terminal {
+        anyhow::bail!(
+            "--required-only, --filter and --exclude-deprecated apply only to terminal output"
+        );
+    }
+
+    let output = match (args.output, pointer.as_deref()) {
+        (ExplainFormat::Terminal, Some(ptr)) => {
+            jsonschema_explain::explain_at_path(&schema_value, ptr, &display_name, &opts)
+                .map_err(|e| anyhow::anyhow!("{e}"))?
+        }
+        (ExplainFormat::Terminal, None) => {
+            jsonschema_explain::explain(&schema_value, &display_name, &opts)
+        }
+        (ExplainFormat::Markdown, Some(ptr)) => jsonschema_explain::explain_markdown_at_path(
+            &schema_value,
+            ptr,
+            &display_name,
+            opts.max_depth,
+        )
+        .map_err(|e| anyhow::anyhow!("{e}"))?,
+        (ExplainFormat::Markdown, None) => {
+            jsonschema_explain::explain_markdown(&schema_value, &display_name, opts.max_depth)
+        }
+        (ExplainFormat::Html, Some(ptr)) => jsonschema_explain::explain_html_at_path(
+            &schema_value,
+            ptr,
+            &display_name,
+            opts.max_depth,
+        )
+        .map_err(|e| anyhow::anyhow!("{e}"))?,
+        (ExplainFormat::Html, None) => {
+            jsonschema_explain::explain_html(&schema_value, &display_name, opts.max_depth)
+        }
     };
 
     if is_tty && !args.no_pager {
@@ -315,6 +531,7 @@ pub async fn explain_resolved_schema(
 ) -> Result<()> {
     match fetch_schema(&resolved.schema_uri, resolved.is_remote, cache).await {
         Ok(schema) => {
+            let annotations = annotation_keywords(&schema);
             let sv = jsonschema_schema::SchemaValue::Schema(Box::new(schema));
             let is_tty = std::io::stdout().is_terminal();
             let use_color = global.use_color(is_tty);
@@ -324,6 +541,12 @@ pub async fn explain_resolved_schema(
                 width: lintel_cli_common::terminal_width(),
                 validation_errors: vec![],
                 extended: display.extended,
+                max_depth: Some(jsonschema_explain::DEFAULT_MAX_DEPTH),
+                example_format: jsonschema_explain::ExampleFormat::Json,
+                annotations,
+                required_only: false,
+                property_filter: None,
+                exclude_deprecated: false,
             };
             let output = jsonschema_explain::explain(&sv, &resolved.display_name, &opts);
             if is_tty && !display.no_pager {
@@ -341,6 +564,171 @@ pub async fn explain_resolved_schema(
     }
 }
 
+/// The custom annotation keywords `lintel.toml` declares for `schema`,
+/// through `annotations` and the vocabularies in its `$vocabulary`.
+fn annotation_keywords(schema: &jsonschema_schema::Schema) -> Vec<String> {
+    let (config, _, _) = lintel_validate::validate::load_config(None);
+    let vocabularies = schema
+        .vocabulary
+        .iter()
+        .flat_map(|vocabularies| vocabularies.keys().map(url::Url::as_str));
+    config
+        .annotation_keywords(vocabularies)
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Open the `--interactive` tree browser.
+#[cfg(feature = "tui")]
+fn browse(schema: &jsonschema_schema::SchemaValue, pointer: &str, name: &str) -> Result<()> {
+    tui::browse(schema, pointer, name)
+}
+
+#[cfg(not(feature = "tui"))]
+fn browse(_schema: &jsonschema_schema::SchemaValue, _pointer: &str, _name: &str) -> Result<()> {
+    anyhow::bail!("--interactive needs lintel-explain built with the `tui` feature")
+}
+
+/// Print `--search` hits, best first: the name and pointer of each match,
+/// followed by the first sentence of its description.
+fn print_search_hits(
+    schema: &jsonschema_schema::SchemaValue,
+    query: &str,
+    global: &CLIGlobalOptions,
+) -> Result<()> {
+    use ansi_term_styles::{BOLD, DIM, RESET};
+
+    let hits = jsonschema_explain::search(schema, query);
+    if hits.is_empty() {
+        anyhow::bail!("no properties match '{query}'");
+    }
+    let use_color = global.use_color(std::io::stdout().is_terminal());
+    let (bold, dim, reset) = if use_color {
+        (BOLD, DIM, RESET)
+    } else {
+        ("", "", "")
+    };
+    for hit in hits {
+        println!("{bold}{}{reset}  {dim}{}{reset}", hit.name, hit.pointer);
+        if let Some(summary) = hit.summary {
+            println!("    {summary}");
+        }
+    }
+    Ok(())
+}
+
+/// Map `--at LINE:COL` to a `JSONPath` using the data file's content.
+fn pointer_at_position(
+    pos: FilePosition,
+    pointer: Option<&str>,
+    data_source_str: Option<&str>,
+    fetched: Option<&FetchedData>,
+) -> Result<String> {
+    if pointer.is_some() {
+        anyhow::bail!("--at cannot be combined with a pointer");
+    }
+    let (content, file_name) = if let Some(fetched) = fetched {
+        (fetched.content.clone(), fetched.filename.clone())
+    } else {
+        let src = data_source_str.context("--at requires a data file (--file or a file path)")?;
+        let content =
+            std::fs::read_to_string(src).with_context(|| format!("failed to read {src}"))?;
+        (content, src.to_string())
+    };
+    position::jsonpath_at(&content, &file_name, pos)
+}
+
+/// Resolve and load the schemas of local files, with external `$ref`s
+/// inlined and migrated to 2020-12. Used by `lintel format --sort-keys schema`.
+///
+/// `lintel.toml` and the catalogs are loaded once from the current
+/// directory. Files are read and resolved concurrently, and each distinct
+/// schema is fetched once. Files with no schema, or whose schema fails to
+/// load, are left out.
+pub async fn load_schemas_for_files(
+    paths: Vec<PathBuf>,
+    cache: &CliCacheOptions,
+) -> HashMap<PathBuf, jsonschema_schema::SchemaValue> {
+    let retriever = resolve::build_retriever(cache);
+    let file_resolver =
+        Arc::new(FileSchemaResolver::load(None, &retriever, cache.no_catalog).await);
+
+    let mut resolve_set = tokio::task::JoinSet::new();
+    for path in paths {
+        let file_resolver = Arc::clone(&file_resolver);
+        resolve_set.spawn(async move {
+            let resolved = match tokio::fs::read_to_string(&path).await {
+                Ok(content) => file_resolver.resolve(&content, &path),
+                Err(e) => {
+                    tracing::debug!("failed to read {}: {e}", path.display());
+                    None
+                }
+            };
+            (path, resolved)
+        });
+    }
+    let mut by_schema: HashMap<(String, bool), Vec<PathBuf>> = HashMap::new();
+    while let Some(result) = resolve_set.join_next().await {
+        match result {
+            Ok((path, Some(resolved))) => by_schema
+                .entry((resolved.schema_uri, resolved.is_remote))
+                .or_default()
+                .push(path),
+            Ok((_, None)) => {}
+            Err(e) => tracing::warn!("schema resolution task failed: {e}"),
+        }
+    }
+
+    let mut fetch_set = tokio::task::JoinSet::new();
+    for ((uri, is_remote), paths) in by_schema {
+        let retriever = retriever.clone();
+        fetch_set.spawn(async move {
+            let schema = fetch_schema_with(&retriever, &uri, is_remote).await;
+            (uri, schema, paths)
+        });
+    }
+    let mut schemas = HashMap::new();
+    while let Some(result) = fetch_set.join_next().await {
+        match result {
+            Ok((_, Ok(schema), paths)) => {
+                let schema = jsonschema_schema::SchemaValue::Schema(Box::new(schema));
+                for path in paths {
+                    schemas.insert(path, schema.clone());
+                }
+            }
+            Ok((uri, Err(e), _)) => tracing::debug!("failed to load schema {uri}: {e}"),
+            Err(e) => tracing::warn!("schema fetch task failed: {e}"),
+        }
+    }
+    schemas
+}
+
+/// Resolve and load the schema for content that is not on disk (e.g. an
+/// editor buffer), as [`load_schemas_for_files`] does for files. `path` is
+/// used for catalog matching and to locate `lintel.toml`; it does not need
+/// to exist.
+///
+/// Returns `Ok(None)` when no schema applies to the content.
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be fetched or deserialized.
+pub async fn load_schema_for_content(
+    content: &str,
+    path: &Path,
+    cache: &CliCacheOptions,
+) -> Result<Option<jsonschema_schema::SchemaValue>> {
+    let Some(resolved) = resolve::resolve_schema_for_content(content, path, None, cache).await?
+    else {
+        return Ok(None);
+    };
+    let schema = fetch_schema(&resolved.schema_uri, resolved.is_remote, cache).await?;
+    Ok(Some(jsonschema_schema::SchemaValue::Schema(Box::new(
+        schema,
+    ))))
+}
+
 /// If the data source is a URL, fetch its content; otherwise return `None`.
 async fn fetch_data_source(data_source_str: Option<&str>) -> Result<Option<FetchedData>> {
     let Some(src) = data_source_str else {
@@ -458,12 +846,30 @@ async fn run_validation(
     }
 }
 
-async fn fetch_schema(
+/// Fetch a schema, inline its external `$ref`s and migrate it to draft
+/// 2020-12. `is_remote` says whether `schema_uri` is a URL or a local path.
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be fetched, read or deserialized.
+pub async fn fetch_schema(
     schema_uri: &str,
     is_remote: bool,
     cache: &CliCacheOptions,
 ) -> Result<jsonschema_schema::Schema> {
-    let retriever = resolve::build_retriever(cache);
+    fetch_schema_with(&resolve::build_retriever(cache), schema_uri, is_remote).await
+}
+
+/// [`fetch_schema`] with an existing retriever, sharing its in-memory cache.
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be fetched, read or deserialized.
+pub async fn fetch_schema_with(
+    retriever: &lintel_schema_cache::SchemaCache,
+    schema_uri: &str,
+    is_remote: bool,
+) -> Result<jsonschema_schema::Schema> {
     let mut value: serde_json::Value = if is_remote {
         let (val, _) = retriever
             .fetch(schema_uri)
@@ -477,7 +883,7 @@ async fn fetch_schema(
             .with_context(|| format!("failed to parse schema: {schema_uri}"))?
     };
 
-    inline::inline_external_refs(&mut value, schema_uri, &retriever).await?;
+    inline::inline_external_refs(&mut value, schema_uri, retriever).await?;
     jsonschema_migrate::migrate_to_2020_12(&mut value);
     let json_string = serde_json::to_string(&value)
         .with_context(|| format!("failed to serialize schema: {schema_uri}"))?;
@@ -527,6 +933,19 @@ async fn collect_validation_errors(
         no_catalog: cache.no_catalog,
         config_dir,
         schema_cache_ttl: cache.schema_cache_ttl,
+        fetch_timeout: cache.fetch_timeout,
+        fetch_retries: cache.fetch_retries,
+        fetch_retry_backoff: cache.fetch_retry_backoff,
+        trace_resolution: false,
+        lock: lintel_validate::lock::LockMode::Auto,
+        openapi_examples: false,
+        validate_content: false,
+        strict_instances: false,
+        report_unused_suppressions: false,
+        fail_fast: false,
+        max_errors: None,
+        timeout_per_file: None,
+        formats: lintel_validate::formats::FormatRegistry::default(),
     };
 
     let result = match lintel_validate::validate::run(&validate_args).await {
@@ -711,16 +1130,30 @@ mod tests {
                 force_validation: false,
                 force: false,
                 no_catalog: false,
+                fetch_timeout: None,
+                fetch_retries: None,
+                fetch_retry_backoff: None,
             },
             no_syntax_highlighting: false,
             no_pager: false,
             extended: false,
+            output: ExplainFormat::Terminal,
+            depth: ExplainDepth::default(),
+            required_only: false,
+            filter: None,
+            exclude_deprecated: false,
+            example: false,
+            format: None,
+            at: None,
+            search: None,
+            interactive: false,
             positional: None,
             pointer: None,
         };
         let global = CLIGlobalOptions {
             colors: None,
             verbose: false,
+            quiet: false,
             log_level: lintel_cli_common::LogLevel::None,
         };
         let err = run(args, &global).await.unwrap_err();
@@ -746,6 +1179,117 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn example_format_follows_data_file() {
+        use jsonschema_explain::ExampleFormat;
+        assert_eq!(example_format_for("values.yaml"), ExampleFormat::Yaml);
+        assert_eq!(example_format_for("ci/.gitlab-ci.YML"), ExampleFormat::Yaml);
+        assert_eq!(example_format_for("lintel.toml"), ExampleFormat::Toml);
+        assert_eq!(example_format_for("package.json"), ExampleFormat::Json);
+        assert_eq!(example_format_for("Dockerfile"), ExampleFormat::Json);
+    }
+
+    #[test]
+    fn cli_parses_output_markdown() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json", "--output", "markdown"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.output, ExplainFormat::Markdown);
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_output_html() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json", "--output", "html"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.output, ExplainFormat::Html);
+        Ok(())
+    }
+
+    #[test]
+    fn cli_output_defaults_to_terminal() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.output, ExplainFormat::Terminal);
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_depth() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json", "--depth", "5"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.depth, ExplainDepth::Levels(5));
+        assert_eq!(args.depth.max_depth(), Some(5));
+
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json", "--depth", "unlimited"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.depth.max_depth(), None);
+
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.depth.max_depth(), Some(3));
+
+        assert!(
+            test_cli()
+                .run_inner(&["--schema", "s.json", "--depth", "deep"])
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_at() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--file", "config.yaml", "--at", "42:7"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(
+            args.at,
+            Some(FilePosition {
+                line: 42,
+                column: 7
+            })
+        );
+        assert!(
+            test_cli()
+                .run_inner(&["--file", "config.yaml", "--at", "42"])
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_search() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["package.json", "--search", "licence"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(args.search.as_deref(), Some("licence"));
+        assert_eq!(args.positional.as_deref(), Some("package.json"));
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_interactive() -> anyhow::Result<()> {
+        let (_, args) = test_cli()
+            .run_inner(&["--schema", "s.json", "--interactive"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert!(args.interactive);
+        Ok(())
+    }
+
+    #[test]
+    fn cli_rejects_unknown_output() {
+        assert!(
+            test_cli()
+                .run_inner(&["--schema", "s.json", "--output", "pdf"])
+                .is_err()
+        );
+    }
+
     // --- positional-only usage ---
 
     #[test]
@@ -0,0 +1,199 @@
+//! Map a `LINE:COL` position in a data file to the property under it.
+//!
+//! The data file is parsed into a value, every key in it is located in the
+//! source text with [`find_instance_path_span`], and the key that starts
+//! closest before the position wins. This works the same way for every
+//! format the validator can parse, without needing a span-aware parser.
+
+use core::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use lintel_diagnostics::find_instance_path_span;
+use serde_json::Value;
+
+/// A 1-based `LINE:COL` position in a data file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl core::str::FromStr for FilePosition {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid position '{s}', expected: LINE:COL");
+        let (line, column) = s.split_once(':').ok_or_else(err)?;
+        let line: usize = line.parse().map_err(|_| err())?;
+        let column: usize = column.parse().map_err(|_| err())?;
+        if line == 0 || column == 0 {
+            return Err(err());
+        }
+        Ok(Self { line, column })
+    }
+}
+
+/// Parse `content` as the format implied by `file_name` and return the
+/// `JSONPath` of the property at `pos`, or `$` when the position is before
+/// the first key.
+///
+/// # Errors
+///
+/// Returns an error if the file format is unknown or the content fails to parse.
+pub(crate) fn jsonpath_at(content: &str, file_name: &str, pos: FilePosition) -> Result<String> {
+    let format = lintel_validate::parsers::detect_format(Path::new(file_name))
+        .with_context(|| format!("cannot determine the file format of {file_name}"))?;
+    let value = lintel_validate::parsers::parser_for(format)
+        .parse(content, file_name)
+        .map_err(|e| anyhow::anyhow!("failed to parse {file_name}: {e}"))?;
+    Ok(instance_path_at(content, &value, pos).map_or_else(|| "$".to_string(), |p| to_jsonpath(&p)))
+}
+
+/// Find the instance path (as segments) of the key that starts closest
+/// before `pos`.
+fn instance_path_at(content: &str, value: &Value, pos: FilePosition) -> Option<Vec<String>> {
+    let target = lintel_validate::parsers::line_col_to_offset(content, pos.line, pos.column);
+    let mut keys = Vec::new();
+    collect_keys(content, value, 0, &mut Vec::new(), &mut keys);
+    keys.into_iter()
+        .filter(|(start, _)| *start <= target)
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, segments)| segments)
+}
+
+/// Locate every object key in `value`, searching from byte `from`, and
+/// record its start offset with its path. Returns the furthest offset
+/// reached so the next array element is searched after this one.
+///
+/// Keys of the same object are all searched from the object's own start,
+/// so this does not depend on the parsed map preserving source order.
+#[allow(clippy::too_many_arguments)]
+fn collect_keys(
+    content: &str,
+    value: &Value,
+    from: usize,
+    prefix: &mut Vec<String>,
+    out: &mut Vec<(usize, Vec<String>)>,
+) -> usize {
+    match value {
+        Value::Object(map) => {
+            let mut end = from;
+            for (key, child) in map {
+                let Some((start, len)) = find_key(content, key, from) else {
+                    continue;
+                };
+                prefix.push(key.clone());
+                out.push((start, prefix.clone()));
+                end = end.max(collect_keys(content, child, start + len, prefix, out));
+                prefix.pop();
+            }
+            end
+        }
+        Value::Array(items) => {
+            let mut from = from;
+            for (i, child) in items.iter().enumerate() {
+                prefix.push(i.to_string());
+                from = collect_keys(content, child, from, prefix, out);
+                prefix.pop();
+            }
+            from
+        }
+        _ => from,
+    }
+}
+
+/// Find the span of `key` used as an object key at or after byte `from`.
+fn find_key(content: &str, key: &str, from: usize) -> Option<(usize, usize)> {
+    let rest = content.get(from..)?;
+    let pointer = format!("/{}", key.replace('~', "~0").replace('/', "~1"));
+    let (offset, len) = find_instance_path_span(rest, &pointer);
+    (len > 0).then_some((from + offset, len))
+}
+
+/// Render instance path segments as a `JSONPath` for [`crate::path::to_schema_pointer`].
+///
+/// Numeric segments are treated as array indices, which the schema pointer
+/// conversion maps to `items`.
+fn to_jsonpath(segments: &[String]) -> String {
+    let mut out = String::from("$");
+    for segment in segments {
+        if segment.parse::<usize>().is_ok() {
+            let _ = write!(out, "[{segment}]");
+        } else if segment.contains(['.', '[', ']', '"']) {
+            let quote = if segment.contains('"') { '\'' } else { '"' };
+            let _ = write!(out, "[{quote}{segment}{quote}]");
+        } else {
+            out.push('.');
+            out.push_str(segment);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn at(content: &str, file_name: &str, line: usize, column: usize) -> String {
+        jsonpath_at(content, file_name, FilePosition { line, column }).unwrap()
+    }
+
+    #[test]
+    fn parses_positions() {
+        assert_eq!(
+            "42:7".parse::<FilePosition>().unwrap(),
+            FilePosition {
+                line: 42,
+                column: 7
+            }
+        );
+        assert!("42".parse::<FilePosition>().is_err());
+        assert!("0:1".parse::<FilePosition>().is_err());
+        assert!("a:b".parse::<FilePosition>().is_err());
+    }
+
+    #[test]
+    fn yaml_nested_keys() {
+        let content = "name: demo\nserver:\n  host: localhost\n  port: 8080\nmode: dev\n";
+        assert_eq!(at(content, "c.yaml", 1, 3), "$.name");
+        assert_eq!(at(content, "c.yaml", 3, 9), "$.server.host");
+        assert_eq!(at(content, "c.yaml", 4, 3), "$.server.port");
+        assert_eq!(at(content, "c.yaml", 5, 1), "$.mode");
+    }
+
+    #[test]
+    fn yaml_sequence_items() {
+        let content = "steps:\n  - name: build\n    run: make\n  - name: test\n";
+        assert_eq!(at(content, "w.yaml", 3, 10), "$.steps[0].run");
+        assert_eq!(at(content, "w.yaml", 4, 6), "$.steps[1].name");
+    }
+
+    #[test]
+    fn json_single_line() {
+        let content = r#"{"a": {"b": 1}, "c": 2}"#;
+        assert_eq!(at(content, "x.json", 1, 9), "$.a.b");
+        assert_eq!(at(content, "x.json", 1, 20), "$.c");
+    }
+
+    #[test]
+    fn toml_tables() {
+        let content = "title = \"x\"\n\n[package]\nname = \"demo\"\n";
+        assert_eq!(at(content, "c.toml", 4, 8), "$.package.name");
+        assert_eq!(at(content, "c.toml", 3, 2), "$.package");
+    }
+
+    #[test]
+    fn before_first_key_is_root() {
+        let content = "# comment\nname: demo\n";
+        assert_eq!(at(content, "c.yaml", 1, 1), "$");
+    }
+
+    #[test]
+    fn unusual_keys_use_bracket_notation() {
+        assert_eq!(
+            to_jsonpath(&["a.b".to_string(), "0".to_string(), "c".to_string()]),
+            "$[\"a.b\"][0].c"
+        );
+    }
+}
@@ -1,9 +1,12 @@
 //! Schema resolution for files.
 //!
 //! Resolves a schema URI for a given file path using priority order:
-//! 1. Inline `$schema` / YAML modeline
+//! 1. Inline `$schema` / YAML modeline, else a `.lintel-schema` sidecar
 //! 2. Custom schema mappings from `lintel.toml [schemas]`
 //! 3. Catalog matching
+//!
+//! A `[schemas]` mapping with `priority = "high"` moves ahead of inline
+//! declarations; one with `priority = "low"` falls behind catalog matches.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -11,6 +14,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 
 use lintel_cli_common::CliCacheOptions;
+use lintel_config::{MappingPriority, SchemaMapping};
 use lintel_schema_cache::SchemaCache;
 use lintel_validate::parsers;
 use lintel_validate::validate;
@@ -24,6 +28,8 @@ use schema_catalog::{FileFormat, SchemaMatch};
 #[derive(Debug)]
 pub enum SchemaSource {
     Inline,
+    /// A `.lintel-schema` file in one of the file's parent directories.
+    Sidecar(PathBuf),
     Config,
     Catalog,
 }
@@ -32,6 +38,7 @@ impl core::fmt::Display for SchemaSource {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             SchemaSource::Inline => write!(f, "inline"),
+            SchemaSource::Sidecar(path) => write!(f, "sidecar {}", path.display()),
             SchemaSource::Config => write!(f, "config"),
             SchemaSource::Catalog => write!(f, "catalog"),
         }
@@ -102,6 +109,15 @@ pub fn build_retriever(cache: &CliCacheOptions) -> SchemaCache {
     if let Some(ttl) = cache.schema_cache_ttl {
         builder = builder.ttl(ttl);
     }
+    if let Some(timeout) = cache.fetch_timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(retries) = cache.fetch_retries {
+        builder = builder.retries(retries);
+    }
+    if let Some(backoff) = cache.fetch_retry_backoff {
+        builder = builder.retry_backoff(backoff);
+    }
     builder.build()
 }
 
@@ -144,48 +160,91 @@ pub async fn resolve_schema_for_content(
     cache: &CliCacheOptions,
 ) -> Result<Option<ResolvedFileSchema>> {
     let path_str = file_path.display().to_string();
-    let file_name = file_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or(&path_str);
-
     let retriever = build_retriever(cache);
 
     let search_dir = config_search_dir
         .map(Path::to_path_buf)
         .or_else(|| file_path.parent().map(Path::to_path_buf));
-    let (cfg, config_dir, _config_path) = validate::load_config(search_dir.as_deref());
-
-    let compiled_catalogs =
-        validate::fetch_compiled_catalogs(&retriever, &cfg, cache.no_catalog).await;
+    let file_resolver =
+        FileSchemaResolver::load(search_dir.as_deref(), &retriever, cache.no_catalog).await;
 
     let detected_format = parsers::detect_format(file_path);
     let (parser, instance) = parse_file(detected_format, content, &path_str);
 
-    let Some(resolved) = resolve_schema(
-        parser.as_ref(),
-        content,
-        &instance,
-        &path_str,
-        file_name,
-        &cfg,
-        &compiled_catalogs,
-    ) else {
-        return Ok(None);
-    };
+    Ok(file_resolver.resolve_parsed(parser.as_ref(), content, &instance, file_path))
+}
 
-    Ok(Some(build_resolved_file_schema(
-        resolved,
-        &cfg,
-        &config_dir,
-        file_path,
-        &compiled_catalogs,
-    )))
+/// `lintel.toml` and its compiled catalogs, loaded once to resolve the
+/// schemas of many files.
+pub struct FileSchemaResolver {
+    cfg: lintel_config::Config,
+    config_dir: PathBuf,
+    catalogs: Vec<schema_catalog::CompiledCatalog>,
+}
+
+impl FileSchemaResolver {
+    /// Load the `lintel.toml` found from `search_dir` (the current directory
+    /// when `None`) and fetch its catalogs, unless `no_catalog` is set.
+    pub async fn load(
+        search_dir: Option<&Path>,
+        retriever: &SchemaCache,
+        no_catalog: bool,
+    ) -> Self {
+        let (cfg, config_dir, _config_path) = validate::load_config(search_dir);
+        let catalogs =
+            validate::fetch_compiled_catalogs(retriever, &cfg, &config_dir, no_catalog).await;
+        Self {
+            cfg,
+            config_dir,
+            catalogs,
+        }
+    }
+
+    /// Resolve the schema of `content` read from `file_path`, in the order
+    /// [`resolve_schema_for_content`] uses. `None` when no schema applies or
+    /// the content cannot be parsed.
+    pub fn resolve(&self, content: &str, file_path: &Path) -> Option<ResolvedFileSchema> {
+        let path_str = file_path.display().to_string();
+        let (parser, instance) = try_parse(parsers::detect_format(file_path), content, &path_str)?;
+        self.resolve_parsed(parser.as_ref(), content, &instance, file_path)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_parsed(
+        &self,
+        parser: &dyn parsers::Parser,
+        content: &str,
+        instance: &serde_json::Value,
+        file_path: &Path,
+    ) -> Option<ResolvedFileSchema> {
+        let path_str = file_path.display().to_string();
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&path_str);
+        let resolved = resolve_schema(
+            parser,
+            content,
+            instance,
+            &path_str,
+            file_name,
+            &self.cfg,
+            &self.catalogs,
+        )?;
+        Some(build_resolved_file_schema(
+            resolved,
+            &self.cfg,
+            &self.config_dir,
+            file_path,
+            &self.catalogs,
+        ))
+    }
 }
 
 /// Resolve the schema URI for a file path using only path-based matching:
-/// 1. Custom schema mappings from `lintel.toml [schemas]`
-/// 2. Catalog matching
+/// 1. A `.lintel-schema` sidecar
+/// 2. Custom schema mappings from `lintel.toml [schemas]`
+/// 3. Catalog matching
 ///
 /// Unlike [`resolve_schema_for_file`], this does NOT read the file or check
 /// for inline `$schema` directives. The file does not need to exist.
@@ -205,24 +264,24 @@ pub async fn resolve_schema_for_path(
         .unwrap_or(&path_str);
 
     let retriever = build_retriever(cache);
+    let file_resolver =
+        FileSchemaResolver::load(file_path.parent(), &retriever, cache.no_catalog).await;
 
-    let config_search_dir = file_path.parent().map(Path::to_path_buf);
-    let (cfg, config_dir, _config_path) = validate::load_config(config_search_dir.as_deref());
-
-    let compiled_catalogs =
-        validate::fetch_compiled_catalogs(&retriever, &cfg, cache.no_catalog).await;
-
-    let Some(resolved) = resolve_schema_path_only(&path_str, file_name, &cfg, &compiled_catalogs)
-    else {
+    let Some(resolved) = resolve_schema_path_only(
+        &path_str,
+        file_name,
+        &file_resolver.cfg,
+        &file_resolver.catalogs,
+    ) else {
         return Ok(None);
     };
 
     Ok(Some(build_resolved_file_schema(
         resolved,
-        &cfg,
-        &config_dir,
+        &file_resolver.cfg,
+        &file_resolver.config_dir,
         file_path,
-        &compiled_catalogs,
+        &file_resolver.catalogs,
     )))
 }
 
@@ -239,7 +298,11 @@ fn build_resolved_file_schema(
     file_path: &Path,
     compiled_catalogs: &[schema_catalog::CompiledCatalog],
 ) -> ResolvedFileSchema {
-    let from_inline = matches!(resolved.source, SchemaSource::Inline);
+    // Sidecar paths are already absolute.
+    let from_inline = matches!(
+        resolved.source,
+        SchemaSource::Inline | SchemaSource::Sidecar(_)
+    );
     let (schema_uri, is_remote) = finalize_uri(
         &resolved.uri,
         &cfg.rewrite,
@@ -266,7 +329,7 @@ fn build_resolved_file_schema(
             .catalog_match
             .as_ref()
             .map(|m| m.matched_pattern.to_string()),
-        SchemaSource::Inline => None,
+        SchemaSource::Inline | SchemaSource::Sidecar(_) => None,
     };
 
     let file_match = resolved
@@ -302,7 +365,10 @@ fn resolve_schema<'a>(
     cfg: &'a lintel_config::Config,
     catalogs: &'a [schema_catalog::CompiledCatalog],
 ) -> Option<ResolvedSchema<'a>> {
-    if let Some(uri) = parser.extract_schema_uri(content, instance) {
+    let overrides_inline = cfg
+        .find_schema_mapping_entry(path_str, file_name)
+        .is_some_and(|(_, m)| m.priority() == MappingPriority::High);
+    if !overrides_inline && let Some(uri) = parser.extract_schema_uri(content, instance) {
         return Some(ResolvedSchema {
             uri,
             source: SchemaSource::Inline,
@@ -314,29 +380,34 @@ fn resolve_schema<'a>(
     resolve_schema_path_only(path_str, file_name, cfg, catalogs)
 }
 
-/// Try config mappings and catalog matching only (no inline `$schema`).
+/// Try sidecars, config mappings and catalog matching only (no inline
+/// `$schema`).
 fn resolve_schema_path_only<'a>(
     path_str: &str,
     file_name: &'a str,
     cfg: &'a lintel_config::Config,
     catalogs: &'a [schema_catalog::CompiledCatalog],
 ) -> Option<ResolvedSchema<'a>> {
-    if let Some((pattern, url)) = cfg
-        .schemas
-        .iter()
-        .find(|(pattern, _)| {
-            let p = path_str.strip_prefix("./").unwrap_or(path_str);
-            glob_matcher::glob_match(pattern, p) || glob_matcher::glob_match(pattern, file_name)
-        })
-        .map(|(pattern, url)| (pattern.as_str(), url.as_str()))
+    let mapping = cfg.find_schema_mapping_entry(path_str, file_name);
+    if mapping.is_none_or(|(_, m)| m.priority() != MappingPriority::High)
+        && let Some(sidecar) = lintel_config::sidecar::find_sidecar_schema(Path::new(path_str))
     {
         return Some(ResolvedSchema {
-            uri: url.to_string(),
-            source: SchemaSource::Config,
+            uri: sidecar.uri,
+            source: SchemaSource::Sidecar(sidecar.sidecar),
             catalog_match: None,
-            config_pattern: Some(pattern),
+            config_pattern: None,
         });
     }
+    let from_mapping = |(pattern, m): (&'a str, &SchemaMapping)| ResolvedSchema {
+        uri: m.url().to_string(),
+        source: SchemaSource::Config,
+        catalog_match: None,
+        config_pattern: Some(pattern),
+    };
+    if let Some(m) = mapping.filter(|(_, m)| m.priority() != MappingPriority::Low) {
+        return Some(from_mapping(m));
+    }
 
     catalogs
         .iter()
@@ -347,6 +418,7 @@ fn resolve_schema_path_only<'a>(
             catalog_match: Some(schema_match.into()),
             config_pattern: None,
         })
+        .or_else(|| mapping.map(from_mapping))
 }
 
 /// Apply rewrites, resolve relative paths, and determine whether the URI is remote.
@@ -383,32 +455,34 @@ fn finalize_uri(
 }
 
 /// Parse the file content, trying the detected format first, then all parsers as fallback.
-///
-/// Exits the process when the file cannot be parsed.
-fn parse_file(
+fn try_parse(
     detected_format: Option<FileFormat>,
     content: &str,
     path_str: &str,
-) -> (Box<dyn parsers::Parser>, serde_json::Value) {
+) -> Option<(Box<dyn parsers::Parser>, serde_json::Value)> {
     if let Some(fmt) = detected_format {
         let parser = parsers::parser_for(fmt);
         if let Ok(val) = parser.parse(content, path_str) {
-            return (parser, val);
+            return Some((parser, val));
         }
-        // Try all parsers as fallback
-        if let Some((fmt, val)) = validate::try_parse_all(content, path_str) {
-            return (parsers::parser_for(fmt), val);
-        }
-        eprintln!("{path_str}");
-        eprintln!("  no schema found (file could not be parsed)");
-        std::process::exit(0);
     }
+    validate::try_parse_all(content, path_str).map(|(fmt, val)| (parsers::parser_for(fmt), val))
+}
 
-    if let Some((fmt, val)) = validate::try_parse_all(content, path_str) {
-        return (parsers::parser_for(fmt), val);
+/// Like [`try_parse`], but exits the process when the file cannot be parsed.
+fn parse_file(
+    detected_format: Option<FileFormat>,
+    content: &str,
+    path_str: &str,
+) -> (Box<dyn parsers::Parser>, serde_json::Value) {
+    if let Some(parsed) = try_parse(detected_format, content, path_str) {
+        return parsed;
     }
-
     eprintln!("{path_str}");
-    eprintln!("  no schema found (unrecognized format)");
+    if detected_format.is_some() {
+        eprintln!("  no schema found (file could not be parsed)");
+    } else {
+        eprintln!("  no schema found (unrecognized format)");
+    }
     std::process::exit(0);
 }
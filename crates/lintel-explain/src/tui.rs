@@ -0,0 +1,638 @@
+//! Interactive tree browser for `lintel explain --interactive`.
+//!
+//! The [`Browser`] holds the navigation state (visible rows, selection,
+//! search) and is independent of the terminal; [`browse`] drives it with
+//! `ratatui`. Rows are addressed by schema JSON Pointers and expanded
+//! lazily through [`jsonschema_explain::navigate_pointer`], so recursive
+//! `$ref`s only cost what the user actually opens.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use jsonschema_schema::{Schema, SchemaValue};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+
+/// Maximum depth searched when looking for a property by name.
+const SEARCH_DEPTH: usize = 16;
+
+/// One visible line in the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Row {
+    name: String,
+    pointer: String,
+    depth: usize,
+    expanded: bool,
+    has_children: bool,
+    required: bool,
+    ty: Option<String>,
+}
+
+/// Navigation state for the tree browser.
+pub(crate) struct Browser<'a> {
+    root: &'a SchemaValue,
+    start: String,
+    name: String,
+    rows: Vec<Row>,
+    selected: usize,
+}
+
+fn escape_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn schema_at<'a>(root: &'a SchemaValue, pointer: &str) -> Option<&'a Schema> {
+    let sv = jsonschema_explain::navigate_pointer(root, root, pointer).ok()?;
+    jsonschema_explain::resolve_schema_ref(sv, root).as_schema()
+}
+
+/// Child rows of the node at `pointer`: its properties, or a single `[]`
+/// row for array item schemas.
+fn children(root: &SchemaValue, pointer: &str, depth: usize) -> Vec<Row> {
+    let Some(schema) = schema_at(root, pointer) else {
+        return Vec::new();
+    };
+    let required = schema.required_set();
+    let mut rows: Vec<Row> = schema
+        .properties
+        .keys()
+        .map(|name| {
+            let child = format!("{pointer}/properties/{}", escape_segment(name));
+            make_row(root, name.clone(), child, depth, required.contains(name))
+        })
+        .collect();
+    if rows.is_empty() && schema.items.is_some() {
+        rows.push(make_row(
+            root,
+            "[]".to_string(),
+            format!("{pointer}/items"),
+            depth,
+            false,
+        ));
+    }
+    rows
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_row(
+    root: &SchemaValue,
+    name: String,
+    pointer: String,
+    depth: usize,
+    required: bool,
+) -> Row {
+    let schema = schema_at(root, &pointer);
+    Row {
+        has_children: schema.is_some_and(|s| !s.properties.is_empty() || s.items.is_some()),
+        ty: schema.and_then(Schema::type_str),
+        name,
+        pointer,
+        depth,
+        expanded: false,
+        required,
+    }
+}
+
+/// Pointer chains (top-level first) to every property whose name contains
+/// `query`, case-insensitively. The target of each `$ref` is walked once,
+/// where the search first reaches it: recursive `$ref`s stay finite, and
+/// definitions shared by many properties, as in Kubernetes schemas, are not
+/// searched again for each of them.
+fn find_matches(root: &SchemaValue, start: &str, query: &str) -> Vec<Vec<String>> {
+    #[allow(clippy::too_many_arguments)]
+    fn walk(
+        root: &SchemaValue,
+        pointer: &str,
+        query: &str,
+        chain: &mut Vec<String>,
+        visited: &mut HashSet<*const SchemaValue>,
+        out: &mut Vec<Vec<String>>,
+    ) {
+        if chain.len() >= SEARCH_DEPTH {
+            return;
+        }
+        let Ok(sv) = jsonschema_explain::navigate_pointer(root, root, pointer) else {
+            return;
+        };
+        // `navigate_pointer` resolves `$ref`s, so every property that refers
+        // to a definition lands on the same node.
+        if !visited.insert(core::ptr::from_ref(sv)) {
+            return;
+        }
+        for row in children(root, pointer, chain.len()) {
+            chain.push(row.pointer.clone());
+            if row.name.to_lowercase().contains(query) {
+                out.push(chain.clone());
+            }
+            walk(root, &row.pointer, query, chain, visited, out);
+            chain.pop();
+        }
+    }
+
+    let mut out = Vec::new();
+    let query = query.to_lowercase();
+    if !query.is_empty() {
+        walk(
+            root,
+            start,
+            &query,
+            &mut Vec::new(),
+            &mut HashSet::new(),
+            &mut out,
+        );
+    }
+    out
+}
+
+impl<'a> Browser<'a> {
+    /// Start browsing at `pointer` (empty for the schema root).
+    pub(crate) fn new(root: &'a SchemaValue, pointer: &str, name: &str) -> Self {
+        Self {
+            root,
+            start: pointer.to_string(),
+            name: name.to_string(),
+            rows: children(root, pointer, 0),
+            selected: 0,
+        }
+    }
+
+    fn selected_row(&self) -> Option<&Row> {
+        self.rows.get(self.selected)
+    }
+
+    /// Pointer of the selected row.
+    pub(crate) fn selected_pointer(&self) -> Option<&str> {
+        self.selected_row().map(|r| r.pointer.as_str())
+    }
+
+    pub(crate) fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(crate) fn move_down(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Expand the selected row if it has children.
+    pub(crate) fn expand(&mut self) {
+        self.expand_at(self.selected);
+    }
+
+    fn expand_at(&mut self, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        if row.expanded || !row.has_children {
+            return;
+        }
+        let kids = children(self.root, &row.pointer, row.depth + 1);
+        self.rows[idx].expanded = true;
+        let tail = self.rows.split_off(idx + 1);
+        self.rows.extend(kids);
+        self.rows.extend(tail);
+    }
+
+    /// Collapse the selected row, or jump to its parent if already collapsed.
+    pub(crate) fn collapse(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        if row.expanded {
+            let depth = row.depth;
+            let end = self.rows[self.selected + 1..]
+                .iter()
+                .position(|r| r.depth <= depth)
+                .map_or(self.rows.len(), |p| self.selected + 1 + p);
+            self.rows.drain(self.selected + 1..end);
+            self.rows[self.selected].expanded = false;
+        } else if let Some(parent) = self.parent_index(self.selected) {
+            self.selected = parent;
+        }
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        if self.selected_row().is_some_and(|r| r.expanded) {
+            self.collapse();
+        } else {
+            self.expand();
+        }
+    }
+
+    fn parent_index(&self, idx: usize) -> Option<usize> {
+        let depth = self.rows.get(idx)?.depth;
+        self.rows[..idx].iter().rposition(|r| r.depth < depth)
+    }
+
+    /// Names from the schema root down to the selected row.
+    pub(crate) fn breadcrumbs(&self) -> Vec<String> {
+        let mut crumbs = Vec::new();
+        let mut idx = Some(self.selected);
+        while let Some(i) = idx.filter(|&i| i < self.rows.len()) {
+            crumbs.push(self.rows[i].name.clone());
+            idx = self.parent_index(i);
+        }
+        crumbs.push(self.name.clone());
+        crumbs.reverse();
+        crumbs
+    }
+
+    /// Select the next property (after the current one, wrapping around)
+    /// whose name contains `query`, expanding its ancestors. Returns `false`
+    /// when nothing matches.
+    pub(crate) fn search(&mut self, query: &str) -> bool {
+        let matches = find_matches(self.root, &self.start, query);
+        let current = self.selected_pointer().unwrap_or_default().to_string();
+        let pos = matches
+            .iter()
+            .position(|chain| chain.last().is_some_and(|p| *p == current));
+        let next = match pos {
+            Some(p) => matches.get(p + 1).or_else(|| matches.first()),
+            None => matches
+                .iter()
+                .find(|chain| self.is_after_selection(chain))
+                .or_else(|| matches.first()),
+        };
+        let Some(chain) = next.cloned() else {
+            return false;
+        };
+        self.reveal(&chain);
+        true
+    }
+
+    fn is_after_selection(&self, chain: &[String]) -> bool {
+        chain.last().is_some_and(|p| {
+            self.rows
+                .iter()
+                .position(|r| r.pointer == *p)
+                .is_some_and(|i| i > self.selected)
+        })
+    }
+
+    /// Expand every ancestor in `chain` and select its last entry.
+    fn reveal(&mut self, chain: &[String]) {
+        for pointer in chain {
+            let Some(idx) = self.rows.iter().position(|r| r.pointer == *pointer) else {
+                return;
+            };
+            self.selected = idx;
+            if Some(pointer) != chain.last() {
+                self.expand_at(idx);
+            }
+        }
+    }
+}
+
+/// Run the interactive browser until the user quits.
+///
+/// Keys: `↑`/`k` and `↓`/`j` move, `→`/`l`/`Enter` expand, `←`/`h`
+/// collapse, `Space` toggles, `/` searches, `n` repeats the search and
+/// `q`/`Esc`/`Ctrl-C` quits.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be drawn to or read from.
+pub(crate) fn browse(root: &SchemaValue, pointer: &str, name: &str) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let _restore = RestoreTerminal;
+    event_loop(&mut terminal, &mut Browser::new(root, pointer, name))
+}
+
+/// Restores the terminal when dropped, so neither an error nor a panic
+/// leaves it in raw mode.
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+/// Whether `key` is `Ctrl-C`, which raw mode delivers as a key press
+/// instead of a signal.
+fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Text typed after `/`, while the search prompt is open.
+enum Prompt {
+    Closed,
+    Open(String),
+}
+
+/// The explanation of the selected row, rendered again only when the
+/// selection or the width of its pane changes.
+#[derive(Default)]
+struct Detail {
+    key: Option<(String, usize)>,
+    text: String,
+}
+
+impl Detail {
+    fn of(&mut self, root: &SchemaValue, row: &Row, width: usize) -> &str {
+        let key = (row.pointer.clone(), width);
+        if self.key.as_ref() != Some(&key) {
+            let opts = jsonschema_explain::ExplainOptions {
+                color: false,
+                syntax_highlight: false,
+                width,
+                validation_errors: Vec::new(),
+                extended: false,
+                max_depth: Some(jsonschema_explain::DEFAULT_MAX_DEPTH),
+                example_format: jsonschema_explain::ExampleFormat::Json,
+                annotations: Vec::new(),
+                required_only: false,
+                property_filter: None,
+                exclude_deprecated: false,
+            };
+            self.text = jsonschema_explain::explain_at_path(root, &row.pointer, &row.name, &opts)
+                .unwrap_or_else(|e| e);
+            self.key = Some(key);
+        }
+        &self.text
+    }
+}
+
+fn event_loop(terminal: &mut ratatui::DefaultTerminal, browser: &mut Browser<'_>) -> Result<()> {
+    let mut detail = Detail::default();
+    let mut prompt = Prompt::Closed;
+    let mut query = String::new();
+    let mut status = String::from("/ search  n next  space toggle  q/ctrl-c quit");
+
+    loop {
+        terminal.draw(|frame| draw(frame, browser, &mut detail, &prompt, &status))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_interrupt(&key) {
+            return Ok(());
+        }
+
+        if let Prompt::Open(text) = &mut prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    query = core::mem::take(text);
+                    prompt = Prompt::Closed;
+                    status = search_status(browser, &query);
+                }
+                KeyCode::Esc => prompt = Prompt::Closed,
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => browser.move_up(),
+            KeyCode::Down | KeyCode::Char('j') => browser.move_down(),
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => browser.expand(),
+            KeyCode::Left | KeyCode::Char('h') => browser.collapse(),
+            KeyCode::Char(' ') => browser.toggle(),
+            KeyCode::Char('/') => prompt = Prompt::Open(String::new()),
+            KeyCode::Char('n') if !query.is_empty() => status = search_status(browser, &query),
+            _ => {}
+        }
+    }
+}
+
+fn search_status(browser: &mut Browser<'_>, query: &str) -> String {
+    if browser.search(query) {
+        format!("/{query}")
+    } else {
+        format!("no property matches '{query}'")
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw(
+    frame: &mut ratatui::Frame<'_>,
+    browser: &Browser<'_>,
+    detail: &mut Detail,
+    prompt: &Prompt,
+    status: &str,
+) {
+    let [crumbs_area, body_area, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [tree_area, detail_area] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .areas(body_area);
+
+    let crumbs = browser.breadcrumbs().join(" › ");
+    frame.render_widget(
+        Paragraph::new(crumbs).style(Style::new().add_modifier(Modifier::BOLD)),
+        crumbs_area,
+    );
+
+    let items: Vec<ListItem<'_>> = browser.rows.iter().map(row_item).collect();
+    let list = List::new(items)
+        .block(Block::new().borders(Borders::RIGHT))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(browser.selected));
+    frame.render_stateful_widget(list, tree_area, &mut state);
+
+    let width = usize::from(detail_area.width.saturating_sub(1));
+    let text = browser
+        .selected_row()
+        .map(|row| detail.of(browser.root, row, width))
+        .unwrap_or_default();
+    frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), detail_area);
+
+    let status_line = match prompt {
+        Prompt::Open(text) => format!("/{text}"),
+        Prompt::Closed => status.to_string(),
+    };
+    frame.render_widget(Paragraph::new(status_line), status_area);
+}
+
+fn row_item(row: &Row) -> ListItem<'_> {
+    let marker = match (row.has_children, row.expanded) {
+        (false, _) => "  ",
+        (true, false) => "▸ ",
+        (true, true) => "▾ ",
+    };
+    let mut spans = vec![
+        Span::raw("  ".repeat(row.depth)),
+        Span::raw(marker),
+        Span::styled(row.name.as_str(), Style::new().add_modifier(Modifier::BOLD)),
+    ];
+    if let Some(ty) = &row.ty {
+        spans.push(Span::raw(format!(" ({ty})")));
+    }
+    if row.required {
+        spans.push(Span::raw(" *"));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sv(val: serde_json::Value) -> SchemaValue {
+        SchemaValue::Schema(Box::new(jsonschema_migrate::migrate(val).unwrap()))
+    }
+
+    fn names<'b>(browser: &'b Browser<'_>) -> Vec<&'b str> {
+        browser.rows.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn ctrl_c_interrupts() {
+        assert!(is_interrupt(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(!is_interrupt(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::NONE
+        )));
+    }
+
+    fn sample() -> SchemaValue {
+        sv(json!({
+            "type": "object",
+            "required": ["spec"],
+            "properties": {
+                "metadata": { "type": "object", "properties": { "name": { "type": "string" } } },
+                "spec": {
+                    "type": "object",
+                    "properties": {
+                        "containers": { "type": "array", "items": { "$ref": "#/$defs/Container" } }
+                    }
+                }
+            },
+            "$defs": {
+                "Container": {
+                    "type": "object",
+                    "properties": {
+                        "image": { "type": "string" },
+                        "sidecar": { "$ref": "#/$defs/Container" }
+                    }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn starts_with_top_level_properties() {
+        let root = sample();
+        let browser = Browser::new(&root, "", "pod.json");
+        assert_eq!(names(&browser), vec!["metadata", "spec"]);
+        assert!(browser.rows[1].required);
+        assert_eq!(browser.rows[1].ty.as_deref(), Some("object"));
+    }
+
+    #[test]
+    fn expand_and_collapse() {
+        let root = sample();
+        let mut browser = Browser::new(&root, "", "pod.json");
+        browser.move_down();
+        browser.expand();
+        assert_eq!(names(&browser), vec!["metadata", "spec", "containers"]);
+        browser.move_down();
+        browser.expand();
+        assert_eq!(
+            names(&browser),
+            vec!["metadata", "spec", "containers", "[]"]
+        );
+        assert_eq!(
+            browser.breadcrumbs(),
+            vec!["pod.json", "spec", "containers"]
+        );
+
+        // Collapsing an already-collapsed row moves to its parent.
+        browser.move_down();
+        browser.collapse();
+        assert_eq!(
+            browser.selected_pointer(),
+            Some("/properties/spec/properties/containers")
+        );
+        browser.collapse();
+        assert_eq!(names(&browser), vec!["metadata", "spec", "containers"]);
+        browser.move_up();
+        browser.toggle();
+        assert_eq!(names(&browser), vec!["metadata", "spec"]);
+    }
+
+    #[test]
+    fn search_reveals_nested_match() {
+        let root = sample();
+        let mut browser = Browser::new(&root, "", "pod.json");
+        assert!(browser.search("IMAGE"));
+        assert_eq!(
+            browser.selected_pointer(),
+            Some("/properties/spec/properties/containers/items/properties/image")
+        );
+        assert_eq!(
+            browser.breadcrumbs(),
+            vec!["pod.json", "spec", "containers", "[]", "image"]
+        );
+        assert!(!browser.search("nonexistent"));
+    }
+
+    #[test]
+    fn search_terminates_on_recursive_refs() {
+        let root = sample();
+        let matches = find_matches(&root, "", "sidecar");
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn search_walks_shared_refs_once() {
+        let root = sv(json!({
+            "properties": {
+                "a": { "$ref": "#/$defs/Meta" },
+                "b": { "$ref": "#/$defs/Meta" },
+                "c": { "$ref": "#/$defs/Meta" }
+            },
+            "$defs": {
+                "Meta": { "type": "object", "properties": { "labels": { "type": "object" } } }
+            }
+        }));
+        let matches = find_matches(&root, "", "labels");
+        assert_eq!(
+            matches,
+            vec![vec![
+                "/properties/a".to_string(),
+                "/properties/a/properties/labels".to_string()
+            ]]
+        );
+    }
+
+    #[test]
+    fn detail_is_rendered_once_per_row() {
+        let root = sample();
+        let browser = Browser::new(&root, "", "pod.json");
+        let row = browser.selected_row().unwrap();
+        let mut detail = Detail::default();
+        assert!(detail.of(&root, row, 60).contains("metadata"));
+        detail.text = "cached".to_string();
+        assert_eq!(detail.of(&root, row, 60), "cached");
+        assert_ne!(detail.of(&root, row, 40), "cached");
+    }
+
+    #[test]
+    fn starts_at_pointer() {
+        let root = sample();
+        let browser = Browser::new(&root, "/properties/metadata", "metadata");
+        assert_eq!(names(&browser), vec!["name"]);
+        assert_eq!(browser.breadcrumbs(), vec!["metadata", "name"]);
+    }
+}
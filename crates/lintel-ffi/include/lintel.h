@@ -0,0 +1,40 @@
+/*
+ * C ABI for embedding Lintel. See the lintel-ffi README for details.
+ *
+ * Strings are UTF-8 and NUL-terminated. Every non-NULL `char *` returned by
+ * these functions is owned by the caller and must be released with
+ * `lintel_string_free`. Functions returning NULL record an error message
+ * that `lintel_last_error` returns.
+ */
+
+#ifndef LINTEL_H
+#define LINTEL_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Validate the document at `path`, or `content` as if it were that file
+ * when it is not NULL. Returns the JSON report of `lintel ci --output json`. */
+char *lintel_validate(const char *path, const char *content);
+
+/* Format the document at `path`, or `content`. Returns the formatted text,
+ * which equals the input when it is already formatted. */
+char *lintel_format(const char *path, const char *content);
+
+/* Render the documentation of the schema that applies to the document at
+ * `path`, or `content`, as plain text. */
+char *lintel_explain(const char *path, const char *content);
+
+/* The error message of the last call on this thread that returned NULL, or
+ * NULL if there was none. */
+char *lintel_last_error(void);
+
+/* Free a string returned by this library. NULL is ignored. */
+void lintel_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LINTEL_H */
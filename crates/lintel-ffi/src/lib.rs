@@ -0,0 +1,280 @@
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+use alloc::ffi::CString;
+use core::cell::RefCell;
+use core::ffi::{CStr, c_char};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, bail};
+use lintel_schema_cache::SchemaCache;
+
+thread_local! {
+    /// The error of the last call on this thread that returned null.
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The runtime every call blocks on.
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the tokio runtime")
+    })
+}
+
+/// The schema cache shared by every call, so each schema is downloaded once
+/// per process.
+fn schema_cache() -> &'static SchemaCache {
+    static CACHE: OnceLock<SchemaCache> = OnceLock::new();
+    CACHE.get_or_init(|| SchemaCache::builder().build())
+}
+
+/// Run `f`, returning its string to the caller or recording its error and
+/// returning null. A panic is recorded as an error too, since unwinding
+/// into the caller is undefined behavior.
+fn ffi_call(f: impl FnOnce() -> Result<String>) -> *mut c_char {
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(anyhow::anyhow!("panicked: {}", panic_message(&*payload))))
+        .and_then(|s| CString::new(s).context("result contains a NUL byte"));
+    LAST_ERROR.with(|last| match result {
+        Ok(s) => {
+            *last.borrow_mut() = None;
+            s.into_raw()
+        }
+        Err(e) => {
+            *last.borrow_mut() = Some(format!("{e:#}"));
+            core::ptr::null_mut()
+        }
+    })
+}
+
+/// The message of a caught panic.
+fn panic_message(payload: &(dyn core::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Read a required string argument.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string that outlives the
+/// call.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        bail!("{name} is null");
+    }
+    // SAFETY: non-null, and NUL-terminated per the caller's contract.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .with_context(|| format!("{name} is not valid UTF-8"))
+}
+
+/// Read the document a call is about: `content` when given, otherwise the
+/// file at `path`.
+///
+/// # Safety
+///
+/// As for [`str_arg`], for both pointers.
+unsafe fn document(path: *const c_char, content: *const c_char) -> Result<(PathBuf, String)> {
+    // SAFETY: forwarded from the caller.
+    let path = PathBuf::from(unsafe { str_arg(path, "path") }?);
+    let content = if content.is_null() {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+    } else {
+        // SAFETY: forwarded from the caller.
+        unsafe { str_arg(content, "content") }?.to_string()
+    };
+    Ok((path, content))
+}
+
+/// The directory to search for `lintel.toml` from: the document's own,
+/// when it exists.
+fn config_dir(path: &Path) -> Option<PathBuf> {
+    path.parent()
+        .filter(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn validate_document(path: &Path, content: String) -> Result<String> {
+    let mut builder = lintel::Validator::builder().cache(schema_cache().clone());
+    if let Some(dir) = config_dir(path) {
+        builder = builder.config_dir(dir);
+    }
+    let validator = builder.build();
+    let result = runtime().block_on(validator.validate_str(path, content))?;
+    Ok(lintel_reporters::reporters::json::report_json(&result).to_string())
+}
+
+fn format_document(path: &Path, content: String) -> Result<String> {
+    let (config, _, _) = lintel_validate::validate::load_config(config_dir(path).as_deref());
+    let cfg = lintel_format::format_config_from_lintel(&config);
+    Ok(lintel_format::format_content(path, &content, &cfg)?.unwrap_or(content))
+}
+
+fn explain_document(path: &Path, content: &str, cache: &SchemaCache) -> Result<String> {
+    let schema = runtime().block_on(async {
+        let resolver =
+            lintel_explain::FileSchemaResolver::load(config_dir(path).as_deref(), cache, false)
+                .await;
+        let Some(source) = resolver.resolve(content, path) else {
+            bail!("no schema applies to {}", path.display());
+        };
+        lintel_explain::fetch_schema_with(cache, &source.schema_uri, source.is_remote).await
+    })?;
+    let schema = jsonschema_schema::SchemaValue::Schema(Box::new(schema));
+    let name = path.display().to_string();
+    let opts = jsonschema_explain::ExplainOptions {
+        color: false,
+        syntax_highlight: false,
+        width: 80,
+        validation_errors: vec![],
+        extended: false,
+        max_depth: Some(jsonschema_explain::DEFAULT_MAX_DEPTH),
+        example_format: lintel_explain::example_format_for(&name),
+        annotations: vec![],
+        required_only: false,
+        property_filter: None,
+        exclude_deprecated: false,
+    };
+    Ok(jsonschema_explain::explain(&schema, &name, &opts))
+}
+
+/// Validate the document at `path`, or `content` as if it were that file
+/// when it is not null, returning the JSON report of
+/// `lintel ci --output json`.
+///
+/// # Safety
+///
+/// `path` must point to a NUL-terminated string, and `content` must be null
+/// or point to one. The result must be freed with [`lintel_string_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintel_validate(
+    path: *const c_char,
+    content: *const c_char,
+) -> *mut c_char {
+    ffi_call(|| {
+        // SAFETY: forwarded from the caller.
+        let (path, content) = unsafe { document(path, content) }?;
+        validate_document(&path, content)
+    })
+}
+
+/// Format the document at `path`, or `content`, returning the formatted
+/// text. Already formatted input is returned unchanged.
+///
+/// # Safety
+///
+/// As for [`lintel_validate`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintel_format(path: *const c_char, content: *const c_char) -> *mut c_char {
+    ffi_call(|| {
+        // SAFETY: forwarded from the caller.
+        let (path, content) = unsafe { document(path, content) }?;
+        format_document(&path, content)
+    })
+}
+
+/// Render the documentation of the schema that applies to the document at
+/// `path`, or `content`, as plain text.
+///
+/// # Safety
+///
+/// As for [`lintel_validate`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintel_explain(
+    path: *const c_char,
+    content: *const c_char,
+) -> *mut c_char {
+    ffi_call(|| {
+        // SAFETY: forwarded from the caller.
+        let (path, content) = unsafe { document(path, content) }?;
+        explain_document(&path, &content, schema_cache())
+    })
+}
+
+/// The error message of the last call on this thread that returned null,
+/// or null if there was none. The result must be freed with
+/// [`lintel_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn lintel_last_error() -> *mut c_char {
+    LAST_ERROR
+        .with(|last| last.borrow().clone())
+        .and_then(|message| CString::new(message).ok())
+        .map_or(core::ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that has not been
+/// freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lintel_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: `s` came from `CString::into_raw` per the caller's contract.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take ownership of a returned string.
+    fn take(ptr: *mut c_char) -> Option<String> {
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: returned by this library and freed here.
+        let s = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().to_string();
+        unsafe { lintel_string_free(ptr) };
+        Some(s)
+    }
+
+    fn c(s: &str) -> CString {
+        CString::new(s).expect("test strings have no NUL bytes")
+    }
+
+    #[test]
+    fn formats_over_the_c_abi() {
+        let path = c("app.json");
+        let content = c("{\"a\":1}");
+        // SAFETY: valid NUL-terminated strings.
+        let formatted = take(unsafe { lintel_format(path.as_ptr(), content.as_ptr()) });
+        assert!(formatted.is_some_and(|f| f.contains("\"a\": 1")));
+        assert_eq!(take(lintel_last_error()), None);
+    }
+
+    #[test]
+    fn records_the_error_of_a_failed_call() {
+        let missing = c("/nonexistent/app.json");
+        // SAFETY: null is allowed for `content`.
+        let result = unsafe { lintel_format(missing.as_ptr(), core::ptr::null()) };
+        assert!(result.is_null());
+        let error = take(lintel_last_error()).unwrap_or_default();
+        assert!(error.starts_with("failed to read /nonexistent/app.json"));
+
+        // SAFETY: null `path` is rejected before it is read.
+        let result = unsafe { lintel_validate(core::ptr::null(), core::ptr::null()) };
+        assert!(result.is_null());
+        assert_eq!(take(lintel_last_error()).as_deref(), Some("path is null"));
+    }
+
+    #[test]
+    fn records_a_panic_as_an_error() {
+        let result = ffi_call(|| panic!("boom"));
+        assert!(result.is_null());
+        assert_eq!(take(lintel_last_error()).as_deref(), Some("panicked: boom"));
+    }
+}
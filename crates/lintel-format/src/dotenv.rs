@@ -0,0 +1,138 @@
+//! `.env` (dotenv) formatting.
+//!
+//! Normalizes `KEY = value` to `KEY=value`, `export   KEY` to `export KEY`,
+//! unindents keys and comments, and collapses repeated blank lines. Values
+//! are never rewritten, and multi-line quoted values are kept verbatim.
+
+use anyhow::Result;
+
+/// Format dotenv content. Returns `Ok(None)` if the content is already
+/// formatted.
+///
+/// # Errors
+///
+/// Returns an error for a line that is neither a comment nor a
+/// `KEY=value` assignment.
+pub fn format_text(content: &str) -> Result<Option<String>> {
+    let mut out = String::with_capacity(content.len());
+    let mut pending_blank = false;
+    let mut open_quote: Option<char> = None;
+
+    for (i, raw) in content.lines().enumerate() {
+        if let Some(quote) = open_quote {
+            out.push_str(raw);
+            out.push('\n');
+            if closing_quote(raw, quote).is_some() {
+                open_quote = None;
+            }
+            continue;
+        }
+
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+        if pending_blank {
+            out.push('\n');
+            pending_blank = false;
+        }
+        if trimmed.starts_with('#') {
+            out.push_str(trimmed);
+            out.push('\n');
+            continue;
+        }
+
+        // Only trim the start so an unterminated quoted value keeps its
+        // trailing whitespace.
+        let line = raw.trim_start();
+        let (export, assignment) = match line.strip_prefix("export") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => (true, rest.trim_start()),
+            _ => (false, line),
+        };
+        let Some((key, value)) = assignment.split_once('=') else {
+            anyhow::bail!("invalid .env line {}: {trimmed}", i + 1);
+        };
+        let key = key.trim_end();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            anyhow::bail!("invalid .env line {}: {trimmed}", i + 1);
+        }
+        let value = value.trim_start();
+        open_quote = value
+            .chars()
+            .next()
+            .filter(|q| matches!(q, '"' | '\''))
+            .filter(|&q| closing_quote(&value[1..], q).is_none());
+        let value = if open_quote.is_some() {
+            value
+        } else {
+            value.trim_end()
+        };
+
+        if export {
+            out.push_str("export ");
+        }
+        out.push_str(key);
+        out.push('=');
+        out.push_str(value);
+        out.push('\n');
+    }
+
+    Ok((out != content).then_some(out))
+}
+
+/// Byte offset of the first unescaped `quote` in `s`. Backslash escapes
+/// only apply inside double quotes.
+fn closing_quote(s: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => escaped = true,
+            c if c == quote && !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_assignments() {
+        let input = "\n  # database\nDB_HOST = localhost\nexport   DB_PORT=5432  \n\n\n\nEMPTY=\n";
+        assert_eq!(
+            format_text(input).unwrap().unwrap(),
+            "# database\nDB_HOST=localhost\nexport DB_PORT=5432\n\nEMPTY=\n"
+        );
+    }
+
+    #[test]
+    fn keeps_multiline_values_verbatim() {
+        let input = "KEY=\"-----BEGIN  \n  line two  \n-----END\"\nNEXT = 1\n";
+        assert_eq!(
+            format_text(input).unwrap().unwrap(),
+            "KEY=\"-----BEGIN  \n  line two  \n-----END\"\nNEXT=1\n"
+        );
+    }
+
+    #[test]
+    fn escaped_quote_does_not_close_value() {
+        let input = "A=\"say \\\"hi\\\"\nstill A\"\nB=2\n";
+        assert!(format_text(input).unwrap().is_none());
+    }
+
+    #[test]
+    fn already_formatted_returns_none() {
+        let input = "# comment\nA=1\n\nB='two words'\n";
+        assert!(format_text(input).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_lines_without_assignment() {
+        assert!(format_text("A=1\nnot valid\n").is_err());
+        assert!(format_text("MY KEY=1\n").is_err());
+    }
+}
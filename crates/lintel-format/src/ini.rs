@@ -0,0 +1,139 @@
+//! INI formatting.
+//!
+//! Normalizes layout without touching values: section headers lose inner
+//! padding (`[ core ]` → `[core]`), keys get a single space around `=` (or
+//! a single space after `:`), trailing whitespace and repeated blank lines
+//! are removed, and every section is preceded by a blank line (or by the
+//! comment describing it). Indentation is kept, since parsers such as
+//! Python's `configparser` read indented lines as value continuations, and
+//! lines indented deeper than the key before them are left untouched.
+
+use anyhow::Result;
+
+/// Format INI content. Returns `Ok(None)` if the content is already
+/// formatted.
+///
+/// # Errors
+///
+/// Returns an error for a line that is not a section header, key, comment
+/// or continuation line.
+pub fn format_text(content: &str) -> Result<Option<String>> {
+    let mut out = String::with_capacity(content.len());
+    let mut pending_blank = false;
+    let mut after_comment = false;
+    // Indentation of the last key, while its value may continue on the
+    // following, more deeply indented lines.
+    let mut key_indent: Option<usize> = None;
+
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim_end();
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+
+        if trimmed.is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+
+        let is_comment = trimmed.starts_with([';', '#']);
+        let is_continuation = key_indent.is_some_and(|key| indent.len() > key);
+        let normalized = if is_comment || is_continuation {
+            line.to_string()
+        } else if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            pending_blank |= !out.is_empty() && !after_comment;
+            key_indent = None;
+            format!("{indent}[{}]", name.trim())
+        } else if let Some(entry) = format_entry(trimmed) {
+            key_indent = Some(indent.len());
+            format!("{indent}{entry}")
+        } else if !indent.is_empty() {
+            // Continuation of a multi-line value.
+            line.to_string()
+        } else {
+            anyhow::bail!("invalid INI line {}: {trimmed}", i + 1);
+        };
+
+        if pending_blank {
+            out.push('\n');
+            pending_blank = false;
+        }
+        out.push_str(&normalized);
+        out.push('\n');
+        after_comment = is_comment;
+    }
+
+    Ok((out != content).then_some(out))
+}
+
+/// Normalize `key=value`, `key : value` and bare `key` lines.
+fn format_entry(line: &str) -> Option<String> {
+    let delimiter = line.find(['=', ':']);
+    let Some(pos) = delimiter else {
+        // A key without a value, as allowed by e.g. `.editorconfig`-style
+        // and `my.cnf` files.
+        return (!line.contains(char::is_whitespace)).then(|| line.to_string());
+    };
+    let key = line[..pos].trim_end();
+    if key.is_empty() {
+        return None;
+    }
+    let value = line[pos + 1..].trim_start();
+    let entry = match (&line[pos..=pos], value.is_empty()) {
+        ("=", true) => format!("{key} ="),
+        ("=", false) => format!("{key} = {value}"),
+        (_, true) => format!("{key}:"),
+        (_, false) => format!("{key}: {value}"),
+    };
+    Some(entry)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_sections_keys_and_spacing() {
+        let input = "\n[ core ]\n  editor=vim\nname :  demo   \n\n\n; comment\n[user]\nemail =\n";
+        let formatted = format_text(input).unwrap().unwrap();
+        assert_eq!(
+            formatted,
+            "[core]\n  editor = vim\nname: demo\n\n; comment\n[user]\nemail =\n"
+        );
+    }
+
+    #[test]
+    fn separates_sections_with_one_blank_line() {
+        let input = "[a]\nx = 1\n[b]\ny = 2\n";
+        assert_eq!(
+            format_text(input).unwrap().unwrap(),
+            "[a]\nx = 1\n\n[b]\ny = 2\n"
+        );
+    }
+
+    #[test]
+    fn keeps_continuation_lines() {
+        let input = "[options]\npackages =\n    foo bar\n    baz\n";
+        assert!(format_text(input).unwrap().is_none());
+    }
+
+    #[test]
+    fn keeps_continuation_lines_with_delimiters() {
+        let input = "[options.entry_points]\nconsole_scripts =\n    mycli=pkg.mod:main\n    other = pkg.other:run\n";
+        assert!(format_text(input).unwrap().is_none());
+    }
+
+    #[test]
+    fn already_formatted_returns_none() {
+        let input = "# top\n\n[section]\nkey = value\nflag\n";
+        assert!(format_text(input).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_garbage_lines() {
+        assert!(format_text("[a]\nnot a key\n").is_err());
+    }
+}
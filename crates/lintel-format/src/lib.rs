@@ -1,15 +1,25 @@
 #![doc = include_str!("../README.md")]
 #![allow(unused_assignments)] // thiserror/miette derive macros trigger false positives
 
+mod dotenv;
+mod ini;
+mod schema_node;
+mod sort_keys;
 mod toml;
+mod toml_layout;
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use bpaf::{Bpaf, ShellComp};
+use jsonschema_schema::SchemaValue;
+use lintel_config::TomlStyle;
 use lintel_diagnostics::LintelDiagnostic;
 
+pub use sort_keys::SortKeys;
+
 // ---------------------------------------------------------------------------
 // Format detection
 // ---------------------------------------------------------------------------
@@ -21,15 +31,24 @@ enum FormatKind {
     Toml,
     Yaml,
     Markdown,
+    Ini,
+    Env,
 }
 
 fn detect_format(path: &Path) -> Option<FormatKind> {
+    // `.env`, `.env.local`, `.env.production`, ...
+    let file_name = path.file_name().and_then(|n| n.to_str());
+    if file_name.is_some_and(|n| n == ".env" || n.starts_with(".env.")) {
+        return Some(FormatKind::Env);
+    }
     match path.extension().and_then(|e| e.to_str()) {
         Some("json") => Some(FormatKind::Json),
         Some("jsonc") => Some(FormatKind::Jsonc),
         Some("yaml" | "yml") => Some(FormatKind::Yaml),
         Some("toml") => Some(FormatKind::Toml),
         Some("md" | "mdx") => Some(FormatKind::Markdown),
+        Some("ini") => Some(FormatKind::Ini),
+        Some("env") => Some(FormatKind::Env),
         _ => None,
     }
 }
@@ -44,6 +63,7 @@ pub struct FormatConfig {
     toml: dprint_plugin_toml::configuration::Configuration,
     markdown: dprint_plugin_markdown::configuration::Configuration,
     yaml: pretty_yaml::config::FormatOptions,
+    toml_style: TomlStyle,
 }
 
 impl Default for FormatConfig {
@@ -53,11 +73,22 @@ impl Default for FormatConfig {
             toml: dprint_plugin_toml::configuration::ConfigurationBuilder::new().build(),
             markdown: dprint_plugin_markdown::configuration::ConfigurationBuilder::new().build(),
             yaml: pretty_yaml::config::FormatOptions::default(),
+            toml_style: TomlStyle::default(),
         }
     }
 }
 
 impl FormatConfig {
+    /// Build formatter configs from the `[format]` section of `lintel.toml`.
+    fn from_lintel(format: Option<&lintel_config::Format>) -> Self {
+        let mut config = format
+            .and_then(|f| f.dprint.as_ref())
+            .map(Self::from_dprint)
+            .unwrap_or_default();
+        config.toml_style = format.map(|f| f.toml_style).unwrap_or_default();
+        config
+    }
+
     /// Build formatter configs from a `DprintConfig`.
     fn from_dprint(dprint: &dprint_config::DprintConfig) -> Self {
         let global = build_global_config(dprint);
@@ -108,6 +139,7 @@ impl FormatConfig {
             toml,
             markdown,
             yaml,
+            toml_style: TomlStyle::default(),
         }
     }
 }
@@ -232,9 +264,60 @@ pub fn format_content(path: &Path, content: &str, cfg: &FormatConfig) -> Result<
             })
             .map_err(|e| anyhow::anyhow!("{e}"))
         }
+        FormatKind::Ini => ini::format_text(content),
+        FormatKind::Env => dotenv::format_text(content),
     }
 }
 
+/// How a file's schema is used while formatting it.
+#[derive(Clone, Copy)]
+struct SchemaUse<'a> {
+    sort_keys: SortKeys,
+    /// The file's resolved schema, if any.
+    schema: Option<&'a SchemaValue>,
+}
+
+/// Whether formatting `path` depends on its schema: JSON files with
+/// `--sort-keys schema`, and TOML files with `toml_style = "auto"`.
+fn needs_schema(path: &Path, sort_keys: SortKeys, cfg: &FormatConfig) -> bool {
+    match detect_format(path) {
+        Some(FormatKind::Json | FormatKind::Jsonc) => sort_keys == SortKeys::Schema,
+        Some(FormatKind::Toml) => cfg.toml_style == TomlStyle::Auto,
+        _ => false,
+    }
+}
+
+/// Apply the schema-aware passes (key sorting, TOML table layout), then
+/// format.
+///
+/// JSON keys are sorted by the schema's property order with
+/// `--sort-keys schema`, alphabetically when there is no schema.
+fn format_with_schema(
+    path: &Path,
+    content: &str,
+    cfg: &FormatConfig,
+    schema_use: SchemaUse<'_>,
+) -> Result<Option<String>> {
+    let prepared = match detect_format(path) {
+        Some(FormatKind::Json | FormatKind::Jsonc) if schema_use.sort_keys != SortKeys::None => {
+            let schema = schema_use
+                .schema
+                .filter(|_| schema_use.sort_keys == SortKeys::Schema);
+            Some(sort_keys::sort_json_keys(content, schema)?)
+        }
+        Some(FormatKind::Toml) if cfg.toml_style == TomlStyle::Auto => schema_use
+            .schema
+            .map(|schema| toml_layout::restyle(content, schema))
+            .transpose()?,
+        _ => None,
+    };
+    let Some(prepared) = prepared else {
+        return format_content(path, content, cfg);
+    };
+    let formatted = format_content(path, &prepared, cfg)?.unwrap_or(prepared);
+    Ok((formatted != content).then_some(formatted))
+}
+
 // ---------------------------------------------------------------------------
 // Diagnostics
 // ---------------------------------------------------------------------------
@@ -361,6 +444,16 @@ fn generate_diff(original: &str, formatted: &str, color: bool) -> String {
     out
 }
 
+/// Render a `diff -u` style patch (`a/<path>` → `b/<path>`) that applies
+/// the formatting with `git apply`.
+fn unified_diff(path: &str, original: &str, formatted: &str) -> String {
+    similar::TextDiff::from_lines(original, formatted)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
 fn make_diagnostic(path_str: String, content: &str, formatted: &str) -> LintelDiagnostic {
     let color = std::io::IsTerminal::is_terminal(&std::io::stderr());
     let styled_path = if color {
@@ -393,24 +486,28 @@ struct LoadedConfig {
 }
 
 fn load_config(globs: &[String], user_excludes: &[String]) -> LoadedConfig {
-    let search_dir = globs
-        .iter()
-        .find(|g| Path::new(g).is_dir())
-        .map(PathBuf::from);
+    let search_dir = globs.iter().map(Path::new).find(|g| g.is_dir());
+    load_config_from(search_dir, user_excludes)
+}
 
-    let cfg_result = match &search_dir {
+/// Load the config that applies to `path`, which does not need to exist.
+fn load_config_for_path(path: &Path, user_excludes: &[String]) -> Result<LoadedConfig> {
+    let search_dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map_or_else(std::env::current_dir, std::path::absolute)?;
+    Ok(load_config_from(Some(&search_dir), user_excludes))
+}
+
+fn load_config_from(search_dir: Option<&Path>, user_excludes: &[String]) -> LoadedConfig {
+    let cfg_result = match search_dir {
         Some(dir) => lintel_config::find_and_load(dir).map(Option::unwrap_or_default),
         None => lintel_config::load(),
     };
 
     match cfg_result {
         Ok(cfg) => {
-            let format = cfg
-                .format
-                .as_ref()
-                .and_then(|f| f.dprint.as_ref())
-                .map(FormatConfig::from_dprint)
-                .unwrap_or_default();
+            let format = FormatConfig::from_lintel(cfg.format.as_ref());
 
             let mut excludes = cfg.exclude;
             excludes.extend(user_excludes.iter().cloned());
@@ -438,9 +535,32 @@ pub struct FormatArgs {
     #[bpaf(long("check"), switch)]
     pub check: bool,
 
+    /// Print a unified diff of the changes instead of writing them (implies
+    /// `--check`)
+    #[bpaf(long("diff"), switch)]
+    pub diff: bool,
+
     #[bpaf(long("exclude"), argument("PATTERN"))]
     pub exclude: Vec<String>,
 
+    /// Reorder object keys in JSON files: keep them as is, sort them
+    /// alphabetically, or follow the schema's property order
+    #[bpaf(
+        long("sort-keys"),
+        argument("none|alphabetical|schema"),
+        fallback(SortKeys::None)
+    )]
+    pub sort_keys: SortKeys,
+
+    /// Read content from stdin and write the formatted result to stdout;
+    /// PATH is only used to pick the format and find lintel.toml
+    #[bpaf(
+        long("stdin-filepath"),
+        argument("PATH"),
+        complete_shell(ShellComp::File { mask: None })
+    )]
+    pub stdin_filepath: Option<PathBuf>,
+
     #[bpaf(positional("PATH"), complete_shell(ShellComp::File { mask: None }))]
     pub globs: Vec<String>,
 }
@@ -560,6 +680,47 @@ pub fn fix_format(globs: &[String], user_excludes: &[String]) -> Result<usize> {
 ///
 /// Returns an error if file discovery fails (e.g. invalid glob pattern or I/O error).
 pub fn run(args: &FormatArgs) -> Result<FormatResult> {
+    run_with_schemas(args, &HashMap::new())
+}
+
+/// List the files whose formatting uses their schema, so the caller can
+/// resolve schemas for [`run_with_schemas`] and [`format_stdin`]: JSON files
+/// with `--sort-keys schema`, and TOML files when `lintel.toml` sets
+/// `toml_style = "auto"`. With `--stdin-filepath`, only that path is
+/// considered.
+///
+/// # Errors
+///
+/// Returns an error if file discovery fails (e.g. invalid glob pattern or I/O error).
+pub fn files_needing_schema(args: &FormatArgs) -> Result<Vec<PathBuf>> {
+    if let Some(path) = &args.stdin_filepath {
+        let loaded = load_config_for_path(path, &args.exclude)?;
+        return Ok(needs_schema(path, args.sort_keys, &loaded.format)
+            .then(|| path.clone())
+            .into_iter()
+            .collect());
+    }
+    let loaded = load_config(&args.globs, &args.exclude);
+    if args.sort_keys != SortKeys::Schema && loaded.format.toml_style != TomlStyle::Auto {
+        return Ok(vec![]);
+    }
+    let mut files = collect_files(&args.globs, &loaded.excludes)?;
+    files.retain(|path| needs_schema(path, args.sort_keys, &loaded.format));
+    Ok(files)
+}
+
+/// Like [`run`], with the resolved schema of each file for
+/// `--sort-keys schema` and `toml_style = "auto"`. JSON files missing from
+/// `schemas` fall back to alphabetical key order; TOML files keep their
+/// layout.
+///
+/// # Errors
+///
+/// Returns an error if file discovery fails (e.g. invalid glob pattern or I/O error).
+pub fn run_with_schemas<S: core::hash::BuildHasher>(
+    args: &FormatArgs,
+    schemas: &HashMap<PathBuf, SchemaValue, S>,
+) -> Result<FormatResult> {
     let loaded = load_config(&args.globs, &args.exclude);
     let files = collect_files(&args.globs, &loaded.excludes)?;
 
@@ -583,9 +744,16 @@ pub fn run(args: &FormatArgs) -> Result<FormatResult> {
             }
         };
 
-        match format_content(file_path, &content, &loaded.format) {
+        let schema_use = SchemaUse {
+            sort_keys: args.sort_keys,
+            schema: schemas.get(file_path),
+        };
+        match format_with_schema(file_path, &content, &loaded.format, schema_use) {
             Ok(Some(formatted)) => {
-                if args.check {
+                if args.diff {
+                    print!("{}", unified_diff(&path_str, &content, &formatted));
+                    result.errors.push((path_str, "not formatted".to_string()));
+                } else if args.check {
                     let diag = make_diagnostic(path_str.clone(), &content, &formatted);
                     eprintln!("{:?}", miette::Report::new(diag));
                     result.errors.push((path_str, "not formatted".to_string()));
@@ -608,6 +776,38 @@ pub fn run(args: &FormatArgs) -> Result<FormatResult> {
     Ok(result)
 }
 
+/// Format `content` (e.g. an editor buffer piped through stdin) as if it
+/// were the file at `path`, returning the formatted text.
+///
+/// `path` does not need to exist: it only selects the formatter and the
+/// directory `lintel.toml` is searched from. Already-formatted content is
+/// returned unchanged. `schema` is used for `--sort-keys schema` and
+/// `toml_style = "auto"`.
+///
+/// # Errors
+///
+/// Returns an error if `path` has an unsupported extension or `content`
+/// fails to parse.
+pub fn format_stdin(
+    path: &Path,
+    content: &str,
+    args: &FormatArgs,
+    schema: Option<&SchemaValue>,
+) -> Result<String> {
+    if detect_format(path).is_none() {
+        anyhow::bail!("unsupported file type: {}", path.display());
+    }
+    let loaded = load_config_for_path(path, &args.exclude)?;
+    let schema_use = SchemaUse {
+        sort_keys: args.sort_keys,
+        schema,
+    };
+    Ok(
+        format_with_schema(path, content, &loaded.format, schema_use)?
+            .unwrap_or_else(|| content.to_string()),
+    )
+}
+
 /// Check formatting of pre-discovered files, returning diagnostics.
 ///
 /// Unlike [`check_format`], this skips file discovery and config loading —
@@ -658,10 +858,42 @@ pub fn fix_format_files(files: &[PathBuf], config: &FormatConfig) -> Result<usiz
 
 /// Build a [`FormatConfig`] from a [`lintel_config::Config`].
 pub fn format_config_from_lintel(config: &lintel_config::Config) -> FormatConfig {
-    config
-        .format
-        .as_ref()
-        .and_then(|f| f.dprint.as_ref())
-        .map(FormatConfig::from_dprint)
-        .unwrap_or_default()
+    FormatConfig::from_lintel(config.format.as_ref())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> FormatArgs {
+        use bpaf::Parser;
+
+        format_args().to_options().run_inner(args).unwrap()
+    }
+
+    #[test]
+    fn unified_diff_has_git_style_headers() {
+        let diff = unified_diff("conf/a.json", "{\"a\":1}\n", "{ \"a\": 1 }\n");
+        assert_eq!(
+            diff,
+            "--- a/conf/a.json\n+++ b/conf/a.json\n@@ -1 +1 @@\n-{\"a\":1}\n+{ \"a\": 1 }\n"
+        );
+    }
+
+    #[test]
+    fn format_stdin_detects_format_from_path() {
+        let path = Path::new("does/not/exist/config.json");
+        let args = parse_args(&["--stdin-filepath", "does/not/exist/config.json"]);
+        assert_eq!(args.stdin_filepath.as_deref(), Some(path));
+        assert_eq!(
+            format_stdin(path, "{\n\"a\":1}", &args, None).unwrap(),
+            "{\n  \"a\": 1\n}\n"
+        );
+        assert_eq!(
+            format_stdin(path, "{\n  \"a\": 1\n}\n", &args, None).unwrap(),
+            "{\n  \"a\": 1\n}\n"
+        );
+        assert!(format_stdin(Path::new("notes.txt"), "x", &args, None).is_err());
+    }
 }
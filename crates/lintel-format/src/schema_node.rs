@@ -0,0 +1,97 @@
+//! Schema lookups shared by the schema-aware formatting passes.
+
+use jsonschema_schema::{Schema, SchemaValue, SimpleType, TypeValue};
+
+/// The schemas that declare an object's properties: the object schema
+/// itself plus its `allOf`, `oneOf` and `anyOf` entries, with `$ref`s
+/// followed.
+pub(crate) struct Node<'a> {
+    branches: Vec<&'a Schema>,
+}
+
+/// How deeply nested composition keywords are searched for properties.
+const MAX_BRANCH_DEPTH: usize = 8;
+
+impl<'a> Node<'a> {
+    pub(crate) fn new(sv: &'a SchemaValue, root: &'a SchemaValue) -> Self {
+        let mut branches = Vec::new();
+        collect_branches(sv, root, 0, &mut branches);
+        Self { branches }
+    }
+
+    /// Declared property names across all branches, first declaration wins.
+    pub(crate) fn order(&self) -> Vec<&'a str> {
+        let mut order: Vec<&'a str> = Vec::new();
+        for key in self.branches.iter().flat_map(|b| b.properties.keys()) {
+            if !order.contains(&key.as_str()) {
+                order.push(key);
+            }
+        }
+        order
+    }
+
+    /// The schema for the value of `key`, from the first branch declaring it.
+    pub(crate) fn property(&self, key: &str) -> Option<&'a SchemaValue> {
+        self.branches.iter().find_map(|b| b.properties.get(key))
+    }
+
+    /// Like [`Node::property`], falling back to `additionalProperties` for
+    /// keys of map-like objects.
+    pub(crate) fn value(&self, key: &str) -> Option<&'a SchemaValue> {
+        self.property(key).or_else(|| {
+            self.branches
+                .iter()
+                .find_map(|b| b.additional_properties.as_deref())
+        })
+    }
+
+    /// The schema of the array's items.
+    pub(crate) fn items(&self) -> Option<&'a SchemaValue> {
+        self.branches.iter().find_map(|b| b.items.as_deref())
+    }
+
+    /// Whether the schema describes an object: some branch has
+    /// `"type": "object"` or declares properties.
+    pub(crate) fn is_object(&self) -> bool {
+        self.branches.iter().any(|b| {
+            !b.properties.is_empty()
+                || match &b.type_ {
+                    Some(TypeValue::Single(t)) => *t == SimpleType::Object,
+                    Some(TypeValue::Union(ts)) => ts.contains(&SimpleType::Object),
+                    None => false,
+                }
+        })
+    }
+}
+
+fn collect_branches<'a>(
+    sv: &'a SchemaValue,
+    root: &'a SchemaValue,
+    depth: usize,
+    out: &mut Vec<&'a Schema>,
+) {
+    let Some(schema) = resolve(sv, root).as_schema() else {
+        return;
+    };
+    if depth > MAX_BRANCH_DEPTH || out.iter().any(|b| core::ptr::eq(*b, schema)) {
+        return;
+    }
+    out.push(schema);
+    let compositions = [
+        schema.all_of.as_deref(),
+        schema.one_of.as_deref(),
+        schema.any_of.as_deref(),
+    ];
+    for entry in compositions.into_iter().flatten().flatten() {
+        collect_branches(entry, root, depth + 1, out);
+    }
+}
+
+/// Follow a local `$ref` against the document root.
+pub(crate) fn resolve<'a>(sv: &'a SchemaValue, root: &'a SchemaValue) -> &'a SchemaValue {
+    sv.as_schema()
+        .and_then(|s| s.ref_.as_deref())
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|fragment| jsonschema_schema::navigate_pointer(root, root, fragment).ok())
+        .unwrap_or(sv)
+}
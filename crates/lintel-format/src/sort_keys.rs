@@ -0,0 +1,375 @@
+//! Object key ordering for JSON and JSONC files.
+//!
+//! Keys are reordered in the source text rather than by re-serializing a
+//! parsed value, so comments, number spellings and string escapes survive.
+//! Each member keeps the comments above it and any comments trailing it on
+//! the same line, while the whitespace before each member stays in place,
+//! so the reordered text keeps its layout before it is formatted.
+//!
+//! With a schema, keys follow the order their properties are declared in
+//! (including `oneOf`/`anyOf` branches and `allOf` entries); keys the schema
+//! does not declare follow alphabetically. `$schema` always stays first.
+
+use jsonschema_schema::SchemaValue;
+
+use crate::schema_node::{Node, resolve};
+
+/// How `lintel format` orders object keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKeys {
+    /// Keep keys in their original order.
+    #[default]
+    None,
+    /// Sort keys alphabetically.
+    Alphabetical,
+    /// Follow the property order of the file's schema, then alphabetically.
+    Schema,
+}
+
+impl core::str::FromStr for SortKeys {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "alphabetical" => Ok(Self::Alphabetical),
+            "schema" => Ok(Self::Schema),
+            _ => Err(format!(
+                "unknown key order '{s}', expected: none, alphabetical, schema"
+            )),
+        }
+    }
+}
+
+/// Reorder the keys of every object in `content`.
+///
+/// `schema` is the file's schema when sorting by schema; without one, keys
+/// are sorted alphabetically.
+///
+/// # Errors
+///
+/// Returns an error if `content` is not valid JSON or JSONC.
+pub(crate) fn sort_json_keys(
+    content: &str,
+    schema: Option<&SchemaValue>,
+) -> anyhow::Result<String> {
+    let mut parser = Parser {
+        src: content,
+        pos: 0,
+        root: schema,
+    };
+    let mut out = parser.trivia().to_string();
+    out.push_str(&parser.value(schema)?);
+    out.push_str(&content[parser.pos..]);
+    Ok(out)
+}
+
+/// One `"key": value` member of an object, with the text around it.
+struct Member {
+    key: String,
+    /// Leading comments, the key, the colon and the rewritten value. The
+    /// whitespace before them belongs to the member's position instead.
+    text: String,
+    /// Comments on the same line after the member's value and comma.
+    trailing: String,
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    root: Option<&'a SchemaValue>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error(&self, expected: &str) -> anyhow::Error {
+        anyhow::anyhow!("expected {expected} at byte {}", self.pos)
+    }
+
+    /// Skip whitespace and comments, returning the skipped text.
+    fn trivia(&mut self) -> &'a str {
+        let start = self.pos;
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(body) = trimmed.strip_prefix("/*") {
+                self.pos += body.find("*/").map_or(trimmed.len(), |end| end + 4);
+            } else {
+                break;
+            }
+        }
+        &self.src[start..self.pos]
+    }
+
+    /// Skip comments on the current line, returning them with the spaces
+    /// before them, or `""` if the line has none.
+    fn same_line_comments(&mut self) -> &'a str {
+        let start = self.pos;
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start_matches([' ', '\t']);
+            let indent = rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.pos += indent + trimmed.find('\n').unwrap_or(trimmed.len());
+                break;
+            }
+            match trimmed
+                .strip_prefix("/*")
+                .and_then(|body| body.find("*/").map(|end| (body, end)))
+            {
+                Some((body, end)) if !body[..end].contains('\n') => self.pos += indent + end + 4,
+                _ => break,
+            }
+        }
+        &self.src[start..self.pos]
+    }
+
+    fn value(&mut self, schema: Option<&'a SchemaValue>) -> anyhow::Result<String> {
+        match self.rest().chars().next() {
+            Some('{') => self.object(schema),
+            Some('[') => self.array(schema),
+            Some('"') => Ok(self.string()?.to_string()),
+            Some(_) => {
+                let len = self
+                    .rest()
+                    .find(|c: char| c.is_whitespace() || matches!(c, ',' | '}' | ']' | '/'))
+                    .unwrap_or(self.rest().len());
+                if len == 0 {
+                    return Err(self.error("a value"));
+                }
+                let scalar = &self.rest()[..len];
+                self.pos += len;
+                Ok(scalar.to_string())
+            }
+            None => Err(self.error("a value")),
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<&'a str> {
+        if !self.rest().starts_with('"') {
+            return Err(self.error("a string"));
+        }
+        let start = self.pos;
+        let mut escaped = false;
+        for (i, c) in self.rest().char_indices().skip(1) {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => {
+                    self.pos += i + 1;
+                    return Ok(&self.src[start..self.pos]);
+                }
+                _ => escaped = false,
+            }
+        }
+        Err(self.error("a closing '\"'"))
+    }
+
+    fn object(&mut self, schema: Option<&'a SchemaValue>) -> anyhow::Result<String> {
+        self.pos += 1; // '{'
+        let node = schema.zip(self.root).map(|(s, root)| Node::new(s, root));
+        let mut members = Vec::new();
+        let mut gaps = Vec::new();
+        let mut trailing_comma = false;
+        let closing = loop {
+            let leading = self.trivia();
+            if self.rest().starts_with('}') {
+                break leading;
+            }
+            let gap = &leading[..leading.len() - leading.trim_start().len()];
+            let start = self.pos - leading.len() + gap.len();
+            gaps.push(gap);
+            let raw_key = self.string()?;
+            let key: String = serde_json::from_str(raw_key)
+                .map_err(|e| anyhow::anyhow!("invalid key {raw_key}: {e}"))?;
+            self.trivia();
+            if !self.rest().starts_with(':') {
+                return Err(self.error("':'"));
+            }
+            self.pos += 1;
+            self.trivia();
+            let value_start = self.pos;
+            let child = node.as_ref().and_then(|n| n.property(&key));
+            let mut text = self.src[start..value_start].to_string();
+            text.push_str(&self.value(child)?);
+            let after_value = self.pos;
+            self.trivia();
+            let has_comma = self.rest().starts_with(',');
+            if has_comma {
+                text.push_str(&self.src[after_value..self.pos]);
+                self.pos += 1;
+            } else {
+                self.pos = after_value;
+            }
+            let trailing = self.same_line_comments().to_string();
+            members.push(Member {
+                key,
+                text,
+                trailing,
+            });
+            trailing_comma = has_comma;
+            if !has_comma {
+                let closing = self.trivia();
+                if !self.rest().starts_with('}') {
+                    return Err(self.error("',' or '}'"));
+                }
+                break closing;
+            }
+        };
+        self.pos += 1; // '}'
+
+        let order = node.as_ref().map(Node::order).unwrap_or_default();
+        members.sort_by_cached_key(|m| rank(&m.key, &order));
+
+        let mut out = String::from("{");
+        for (i, (member, gap)) in members.iter().zip(&gaps).enumerate() {
+            out.push_str(gap);
+            out.push_str(&member.text);
+            let next = gaps.get(i + 1).copied();
+            if next.is_some() || trailing_comma {
+                out.push(',');
+            }
+            out.push_str(&member.trailing);
+            // A moved line comment must not swallow whatever follows it.
+            if !member.trailing.is_empty() && !starts_new_line(next.unwrap_or(closing)) {
+                out.push('\n');
+            }
+        }
+        out.push_str(closing);
+        out.push('}');
+        Ok(out)
+    }
+
+    fn array(&mut self, schema: Option<&'a SchemaValue>) -> anyhow::Result<String> {
+        self.pos += 1; // '['
+        let items = schema
+            .zip(self.root)
+            .and_then(|(s, root)| resolve(s, root).as_schema())
+            .and_then(|s| s.items.as_deref());
+        let mut out = String::from("[");
+        loop {
+            out.push_str(self.trivia());
+            if self.rest().starts_with(']') {
+                break;
+            }
+            out.push_str(&self.value(items)?);
+            out.push_str(self.trivia());
+            if self.rest().starts_with(',') {
+                self.pos += 1;
+                out.push(',');
+            } else if !self.rest().starts_with(']') {
+                return Err(self.error("',' or ']'"));
+            }
+        }
+        self.pos += 1; // ']'
+        out.push(']');
+        Ok(out)
+    }
+}
+
+fn starts_new_line(text: &str) -> bool {
+    text.trim_start_matches([' ', '\t'])
+        .starts_with(['\n', '\r'])
+}
+
+/// Sort key for a member: `$schema` first, then schema-declared keys in
+/// declaration order, then everything else alphabetically.
+fn rank(key: &str, order: &[&str]) -> (u8, usize, String) {
+    if key == "$schema" {
+        return (0, 0, String::new());
+    }
+    match order.iter().position(|k| *k == key) {
+        Some(i) => (1, i, String::new()),
+        None => (2, 0, key.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn schema(value: serde_json::Value) -> SchemaValue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn parses_sort_keys() {
+        assert_eq!("schema".parse::<SortKeys>().unwrap(), SortKeys::Schema);
+        assert_eq!(
+            "alphabetical".parse::<SortKeys>().unwrap(),
+            SortKeys::Alphabetical
+        );
+        assert!("random".parse::<SortKeys>().is_err());
+    }
+
+    #[test]
+    fn sorts_alphabetically_without_schema() {
+        let sorted = sort_json_keys(r#"{"b": 1, "a": {"d": 2, "c": 3}}"#, None).unwrap();
+        assert_eq!(sorted, r#"{"a": {"c": 3, "d": 2}, "b": 1}"#);
+    }
+
+    #[test]
+    fn follows_schema_order_then_alphabetical() {
+        let schema = schema(serde_json::json!({
+            "properties": {
+                "name": {},
+                "version": {},
+                "server": { "$ref": "#/$defs/Server" }
+            },
+            "$defs": {
+                "Server": { "properties": { "port": {}, "host": {} } }
+            }
+        }));
+        let content = r#"{
+  "zeta": true,
+  "server": { "host": "x", "port": 1, "extra": 0 },
+  "version": "1",
+  "$schema": "s.json",
+  "name": "demo"
+}
+"#;
+        let sorted = sort_json_keys(content, Some(&schema)).unwrap();
+        assert_eq!(
+            sorted,
+            r#"{
+  "$schema": "s.json",
+  "name": "demo",
+  "version": "1",
+  "server": { "port": 1, "host": "x", "extra": 0 },
+  "zeta": true
+}
+"#
+        );
+    }
+
+    #[test]
+    fn uses_composition_branches() {
+        let schema = schema(serde_json::json!({
+            "allOf": [{ "properties": { "b": {} } }],
+            "oneOf": [{ "properties": { "a": {} } }]
+        }));
+        let sorted = sort_json_keys(r#"{"c": 0, "a": 1, "b": 2}"#, Some(&schema)).unwrap();
+        assert_eq!(sorted, r#"{"b": 2, "a": 1, "c": 0}"#);
+    }
+
+    #[test]
+    fn keeps_comments_with_their_members() {
+        let content = "{\n  // second\n  \"b\": 1, // about b\n  \"a\": [1, 2] /* a */\n}\n";
+        let sorted = sort_json_keys(content, None).unwrap();
+        assert_eq!(
+            sorted,
+            "{\n  \"a\": [1, 2], /* a */\n  // second\n  \"b\": 1 // about b\n}\n"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(sort_json_keys(r#"{"a" 1}"#, None).is_err());
+        assert!(sort_json_keys(r"{a: 1}", None).is_err());
+    }
+}
@@ -55,6 +55,7 @@ const LINTEL_SORT: TomlSortConfig = TomlSortConfig {
             &[
                 "root",
                 "no-default-catalog",
+                "no-schema-index",
                 "exclude",
                 "registries",
                 "schemas",
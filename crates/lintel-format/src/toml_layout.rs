@@ -0,0 +1,216 @@
+//! Schema-aware table layout for TOML files (`toml_style = "auto"`).
+//!
+//! The file's JSON Schema decides where a different TOML spelling of the
+//! same data reads better:
+//!
+//! - An inline array of objects whose entries only hold scalars becomes a
+//!   run of `[[table]]` entries, when the schema says the items are objects.
+//! - A table that is only a chain of single-key tables ending in a value
+//!   (`[server.tls.cert]` holding just `path`) becomes a dotted key
+//!   (`tls.cert.path = ...`) in its parent, when the schema says it is an
+//!   object.
+//!
+//! Keys the schema does not describe, and anything carrying comments, are
+//! left as written. Whitespace is normalized afterwards by the regular TOML
+//! formatter.
+
+use jsonschema_schema::SchemaValue;
+use toml_edit::{Array, ArrayOfTables, DocumentMut, Item, Table, Value};
+
+use crate::schema_node::Node;
+
+/// How many nested single-key tables it takes before a table is written as
+/// a dotted key instead.
+const MIN_DOTTED_DEPTH: usize = 2;
+
+/// Rewrite the table layout of `content` guided by `schema`.
+///
+/// # Errors
+///
+/// Returns an error if `content` is not valid TOML.
+pub(crate) fn restyle(content: &str, schema: &SchemaValue) -> anyhow::Result<String> {
+    let mut doc: DocumentMut = content.parse().map_err(|e| anyhow::anyhow!("{e}"))?;
+    restyle_table(doc.as_table_mut(), Some(&Node::new(schema, schema)), schema);
+    Ok(doc.to_string())
+}
+
+fn restyle_table(table: &mut Table, node: Option<&Node<'_>>, root: &SchemaValue) {
+    for (mut key, item) in table.iter_mut() {
+        let child = node
+            .and_then(|n| n.value(key.get()))
+            .map(|sv| Node::new(sv, root));
+        let is_object = child.as_ref().is_some_and(Node::is_object);
+        if is_object
+            && item.as_table().is_some_and(|t| !t.is_dotted())
+            && single_key_depth(item).is_some_and(|d| d >= MIN_DOTTED_DEPTH)
+        {
+            make_dotted(item);
+            continue;
+        }
+        match item {
+            Item::Value(Value::Array(array)) => {
+                let items_are_objects = child
+                    .as_ref()
+                    .and_then(Node::items)
+                    .is_some_and(|items| Node::new(items, root).is_object());
+                if items_are_objects && is_table_array(array) {
+                    *item = Item::ArrayOfTables(to_array_of_tables(array));
+                    // Drop the `key = ` spacing so the header reads `[[key]]`.
+                    key.fmt();
+                }
+            }
+            Item::Table(t) if !t.is_dotted() => restyle_table(t, child.as_ref(), root),
+            Item::ArrayOfTables(array) => {
+                let items = child
+                    .as_ref()
+                    .and_then(Node::items)
+                    .map(|sv| Node::new(sv, root));
+                for t in array.iter_mut() {
+                    restyle_table(t, items.as_ref(), root);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether every element is an inline table holding only scalars (or arrays
+/// of scalars), so the array can be written as `[[table]]` entries.
+fn is_table_array(array: &Array) -> bool {
+    !array.is_empty()
+        && !array.to_string().contains('#')
+        && array.iter().all(|v| {
+            v.as_inline_table()
+                .is_some_and(|t| t.iter().all(|(_, v)| is_scalar_like(v)))
+        })
+}
+
+fn is_scalar_like(value: &Value) -> bool {
+    match value {
+        Value::InlineTable(_) => false,
+        Value::Array(array) => array
+            .iter()
+            .all(|v| !matches!(v, Value::Array(_) | Value::InlineTable(_))),
+        _ => true,
+    }
+}
+
+fn to_array_of_tables(array: &Array) -> ArrayOfTables {
+    let mut tables = ArrayOfTables::new();
+    for inline in array.iter().filter_map(Value::as_inline_table) {
+        let mut table = inline.clone().into_table();
+        table.fmt();
+        tables.push(table);
+    }
+    tables
+}
+
+/// The number of nested tables from `item` down to a value when each has
+/// exactly one entry and no comments, or `None` if the chain branches.
+fn single_key_depth(item: &Item) -> Option<usize> {
+    match item {
+        Item::Table(t) if t.len() == 1 && !has_comments(t) => {
+            let (_, child) = t.iter().next()?;
+            Some(1 + single_key_depth(child)?)
+        }
+        Item::Value(_) => Some(0),
+        _ => None,
+    }
+}
+
+fn has_comments(table: &Table) -> bool {
+    table
+        .decor()
+        .prefix()
+        .and_then(|p| p.as_str())
+        .is_some_and(|p| p.contains('#'))
+}
+
+fn make_dotted(item: &mut Item) {
+    if let Item::Table(t) = item {
+        t.set_dotted(true);
+        for (_, child) in t.iter_mut() {
+            make_dotted(child);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(value: serde_json::Value) -> SchemaValue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn authors_schema() -> SchemaValue {
+        schema(json!({
+            "type": "object",
+            "properties": {
+                "authors": {
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/Author" }
+                },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "$defs": {
+                "Author": { "type": "object", "properties": { "name": { "type": "string" } } }
+            }
+        }))
+    }
+
+    #[test]
+    fn arrays_of_objects_become_table_arrays() {
+        let input =
+            "tags = [\"a\"]\nauthors = [{ name = \"a\" }, { name = \"b\", emails = [\"b@x\"] }]\n";
+        let out = restyle(input, &authors_schema()).unwrap();
+        assert_eq!(out.matches("[[authors]]").count(), 2);
+        let doc: DocumentMut = out.parse().unwrap();
+        assert!(doc["authors"].is_array_of_tables());
+        assert_eq!(doc["authors"][1]["name"].as_str(), Some("b"));
+        assert!(doc["tags"].is_array());
+    }
+
+    #[test]
+    fn keeps_arrays_the_schema_does_not_describe_as_objects() {
+        let input = "other = [{ name = \"a\" }]\n";
+        assert_eq!(restyle(input, &authors_schema()).unwrap(), input);
+    }
+
+    #[test]
+    fn keeps_arrays_with_nested_tables_or_comments() {
+        let nested = "authors = [{ name = \"a\", links = { home = \"x\" } }]\n";
+        assert_eq!(restyle(nested, &authors_schema()).unwrap(), nested);
+        let commented = "authors = [\n  { name = \"a\" }, # first\n]\n";
+        assert_eq!(restyle(commented, &authors_schema()).unwrap(), commented);
+    }
+
+    #[test]
+    fn deep_single_key_tables_become_dotted_keys() {
+        let schema = schema(json!({
+            "properties": {
+                "server": {
+                    "type": "object",
+                    "properties": { "tls": { "type": "object" } }
+                }
+            }
+        }));
+        let input = "[server]\nhost = \"x\"\n\n[server.tls.cert]\npath = \"a\"\n";
+        let out = restyle(input, &schema).unwrap();
+        assert!(out.contains("tls.cert.path = \"a\""), "{out}");
+        assert!(!out.contains("[server.tls"), "{out}");
+        let doc: DocumentMut = out.parse().unwrap();
+        assert_eq!(doc["server"]["tls"]["cert"]["path"].as_str(), Some("a"));
+    }
+
+    #[test]
+    fn keeps_tables_with_several_keys() {
+        let schema = schema(json!({
+            "properties": { "server": { "type": "object" } }
+        }));
+        let input = "[server]\nhost = \"x\"\nport = 1\n";
+        assert_eq!(restyle(input, &schema).unwrap(), input);
+    }
+}
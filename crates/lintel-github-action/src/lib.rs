@@ -6,7 +6,7 @@ use anyhow::{Context, Result, bail};
 use bpaf::Bpaf;
 use serde::Serialize;
 
-use lintel_diagnostics::{DEFAULT_LABEL, LintelDiagnostic, offset_to_line_col};
+use lintel_diagnostics::{DEFAULT_LABEL, LintelDiagnostic};
 
 // -----------------------------------------------------------------------
 // CLI args
@@ -56,6 +56,8 @@ struct Annotation {
     path: String,
     start_line: usize,
     end_line: usize,
+    start_column: usize,
+    end_column: usize,
     annotation_level: String,
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,16 +69,8 @@ struct Annotation {
 // -----------------------------------------------------------------------
 
 fn error_to_annotation(error: &LintelDiagnostic) -> Annotation {
-    let path = error.path().replace('\\', "/");
-    let (line, _col) = match error {
-        LintelDiagnostic::Parse { src, span, .. } => offset_to_line_col(src.inner(), span.offset()),
-        LintelDiagnostic::Validation(v) => offset_to_line_col(v.src.inner(), v.span.offset()),
-        LintelDiagnostic::SchemaMismatch { line_number, .. } => (*line_number, 1),
-        LintelDiagnostic::Io { .. }
-        | LintelDiagnostic::SchemaFetch { .. }
-        | LintelDiagnostic::SchemaCompile { .. }
-        | LintelDiagnostic::Format { .. } => (1, 1),
-    };
+    let path = error.file().replace('\\', "/");
+    let (line, col) = error.line_col().unwrap_or((1, 1));
 
     let title = match error {
         LintelDiagnostic::Parse { .. } => Some("parse error".to_string()),
@@ -88,6 +82,10 @@ fn error_to_annotation(error: &LintelDiagnostic) -> Annotation {
         LintelDiagnostic::Io { .. } => Some("io error".to_string()),
         LintelDiagnostic::SchemaFetch { .. } => Some("schema fetch error".to_string()),
         LintelDiagnostic::SchemaCompile { .. } => Some("schema compile error".to_string()),
+        LintelDiagnostic::SchemaChanged { .. } => Some("schema changed".to_string()),
+        LintelDiagnostic::Timeout { .. } => Some("timeout".to_string()),
+        LintelDiagnostic::UnusedSuppression { .. } => Some("unused suppression".to_string()),
+        LintelDiagnostic::Truncated { .. } => Some("more errors".to_string()),
         LintelDiagnostic::Format { .. } => Some("format error".to_string()),
     };
 
@@ -95,7 +93,14 @@ fn error_to_annotation(error: &LintelDiagnostic) -> Annotation {
         path,
         start_line: line,
         end_line: line,
-        annotation_level: "failure".to_string(),
+        start_column: col,
+        end_column: col,
+        annotation_level: if error.is_warning() {
+            "warning"
+        } else {
+            "failure"
+        }
+        .to_string(),
         message: error.message().to_string(),
         title,
     }
@@ -258,8 +263,8 @@ pub async fn run(args: &mut GithubActionArgs) -> Result<bool> {
     // Convert all errors to annotations.
     let annotations: Vec<Annotation> = result.errors.iter().map(error_to_annotation).collect();
 
-    let had_errors = !annotations.is_empty();
-    let error_count = annotations.len();
+    let error_count = result.error_count();
+    let had_errors = error_count > 0;
 
     let error_label = if error_count == 1 { "error" } else { "errors" };
     let title = if error_count > 0 {
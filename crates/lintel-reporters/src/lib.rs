@@ -1,11 +1,17 @@
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
+pub mod progress;
 pub mod reporters;
 
 use lintel_diagnostics::reporter::Reporter;
 
+pub use progress::Progress;
 pub use reporters::github::GithubReporter;
+pub use reporters::json::JsonReporter;
 pub use reporters::pretty::PrettyReporter;
+pub use reporters::sarif::SarifReporter;
 pub use reporters::text::TextReporter;
 
 // -----------------------------------------------------------------------
@@ -17,6 +23,8 @@ pub enum ReporterKind {
     Pretty,
     Text,
     Github,
+    Json,
+    Sarif,
 }
 
 impl core::str::FromStr for ReporterKind {
@@ -26,8 +34,10 @@ impl core::str::FromStr for ReporterKind {
             "pretty" => Ok(Self::Pretty),
             "text" => Ok(Self::Text),
             "github" => Ok(Self::Github),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
             _ => Err(format!(
-                "unknown reporter '{s}', expected: pretty, text, github"
+                "unknown reporter '{s}', expected: pretty, text, github, json, sarif"
             )),
         }
     }
@@ -39,15 +49,38 @@ impl core::fmt::Display for ReporterKind {
             Self::Pretty => write!(f, "pretty"),
             Self::Text => write!(f, "text"),
             Self::Github => write!(f, "github"),
+            Self::Json => write!(f, "json"),
+            Self::Sarif => write!(f, "sarif"),
         }
     }
 }
 
-/// Create a reporter from the kind and verbose flag.
-pub fn make_reporter(kind: ReporterKind, verbose: bool) -> Box<dyn Reporter> {
+/// Create a reporter from the kind and the verbose and quiet flags.
+///
+/// The pretty and text reporters show a progress line while files are
+/// checked when stderr is a terminal, unless `verbose` or `quiet` is set.
+/// Machine-readable outputs never do.
+pub fn make_reporter(kind: ReporterKind, verbose: bool, quiet: bool) -> Box<dyn Reporter> {
+    let progress = || {
+        if verbose || quiet {
+            None
+        } else {
+            Progress::for_terminal()
+        }
+    };
     match kind {
-        ReporterKind::Pretty => Box::new(PrettyReporter { verbose }),
-        ReporterKind::Text => Box::new(TextReporter { verbose }),
-        ReporterKind::Github => Box::new(GithubReporter { verbose }),
+        ReporterKind::Pretty => Box::new(PrettyReporter {
+            verbose,
+            quiet,
+            progress: progress(),
+        }),
+        ReporterKind::Text => Box::new(TextReporter {
+            verbose,
+            quiet,
+            progress: progress(),
+        }),
+        ReporterKind::Github => Box::new(GithubReporter { verbose, quiet }),
+        ReporterKind::Json => Box::new(JsonReporter { verbose }),
+        ReporterKind::Sarif => Box::new(SarifReporter { verbose }),
     }
 }
@@ -0,0 +1,119 @@
+use alloc::collections::BTreeSet;
+use core::time::Duration;
+use std::io::{IsTerminal, Write};
+use std::time::Instant;
+
+use lintel_diagnostics::reporter::CheckedFile;
+
+/// How long a run goes before the progress line appears, so small runs
+/// finish without it.
+const DELAY: Duration = Duration::from_millis(300);
+
+/// Minimum time between redraws.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// A progress line on stderr counting the files checked and the schemas
+/// they used, redrawn in place.
+pub struct Progress {
+    start: Instant,
+    last_draw: Option<Instant>,
+    files: usize,
+    schemas: BTreeSet<String>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            last_draw: None,
+            files: 0,
+            schemas: BTreeSet::new(),
+        }
+    }
+
+    /// A progress line when stderr is a terminal, `None` otherwise.
+    pub fn for_terminal() -> Option<Self> {
+        std::io::stderr().is_terminal().then(Self::new)
+    }
+
+    /// Count `file` and redraw the line if it is due.
+    pub fn on_file_checked(&mut self, file: &CheckedFile) {
+        if let Some(line) = self.tick(file, Instant::now()) {
+            let mut stderr = std::io::stderr();
+            let _ = write!(stderr, "\r\x1b[2K{line}");
+            let _ = stderr.flush();
+        }
+    }
+
+    /// Erase the line before the report is printed.
+    pub fn clear(&mut self) {
+        if self.last_draw.take().is_some() {
+            eprint!("\r\x1b[2K");
+        }
+    }
+
+    /// Count `file` checked at `now` and return the line to draw, if any.
+    fn tick(&mut self, file: &CheckedFile, now: Instant) -> Option<String> {
+        self.files += 1;
+        if !self.schemas.contains(&file.schema) {
+            self.schemas.insert(file.schema.clone());
+        }
+        if now.duration_since(self.start) < DELAY
+            || self
+                .last_draw
+                .is_some_and(|last| now.duration_since(last) < INTERVAL)
+        {
+            return None;
+        }
+        self.last_draw = Some(now);
+        let files = if self.files == 1 { "file" } else { "files" };
+        let schemas = if self.schemas.len() == 1 {
+            "schema"
+        } else {
+            "schemas"
+        };
+        Some(format!(
+            "Checked {} {files} against {} {schemas}...",
+            self.files,
+            self.schemas.len()
+        ))
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(schema: &str) -> CheckedFile {
+        CheckedFile {
+            path: "a.json".into(),
+            schema: schema.into(),
+            cache_status: None,
+            validation_cache_status: None,
+            duration: None,
+        }
+    }
+
+    #[test]
+    fn draws_after_a_delay_and_throttles() {
+        let mut progress = Progress::new();
+        let start = progress.start;
+        assert_eq!(progress.tick(&file("a.json"), start), None);
+        let late = start + DELAY;
+        assert_eq!(
+            progress.tick(&file("a.json"), late).as_deref(),
+            Some("Checked 2 files against 1 schema...")
+        );
+        assert_eq!(progress.tick(&file("b.json"), late + INTERVAL / 2), None);
+        assert_eq!(
+            progress.tick(&file("b.json"), late + INTERVAL).as_deref(),
+            Some("Checked 4 files against 2 schemas...")
+        );
+    }
+}
@@ -2,12 +2,16 @@ use core::time::Duration;
 
 use lintel_diagnostics::DEFAULT_LABEL;
 use lintel_diagnostics::LintelDiagnostic;
-use lintel_diagnostics::offset_to_line_col;
-use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter, format_checked_verbose};
+use lintel_diagnostics::reporter::{
+    CheckResult, CheckedFile, Reporter, format_checked_verbose, format_warnings_note,
+};
 
-/// GitHub Actions reporter: emits `::error` workflow commands to stdout.
+/// GitHub Actions reporter: emits `::error` and `::warning` workflow commands
+/// to stdout.
 pub struct GithubReporter {
     pub verbose: bool,
+    /// Emit errors only: no warnings and no summary line.
+    pub quiet: bool,
 }
 
 /// Escape a string for GitHub Actions workflow commands.
@@ -19,24 +23,24 @@ fn escape_workflow(s: &str) -> String {
         .replace('\r', "%0D")
 }
 
+/// Escape a workflow command property value (`file=`, `title=`).
+///
+/// Properties are comma-separated `key=value` pairs terminated by `::`, so
+/// `:` and `,` must be escaped in addition to the message escapes.
+fn escape_property(s: &str) -> String {
+    escape_workflow(s).replace(':', "%3A").replace(',', "%2C")
+}
+
 /// Normalize path separators to forward slashes for GitHub.
 fn normalize_path(path: &str) -> String {
     path.replace('\\', "/")
 }
 
 fn emit_lint_error(error: &LintelDiagnostic) {
-    let path = normalize_path(error.path());
+    let path = escape_property(&normalize_path(error.file()));
     let message = escape_workflow(error.message());
 
-    let (line, col) = match error {
-        LintelDiagnostic::Parse { src, span, .. } => offset_to_line_col(src.inner(), span.offset()),
-        LintelDiagnostic::Validation(v) => offset_to_line_col(v.src.inner(), v.span.offset()),
-        LintelDiagnostic::SchemaMismatch { line_number, .. } => (*line_number, 1),
-        LintelDiagnostic::Io { .. }
-        | LintelDiagnostic::SchemaFetch { .. }
-        | LintelDiagnostic::SchemaCompile { .. }
-        | LintelDiagnostic::Format { .. } => (1, 1),
-    };
+    let (line, col) = error.line_col().unwrap_or((1, 1));
 
     let title = match error {
         LintelDiagnostic::Parse { .. } => "parse error",
@@ -46,36 +50,53 @@ fn emit_lint_error(error: &LintelDiagnostic) {
         LintelDiagnostic::Io { .. } => "io error",
         LintelDiagnostic::SchemaFetch { .. } => "schema fetch error",
         LintelDiagnostic::SchemaCompile { .. } => "schema compile error",
+        LintelDiagnostic::SchemaChanged { .. } => "schema changed",
+        LintelDiagnostic::Timeout { .. } => "timeout",
+        LintelDiagnostic::UnusedSuppression { .. } => "unused suppression",
+        LintelDiagnostic::Truncated { .. } => "more errors",
         LintelDiagnostic::Format { .. } => "format error",
     };
 
-    println!("::error file={path},line={line},col={col},title={title}::{message}");
+    let title = escape_property(title);
+    let command = if error.is_warning() {
+        "warning"
+    } else {
+        "error"
+    };
+    println!("::{command} file={path},line={line},col={col},title={title}::{message}");
 }
 
 fn plural(n: usize) -> &'static str {
     if n == 1 { "error" } else { "errors" }
 }
 
-fn print_summary(n: usize, error_count: usize, ms: u128) {
+fn print_summary(n: usize, error_count: usize, warning_count: usize, ms: u128) {
+    let warnings = format_warnings_note(warning_count);
     if error_count > 0 {
         let label = plural(error_count);
-        eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.");
+        eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.{warnings}");
     } else {
-        eprintln!("Checked {n} files in {ms}ms. No errors.");
+        eprintln!("Checked {n} files in {ms}ms. No errors.{warnings}");
     }
 }
 
 impl Reporter for GithubReporter {
     fn report(&mut self, result: CheckResult, elapsed: Duration) {
         let n = result.files_checked();
-        let error_count = result.errors.len();
+        let error_count = result.error_count();
+        let warning_count = result.warning_count();
 
         for error in &result.errors {
+            if self.quiet && error.is_warning() {
+                continue;
+            }
             emit_lint_error(error);
         }
 
-        let ms = elapsed.as_millis();
-        print_summary(n, error_count, ms);
+        if !self.quiet {
+            let ms = elapsed.as_millis();
+            print_summary(n, error_count, warning_count, ms);
+        }
     }
 
     fn on_file_checked(&mut self, file: &CheckedFile) {
@@ -84,3 +105,27 @@ impl Reporter for GithubReporter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_workflow_encodes_newlines_and_percent() {
+        assert_eq!(escape_workflow("100%\nnext\r"), "100%25%0Anext%0D");
+    }
+
+    #[test]
+    fn escape_property_encodes_separators() {
+        assert_eq!(escape_property("/a,b:c"), "/a%2Cb%3Ac");
+        assert_eq!(escape_property("50%"), "50%25");
+    }
+
+    #[test]
+    fn normalize_path_uses_forward_slashes() {
+        assert_eq!(
+            normalize_path(r"src\config\app.json"),
+            "src/config/app.json"
+        );
+    }
+}
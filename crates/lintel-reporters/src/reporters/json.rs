@@ -0,0 +1,203 @@
+use core::time::Duration;
+
+use miette::Diagnostic;
+use serde_json::{Map, Value, json};
+
+use lintel_diagnostics::LintelDiagnostic;
+use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter, format_checked_verbose};
+
+/// JSON reporter: prints one JSON document with every diagnostic to stdout.
+pub struct JsonReporter {
+    pub verbose: bool,
+}
+
+/// The diagnostic code (`validation(required)`, `parse`, ...).
+pub(crate) fn code(error: &LintelDiagnostic) -> String {
+    error.code().map(|c| c.to_string()).unwrap_or_default()
+}
+
+pub(crate) fn level(error: &LintelDiagnostic) -> &'static str {
+    if error.is_warning() {
+        "warning"
+    } else {
+        "error"
+    }
+}
+
+fn diagnostic_json(error: &LintelDiagnostic) -> Value {
+    let mut map = Map::new();
+    map.insert("path".into(), error.path().replace('\\', "/").into());
+    if let Some((line, column)) = error.line_col() {
+        map.insert("line".into(), line.into());
+        map.insert("column".into(), column.into());
+    }
+    map.insert("severity".into(), level(error).into());
+    map.insert("code".into(), code(error).into());
+    map.insert("message".into(), error.message().into());
+    if let LintelDiagnostic::Validation(v) = error {
+        map.insert("instance_path".into(), v.instance_path.clone().into());
+        map.insert("schema_path".into(), v.schema_path.clone().into());
+        map.insert("schema_url".into(), v.schema_url.clone().into());
+        map.insert("fingerprint".into(), v.fingerprint().into());
+        if !v.allowed_values.is_empty() {
+            map.insert("allowed_values".into(), v.allowed_values.clone().into());
+        }
+        if let Some((span, replacement)) = v.suggested_fix() {
+            map.insert(
+                "fix".into(),
+                json!({
+                    "offset": span.offset(),
+                    "length": span.len(),
+                    "replacement": replacement,
+                }),
+            );
+        }
+    }
+    Value::Object(map)
+}
+
+/// The report for a check run, as printed by [`JsonReporter`].
+pub fn report_json(result: &CheckResult) -> Value {
+    json!({
+        "files_checked": result.files_checked(),
+        "error_count": result.error_count(),
+        "warning_count": result.warning_count(),
+        "diagnostics": result.errors.iter().map(diagnostic_json).collect::<Vec<_>>(),
+    })
+}
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, result: CheckResult, _elapsed: Duration) {
+        let report = report_json(&result);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+    }
+
+    fn on_file_checked(&mut self, file: &CheckedFile) {
+        if self.verbose {
+            eprintln!("{}", format_checked_verbose(file));
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use lintel_diagnostics::ValidationDiagnostic;
+    use miette::NamedSource;
+
+    pub(crate) fn sample() -> CheckResult {
+        let content = "name: demo\nport: eighty\n";
+        CheckResult {
+            errors: vec![
+                LintelDiagnostic::Validation(ValidationDiagnostic {
+                    src: NamedSource::new("app.yaml", content.to_string()),
+                    span: 17.into(),
+                    schema_span: 17.into(),
+                    path: "app.yaml".into(),
+                    instance_path: "/port".into(),
+                    label: "/port".into(),
+                    message: "\"eighty\" is not of type \"integer\"".into(),
+                    schema_url: "https://example.com/app.json".into(),
+                    schema_path: "/properties/port/type".into(),
+                    validation_code: "validation(type)".into(),
+                    schema_description: None,
+                    suggestion: None,
+                    allowed_values: Vec::new(),
+                    severity: None,
+                }),
+                LintelDiagnostic::Io {
+                    path: "missing.json".into(),
+                    message: "file not found".into(),
+                },
+            ],
+            checked: vec![],
+            timings: lintel_diagnostics::reporter::Timings::default(),
+            schema_hashes: alloc::collections::BTreeMap::new(),
+            without_schema: Vec::new(),
+        }
+    }
+
+    /// A misspelled property with a "did you mean" suggestion.
+    pub(crate) fn misspelled() -> LintelDiagnostic {
+        let content = "name: demo\nprot: 80\n";
+        LintelDiagnostic::Validation(ValidationDiagnostic {
+            src: NamedSource::new("app.yaml", content.to_string()),
+            span: (11, 4).into(),
+            schema_span: (11, 4).into(),
+            path: "app.yaml".into(),
+            instance_path: "/prot".into(),
+            label: "/prot".into(),
+            message: "'prot' is not in the schema; did you mean 'port'?".into(),
+            schema_url: "https://example.com/app.json".into(),
+            schema_path: "/properties".into(),
+            validation_code: "validation(undescribed_property)".into(),
+            schema_description: None,
+            suggestion: Some("port".into()),
+            allowed_values: Vec::new(),
+            severity: Some(miette::Severity::Warning),
+        })
+    }
+
+    #[test]
+    fn reports_suggested_fixes() {
+        let report = diagnostic_json(&misspelled());
+        assert_eq!(
+            report["fix"],
+            json!({ "offset": 11, "length": 4, "replacement": "port" })
+        );
+        assert_eq!(diagnostic_json(&sample().errors[0]).get("fix"), None);
+    }
+
+    #[test]
+    fn reports_allowed_values() {
+        let mut result = sample();
+        let LintelDiagnostic::Validation(v) = &mut result.errors[0] else {
+            panic!("expected a validation error");
+        };
+        v.allowed_values = vec![json!("debug"), json!("info")];
+        let report = diagnostic_json(&result.errors[0]);
+        assert_eq!(report["allowed_values"], json!(["debug", "info"]));
+        assert_eq!(
+            diagnostic_json(&sample().errors[0]).get("allowed_values"),
+            None
+        );
+    }
+
+    #[test]
+    fn reports_fingerprints_of_validation_errors() {
+        let result = sample();
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation error");
+        };
+        let fingerprint = v.fingerprint();
+        let report = report_json(&result);
+        assert_eq!(report["error_count"], 2);
+        assert_eq!(
+            report["diagnostics"][0],
+            json!({
+                "path": "app.yaml",
+                "line": 2,
+                "column": 7,
+                "severity": "error",
+                "code": "validation(type)",
+                "message": "\"eighty\" is not of type \"integer\"",
+                "instance_path": "/port",
+                "schema_path": "/properties/port/type",
+                "schema_url": "https://example.com/app.json",
+                "fingerprint": fingerprint,
+            })
+        );
+        assert_eq!(
+            report["diagnostics"][1],
+            json!({
+                "path": "missing.json",
+                "severity": "error",
+                "code": "io",
+                "message": "file not found",
+            })
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod github;
+pub mod json;
 pub mod pretty;
+pub mod sarif;
 pub mod text;
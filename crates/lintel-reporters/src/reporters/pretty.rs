@@ -4,50 +4,70 @@ use std::io::IsTerminal;
 use ansi_term_styles::{BOLD, DIM, RESET};
 use miette::Report;
 
-use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter, format_checked_verbose};
+use lintel_diagnostics::reporter::{
+    CheckResult, CheckedFile, Reporter, format_checked_verbose, format_warnings_note,
+};
+
+use crate::progress::Progress;
 
 /// Pretty reporter: fancy miette output with colors and timing.
 pub struct PrettyReporter {
     pub verbose: bool,
+    /// Print errors only: no warnings and no summary line.
+    pub quiet: bool,
+    pub progress: Option<Progress>,
 }
 
 fn plural(n: usize) -> &'static str {
     if n == 1 { "error" } else { "errors" }
 }
 
-fn print_summary(n: usize, error_count: usize, ms: u128) {
+fn print_summary(n: usize, error_count: usize, warning_count: usize, ms: u128) {
     let label = plural(error_count);
+    let warnings = format_warnings_note(warning_count);
     if error_count > 0 {
         if std::io::stderr().is_terminal() {
             eprintln!(
-                "{BOLD}Checked {n} files{RESET} {DIM}in {ms}ms.{RESET} {BOLD}{error_count} {label} found.{RESET}"
+                "{BOLD}Checked {n} files{RESET} {DIM}in {ms}ms.{RESET} {BOLD}{error_count} {label} found.{RESET}{warnings}"
             );
         } else {
-            eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.");
+            eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.{warnings}");
         }
     } else if std::io::stderr().is_terminal() {
-        eprintln!("{BOLD}Checked {n} files{RESET} {DIM}in {ms}ms.{RESET}");
+        eprintln!("{BOLD}Checked {n} files{RESET} {DIM}in {ms}ms.{RESET}{warnings}");
     } else {
-        eprintln!("Checked {n} files in {ms}ms.");
+        eprintln!("Checked {n} files in {ms}ms.{warnings}");
     }
 }
 
 impl Reporter for PrettyReporter {
     fn report(&mut self, result: CheckResult, elapsed: Duration) {
+        if let Some(progress) = &mut self.progress {
+            progress.clear();
+        }
         let n = result.files_checked();
-        let error_count = result.errors.len();
+        let error_count = result.error_count();
+        let warning_count = result.warning_count();
 
         for error in result.errors {
+            if self.quiet && error.is_warning() {
+                continue;
+            }
             eprintln!("{:?}", Report::new(error));
         }
 
-        let ms = elapsed.as_millis();
-        print_summary(n, error_count, ms);
+        if !self.quiet {
+            let ms = elapsed.as_millis();
+            print_summary(n, error_count, warning_count, ms);
+        }
     }
 
     fn on_file_checked(&mut self, file: &CheckedFile) {
         if self.verbose {
             eprintln!("{}", format_checked_verbose(file));
         }
+        if let Some(progress) = &mut self.progress {
+            progress.on_file_checked(file);
+        }
     }
 }
@@ -0,0 +1,151 @@
+use alloc::collections::BTreeSet;
+use core::time::Duration;
+
+use serde_json::{Value, json};
+
+use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter, format_checked_verbose};
+use lintel_diagnostics::{DEFAULT_LABEL, LintelDiagnostic};
+
+use super::json::{code, level};
+
+/// SARIF reporter: prints a SARIF 2.1.0 log to stdout, for code scanning
+/// dashboards.
+pub struct SarifReporter {
+    pub verbose: bool,
+}
+
+/// Key of lintel's fingerprint in `partialFingerprints`.
+const FINGERPRINT_KEY: &str = "lintel/v1";
+
+fn result_json(error: &LintelDiagnostic) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": error.file().replace('\\', "/") },
+        },
+    });
+    if let Some((line, column)) = error.line_col() {
+        location["physicalLocation"]["region"] = json!({
+            "startLine": line,
+            "startColumn": column,
+        });
+    }
+    let mut result = json!({
+        "ruleId": code(error),
+        "level": level(error),
+        "message": { "text": error.message() },
+        "locations": [location],
+    });
+    if let LintelDiagnostic::Validation(v) = error {
+        result["partialFingerprints"] = json!({ FINGERPRINT_KEY: v.fingerprint() });
+        if v.instance_path != DEFAULT_LABEL {
+            result["locations"][0]["logicalLocations"] =
+                json!([{ "fullyQualifiedName": v.instance_path }]);
+        }
+        if !v.allowed_values.is_empty() {
+            result["properties"] = json!({ "allowedValues": v.allowed_values });
+        }
+        if let Some((span, replacement)) = v.suggested_fix() {
+            result["fixes"] = json!([{
+                "description": { "text": format!("Replace with {replacement}") },
+                "artifactChanges": [{
+                    "artifactLocation": { "uri": error.file().replace('\\', "/") },
+                    "replacements": [{
+                        "deletedRegion": {
+                            "byteOffset": span.offset(),
+                            "byteLength": span.len(),
+                        },
+                        "insertedContent": { "text": replacement },
+                    }],
+                }],
+            }]);
+        }
+    }
+    result
+}
+
+/// The SARIF log for a check run.
+fn sarif_log(result: &CheckResult) -> Value {
+    let rules: BTreeSet<String> = result.errors.iter().map(code).collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "lintel",
+                    "informationUri": "https://github.com/lintel-rs/lintel",
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "results": result.errors.iter().map(result_json).collect::<Vec<_>>(),
+        }],
+    })
+}
+
+impl Reporter for SarifReporter {
+    fn report(&mut self, result: CheckResult, _elapsed: Duration) {
+        let log = sarif_log(&result);
+        println!("{}", serde_json::to_string_pretty(&log).unwrap_or_default());
+    }
+
+    fn on_file_checked(&mut self, file: &CheckedFile) {
+        if self.verbose {
+            eprintln!("{}", format_checked_verbose(file));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sarif_results_carry_fingerprints() {
+        let result = crate::reporters::json::tests::sample();
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation error");
+        };
+        let fingerprint = v.fingerprint();
+        let log = sarif_log(&result);
+        let run = &log["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([{ "id": "io" }, { "id": "validation(type)" }])
+        );
+        assert_eq!(
+            run["results"][0],
+            json!({
+                "ruleId": "validation(type)",
+                "level": "error",
+                "message": { "text": "\"eighty\" is not of type \"integer\"" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "app.yaml" },
+                        "region": { "startLine": 2, "startColumn": 7 },
+                    },
+                    "logicalLocations": [{ "fullyQualifiedName": "/port" }],
+                }],
+                "partialFingerprints": { "lintel/v1": fingerprint },
+            })
+        );
+        assert_eq!(run["results"][1].get("partialFingerprints"), None);
+    }
+
+    #[test]
+    fn sarif_results_carry_suggested_fixes() {
+        let result = result_json(&crate::reporters::json::tests::misspelled());
+        assert_eq!(
+            result["fixes"],
+            json!([{
+                "description": { "text": "Replace with port" },
+                "artifactChanges": [{
+                    "artifactLocation": { "uri": "app.yaml" },
+                    "replacements": [{
+                        "deletedRegion": { "byteOffset": 11, "byteLength": 4 },
+                        "insertedContent": { "text": "port" },
+                    }],
+                }],
+            }])
+        );
+    }
+}
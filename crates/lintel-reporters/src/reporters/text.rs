@@ -2,26 +2,47 @@ use core::time::Duration;
 
 use lintel_diagnostics::DEFAULT_LABEL;
 use lintel_diagnostics::LintelDiagnostic;
-use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Reporter, format_checked_verbose};
+use lintel_diagnostics::reporter::{
+    CheckResult, CheckedFile, Reporter, format_checked_verbose, format_warnings_note,
+};
+
+use crate::progress::Progress;
 
 /// Text reporter: plain one-line-per-error output suitable for CI pipelines.
 pub struct TextReporter {
     pub verbose: bool,
+    /// Print errors only: no warnings and no summary line.
+    pub quiet: bool,
+    pub progress: Option<Progress>,
 }
 
-fn print_lint_errors(errors: &[LintelDiagnostic]) {
+fn print_lint_errors(errors: &[LintelDiagnostic], quiet: bool) {
     for error in errors {
-        let path = error.path();
+        if quiet && error.is_warning() {
+            continue;
+        }
+        let level = if error.is_warning() {
+            "warning"
+        } else {
+            "error"
+        };
         match error {
-            LintelDiagnostic::Validation(v) if v.instance_path != DEFAULT_LABEL => {
-                eprintln!(
-                    "error: {path}: {} (at {})",
-                    error.message(),
-                    v.instance_path,
-                );
+            // `file:line:col`, so editors and terminals can jump to the value
+            LintelDiagnostic::Validation(v) => {
+                let (line, col) = v.line_col();
+                let file = error.file();
+                if v.instance_path == DEFAULT_LABEL {
+                    eprintln!("{level}: {file}:{line}:{col}: {}", error.message());
+                } else {
+                    eprintln!(
+                        "{level}: {file}:{line}:{col}: {} (at {})",
+                        error.message(),
+                        v.instance_path,
+                    );
+                }
             }
             _ => {
-                eprintln!("error: {path}: {}", error.message());
+                eprintln!("{level}: {}: {}", error.path(), error.message());
             }
         }
     }
@@ -31,29 +52,39 @@ fn plural(n: usize) -> &'static str {
     if n == 1 { "error" } else { "errors" }
 }
 
-fn print_summary(n: usize, error_count: usize, ms: u128) {
+fn print_summary(n: usize, error_count: usize, warning_count: usize, ms: u128) {
+    let warnings = format_warnings_note(warning_count);
     if error_count > 0 {
         let label = plural(error_count);
-        eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.");
+        eprintln!("Checked {n} files in {ms}ms. {error_count} {label} found.{warnings}");
     } else {
-        eprintln!("Checked {n} files in {ms}ms. No errors.");
+        eprintln!("Checked {n} files in {ms}ms. No errors.{warnings}");
     }
 }
 
 impl Reporter for TextReporter {
     fn report(&mut self, result: CheckResult, elapsed: Duration) {
+        if let Some(progress) = &mut self.progress {
+            progress.clear();
+        }
         let n = result.files_checked();
-        let error_count = result.errors.len();
+        let error_count = result.error_count();
+        let warning_count = result.warning_count();
 
-        print_lint_errors(&result.errors);
+        print_lint_errors(&result.errors, self.quiet);
 
-        let ms = elapsed.as_millis();
-        print_summary(n, error_count, ms);
+        if !self.quiet {
+            let ms = elapsed.as_millis();
+            print_summary(n, error_count, warning_count, ms);
+        }
     }
 
     fn on_file_checked(&mut self, file: &CheckedFile) {
         if self.verbose {
             eprintln!("{}", format_checked_verbose(file));
         }
+        if let Some(progress) = &mut self.progress {
+            progress.on_file_checked(file);
+        }
     }
 }
@@ -0,0 +1,731 @@
+//! The disk-backed, HTTP-fetching [`SchemaCache`].
+
+use alloc::sync::Arc;
+use core::error::Error;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::entries::{self, EntryLock};
+use crate::retriever::{CacheValidators, HttpRetriever, SchemaRetriever};
+use crate::{
+    CacheStatus, DEFAULT_CACHE_MAX_SIZE, DEFAULT_FETCH_RETRIES, DEFAULT_FETCH_RETRY_BACKOFF,
+    DEFAULT_FETCH_TIMEOUT, DEFAULT_SCHEMA_CACHE_TTL,
+};
+
+/// A disk-backed schema cache with HTTP fetching and JSON parsing.
+///
+/// Schemas are fetched through a [`SchemaRetriever`], over HTTP by default,
+/// and stored in `<cache_dir>` by the SHA-256 of their content, so a schema
+/// served by several URLs is stored once. When a schema is requested, the
+/// cache is checked first; on a miss the schema is fetched and written to
+/// disk for future use, evicting the least recently used schemas once the
+/// directory grows past its size limit.
+#[derive(Clone)]
+pub struct SchemaCache {
+    cache_dir: Option<PathBuf>,
+    /// Where misses are fetched from. `None` in memory-only mode.
+    retriever: Option<Arc<dyn SchemaRetriever>>,
+    skip_read: bool,
+    ttl: Option<Duration>,
+    /// Size limit of the schemas on disk, in bytes.
+    max_size: u64,
+    /// In-memory cache shared across all clones via `Arc`.
+    memory_cache: Arc<Mutex<HashMap<String, Value>>>,
+    /// SHA-256 hex digests of the raw content fetched for each URI.
+    content_hashes: Arc<Mutex<HashMap<String, String>>>,
+    /// Per-URI locks, so concurrent fetches of one URI share a download.
+    in_flight: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+    /// Semaphore that limits concurrent HTTP requests across all callers.
+    http_semaphore: Arc<tokio::sync::Semaphore>,
+}
+
+/// Builder for constructing a [`SchemaCache`] with sensible defaults.
+///
+/// Defaults:
+/// - `cache_dir`: [`ensure_cache_dir()`]
+/// - `force_fetch`: `false`
+/// - `ttl`: [`DEFAULT_SCHEMA_CACHE_TTL`] (12 hours)
+/// - `timeout`: [`DEFAULT_FETCH_TIMEOUT`] (30 seconds)
+/// - `retries`: [`DEFAULT_FETCH_RETRIES`], starting [`DEFAULT_FETCH_RETRY_BACKOFF`] apart
+/// - `max_size`: [`DEFAULT_CACHE_MAX_SIZE`] (500 MB)
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let cache = SchemaCache::builder().build();
+/// let cache = SchemaCache::builder().force_fetch(true).ttl(Duration::from_secs(3600)).build();
+/// ```
+/// Default maximum number of concurrent HTTP requests.
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 20;
+
+#[must_use]
+pub struct SchemaCacheBuilder {
+    cache_dir: Option<PathBuf>,
+    skip_read: bool,
+    ttl: Option<Duration>,
+    max_size: u64,
+    timeout: Duration,
+    retries: u32,
+    retry_backoff: Duration,
+    max_concurrent_requests: usize,
+    retriever: Option<Arc<dyn SchemaRetriever>>,
+}
+
+impl SchemaCacheBuilder {
+    /// Override the default cache directory.
+    pub fn cache_dir(mut self, dir: PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    /// When `true`, bypass cache reads and always fetch from the network.
+    /// Fetched schemas are still written to the cache.
+    pub fn force_fetch(mut self, force: bool) -> Self {
+        self.skip_read = force;
+        self
+    }
+
+    /// Override the default TTL for cached schemas.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Override the size limit of the schemas on disk, in bytes. Once a
+    /// download takes the cache past it, the least recently used schemas
+    /// are deleted.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Override the time limit for a single HTTP request made by the default
+    /// [`HttpRetriever`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Override how many times a request of the default [`HttpRetriever`]
+    /// that timed out, could not connect, or got a 5xx or 429 response is
+    /// retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Override the delay before the first retry. Each further retry waits
+    /// twice as long as the previous one.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum number of concurrent HTTP requests.
+    pub fn max_concurrent_requests(mut self, n: usize) -> Self {
+        self.max_concurrent_requests = n;
+        self
+    }
+
+    /// Fetch schemas through `retriever` instead of the default
+    /// [`HttpRetriever`]. The cache directory, TTL and request limit still
+    /// apply; [`timeout`](Self::timeout), [`retries`](Self::retries) and
+    /// [`retry_backoff`](Self::retry_backoff) do not.
+    pub fn retriever(mut self, retriever: impl SchemaRetriever + 'static) -> Self {
+        self.retriever = Some(Arc::new(retriever));
+        self
+    }
+
+    /// Returns the cache directory that will be used, or [`ensure_cache_dir()`]
+    /// if none was explicitly set.
+    ///
+    /// Useful when callers need the resolved path before calling [`build`](Self::build).
+    pub fn cache_dir_or_default(&self) -> PathBuf {
+        self.cache_dir.clone().unwrap_or_else(ensure_cache_dir)
+    }
+
+    /// Build the [`SchemaCache`].
+    pub fn build(self) -> SchemaCache {
+        let retriever = self.retriever.unwrap_or_else(|| {
+            Arc::new(
+                HttpRetriever::default()
+                    .timeout(self.timeout)
+                    .retries(self.retries)
+                    .retry_backoff(self.retry_backoff),
+            )
+        });
+        SchemaCache {
+            cache_dir: self.cache_dir,
+            retriever: Some(retriever),
+            skip_read: self.skip_read,
+            ttl: self.ttl,
+            max_size: self.max_size,
+            memory_cache: Arc::new(Mutex::new(HashMap::new())),
+            content_hashes: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            http_semaphore: Arc::new(tokio::sync::Semaphore::new(self.max_concurrent_requests)),
+        }
+    }
+}
+
+impl SchemaCache {
+    /// Returns a builder pre-configured with sensible defaults.
+    ///
+    /// - `cache_dir` = [`ensure_cache_dir()`]
+    /// - `ttl` = [`DEFAULT_SCHEMA_CACHE_TTL`]
+    /// - `force_fetch` = `false`
+    /// - `timeout` = [`DEFAULT_FETCH_TIMEOUT`]
+    /// - `retries` = [`DEFAULT_FETCH_RETRIES`]
+    /// - `max_size` = [`DEFAULT_CACHE_MAX_SIZE`]
+    pub fn builder() -> SchemaCacheBuilder {
+        SchemaCacheBuilder {
+            cache_dir: Some(ensure_cache_dir()),
+            skip_read: false,
+            ttl: Some(DEFAULT_SCHEMA_CACHE_TTL),
+            max_size: DEFAULT_CACHE_MAX_SIZE,
+            timeout: DEFAULT_FETCH_TIMEOUT,
+            retries: DEFAULT_FETCH_RETRIES,
+            retry_backoff: DEFAULT_FETCH_RETRY_BACKOFF,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            retriever: None,
+        }
+    }
+
+    /// Test constructor — memory-only, no HTTP, no disk.
+    ///
+    /// Pre-populate with [`insert`](Self::insert). Calls to [`fetch`](Self::fetch)
+    /// for unknown URIs will error.
+    pub fn memory() -> Self {
+        Self {
+            cache_dir: None,
+            retriever: None,
+            skip_read: false,
+            ttl: None,
+            max_size: DEFAULT_CACHE_MAX_SIZE,
+            memory_cache: Arc::new(Mutex::new(HashMap::new())),
+            content_hashes: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            http_semaphore: Arc::new(tokio::sync::Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS)),
+        }
+    }
+
+    /// Insert a value into the in-memory cache (useful for tests).
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    pub fn insert(&self, uri: &str, value: Value) {
+        self.memory_cache
+            .lock()
+            .expect("memory cache poisoned")
+            .insert(uri.to_string(), value);
+    }
+
+    /// Look up a schema by URI from the in-memory cache only.
+    ///
+    /// Returns `None` if the URI is not in memory. Does not check disk cache
+    /// or fetch from the network.
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    pub fn get(&self, uri: &str) -> Option<Value> {
+        self.memory_cache
+            .lock()
+            .expect("memory cache poisoned")
+            .get(uri)
+            .cloned()
+    }
+
+    /// Return the SHA-256 hex digest of the raw content last fetched for `uri`.
+    ///
+    /// Returns `None` if the URI has not been fetched or was inserted via
+    /// [`insert`](Self::insert) (which has no raw content to hash).
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    pub fn content_hash(&self, uri: &str) -> Option<String> {
+        self.content_hashes
+            .lock()
+            .expect("content hashes poisoned")
+            .get(uri)
+            .cloned()
+    }
+
+    /// The lock held while `uri` is fetched.
+    fn flight(&self, uri: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.in_flight
+            .lock()
+            .expect("in-flight fetches poisoned")
+            .entry(uri.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Compute SHA-256 of raw content and store it keyed by URI.
+    fn store_content_hash(&self, uri: &str, content: &str) {
+        let hash = Self::hash_content(content);
+        self.content_hashes
+            .lock()
+            .expect("content hashes poisoned")
+            .insert(uri.to_string(), hash);
+    }
+
+    /// Compute the SHA-256 hash of arbitrary content, returned as a 64-char hex string.
+    pub fn hash_content(content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Fetch a schema by URI, using the disk cache when available.
+    ///
+    /// Returns the parsed schema and a [`CacheStatus`] indicating whether the
+    /// result came from the disk cache, the network, or caching was disabled.
+    ///
+    /// When `skip_read` is set, the cache read is skipped but fetched schemas
+    /// are still written to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema cannot be fetched from the network,
+    /// read from disk cache, or parsed as JSON.
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    #[tracing::instrument(level = "debug", skip(self), fields(status))]
+    pub async fn fetch(
+        &self,
+        uri: &str,
+    ) -> Result<(Value, CacheStatus), Box<dyn Error + Send + Sync>> {
+        // Check in-memory cache first (unless skip_read is set)
+        if !self.skip_read
+            && let Some(value) = self
+                .memory_cache
+                .lock()
+                .expect("memory cache poisoned")
+                .get(uri)
+                .cloned()
+        {
+            tracing::Span::current().record("status", "memory_hit");
+            return Ok((value, CacheStatus::Hit));
+        }
+
+        // Memory-only mode: if not in cache, error out.
+        let Some(ref retriever) = self.retriever else {
+            return Err(format!("memory-only cache: no entry for {uri}").into());
+        };
+
+        // Fetch each URI once at a time, in this process and, through the
+        // entry lock, across processes sharing the cache directory. Callers
+        // that waited find the schema cached by the one before them.
+        let flight = self.flight(uri);
+        let _flight = flight.lock().await;
+        if !self.skip_read
+            && let Some(value) = self.get(uri)
+        {
+            tracing::Span::current().record("status", "memory_hit");
+            return Ok((value, CacheStatus::Hit));
+        }
+        let _entry_lock = match self.cache_dir {
+            Some(ref cache_dir) => EntryLock::acquire(cache_dir, &Self::hash_uri(uri)).await,
+            None => None,
+        };
+        self.fetch_locked(uri, retriever.as_ref()).await
+    }
+
+    /// Fetch a schema from the disk cache or `retriever`, holding the locks
+    /// on its URI.
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    async fn fetch_locked(
+        &self,
+        uri: &str,
+        retriever: &dyn SchemaRetriever,
+    ) -> Result<(Value, CacheStatus), Box<dyn Error + Send + Sync>> {
+        // Check disk cache (unless skip_read is set)
+        let mut stored = CacheValidators::default();
+        let mut cached_content: Option<String> = None;
+
+        if let Some(ref cache_dir) = self.cache_dir {
+            let hash = Self::hash_uri(uri);
+            if let Some(content) = entries::read_content(cache_dir, &hash).await {
+                if !self.skip_read
+                    && !self.is_expired(&entries::pointer_path(cache_dir, &hash))
+                    && let Ok(value) = serde_json::from_str::<Value>(&content)
+                {
+                    // Fresh cache — return immediately
+                    self.store_content_hash(uri, &content);
+                    self.memory_cache
+                        .lock()
+                        .expect("memory cache poisoned")
+                        .insert(uri.to_string(), value.clone());
+                    tracing::Span::current().record("status", "cache_hit");
+                    return Ok((value, CacheStatus::Hit));
+                }
+
+                // Stale or skip_read — revalidate with a conditional fetch,
+                // keeping the cached content for a 304 response.
+                cached_content = Some(content);
+                stored = CacheValidators::read(cache_dir, &hash).await;
+            }
+        }
+
+        // Acquire a permit before fetching
+        let _permit = self
+            .http_semaphore
+            .acquire()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+
+        // Conditional network fetch
+        tracing::Span::current().record("status", "network_fetch");
+        let conditional = retriever.fetch(uri, &stored).await?;
+
+        if conditional.body.is_none() {
+            // 304 Not Modified — use cached content
+            if let Some(content) = cached_content {
+                let value: Value = serde_json::from_str(&content)?;
+                self.store_content_hash(uri, &content);
+                self.memory_cache
+                    .lock()
+                    .expect("memory cache poisoned")
+                    .insert(uri.to_string(), value.clone());
+
+                // Touch the entry to reset TTL
+                if let Some(ref cache_dir) = self.cache_dir {
+                    entries::touch(&entries::pointer_path(cache_dir, &Self::hash_uri(uri)));
+                }
+
+                tracing::Span::current().record("status", "not_modified");
+                return Ok((value, CacheStatus::Hit));
+            }
+        }
+
+        let body = conditional
+            .body
+            .ok_or_else(|| format!("{uri}: 304 Not Modified without a cached copy"))?;
+        let value: Value = serde_json::from_str(&body)?;
+        self.store_content_hash(uri, &body);
+
+        // Populate in-memory cache
+        self.memory_cache
+            .lock()
+            .expect("memory cache poisoned")
+            .insert(uri.to_string(), value.clone());
+
+        let status = if let Some(ref cache_dir) = self.cache_dir {
+            if let Err(e) = entries::write_content(
+                cache_dir,
+                uri,
+                &body,
+                &conditional.validators,
+                self.max_size,
+            )
+            .await
+            {
+                tracing::warn!(
+                    dir = %cache_dir.display(),
+                    error = %e,
+                    "failed to write schema to disk cache"
+                );
+            }
+            CacheStatus::Miss
+        } else {
+            CacheStatus::Disabled
+        };
+
+        Ok((value, status))
+    }
+
+    /// Check whether a cached file has exceeded the configured TTL.
+    ///
+    /// Returns `false` (not expired) when:
+    /// - No TTL is configured (`self.ttl` is `None`)
+    /// - The file metadata or mtime cannot be read (graceful degradation)
+    fn is_expired(&self, path: &std::path::Path) -> bool {
+        let Some(ttl) = self.ttl else {
+            return false;
+        };
+        fs::metadata(path)
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|mtime| mtime.elapsed().ok())
+            .is_some_and(|age| age > ttl)
+    }
+
+    /// Compute the SHA-256 hash of a URI, returned as a 64-char hex string.
+    pub fn hash_uri(uri: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(uri.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Return a usable cache directory for schemas, creating it if necessary.
+///
+/// Tries `<system_cache>/lintel/schemas` first, falling back to
+/// `<temp_dir>/lintel/schemas` when the preferred path is unwritable.
+pub fn ensure_cache_dir() -> PathBuf {
+    let candidates = [
+        dirs::cache_dir().map(|d| d.join("lintel").join("schemas")),
+        Some(std::env::temp_dir().join("lintel").join("schemas")),
+    ];
+    for candidate in candidates.into_iter().flatten() {
+        if fs::create_dir_all(&candidate).is_ok() {
+            return candidate;
+        }
+    }
+    std::env::temp_dir().join("lintel").join("schemas")
+}
+
+// -- jsonschema trait impls --------------------------------------------------
+
+#[async_trait::async_trait]
+impl jsonschema::AsyncRetrieve for SchemaCache {
+    async fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let (value, _status) = self.fetch(uri.as_str()).await?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_uri_deterministic() {
+        let a = SchemaCache::hash_uri("https://example.com/schema.json");
+        let b = SchemaCache::hash_uri("https://example.com/schema.json");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn hash_uri_different_inputs() {
+        let a = SchemaCache::hash_uri("https://example.com/a.json");
+        let b = SchemaCache::hash_uri("https://example.com/b.json");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn hash_uri_is_64_hex_chars() {
+        let h = SchemaCache::hash_uri("https://example.com/schema.json");
+        assert_eq!(h.len(), 64);
+        assert!(h.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    /// Convert a `Box<dyn Error + Send + Sync>` to `anyhow::Error`.
+    #[allow(clippy::needless_pass_by_value)]
+    fn boxerr(e: Box<dyn Error + Send + Sync>) -> anyhow::Error {
+        anyhow::anyhow!("{e}")
+    }
+
+    #[tokio::test]
+    async fn memory_cache_insert_and_fetch() -> anyhow::Result<()> {
+        let cache = SchemaCache::memory();
+        cache.insert(
+            "https://example.com/s.json",
+            serde_json::json!({"type": "object"}),
+        );
+        let (val, status) = cache
+            .fetch("https://example.com/s.json")
+            .await
+            .map_err(boxerr)?;
+        assert_eq!(val, serde_json::json!({"type": "object"}));
+        assert_eq!(status, CacheStatus::Hit);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn memory_cache_missing_uri_errors() {
+        let cache = SchemaCache::memory();
+        assert!(
+            cache
+                .fetch("https://example.com/missing.json")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn async_retrieve_trait_delegates() -> anyhow::Result<()> {
+        let cache = SchemaCache::memory();
+        cache.insert(
+            "https://example.com/s.json",
+            serde_json::json!({"type": "object"}),
+        );
+        let uri: jsonschema::Uri<String> = "https://example.com/s.json".parse()?;
+        let val = jsonschema::AsyncRetrieve::retrieve(&cache, &uri)
+            .await
+            .map_err(boxerr)?;
+        assert_eq!(val, serde_json::json!({"type": "object"}));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validators_round_trip_and_clear() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let hash = SchemaCache::hash_uri("https://example.com/s.json");
+        let validators = CacheValidators {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        validators.write(tmp.path(), &hash).await;
+        assert_eq!(CacheValidators::read(tmp.path(), &hash).await, validators);
+
+        let etag_only = CacheValidators {
+            etag: Some("\"def\"".to_string()),
+            last_modified: None,
+        };
+        etag_only.write(tmp.path(), &hash).await;
+        assert_eq!(CacheValidators::read(tmp.path(), &hash).await, etag_only);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fetch_timeout_reports_attempts() -> anyhow::Result<()> {
+        // Connections are queued in the backlog but never answered.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let uri = format!("http://{}/schema.json", listener.local_addr()?);
+        let tmp = tempfile::tempdir()?;
+        let cache = SchemaCache::builder()
+            .cache_dir(tmp.path().to_path_buf())
+            .timeout(Duration::from_millis(100))
+            .retries(1)
+            .retry_backoff(Duration::from_millis(1))
+            .build();
+        let err = cache
+            .fetch(&uri)
+            .await
+            .expect_err("request should time out");
+        assert_eq!(
+            err.to_string(),
+            "request timed out after 100ms (2 attempts)"
+        );
+        Ok(())
+    }
+
+    /// Serves one schema with a fixed `ETag`, recording the validators of
+    /// every fetch.
+    #[derive(Clone, Default)]
+    struct RecordingRetriever {
+        seen: Arc<Mutex<Vec<CacheValidators>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SchemaRetriever for RecordingRetriever {
+        async fn fetch(
+            &self,
+            uri: &str,
+            cached: &CacheValidators,
+        ) -> Result<crate::FetchResponse, Box<dyn Error + Send + Sync>> {
+            self.seen
+                .lock()
+                .expect("seen poisoned")
+                .push(cached.clone());
+            if uri != "s3://schemas/app.json" {
+                return Err(format!("no such schema: {uri}").into());
+            }
+            let etag = "\"v1\"".to_string();
+            Ok(crate::FetchResponse {
+                body: (cached.etag.as_ref() != Some(&etag))
+                    .then(|| r#"{"type": "object"}"#.to_string()),
+                validators: CacheValidators {
+                    etag: Some(etag),
+                    last_modified: None,
+                },
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_retriever_is_cached_and_revalidated() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let uri = "s3://schemas/app.json";
+        let retriever = RecordingRetriever::default();
+        let builder = || {
+            SchemaCache::builder()
+                .cache_dir(tmp.path().to_path_buf())
+                .retriever(retriever.clone())
+        };
+
+        let cache = builder().build();
+        let (val, status) = cache.fetch(uri).await.map_err(boxerr)?;
+        assert_eq!(val, serde_json::json!({"type": "object"}));
+        assert_eq!(status, CacheStatus::Miss);
+        assert!(cache.fetch("s3://schemas/missing.json").await.is_err());
+
+        // A new cache serves the copy on disk without fetching.
+        let (_, status) = builder().build().fetch(uri).await.map_err(boxerr)?;
+        assert_eq!(status, CacheStatus::Hit);
+
+        // Forcing a fetch revalidates with the stored validators.
+        let cache = builder().force_fetch(true).build();
+        let (val, status) = cache.fetch(uri).await.map_err(boxerr)?;
+        assert_eq!(val, serde_json::json!({"type": "object"}));
+        assert_eq!(status, CacheStatus::Hit);
+
+        let seen = retriever.seen.lock().expect("seen poisoned").clone();
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[0], CacheValidators::default());
+        assert_eq!(seen[2].etag.as_deref(), Some("\"v1\""));
+        Ok(())
+    }
+
+    /// Counts its fetches, each of which takes a moment.
+    #[derive(Clone, Default)]
+    struct SlowRetriever {
+        calls: Arc<core::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl SchemaRetriever for SlowRetriever {
+        async fn fetch(
+            &self,
+            _uri: &str,
+            _cached: &CacheValidators,
+        ) -> Result<crate::FetchResponse, Box<dyn Error + Send + Sync>> {
+            self.calls
+                .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(crate::FetchResponse {
+                body: Some(r#"{"type": "object"}"#.to_string()),
+                validators: CacheValidators::default(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_fetches_share_one_download() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let uri = "https://example.com/s.json";
+        let retriever = SlowRetriever::default();
+        let cache = || {
+            SchemaCache::builder()
+                .cache_dir(tmp.path().to_path_buf())
+                .retriever(retriever.clone())
+                .build()
+        };
+
+        // Within one cache, and between caches sharing the directory as
+        // separate processes would.
+        let (shared, other) = (cache(), cache());
+        let (a, b, c) = tokio::join!(shared.fetch(uri), shared.fetch(uri), other.fetch(uri));
+        let statuses = [
+            a.map_err(boxerr)?.1,
+            b.map_err(boxerr)?.1,
+            c.map_err(boxerr)?.1,
+        ];
+        assert_eq!(
+            retriever.calls.load(core::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        let misses = statuses.iter().filter(|s| **s == CacheStatus::Miss).count();
+        assert_eq!(misses, 1, "{statuses:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn ensure_cache_dir_ends_with_schemas() {
+        let dir = ensure_cache_dir();
+        assert!(dir.ends_with("lintel/schemas"));
+    }
+}
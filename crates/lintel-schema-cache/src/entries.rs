@@ -0,0 +1,547 @@
+//! The on-disk layout of a cache directory, and inspecting and maintaining
+//! the schemas stored in it.
+//!
+//! Schemas are content-addressed: each one is stored once as
+//! `content/<sha256>.json`, named by the SHA-256 of its content, however
+//! many URLs serve it. Each URL has an entry named by the SHA-256 of the URL:
+//! `<hash>.sha256` holds the content hash it points to, and `<hash>.url`,
+//! `<hash>.etag` and `<hash>.last-modified` its URL and HTTP validators.
+//!
+//! Files are written to a temporary name and renamed into place, so other
+//! processes never read a partly written file, and a URL is only fetched
+//! while holding an exclusive lock on `locks/<hash>.lock`, so processes
+//! sharing the directory download it once between them.
+//!
+//! The modification time of `<hash>.sha256` is when the URL was last
+//! downloaded or revalidated, which the TTL is measured from. The
+//! modification time of a content file is when it was last used, so the
+//! least recently used content is evicted first when the directory grows
+//! past its size limit.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::{CacheValidators, SchemaCache};
+
+/// Subdirectory holding the content-addressed schemas.
+const CONTENT_DIR: &str = "content";
+/// Subdirectory holding the lock file of each entry.
+const LOCK_DIR: &str = "locks";
+/// Entry file holding the content hash a URL points to.
+const POINTER_EXT: &str = "sha256";
+/// Entry file holding the URL a schema was fetched from.
+const URL_EXT: &str = "url";
+/// Every file of an entry.
+const ENTRY_EXTS: [&str; 4] = [POINTER_EXT, URL_EXT, "etag", "last-modified"];
+
+/// The file recording which content the URL with the given hash points to.
+pub(crate) fn pointer_path(cache_dir: &Path, hash: &str) -> PathBuf {
+    cache_dir.join(format!("{hash}.{POINTER_EXT}"))
+}
+
+fn content_path(cache_dir: &Path, content_hash: &str) -> PathBuf {
+    cache_dir
+        .join(CONTENT_DIR)
+        .join(format!("{content_hash}.json"))
+}
+
+fn lock_path(cache_dir: &Path, hash: &str) -> PathBuf {
+    cache_dir.join(LOCK_DIR).join(format!("{hash}.lock"))
+}
+
+/// An exclusive lock on the entry of one URL, held across processes.
+/// Released when dropped.
+pub(crate) struct EntryLock {
+    _file: fs::File,
+}
+
+impl EntryLock {
+    /// Wait for the lock on the entry with the given URI hash. Returns `None`
+    /// if the lock file cannot be created, for example in a read-only cache
+    /// directory, in which case the caller goes ahead without it.
+    pub(crate) async fn acquire(cache_dir: &Path, hash: &str) -> Option<Self> {
+        let path = lock_path(cache_dir, hash);
+        tokio::task::spawn_blocking(move || {
+            fs::create_dir_all(path.parent()?).ok()?;
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)
+                .ok()?;
+            file.lock().ok()?;
+            Some(Self { _file: file })
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+}
+
+/// Write `contents` to a temporary file next to `path` and rename it into
+/// place.
+async fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let tmp = path.with_extension(format!(
+        "tmp.{}.{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&tmp, contents).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Mark `path` as modified now.
+pub(crate) fn touch(path: &Path) {
+    let _ = filetime::set_file_mtime(path, filetime::FileTime::now());
+}
+
+/// Read the schema cached for the URL with the given hash, marking its
+/// content as used.
+pub(crate) async fn read_content(cache_dir: &Path, hash: &str) -> Option<String> {
+    let content_hash = tokio::fs::read_to_string(pointer_path(cache_dir, hash))
+        .await
+        .ok()?;
+    let path = content_path(cache_dir, content_hash.trim());
+    let content = tokio::fs::read_to_string(&path).await.ok()?;
+    touch(&path);
+    Some(content)
+}
+
+/// Store `content` as the schema for `uri`, then evict the least recently
+/// used content until the directory is no larger than `max_size` bytes.
+///
+/// # Errors
+///
+/// Returns an error if the content or the entry cannot be written.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn write_content(
+    cache_dir: &Path,
+    uri: &str,
+    content: &str,
+    validators: &CacheValidators,
+    max_size: u64,
+) -> io::Result<()> {
+    let hash = SchemaCache::hash_uri(uri);
+    let content_hash = SchemaCache::hash_content(content);
+    let path = content_path(cache_dir, &content_hash);
+    tokio::fs::create_dir_all(cache_dir.join(CONTENT_DIR)).await?;
+    write_atomic(&path, content).await?;
+    validators.write(cache_dir, &hash).await;
+    write_atomic(&cache_dir.join(format!("{hash}.{URL_EXT}")), uri).await?;
+    // Written last, so an entry never points to content that is not there.
+    write_atomic(&pointer_path(cache_dir, &hash), &content_hash).await?;
+    // Scanning the content directory blocks, so keep it off the runtime.
+    let cache_dir = cache_dir.to_path_buf();
+    let _ = tokio::task::spawn_blocking(move || evict(&cache_dir, max_size, &path)).await;
+    Ok(())
+}
+
+impl CacheValidators {
+    /// Read the validators stored for the schema with the given URI hash.
+    pub(crate) async fn read(cache_dir: &Path, hash: &str) -> Self {
+        let read = |ext: &str| tokio::fs::read_to_string(cache_dir.join(format!("{hash}.{ext}")));
+        Self {
+            etag: read("etag").await.ok(),
+            last_modified: read("last-modified").await.ok(),
+        }
+    }
+
+    /// Store the validators for the schema with the given URI hash, removing
+    /// any the server no longer sends.
+    pub(crate) async fn write(&self, cache_dir: &Path, hash: &str) {
+        for (ext, value) in [("etag", &self.etag), ("last-modified", &self.last_modified)] {
+            let path = cache_dir.join(format!("{hash}.{ext}"));
+            let _ = match value {
+                Some(value) => write_atomic(&path, value).await,
+                None => tokio::fs::remove_file(&path).await,
+            };
+        }
+    }
+}
+
+/// A content file, with its size and when it was last used.
+struct ContentFile {
+    path: PathBuf,
+    size: u64,
+    used: SystemTime,
+}
+
+fn content_files(cache_dir: &Path) -> Vec<ContentFile> {
+    let Ok(dir) = fs::read_dir(cache_dir.join(CONTENT_DIR)) else {
+        return Vec::new();
+    };
+    dir.filter_map(|entry| {
+        let entry = entry.ok()?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            return None;
+        }
+        let meta = entry.metadata().ok().filter(fs::Metadata::is_file)?;
+        Some(ContentFile {
+            path,
+            size: meta.len(),
+            used: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        })
+    })
+    .collect()
+}
+
+/// Delete the least recently used content, other than `keep`, until the
+/// content is no larger than `max_size` bytes. Entries pointing to deleted
+/// content are misses from then on.
+fn evict(cache_dir: &Path, max_size: u64, keep: &Path) {
+    let mut files = content_files(cache_dir);
+    let mut total: u64 = files.iter().map(|f| f.size).sum();
+    if total <= max_size {
+        return;
+    }
+    files.sort_by_key(|f| f.used);
+    for file in files {
+        if total <= max_size {
+            break;
+        }
+        if file.path != keep && fs::remove_file(&file.path).is_ok() {
+            tracing::debug!(path = %file.path.display(), "evicted cached schema");
+            total -= file.size;
+        }
+    }
+}
+
+/// Delete what no schema needs any more: entries whose content was evicted,
+/// content no entry points to, and leftover files, such as those of older
+/// cache layouts. Returns the number of bytes freed.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory exists but cannot be read.
+pub fn collect_garbage(cache_dir: &Path) -> io::Result<u64> {
+    let dir = match fs::read_dir(cache_dir) {
+        Ok(dir) => dir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut freed = 0;
+    let mut remove = |path: &Path| {
+        let size = fs::metadata(path).map_or(0, |m| m.len());
+        if fs::remove_file(path).is_ok() {
+            freed += size;
+        }
+    };
+    let mut referenced = HashSet::new();
+    for entry in dir.filter_map(Result::ok) {
+        let path = entry.path();
+        let Some((hash, ext)) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.split_once('.'))
+            .map(|(hash, ext)| (hash.to_string(), ext.to_string()))
+        else {
+            continue;
+        };
+        if !is_hash(&hash) {
+            continue;
+        }
+        if ext == POINTER_EXT {
+            match fs::read_to_string(&path) {
+                Ok(content_hash) if content_path(cache_dir, content_hash.trim()).is_file() => {
+                    referenced.insert(content_hash.trim().to_string());
+                }
+                _ => {
+                    for ext in ENTRY_EXTS {
+                        remove(&path.with_extension(ext));
+                    }
+                }
+            }
+        } else if !ENTRY_EXTS.contains(&ext.as_str()) || !pointer_path(cache_dir, &hash).exists() {
+            remove(&path);
+        }
+    }
+    for file in content_files(cache_dir) {
+        let content_hash = file.path.file_stem().and_then(|s| s.to_str());
+        if !content_hash.is_some_and(|h| referenced.contains(h)) {
+            remove(&file.path);
+        }
+    }
+    Ok(freed)
+}
+
+/// A schema stored in a cache directory.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// SHA-256 of the URL, which names the entry's files.
+    pub hash: String,
+    /// SHA-256 of the schema, which names its content file.
+    pub content_hash: String,
+    /// The cached schema, `<cache_dir>/content/<content_hash>.json`.
+    pub path: PathBuf,
+    /// The URL the schema was fetched from. `None` if it was not recorded.
+    pub url: Option<String>,
+    /// Size of the schema in bytes.
+    pub size: u64,
+    /// When the schema was last downloaded or revalidated.
+    pub modified: Option<SystemTime>,
+}
+
+/// The outcome of [`CacheEntry::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// The schema matches its content hash and is valid JSON.
+    Ok,
+    /// The schema is damaged, for the given reason.
+    Corrupt(String),
+}
+
+impl CacheEntry {
+    /// Every schema in `cache_dir`, sorted by URL. A missing directory has
+    /// none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but cannot be read.
+    pub fn list(cache_dir: &Path) -> io::Result<Vec<Self>> {
+        let dir = match fs::read_dir(cache_dir) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries: Vec<Self> = dir
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                let hash = name.to_str()?.strip_suffix(".sha256")?;
+                is_hash(hash).then(|| Self::load(cache_dir, hash, None))?
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.url, &a.hash).cmp(&(&b.url, &b.hash)));
+        Ok(entries)
+    }
+
+    /// The cached schema for `url` in `cache_dir`, if there is one.
+    pub fn find(cache_dir: &Path, url: &str) -> Option<Self> {
+        Self::load(cache_dir, &SchemaCache::hash_uri(url), Some(url))
+    }
+
+    fn load(cache_dir: &Path, hash: &str, url: Option<&str>) -> Option<Self> {
+        let pointer = pointer_path(cache_dir, hash);
+        let content_hash = fs::read_to_string(&pointer).ok()?.trim().to_string();
+        let path = content_path(cache_dir, &content_hash);
+        let size = fs::metadata(&path)
+            .ok()
+            .filter(fs::Metadata::is_file)?
+            .len();
+        let url = url
+            .map(str::to_string)
+            .or_else(|| fs::read_to_string(pointer.with_extension(URL_EXT)).ok());
+        Some(Self {
+            hash: hash.to_string(),
+            content_hash,
+            path,
+            url,
+            size,
+            modified: fs::metadata(&pointer).and_then(|m| m.modified()).ok(),
+        })
+    }
+
+    /// Time since the schema was last downloaded or revalidated.
+    pub fn age(&self) -> Option<Duration> {
+        self.modified?.elapsed().ok()
+    }
+
+    /// The HTTP validators stored with the schema.
+    pub fn validators(&self) -> CacheValidators {
+        let read = |ext: &str| fs::read_to_string(self.entry_file(ext)).ok();
+        CacheValidators {
+            etag: read("etag"),
+            last_modified: read("last-modified"),
+        }
+    }
+
+    /// Check the schema against its content hash, and that it is still
+    /// valid JSON.
+    pub fn verify(&self) -> Verification {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) => return Verification::Corrupt(format!("cannot be read: {e}")),
+        };
+        if let Some(ref url) = self.url
+            && SchemaCache::hash_uri(url) != self.hash
+        {
+            return Verification::Corrupt(format!("entry does not match its URL {url}"));
+        }
+        let actual = SchemaCache::hash_content(&content);
+        if actual != self.content_hash {
+            return Verification::Corrupt(format!(
+                "content hash {actual} differs from recorded {}",
+                self.content_hash
+            ));
+        }
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(&content) {
+            return Verification::Corrupt(format!("not valid JSON: {e}"));
+        }
+        Verification::Ok
+    }
+
+    /// Delete the entry. Its content is deleted by [`collect_garbage`] once
+    /// no other entry points to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be deleted.
+    pub fn remove(&self) -> io::Result<()> {
+        fs::remove_file(self.entry_file(POINTER_EXT))?;
+        for ext in ENTRY_EXTS {
+            let _ = fs::remove_file(self.entry_file(ext));
+        }
+        if let Some(cache_dir) = self.cache_dir() {
+            let _ = fs::remove_file(lock_path(cache_dir, &self.hash));
+        }
+        Ok(())
+    }
+
+    fn cache_dir(&self) -> Option<&Path> {
+        self.path.parent()?.parent()
+    }
+
+    fn entry_file(&self, ext: &str) -> PathBuf {
+        let cache_dir = self.cache_dir().unwrap_or(Path::new("."));
+        cache_dir.join(format!("{}.{ext}", self.hash))
+    }
+}
+
+/// Whether `name` is a SHA-256 hex digest, as cache files are named.
+fn is_hash(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::error::Error;
+
+    use super::*;
+    use crate::{FetchResponse, SchemaRetriever};
+
+    /// Serves a schema of `len` bytes at every URL ending in `.json`; two
+    /// URLs ending in the same letter serve the same schema.
+    struct Fixed {
+        len: usize,
+    }
+
+    #[async_trait::async_trait]
+    impl SchemaRetriever for Fixed {
+        async fn fetch(
+            &self,
+            uri: &str,
+            _cached: &CacheValidators,
+        ) -> Result<FetchResponse, Box<dyn Error + Send + Sync>> {
+            let stem = uri.strip_suffix(".json").ok_or("not a schema")?;
+            let letter = stem.chars().last().unwrap_or_default();
+            let padding = " ".repeat(self.len.saturating_sub(13));
+            Ok(FetchResponse {
+                body: Some(format!(r#"{{"$id":"{letter}"{padding}}}"#)),
+                validators: CacheValidators {
+                    etag: Some("\"v1\"".to_string()),
+                    last_modified: None,
+                },
+            })
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn boxerr(e: Box<dyn Error + Send + Sync>) -> anyhow::Error {
+        anyhow::anyhow!("{e}")
+    }
+
+    #[tokio::test]
+    async fn lists_verifies_and_removes_entries() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let cache = SchemaCache::builder()
+            .cache_dir(tmp.path().to_path_buf())
+            .retriever(Fixed { len: 100 })
+            .build();
+        for uri in [
+            "https://example.com/b.json",
+            "https://example.com/a.json",
+            "https://mirror.example.com/a.json",
+        ] {
+            cache.fetch(uri).await.map_err(boxerr)?;
+        }
+        // A file of the old layout, which kept schemas by URL hash.
+        let legacy = tmp.path().join(format!("{}.json", "0".repeat(64)));
+        fs::write(&legacy, "{}")?;
+
+        let entries = CacheEntry::list(tmp.path())?;
+        let urls: Vec<_> = entries.iter().filter_map(|e| e.url.as_deref()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/a.json",
+                "https://example.com/b.json",
+                "https://mirror.example.com/a.json"
+            ]
+        );
+        assert_eq!(entries[0].path, entries[2].path, "same content is shared");
+        assert_eq!(content_files(tmp.path()).len(), 2);
+        assert_eq!(entries[0].verify(), Verification::Ok);
+        assert_eq!(entries[0].validators().etag.as_deref(), Some("\"v1\""));
+
+        fs::write(&entries[1].path, r#"{"$id": "tampered"}"#)?;
+        assert!(matches!(entries[1].verify(), Verification::Corrupt(_)));
+
+        entries[0].remove()?;
+        entries[1].remove()?;
+        assert!(CacheEntry::find(tmp.path(), "https://example.com/a.json").is_none());
+        assert!(collect_garbage(tmp.path())? > 0);
+        assert!(!legacy.exists());
+        assert_eq!(content_files(tmp.path()).len(), 1, "a.json is still used");
+        assert!(CacheEntry::find(tmp.path(), "https://mirror.example.com/a.json").is_some());
+        assert!(CacheEntry::list(&tmp.path().join("missing"))?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_content() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let cache = || {
+            SchemaCache::builder()
+                .cache_dir(tmp.path().to_path_buf())
+                .retriever(Fixed { len: 100 })
+                .max_size(250)
+                .build()
+        };
+        cache()
+            .fetch("https://example.com/a.json")
+            .await
+            .map_err(boxerr)?;
+        cache()
+            .fetch("https://example.com/b.json")
+            .await
+            .map_err(boxerr)?;
+        // Make b.json the least recently used, then use a.json.
+        let past = filetime::FileTime::from_unix_time(0, 0);
+        let b = CacheEntry::find(tmp.path(), "https://example.com/b.json");
+        filetime::set_file_mtime(&b.ok_or_else(|| anyhow::anyhow!("b.json"))?.path, past)?;
+        let (_, status) = cache()
+            .fetch("https://example.com/a.json")
+            .await
+            .map_err(boxerr)?;
+        assert_eq!(status, crate::CacheStatus::Hit);
+
+        cache()
+            .fetch("https://example.com/c.json")
+            .await
+            .map_err(boxerr)?;
+        assert!(CacheEntry::find(tmp.path(), "https://example.com/a.json").is_some());
+        assert!(CacheEntry::find(tmp.path(), "https://example.com/b.json").is_none());
+        assert!(CacheEntry::find(tmp.path(), "https://example.com/c.json").is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! The [`SchemaRetriever`] a [`SchemaCache`](crate::SchemaCache) downloads
+//! schemas through, and the built-in [`HttpRetriever`].
+
+use core::error::Error;
+use core::time::Duration;
+
+use crate::{DEFAULT_FETCH_RETRIES, DEFAULT_FETCH_RETRY_BACKOFF, DEFAULT_FETCH_TIMEOUT};
+
+/// Cache validators stored next to a cached schema, used to revalidate it
+/// once its TTL has expired.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CacheValidators {
+    /// `ETag` header, sent back as `If-None-Match`.
+    pub etag: Option<String>,
+    /// `Last-Modified` header, sent back as `If-Modified-Since`.
+    pub last_modified: Option<String>,
+}
+
+/// What a [`SchemaRetriever`] fetched.
+#[derive(Debug)]
+pub struct FetchResponse {
+    /// The raw schema. `None` means the cached copy is still current, like a
+    /// 304 Not Modified response.
+    pub body: Option<String>,
+    /// Validators to store with the schema for the next revalidation.
+    pub validators: CacheValidators,
+}
+
+/// Downloads schemas on behalf of a [`SchemaCache`](crate::SchemaCache).
+///
+/// The cache handles the memory and disk layers, the TTL and the limit on
+/// concurrent requests, and only calls the retriever on a miss or to
+/// revalidate an expired entry. Implement it to fetch schemas from stores
+/// the built-in [`HttpRetriever`] cannot reach, or with credentials it does
+/// not send, and pass it to
+/// [`SchemaCacheBuilder::retriever`](crate::SchemaCacheBuilder::retriever).
+#[async_trait::async_trait]
+pub trait SchemaRetriever: Send + Sync {
+    /// Fetch the schema at `uri`.
+    ///
+    /// `cached` holds the validators stored with the cached copy, if there
+    /// is one. A retriever that can tell the copy is still current may
+    /// return a [`FetchResponse`] without a body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema cannot be fetched.
+    async fn fetch(
+        &self,
+        uri: &str,
+        cached: &CacheValidators,
+    ) -> Result<FetchResponse, Box<dyn Error + Send + Sync>>;
+}
+
+/// The default [`SchemaRetriever`]: conditional GET requests with reqwest,
+/// retrying transient failures with exponential backoff.
+#[must_use]
+pub struct HttpRetriever {
+    client: reqwest::Client,
+    timeout: Duration,
+    retries: u32,
+    retry_backoff: Duration,
+}
+
+impl Default for HttpRetriever {
+    fn default() -> Self {
+        Self::new(reqwest::Client::new())
+    }
+}
+
+impl HttpRetriever {
+    /// Send requests with `client`, with the default timeout and retries.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            timeout: DEFAULT_FETCH_TIMEOUT,
+            retries: DEFAULT_FETCH_RETRIES,
+            retry_backoff: DEFAULT_FETCH_RETRY_BACKOFF,
+        }
+    }
+
+    /// Override the time limit for a single request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Override how many times a request that timed out, could not connect,
+    /// or got a 5xx or 429 response is retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Override the delay before the first retry. Each further retry waits
+    /// twice as long as the previous one.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Internal: perform a single conditional GET.
+    async fn get_conditional_once(
+        &self,
+        uri: &str,
+        cached: &CacheValidators,
+    ) -> Result<FetchResponse, reqwest::Error> {
+        let mut req = self.client.get(uri).timeout(self.timeout);
+        if let Some(ref etag) = cached.etag {
+            req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(ref last_modified) = cached.last_modified {
+            req = req.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let resp = req.send().await?;
+        if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResponse {
+                body: None,
+                validators: CacheValidators::default(),
+            });
+        }
+        let resp = resp.error_for_status()?;
+        let header = |name: reqwest::header::HeaderName| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let validators = CacheValidators {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        };
+        let body = resp.text().await?;
+        Ok(FetchResponse {
+            body: Some(body),
+            validators,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SchemaRetriever for HttpRetriever {
+    async fn fetch(
+        &self,
+        uri: &str,
+        cached: &CacheValidators,
+    ) -> Result<FetchResponse, Box<dyn Error + Send + Sync>> {
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.get_conditional_once(uri, cached).await {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt <= self.retries && is_transient(&e) => {
+                    tracing::debug!(uri, attempt, error = %e, "retrying schema fetch");
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(e) if e.is_timeout() => {
+                    return Err(format!(
+                        "request timed out after {:?} ({attempt} attempt{})",
+                        self.timeout,
+                        if attempt == 1 { "" } else { "s" }
+                    )
+                    .into());
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Whether a failed request is worth retrying: it timed out, could not
+/// connect, or the server answered with a 5xx or 429 status.
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout()
+        || err.is_connect()
+        || err
+            .status()
+            .is_some_and(|s| s.is_server_error() || s == reqwest::StatusCode::TOO_MANY_REQUESTS)
+}
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result, bail};
 use bpaf::Bpaf;
 
+pub mod lint;
+
 #[derive(Debug, Clone, Bpaf)]
 #[bpaf(generate(schema_command_inner))]
 pub enum SchemaCommand {
@@ -0,0 +1,790 @@
+//! Lints for JSON Schema documents, run by `lintel schema-lint`.
+//!
+//! Each finding names the rule that produced it (see [`RULES`]), its
+//! severity and the JSON pointer of the schema it is about. The
+//! `[schema-lint]` table of `lintel.toml` changes a rule's severity or turns
+//! it off, and a `lintel-disable-next-line` comment (see [`Suppressions`])
+//! silences findings on the line after it.
+
+use std::collections::{HashMap, HashSet};
+
+use lintel_config::Severity;
+use serde_json::{Map, Value};
+
+/// A lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// The ID used in `[schema-lint]` and disable comments.
+    pub id: &'static str,
+    /// What the rule reports.
+    pub description: &'static str,
+    /// Severity when `[schema-lint]` does not set one.
+    pub default_severity: Severity,
+}
+
+/// Every rule.
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "unreachable-def",
+        description: "`$defs` and `definitions` entries no `$ref` reaches",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        id: "missing-description",
+        description: "properties without a `description`",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        id: "duplicate-enum",
+        description: "`enum` values listed more than once",
+        default_severity: Severity::Error,
+    },
+    Rule {
+        id: "default-type-mismatch",
+        description: "`default` values that do not match the schema's `type`",
+        default_severity: Severity::Error,
+    },
+    Rule {
+        id: "ambiguous-one-of",
+        description: "`oneOf` branches that can match the same instance",
+        default_severity: Severity::Error,
+    },
+    Rule {
+        id: "missing-additional-properties",
+        description: "object schemas that do not decide on `additionalProperties`",
+        default_severity: Severity::Warning,
+    },
+    Rule {
+        id: "unknown-keyword",
+        description: "keywords that are not part of JSON Schema or a declared vocabulary",
+        default_severity: Severity::Off,
+    },
+];
+
+/// The rule with the given ID.
+pub fn rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Keys whose value is a schema, inside a schema.
+const SUBSCHEMA_KEYS: &[&str] = &[
+    "items",
+    "additionalItems",
+    "additionalProperties",
+    "not",
+    "if",
+    "then",
+    "else",
+    "contains",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "contentSchema",
+];
+
+/// Keys whose value is a map of schemas, inside a schema.
+const SUBSCHEMA_MAP_KEYS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+];
+
+/// Keys whose value is a list of schemas, inside a schema.
+const SUBSCHEMA_LIST_KEYS: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+
+/// Keys that describe a schema without constraining what it accepts.
+const ANNOTATION_KEYS: &[&str] = &[
+    "type",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "$comment",
+];
+
+/// Keywords of JSON Schema drafts 4 to 2020-12, and the ones lintel reads.
+const KEYWORDS: &[&str] = &[
+    // Core
+    "$schema",
+    "$id",
+    "id",
+    "$ref",
+    "$anchor",
+    "$dynamicRef",
+    "$dynamicAnchor",
+    "$recursiveRef",
+    "$recursiveAnchor",
+    "$vocabulary",
+    "$comment",
+    "$defs",
+    "definitions",
+    // Applicators
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "if",
+    "then",
+    "else",
+    "dependentSchemas",
+    "dependencies",
+    "prefixItems",
+    "items",
+    "additionalItems",
+    "contains",
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    // Validation
+    "type",
+    "enum",
+    "const",
+    "multipleOf",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "maxContains",
+    "minContains",
+    "maxProperties",
+    "minProperties",
+    "required",
+    "dependentRequired",
+    // Meta-data, format and content
+    "title",
+    "description",
+    "default",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "examples",
+    "format",
+    "contentEncoding",
+    "contentMediaType",
+    "contentSchema",
+    // Custom error messages, read by lintel like ajv-errors
+    "errorMessage",
+];
+
+/// A problem found in a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The rule that reported it, e.g. `duplicate-enum`.
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// JSON pointer to the schema or keyword the finding is about.
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Where a schema sits relative to its parent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Root,
+    /// A value of `properties`.
+    Property,
+    /// A branch of `allOf`, `anyOf` or `oneOf`.
+    Branch,
+    Other,
+}
+
+/// Run every rule on `schema`, with severities from `levels` (the
+/// `[schema-lint]` table). Findings of rules that are `off` are dropped.
+///
+/// `annotations` are custom keywords the `unknown-keyword` rule accepts, on
+/// top of the JSON Schema ones.
+pub fn lint<S: core::hash::BuildHasher>(
+    schema: &Value,
+    levels: &HashMap<String, Severity, S>,
+    annotations: &[&str],
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    walk(
+        schema,
+        &mut String::new(),
+        Position::Root,
+        &mut |map, pointer, position| {
+            check_schema(map, pointer, position, &mut findings);
+            unknown_keywords(map, pointer, annotations, &mut findings);
+        },
+    );
+    unreachable_defs(schema, &mut findings);
+    findings.retain_mut(|finding| {
+        if let Some(&level) = levels.get(finding.rule) {
+            finding.severity = level;
+        }
+        finding.severity != Severity::Off
+    });
+    findings
+}
+
+/// A finding of `rule` at its default severity.
+fn finding(rule: &'static str, pointer: String, message: String) -> Finding {
+    Finding {
+        rule,
+        severity: self::rule(rule).map_or(Severity::Error, |r| r.default_severity),
+        pointer,
+        message,
+    }
+}
+
+/// `lintel-disable-next-line` comments in a schema's source.
+///
+/// A `#` or `//` comment of the form `lintel-disable-next-line` silences
+/// every finding on the next line; listing rule IDs after it
+/// (`lintel-disable-next-line missing-description, unreachable-def`) silences
+/// only those. Text after ` -- ` is a free-form reason.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// Silenced rules by 1-based line; an empty list silences every rule.
+    lines: HashMap<usize, Vec<String>>,
+}
+
+impl Suppressions {
+    pub fn parse(content: &str) -> Self {
+        let mut lines = HashMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let Some(comment) = trimmed
+                .strip_prefix("//")
+                .or_else(|| trimmed.strip_prefix('#'))
+            else {
+                continue;
+            };
+            let Some(rest) = comment
+                .trim_start()
+                .strip_prefix("lintel-disable-next-line")
+            else {
+                continue;
+            };
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let rules = rest.split(" -- ").next().unwrap_or_default();
+            let rules = rules
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect();
+            lines.insert(i + 2, rules);
+        }
+        Self { lines }
+    }
+
+    /// Whether a finding of `rule` on `line` (1-based) is silenced.
+    pub fn silences(&self, line: usize, rule: &str) -> bool {
+        self.lines
+            .get(&line)
+            .is_some_and(|rules| rules.is_empty() || rules.iter().any(|id| id == rule))
+    }
+}
+
+/// Append `key` to a JSON pointer.
+fn push_segment(pointer: &mut String, key: &str) {
+    pointer.push('/');
+    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
+
+/// Called by [`walk`] with a schema, its JSON pointer and its position.
+type Visit<'a> = dyn FnMut(&Map<String, Value>, &str, Position) + 'a;
+
+/// Call `visit` with every schema in `value`.
+fn walk(value: &Value, pointer: &mut String, position: Position, visit: &mut Visit<'_>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    visit(map, pointer, position);
+
+    for (key, child) in map {
+        let key = key.as_str();
+        let len = pointer.len();
+        push_segment(pointer, key);
+        if SUBSCHEMA_MAP_KEYS.contains(&key) {
+            let position = if key == "properties" {
+                Position::Property
+            } else {
+                Position::Other
+            };
+            for (name, schema) in child.as_object().into_iter().flatten() {
+                let len = pointer.len();
+                push_segment(pointer, name);
+                walk(schema, pointer, position, visit);
+                pointer.truncate(len);
+            }
+        } else if SUBSCHEMA_LIST_KEYS.contains(&key) || (key == "items" && child.is_array()) {
+            let position = if key == "prefixItems" || key == "items" {
+                Position::Other
+            } else {
+                Position::Branch
+            };
+            for (i, schema) in child.as_array().into_iter().flatten().enumerate() {
+                let len = pointer.len();
+                push_segment(pointer, &i.to_string());
+                walk(schema, pointer, position, visit);
+                pointer.truncate(len);
+            }
+        } else if SUBSCHEMA_KEYS.contains(&key) {
+            walk(child, pointer, Position::Other, visit);
+        }
+        pointer.truncate(len);
+    }
+}
+
+fn check_schema(
+    map: &Map<String, Value>,
+    pointer: &str,
+    position: Position,
+    out: &mut Vec<Finding>,
+) {
+    let mut report = |rule: &'static str, suffix: &str, message: String| {
+        out.push(finding(rule, format!("{pointer}{suffix}"), message));
+    };
+
+    if position == Position::Property
+        && !map.contains_key("description")
+        && !map.contains_key("$ref")
+    {
+        report(
+            "missing-description",
+            "",
+            "property has no description".into(),
+        );
+    }
+
+    if let Some(Value::Array(values)) = map.get("enum") {
+        for value in duplicates(values) {
+            report(
+                "duplicate-enum",
+                "/enum",
+                format!("enum lists {value} more than once"),
+            );
+        }
+    }
+
+    if let (Some(default), Some(types)) = (map.get("default"), types(map))
+        && !types.iter().any(|ty| has_type(default, ty))
+    {
+        report(
+            "default-type-mismatch",
+            "/default",
+            format!("default {default} is not of type {}", types.join(" or ")),
+        );
+    }
+
+    if let Some(Value::Array(branches)) = map.get("oneOf") {
+        for (i, j) in ambiguous_pairs(branches) {
+            report(
+                "ambiguous-one-of",
+                "/oneOf",
+                format!("oneOf branches {i} and {j} can match the same instance"),
+            );
+        }
+    }
+
+    let is_object = types(map).map_or(map.contains_key("properties"), |t| t.contains(&"object"));
+    let decided = ["additionalProperties", "unevaluatedProperties"]
+        .iter()
+        .any(|key| map.contains_key(*key));
+    let composed = ["$ref", "allOf", "anyOf", "oneOf"]
+        .iter()
+        .any(|key| map.contains_key(*key));
+    if is_object && !decided && !composed && position != Position::Branch {
+        report(
+            "missing-additional-properties",
+            "",
+            "object schema does not set additionalProperties".into(),
+        );
+    }
+}
+
+/// Report keywords of `map` that are neither JSON Schema keywords nor listed
+/// in `annotations`.
+fn unknown_keywords(
+    map: &Map<String, Value>,
+    pointer: &str,
+    annotations: &[&str],
+    out: &mut Vec<Finding>,
+) {
+    for key in map.keys() {
+        if KEYWORDS.contains(&key.as_str()) || annotations.contains(&key.as_str()) {
+            continue;
+        }
+        let mut at = pointer.to_string();
+        push_segment(&mut at, key);
+        out.push(finding(
+            "unknown-keyword",
+            at,
+            format!("unknown keyword {key}"),
+        ));
+    }
+}
+
+/// Values listed more than once, each reported once.
+fn duplicates(values: &[Value]) -> Vec<&Value> {
+    values
+        .iter()
+        .enumerate()
+        .filter(|&(i, value)| {
+            values.iter().position(|v| v == value) == Some(i) && values[i + 1..].contains(value)
+        })
+        .map(|(_, value)| value)
+        .collect()
+}
+
+/// The index pairs of `oneOf` branches that are [`ambiguous`].
+fn ambiguous_pairs(branches: &[Value]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (i, a) in branches.iter().enumerate() {
+        for (j, b) in branches.iter().enumerate().skip(i + 1) {
+            if ambiguous(a, b) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// The schema's `type` as a list, or `None` if it accepts every type.
+fn types(map: &Map<String, Value>) -> Option<Vec<&str>> {
+    match map.get("type")? {
+        Value::String(ty) => Some(vec![ty.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value
+                    .as_f64()
+                    .is_some_and(|n| n.fract().abs() < f64::EPSILON)
+        }
+        _ => true,
+    }
+}
+
+/// Whether two `oneOf` branches certainly overlap: they are equal, or their
+/// types overlap and one of them accepts every value of its types.
+fn ambiguous(a: &Value, b: &Value) -> bool {
+    if a == b {
+        return true;
+    }
+    let (Some(a), Some(b)) = (a.as_object(), b.as_object()) else {
+        return false;
+    };
+    let bare = |map: &Map<String, Value>| map.keys().all(|k| ANNOTATION_KEYS.contains(&k.as_str()));
+    if !bare(a) && !bare(b) {
+        return false;
+    }
+    match (types(a), types(b)) {
+        (Some(a), Some(b)) => a.iter().any(|x| {
+            b.iter().any(|y| {
+                x == y || matches!((*x, *y), ("integer", "number") | ("number", "integer"))
+            })
+        }),
+        _ => true,
+    }
+}
+
+/// Report top-level `$defs` and `definitions` entries that no `$ref`
+/// reaches from the root schema, directly or through other definitions.
+fn unreachable_defs(schema: &Value, out: &mut Vec<Finding>) {
+    let Some(root) = schema.as_object() else {
+        return;
+    };
+    let containers = ["$defs", "definitions"];
+    let root_id = root.get("$id").and_then(Value::as_str);
+
+    let mut refs = Vec::new();
+    for (key, value) in root {
+        if !containers.contains(&key.as_str()) {
+            collect_entry_refs(key, value, &mut refs);
+        }
+    }
+    let mut reached: HashSet<(&str, &str)> = HashSet::new();
+    while let Some(reference) = refs.pop() {
+        let Some((base, fragment)) = reference.split_once('#') else {
+            continue;
+        };
+        if !base.is_empty() && Some(base) != root_id {
+            continue;
+        }
+        for (container, name, def) in definitions(root) {
+            let by_pointer = fragment
+                .strip_prefix('/')
+                .and_then(|f| f.split_once('/').or(Some((f, ""))))
+                .is_some_and(|(c, rest)| {
+                    let target = rest.split('/').next().unwrap_or_default();
+                    c == container && target.replace("~1", "/").replace("~0", "~") == name
+                });
+            let by_anchor = def.get("$anchor").and_then(Value::as_str) == Some(fragment);
+            if (by_pointer || by_anchor) && reached.insert((container, name)) {
+                collect_refs(def, &mut refs);
+            }
+        }
+    }
+
+    for (container, name, _) in definitions(root) {
+        if !reached.contains(&(container, name)) {
+            let mut pointer = String::new();
+            push_segment(&mut pointer, container);
+            push_segment(&mut pointer, name);
+            out.push(finding(
+                "unreachable-def",
+                pointer,
+                format!("{name} is never referenced"),
+            ));
+        }
+    }
+}
+
+/// The entries of the root schema's `$defs` and `definitions`.
+fn definitions(root: &Map<String, Value>) -> impl Iterator<Item = (&str, &str, &Value)> {
+    ["$defs", "definitions"]
+        .into_iter()
+        .flat_map(move |container| {
+            root.get(container)
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(move |(name, def)| (container, name.as_str(), def))
+        })
+}
+
+fn collect_refs<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                collect_entry_refs(key, value, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The reference of a `$ref` or `$dynamicRef` entry, or the references
+/// inside the value of any other entry.
+fn collect_entry_refs<'a>(key: &str, value: &'a Value, out: &mut Vec<&'a str>) {
+    match value.as_str() {
+        Some(reference) if key == "$ref" || key == "$dynamicRef" => out.push(reference),
+        _ => collect_refs(value, out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lint_default(schema: &Value) -> Vec<Finding> {
+        lint(schema, &HashMap::new(), &[])
+    }
+
+    /// `(rule, pointer)` of each finding, sorted by pointer.
+    fn rules(schema: &Value) -> Vec<(&'static str, String)> {
+        let mut rules: Vec<_> = lint(schema, &HashMap::new(), &[])
+            .into_iter()
+            .map(|f| (f.rule, f.pointer))
+            .collect();
+        rules.sort_by(|a, b| a.1.cmp(&b.1));
+        rules
+    }
+
+    #[test]
+    fn reports_each_rule() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "mode": {
+                    "description": "How to run",
+                    "enum": ["fast", "slow", "fast"],
+                    "default": 1
+                },
+                "size": { "type": "integer", "default": 2.0 },
+                "value": {
+                    "description": "A value",
+                    "oneOf": [{ "type": "string" }, { "type": "string", "minLength": 3 }]
+                },
+                "options": {
+                    "description": "Options",
+                    "type": "object",
+                    "properties": { "name": { "$ref": "#/$defs/name" } }
+                }
+            },
+            "$defs": {
+                "name": { "type": "string", "minLength": 1 },
+                "unused": { "type": "string" }
+            }
+        });
+        assert_eq!(
+            rules(&schema),
+            [
+                ("unreachable-def", "/$defs/unused".to_string()),
+                ("duplicate-enum", "/properties/mode/enum".to_string()),
+                (
+                    "missing-additional-properties",
+                    "/properties/options".to_string()
+                ),
+                ("missing-description", "/properties/size".to_string()),
+                ("ambiguous-one-of", "/properties/value/oneOf".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_must_match_type() {
+        let findings = lint_default(&json!({ "type": ["string", "null"], "default": 3 }));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "default-type-mismatch");
+        assert_eq!(
+            findings[0].message,
+            "default 3 is not of type string or null"
+        );
+        assert!(lint_default(&json!({ "type": "number", "default": 3 })).is_empty());
+    }
+
+    #[test]
+    fn definitions_reached_through_other_definitions_and_anchors() {
+        let schema = json!({
+            "$id": "https://example.com/s.json",
+            "$ref": "https://example.com/s.json#/definitions/a",
+            "definitions": {
+                "a": { "$ref": "#/definitions/b~1c" },
+                "b/c": { "$ref": "#item" },
+                "d": { "$anchor": "item" },
+                "e": { "$ref": "#/definitions/e" }
+            }
+        });
+        assert_eq!(
+            rules(&schema),
+            [("unreachable-def", "/definitions/e".to_string())]
+        );
+    }
+
+    #[test]
+    fn oneof_branches_with_distinct_types_are_not_ambiguous() {
+        let schema = json!({
+            "oneOf": [
+                { "type": "string" },
+                { "type": "array" },
+                { "type": "object", "required": ["a"], "additionalProperties": false },
+                { "type": "object", "required": ["b"], "additionalProperties": false }
+            ]
+        });
+        assert!(lint_default(&schema).is_empty());
+        let findings =
+            lint_default(&json!({ "oneOf": [{ "type": "integer" }, { "type": "number" }] }));
+        assert_eq!(
+            findings[0].message,
+            "oneOf branches 0 and 1 can match the same instance"
+        );
+    }
+
+    #[test]
+    fn configured_severities_override_defaults() {
+        let schema = json!({
+            "properties": { "a": { "type": "string", "default": 1 } },
+            "additionalProperties": false
+        });
+        let levels = HashMap::from([
+            ("missing-description".to_string(), Severity::Off),
+            ("default-type-mismatch".to_string(), Severity::Warning),
+        ]);
+        let findings = lint(&schema, &levels, &[]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "default-type-mismatch");
+        assert_eq!(findings[0].severity, Severity::Warning);
+
+        let findings = lint_default(&schema);
+        assert_eq!(
+            findings
+                .iter()
+                .map(|f| (f.rule, f.severity))
+                .collect::<Vec<_>>(),
+            [
+                ("missing-description", Severity::Warning),
+                ("default-type-mismatch", Severity::Error)
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keywords_are_opt_in_and_respect_annotations() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "x-order": 1,
+            "properties": {
+                "port": {
+                    "description": "Port",
+                    "markdownDescription": "**Port**",
+                    "x-kubernetes-int-or-string": true
+                }
+            }
+        });
+        assert!(lint_default(&schema).is_empty());
+
+        let levels = HashMap::from([("unknown-keyword".to_string(), Severity::Warning)]);
+        let pointers = |annotations: &[&str]| -> Vec<String> {
+            let mut pointers: Vec<_> = lint(&schema, &levels, annotations)
+                .into_iter()
+                .map(|f| f.pointer)
+                .collect();
+            pointers.sort();
+            pointers
+        };
+        assert_eq!(
+            pointers(&[]),
+            [
+                "/properties/port/markdownDescription",
+                "/properties/port/x-kubernetes-int-or-string",
+                "/x-order"
+            ]
+        );
+        assert_eq!(
+            pointers(&["markdownDescription", "x-kubernetes-int-or-string"]),
+            ["/x-order"]
+        );
+    }
+
+    #[test]
+    fn disable_comments_silence_the_next_line() {
+        let content = "# lintel-disable-next-line\na: 1\n  // lintel-disable-next-line missing-description, unreachable-def -- legacy\nb: 2\n# lintel-disable-next-lines\nc: 3\n";
+        let suppressions = Suppressions::parse(content);
+        assert!(suppressions.silences(2, "duplicate-enum"));
+        assert!(suppressions.silences(4, "unreachable-def"));
+        assert!(!suppressions.silences(4, "duplicate-enum"));
+        assert!(!suppressions.silences(4, "legacy"));
+        assert!(!suppressions.silences(6, "duplicate-enum"));
+        assert!(!suppressions.silences(3, "duplicate-enum"));
+    }
+}
@@ -0,0 +1,308 @@
+//! Documents inside tar and zip archives.
+//!
+//! `lintel validate chart-1.2.3.tgz` unpacks the archive in memory and
+//! validates each member as if it were a file of its own. A member is named
+//! `<archive>!<path inside the archive>`, e.g.
+//! `chart-1.2.3.tgz!chart/templates/deployment.yaml`; schemas are resolved
+//! from the path inside the archive. Archives inside archives are unpacked
+//! too, up to [`MAX_DEPTH`] levels. Compressed archives are decompressed as
+//! they are read, and all members together, nested archives included, may
+//! take at most [`MAX_TOTAL_SIZE`] bytes, so a small archive cannot expand
+//! into gigabytes of memory.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Separates an archive's path from the path of a member inside it.
+pub const SEPARATOR: char = '!';
+
+/// How many archives deep nested archives are unpacked.
+const MAX_DEPTH: usize = 4;
+
+/// Members larger than this are not read.
+const MAX_MEMBER_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Bytes an archive may unpack to, across all its members and the archives
+/// nested in it.
+const MAX_TOTAL_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The kinds of archive that are unpacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl Kind {
+    fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let (stem, extension) = name.rsplit_once('.')?;
+        match extension {
+            "tgz" => Some(Self::TarGz),
+            "gz" if Path::new(stem).extension().is_some_and(|e| e == "tar") => Some(Self::TarGz),
+            "tar" => Some(Self::Tar),
+            "zip" => Some(Self::Zip),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `path` names a tar, gzipped tar or zip archive.
+pub fn is_archive(path: &Path) -> bool {
+    Kind::detect(path).is_some()
+}
+
+/// The path schemas are resolved from: the path inside the archive for an
+/// archive member, otherwise `path` itself.
+pub fn member_path(path: &str) -> &str {
+    path.rsplit_once(SEPARATOR)
+        .filter(|(archive, _)| is_archive(Path::new(archive)))
+        .map_or(path, |(_, member)| member)
+}
+
+/// Unpack the archive at `path`, whose content is `bytes`, into its text
+/// members, each paired with its `<archive>!<member>` path. Directories,
+/// links and members that are not UTF-8 are left out.
+///
+/// # Errors
+///
+/// Returns an error if the archive is corrupt, a member is larger than
+/// the size limit, or the members together are larger than the total limit.
+pub fn read_archive(path: &Path, bytes: &[u8]) -> io::Result<Vec<(PathBuf, String)>> {
+    read_archive_within(path, bytes, MAX_TOTAL_SIZE)
+}
+
+fn read_archive_within(
+    path: &Path,
+    bytes: &[u8],
+    total_size: u64,
+) -> io::Result<Vec<(PathBuf, String)>> {
+    let mut documents = Vec::new();
+    let mut budget = total_size;
+    unpack(
+        &path.display().to_string(),
+        bytes,
+        0,
+        total_size,
+        &mut budget,
+        &mut documents,
+    )?;
+    Ok(documents)
+}
+
+/// Unpack archive `name` into `documents`, taking the size of its members
+/// off `budget`, what is left of the total size `limit`.
+#[allow(clippy::too_many_arguments)]
+fn unpack(
+    name: &str,
+    bytes: &[u8],
+    depth: usize,
+    limit: u64,
+    budget: &mut u64,
+    documents: &mut Vec<(PathBuf, String)>,
+) -> io::Result<()> {
+    let Some(kind) = Kind::detect(Path::new(name)) else {
+        return Ok(());
+    };
+    let too_large = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{name} unpacks to more than {limit} bytes"),
+        )
+    };
+    // The decompressed stream holds the members plus their headers, so it
+    // cannot be smaller than what is left of the budget either.
+    let stream_limit = *budget;
+    let mut add = |member: &str, content: Vec<u8>| -> io::Result<()> {
+        *budget = budget
+            .checked_sub(content.len() as u64)
+            .ok_or_else(too_large)?;
+        let member_name = format!("{name}{SEPARATOR}{member}");
+        if is_archive(Path::new(member)) {
+            if depth + 1 < MAX_DEPTH {
+                unpack(&member_name, &content, depth + 1, limit, budget, documents)?;
+            }
+        } else if let Ok(text) = String::from_utf8(content) {
+            documents.push((PathBuf::from(member_name), text));
+        }
+        Ok(())
+    };
+    match kind {
+        Kind::Tar => unpack_tar(bytes, &mut add),
+        Kind::TarGz => unpack_tar(
+            Capped {
+                inner: flate2::read::GzDecoder::new(bytes),
+                remaining: stream_limit,
+                too_large,
+            },
+            &mut add,
+        ),
+        Kind::Zip => unpack_zip(bytes, &mut add),
+    }
+}
+
+/// A reader that fails with `too_large` once more than `remaining` bytes
+/// have been read from `inner`, to stop a decompression bomb early.
+struct Capped<R, E> {
+    inner: R,
+    remaining: u64,
+    too_large: E,
+}
+
+impl<R: Read, E: Fn() -> io::Error> Read for Capped<R, E> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.remaining = self
+            .remaining
+            .checked_sub(n as u64)
+            .ok_or_else(&self.too_large)?;
+        Ok(n)
+    }
+}
+
+fn unpack_tar(
+    reader: impl Read,
+    add: &mut impl FnMut(&str, Vec<u8>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let member = entry.path()?.to_string_lossy().into_owned();
+        let size = entry.size();
+        let content = read_member(&mut entry, size, &member)?;
+        add(&member, content)?;
+    }
+    Ok(())
+}
+
+fn unpack_zip(
+    bytes: &[u8],
+    add: &mut impl FnMut(&str, Vec<u8>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut archive = zip::ZipArchive::new(io::Cursor::new(bytes)).map_err(io::Error::other)?;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(io::Error::other)?;
+        if !file.is_file() {
+            continue;
+        }
+        let member = file.name().to_string();
+        let size = file.size();
+        let content = read_member(&mut file, size, &member)?;
+        add(&member, content)?;
+    }
+    Ok(())
+}
+
+/// Read a member of the `size` its header declares, refusing members over
+/// [`MAX_MEMBER_SIZE`] whatever their header says.
+fn read_member(reader: &mut impl Read, size: u64, member: &str) -> io::Result<Vec<u8>> {
+    let too_large = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{member} is larger than {MAX_MEMBER_SIZE} bytes"),
+        )
+    };
+    if size > MAX_MEMBER_SIZE {
+        return Err(too_large());
+    }
+    let mut content = Vec::new();
+    reader.take(MAX_MEMBER_SIZE + 1).read_to_end(&mut content)?;
+    if content.len() as u64 > MAX_MEMBER_SIZE {
+        return Err(too_large());
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tar(members: &[(&str, &[u8])]) -> anyhow::Result<Vec<u8>> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *content)?;
+        }
+        Ok(builder.into_inner()?)
+    }
+
+    fn gzip(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(bytes)?;
+        Ok(encoder.finish()?)
+    }
+
+    #[test]
+    fn unpacks_nested_archives_and_skips_binary_members() -> anyhow::Result<()> {
+        let inner = tar(&[("values.yaml", b"replicas: 2\n")])?;
+        let chart = gzip(&tar(&[
+            ("chart/Chart.yaml", b"name: chart\n"),
+            ("chart/logo.png", &[0xff, 0xfe, 0x00]),
+            ("chart/charts/dep.tar", &inner),
+        ])?)?;
+
+        let documents = read_archive(Path::new("chart-1.2.3.tgz"), &chart)?;
+        let names: Vec<String> = documents
+            .iter()
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "chart-1.2.3.tgz!chart/Chart.yaml",
+                "chart-1.2.3.tgz!chart/charts/dep.tar!values.yaml",
+            ]
+        );
+        assert_eq!(documents[1].1, "replicas: 2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn compressed_archives_stop_at_the_total_size() -> anyhow::Result<()> {
+        // Two megabytes of zeros compress to a few kilobytes.
+        let zeros = vec![0_u8; 2 * 1024 * 1024];
+        let bomb = gzip(&tar(&[("a.json", &zeros)])?)?;
+        assert!(bomb.len() < 64 * 1024);
+
+        let error = read_archive_within(Path::new("bomb.tgz"), &bomb, 1024 * 1024)
+            .expect_err("the archive unpacks past the limit");
+        assert!(
+            error
+                .to_string()
+                .contains("unpacks to more than 1048576 bytes"),
+            "{error}"
+        );
+
+        // The limit covers archives nested in the archive too.
+        let nested = tar(&[("inner.tgz", &bomb)])?;
+        let error = read_archive_within(Path::new("outer.tar"), &nested, 1024 * 1024)
+            .expect_err("the nested archive unpacks past the limit");
+        assert!(
+            error
+                .to_string()
+                .contains("unpacks to more than 1048576 bytes"),
+            "{error}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn member_path_is_the_path_inside_the_innermost_archive() {
+        assert_eq!(
+            member_path("chart.tgz!chart/templates/deployment.yaml"),
+            "chart/templates/deployment.yaml"
+        );
+        assert_eq!(member_path("a.zip!b.tar!c.json"), "c.json");
+        assert_eq!(member_path("config/wow!.json"), "config/wow!.json");
+        assert!(is_archive(Path::new("dist/Bundle.TAR.GZ")));
+        assert!(!is_archive(Path::new("archive.json")));
+    }
+}
@@ -0,0 +1,97 @@
+//! Picking the branch of a failed `oneOf` or `anyOf` to report.
+//!
+//! When no branch matches, every branch has its own errors and most of them
+//! are noise: a Kubernetes manifest with `kind: Service` fails the
+//! `Deployment` branch on `kind` before anything else. Branches are ranked by
+//! how well they fit the instance, and when one fits best its errors are
+//! reported instead of the bare "not valid under any of the schemas" error.
+
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+
+/// Index of the branch that fits the instance at `instance_path` best, or
+/// `None` when several fit equally well.
+///
+/// A branch fits worse when a `const` or `enum` on one of the instance's
+/// properties rejects it (a discriminator like `kind` or `type`), then when
+/// the instance has the wrong type for it, then the more errors it has.
+pub(crate) fn best_branch(instance_path: &str, branches: &[Vec<ValidationError>]) -> Option<usize> {
+    let ranks: Vec<_> = branches
+        .iter()
+        .map(|errors| rank(instance_path, errors))
+        .collect();
+    let best = ranks.iter().min()?;
+    let mut matching = ranks.iter().enumerate().filter(|(_, r)| *r == best);
+    let (index, _) = matching.next()?;
+    matching.next().is_none().then_some(index)
+}
+
+fn rank(instance_path: &str, errors: &[ValidationError]) -> (bool, bool, usize) {
+    let discriminated = errors.iter().any(|e| {
+        matches!(
+            e.kind,
+            ValidationErrorKind::Constant { .. } | ValidationErrorKind::Enum { .. }
+        ) && e
+            .instance_path
+            .strip_prefix(instance_path)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .is_some_and(|property| !property.contains('/'))
+    });
+    let wrong_type = errors.iter().any(|e| {
+        matches!(e.kind, ValidationErrorKind::Type { .. }) && e.instance_path == instance_path
+    });
+    (discriminated, wrong_type, errors.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(instance_path: &str, kind: ValidationErrorKind) -> ValidationError {
+        ValidationError {
+            instance_path: instance_path.to_string(),
+            schema_path: String::new(),
+            kind,
+            span: (0, 0),
+        }
+    }
+
+    fn required(instance_path: &str) -> ValidationError {
+        error(
+            instance_path,
+            ValidationErrorKind::Required {
+                property: "\"x\"".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn discriminator_beats_error_count() {
+        let kind = ValidationErrorKind::Constant {
+            expected_value: json!("Deployment"),
+        };
+        let branches = [
+            vec![error("/items/0/kind", kind)],
+            vec![required("/items/0"), required("/items/0")],
+        ];
+        assert_eq!(best_branch("/items/0", &branches), Some(1));
+    }
+
+    #[test]
+    fn wrong_type_then_fewest_errors() {
+        let object = ValidationErrorKind::Type {
+            expected: "object".to_string(),
+        };
+        let branches = [
+            vec![error("/a", object)],
+            vec![required("/a"), required("/a")],
+            vec![required("/a")],
+        ];
+        assert_eq!(best_branch("/a", &branches), Some(2));
+        assert_eq!(best_branch("/a", &branches[1..]), Some(1));
+        assert_eq!(
+            best_branch("/a", &[vec![required("/a")], vec![required("/a")]]),
+            None
+        );
+    }
+}
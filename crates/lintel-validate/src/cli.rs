@@ -0,0 +1,368 @@
+//! The `ValidateArgs` CLI struct and the `run` function shared by the
+//! check, ci and validate commands.
+
+use core::time::Duration;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use bpaf::{Bpaf, ShellComp};
+use lintel_diagnostics::reporter::{
+    CheckResult, CheckedFile, ExitStatus, Reporter, format_badge, format_summary,
+    format_unknown_files, run_report,
+};
+
+use lintel_cli_common::CliCacheOptions;
+
+use crate::{formats, lock, validate};
+
+// -----------------------------------------------------------------------
+// ValidateArgs — shared CLI struct
+// -----------------------------------------------------------------------
+
+#[derive(Debug, Clone, Bpaf)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ValidateArgs {
+    #[bpaf(long("exclude"), argument("PATTERN"))]
+    pub exclude: Vec<String>,
+
+    #[bpaf(external(lintel_cli_common::cli_cache_options))]
+    pub cache: CliCacheOptions,
+
+    /// Print every schema source considered for each file to stderr
+    #[bpaf(long("trace-resolution"), switch)]
+    pub trace_resolution: bool,
+
+    /// Fail files whose remote schema differs from lintel.lock
+    #[bpaf(long("frozen"), switch)]
+    pub frozen: bool,
+
+    /// Create or update lintel.lock with the remote schemas used
+    #[bpaf(long("update-lock"), switch)]
+    pub update_lock: bool,
+
+    /// Also validate examples and defaults in OpenAPI documents against
+    /// their schemas
+    #[bpaf(long("openapi-examples"), switch)]
+    pub openapi_examples: bool,
+
+    /// Decode base64 `contentEncoding` values and check embedded JSON
+    /// against `contentMediaType` and `contentSchema`
+    #[bpaf(long("validate-content"), switch)]
+    pub validate_content: bool,
+
+    /// Warn about properties the schema allows only because it omits
+    /// `additionalProperties`, such as misspelled optional keys
+    #[bpaf(long("strict-instances"), switch)]
+    pub strict_instances: bool,
+
+    /// Report `lintel-ignore` comments that silence no errors
+    #[bpaf(long("report-unused-suppressions"), switch)]
+    pub report_unused_suppressions: bool,
+
+    /// Exit with status 1 when there are warnings
+    #[bpaf(long("error-on-warnings"), switch)]
+    pub error_on_warnings: bool,
+
+    /// Stop validating after the first file that fails
+    #[bpaf(long("fail-fast"), switch)]
+    pub fail_fast: bool,
+
+    /// Report at most N diagnostics per file, followed by a count of the rest
+    #[bpaf(long("max-errors"), argument("N"))]
+    pub max_errors: Option<usize>,
+
+    /// Give up on validating a file after this long (e.g. "5s") and report
+    /// a timeout for it instead of holding up the run
+    #[bpaf(
+        long("timeout-per-file"),
+        argument::<String>("DURATION"),
+        parse(lintel_cli_common::parse_duration),
+        optional
+    )]
+    pub timeout_per_file: Option<Duration>,
+
+    /// Validate the document on stdin instead of files
+    #[bpaf(long("stdin"), switch)]
+    pub stdin: bool,
+
+    /// Path of the document on stdin, used to pick its schema, format and
+    /// `lintel.toml`
+    #[bpaf(
+        long("stdin-filepath"),
+        argument("PATH"),
+        complete_shell(ShellComp::File { mask: None })
+    )]
+    pub stdin_filepath: Option<String>,
+
+    /// Merge the given Docker Compose files in order, like repeated
+    /// `docker compose -f`, and validate the merged configuration
+    #[bpaf(long("compose-merge"), switch)]
+    pub compose_merge: bool,
+
+    /// Only validate files changed since this git revision, including
+    /// uncommitted and untracked ones
+    #[bpaf(long("since"), argument("REV"))]
+    pub since: Option<String>,
+
+    /// Which changes `--since` selects, as for `git diff --diff-filter`
+    /// (e.g. `AM`)
+    #[bpaf(long("diff-filter"), argument("FILTER"))]
+    pub diff_filter: Option<String>,
+
+    /// Print time per phase, counts by schema and by error keyword, and the
+    /// slowest files to stderr after the run
+    #[bpaf(long("summary"), switch)]
+    pub summary: bool,
+
+    /// Print the files no schema was found for, grouped by extension, to
+    /// stderr after the run
+    #[bpaf(long("unknown-files"), switch)]
+    pub unknown_files: bool,
+
+    /// Write counts and timings of the run as JSON to PATH, for dashboards
+    #[bpaf(
+        long("report-file"),
+        argument("PATH"),
+        complete_shell(ShellComp::File { mask: None })
+    )]
+    pub report_file: Option<PathBuf>,
+
+    /// Write a shields.io-style SVG badge with the result of the run to PATH
+    #[bpaf(
+        long("badge"),
+        argument("PATH"),
+        complete_shell(ShellComp::File { mask: None })
+    )]
+    pub badge: Option<PathBuf>,
+
+    #[bpaf(positional("PATH"), complete_shell(ShellComp::File { mask: None }))]
+    pub globs: Vec<String>,
+}
+
+impl From<&ValidateArgs> for validate::ValidateArgs {
+    fn from(args: &ValidateArgs) -> Self {
+        // When a single directory is passed as an arg, use it as the config
+        // search directory so that `lintel.toml` inside that directory is found.
+        // A document on stdin finds the config of the directory it claims to be in.
+        let config_dir = if args.stdin {
+            args.stdin_filepath
+                .as_deref()
+                .and_then(|path| std::path::Path::new(path).parent())
+                .filter(|dir| dir.is_dir())
+                .map(std::path::Path::to_path_buf)
+        } else {
+            args.globs
+                .iter()
+                .find(|g| std::path::Path::new(g).is_dir())
+                .map(std::path::PathBuf::from)
+        };
+
+        validate::ValidateArgs {
+            globs: args.globs.clone(),
+            exclude: args.exclude.clone(),
+            cache_dir: args.cache.cache_dir.clone(),
+            force_schema_fetch: args.cache.force_schema_fetch || args.cache.force,
+            force_validation: args.cache.force_validation || args.cache.force,
+            no_catalog: args.cache.no_catalog,
+            config_dir,
+            schema_cache_ttl: args.cache.schema_cache_ttl,
+            fetch_timeout: args.cache.fetch_timeout,
+            fetch_retries: args.cache.fetch_retries,
+            fetch_retry_backoff: args.cache.fetch_retry_backoff,
+            trace_resolution: args.trace_resolution,
+            lock: if args.frozen {
+                lock::LockMode::Frozen
+            } else if args.update_lock {
+                lock::LockMode::Update
+            } else {
+                lock::LockMode::Auto
+            },
+            openapi_examples: args.openapi_examples,
+            validate_content: args.validate_content,
+            strict_instances: args.strict_instances,
+            report_unused_suppressions: args.report_unused_suppressions,
+            fail_fast: args.fail_fast,
+            max_errors: args.max_errors,
+            timeout_per_file: args.timeout_per_file,
+            formats: formats::FormatRegistry::default(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+/// Load `lintel.toml` and merge its excludes into the args.
+///
+/// Config excludes are prepended so they have the same priority as CLI excludes.
+/// When a directory arg is passed (e.g. `lintel check some/dir`), we search
+/// for `lintel.toml` starting from that directory rather than cwd.
+pub fn merge_config(args: &mut ValidateArgs) {
+    let search_dir = args
+        .globs
+        .iter()
+        .find(|g| std::path::Path::new(g).is_dir())
+        .map(std::path::PathBuf::from);
+
+    let cfg_result = match &search_dir {
+        Some(dir) => lintel_config::find_and_load(dir).map(Option::unwrap_or_default),
+        None => lintel_config::load(),
+    };
+
+    match cfg_result {
+        Ok(cfg) => {
+            // Config excludes first, then CLI excludes.
+            let cli_excludes = core::mem::take(&mut args.exclude);
+            args.exclude = cfg.exclude;
+            args.exclude.extend(cli_excludes);
+        }
+        Err(e) => {
+            eprintln!("warning: failed to load lintel.toml: {e}");
+        }
+    }
+}
+
+/// Name of the document read with `--stdin` when `--stdin-filepath` is not
+/// given.
+pub const STDIN_PATH: &str = "<stdin>";
+
+/// With `--stdin`, read the document on stdin and pair it with the path it
+/// is validated as.
+///
+/// # Errors
+///
+/// Returns an error if stdin cannot be read.
+pub fn read_stdin(args: &ValidateArgs) -> Result<Option<(PathBuf, String)>> {
+    if !args.stdin {
+        return Ok(None);
+    }
+    let mut content = String::new();
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .context("failed to read stdin")?;
+    let path = args.stdin_filepath.as_deref().unwrap_or(STDIN_PATH);
+    Ok(Some((PathBuf::from(path), content)))
+}
+
+/// With `--compose-merge`, merge the Compose files given as paths into the
+/// one document to validate, named after the first file.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or parsed, or its `extends`
+/// cannot be resolved.
+pub fn read_compose_merge(args: &ValidateArgs) -> Result<Option<(PathBuf, String)>> {
+    if !args.compose_merge {
+        return Ok(None);
+    }
+    let files: Vec<PathBuf> = args.globs.iter().map(PathBuf::from).collect();
+    crate::compose::merge_files(&files).map(Some)
+}
+
+/// With `--since`, list the files to validate from git instead of walking
+/// the paths.
+///
+/// # Errors
+///
+/// Returns an error if git fails, or if `--diff-filter` is given without
+/// `--since`.
+pub fn changed_files(
+    args: &ValidateArgs,
+    lib_args: &validate::ValidateArgs,
+) -> Result<Option<Vec<PathBuf>>> {
+    let Some(since) = &args.since else {
+        if args.diff_filter.is_some() {
+            anyhow::bail!("--diff-filter requires --since");
+        }
+        return Ok(None);
+    };
+    validate::collect_changed_files(
+        &lib_args.globs,
+        &lib_args.exclude,
+        since,
+        args.diff_filter.as_deref(),
+    )
+    .map(Some)
+}
+
+/// The files `--report-file` and `--badge` ask for, with their contents.
+pub fn run_outputs(
+    args: &ValidateArgs,
+    result: &CheckResult,
+    elapsed: Duration,
+    status: ExitStatus,
+) -> Vec<(PathBuf, String)> {
+    let mut outputs = Vec::new();
+    if let Some(path) = &args.report_file {
+        let report = run_report(result, elapsed, status);
+        outputs.push((path.clone(), format!("{report:#}\n")));
+    }
+    if let Some(path) = &args.badge {
+        outputs.push((path.clone(), format_badge(result, status)));
+    }
+    outputs
+}
+
+/// Write the files of [`run_outputs`].
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be written.
+pub fn write_outputs(outputs: Vec<(PathBuf, String)>) -> Result<()> {
+    for (path, contents) in outputs {
+        std::fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------
+// Run function — shared between check/ci/validate commands
+// -----------------------------------------------------------------------
+
+/// Run validation and report results via the given reporter.
+///
+/// Returns the [`ExitStatus`] for the worst diagnostic.
+///
+/// # Errors
+///
+/// Returns an error if file collection or schema validation encounters an I/O error.
+pub async fn run(args: &mut ValidateArgs, reporter: &mut dyn Reporter) -> Result<ExitStatus> {
+    merge_config(args);
+
+    let lib_args = validate::ValidateArgs::from(&*args);
+    let start = Instant::now();
+    let on_check = |file: &CheckedFile| reporter.on_file_checked(file);
+    let document = match read_stdin(args)? {
+        Some(document) => Some(document),
+        None => read_compose_merge(args)?,
+    };
+    let result: CheckResult = match document {
+        Some(document) => {
+            validate::run_with_contents(&lib_args, vec![document], None, on_check).await?
+        }
+        None => match changed_files(args, &lib_args)? {
+            Some(files) => validate::run_with_files(&lib_args, None, files, on_check).await?,
+            None => validate::run_with(&lib_args, None, on_check).await?,
+        },
+    };
+    let status = result.exit_status(args.error_on_warnings);
+    let elapsed = start.elapsed();
+    let summary = args.summary.then(|| format_summary(&result));
+    let unknown_files = args.unknown_files.then(|| format_unknown_files(&result));
+    let outputs = run_outputs(args, &result, elapsed, status);
+
+    reporter.report(result, elapsed);
+    if let Some(summary) = summary {
+        eprint!("{summary}");
+    }
+    if let Some(unknown_files) = unknown_files {
+        eprint!("{unknown_files}");
+    }
+    write_outputs(outputs)?;
+
+    Ok(status)
+}
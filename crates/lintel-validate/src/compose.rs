@@ -0,0 +1,239 @@
+//! `--compose-merge`: validate the effective Docker Compose configuration.
+//!
+//! `docker compose -f compose.yml -f compose.override.yml` merges its files
+//! before using them, so a fragment such as an override file is often not a
+//! valid configuration on its own, and an invalid combination can hide in
+//! two valid files. [`merge_files`] builds the document Compose would run:
+//! each file's `extends` are resolved, then the files are merged in order
+//! following the Compose merge rules.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+/// Sequences an override file replaces rather than extends.
+const REPLACED_SEQUENCES: &[&str] = &["command", "entrypoint", "test"];
+
+/// Mappings that may also be written as a list of `KEY=VALUE` strings.
+const KEY_VALUE_LISTS: &[&str] = &[
+    "annotations",
+    "args",
+    "environment",
+    "extra_hosts",
+    "labels",
+    "sysctls",
+];
+
+/// How many `extends` a service may chain through.
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Merge the Compose files at `paths` in order, like repeated `-f` options,
+/// into a YAML document paired with the first file's path.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or parsed, or a service
+/// `extends` a service that does not exist or extends itself.
+pub fn merge_files(paths: &[PathBuf]) -> Result<(PathBuf, String)> {
+    let Some(first) = paths.first() else {
+        anyhow::bail!("--compose-merge needs at least one compose file");
+    };
+    let mut merged = Value::Object(Map::new());
+    for path in paths {
+        let mut document = load(path)?;
+        resolve_extends(&mut document, path)?;
+        merge(&mut merged, document, "");
+    }
+    let yaml = serde_yaml::to_string(&merged).context("failed to serialize merged compose file")?;
+    Ok((first.clone(), yaml))
+}
+
+fn load(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let value: Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(if value.is_null() {
+        Value::Object(Map::new())
+    } else {
+        value
+    })
+}
+
+/// Replace each service's `extends` with the service it extends, merged
+/// with the service's own settings.
+fn resolve_extends(document: &mut Value, path: &Path) -> Result<()> {
+    let Some(services) = document.get("services").and_then(Value::as_object) else {
+        return Ok(());
+    };
+    let mut resolved = Map::new();
+    for (name, service) in services {
+        resolved.insert(name.clone(), extended(services, service, path, 0)?);
+    }
+    document["services"] = Value::Object(resolved);
+    Ok(())
+}
+
+/// `service` with its `extends` chain merged in.
+fn extended(
+    services: &Map<String, Value>,
+    service: &Value,
+    path: &Path,
+    depth: usize,
+) -> Result<Value> {
+    let Some(extends) = service.get("extends") else {
+        return Ok(service.clone());
+    };
+    if depth >= MAX_EXTENDS_DEPTH {
+        anyhow::bail!(
+            "{}: `extends` chain is longer than {MAX_EXTENDS_DEPTH} services or circular",
+            path.display()
+        );
+    }
+    let (base_name, file) = match extends {
+        Value::String(name) => (name.as_str(), None),
+        Value::Object(spec) => (
+            spec.get("service")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+            spec.get("file").and_then(Value::as_str),
+        ),
+        _ => anyhow::bail!("{}: `extends` must be a string or mapping", path.display()),
+    };
+
+    let mut base = if let Some(file) = file {
+        let base_path = path.parent().unwrap_or(Path::new(".")).join(file);
+        let document = load(&base_path)?;
+        let base_services = document
+            .get("services")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let base = base_services.get(base_name).with_context(|| {
+            format!(
+                "{}: no service `{base_name}` to extend",
+                base_path.display()
+            )
+        })?;
+        extended(&base_services, base, &base_path, depth + 1)?
+    } else {
+        let base = services
+            .get(base_name)
+            .with_context(|| format!("{}: no service `{base_name}` to extend", path.display()))?;
+        extended(services, base, path, depth + 1)?
+    };
+
+    let mut own = service.clone();
+    if let Some(own) = own.as_object_mut() {
+        own.remove("extends");
+    }
+    merge(&mut base, own, "");
+    Ok(base)
+}
+
+/// Merge `over` into `base` following the Compose merge rules: mappings
+/// merge key by key, `KEY=VALUE` lists merge as mappings, most sequences
+/// are appended to, and everything else is replaced. `key` is the key
+/// `base` is stored under.
+fn merge(base: &mut Value, over: Value, key: &str) {
+    if KEY_VALUE_LISTS.contains(&key) && (base.is_array() || over.is_array()) {
+        *base = Value::Object(key_value_map(core::mem::take(base)));
+        merge(base, Value::Object(key_value_map(over)), "");
+        return;
+    }
+    match (base, over) {
+        (Value::Object(base), Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value, &key),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(over)) if !REPLACED_SEQUENCES.contains(&key) => {
+            for item in over {
+                if !base.contains(&item) {
+                    base.push(item);
+                }
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
+/// A `KEY=VALUE` list, or a mapping, as a mapping. A bare `KEY` maps to null.
+fn key_value_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        Value::Array(items) => items
+            .into_iter()
+            .filter_map(|item| {
+                let item = item.as_str()?.to_string();
+                Some(match item.split_once('=') {
+                    Some((key, value)) => (key.to_string(), Value::String(value.to_string())),
+                    None => (item, Value::Null),
+                })
+            })
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn merges_overrides_and_resolves_extends() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let base = tmp.path().join("compose.yml");
+        let over = tmp.path().join("compose.override.yml");
+        fs::write(
+            tmp.path().join("common.yml"),
+            "services:\n  app:\n    image: app:1\n    ports: ['80:80']\n",
+        )?;
+        fs::write(
+            &base,
+            "services:\n  web:\n    extends: {file: common.yml, service: app}\n    \
+             command: [serve]\n    environment: [MODE=dev, DEBUG]\n  worker:\n    \
+             extends: web\n",
+        )?;
+        fs::write(
+            &over,
+            "services:\n  web:\n    command: [serve, --prod]\n    ports: ['443:443']\n    \
+             environment: {MODE: prod}\n",
+        )?;
+
+        let (path, yaml) = merge_files(&[base.clone(), over])?;
+        assert_eq!(path, base);
+        let merged: Value = serde_yaml::from_str(&yaml)?;
+        assert_eq!(
+            merged["services"]["web"],
+            serde_json::json!({
+                "image": "app:1",
+                "command": ["serve", "--prod"],
+                "ports": ["80:80", "443:443"],
+                "environment": {"MODE": "prod", "DEBUG": null},
+            })
+        );
+        assert_eq!(merged["services"]["worker"]["image"], "app:1");
+        assert!(merged["services"]["worker"].get("extends").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn circular_extends_is_an_error() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("compose.yml");
+        fs::write(
+            &path,
+            "services:\n  a:\n    extends: b\n  b:\n    extends: a\n",
+        )?;
+        assert!(merge_files(&[path]).is_err());
+        Ok(())
+    }
+}
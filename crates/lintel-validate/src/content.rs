@@ -0,0 +1,242 @@
+//! Validation of embedded documents, enabled with `--validate-content`.
+//!
+//! String values whose schema has `contentEncoding: base64` must decode, and
+//! with `contentMediaType: application/json` (or any `+json` type) the
+//! decoded text must parse as JSON and match `contentSchema`, if present.
+//! Other encodings and media types are not checked.
+//!
+//! The `jsonschema` crate only checks these keywords for drafts 6 and 7 and
+//! ignores `contentSchema`, so they are replaced by a custom keyword.
+
+use base64::Engine;
+use jsonschema::{Keyword, ValidationError};
+use serde_json::{Map, Value};
+
+/// Install the content keywords on `options`. `root` is the schema being
+/// compiled; its `$defs` and `definitions` are available to `contentSchema`.
+pub(crate) fn apply<R>(
+    options: jsonschema::ValidationOptions<R>,
+    root: &Value,
+) -> jsonschema::ValidationOptions<R> {
+    let defs: Map<String, Value> = ["$schema", "$defs", "definitions"]
+        .into_iter()
+        .filter_map(|key| Some((key.to_string(), root.get(key)?.clone())))
+        .collect();
+    let for_media_type = defs.clone();
+    options
+        .with_keyword("contentEncoding", move |parent, _, _| {
+            Ok(Box::new(Content::new(parent, &defs)))
+        })
+        .with_keyword("contentMediaType", move |parent, _, _| {
+            // With an encoding, the `contentEncoding` keyword checks both
+            if parent.contains_key("contentEncoding") {
+                return Ok(Box::new(Content::default()));
+            }
+            Ok(Box::new(Content::new(parent, &for_media_type)))
+        })
+}
+
+#[derive(Default)]
+struct Content {
+    base64: bool,
+    json: bool,
+    schema: Option<jsonschema::Validator>,
+}
+
+impl Content {
+    fn new(parent: &Map<String, Value>, defs: &Map<String, Value>) -> Self {
+        let encoding = parent.get("contentEncoding").and_then(Value::as_str);
+        if encoding.is_some_and(|e| !e.eq_ignore_ascii_case("base64")) {
+            return Self::default();
+        }
+        let json = parent
+            .get("contentMediaType")
+            .and_then(Value::as_str)
+            .is_some_and(is_json_media_type);
+        let schema = if json {
+            parent
+                .get("contentSchema")
+                .and_then(|schema| compile_content_schema(schema, defs))
+        } else {
+            None
+        };
+        Self {
+            base64: encoding.is_some(),
+            json,
+            schema,
+        }
+    }
+
+    fn check(&self, value: &str) -> Result<(), String> {
+        let decoded;
+        let text = if self.base64 {
+            let compact: String = value.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(compact)
+                .map_err(|e| format!("value is not valid base64: {e}"))?;
+            decoded = String::from_utf8(bytes);
+            match &decoded {
+                Ok(text) => text.as_str(),
+                // Binary content is fine unless it must be JSON
+                Err(_) if !self.json => return Ok(()),
+                Err(_) => return Err("decoded value is not UTF-8 text".to_string()),
+            }
+        } else {
+            value
+        };
+        if !self.json {
+            return Ok(());
+        }
+        let document: Value = serde_json::from_str(text)
+            .map_err(|e| format!("embedded document is not valid JSON: {e}"))?;
+        let Some(schema) = &self.schema else {
+            return Ok(());
+        };
+        let errors: Vec<String> = schema
+            .iter_errors(&document)
+            .map(|error| {
+                let path = error.instance_path().to_string();
+                let at = if path.is_empty() { "/" } else { &path };
+                format!("{at}: {error}")
+            })
+            .collect();
+        match errors.as_slice() {
+            [] => Ok(()),
+            [error] => Err(format!(
+                "embedded document does not match contentSchema: {error}"
+            )),
+            [error, rest @ ..] => Err(format!(
+                "embedded document does not match contentSchema: {error} (and {} more)",
+                rest.len()
+            )),
+        }
+    }
+}
+
+impl Keyword for Content {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        match instance {
+            Value::String(s) => self.check(s).map_err(ValidationError::custom),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        instance.as_str().is_none_or(|s| self.check(s).is_ok())
+    }
+}
+
+/// `application/json`, or a structured syntax suffix like
+/// `application/vnd.api+json`, ignoring parameters.
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case("application/json")
+        || essence.to_ascii_lowercase().ends_with("+json")
+}
+
+/// Compile `contentSchema` on its own, with the root schema's definitions.
+/// References to other documents accept anything.
+fn compile_content_schema(
+    schema: &Value,
+    defs: &Map<String, Value>,
+) -> Option<jsonschema::Validator> {
+    let mut document = defs.clone();
+    match schema {
+        Value::Object(map) => document.extend(map.clone()),
+        Value::Bool(false) => {
+            document.insert("not".to_string(), Value::Object(Map::new()));
+        }
+        _ => {}
+    }
+    match jsonschema::options()
+        .with_retriever(PermissiveRetriever)
+        .build(&Value::Object(document))
+    {
+        Ok(validator) => Some(validator),
+        Err(e) => {
+            eprintln!("warning: contentSchema is not checked: {e}");
+            None
+        }
+    }
+}
+
+/// Resolves every external `$ref` in `contentSchema` to `true`, so no
+/// network requests are made while a schema is compiled.
+struct PermissiveRetriever;
+
+impl jsonschema::Retrieve for PermissiveRetriever {
+    fn retrieve(
+        &self,
+        _uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn core::error::Error + Send + Sync>> {
+        Ok(Value::Bool(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validator(schema: &Value) -> anyhow::Result<jsonschema::Validator> {
+        Ok(apply(jsonschema::options(), schema).build(schema)?)
+    }
+
+    #[test]
+    fn base64_json_is_decoded_and_checked() -> anyhow::Result<()> {
+        let schema = json!({
+            "properties": {
+                "blob": {
+                    "type": "string",
+                    "contentEncoding": "base64",
+                    "contentMediaType": "application/json",
+                    "contentSchema": {"$ref": "#/$defs/payload"}
+                }
+            },
+            "$defs": {
+                "payload": {"type": "object", "required": ["name"]}
+            }
+        });
+        let v = validator(&schema)?;
+        let encode = |s: &str| base64::engine::general_purpose::STANDARD.encode(s);
+
+        assert!(v.is_valid(&json!({"blob": encode(r#"{"name": "x"}"#)})));
+        let messages = |blob: String| -> Vec<String> {
+            v.iter_errors(&json!({ "blob": blob }))
+                .map(|e| e.to_string())
+                .collect()
+        };
+        assert_eq!(
+            messages("not base64!".to_string()),
+            ["value is not valid base64: Invalid symbol 33, offset 9."]
+        );
+        assert_eq!(
+            messages(encode("{oops")),
+            ["embedded document is not valid JSON: key must be a string at line 1 column 2"]
+        );
+        assert_eq!(
+            messages(encode("{}")),
+            [r#"embedded document does not match contentSchema: /: "name" is a required property"#]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_strings_without_encoding() -> anyhow::Result<()> {
+        let v = validator(&json!({"contentMediaType": "application/vnd.api+json; charset=utf-8"}))?;
+        assert!(v.is_valid(&json!("[1, 2]")));
+        assert!(!v.is_valid(&json!("[1, 2")));
+        assert!(v.is_valid(&json!(42)));
+        Ok(())
+    }
+
+    #[test]
+    fn other_encodings_and_media_types_are_not_checked() -> anyhow::Result<()> {
+        let v = validator(&json!({"contentEncoding": "base32"}))?;
+        assert!(v.is_valid(&json!("not base32")));
+        let v = validator(&json!({"contentEncoding": "base64", "contentMediaType": "image/png"}))?;
+        assert!(v.is_valid(&json!("iVBORw0KGgo=")));
+        assert!(!v.is_valid(&json!("iVBORw0KGgo")));
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Warnings for instance values whose schema is marked `deprecated: true`.
+//!
+//! Deprecations are found in the annotations of a validation run, so a
+//! deprecated schema reached through `$ref`, `allOf` or a matching `oneOf`
+//! branch is reported, while one in a branch that does not apply is not.
+
+use std::collections::HashSet;
+
+use lintel_diagnostics::find_instance_path_span;
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+use serde_json::Value;
+
+/// Whether `schema` marks any subschema `deprecated: true`. Instances of
+/// schemas that do not are not evaluated for deprecations.
+pub(crate) fn marks_deprecated(schema: &Value) -> bool {
+    match schema {
+        Value::Object(map) => {
+            map.get("deprecated") == Some(&Value::Bool(true)) || map.values().any(marks_deprecated)
+        }
+        Value::Array(items) => items.iter().any(marks_deprecated),
+        _ => false,
+    }
+}
+
+/// One warning per value of the instance that a deprecated schema applies
+/// to, with that schema's `description`. `instance_prefix` and `content` are
+/// used like in [`convert_error`](crate::document::convert_error).
+pub(crate) fn deprecated_properties(
+    evaluation: &jsonschema::Evaluation,
+    content: &str,
+    instance_prefix: &str,
+) -> Vec<ValidationError> {
+    let mut seen = HashSet::new();
+    let mut warnings = Vec::new();
+    for entry in evaluation.iter_annotations() {
+        let annotations = entry.annotations.value();
+        let location = entry.instance_location.as_str();
+        if annotations.get("deprecated") != Some(&Value::Bool(true))
+            || location.is_empty()
+            || !seen.insert(location)
+        {
+            continue;
+        }
+        let property = location
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .replace("~1", "/")
+            .replace("~0", "~");
+        let description = annotations
+            .get("description")
+            .and_then(Value::as_str)
+            .map(|d| d.trim().to_string());
+        let instance_path = format!("{instance_prefix}{location}");
+        warnings.push(ValidationError {
+            span: find_instance_path_span(content, &instance_path),
+            instance_path,
+            schema_path: format!(
+                "{}/deprecated",
+                crate::document::keyword_pointer(entry.schema_location)
+            ),
+            kind: ValidationErrorKind::Deprecated {
+                property,
+                description,
+            },
+        });
+    }
+    warnings.sort_by(|a, b| a.instance_path.cmp(&b.instance_path));
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_values_of_deprecated_schemas() -> anyhow::Result<()> {
+        let schema = json!({
+            "properties": {
+                "name": {"type": "string"},
+                "old": {"deprecated": true, "description": "Use `new` instead."},
+                "legacy": {"$ref": "#/$defs/legacy"},
+                "a/b": {"deprecated": true}
+            },
+            "$defs": {"legacy": {"deprecated": true}}
+        });
+        assert!(marks_deprecated(&schema));
+        assert!(!marks_deprecated(
+            &json!({"properties": {"deprecated": {}}})
+        ));
+
+        let validator = jsonschema::validator_for(&schema)?;
+        let instance = json!({"name": "x", "old": 1, "legacy": 2, "a/b": 3});
+        let evaluation = validator.evaluate(&instance);
+        let warnings: Vec<_> = deprecated_properties(&evaluation, "", "/spec")
+            .into_iter()
+            .map(|w| (w.instance_path, w.schema_path, w.kind.message()))
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                (
+                    "/spec/a~1b".to_string(),
+                    "/properties/a~1b/deprecated".to_string(),
+                    "'a/b' is deprecated".to_string()
+                ),
+                (
+                    "/spec/legacy".to_string(),
+                    "/$defs/legacy/deprecated".to_string(),
+                    "'legacy' is deprecated".to_string()
+                ),
+                (
+                    "/spec/old".to_string(),
+                    "/properties/old/deprecated".to_string(),
+                    "'old' is deprecated: Use `new` instead.".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! What a schema says about a validation error: the `description` of the
+//! failing schema node, shown as a help note, and the custom message its
+//! `errorMessage` keyword gives.
+
+use serde_json::Value;
+
+use crate::suggest::{navigate_schema_path, resolve_ref};
+
+/// Find the `description` of the schema node a validation error came from.
+///
+/// `schema_path` ends with the failing keyword (e.g. `/properties/port/type`),
+/// so the description is read from its parent. For `required` errors, the
+/// missing property's own description is preferred when the schema has one.
+pub(crate) fn schema_description(
+    schema: &Value,
+    schema_path: &str,
+    required_property: Option<&str>,
+) -> Option<String> {
+    let parent_path = schema_path
+        .rsplit_once('/')
+        .map_or("", |(parent, _)| parent);
+    let parent = resolve_ref(navigate_schema_path(schema, parent_path)?, schema);
+
+    let property_description = required_property.and_then(|prop| {
+        let prop_schema = parent.get("properties")?.get(prop)?;
+        resolve_ref(prop_schema, schema).get("description")
+    });
+
+    property_description
+        .or_else(|| parent.get("description"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(String::from)
+}
+
+/// The custom message a schema gives for a validation error with its
+/// `errorMessage` keyword, next to the failing keyword.
+///
+/// Like ajv-errors, `errorMessage` is either a string used for every error of
+/// its schema, or an object keyed by keyword. For `required`, the value may
+/// also be an object keyed by the missing property.
+pub(crate) fn schema_error_message(
+    schema: &Value,
+    schema_path: &str,
+    required_property: Option<&str>,
+) -> Option<String> {
+    let (parent_path, keyword) = schema_path.rsplit_once('/').unwrap_or(("", schema_path));
+    let parent = resolve_ref(navigate_schema_path(schema, parent_path)?, schema);
+    let message = match parent.get("errorMessage")? {
+        Value::Object(by_keyword) => match by_keyword.get(keyword)? {
+            Value::Object(by_property) => by_property.get(required_property?.trim_matches('"'))?,
+            message => message,
+        },
+        message => message,
+    };
+    message.as_str().map(String::from)
+}
+
+/// Fill a custom message template: `{message}` becomes the message lintel
+/// would print and `{instance_path}` the JSON pointer of the failing value.
+pub(crate) fn fill_message_template(template: &str, message: &str, instance_path: &str) -> String {
+    template
+        .replace("{message}", message)
+        .replace("{instance_path}", instance_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // --- schema_description ---
+
+    #[test]
+    fn description_of_failing_property() {
+        let schema = json!({
+            "properties": {
+                "port": { "type": "integer", "description": "Port to listen on" }
+            }
+        });
+        assert_eq!(
+            schema_description(&schema, "/properties/port/type", None).as_deref(),
+            Some("Port to listen on")
+        );
+    }
+
+    #[test]
+    fn description_follows_ref_segments() {
+        let schema = json!({
+            "properties": { "server": { "$ref": "#/$defs/server" } },
+            "$defs": {
+                "server": {
+                    "description": "Server settings",
+                    "required": ["host"]
+                }
+            }
+        });
+        assert_eq!(
+            schema_description(&schema, "/properties/server/$ref/required", None).as_deref(),
+            Some("Server settings")
+        );
+    }
+
+    #[test]
+    fn description_prefers_missing_required_property() {
+        let schema = json!({
+            "description": "Root",
+            "properties": { "name": { "description": "Package name" } },
+            "required": ["name"]
+        });
+        assert_eq!(
+            schema_description(&schema, "/required", Some("name")).as_deref(),
+            Some("Package name")
+        );
+        assert_eq!(
+            schema_description(&schema, "/required", Some("other")).as_deref(),
+            Some("Root")
+        );
+    }
+
+    #[test]
+    fn description_missing_returns_none() {
+        let schema = json!({ "properties": { "a": { "type": "string" } } });
+        assert!(schema_description(&schema, "/properties/a/type", None).is_none());
+        assert!(schema_description(&schema, "/properties/missing/type", None).is_none());
+    }
+
+    // --- schema_error_message ---
+
+    #[test]
+    fn error_message_string_and_per_keyword() {
+        let schema = json!({
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "pattern": "^[a-z-]+$",
+                    "errorMessage": { "pattern": "use kebab-case: {message}" }
+                },
+                "port": { "$ref": "#/$defs/port" }
+            },
+            "required": ["name"],
+            "errorMessage": { "required": { "name": "every service needs a name" } },
+            "$defs": { "port": { "type": "integer", "errorMessage": "ports are numbers" } }
+        });
+        assert_eq!(
+            schema_error_message(&schema, "/properties/name/pattern", None).as_deref(),
+            Some("use kebab-case: {message}")
+        );
+        assert_eq!(
+            schema_error_message(&schema, "/properties/name/type", None),
+            None
+        );
+        assert_eq!(
+            schema_error_message(&schema, "/properties/port/$ref/type", None).as_deref(),
+            Some("ports are numbers")
+        );
+        assert_eq!(
+            schema_error_message(&schema, "/required", Some("\"name\"")).as_deref(),
+            Some("every service needs a name")
+        );
+        assert_eq!(
+            fill_message_template("{instance_path}: {message}", "bad", "/name"),
+            "/name: bad"
+        );
+    }
+}
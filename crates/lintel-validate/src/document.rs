@@ -0,0 +1,500 @@
+//! Validation of a single document against an already-known schema, with
+//! no filesystem or network access of its own.
+//!
+//! This is the part of the pipeline shared by `lintel check` and embedders
+//! such as the WASM build: turning `jsonschema` errors into
+//! [`ValidationError`]s and those into [`LintelDiagnostic`]s with the same
+//! suggestions, descriptions and messages. [`validate_document`] runs it end
+//! to end, resolving `$ref`s through a caller-provided retriever.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use lintel_diagnostics::{
+    DEFAULT_LABEL, LintelDiagnostic, ValidationDiagnostic, find_instance_path_span, format_label,
+};
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+use schema_catalog::FileFormat;
+
+use crate::parsers;
+use crate::{describe, suggest};
+
+/// Validate `content` as if it were the file at `path`, which decides its
+/// format, against `schema`.
+///
+/// `schema_url` names the schema in diagnostics and is the base URI its
+/// relative `$ref`s resolve against. Every external `$ref` is fetched
+/// through `retriever`, so the caller decides whether schemas come from
+/// disk, the network or memory. Diagnostics match those of `lintel check`
+/// for the same document and schema, including `lintel-ignore` comments;
+/// parse errors and schemas that fail to compile are reported as
+/// diagnostics too.
+///
+/// Markdown is validated by its front matter. Documents in a format that
+/// cannot be detected or parsed produce no diagnostics, like files
+/// `lintel check` skips.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_document(
+    path: &str,
+    content: &str,
+    schema: &Value,
+    schema_url: &str,
+    retriever: impl jsonschema::Retrieve + 'static,
+) -> Vec<LintelDiagnostic> {
+    let format = parsers::detect_format(Path::new(path));
+    let (format, instance) = match format {
+        Some(format) => match parsers::parser_for(format).parse(content, path) {
+            Ok(instance) => (format, instance),
+            Err(error) => return vec![error],
+        },
+        None => match parsers::try_parse_all(content, path) {
+            Some(parsed) => parsed,
+            None => return Vec::new(),
+        },
+    };
+    if instance.is_null() {
+        return Vec::new();
+    }
+    // Diagnostics of front matter point into the whole Markdown file.
+    let (document, offset) = match format {
+        FileFormat::Markdown => parsers::markdown::front_matter(content).unwrap_or((content, 0)),
+        _ => (content, 0),
+    };
+
+    let mut options = crate::patterns::apply(jsonschema::options())
+        .with_retriever(retriever)
+        .should_validate_formats(true);
+    if let Some(base_uri) = schema_url
+        .split('#')
+        .next()
+        .filter(|uri| uri.contains("://"))
+    {
+        options = options.with_base_uri(base_uri);
+    }
+    let validator = match options.build(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            return vec![LintelDiagnostic::SchemaCompile {
+                path: path.to_string(),
+                message: format!("failed to compile schema: {e}"),
+            }];
+        }
+    };
+
+    let deprecations = crate::deprecated::marks_deprecated(schema);
+    let validation_errors = document_errors(
+        &validator,
+        schema,
+        &instance,
+        document,
+        "",
+        false,
+        deprecations,
+    );
+    let source = DiagnosticSource {
+        path,
+        file: path,
+        content,
+        offset,
+    };
+    let mut diagnostics = Vec::new();
+    push_validation_errors(
+        &source,
+        &instance,
+        "",
+        schema_url,
+        &validation_errors,
+        &mut diagnostics,
+        Some(schema),
+    );
+    let mut suppressions = crate::suppress::parse(content);
+    crate::suppress::apply(&mut diagnostics, path, &mut suppressions);
+    diagnostics
+}
+
+/// The errors of `instance`, parsed from `content`, against the compiled
+/// `validator` for `schema`: its `jsonschema` errors, with `deprecations`
+/// the deprecated properties it sets (see
+/// [`marks_deprecated`](crate::deprecated::marks_deprecated)), and with
+/// `strict_instances` the properties the schema allows only by omitting
+/// `additionalProperties`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn document_errors(
+    validator: &jsonschema::Validator,
+    schema: &Value,
+    instance: &Value,
+    content: &str,
+    instance_prefix: &str,
+    strict_instances: bool,
+    deprecations: bool,
+) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = validator
+        .iter_errors(instance)
+        .flat_map(|error| convert_error(&error, content, instance_prefix))
+        .collect();
+    if strict_instances || deprecations {
+        let evaluation = validator.evaluate(instance);
+        if strict_instances {
+            errors.extend(crate::strict::undescribed_properties(
+                &evaluation,
+                schema,
+                instance,
+                content,
+                instance_prefix,
+            ));
+        }
+        if deprecations {
+            errors.extend(crate::deprecated::deprecated_properties(
+                &evaluation,
+                content,
+                instance_prefix,
+            ));
+        }
+    }
+    errors
+}
+
+/// The file a document's diagnostics point into: the document itself, or
+/// the file it is embedded in.
+pub(crate) struct DiagnosticSource<'a> {
+    /// The path diagnostics are reported under: the file, or `file:line`
+    /// for a document embedded in it.
+    pub(crate) path: &'a str,
+    pub(crate) file: &'a str,
+    pub(crate) content: &'a str,
+    /// Byte offset of the document in `content`.
+    pub(crate) offset: usize,
+}
+
+/// Convert the [`ValidationError`]s of `instance` into
+/// [`LintelDiagnostic::Validation`] diagnostics.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn push_validation_errors(
+    source: &DiagnosticSource<'_>,
+    instance: &Value,
+    instance_prefix: &str,
+    schema_url: &str,
+    validation_errors: &[ValidationError],
+    errors: &mut Vec<LintelDiagnostic>,
+    schema: Option<&Value>,
+) {
+    for ve in validation_errors {
+        let instance_path = if ve.instance_path.is_empty() {
+            DEFAULT_LABEL.to_string()
+        } else {
+            ve.instance_path.clone()
+        };
+        let label = format_label(&instance_path, &ve.schema_path);
+        let source_span: miette::SourceSpan = (ve.span.0 + source.offset, ve.span.1).into();
+        let mut message = ve.kind.message();
+        let suggestion = match (&ve.kind, schema) {
+            (
+                ValidationErrorKind::AdditionalProperty { property }
+                | ValidationErrorKind::UnevaluatedProperty { property }
+                | ValidationErrorKind::UndescribedProperty { property },
+                Some(s),
+            ) => suggest::suggest_property(property, &ve.schema_path, s),
+            _ => None,
+        };
+        if let Some(suggestion) = &suggestion {
+            message = format!("{message}; did you mean '{suggestion}'?");
+        }
+        let allowed_values = ve.kind.allowed_values().unwrap_or_default();
+        if let ValidationErrorKind::Enum { .. } = ve.kind
+            && let Some(value) = ve
+                .instance_path
+                .strip_prefix(instance_prefix)
+                .and_then(|pointer| instance.pointer(pointer))
+            && let Some(closest) = suggest::suggest_value(value, &allowed_values)
+        {
+            message = format!("{message}; did you mean {closest}?");
+        }
+        let required_property = match ve.kind {
+            ValidationErrorKind::Required { ref property } => Some(property.as_str()),
+            _ => None,
+        };
+        let schema_description = schema
+            .and_then(|s| describe::schema_description(s, &ve.schema_path, required_property));
+        if let Some(template) = schema
+            .and_then(|s| describe::schema_error_message(s, &ve.schema_path, required_property))
+        {
+            message = describe::fill_message_template(&template, &message, &ve.instance_path);
+        }
+        errors.push(LintelDiagnostic::Validation(ValidationDiagnostic {
+            src: miette::NamedSource::new(source.file, source.content.to_string()),
+            span: source_span,
+            schema_span: source_span,
+            path: source.path.to_string(),
+            instance_path,
+            label,
+            message,
+            schema_url: schema_url.to_string(),
+            schema_path: ve.schema_path.clone(),
+            validation_code: format!("validation({})", ve.kind.as_ref()),
+            schema_description,
+            suggestion,
+            allowed_values,
+            severity: matches!(
+                ve.kind,
+                ValidationErrorKind::UndescribedProperty { .. }
+                    | ValidationErrorKind::Deprecated { .. }
+                    | ValidationErrorKind::Plugin { warning: true, .. }
+            )
+            .then_some(miette::Severity::Warning),
+        }));
+    }
+}
+
+/// The schema keyword that produced an error: the last segment of its schema
+/// path (`/properties/port/type` → `type`).
+pub(crate) fn error_keyword(schema_path: &str) -> &str {
+    schema_path.rsplit('/').next().unwrap_or(schema_path)
+}
+
+/// The JSON pointer of the schema keyword behind an annotation. Schemas
+/// compiled with a base URI locate their annotations as `<uri>#<pointer>`.
+pub(crate) fn keyword_pointer(schema_location: &str) -> &str {
+    schema_location
+        .split_once('#')
+        .map_or(schema_location, |(_, pointer)| pointer)
+}
+
+/// Map a `jsonschema::error::ValidationErrorKind` to our serializable
+/// [`ValidationErrorKind`]. `AdditionalProperties` and
+/// `UnevaluatedProperties` are handled separately in [`convert_error`].
+fn convert_kind(kind: &jsonschema::error::ValidationErrorKind) -> ValidationErrorKind {
+    use jsonschema::error::{TypeKind, ValidationErrorKind as JK};
+
+    match kind {
+        JK::AdditionalItems { limit } => ValidationErrorKind::AdditionalItems { limit: *limit },
+        JK::AdditionalProperties { .. } | JK::UnevaluatedProperties { .. } => {
+            unreachable!("handled in convert_error")
+        }
+        JK::AnyOf { .. } => ValidationErrorKind::AnyOf,
+        JK::BacktrackLimitExceeded { error } => ValidationErrorKind::BacktrackLimitExceeded {
+            message: error.to_string(),
+        },
+        JK::Constant { expected_value } => ValidationErrorKind::Constant {
+            expected_value: expected_value.clone(),
+        },
+        JK::Contains => ValidationErrorKind::Contains,
+        JK::ContentEncoding { content_encoding } => ValidationErrorKind::ContentEncoding {
+            content_encoding: content_encoding.clone(),
+        },
+        JK::ContentMediaType { content_media_type } => ValidationErrorKind::ContentMediaType {
+            content_media_type: content_media_type.clone(),
+        },
+        JK::Custom { keyword, message } => ValidationErrorKind::Custom {
+            keyword: keyword.clone(),
+            message: message.clone(),
+        },
+        JK::Enum { options } => ValidationErrorKind::Enum {
+            options: options.clone(),
+        },
+        JK::ExclusiveMaximum { limit } => ValidationErrorKind::ExclusiveMaximum {
+            limit: limit.clone(),
+        },
+        JK::ExclusiveMinimum { limit } => ValidationErrorKind::ExclusiveMinimum {
+            limit: limit.clone(),
+        },
+        JK::FalseSchema => ValidationErrorKind::FalseSchema,
+        JK::Format { format } => ValidationErrorKind::Format {
+            format: format.clone(),
+        },
+        JK::FromUtf8 { error } => ValidationErrorKind::FromUtf8 {
+            message: error.to_string(),
+        },
+        JK::MaxItems { limit } => ValidationErrorKind::MaxItems { limit: *limit },
+        JK::Maximum { limit } => ValidationErrorKind::Maximum {
+            limit: limit.clone(),
+        },
+        JK::MaxLength { limit } => ValidationErrorKind::MaxLength { limit: *limit },
+        JK::MaxProperties { limit } => ValidationErrorKind::MaxProperties { limit: *limit },
+        JK::MinItems { limit } => ValidationErrorKind::MinItems { limit: *limit },
+        JK::Minimum { limit } => ValidationErrorKind::Minimum {
+            limit: limit.clone(),
+        },
+        JK::MinLength { limit } => ValidationErrorKind::MinLength { limit: *limit },
+        JK::MinProperties { limit } => ValidationErrorKind::MinProperties { limit: *limit },
+        JK::MultipleOf { multiple_of } => ValidationErrorKind::MultipleOf {
+            multiple_of: *multiple_of,
+        },
+        JK::Not { .. } => ValidationErrorKind::Not,
+        JK::OneOfMultipleValid { .. } => ValidationErrorKind::OneOfMultipleValid,
+        JK::OneOfNotValid { .. } => ValidationErrorKind::OneOfNotValid,
+        JK::Pattern { pattern } => ValidationErrorKind::Pattern {
+            pattern: pattern.clone(),
+        },
+        JK::PropertyNames { error } => ValidationErrorKind::PropertyNames {
+            message: error.to_string(),
+        },
+        JK::Required { property } => ValidationErrorKind::Required {
+            property: match property {
+                Value::String(s) => format!("\"{s}\""),
+                other => other.to_string(),
+            },
+        },
+        JK::Type { kind } => {
+            let expected = match kind {
+                TypeKind::Single(t) => t.to_string(),
+                TypeKind::Multiple(ts) => {
+                    let parts: Vec<String> = ts.iter().map(|t| t.to_string()).collect();
+                    parts.join(", ")
+                }
+            };
+            ValidationErrorKind::Type { expected }
+        }
+        JK::UnevaluatedItems { unexpected } => ValidationErrorKind::UnevaluatedItems {
+            unexpected: unexpected.clone(),
+        },
+        JK::UniqueItems => ValidationErrorKind::UniqueItems,
+        JK::Referencing(err) => ValidationErrorKind::Referencing {
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Convert a single `jsonschema::ValidationError` into one or more typed
+/// [`ValidationError`]s with pre-computed spans.
+///
+/// `AdditionalProperties` and `UnevaluatedProperties` errors are split into
+/// one per unexpected property.
+/// A failed `anyOf` or `oneOf` is replaced by the errors of the branch that
+/// fits the instance best, when there is one.
+/// `instance_prefix` is prepended to instance paths when only a fragment of
+/// the document was validated.
+pub(crate) fn convert_error(
+    error: &jsonschema::ValidationError<'_>,
+    content: &str,
+    instance_prefix: &str,
+) -> Vec<ValidationError> {
+    use jsonschema::error::ValidationErrorKind as JK;
+
+    let schema_path = error.schema_path().to_string();
+    let base_instance_path = format!("{instance_prefix}{}", error.instance_path());
+
+    if let JK::AdditionalProperties { unexpected } | JK::UnevaluatedProperties { unexpected } =
+        error.kind()
+    {
+        let additional = matches!(error.kind(), JK::AdditionalProperties { .. });
+        return unexpected
+            .iter()
+            .map(|prop| {
+                let instance_path = format!("{base_instance_path}/{prop}");
+                let span = find_instance_path_span(content, &instance_path);
+                let property = prop.clone();
+                ValidationError {
+                    instance_path,
+                    schema_path: schema_path.clone(),
+                    kind: if additional {
+                        ValidationErrorKind::AdditionalProperty { property }
+                    } else {
+                        ValidationErrorKind::UnevaluatedProperty { property }
+                    },
+                    span,
+                }
+            })
+            .collect();
+    }
+
+    if let JK::AnyOf { context } | JK::OneOfNotValid { context } = error.kind() {
+        let mut branches: Vec<Vec<ValidationError>> = context
+            .iter()
+            .map(|branch| {
+                branch
+                    .iter()
+                    .flat_map(|e| convert_error(e, content, instance_prefix))
+                    .collect()
+            })
+            .collect();
+        if let Some(best) = crate::branch::best_branch(&base_instance_path, &branches) {
+            return branches.swap_remove(best);
+        }
+    }
+
+    let span = find_instance_path_span(content, &base_instance_path);
+    vec![ValidationError {
+        instance_path: base_instance_path,
+        schema_path,
+        kind: convert_kind(error.kind()),
+        span,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Serves schemas from memory, like an embedder without a filesystem.
+    struct Resources(HashMap<String, Value>);
+
+    impl jsonschema::Retrieve for Resources {
+        fn retrieve(
+            &self,
+            uri: &jsonschema::Uri<String>,
+        ) -> Result<Value, Box<dyn core::error::Error + Send + Sync>> {
+            self.0
+                .get(uri.as_str())
+                .cloned()
+                .ok_or_else(|| format!("no resource for {uri}").into())
+        }
+    }
+
+    fn resources() -> Resources {
+        Resources(HashMap::from([(
+            "https://example.com/port.json".to_string(),
+            serde_json::json!({"type": "integer", "description": "Listening port"}),
+        )]))
+    }
+
+    #[test]
+    fn validates_a_document_with_refs_from_the_retriever() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}, "port": {"$ref": "port.json"}},
+            "additionalProperties": false
+        });
+        let content = "name: demo\nport: http\nnmae: x\n# lintel-ignore\nother: 1\n";
+        let diagnostics = validate_document(
+            "app.yaml",
+            content,
+            &schema,
+            "https://example.com/app.json",
+            resources(),
+        );
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .filter_map(|d| match d {
+                LintelDiagnostic::Validation(v) => {
+                    Some((v.instance_path.as_str(), v.message.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("/port", r#"value is not of type "integer""#),
+                (
+                    "/nmae",
+                    "Additional properties are not allowed ('nmae' was unexpected); did you mean 'name'?"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_parse_and_compile_errors() {
+        let schema = serde_json::json!({"$ref": "https://example.com/missing.json"});
+        let diagnostics = validate_document("app.json", "{", &schema, "schema.json", resources());
+        assert!(matches!(diagnostics[..], [LintelDiagnostic::Parse { .. }]));
+        let diagnostics = validate_document("app.json", "{}", &schema, "schema.json", resources());
+        assert!(matches!(
+            diagnostics[..],
+            [LintelDiagnostic::SchemaCompile { .. }]
+        ));
+    }
+}
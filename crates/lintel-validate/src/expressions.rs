@@ -0,0 +1,267 @@
+//! Checks for the `${{ }}` expressions of GitHub Actions workflows and
+//! actions.
+//!
+//! The workflow and action schemas see an expression as an opaque string, so
+//! a misspelled context (`${{ secret.TOKEN }}`) or function
+//! (`${{ startWith(github.ref, 'refs/tags/') }}`) only fails once the
+//! workflow runs. With `[github-actions] expressions = true`, every
+//! expression and every `if:` condition is checked against the contexts and
+//! functions GitHub defines.
+
+use core::fmt::Write;
+
+use lintel_diagnostics::find_instance_path_span;
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+use serde_json::Value;
+
+/// Contexts an expression can read.
+const CONTEXTS: &[&str] = &[
+    "env", "github", "inputs", "job", "jobs", "matrix", "needs", "runner", "secrets", "steps",
+    "strategy", "vars",
+];
+
+/// Literals spelled like identifiers.
+const LITERALS: &[&str] = &["true", "false", "null", "NaN", "Infinity"];
+
+/// Functions an expression can call.
+const FUNCTIONS: &[&str] = &[
+    "always",
+    "cancelled",
+    "contains",
+    "endsWith",
+    "failure",
+    "format",
+    "fromJSON",
+    "hashFiles",
+    "join",
+    "startsWith",
+    "success",
+    "toJSON",
+];
+
+/// Whether documents validated against `schema_uri` are GitHub workflows or
+/// actions.
+pub(crate) fn applies_to(schema_uri: &str) -> bool {
+    let name = schema_uri.rsplit('/').next().unwrap_or(schema_uri);
+    matches!(name, "github-workflow.json" | "github-action.json")
+}
+
+/// One error per unknown context or function in the expressions of
+/// `instance`. `instance_prefix` and `content` are used like in
+/// [`convert_error`](crate::document::convert_error).
+pub(crate) fn check(
+    instance: &Value,
+    content: &str,
+    instance_prefix: &str,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut pointer = instance_prefix.to_string();
+    walk(instance, None, &mut pointer, content, &mut errors);
+    errors
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    value: &Value,
+    key: Option<&str>,
+    pointer: &mut String,
+    content: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let len = pointer.len();
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                walk(value, Some(key), pointer, content, errors);
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                let _ = write!(pointer, "/{index}");
+                walk(value, None, pointer, content, errors);
+                pointer.truncate(len);
+            }
+        }
+        Value::String(text) => {
+            let mut problems = Vec::new();
+            if text.contains("${{") {
+                for expression in expressions(text) {
+                    check_expression(expression, &mut problems);
+                }
+            } else if key == Some("if") {
+                // `if:` conditions are expressions without the `${{ }}`.
+                check_expression(text, &mut problems);
+            }
+            for (rule, message) in problems {
+                errors.push(ValidationError {
+                    instance_path: pointer.clone(),
+                    schema_path: format!("/github-expressions/{rule}"),
+                    kind: ValidationErrorKind::Expression { message },
+                    span: find_instance_path_span(content, pointer),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The expressions between `${{` and `}}` in `text`. A `}}` inside a
+/// string literal does not end an expression.
+fn expressions(text: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${{") {
+        let body = &rest[start + 3..];
+        let mut in_string = false;
+        let mut end = None;
+        let bytes = body.as_bytes();
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'\'' => in_string = !in_string,
+                b'}' if !in_string && bytes.get(i + 1) == Some(&b'}') => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else { break };
+        found.push(&body[..end]);
+        rest = &body[end + 2..];
+    }
+    found
+}
+
+/// Report the unknown contexts and functions of one expression as
+/// `(rule, message)` pairs.
+fn check_expression(expression: &str, problems: &mut Vec<(&'static str, String)>) {
+    let mut chars = expression.char_indices().peekable();
+    let mut after_dot = false;
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '.' {
+            after_dot = true;
+            continue;
+        }
+        let is_property = core::mem::take(&mut after_dot);
+        if c == '\'' {
+            // String literals escape `'` as `''`, which this reads as an
+            // empty literal followed by another.
+            while chars.next().is_some_and(|(_, c)| c != '\'') {}
+            continue;
+        }
+        if !(c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some(&(i, c)) = chars.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                break;
+            }
+            end = i + 1;
+            chars.next();
+        }
+        // Property names and numbers are not checked.
+        if is_property || c.is_ascii_digit() {
+            continue;
+        }
+        let name = &expression[start..end];
+        let problem = if expression[end..].trim_start().starts_with('(') {
+            (!is_known(name, FUNCTIONS))
+                .then(|| ("unknown-function", describe("function", name, FUNCTIONS)))
+        } else {
+            (!is_known(name, CONTEXTS) && !is_known(name, LITERALS))
+                .then(|| ("undefined-context", describe("context", name, CONTEXTS)))
+        };
+        if let Some((rule, message)) = problem {
+            let message = format!("{message} in expression '{}'", expression.trim());
+            if !problems.iter().any(|(_, m)| *m == message) {
+                problems.push((rule, message));
+            }
+        }
+    }
+}
+
+/// Contexts and functions are case-insensitive.
+fn is_known(name: &str, known: &[&str]) -> bool {
+    known.iter().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+fn describe(what: &str, name: &str, known: &[&str]) -> String {
+    match crate::suggest::suggest_name(name, known) {
+        Some(suggestion) => format!("unknown {what} '{name}' (did you mean '{suggestion}'?)"),
+        None => format!("unknown {what} '{name}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn messages(instance: &Value) -> Vec<(String, String, String)> {
+        check(instance, "", "")
+            .into_iter()
+            .map(|e| (e.instance_path, e.schema_path, e.kind.message()))
+            .collect()
+    }
+
+    #[test]
+    fn known_contexts_functions_and_literals_pass() {
+        let workflow = json!({
+            "on": "push",
+            "jobs": {"build": {
+                "if": "github.event_name == 'push' && !cancelled()",
+                "runs-on": "${{ matrix.os }}",
+                "steps": [{
+                    "if": "${{ startsWith(GitHub.ref, 'refs/tags/') || env.FORCE == 'true' }}",
+                    "run": "echo ${{ format('{0}}} and ''{1}''', steps.build-step.outputs.x, 1.5) }}",
+                    "with": {"key": "${{ hashFiles('**/Cargo.lock') }}-${{ null }}"}
+                }]
+            }}
+        });
+        assert_eq!(messages(&workflow), []);
+    }
+
+    #[test]
+    fn reports_unknown_contexts_and_functions() {
+        let workflow = json!({
+            "jobs": {"deploy": {
+                "if": "succes()",
+                "steps": [{"run": "echo ${{ secret.TOKEN }} ${{ secret.TOKEN }} ${{ toJson(foo) }}"}]
+            }}
+        });
+        assert_eq!(
+            messages(&workflow),
+            [
+                (
+                    "/jobs/deploy/if".to_string(),
+                    "/github-expressions/unknown-function".to_string(),
+                    "unknown function 'succes' (did you mean 'success'?) in expression 'succes()'"
+                        .to_string()
+                ),
+                (
+                    "/jobs/deploy/steps/0/run".to_string(),
+                    "/github-expressions/undefined-context".to_string(),
+                    "unknown context 'secret' (did you mean 'secrets'?) in expression \
+                     'secret.TOKEN'"
+                        .to_string()
+                ),
+                (
+                    "/jobs/deploy/steps/0/run".to_string(),
+                    "/github-expressions/undefined-context".to_string(),
+                    "unknown context 'foo' in expression 'toJson(foo)'".to_string()
+                ),
+            ]
+        );
+        assert!(applies_to(
+            "https://json.schemastore.org/github-workflow.json"
+        ));
+        assert!(!applies_to("https://json.schemastore.org/package.json"));
+    }
+}
@@ -0,0 +1,443 @@
+//! Custom `format` keyword validators.
+//!
+//! The `[formats]` table of `lintel.toml` maps format names used by schemas
+//! to the built-in validators in [`BUILTIN`]:
+//!
+//! ```toml
+//! [formats]
+//! quantity = "k8s-quantity"
+//! version-range = "semver-range"
+//! ```
+//!
+//! When lintel is embedded as a library, [`FormatRegistry::register`] adds
+//! validators written in Rust.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+
+/// A check that a string is in some format.
+pub type FormatCheck = fn(&str) -> bool;
+
+/// Built-in validators that `[formats]` entries can refer to, by name.
+pub const BUILTIN: &[(&str, FormatCheck)] = &[
+    ("cron", is_cron),
+    ("k8s-quantity", is_k8s_quantity),
+    ("semver", is_semver),
+    ("semver-range", is_semver_range),
+];
+
+type FormatFn = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Validators for `format` values, added to those of the `jsonschema` crate.
+///
+/// Validators registered from Rust take priority over `[formats]` entries
+/// in `lintel.toml` for the same format name.
+///
+/// Validation results are cached by file, schema and format names, so after
+/// changing what a Rust validator accepts, run once with
+/// `force_validation`.
+#[derive(Clone, Default)]
+pub struct FormatRegistry {
+    /// Format name → (validator name for the cache key, validator).
+    formats: BTreeMap<String, (String, FormatFn)>,
+}
+
+impl core::fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.formats.iter().map(|(name, (id, _))| (name, id)))
+            .finish()
+    }
+}
+
+impl FormatRegistry {
+    /// Validate values of the format `name` with `validator`, replacing any
+    /// validator registered for it before.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        validator: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        let name = name.into();
+        self.formats
+            .insert(name, ("rust".to_string(), Arc::new(validator)));
+        self
+    }
+
+    /// Validate values of the format `name` with the built-in validator
+    /// called `validator` (see [`BUILTIN`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no built-in validator called `validator`.
+    pub fn register_builtin(
+        &mut self,
+        name: impl Into<String>,
+        validator: &str,
+    ) -> Result<&mut Self> {
+        let Some((id, check)) = BUILTIN.iter().find(|(id, _)| *id == validator) else {
+            let names: Vec<&str> = BUILTIN.iter().map(|(id, _)| *id).collect();
+            bail!(
+                "unknown format validator '{validator}', expected one of: {}",
+                names.join(", ")
+            );
+        };
+        self.formats
+            .insert(name.into(), ((*id).to_string(), Arc::new(*check)));
+        Ok(self)
+    }
+
+    /// Whether no validators are registered.
+    pub fn is_empty(&self) -> bool {
+        self.formats.is_empty()
+    }
+
+    /// This registry plus the `[formats]` entries of `lintel.toml` for
+    /// formats it does not already validate.
+    pub(crate) fn with_config(&self, formats: &HashMap<String, String>) -> Result<Self> {
+        let mut merged = self.clone();
+        let sorted: BTreeMap<&String, &String> = formats.iter().collect();
+        for (name, validator) in sorted {
+            if !self.formats.contains_key(name) {
+                merged
+                    .register_builtin(name.clone(), validator)
+                    .with_context(|| format!("invalid [formats] entry '{name}' in lintel.toml"))?;
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Add the validators to `options`.
+    pub(crate) fn apply<R>(
+        &self,
+        mut options: jsonschema::ValidationOptions<R>,
+    ) -> jsonschema::ValidationOptions<R> {
+        for (name, (_, validator)) in &self.formats {
+            let validator = Arc::clone(validator);
+            options = options.with_format(name.clone(), move |value: &str| validator(value));
+        }
+        options
+    }
+
+    /// Identifies the validators in validation cache keys, e.g.
+    /// `quantity=k8s-quantity,version=rust`.
+    pub(crate) fn cache_key(&self) -> String {
+        let entries: Vec<String> = self
+            .formats
+            .iter()
+            .map(|(name, (id, _))| format!("{name}={id}"))
+            .collect();
+        entries.join(",")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Built-in validators
+// ---------------------------------------------------------------------------
+
+/// A Kubernetes resource quantity, e.g. `500m`, `1.5Gi` or `2e3`.
+pub fn is_k8s_quantity(s: &str) -> bool {
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(end);
+    if number.matches('.').count() > 1 || !number.bytes().any(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    match suffix {
+        "" | "Ki" | "Mi" | "Gi" | "Ti" | "Pi" | "Ei" | "n" | "u" | "m" | "k" | "M" | "G" | "T"
+        | "P" | "E" => true,
+        _ => suffix.strip_prefix(['e', 'E']).is_some_and(|exponent| {
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+        }),
+    }
+}
+
+/// A semantic version, e.g. `1.2.3` or `1.0.0-rc.1+build.5`.
+pub fn is_semver(s: &str) -> bool {
+    let Some((core, pre, build)) = split_version(s) else {
+        return false;
+    };
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts.iter().all(|p| is_numeric_identifier(p))
+        && pre.is_none_or(is_prerelease)
+        && build.is_none_or(is_build)
+}
+
+/// An npm-style semantic version range, e.g. `^1.2.0 || >=2.0.0 <3`,
+/// `1.x` or `1.0.0 - 2.0.0`.
+pub fn is_semver_range(s: &str) -> bool {
+    s.split("||").all(|range| {
+        let parts: Vec<&str> = range.split_whitespace().collect();
+        match parts.as_slice() {
+            [from, "-", to] => is_partial_version(from) && is_partial_version(to),
+            comparators => comparators.iter().all(|c| {
+                let version = ["<=", ">=", "<", ">", "=", "~", "^"]
+                    .iter()
+                    .find_map(|op| c.strip_prefix(op))
+                    .unwrap_or(c);
+                is_partial_version(version)
+            }),
+        }
+    })
+}
+
+/// A version in a range: `1`, `1.2`, `1.x`, `*` or a full version with an
+/// optional `v` prefix.
+fn is_partial_version(s: &str) -> bool {
+    let s = s.strip_prefix('v').unwrap_or(s);
+    let Some((core, pre, build)) = split_version(s) else {
+        return false;
+    };
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.len() > 3 || (parts.len() < 3 && (pre.is_some() || build.is_some())) {
+        return false;
+    }
+    parts
+        .iter()
+        .all(|p| matches!(*p, "x" | "X" | "*") || is_numeric_identifier(p))
+        && pre.is_none_or(is_prerelease)
+        && build.is_none_or(is_build)
+}
+
+/// Split `1.2.3-pre+build` into its version core, pre-release and build.
+fn split_version(s: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+    let (s, build) = match s.split_once('+') {
+        Some((s, build)) => (s, Some(build)),
+        None => (s, None),
+    };
+    let (core, pre) = match s.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (s, None),
+    };
+    (!core.is_empty()).then_some((core, pre, build))
+}
+
+/// A number without leading zeros.
+fn is_numeric_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && (s == "0" || !s.starts_with('0'))
+}
+
+fn is_prerelease(s: &str) -> bool {
+    s.split('.').all(|id| {
+        is_alphanumeric_identifier(id)
+            && (!id.bytes().all(|b| b.is_ascii_digit()) || is_numeric_identifier(id))
+    })
+}
+
+fn is_build(s: &str) -> bool {
+    s.split('.').all(is_alphanumeric_identifier)
+}
+
+fn is_alphanumeric_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Allowed values of a cron field.
+struct CronField {
+    min: u32,
+    max: u32,
+    /// Names for the values from `min`, e.g. `JAN` for month 1.
+    names: &'static [&'static str],
+}
+
+const CRON_FIELDS: [CronField; 5] = [
+    CronField {
+        min: 0,
+        max: 59,
+        names: &[],
+    },
+    CronField {
+        min: 0,
+        max: 23,
+        names: &[],
+    },
+    CronField {
+        min: 1,
+        max: 31,
+        names: &[],
+    },
+    CronField {
+        min: 1,
+        max: 12,
+        names: &[
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ],
+    },
+    CronField {
+        min: 0,
+        max: 7,
+        names: &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+    },
+];
+
+/// A five-field cron schedule, e.g. `*/15 9-17 * * MON-FRI`, or a macro such
+/// as `@daily`.
+pub fn is_cron(s: &str) -> bool {
+    let s = s.trim();
+    if s.starts_with('@') {
+        return matches!(
+            s,
+            "@yearly"
+                | "@annually"
+                | "@monthly"
+                | "@weekly"
+                | "@daily"
+                | "@midnight"
+                | "@hourly"
+                | "@reboot"
+        );
+    }
+    let fields: Vec<&str> = s.split_whitespace().collect();
+    fields.len() == CRON_FIELDS.len()
+        && fields
+            .iter()
+            .zip(&CRON_FIELDS)
+            .all(|(value, field)| is_cron_field(value, field))
+}
+
+/// A comma-separated list of `*`, values and ranges, each with an optional
+/// `/step`.
+fn is_cron_field(s: &str, field: &CronField) -> bool {
+    s.split(',').all(|item| {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+        if step.is_some_and(|step| cron_number(step).is_none_or(|n| n == 0)) {
+            return false;
+        }
+        if range == "*" {
+            return true;
+        }
+        match range.split_once('-') {
+            Some((from, to)) => {
+                matches!((cron_value(from, field), cron_value(to, field)), (Some(from), Some(to)) if from <= to)
+            }
+            None => cron_value(range, field).is_some(),
+        }
+    })
+}
+
+fn cron_value(s: &str, field: &CronField) -> Option<u32> {
+    if let Some(index) = field
+        .names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(s))
+    {
+        return u32::try_from(index).ok().map(|i| field.min + i);
+    }
+    cron_number(s).filter(|n| (field.min..=field.max).contains(n))
+}
+
+fn cron_number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k8s_quantities() {
+        for ok in [
+            "1", "500m", "1.5Gi", "+2", "-0.5", ".5", "1.", "2e3", "1E-2", "128Mi", "1k",
+        ] {
+            assert!(is_k8s_quantity(ok), "{ok}");
+        }
+        for bad in ["", "Gi", "1.2.3", "1GB", "1gi", "1e", "1e+", "1 Gi", "."] {
+            assert!(!is_k8s_quantity(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn semantic_versions() {
+        for ok in [
+            "0.0.0",
+            "1.2.3",
+            "1.0.0-rc.1",
+            "1.0.0-alpha-1+build.5",
+            "10.20.30+001",
+        ] {
+            assert!(is_semver(ok), "{ok}");
+        }
+        for bad in [
+            "1.2", "01.2.3", "1.2.3-", "1.2.3-01", "1.2.3+", "v1.2.3", "1.2.3.4",
+        ] {
+            assert!(!is_semver(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn semantic_version_ranges() {
+        for ok in [
+            "^1.2.0",
+            "~1.2",
+            ">=2.0.0 <3",
+            "1.x || >=2.5.0",
+            "1.0.0 - 2.0.0",
+            "*",
+            "",
+            "v1.2.3",
+            "<=1.0.0-beta.2",
+        ] {
+            assert!(is_semver_range(ok), "{ok}");
+        }
+        for bad in ["^1.2.0.0", ">=a", "1.0.0 -", "~>1.2", "1.2-beta", "^01.2.3"] {
+            assert!(!is_semver_range(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn cron_schedules() {
+        for ok in [
+            "* * * * *",
+            "*/15 9-17 * * MON-FRI",
+            "0 0 1,15 jan-jun 0",
+            "5/10 0 * * 7",
+            "@daily",
+        ] {
+            assert!(is_cron(ok), "{ok}");
+        }
+        for bad in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "@often",
+        ] {
+            assert!(!is_cron(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn config_formats_do_not_replace_rust_validators() -> anyhow::Result<()> {
+        let mut registry = FormatRegistry::default();
+        registry.register("version", |s: &str| s == "latest");
+        let config = HashMap::from([
+            ("version".to_string(), "semver".to_string()),
+            ("schedule".to_string(), "cron".to_string()),
+        ]);
+        let merged = registry.with_config(&config)?;
+        assert_eq!(merged.cache_key(), "schedule=cron,version=rust");
+
+        let bad = HashMap::from([("size".to_string(), "bytes".to_string())]);
+        let err = registry.with_config(&bad).expect_err("unknown validator");
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid [formats] entry 'size' in lintel.toml: unknown format validator 'bytes', expected one of: cron, k8s-quantity, semver, semver-range"
+        );
+        Ok(())
+    }
+}
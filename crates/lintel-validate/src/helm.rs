@@ -0,0 +1,72 @@
+//! Schema resolution for Helm charts.
+//!
+//! A directory with a `Chart.yaml` is a chart. Its `values.yaml` and
+//! `values.<name>.yaml` files are validated against the chart's
+//! `values.schema.json`, the schema `helm install` and `helm lint` enforce,
+//! and `Chart.yaml` itself against the `SchemaStore` chart schema.
+
+use std::path::Path;
+
+/// The `SchemaStore` schema for `Chart.yaml`.
+pub(crate) const CHART_SCHEMA_URL: &str = "https://json.schemastore.org/chart.json";
+
+/// The chart's `values.schema.json`, as an absolute path, if `path` is a
+/// values file of a chart that has one.
+pub(crate) fn values_schema(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let is_values = name == "values.yaml"
+        || name
+            .strip_prefix("values.")
+            .and_then(|rest| rest.strip_suffix(".yaml"))
+            .is_some_and(|env| !env.is_empty());
+    if !is_values {
+        return None;
+    }
+    let chart = path.parent()?;
+    let schema = chart.join("values.schema.json");
+    if !chart.join("Chart.yaml").is_file() || !schema.is_file() {
+        return None;
+    }
+    let schema = std::path::absolute(&schema).ok()?;
+    Some(schema.to_string_lossy().into_owned())
+}
+
+/// [`CHART_SCHEMA_URL`] if `path` is a `Chart.yaml`.
+pub(crate) fn chart_schema(path: &Path) -> Option<String> {
+    (path.file_name()? == "Chart.yaml" && path.is_file()).then(|| CHART_SCHEMA_URL.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn values_files_of_a_chart_use_its_values_schema() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("values.yaml"), "replicas: 1\n")?;
+        fs::write(tmp.path().join("values.schema.json"), "{}")?;
+        let schema = std::path::absolute(tmp.path().join("values.schema.json"))?;
+        let schema = schema.to_string_lossy().into_owned();
+
+        // Not a chart until it has a Chart.yaml.
+        assert_eq!(values_schema(&tmp.path().join("values.yaml")), None);
+
+        fs::write(tmp.path().join("Chart.yaml"), "name: app\n")?;
+        assert_eq!(
+            values_schema(&tmp.path().join("values.yaml")),
+            Some(schema.clone())
+        );
+        assert_eq!(
+            values_schema(&tmp.path().join("values.production.yaml")),
+            Some(schema)
+        );
+        assert_eq!(values_schema(&tmp.path().join("values.json")), None);
+        assert_eq!(values_schema(&tmp.path().join("other.yaml")), None);
+        assert_eq!(
+            chart_schema(&tmp.path().join("Chart.yaml")).as_deref(),
+            Some(CHART_SCHEMA_URL)
+        );
+        Ok(())
+    }
+}
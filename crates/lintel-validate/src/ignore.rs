@@ -0,0 +1,104 @@
+//! `ignore_keywords` from `[[override]]` blocks.
+//!
+//! Ignored keywords are removed from the schema, and from every schema it
+//! references, before it is compiled. Filtering errors afterwards would not
+//! be enough: an ignored keyword failing inside `anyOf` or `oneOf` fails the
+//! branch around it, and that error comes from a keyword that is not
+//! ignored. Replacing the keywords with no-op ones is not enough either,
+//! since the `jsonschema` crate checks some keywords together (`properties`
+//! with `additionalProperties`, `properties` with `required`).
+
+use serde_json::Value;
+
+/// Keywords whose value is a schema.
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "contentSchema",
+    "else",
+    "if",
+    "items",
+    "not",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords whose value is an array of schemas.
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf", "prefixItems"];
+
+/// Keywords whose value maps names to schemas.
+const SCHEMA_MAP_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependencies",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// Remove the keywords in `ignored` from `schema` and its subschemas.
+/// Property names and other values that are not schemas are kept.
+pub(crate) fn strip(schema: &mut Value, ignored: &[String]) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+    object.retain(|keyword, _| !ignored.contains(keyword));
+    for (keyword, value) in object.iter_mut() {
+        let keyword = keyword.as_str();
+        if SCHEMA_KEYWORDS.contains(&keyword) {
+            strip(value, ignored);
+        }
+        if SCHEMA_ARRAY_KEYWORDS.contains(&keyword)
+            && let Value::Array(schemas) = value
+        {
+            for subschema in schemas {
+                strip(subschema, ignored);
+            }
+        }
+        if SCHEMA_MAP_KEYWORDS.contains(&keyword)
+            && let Value::Object(schemas) = value
+        {
+            for subschema in schemas.values_mut() {
+                strip(subschema, ignored);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn strips_keywords_from_subschemas_only() {
+        let mut schema = json!({
+            "anyOf": [
+                {
+                    "type": "object",
+                    "properties": { "required": { "type": "string", "required": ["a"] } },
+                    "required": ["name"]
+                },
+                { "type": "string" }
+            ],
+            "$defs": { "item": { "items": [{ "required": ["b"] }] } }
+        });
+        strip(&mut schema, &["required".to_string()]);
+        assert_eq!(
+            schema,
+            json!({
+                "anyOf": [
+                    {
+                        "type": "object",
+                        "properties": { "required": { "type": "string" } }
+                    },
+                    { "type": "string" }
+                ],
+                "$defs": { "item": { "items": [{}] } }
+            })
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Schema resolution for Kubernetes manifests by `apiVersion` and `kind`.
+//!
+//! Built-in resources map to the `kubernetes-json-schema` layout used by
+//! kubeconform; custom resources map to a CRD catalog such as the datreeio
+//! `CRDs-catalog`. See [`lintel_config::Kubernetes`].
+
+use lintel_config::Kubernetes;
+use serde_json::Value;
+
+const DEFAULT_BASE_URL: &str =
+    "https://raw.githubusercontent.com/yannh/kubernetes-json-schema/master";
+
+const DEFAULT_CRD_CATALOG: &str =
+    "https://raw.githubusercontent.com/datreeio/CRDs-catalog/main/{group}/{kind}_{version}.json";
+
+/// API groups served by the Kubernetes API server itself. Other groups,
+/// including some under `k8s.io`, are custom resources.
+const BUILTIN_GROUPS: &[&str] = &[
+    "",
+    "admissionregistration.k8s.io",
+    "apiextensions.k8s.io",
+    "apiregistration.k8s.io",
+    "apps",
+    "authentication.k8s.io",
+    "authorization.k8s.io",
+    "autoscaling",
+    "batch",
+    "certificates.k8s.io",
+    "coordination.k8s.io",
+    "discovery.k8s.io",
+    "events.k8s.io",
+    "flowcontrol.apiserver.k8s.io",
+    "internal.apiserver.k8s.io",
+    "networking.k8s.io",
+    "node.k8s.io",
+    "policy",
+    "rbac.authorization.k8s.io",
+    "resource.k8s.io",
+    "scheduling.k8s.io",
+    "storage.k8s.io",
+    "storagemigration.k8s.io",
+];
+
+/// The schema URL for a manifest, if Kubernetes resolution is enabled and the
+/// document has string `apiVersion` and `kind` fields.
+pub(crate) fn schema_uri(config: &Kubernetes, instance: &Value) -> Option<String> {
+    if config.enabled != Some(true) {
+        return None;
+    }
+    let api_version = instance.get("apiVersion")?.as_str()?;
+    let kind = instance.get("kind")?.as_str()?.to_ascii_lowercase();
+    let (group, version) = api_version.rsplit_once('/').unwrap_or(("", api_version));
+
+    if BUILTIN_GROUPS.contains(&group) {
+        let base = config
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/');
+        let k8s_version = match config.version.as_deref() {
+            None | Some("master") => "master".to_string(),
+            Some(v) if v.starts_with('v') => v.to_string(),
+            Some(v) => format!("v{v}"),
+        };
+        // `rbac.authorization.k8s.io/v1` -> `-rbac-v1`, `v1` -> `-v1`
+        let suffix = match group.split('.').next() {
+            Some(prefix) if !prefix.is_empty() => format!("-{prefix}-{version}"),
+            _ => format!("-{version}"),
+        };
+        return Some(format!(
+            "{base}/{k8s_version}-standalone-strict/{kind}{suffix}.json"
+        ));
+    }
+
+    let template = config
+        .crds
+        .iter()
+        .filter(|(pattern, _)| glob_matcher::glob_match(pattern, group))
+        .max_by_key(|(pattern, _)| pattern.len())
+        .map_or(DEFAULT_CRD_CATALOG, |(_, template)| template.as_str());
+    Some(
+        template
+            .replace("{group}", group)
+            .replace("{kind}", &kind)
+            .replace("{version}", version),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(api_version: &str, kind: &str) -> Value {
+        json!({ "apiVersion": api_version, "kind": kind })
+    }
+
+    #[test]
+    fn builtin_resources_use_kubernetes_json_schema() {
+        let config = Kubernetes {
+            enabled: Some(true),
+            version: Some("1.31.0".into()),
+            ..Default::default()
+        };
+        let base = "https://raw.githubusercontent.com/yannh/kubernetes-json-schema/master/v1.31.0-standalone-strict";
+        assert_eq!(
+            schema_uri(&config, &manifest("v1", "ConfigMap")),
+            Some(format!("{base}/configmap-v1.json"))
+        );
+        assert_eq!(
+            schema_uri(&config, &manifest("apps/v1", "Deployment")),
+            Some(format!("{base}/deployment-apps-v1.json"))
+        );
+        assert_eq!(
+            schema_uri(&config, &manifest("rbac.authorization.k8s.io/v1", "Role")),
+            Some(format!("{base}/role-rbac-v1.json"))
+        );
+        assert_eq!(schema_uri(&config, &json!({ "kind": "Pod" })), None);
+        assert_eq!(
+            schema_uri(&Kubernetes::default(), &manifest("v1", "Pod")),
+            None
+        );
+    }
+
+    #[test]
+    fn custom_resources_use_the_longest_matching_crd_catalog() {
+        let config = Kubernetes {
+            enabled: Some(true),
+            crds: [
+                (
+                    "*".to_string(),
+                    "https://all/{group}/{kind}.json".to_string(),
+                ),
+                (
+                    "*.example.com".to_string(),
+                    "https://example/{kind}_{version}.json".to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            schema_uri(&config, &manifest("db.example.com/v1alpha1", "Cluster")).as_deref(),
+            Some("https://example/cluster_v1alpha1.json")
+        );
+        assert_eq!(
+            schema_uri(&config, &manifest("cert-manager.io/v1", "Certificate")).as_deref(),
+            Some("https://all/cert-manager.io/certificate.json")
+        );
+        assert_eq!(
+            schema_uri(
+                &Kubernetes {
+                    enabled: Some(true),
+                    ..Default::default()
+                },
+                &manifest("monitoring.coreos.com/v1", "ServiceMonitor")
+            )
+            .as_deref(),
+            Some(
+                "https://raw.githubusercontent.com/datreeio/CRDs-catalog/main/monitoring.coreos.com/servicemonitor_v1.json"
+            )
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! `lintel.lock`: the content hash of every remote schema a project uses.
+//!
+//! The lockfile lives next to `lintel.toml`. When it exists, each run records
+//! the SHA-256 of the remote schemas it downloads, including the ones
+//! reached through `$ref`; with `--frozen` a schema whose content differs
+//! from the recorded hash fails the files using it instead.
+
+use alloc::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use lintel_diagnostics::LintelDiagnostic;
+use lintel_schema_cache::CacheStatus;
+
+/// File name of the lockfile, next to `lintel.toml`.
+pub const LOCK_FILENAME: &str = "lintel.lock";
+
+const HEADER: &str = "# This file is generated by lintel. Do not edit it by hand.\n\
+# Run `lintel check --update-lock` after upstream schemas change.\n\n";
+
+/// How a run treats `lintel.lock`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockMode {
+    /// If the lockfile exists, record new schemas and warn about changed
+    /// ones without accepting the change.
+    #[default]
+    Auto,
+    /// Create the lockfile if needed and record new and changed schemas.
+    Update,
+    /// Fail files whose remote schema is missing from, or differs from, the
+    /// lockfile. The lockfile is never written.
+    Frozen,
+}
+
+/// The contents of `lintel.lock`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lockfile {
+    #[serde(default, rename = "schema")]
+    pub schemas: Vec<LockedSchema>,
+}
+
+/// One remote schema pinned by the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedSchema {
+    pub url: String,
+    /// SHA-256 hex digest of the schema as downloaded.
+    pub sha256: String,
+    /// When this content was first seen, in RFC 3339.
+    pub fetched: String,
+}
+
+impl Lockfile {
+    /// Read a lockfile, returning `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        toml::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Write the lockfile, entries sorted by URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.schemas.sort_by(|a, b| a.url.cmp(&b.url));
+        let body = toml::to_string(self).context("failed to serialize lockfile")?;
+        std::fs::write(path, format!("{HEADER}{body}"))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// The entry for `url`, if it is locked.
+    pub fn get(&self, url: &str) -> Option<&LockedSchema> {
+        self.schemas.iter().find(|s| s.url == url)
+    }
+
+    /// Record the content hash of `url`. Returns `true` if the entry was
+    /// added or its hash changed.
+    pub fn record(&mut self, url: &str, sha256: &str, fetched: &str) -> bool {
+        let entry = LockedSchema {
+            url: url.to_string(),
+            sha256: sha256.to_string(),
+            fetched: fetched.to_string(),
+        };
+        match self.schemas.iter_mut().find(|s| s.url == url) {
+            Some(existing) if existing.sha256 == sha256 => false,
+            Some(existing) => {
+                *existing = entry;
+                true
+            }
+            None => {
+                self.schemas.push(entry);
+                true
+            }
+        }
+    }
+}
+
+/// `lintel.lock` as checked during one run.
+///
+/// Every remote schema the run downloads, both the schemas files name and
+/// the ones they `$ref`, goes through [`check`](Self::check), so the lock
+/// covers everything validation depends on.
+pub(crate) struct SchemaLock {
+    mode: LockMode,
+    path: PathBuf,
+    /// Timestamp recorded for new entries.
+    fetched: String,
+    state: Mutex<LockState>,
+}
+
+struct LockState {
+    lock: Lockfile,
+    /// Whether entries were recorded and the lockfile needs writing.
+    changed: bool,
+    /// Schemas that changed since they were locked, by URL, with the time
+    /// they were locked.
+    outdated: BTreeMap<String, String>,
+}
+
+impl SchemaLock {
+    /// Load the lockfile in `config_dir`. Returns `None` when there is
+    /// nothing to check: in [`LockMode::Auto`] without a lockfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile cannot be read, or if `--frozen` is
+    /// used without one.
+    pub(crate) fn load(mode: LockMode, config_dir: &Path) -> Result<Option<Self>> {
+        let path = config_dir.join(LOCK_FILENAME);
+        let lock = match (mode, Lockfile::load(&path)?) {
+            (LockMode::Auto, None) => return Ok(None),
+            (LockMode::Frozen, None) => bail!(
+                "--frozen requires {}; create it with --update-lock",
+                path.display()
+            ),
+            (_, lock) => lock.unwrap_or_default(),
+        };
+        Ok(Some(Self {
+            mode,
+            fetched: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            state: Mutex::new(LockState {
+                lock,
+                // `--update-lock` creates the lockfile even if it stays empty
+                changed: mode == LockMode::Update && !path.exists(),
+                outdated: BTreeMap::new(),
+            }),
+            path,
+        }))
+    }
+
+    /// Check the SHA-256 `hash` of the content downloaded for `uri`,
+    /// recording it when the mode accepts new or changed content.
+    ///
+    /// # Errors
+    ///
+    /// In [`LockMode::Frozen`], returns a message explaining why `uri` may
+    /// not be used when it is missing from the lockfile or its content
+    /// differs.
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    pub(crate) fn check(&self, uri: &str, hash: &str) -> Result<(), String> {
+        let mut state = self.state.lock().expect("lockfile poisoned");
+        let state = &mut *state;
+        let accept = match self.mode {
+            LockMode::Update => true,
+            LockMode::Auto => state.lock.get(uri).is_none(),
+            LockMode::Frozen => false,
+        };
+        if accept {
+            state.changed |= state.lock.record(uri, hash, &self.fetched);
+            return Ok(());
+        }
+        match state.lock.get(uri) {
+            Some(locked) if locked.sha256 == hash => Ok(()),
+            Some(locked) if self.mode == LockMode::Auto => {
+                state
+                    .outdated
+                    .insert(uri.to_string(), locked.fetched.clone());
+                Ok(())
+            }
+            Some(locked) => Err(format!(
+                "schema {uri} changed since it was locked on {} (sha256 {hash}, \
+                 locked {}); run with --update-lock to accept the change",
+                locked.fetched, locked.sha256
+            )),
+            None => Err(format!(
+                "schema {uri} is not in {LOCK_FILENAME}; run with --update-lock to add it"
+            )),
+        }
+    }
+
+    /// Write the lockfile if entries were recorded, and return a warning for
+    /// each schema that changed since it was locked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile cannot be written.
+    #[allow(clippy::missing_panics_doc)] // Mutex poisoning is unreachable
+    pub(crate) fn finish(&self) -> Result<Vec<LintelDiagnostic>> {
+        let mut state = self.state.lock().expect("lockfile poisoned");
+        if state.changed {
+            state.lock.save(&self.path)?;
+        }
+        let path = self.path.display().to_string();
+        Ok(core::mem::take(&mut state.outdated)
+            .into_iter()
+            .map(|(uri, locked)| LintelDiagnostic::SchemaChanged {
+                path: path.clone(),
+                message: format!(
+                    "schema {uri} changed since it was locked on {locked}; \
+                     run with --update-lock to accept the change"
+                ),
+            })
+            .collect())
+    }
+}
+
+/// Check the prefetched remote schemas against `lock`. `content_hash`
+/// returns the SHA-256 of a schema's raw content, see
+/// [`lintel_schema_cache::SchemaCache::content_hash`].
+///
+/// Schemas the lock rejects are turned into fetch errors so every file
+/// using them reports one.
+pub(crate) fn apply(
+    lock: &SchemaLock,
+    content_hash: impl Fn(&str) -> Option<String>,
+    prefetched: &mut HashMap<String, Result<(Value, CacheStatus), String>>,
+) {
+    for (uri, result) in prefetched.iter_mut() {
+        if result.is_err() {
+            continue;
+        }
+        let Some(hash) = content_hash(uri) else {
+            continue;
+        };
+        if let Err(message) = lock.check(uri, &hash) {
+            *result = Err(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_round_trips_entries() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join(LOCK_FILENAME);
+        let mut lock = Lockfile::default();
+        assert!(lock.record("https://b.example/s.json", "bb", "2026-01-01T00:00:00Z"));
+        assert!(lock.record("https://a.example/s.json", "aa", "2026-01-01T00:00:00Z"));
+        assert!(!lock.record("https://a.example/s.json", "aa", "2026-02-01T00:00:00Z"));
+        assert!(lock.record("https://a.example/s.json", "a2", "2026-02-01T00:00:00Z"));
+        lock.save(&path)?;
+
+        let content = std::fs::read_to_string(&path)?;
+        assert!(content.starts_with("# This file is generated by lintel."));
+        let loaded = Lockfile::load(&path)?.expect("lockfile should exist");
+        assert_eq!(loaded, lock);
+        assert_eq!(loaded.schemas[0].url, "https://a.example/s.json");
+        assert_eq!(loaded.schemas[0].sha256, "a2");
+        assert_eq!(loaded.schemas[0].fetched, "2026-02-01T00:00:00Z");
+        assert_eq!(Lockfile::load(&tmp.path().join("missing.lock"))?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn frozen_rejects_changed_and_unlocked_schemas() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let changed = "https://example.com/changed.json";
+        let unlocked = "https://example.com/new.json";
+        let same = "https://example.com/same.json";
+        let mut lock = Lockfile::default();
+        lock.record(changed, "old", "2026-01-01T00:00:00Z");
+        lock.record(same, "hash", "2026-01-01T00:00:00Z");
+        lock.save(&tmp.path().join(LOCK_FILENAME))?;
+
+        let mut prefetched: HashMap<String, Result<(Value, CacheStatus), String>> =
+            [changed, unlocked, same]
+                .into_iter()
+                .map(|uri| (uri.to_string(), Ok((Value::Null, CacheStatus::Hit))))
+                .collect();
+        let lock_state = SchemaLock::load(LockMode::Frozen, tmp.path())?.expect("lockfile exists");
+        apply(&lock_state, |_| Some("hash".to_string()), &mut prefetched);
+        assert!(prefetched[same].is_ok());
+        assert!(
+            prefetched[changed]
+                .as_ref()
+                .is_err_and(|e| e.contains("changed since it was locked"))
+        );
+        assert!(
+            prefetched[unlocked]
+                .as_ref()
+                .is_err_and(|e| e.contains("is not in lintel.lock"))
+        );
+        assert!(lock_state.finish()?.is_empty());
+        assert_eq!(Lockfile::load(&tmp.path().join(LOCK_FILENAME))?, Some(lock));
+
+        let missing = tmp.path().join("sub");
+        assert!(SchemaLock::load(LockMode::Frozen, &missing).is_err());
+        assert!(SchemaLock::load(LockMode::Auto, &missing)?.is_none());
+        assert!(!missing.join(LOCK_FILENAME).exists());
+        Ok(())
+    }
+    #[test]
+    fn auto_records_new_schemas_and_warns_about_changed_ones() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join(LOCK_FILENAME);
+        let mut lock = Lockfile::default();
+        lock.record("https://example.com/a.json", "old", "2026-01-01T00:00:00Z");
+        lock.save(&path)?;
+
+        let lock_state = SchemaLock::load(LockMode::Auto, tmp.path())?.expect("lockfile exists");
+        lock_state
+            .check("https://example.com/a.json", "new")
+            .expect("auto accepts");
+        lock_state
+            .check("https://example.com/a.json", "new")
+            .expect("auto accepts");
+        lock_state
+            .check("https://example.com/b.json", "b")
+            .expect("auto accepts");
+        let warnings = lock_state.finish()?;
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].is_warning());
+        assert!(
+            warnings[0]
+                .message()
+                .contains("https://example.com/a.json changed")
+        );
+
+        let saved = Lockfile::load(&path)?.expect("lockfile exists");
+        assert_eq!(
+            saved
+                .get("https://example.com/a.json")
+                .map(|s| s.sha256.as_str()),
+            Some("old")
+        );
+        assert_eq!(
+            saved
+                .get("https://example.com/b.json")
+                .map(|s| s.sha256.as_str()),
+            Some("b")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,390 @@
+//! OpenAPI 3.0 and 3.1 documents.
+//!
+//! Documents with an `openapi` version and an `info` object resolve to the
+//! matching OpenAPI meta-schema. With `--openapi-examples`, the `example`,
+//! `examples` and `default` values inside the document are also validated
+//! against the schemas they belong to, with `$ref`s resolved within the
+//! document.
+
+use serde_json::{Map, Value};
+
+use lintel_validation_cache::ValidationError;
+
+/// Meta-schema of OpenAPI 3.0 documents (JSON Schema draft 4).
+pub const OPENAPI_30_SCHEMA: &str = "https://spec.openapis.org/oas/3.0/schema/2021-09-28";
+
+/// Meta-schema of OpenAPI 3.1 documents (JSON Schema 2020-12).
+pub const OPENAPI_31_SCHEMA: &str = "https://spec.openapis.org/oas/3.1/schema/2022-10-07";
+
+/// Keys whose value is a schema, inside a schema.
+const SUBSCHEMA_KEYS: &[&str] = &[
+    "items",
+    "additionalProperties",
+    "not",
+    "if",
+    "then",
+    "else",
+    "contains",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keys whose value is a map of schemas, inside a schema.
+const SUBSCHEMA_MAP_KEYS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+];
+
+/// Keys whose value is a list of schemas, inside a schema.
+const SUBSCHEMA_LIST_KEYS: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+
+/// The OpenAPI version of a document: `(3, 0)` or `(3, 1)`.
+fn version(doc: &Value) -> Option<(u32, u32)> {
+    doc.get("info")?.as_object()?;
+    let version = doc.get("openapi")?.as_str()?;
+    if version.starts_with("3.0.") || version == "3.0" {
+        Some((3, 0))
+    } else if version.starts_with("3.1.") || version == "3.1" {
+        Some((3, 1))
+    } else {
+        None
+    }
+}
+
+/// The meta-schema for an OpenAPI document, if `doc` is one.
+pub(crate) fn schema_uri(doc: &Value) -> Option<String> {
+    match version(doc)? {
+        (3, 0) => Some(OPENAPI_30_SCHEMA.to_string()),
+        _ => Some(OPENAPI_31_SCHEMA.to_string()),
+    }
+}
+
+/// An example or default value and the schema it must match, as JSON
+/// pointers into the document.
+#[derive(Debug, PartialEq, Eq)]
+struct Example {
+    value: String,
+    schema: String,
+}
+
+/// Validate the examples and defaults of an OpenAPI document against their
+/// schemas. Returns the errors of each failing example with the pointer of
+/// the schema it was checked against; instance paths are relative to the
+/// document. Returns nothing for documents that are not OpenAPI.
+pub(crate) fn check_examples(doc: &Value, content: &str) -> Vec<(String, Vec<ValidationError>)> {
+    let Some(version) = version(doc) else {
+        return Vec::new();
+    };
+    let mut examples = Vec::new();
+    collect(doc, &mut String::new(), Context::Document, &mut examples);
+
+    // Schemas `$ref` into the document, so every example is validated against
+    // the whole document with a root `$ref` to its schema.
+    let mut root = doc.clone();
+    if version == (3, 0) {
+        nullable_to_type(&mut root);
+    }
+    let mut results = Vec::new();
+    for example in examples {
+        let Some(value) = doc.pointer(&example.value) else {
+            continue;
+        };
+        if let Some(map) = root.as_object_mut() {
+            map.insert("$ref".into(), Value::String(format!("#{}", example.schema)));
+        }
+        let mut options = jsonschema::options();
+        if version == (3, 0) {
+            options = options.with_draft(jsonschema::Draft::Draft4);
+        }
+        let Ok(validator) = options.build(&root) else {
+            continue;
+        };
+        let errors: Vec<ValidationError> = validator
+            .iter_errors(value)
+            .flat_map(|error| crate::document::convert_error(&error, content, &example.value))
+            .collect();
+        if !errors.is_empty() {
+            results.push((example.schema, errors));
+        }
+    }
+    results
+}
+
+/// Where a value sits in an OpenAPI document.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Context {
+    Document,
+    Schema,
+}
+
+/// Append `key` to a JSON pointer.
+fn push_segment(pointer: &mut String, key: &str) {
+    pointer.push('/');
+    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
+
+fn collect(value: &Value, pointer: &mut String, context: Context, out: &mut Vec<Example>) {
+    let Some(map) = value.as_object() else {
+        if let Value::Array(items) = value {
+            for (i, item) in items.iter().enumerate() {
+                let len = pointer.len();
+                push_segment(pointer, &i.to_string());
+                collect(item, pointer, context, out);
+                pointer.truncate(len);
+            }
+        }
+        return;
+    };
+
+    if context == Context::Schema {
+        collect_schema_examples(map, pointer, out);
+    } else if map.get("schema").is_some_and(Value::is_object) {
+        collect_media_examples(map, pointer, out);
+    }
+
+    for (key, child) in map {
+        let key = key.as_str();
+        let child_context = match context {
+            // Examples are data, not schemas.
+            _ if matches!(key, "example" | "examples") => continue,
+            Context::Schema if key == "default" => continue,
+            Context::Schema if is_subschema_key(key) => Context::Schema,
+            Context::Document if key == "schema" => Context::Schema,
+            _ => Context::Document,
+        };
+        let schema_map = match context {
+            Context::Schema => SUBSCHEMA_MAP_KEYS.contains(&key),
+            Context::Document => key == "schemas" && pointer.as_str() == "/components",
+        };
+        let len = pointer.len();
+        push_segment(pointer, key);
+        if schema_map {
+            collect_schema_map(child, pointer, out);
+        } else {
+            collect(child, pointer, child_context, out);
+        }
+        pointer.truncate(len);
+    }
+}
+
+fn is_subschema_key(key: &str) -> bool {
+    SUBSCHEMA_KEYS.contains(&key)
+        || SUBSCHEMA_MAP_KEYS.contains(&key)
+        || SUBSCHEMA_LIST_KEYS.contains(&key)
+}
+
+/// Walk a map whose values are all schemas.
+fn collect_schema_map(value: &Value, pointer: &mut String, out: &mut Vec<Example>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    for (key, schema) in map {
+        let len = pointer.len();
+        push_segment(pointer, key);
+        collect(schema, pointer, Context::Schema, out);
+        pointer.truncate(len);
+    }
+}
+
+/// The `example` and `examples` values of a Media Type, Parameter or Header
+/// Object, checked against its `schema`.
+fn collect_media_examples(object: &Map<String, Value>, pointer: &str, out: &mut Vec<Example>) {
+    let schema = format!("{pointer}/schema");
+    if object.contains_key("example") {
+        out.push(Example {
+            value: format!("{pointer}/example"),
+            schema: schema.clone(),
+        });
+    }
+    let Some(Value::Object(examples)) = object.get("examples") else {
+        return;
+    };
+    for (name, example) in examples {
+        if example.get("value").is_some() {
+            let mut value = pointer.to_string();
+            push_segment(&mut value, "examples");
+            push_segment(&mut value, name);
+            push_segment(&mut value, "value");
+            out.push(Example {
+                value,
+                schema: schema.clone(),
+            });
+        }
+    }
+}
+
+/// The `example`, `examples` (3.1) and `default` values of a Schema Object.
+fn collect_schema_examples(schema: &Map<String, Value>, pointer: &str, out: &mut Vec<Example>) {
+    if schema.contains_key("$ref") {
+        return;
+    }
+    for key in ["example", "default"] {
+        if schema.contains_key(key) {
+            out.push(Example {
+                value: format!("{pointer}/{key}"),
+                schema: pointer.to_string(),
+            });
+        }
+    }
+    if let Some(Value::Array(examples)) = schema.get("examples") {
+        for i in 0..examples.len() {
+            out.push(Example {
+                value: format!("{pointer}/examples/{i}"),
+                schema: pointer.to_string(),
+            });
+        }
+    }
+}
+
+/// Rewrite OpenAPI 3.0 `nullable: true` as a `null` entry in `type`, which
+/// JSON Schema validators understand.
+fn nullable_to_type(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if map.get("nullable") == Some(&Value::Bool(true))
+                && let Some(Value::String(ty)) = map.get("type")
+            {
+                let types = Value::Array(vec![Value::String(ty.clone()), "null".into()]);
+                map.insert("type".into(), types);
+            }
+            map.values_mut().for_each(nullable_to_type);
+        }
+        Value::Array(items) => items.iter_mut().for_each(nullable_to_type),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{
+                            "name": "limit",
+                            "in": "query",
+                            "schema": { "type": "integer", "default": "ten" },
+                            "example": 5
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Pet" },
+                                        "examples": {
+                                            "good": { "value": { "name": "Rex", "tag": null } },
+                                            "bad": { "value": { "tag": "dog" } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": { "type": "string", "example": "Rex" },
+                            "tag": { "type": "string", "nullable": true, "example": 7 }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn detects_openapi_versions() {
+        assert_eq!(schema_uri(&spec()).as_deref(), Some(OPENAPI_30_SCHEMA));
+        assert_eq!(
+            schema_uri(&json!({ "openapi": "3.1.0", "info": {} })).as_deref(),
+            Some(OPENAPI_31_SCHEMA)
+        );
+        assert_eq!(schema_uri(&json!({ "swagger": "2.0", "info": {} })), None);
+        assert_eq!(schema_uri(&json!({ "openapi": "3.0.0" })), None);
+    }
+
+    #[test]
+    fn collects_examples_with_their_schemas() {
+        let mut examples = Vec::new();
+        collect(
+            &spec(),
+            &mut String::new(),
+            Context::Document,
+            &mut examples,
+        );
+        let mut pairs: Vec<(String, String)> =
+            examples.into_iter().map(|e| (e.value, e.schema)).collect();
+        pairs.sort_unstable();
+
+        let params = "/paths/~1pets/get/parameters/0";
+        let media = "/paths/~1pets/get/responses/200/content/application~1json";
+        let pet = "/components/schemas/Pet/properties";
+        let expected = [
+            (format!("{pet}/name/example"), format!("{pet}/name")),
+            (format!("{pet}/tag/example"), format!("{pet}/tag")),
+            (format!("{params}/example"), format!("{params}/schema")),
+            (
+                format!("{params}/schema/default"),
+                format!("{params}/schema"),
+            ),
+            (
+                format!("{media}/examples/bad/value"),
+                format!("{media}/schema"),
+            ),
+            (
+                format!("{media}/examples/good/value"),
+                format!("{media}/schema"),
+            ),
+        ];
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn reports_examples_that_do_not_match_their_schema() {
+        let doc = spec();
+        let content = serde_json::to_string_pretty(&doc).unwrap_or_default();
+        let mut failing: Vec<(String, Vec<String>)> = check_examples(&doc, &content)
+            .into_iter()
+            .map(|(schema, errors)| {
+                let paths = errors.into_iter().map(|e| e.instance_path).collect();
+                (schema, paths)
+            })
+            .collect();
+        failing.sort_unstable();
+
+        let params = "/paths/~1pets/get/parameters/0";
+        let media = "/paths/~1pets/get/responses/200/content/application~1json";
+        let pet = "/components/schemas/Pet/properties";
+        // `tag: null` in the good example is allowed by `nullable`.
+        assert_eq!(
+            failing,
+            [
+                (format!("{pet}/tag"), vec![format!("{pet}/tag/example")]),
+                (
+                    format!("{params}/schema"),
+                    vec![format!("{params}/schema/default")]
+                ),
+                (
+                    format!("{media}/schema"),
+                    vec![format!("{media}/examples/bad/value")]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Jsonnet and CUE files, evaluated to JSON before validation.
+//!
+//! Evaluation runs the `jsonnet` or `cue` command on the file, so the tool
+//! must be on `PATH`. The output is validated, with errors located in the
+//! source by key; evaluation errors point at the first position the tool
+//! reports in the file.
+//!
+//! Detection of `.jsonnet` and `.cue` files is behind the `jsonnet` and `cue`
+//! cargo features.
+
+use std::process::Command;
+
+use miette::NamedSource;
+use serde_json::Value;
+
+use lintel_diagnostics::LintelDiagnostic;
+
+use super::Parser;
+
+pub struct JsonnetParser;
+
+impl Parser for JsonnetParser {
+    fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+        evaluate(Command::new("jsonnet").arg(file_name), content, file_name)
+    }
+}
+
+pub struct CueParser;
+
+impl Parser for CueParser {
+    fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+        evaluate(
+            Command::new("cue")
+                .args(["export", "--out", "json"])
+                .arg(file_name),
+            content,
+            file_name,
+        )
+    }
+}
+
+/// Run `command`, which evaluates the file at `file_name` and prints JSON.
+#[allow(clippy::result_large_err)]
+fn evaluate(
+    command: &mut Command,
+    content: &str,
+    file_name: &str,
+) -> Result<Value, LintelDiagnostic> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let error = |offset: usize, message: String| LintelDiagnostic::Parse {
+        src: NamedSource::new(file_name, content.to_string()),
+        span: offset.into(),
+        message,
+    };
+    let output = command
+        .output()
+        .map_err(|e| error(0, format!("failed to run {program}: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let offset = error_location(&stderr, file_name).map_or(0, |(line, col)| {
+            super::line_col_to_offset(content, line, col)
+        });
+        return Err(error(offset, stderr.trim().to_string()));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| error(0, format!("{program} did not print JSON: {e}")))
+}
+
+/// The first `NAME:LINE:COL` (or `NAME:(LINE:COL)`) in `message` whose
+/// `NAME` is the file at `file_name`. Tools print paths relative to where
+/// they run, so only the file name is compared.
+fn error_location(message: &str, file_name: &str) -> Option<(usize, usize)> {
+    let name = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+    let mut rest = message;
+    while let Some(pos) = rest.find(name) {
+        let boundary = rest[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || matches!(c, '/' | '\\'));
+        rest = &rest[pos + name.len()..];
+        let Some(after) = rest.strip_prefix(':') else {
+            continue;
+        };
+        let after = after.strip_prefix('(').unwrap_or(after);
+        if let (true, Some((line, after))) = (boundary, leading_number(after))
+            && let Some((col, _)) = after.strip_prefix(':').and_then(leading_number)
+        {
+            return Some((line, col));
+        }
+    }
+    None
+}
+
+fn leading_number(s: &str) -> Option<(usize, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    Some((s[..end].parse().ok()?, &s[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_jsonnet_error_locations() {
+        assert_eq!(
+            error_location(
+                "STATIC ERROR: deploy/app.jsonnet:3:12-15: unknown variable: prot",
+                "deploy/app.jsonnet"
+            ),
+            Some((3, 12))
+        );
+        assert_eq!(
+            error_location(
+                "RUNTIME ERROR: boom\n\tlib.libsonnet:1:1-5\tfunction <f>\n\t./app.jsonnet:(7:3)-(9:4)\tobject <anonymous>\n",
+                "app.jsonnet"
+            ),
+            Some((7, 3))
+        );
+    }
+
+    #[test]
+    fn finds_cue_error_locations() {
+        let message = "replicas: conflicting values 3 and string (mismatched types int and string):\n    ./schema.cue:2:12\n    /tmp/x/config.cue:4:13\n";
+        assert_eq!(error_location(message, "/tmp/x/config.cue"), Some((4, 13)));
+        assert_eq!(error_location(message, "other.cue"), None);
+        assert_eq!(error_location("my-config.cue:1:1", "config.cue"), None);
+    }
+}
@@ -0,0 +1,27 @@
+use miette::NamedSource;
+use serde_json::Value;
+
+use lintel_diagnostics::LintelDiagnostic;
+
+use super::Parser;
+
+/// HCL (`.tf`, `.hcl`), in the JSON representation Terraform's JSON syntax
+/// uses: blocks nest under their type and labels, repeated blocks become
+/// arrays, and non-literal expressions become `${...}` strings.
+pub struct HclParser;
+
+impl Parser for HclParser {
+    fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+        hcl::from_str(content).map_err(|e| {
+            let offset = match &e {
+                hcl::Error::Parse(err) => err.location().offset(),
+                _ => 0,
+            };
+            LintelDiagnostic::Parse {
+                src: NamedSource::new(file_name, content.to_string()),
+                span: offset.into(),
+                message: e.to_string(),
+            }
+        })
+    }
+}
@@ -1,4 +1,9 @@
+use core::fmt;
+
 use miette::NamedSource;
+use serde::de::{
+    self as de, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
 use serde_json::Value;
 
 use lintel_diagnostics::LintelDiagnostic;
@@ -9,14 +14,7 @@ pub struct JsonParser;
 
 impl Parser for JsonParser {
     fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
-        serde_json::from_str(content).map_err(|e| {
-            let offset = super::line_col_to_offset(content, e.line(), e.column());
-            LintelDiagnostic::Parse {
-                src: NamedSource::new(file_name, content.to_string()),
-                span: offset.into(),
-                message: e.to_string(),
-            }
-        })
+        serde_json::from_str(content).map_err(|e| parse_error(content, file_name, &e))
     }
 
     fn annotate(&self, content: &str, schema_url: &str) -> Option<String> {
@@ -27,3 +25,93 @@ impl Parser for JsonParser {
         super::strip_json_schema_property(content)
     }
 }
+
+fn parse_error(content: &str, file_name: &str, e: &serde_json::Error) -> LintelDiagnostic {
+    let offset = super::line_col_to_offset(content, e.line(), e.column());
+    LintelDiagnostic::Parse {
+        src: NamedSource::new(file_name, content.to_string()),
+        span: offset.into(),
+        message: e.to_string(),
+    }
+}
+
+/// Parse a JSON document into its outline: the scalar members of a
+/// top-level object, such as `$schema`, `apiVersion` or `openapi`, which is
+/// enough to pick its schema. Arrays and objects are checked for syntax and
+/// skipped rather than built, so a large document costs no memory beyond
+/// its text.
+#[allow(clippy::result_large_err)]
+pub(crate) fn parse_outline(content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    Outline { nested: false }
+        .deserialize(&mut deserializer)
+        .and_then(|outline| deserializer.end().map(|()| outline))
+        .map(|outline| outline.unwrap_or(Value::Null))
+        .map_err(|e| parse_error(content, file_name, &e))
+}
+
+/// Deserializes a scalar, and `None` for an array or object below the top
+/// level.
+#[derive(Clone, Copy)]
+struct Outline {
+    nested: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for Outline {
+    type Value = Option<Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Outline {
+    type Value = Option<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Some(Value::Bool(v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Some(Value::String(v.to_string())))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Some(Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok((!self.nested).then(|| Value::Array(Vec::new())))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        if self.nested {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            return Ok(None);
+        }
+        let mut members = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if let Some(value) = map.next_value_seed(Outline { nested: true })? {
+                members.insert(key, value);
+            }
+        }
+        Ok(Some(Value::Object(members)))
+    }
+}
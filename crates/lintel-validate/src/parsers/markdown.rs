@@ -1,3 +1,4 @@
+use schema_catalog::FileFormat;
 use serde_json::Value;
 
 use lintel_diagnostics::LintelDiagnostic;
@@ -71,6 +72,107 @@ fn extract_toml_frontmatter(content: &str) -> Option<(&str, usize)> {
     Some((frontmatter, front_start))
 }
 
+/// The YAML (`---`) or TOML (`+++`) front matter block and its byte offset
+/// in the Markdown content.
+pub fn front_matter(content: &str) -> Option<(&str, usize)> {
+    extract_yaml_frontmatter(content).or_else(|| extract_toml_frontmatter(content))
+}
+
+/// Front matter key mapping code fence languages to schema URIs, for fences
+/// without a `schema=` attribute.
+pub const FENCE_SCHEMAS_KEY: &str = "fence-schemas";
+
+/// A fenced code block in a Markdown file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeFence<'a> {
+    /// First word of the info string (`yaml` in ```` ```yaml schema=… ````).
+    pub lang: &'a str,
+    /// Value of the `schema=` attribute in the info string.
+    pub schema: Option<&'a str>,
+    /// The lines between the opening and closing fence.
+    pub body: &'a str,
+    /// Byte offset of `body` in the Markdown content.
+    pub offset: usize,
+    /// 1-based line number of the opening fence.
+    pub line_number: usize,
+}
+
+/// The file format of a code fence language, if it is one Lintel validates.
+pub fn fence_format(lang: &str) -> Option<FileFormat> {
+    match lang.to_ascii_lowercase().as_str() {
+        "json" => Some(FileFormat::Json),
+        "jsonc" => Some(FileFormat::Jsonc),
+        "json5" => Some(FileFormat::Json5),
+        "yaml" | "yml" => Some(FileFormat::Yaml),
+        "toml" => Some(FileFormat::Toml),
+        _ => None,
+    }
+}
+
+/// Find the fenced code blocks (```` ``` ```` or `~~~`) after the front
+/// matter. Unclosed fences are ignored.
+pub fn code_fences(content: &str) -> Vec<CodeFence<'_>> {
+    let front_matter_end = front_matter(content).map_or(0, |(front, start)| start + front.len());
+
+    let mut fences = Vec::new();
+    // The open fence's marker character and length, and the fence so far.
+    let mut open: Option<(char, usize, CodeFence<'_>)> = None;
+    let mut offset = 0;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+        if line_start < front_matter_end {
+            continue;
+        }
+        let Some((marker, len, info)) = fence_marker(line) else {
+            continue;
+        };
+        match open.take() {
+            Some((open_marker, open_len, mut fence))
+                if marker == open_marker && len >= open_len && info.is_empty() =>
+            {
+                fence.body = &content[fence.offset..line_start];
+                fences.push(fence);
+            }
+            Some(still_open) => open = Some(still_open),
+            // Backtick fences cannot have backticks in their info string.
+            None if marker == '`' && info.contains('`') => {}
+            None => {
+                let mut words = info.split_whitespace();
+                let lang = words.next().unwrap_or("");
+                let schema = words
+                    .find_map(|w| w.strip_prefix("schema="))
+                    .map(|s| s.trim_matches('"'));
+                let fence = CodeFence {
+                    lang,
+                    schema,
+                    body: "",
+                    offset,
+                    line_number: index + 1,
+                };
+                open = Some((marker, len, fence));
+            }
+        }
+    }
+    fences
+}
+
+/// Parse a fence line: the marker character, the length of the marker run
+/// and the trimmed info string. Fences may be indented by up to three spaces.
+fn fence_marker(line: &str) -> Option<(char, usize, &str)> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let rest = line.trim_start_matches(' ');
+    if line.len() - rest.len() > 3 {
+        return None;
+    }
+    let marker = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = rest.len() - rest.trim_start_matches(marker).len();
+    if len < 3 {
+        return None;
+    }
+    Some((marker, len, rest[len..].trim()))
+}
+
 impl Parser for MarkdownParser {
     fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
         // Try YAML frontmatter first (---)
@@ -151,6 +253,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn front_matter_block_and_offset() {
+        let content = "<!-- note -->\n+++\nname = \"test\"\n+++\n# Body\n";
+        let (front, offset) = front_matter(content).expect("front matter");
+        assert_eq!(front, "name = \"test\"");
+        assert_eq!(&content[offset..offset + front.len()], front);
+        assert_eq!(front_matter("# Body\n---\na: 1\n---\n"), None);
+    }
+
+    #[test]
+    fn finds_code_fences_after_front_matter() {
+        let content = "---\ntitle: x\n---\n# Config\n\n```yaml schema=https://example.com/s.json\nname: a\n```\n\n~~~~json\n{\"a\": 1}\n```\n~~~~\n\n```\nunclosed\n";
+        let fences = code_fences(content);
+        assert_eq!(fences.len(), 2);
+        assert_eq!(fences[0].lang, "yaml");
+        assert_eq!(fences[0].schema, Some("https://example.com/s.json"));
+        assert_eq!(fences[0].body, "name: a\n");
+        assert_eq!(&content[fences[0].offset..][..7], "name: a");
+        assert_eq!(fences[0].line_number, 6);
+        assert_eq!(fences[1].lang, "json");
+        assert_eq!(fences[1].schema, None);
+        assert_eq!(fences[1].body, "{\"a\": 1}\n```\n");
+        assert_eq!(fences[1].line_number, 10);
+    }
+
+    #[test]
+    fn fence_languages_map_to_formats() {
+        assert_eq!(fence_format("YAML"), Some(FileFormat::Yaml));
+        assert_eq!(fence_format("yml"), Some(FileFormat::Yaml));
+        assert_eq!(fence_format("json5"), Some(FileFormat::Json5));
+        assert_eq!(fence_format("rust"), None);
+    }
+
     #[test]
     fn parse_toml_frontmatter() -> anyhow::Result<()> {
         let content = "+++\nname = \"test\"\n+++\n# Body\n";
@@ -1,24 +1,38 @@
+mod evaluate;
+#[cfg(feature = "hcl")]
+mod hcl_parser;
 mod json;
 mod json5;
 mod jsonc;
 pub mod jsonl;
-mod markdown;
+pub mod markdown;
 mod toml_parser;
-mod yaml;
+#[cfg(feature = "xml")]
+mod xml;
+pub mod yaml;
 
 use std::path::Path;
 
+#[cfg(not(all(feature = "xml", feature = "hcl")))]
+use miette::NamedSource;
 use schema_catalog::FileFormat;
 use serde_json::Value;
 
 use lintel_diagnostics::LintelDiagnostic;
 
+pub use self::evaluate::{CueParser, JsonnetParser};
+#[cfg(feature = "hcl")]
+pub use self::hcl_parser::HclParser;
 pub use self::json::JsonParser;
+#[cfg(any(feature = "native", test))]
+pub(crate) use self::json::parse_outline;
 pub use self::json5::Json5Parser;
 pub use self::jsonc::JsoncParser;
 pub use self::jsonl::JsonlParser;
 pub use self::markdown::MarkdownParser;
 pub use self::toml_parser::TomlParser;
+#[cfg(feature = "xml")]
+pub use self::xml::XmlParser;
 pub use self::yaml::YamlParser;
 
 /// Parse file content into a `serde_json::Value`.
@@ -70,6 +84,14 @@ pub fn detect_format(path: &Path) -> Option<FileFormat> {
         Some("json" | "jsonc") => Some(FileFormat::Jsonc),
         Some("toml") => Some(FileFormat::Toml),
         Some("md" | "mdx") => Some(FileFormat::Markdown),
+        #[cfg(feature = "xml")]
+        Some("xml") => Some(FileFormat::Xml),
+        #[cfg(feature = "hcl")]
+        Some("tf" | "hcl") => Some(FileFormat::Hcl),
+        #[cfg(feature = "jsonnet")]
+        Some("jsonnet") => Some(FileFormat::Jsonnet),
+        #[cfg(feature = "cue")]
+        Some("cue") => Some(FileFormat::Cue),
         _ => None,
     }
 }
@@ -84,9 +106,64 @@ pub fn parser_for(format: FileFormat) -> Box<dyn Parser> {
         FileFormat::Toml => Box::new(TomlParser),
         FileFormat::Yaml => Box::new(YamlParser),
         FileFormat::Markdown => Box::new(MarkdownParser),
+        #[cfg(feature = "xml")]
+        FileFormat::Xml => Box::new(XmlParser),
+        #[cfg(not(feature = "xml"))]
+        FileFormat::Xml => Box::new(Unsupported {
+            format: "XML",
+            feature: "xml",
+        }),
+        #[cfg(feature = "hcl")]
+        FileFormat::Hcl => Box::new(HclParser),
+        #[cfg(not(feature = "hcl"))]
+        FileFormat::Hcl => Box::new(Unsupported {
+            format: "HCL",
+            feature: "hcl",
+        }),
+        FileFormat::Jsonnet => Box::new(JsonnetParser),
+        FileFormat::Cue => Box::new(CueParser),
     }
 }
 
+/// A format left out of this build: parsing fails, naming the cargo feature
+/// that adds it.
+#[cfg(not(all(feature = "xml", feature = "hcl")))]
+struct Unsupported {
+    format: &'static str,
+    feature: &'static str,
+}
+
+#[cfg(not(all(feature = "xml", feature = "hcl")))]
+impl Parser for Unsupported {
+    fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+        Err(LintelDiagnostic::Parse {
+            src: NamedSource::new(file_name, content.to_string()),
+            span: 0.into(),
+            message: format!(
+                "{} files need lintel built with the `{}` feature",
+                self.format, self.feature
+            ),
+        })
+    }
+}
+
+/// Try parsing content with each known format, returning the first success.
+///
+/// JSONC is tried first (superset of JSON, handles comments), then YAML and
+/// TOML which cover the most common config formats, followed by the rest.
+pub fn try_parse_all(content: &str, file_name: &str) -> Option<(FileFormat, Value)> {
+    use FileFormat::{Json, Json5, Jsonc, Markdown, Toml, Yaml};
+    const FORMATS: [FileFormat; 6] = [Jsonc, Yaml, Toml, Json, Json5, Markdown];
+
+    for fmt in FORMATS {
+        let parser = parser_for(fmt);
+        if let Ok(val) = parser.parse(content, file_name) {
+            return Some((fmt, val));
+        }
+    }
+    None
+}
+
 /// Insert `"$schema": "URL"` as the first property after `{` in a JSON object.
 ///
 /// Uses string manipulation (not parse+reserialize) to preserve formatting.
@@ -290,6 +367,22 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "xml")]
+    fn detect_format_xml() {
+        assert_eq!(detect_format(Path::new("pom.xml")), Some(FileFormat::Xml));
+    }
+
+    #[test]
+    #[cfg(feature = "hcl")]
+    fn detect_format_hcl() {
+        assert_eq!(detect_format(Path::new("main.tf")), Some(FileFormat::Hcl));
+        assert_eq!(
+            detect_format(Path::new("terragrunt.hcl")),
+            Some(FileFormat::Hcl)
+        );
+    }
+
     #[test]
     fn detect_format_unknown_returns_none() {
         assert_eq!(detect_format(Path::new("foo.txt")), None);
@@ -449,6 +542,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn json_outline_keeps_top_level_scalars() -> anyhow::Result<()> {
+        let outline = parse_outline(
+            r#"{"$schema":"s.json","items":[{"a":1}],"kind":"Pod","n":1.5,"ok":true}"#,
+            "test.json",
+        )?;
+        assert_eq!(
+            outline,
+            serde_json::json!({"$schema": "s.json", "kind": "Pod", "n": 1.5, "ok": true})
+        );
+        assert_eq!(
+            parse_outline("[1, [2]]", "test.json")?,
+            serde_json::json!([])
+        );
+        assert!(parse_outline(r#"{"a": [1,]}"#, "test.json").is_err());
+        assert!(parse_outline("{} {}", "test.json").is_err());
+        Ok(())
+    }
+
     #[test]
     fn parser_for_yaml_parses() -> anyhow::Result<()> {
         let p = parser_for(FileFormat::Yaml);
@@ -480,4 +592,25 @@ mod tests {
         assert_eq!(val, serde_json::json!({"key": "value"}));
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "hcl")]
+    fn parser_for_hcl_parses() -> anyhow::Result<()> {
+        let p = parser_for(FileFormat::Hcl);
+        let val = p.parse(
+            "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n  tags = { team = \"infra\" }\n}\n",
+            "main.tf",
+        )?;
+        assert_eq!(
+            val,
+            serde_json::json!({
+                "resource": {
+                    "aws_s3_bucket": {
+                        "logs": { "bucket": "logs", "tags": { "team": "infra" } }
+                    }
+                }
+            })
+        );
+        Ok(())
+    }
 }
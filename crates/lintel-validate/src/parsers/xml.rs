@@ -0,0 +1,147 @@
+//! XML documents, mapped to JSON for validation.
+//!
+//! The mapping follows the common `xmltodict` conventions:
+//!
+//! - the document is an object with the root element's name as its only key;
+//! - attributes become `@name` properties and text content `#text`;
+//! - an element with neither attributes nor child elements is its text, or
+//!   `null` when empty;
+//! - child elements become properties, and repeated elements an array in
+//!   document order.
+//!
+//! Names keep their namespace prefix (`xsi:schemaLocation`). Namespace
+//! declarations, comments and processing instructions are dropped, and all
+//! values are strings.
+
+use miette::NamedSource;
+use serde_json::{Map, Value};
+
+use lintel_diagnostics::LintelDiagnostic;
+
+use super::Parser;
+
+pub struct XmlParser;
+
+impl Parser for XmlParser {
+    fn parse(&self, content: &str, file_name: &str) -> Result<Value, LintelDiagnostic> {
+        let options = roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..roxmltree::ParsingOptions::default()
+        };
+        let doc = roxmltree::Document::parse_with_options(content, options).map_err(|e| {
+            let pos = e.pos();
+            LintelDiagnostic::Parse {
+                src: NamedSource::new(file_name, content.to_string()),
+                span: super::line_col_to_offset(content, pos.row as usize, pos.col as usize).into(),
+                message: e.to_string(),
+            }
+        })?;
+        let root = doc.root_element();
+        let mut map = Map::new();
+        let name = qualified_name(root, root.tag_name().namespace(), root.tag_name().name());
+        map.insert(name, element_value(root));
+        Ok(Value::Object(map))
+    }
+}
+
+/// The name of an element or attribute with its namespace prefix, if any.
+fn qualified_name(node: roxmltree::Node<'_, '_>, namespace: Option<&str>, name: &str) -> String {
+    match namespace.and_then(|ns| node.lookup_prefix(ns)) {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}:{name}"),
+        _ => name.to_string(),
+    }
+}
+
+fn element_value(node: roxmltree::Node<'_, '_>) -> Value {
+    let mut map = Map::new();
+    for attr in node.attributes() {
+        map.insert(
+            format!("@{}", qualified_name(node, attr.namespace(), attr.name())),
+            Value::String(attr.value().to_string()),
+        );
+    }
+
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+        } else if child.is_element() {
+            let tag = child.tag_name();
+            let name = qualified_name(child, tag.namespace(), tag.name());
+            let value = element_value(child);
+            match map.get_mut(&name) {
+                Some(Value::Array(items)) => items.push(value),
+                Some(existing) => {
+                    let first = existing.take();
+                    *existing = Value::Array(vec![first, value]);
+                }
+                None => {
+                    map.insert(name, value);
+                }
+            }
+        }
+    }
+
+    let text = text.trim();
+    if map.is_empty() {
+        return if text.is_empty() {
+            Value::Null
+        } else {
+            Value::String(text.to_string())
+        };
+    }
+    if !text.is_empty() {
+        map.insert("#text".into(), Value::String(text.to_string()));
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn maps_elements_attributes_and_text() -> anyhow::Result<()> {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- build file -->
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+  <artifactId>demo</artifactId>
+  <packaging/>
+  <modules>
+    <module>core</module>
+    <module>cli</module>
+  </modules>
+  <name lang="en">Demo <![CDATA[& friends]]></name>
+</project>
+"#;
+        let value = XmlParser.parse(content, "pom.xml")?;
+        assert_eq!(
+            value,
+            json!({
+                "project": {
+                    "@xsi:schemaLocation": "http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd",
+                    "modelVersion": "4.0.0",
+                    "artifactId": "demo",
+                    "packaging": null,
+                    "modules": { "module": ["core", "cli"] },
+                    "name": { "@lang": "en", "#text": "Demo & friends" }
+                }
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_parse_errors_at_their_position() {
+        let content = "<project>\n  <name>demo</nam>\n</project>\n";
+        let Err(LintelDiagnostic::Parse { span, .. }) = XmlParser.parse(content, "pom.xml") else {
+            panic!("expected a parse error");
+        };
+        // Line 2, where the mismatched closing tag is.
+        assert_eq!(content[..span.offset()].matches('\n').count(), 1);
+    }
+}
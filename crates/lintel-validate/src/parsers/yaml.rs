@@ -81,11 +81,54 @@ fn strip_yaml_modeline(content: &str) -> String {
     content.to_string()
 }
 
+/// Whether `line` is a `---` document start marker.
+fn is_document_start(line: &str) -> bool {
+    line.strip_prefix("---")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '\r', '\n']))
+}
+
+/// Split a multi-document YAML stream into its documents, as byte offsets
+/// and text. A document's text starts at its `---` marker and keeps the
+/// comments above it when nothing but comments precedes them, so each
+/// document carries its own modeline. Documents without content are left
+/// out.
+pub fn split_documents(content: &str) -> Vec<(usize, &str)> {
+    let mut documents = Vec::new();
+    let mut start = 0;
+    let mut has_marker = false;
+    let mut has_content = false;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if is_document_start(line) {
+            if has_content {
+                documents.push((start, &content[start..offset]));
+            }
+            // Comments before the first marker lead its document; an empty
+            // document is dropped.
+            if has_content || has_marker {
+                start = offset;
+            }
+            has_marker = true;
+            has_content = false;
+        }
+        let trimmed = line.trim();
+        has_content |= !(trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed == "..."
+            || is_document_start(line));
+        offset += line.len();
+    }
+    if has_content {
+        documents.push((start, &content[start..]));
+    }
+    documents
+}
+
 /// Extract schema URI from `# yaml-language-server: $schema=URL` comment.
 fn extract_yaml_modeline_schema(content: &str) -> Option<String> {
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.is_empty() {
+        if trimmed.is_empty() || is_document_start(line) {
             continue;
         }
         if !trimmed.starts_with('#') {
@@ -104,3 +147,44 @@ fn extract_yaml_modeline_schema(content: &str) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_documents_with_their_modelines() {
+        let content = "\
+# yaml-language-server: $schema=a.json
+---
+kind: A
+---
+# yaml-language-server: $schema=b.json
+kind: B
+--- # trailing
+...
+---
+kind: C
+";
+        let documents = split_documents(content);
+        let texts: Vec<&str> = documents.iter().map(|(_, text)| *text).collect();
+        assert_eq!(
+            texts,
+            [
+                "# yaml-language-server: $schema=a.json\n---\nkind: A\n",
+                "---\n# yaml-language-server: $schema=b.json\nkind: B\n",
+                "---\nkind: C\n",
+            ]
+        );
+        assert_eq!(documents[1].0, content.find("---\n#").unwrap_or_default());
+        let modelines: Vec<_> = texts
+            .iter()
+            .map(|text| extract_yaml_modeline_schema(text))
+            .collect();
+        assert_eq!(
+            modelines,
+            [Some("a.json".to_string()), Some("b.json".to_string()), None]
+        );
+        assert_eq!(split_documents("a: 1\n---\n").len(), 1);
+    }
+}
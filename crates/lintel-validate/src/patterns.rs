@@ -0,0 +1,117 @@
+//! `pattern` regexes compiled once per process.
+//!
+//! The `jsonschema` crate caches compiled regexes only while it compiles
+//! one schema, and schemas from a catalog repeat the same patterns many
+//! times over. The `pattern` keyword is replaced by one that compiles each
+//! distinct pattern into a validator of its own once and shares it across
+//! every schema, so errors, regex dialect and backtracking limits stay
+//! those of the crate.
+//!
+//! `patternProperties` regexes are also compiled by `additionalProperties`
+//! and `unevaluatedProperties`, which a custom keyword cannot reach, so they
+//! keep the per-schema cache.
+
+use alloc::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use jsonschema::{Keyword, ValidationError, Validator};
+use serde_json::Value;
+
+static PATTERNS: LazyLock<Mutex<HashMap<String, Arc<Validator>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Install the shared `pattern` keyword on `options`.
+pub(crate) fn apply<R>(
+    options: jsonschema::ValidationOptions<R>,
+) -> jsonschema::ValidationOptions<R> {
+    options.with_keyword("pattern", |_, value, _| {
+        Ok(Box::new(Pattern(compiled(value)?)))
+    })
+}
+
+/// The validator of `{"pattern": value}`, compiled on first use.
+fn compiled(value: &Value) -> Result<Arc<Validator>, ValidationError<'static>> {
+    let Value::String(pattern) = value else {
+        // Let the crate report the schema error
+        return jsonschema::validator_for(&serde_json::json!({ "pattern": value })).map(Arc::new);
+    };
+    if let Some(validator) = PATTERNS
+        .lock()
+        .expect("pattern cache poisoned")
+        .get(pattern)
+    {
+        return Ok(Arc::clone(validator));
+    }
+    let validator = Arc::new(jsonschema::validator_for(
+        &serde_json::json!({ "pattern": pattern }),
+    )?);
+    PATTERNS
+        .lock()
+        .expect("pattern cache poisoned")
+        .insert(pattern.clone(), Arc::clone(&validator));
+    Ok(validator)
+}
+
+struct Pattern(Arc<Validator>);
+
+impl Keyword for Pattern {
+    fn validate<'i>(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        self.0.validate(instance)
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        self.0.is_valid(instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn schemas_share_compiled_patterns() {
+        let pattern = "^shared-[0-9]+$";
+        let schema = json!({
+            "properties": { "name": { "pattern": pattern } }
+        });
+        let first = apply(jsonschema::options())
+            .build(&schema)
+            .expect("schema compiles");
+        let cached = Arc::clone(&PATTERNS.lock().expect("lock")[pattern]);
+        let second = apply(jsonschema::options())
+            .build(&json!({ "items": { "pattern": pattern } }))
+            .expect("schema compiles");
+        assert!(Arc::ptr_eq(
+            &cached,
+            &PATTERNS.lock().expect("lock")[pattern]
+        ));
+
+        assert!(first.is_valid(&json!({ "name": "shared-12" })));
+        let instance = json!({ "name": "other" });
+        let error = first
+            .validate(&instance)
+            .expect_err("pattern does not match");
+        assert!(matches!(
+            error.kind(),
+            jsonschema::error::ValidationErrorKind::Pattern { pattern: p } if p == pattern
+        ));
+        assert_eq!(error.instance_path().to_string(), "/name");
+        assert!(!second.is_valid(&json!(["shared-1", "x"])));
+    }
+
+    #[test]
+    fn invalid_patterns_fail_to_compile() {
+        assert!(
+            apply(jsonschema::options())
+                .build(&json!({ "pattern": "(" }))
+                .is_err()
+        );
+        assert!(
+            apply(jsonschema::options())
+                .build(&json!({ "pattern": 5 }))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! External plugins declared in `[plugins]`.
+//!
+//! A plugin is a program that speaks JSON over stdio, or a sandboxed
+//! WebAssembly module that speaks it through its memory. For each hook it
+//! enables, Lintel runs it once with every file its `files` globs match and
+//! sends it a request: a program reads it from stdin and writes its response
+//! to stdout, with output on stderr passed through.
+//!
+//! A `resolve` request lists each file's `path`, its `content` and, when it
+//! parses, its `document`; the response maps paths to schema URIs for the
+//! files the plugin knows:
+//!
+//! ```json
+//! {"version": 1, "hook": "resolve", "files": [{"path": "k8s/app.yaml", "content": "...", "document": {}}]}
+//! {"schemas": {"k8s/app.yaml": "https://schemas.example.com/app.json"}}
+//! ```
+//!
+//! A `validate` request lists each validated document's `id`, the `path` of
+//! its file, its `schema` and the `document`. A file can hold several
+//! documents (JSON Lines, multi-document YAML, Markdown code fences), so the
+//! response's diagnostics name the document by `id`, each with an optional
+//! JSON pointer into it, a `rule` and a `severity` (`error` or `warning`):
+//!
+//! ```json
+//! {"version": 1, "hook": "validate", "files": [{"id": 0, "path": "k8s/app.yaml", "schema": "...", "document": {}}]}
+//! {"diagnostics": [{"id": 0, "instance_path": "/spec/replicas", "message": "...", "rule": "max-replicas", "severity": "warning"}]}
+//! ```
+//!
+//! Commands run as child processes of the async runtime, killed once they
+//! pass their `timeout`.
+
+use alloc::borrow::Cow;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{Context, Result, bail};
+use lintel_config::{Config, Plugin};
+use lintel_diagnostics::find_instance_path_span;
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::parsers;
+
+/// Version of the request and response format.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// How long a plugin command may run when its `timeout` is not set.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Serialize)]
+struct Request<'a> {
+    version: u32,
+    hook: &'static str,
+    files: Vec<RequestFile<'a>>,
+}
+
+#[derive(Serialize)]
+struct RequestFile<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    path: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<&'a str>,
+    document: Cow<'a, Value>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Response {
+    schemas: HashMap<String, String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    /// The `id` of the document in the request.
+    id: usize,
+    #[serde(default)]
+    instance_path: String,
+    message: String,
+    #[serde(default)]
+    rule: Option<String>,
+    #[serde(default)]
+    severity: DiagnosticSeverity,
+}
+
+#[derive(Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DiagnosticSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+/// A validated document sent to `validate` plugins.
+pub(crate) struct PluginFile<'a> {
+    /// The file the document is in, which `files` globs match.
+    pub(crate) path: &'a str,
+    pub(crate) schema: &'a str,
+    pub(crate) document: &'a Value,
+    /// The document's source and its pointer within it, used like in
+    /// [`convert_error`](crate::document::convert_error) to locate
+    /// diagnostics.
+    pub(crate) content: &'a str,
+    pub(crate) instance_prefix: &'a str,
+}
+
+/// Whether a `validate` plugin is sent the file at `path`.
+pub(crate) fn validates(config: &Config, path: &str) -> bool {
+    plugins_with(config, |p| p.validate)
+        .iter()
+        .any(|(_, plugin)| plugin.matches(path))
+}
+
+/// Plugins with `hook` enabled, in name order.
+fn plugins_with(config: &Config, hook: fn(&Plugin) -> bool) -> Vec<(&str, &Plugin)> {
+    let mut plugins: Vec<_> = config
+        .plugins
+        .iter()
+        .filter(|(_, plugin)| hook(plugin))
+        .map(|(name, plugin)| (name.as_str(), plugin))
+        .collect();
+    plugins.sort_by_key(|(name, _)| *name);
+    plugins
+}
+
+/// The schema URI `resolve` plugins name for each file, by path. The first
+/// plugin to name a file's schema wins.
+///
+/// # Errors
+///
+/// Returns an error if a plugin cannot be run, fails, or writes a response
+/// that is not valid.
+pub(crate) async fn resolve_schemas(
+    config: &Config,
+    config_dir: &Path,
+    files: &[(PathBuf, String)],
+) -> Result<HashMap<String, String>> {
+    let mut schemas = HashMap::new();
+    let paths: Vec<String> = files
+        .iter()
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    for (name, plugin) in plugins_with(config, |p| p.resolve) {
+        let request_files: Vec<RequestFile<'_>> = files
+            .iter()
+            .zip(&paths)
+            .filter(|(_, path)| plugin.matches(path))
+            .map(|((path, content), path_str)| RequestFile {
+                id: None,
+                path: path_str,
+                content: Some(content.as_str()),
+                schema: None,
+                document: Cow::Owned(
+                    parsers::detect_format(path)
+                        .and_then(|format| {
+                            parsers::parser_for(format).parse(content, path_str).ok()
+                        })
+                        .unwrap_or(Value::Null),
+                ),
+            })
+            .collect();
+        if request_files.is_empty() {
+            continue;
+        }
+        let response = call(name, plugin, config_dir, "resolve", request_files).await?;
+        for (path, schema) in response.schemas {
+            if paths.contains(&path) {
+                schemas.entry(path).or_insert(schema);
+            }
+        }
+    }
+    Ok(schemas)
+}
+
+/// The diagnostics of `validate` plugins, each with the index of the
+/// document in `files` it belongs to, which is also the document's `id` in
+/// the request.
+///
+/// # Errors
+///
+/// Returns an error if a plugin cannot be run, fails, or writes a response
+/// that is not valid.
+pub(crate) async fn validate(
+    config: &Config,
+    config_dir: &Path,
+    files: &[PluginFile<'_>],
+) -> Result<Vec<(usize, ValidationError)>> {
+    let mut errors = Vec::new();
+    for (name, plugin) in plugins_with(config, |p| p.validate) {
+        let request_files: Vec<RequestFile<'_>> = files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| plugin.matches(file.path))
+            .map(|(id, file)| RequestFile {
+                id: Some(id),
+                path: file.path,
+                content: None,
+                schema: Some(file.schema),
+                document: Cow::Borrowed(file.document),
+            })
+            .collect();
+        if request_files.is_empty() {
+            continue;
+        }
+        let response = call(name, plugin, config_dir, "validate", request_files).await?;
+        for diagnostic in response.diagnostics {
+            let index = diagnostic.id;
+            let Some(file) = files.get(index).filter(|file| plugin.matches(file.path)) else {
+                tracing::warn!(
+                    plugin = name,
+                    id = index,
+                    "plugin reported a document it was not sent"
+                );
+                continue;
+            };
+            let instance_path = format!("{}{}", file.instance_prefix, diagnostic.instance_path);
+            let schema_path = match &diagnostic.rule {
+                Some(rule) => format!("/plugins/{name}/{rule}"),
+                None => format!("/plugins/{name}"),
+            };
+            errors.push((
+                index,
+                ValidationError {
+                    span: find_instance_path_span(file.content, &instance_path),
+                    instance_path,
+                    schema_path,
+                    kind: ValidationErrorKind::Plugin {
+                        message: diagnostic.message,
+                        warning: diagnostic.severity == DiagnosticSeverity::Warning,
+                    },
+                },
+            ));
+        }
+    }
+    Ok(errors)
+}
+
+/// Run plugin `name` with a request for `hook` and read its response.
+#[allow(clippy::too_many_arguments)]
+async fn call(
+    name: &str,
+    plugin: &Plugin,
+    config_dir: &Path,
+    hook: &'static str,
+    files: Vec<RequestFile<'_>>,
+) -> Result<Response> {
+    let input = serde_json::to_vec(&Request {
+        version: PROTOCOL_VERSION,
+        hook,
+        files,
+    })?;
+    tracing::debug!(plugin = name, hook, "running plugin");
+    let timeout = plugin.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let output = match &plugin.wasm {
+        Some(_) if !plugin.command.is_empty() => {
+            bail!("plugin `{name}` sets both `command` and `wasm`")
+        }
+        Some(module) => run_wasm(name, &config_dir.join(module), hook, input, timeout).await?,
+        None => run_command(name, plugin, config_dir, input, timeout).await?,
+    };
+    serde_json::from_slice(&output)
+        .with_context(|| format!("plugin `{name}` wrote an invalid {hook} response"))
+}
+
+/// Write `input` to the stdin of plugin `name`'s command and return its
+/// stdout. The command is killed once it runs longer than `timeout`.
+#[allow(clippy::too_many_arguments)]
+async fn run_command(
+    name: &str,
+    plugin: &Plugin,
+    config_dir: &Path,
+    input: Vec<u8>,
+    timeout: Duration,
+) -> Result<Vec<u8>> {
+    let Some((program, args)) = plugin.command.split_first() else {
+        bail!("plugin `{name}` has an empty command");
+    };
+    // A program named by a relative path is relative to lintel.toml, not
+    // looked up on PATH.
+    let program = if Path::new(program).components().count() > 1 {
+        config_dir.join(program)
+    } else {
+        PathBuf::from(program)
+    };
+
+    let mut child = tokio::process::Command::new(&program)
+        .args(args)
+        .current_dir(config_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to run plugin `{name}` ({})", program.display()))?;
+    // Write and read at the same time, so a plugin that answers while still
+    // reading cannot deadlock on a full pipe.
+    let mut stdin = child.stdin.take().context("plugin stdin is not piped")?;
+    let write = async move {
+        // A plugin may exit without reading its whole request; only its
+        // exit status counts.
+        let _ = stdin.write_all(&input).await;
+    };
+    let mut stdout = child.stdout.take().context("plugin stdout is not piped")?;
+    let read = async {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).await.map(|_| output)
+    };
+
+    // Reading ends once every process holding stdout open has exited, the
+    // plugin's own children too, so the timeout covers it as well.
+    let finished =
+        tokio::time::timeout(timeout, async { tokio::join!(write, read, child.wait()) }).await;
+    let Ok(((), output, status)) = finished else {
+        let _ = child.kill().await;
+        bail!("plugin `{name}` timed out after {timeout:?}");
+    };
+    let status = status.with_context(|| format!("failed to run plugin `{name}`"))?;
+    if !status.success() {
+        bail!("plugin `{name}` failed: {status}");
+    }
+    output.with_context(|| format!("failed to read the output of plugin `{name}`"))
+}
+
+/// Run plugin `name`'s module on the blocking thread pool. Its fuel runs out
+/// around `timeout` on current hardware; past `timeout` it fails either way.
+#[cfg(feature = "wasm-plugins")]
+#[allow(clippy::too_many_arguments)]
+async fn run_wasm(
+    name: &str,
+    module: &Path,
+    hook: &'static str,
+    input: Vec<u8>,
+    timeout: Duration,
+) -> Result<Vec<u8>> {
+    let task = tokio::task::spawn_blocking({
+        let (name, module) = (name.to_string(), module.to_path_buf());
+        move || crate::wasm_plugin::call(&name, &module, hook, &input, timeout)
+    });
+    match tokio::time::timeout(timeout, task).await {
+        Ok(output) => output?,
+        Err(_) => bail!("plugin `{name}` timed out after {timeout:?}"),
+    }
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+#[allow(clippy::unused_async, clippy::too_many_arguments)]
+async fn run_wasm(
+    name: &str,
+    _module: &Path,
+    _hook: &'static str,
+    _input: Vec<u8>,
+    _timeout: Duration,
+) -> Result<Vec<u8>> {
+    bail!("plugin `{name}` is a WebAssembly module, but lintel was built without `wasm-plugins`")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A config with one plugin: a shell script that saves its request to
+    /// `request.json` and prints `response`.
+    fn config_with_plugin(dir: &Path, settings: &str, response: &str) -> anyhow::Result<Config> {
+        fs::write(
+            dir.join("plugin.sh"),
+            format!("cat > request.json\necho '{response}'\n"),
+        )?;
+        Ok(toml::from_str(&format!(
+            "[plugins.test]\ncommand = [\"sh\", \"plugin.sh\"]\n{settings}"
+        ))?)
+    }
+
+    #[tokio::test]
+    async fn resolve_sends_matching_files_and_reads_their_schemas() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let config = config_with_plugin(
+            tmp.path(),
+            "files = [\"k8s/**\"]\nresolve = true\n",
+            r#"{"schemas": {"k8s/app.yaml": "https://example.com/app.json", "other.yaml": "x"}}"#,
+        )?;
+        let files = vec![
+            (PathBuf::from("k8s/app.yaml"), "kind: App\n".to_string()),
+            (PathBuf::from("values.yaml"), "a: 1\n".to_string()),
+        ];
+
+        let schemas = resolve_schemas(&config, tmp.path(), &files).await?;
+        assert_eq!(
+            schemas,
+            HashMap::from([(
+                "k8s/app.yaml".to_string(),
+                "https://example.com/app.json".to_string()
+            )])
+        );
+        let request: Value =
+            serde_json::from_str(&fs::read_to_string(tmp.path().join("request.json"))?)?;
+        assert_eq!(
+            request,
+            serde_json::json!({
+                "version": 1,
+                "hook": "resolve",
+                "files": [{"path": "k8s/app.yaml", "content": "kind: App\n", "document": {"kind": "App"}}]
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_diagnostics_become_validation_errors() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let config = config_with_plugin(
+            tmp.path(),
+            "validate = true\n",
+            r#"{"diagnostics": [{"id": 1, "instance_path": "/replicas", "message": "too many", "rule": "max-replicas", "severity": "warning"}, {"id": 7, "message": "unknown"}]}"#,
+        )?;
+        // Two documents of one file, such as two lines of a JSON Lines file
+        let first = serde_json::json!({"replicas": 1});
+        let second = serde_json::json!({"replicas": 50});
+        let files = [
+            PluginFile {
+                path: "app.jsonl",
+                schema: "https://example.com/app.json",
+                document: &first,
+                content: "{\"replicas\": 1}",
+                instance_prefix: "",
+            },
+            PluginFile {
+                path: "app.jsonl",
+                schema: "https://example.com/app.json",
+                document: &second,
+                content: "{\"replicas\": 50}",
+                instance_prefix: "",
+            },
+        ];
+
+        let errors = validate(&config, tmp.path(), &files).await?;
+        assert_eq!(errors.len(), 1);
+        let (index, error) = &errors[0];
+        assert_eq!(*index, 1);
+        assert_eq!(error.instance_path, "/replicas");
+        assert_eq!(error.schema_path, "/plugins/test/max-replicas");
+        assert_eq!(
+            error.kind,
+            ValidationErrorKind::Plugin {
+                message: "too many".to_string(),
+                warning: true,
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failing_plugins_are_errors() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let config: Config = toml::from_str(
+            "[plugins.broken]\ncommand = [\"sh\", \"-c\", \"exit 3\"]\nresolve = true\n",
+        )?;
+        let files = vec![(PathBuf::from("a.json"), "{}".to_string())];
+        let error = resolve_schemas(&config, tmp.path(), &files)
+            .await
+            .expect_err("a failing plugin should be an error");
+        assert!(error.to_string().contains("plugin `broken` failed"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slow_plugins_time_out() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let config: Config = toml::from_str(
+            "[plugins.slow]\ncommand = [\"sleep\", \"10\"]\nresolve = true\ntimeout = \"100ms\"\n",
+        )?;
+        let files = vec![(PathBuf::from("a.json"), "{}".to_string())];
+        let error = resolve_schemas(&config, tmp.path(), &files)
+            .await
+            .expect_err("a plugin past its timeout should be an error");
+        assert!(
+            error.to_string().contains("plugin `slow` timed out"),
+            "{error}"
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
+use alloc::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
 use lintel_schema_cache::SchemaCache;
-use schema_catalog::Catalog;
+use schema_catalog::{Catalog, SchemaEntry};
+use serde_json::Value;
 
 /// The default Lintel catalog registry (always fetched unless `--no-catalog`).
 pub const DEFAULT_REGISTRY: &str = "https://catalog.lintel.tools/catalog.json";
@@ -10,7 +15,8 @@ pub const DEFAULT_REGISTRY: &str = "https://catalog.lintel.tools/catalog.json";
 /// - `github:org/repo`        → tries `main` then `master` branch
 /// - `github:org/repo/branch` → uses the specified branch
 ///
-/// Plain `http://` and `https://` URLs are returned as-is.
+/// Plain `http://` and `https://` URLs are returned as-is. Local registries
+/// (see [`local_path`]) are not URLs and are handled by [`load_local`].
 ///
 /// Returns one or more URLs to try in order.
 pub fn resolve_urls(url: &str) -> Vec<String> {
@@ -61,6 +67,125 @@ pub async fn fetch(
     Err(last_err.unwrap_or_else(|| "no URLs to try".into()))
 }
 
+/// The local path of a `//`-prefixed registry, resolved relative to the
+/// directory containing `lintel.toml`. Returns `None` for remote registries.
+pub fn local_path(url: &str, config_dir: &Path) -> Option<PathBuf> {
+    url.strip_prefix("//").map(|rest| config_dir.join(rest))
+}
+
+/// Load a local registry: either a catalog file, or a directory of schemas
+/// from which a catalog is built in memory. Relative schema URLs in a
+/// catalog file are resolved against the file's directory.
+///
+/// Every `*.json` file below a directory is treated as a schema and becomes
+/// a catalog entry named after its `title` (or file name), with file
+/// patterns from a top-level `fileMatch` keyword or `x-lintel.fileMatch`.
+/// Schemas that declare an `$id` are also registered in `cache` under that
+/// URI, so inline `$schema` declarations and `$ref`s using it resolve
+/// without fetching.
+///
+/// # Errors
+///
+/// Returns an error if the path cannot be read, or a catalog file (or any
+/// schema in the directory) is not valid JSON.
+pub fn load_local(
+    cache: &SchemaCache,
+    path: &Path,
+) -> Result<Catalog, Box<dyn core::error::Error + Send + Sync>> {
+    if !path.is_dir() {
+        let mut catalog = schema_catalog::parse_catalog(&fs::read_to_string(path)?)?;
+        // Relative schema URLs are relative to the catalog file.
+        let base = path.parent().unwrap_or(Path::new(""));
+        for entry in &mut catalog.schemas {
+            if !entry.url.contains("://") && Path::new(&entry.url).is_relative() {
+                entry.url = base.join(&entry.url).to_string_lossy().to_string();
+            }
+        }
+        return Ok(catalog);
+    }
+
+    let mut schemas = Vec::new();
+    for (file, value) in read_schema_dir(path)? {
+        if let Some(id) = value.get("$id").and_then(Value::as_str) {
+            cache.insert(id, value.clone());
+        }
+        schemas.push(schema_entry(&file, &value));
+    }
+    Ok(Catalog {
+        version: 1,
+        schemas,
+        ..Catalog::default()
+    })
+}
+
+/// Read and parse every `*.json` file below `dir`, in sorted path order.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be walked or a file cannot be
+/// read or is not valid JSON.
+pub fn read_schema_dir(
+    dir: &Path,
+) -> Result<Vec<(PathBuf, Value)>, Box<dyn core::error::Error + Send + Sync>> {
+    let mut files = Vec::new();
+    collect_json_files(dir, &mut files)?;
+    let mut schemas = Vec::with_capacity(files.len());
+    for file in files {
+        let content = fs::read_to_string(&file)?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("failed to parse schema {}: {e}", file.display()))?;
+        schemas.push((file, value));
+    }
+    Ok(schemas)
+}
+
+/// All `*.json` files below `dir`, in sorted order.
+fn collect_json_files(dir: &Path, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_json_files(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// A catalog entry for the schema in `file`, using its `title` (or file
+/// name), `description`, `$id` as the source URL, and `fileMatch` (or
+/// `x-lintel.fileMatch`) patterns. The entry's URL is the file path.
+pub fn schema_entry(file: &Path, schema: &Value) -> SchemaEntry {
+    let text = |key: &str| schema.get(key).and_then(Value::as_str).map(str::to_string);
+    let file_match = schema
+        .get("fileMatch")
+        .or_else(|| schema.get("x-lintel").and_then(|x| x.get("fileMatch")))
+        .and_then(Value::as_array)
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    SchemaEntry {
+        name: text("title").unwrap_or_else(|| {
+            file.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        }),
+        description: text("description").unwrap_or_default(),
+        url: file.to_string_lossy().to_string(),
+        source_url: text("$id"),
+        file_match,
+        versions: BTreeMap::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +226,50 @@ mod tests {
         assert_eq!(urls.len(), 1);
         assert_eq!(urls[0], "https://catalog.lintel.tools/catalog.json");
     }
+
+    #[test]
+    fn double_slash_registry_is_local() {
+        let dir = Path::new("/repo");
+        assert_eq!(
+            local_path("//schemas/catalog", dir),
+            Some(PathBuf::from("/repo/schemas/catalog"))
+        );
+        assert_eq!(local_path("github:org/repo", dir), None);
+        assert_eq!(local_path("https://example.com/catalog.json", dir), None);
+    }
+
+    #[test]
+    fn local_directory_builds_catalog() -> Result<(), Box<dyn core::error::Error + Send + Sync>> {
+        let tmp = tempfile::tempdir()?;
+        fs::create_dir(tmp.path().join("nested"))?;
+        fs::write(
+            tmp.path().join("deploy.json"),
+            r#"{"$id":"https://schemas.internal/deploy.json","title":"Deploy","description":"Deploy config","fileMatch":["deploy.yaml"]}"#,
+        )?;
+        fs::write(
+            tmp.path().join("nested/ci.json"),
+            r#"{"x-lintel":{"fileMatch":["ci/*.yml"]}}"#,
+        )?;
+        fs::write(tmp.path().join("notes.txt"), "not a schema")?;
+
+        let cache = SchemaCache::memory();
+        let catalog = load_local(&cache, tmp.path())?;
+        assert_eq!(catalog.schemas.len(), 2);
+        let deploy = &catalog.schemas[0];
+        assert_eq!(deploy.name, "Deploy");
+        assert_eq!(deploy.description, "Deploy config");
+        assert_eq!(deploy.file_match, ["deploy.yaml"]);
+        assert!(deploy.url.ends_with("deploy.json"));
+        let ci = &catalog.schemas[1];
+        assert_eq!(ci.name, "ci.json");
+        assert_eq!(ci.file_match, ["ci/*.yml"]);
+        assert!(cache.get("https://schemas.internal/deploy.json").is_some());
+
+        let compiled = schema_catalog::CompiledCatalog::compile(&catalog);
+        assert_eq!(
+            compiled.find_schema("ci/build.yml", "build.yml"),
+            Some(ci.url.as_str())
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,162 @@
+//! Strict instance checks, enabled with `--strict-instances`.
+//!
+//! Objects described by a schema with `properties` but no
+//! `additionalProperties` accept any other key, so a misspelled optional
+//! property passes validation silently. This finds such keys from the
+//! annotations of a validation run: a key is described when a `properties`,
+//! `patternProperties`, `additionalProperties` or `unevaluatedProperties`
+//! keyword that applies to its object evaluated it.
+
+use std::collections::{HashMap, HashSet};
+
+use lintel_diagnostics::find_instance_path_span;
+use lintel_validation_cache::{ValidationError, ValidationErrorKind};
+use serde_json::Value;
+
+use crate::document::keyword_pointer;
+
+/// An instance object that a schema with `properties` applies to.
+#[derive(Default)]
+struct Object<'a> {
+    /// Schema path of the first `properties` keyword that applies to it.
+    schema_path: Option<&'a str>,
+    /// Keys some keyword evaluated.
+    described: HashSet<&'a str>,
+    /// Whether a schema that applies to it decides on other keys, so every
+    /// key is described.
+    open: bool,
+}
+
+/// Keys of objects in `instance` that no schema describes, one error per
+/// key, from the `evaluation` of `instance` against `schema`.
+/// `instance_prefix` and `content` are used like in
+/// [`convert_error`](crate::document::convert_error).
+///
+/// Annotations of failing subschemas are dropped, so objects that fail
+/// validation are not checked.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn undescribed_properties(
+    evaluation: &jsonschema::Evaluation,
+    schema: &Value,
+    instance: &Value,
+    content: &str,
+    instance_prefix: &str,
+) -> Vec<ValidationError> {
+    let mut objects: HashMap<&str, Object<'_>> = HashMap::new();
+    for entry in evaluation.iter_annotations() {
+        let schema_location = keyword_pointer(entry.schema_location);
+        let Some((parent, keyword)) = schema_location.rsplit_once('/') else {
+            continue;
+        };
+        let object = objects.entry(entry.instance_location.as_str()).or_default();
+        match keyword {
+            "properties" | "patternProperties" => {
+                // `additionalProperties: true` produces no annotation, so
+                // look at the schema itself.
+                let decided = schema.pointer(parent).is_none_or(|parent| {
+                    parent.get("additionalProperties").is_some()
+                        || parent.get("unevaluatedProperties").is_some()
+                });
+                object.open |= decided;
+                if keyword == "properties" {
+                    object.schema_path.get_or_insert(schema_location);
+                }
+            }
+            "additionalProperties" | "unevaluatedProperties" => object.open = true,
+            _ => continue,
+        }
+        let keys = entry.annotations.value().as_array().into_iter().flatten();
+        object.described.extend(keys.filter_map(Value::as_str));
+    }
+
+    let mut errors = Vec::new();
+    for (location, object) in &objects {
+        let Some(schema_path) = object.schema_path.filter(|_| !object.open) else {
+            continue;
+        };
+        let Some(Value::Object(map)) = instance.pointer(location) else {
+            continue;
+        };
+        for key in map.keys() {
+            // `$schema` picks the document's schema for lintel itself
+            if object.described.contains(key.as_str()) || (location.is_empty() && key == "$schema")
+            {
+                continue;
+            }
+            let instance_path = format!("{instance_prefix}{location}/{key}");
+            errors.push(ValidationError {
+                span: find_instance_path_span(content, &instance_path),
+                instance_path,
+                schema_path: schema_path.to_string(),
+                kind: ValidationErrorKind::UndescribedProperty {
+                    property: key.clone(),
+                },
+            });
+        }
+    }
+    errors.sort_by(|a, b| a.instance_path.cmp(&b.instance_path));
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn undescribed(schema: &Value, instance: &Value) -> anyhow::Result<Vec<String>> {
+        let evaluation = jsonschema::validator_for(schema)?.evaluate(instance);
+        let content = serde_json::to_string(instance)?;
+        Ok(
+            undescribed_properties(&evaluation, schema, instance, &content, "")
+                .into_iter()
+                .map(|e| e.instance_path)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn reports_keys_no_schema_describes() -> anyhow::Result<()> {
+        let schema = json!({
+            "properties": {
+                "name": {"type": "string"},
+                "spec": {"$ref": "#/$defs/spec"}
+            },
+            "allOf": [{"properties": {"kind": {}}}],
+            "$defs": {
+                "spec": {"properties": {"replicas": {}}}
+            }
+        });
+        let instance = json!({
+            "name": "web",
+            "kind": "Deployment",
+            "nmae": "typo",
+            "spec": {"replicas": 2, "replicass": 3}
+        });
+        assert_eq!(
+            undescribed(&schema, &instance)?,
+            ["/nmae", "/spec/replicass"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn open_and_free_form_objects_are_not_checked() -> anyhow::Result<()> {
+        let schema = json!({
+            "properties": {
+                "labels": {"type": "object"},
+                "extra": {"properties": {"a": {}}, "additionalProperties": true},
+                "env": {"properties": {"a": {}}, "patternProperties": {"^X_": {}}},
+                "typed": {"properties": {"a": {}}, "additionalProperties": {"type": "integer"}}
+            },
+            "additionalProperties": false
+        });
+        let instance = json!({
+            "labels": {"app": "web"},
+            "extra": {"a": 1, "b": 2},
+            "env": {"a": 1, "X_1": 2, "Y": 3},
+            "typed": {"a": 1, "b": 2}
+        });
+        assert_eq!(undescribed(&schema, &instance)?, ["/env/Y"]);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-//! "Did you mean?" suggestions for `additionalProperties` validation errors.
+//! "Did you mean?" suggestions for `additionalProperties`,
+//! `unevaluatedProperties` and `enum` validation errors.
 //!
 //! When a JSON Schema validation error reports an unexpected property, this
 //! module finds close matches from the schema's valid properties and appends
@@ -29,7 +30,7 @@ fn levenshtein(a: &str, b: &str) -> usize {
 }
 
 /// Resolve a local `$ref` (starting with `#/`) within the schema document.
-fn resolve_ref<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+pub(crate) fn resolve_ref<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
     if let Some(ref_str) = schema.get("$ref").and_then(Value::as_str)
         && let Some(path) = ref_str.strip_prefix("#/")
     {
@@ -87,24 +88,28 @@ fn navigate_pointer<'a>(schema: &'a Value, pointer: &str) -> Option<&'a Value> {
 /// `schema_path`.
 ///
 /// The `schema_path` from a validation error typically ends with
-/// `/additionalProperties` — we strip that suffix to find the parent object
-/// schema, then collect keys from its `properties`, `allOf` entries, and
-/// local `$ref` targets.
+/// `/additionalProperties` or `/unevaluatedProperties` — we strip that suffix
+/// to find the parent object schema, then collect keys from its
+/// `properties`, `allOf`/`anyOf`/`oneOf` entries, and local `$ref` targets.
 fn collect_schema_properties(schema: &Value, schema_path: &str) -> Vec<String> {
-    // Strip the /additionalProperties suffix to get the parent object path.
+    // Strip the /additionalProperties (or /unevaluatedProperties, or
+    // /properties for properties no schema describes) suffix to get the
+    // parent object path.
     let parent_path = schema_path
         .strip_suffix("/additionalProperties")
+        .or_else(|| schema_path.strip_suffix("/unevaluatedProperties"))
+        .or_else(|| schema_path.strip_suffix("/properties"))
         .unwrap_or(schema_path);
 
-    let Some(parent) = navigate_pointer(schema, parent_path) else {
+    let Some(parent) = navigate_schema_path(schema, parent_path) else {
         return Vec::new();
     };
 
     collect_properties_recursive(parent, schema)
 }
 
-/// Recursively collect property names from a schema node, following `$ref` and
-/// `allOf`.
+/// Recursively collect property names from a schema node, following `$ref`,
+/// `allOf`, `anyOf` and `oneOf`.
 fn collect_properties_recursive(node: &Value, root: &Value) -> Vec<String> {
     let resolved = resolve_ref(node, root);
     let mut props = Vec::new();
@@ -114,10 +119,13 @@ fn collect_properties_recursive(node: &Value, root: &Value) -> Vec<String> {
         props.extend(map.keys().cloned());
     }
 
-    // allOf: merge properties from each sub-schema
-    if let Some(Value::Array(all_of)) = resolved.get("allOf") {
-        for sub in all_of {
-            props.extend(collect_properties_recursive(sub, root));
+    // allOf: merge properties from each sub-schema. Properties of anyOf and
+    // oneOf branches are evaluated too, so unevaluatedProperties accepts them.
+    for keyword in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(subs)) = resolved.get(keyword) {
+            for sub in subs {
+                props.extend(collect_properties_recursive(sub, root));
+            }
         }
     }
 
@@ -171,6 +179,46 @@ pub(crate) fn suggest_property(
     suggest_for_property(property, &valid_properties)
 }
 
+/// Find the allowed value closest to a value that failed `enum`.
+///
+/// Values are compared as text (strings without their quotes), so a
+/// misspelled, miscased or mistyped value (`8080` for `"8080"`) finds its
+/// match. Returns `None` if no value is close enough.
+pub(crate) fn suggest_value<'a>(value: &Value, allowed: &'a [Value]) -> Option<&'a Value> {
+    let text = |v: &Value| v.as_str().map_or_else(|| v.to_string(), String::from);
+    let candidates: Vec<String> = allowed.iter().map(text).collect();
+    let best = suggest_for_property(&text(value), &candidates)?;
+    allowed.iter().find(|v| text(v) == best)
+}
+
+/// Find the name in `candidates` closest to a misspelled `name`.
+pub(crate) fn suggest_name(name: &str, candidates: &[&str]) -> Option<String> {
+    let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+    suggest_for_property(name, &candidates)
+}
+
+/// Navigate a validation error's `schema_path`, following `$ref` segments
+/// into their local targets.
+pub(crate) fn navigate_schema_path<'a>(root: &'a Value, schema_path: &str) -> Option<&'a Value> {
+    let path = schema_path.strip_prefix('/').unwrap_or(schema_path);
+    let mut current = root;
+    if path.is_empty() {
+        return Some(current);
+    }
+    for segment in path.split('/') {
+        if segment == "$ref" {
+            let resolved = resolve_ref(current, root);
+            if core::ptr::eq(resolved, current) {
+                return None;
+            }
+            current = resolved;
+        } else {
+            current = navigate_pointer(current, segment)?;
+        }
+    }
+    Some(current)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -291,6 +339,41 @@ mod tests {
         assert_eq!(props, vec!["id", "name"]);
     }
 
+    #[test]
+    fn collect_for_unevaluated_properties() {
+        let schema = json!({
+            "$defs": {
+                "Spec": {
+                    "properties": { "replicas": { "type": "integer" } },
+                    "oneOf": [
+                        { "properties": { "image": { "type": "string" } } },
+                        { "properties": { "chart": { "type": "string" } } }
+                    ],
+                    "unevaluatedProperties": false
+                }
+            }
+        });
+        let mut props = collect_schema_properties(&schema, "/$defs/Spec/unevaluatedProperties");
+        props.sort();
+        assert_eq!(props, vec!["chart", "image", "replicas"]);
+    }
+
+    #[test]
+    fn collect_through_ref_in_schema_path() {
+        let schema = json!({
+            "$defs": {
+                "Spec": {
+                    "properties": { "replicas": { "type": "integer" } },
+                    "additionalProperties": false
+                }
+            },
+            "properties": { "spec": { "$ref": "#/$defs/Spec" } }
+        });
+        let props =
+            collect_schema_properties(&schema, "/properties/spec/$ref/additionalProperties");
+        assert_eq!(props, vec!["replicas"]);
+    }
+
     #[test]
     fn collect_empty_on_bad_path() {
         let schema = json!({"type": "object"});
@@ -298,6 +381,23 @@ mod tests {
         assert!(props.is_empty());
     }
 
+    // --- suggest_value ---
+
+    #[test]
+    fn suggest_value_for_enum() {
+        let allowed = vec![json!("debug"), json!("info"), json!("warn"), json!(8080)];
+        assert_eq!(
+            suggest_value(&json!("wran"), &allowed),
+            Some(&json!("warn"))
+        );
+        assert_eq!(
+            suggest_value(&json!("INFO"), &allowed),
+            Some(&json!("info"))
+        );
+        assert_eq!(suggest_value(&json!("8080"), &allowed), Some(&json!(8080)));
+        assert_eq!(suggest_value(&json!("verbose"), &allowed), None);
+    }
+
     // --- suggest_for_property ---
 
     #[test]
@@ -0,0 +1,156 @@
+//! `lintel-ignore` comments in instance files.
+//!
+//! A comment line `# lintel-ignore <target>...` (or `// lintel-ignore` in
+//! JSONC) silences validation errors reported on the next line that is not
+//! blank or a comment. Each target is either a schema keyword
+//! (`additionalProperties`) or a JSON pointer (`/servers/0/port`), which
+//! matches errors at or below that instance path. Without targets every
+//! error on the line is silenced. Text after ` -- ` is a free-form reason.
+
+use lintel_diagnostics::{LintelDiagnostic, ValidationDiagnostic};
+
+/// A `lintel-ignore` comment.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Suppression {
+    /// Byte offset and length of the comment.
+    pub offset: usize,
+    pub len: usize,
+    /// 1-based line the comment applies to.
+    pub target_line: usize,
+    /// Keywords and pointers to silence; empty silences everything.
+    pub targets: Vec<String>,
+    /// Whether the comment silenced any error.
+    pub used: bool,
+}
+
+impl Suppression {
+    pub(crate) fn silences(&self, error: &ValidationDiagnostic) -> bool {
+        if error.line_col().0 != self.target_line {
+            return false;
+        }
+        self.targets.is_empty()
+            || self.targets.iter().any(|target| {
+                if target.starts_with('/') {
+                    error.instance_path == *target
+                        || error
+                            .instance_path
+                            .strip_prefix(target.as_str())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                } else {
+                    crate::document::error_keyword(&error.schema_path) == target
+                }
+            })
+    }
+}
+
+/// Find the `lintel-ignore` comments in `content`.
+pub(crate) fn parse(content: &str) -> Vec<Suppression> {
+    let mut suppressions = Vec::new();
+    let mut pending: Vec<Suppression> = Vec::new();
+    let mut offset = 0;
+    for (i, line) in content.split('\n').enumerate() {
+        let trimmed = line.trim_start();
+        let start = offset + line.len() - trimmed.len();
+        offset += line.len() + 1;
+        let comment = trimmed
+            .strip_prefix("//")
+            .or_else(|| trimmed.strip_prefix('#'));
+        let Some(comment) = comment else {
+            if !trimmed.trim_end().is_empty() {
+                for mut suppression in pending.drain(..) {
+                    suppression.target_line = i + 1;
+                    suppressions.push(suppression);
+                }
+            }
+            continue;
+        };
+        let Some(rest) = comment.trim_start().strip_prefix("lintel-ignore") else {
+            continue;
+        };
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let rest = rest.split(" -- ").next().unwrap_or_default();
+        pending.push(Suppression {
+            offset: start,
+            len: trimmed.trim_end().len(),
+            target_line: 0,
+            targets: rest
+                .split([',', ' ', '\t'])
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            used: false,
+        });
+    }
+    suppressions
+}
+
+/// Drop the validation errors in the file at `path` silenced by one of its
+/// `lintel-ignore` comments, marking the comments that silenced something
+/// as used.
+pub(crate) fn apply(
+    errors: &mut Vec<LintelDiagnostic>,
+    path: &str,
+    suppressions: &mut [Suppression],
+) {
+    errors.retain(|error| {
+        let LintelDiagnostic::Validation(v) = error else {
+            return true;
+        };
+        // Embedded documents report as `file:line`; match on the file.
+        if v.src.name() != path {
+            return true;
+        }
+        let mut silenced = false;
+        for suppression in suppressions.iter_mut() {
+            if suppression.silences(v) {
+                suppression.used = true;
+                silenced = true;
+            }
+        }
+        !silenced
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_comments_for_the_next_node() {
+        let content = "\
+# lintel-ignore additionalProperties, required -- legacy key
+extra: true
+
+  // lintel-ignore /servers/0/port
+  # a note in between
+
+servers: []
+# lintel-ignored
+# lintel-ignore
+name: demo
+";
+        let parsed: Vec<(usize, Vec<String>)> = parse(content)
+            .into_iter()
+            .map(|s| (s.target_line, s.targets))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (
+                    2,
+                    vec!["additionalProperties".to_string(), "required".to_string()]
+                ),
+                (7, vec!["/servers/0/port".to_string()]),
+                (10, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_comment_applies_to_nothing() {
+        assert!(parse("name: demo\n# lintel-ignore type\n").is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! Go templates of YAML files, such as Helm chart templates.
+//!
+//! A template is not YAML until it is rendered, but most of it already is.
+//! [`stub`] blanks out the template's control actions, comments, `else`
+//! branches and `define` bodies, and replaces each action that renders a
+//! value with a placeholder of the same length. The result parses wherever
+//! the YAML around the actions is sound, and since nothing moves, an error's
+//! offset in the stubbed text is its offset in the template.
+
+use std::path::Path;
+
+/// Whether the file at `path` is a Go template of a YAML file: a
+/// `*.gotmpl` file, or a YAML file under the `templates` directory of a Helm
+/// chart.
+pub(crate) fn is_template(path: &Path) -> bool {
+    if path.extension().is_some_and(|e| e == "gotmpl") {
+        return true;
+    }
+    if !path.extension().is_some_and(|e| e == "yaml" || e == "yml") {
+        return false;
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| !dir.as_os_str().is_empty())
+        .any(|dir| {
+            dir.file_name().is_some_and(|name| name == "templates")
+                && dir
+                    .parent()
+                    .is_some_and(|chart| chart.join("Chart.yaml").is_file())
+        })
+}
+
+/// What an action does to the text around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `if`, `with`, `range` and `block` open a block.
+    Open,
+    /// `define` opens a block that is not part of the output.
+    Define,
+    Else,
+    End,
+    Comment,
+    /// Everything else renders a value.
+    Value,
+}
+
+/// An action `{{ ... }}` as a byte range of the template.
+#[derive(Debug)]
+struct Action {
+    start: usize,
+    end: usize,
+    kind: Kind,
+}
+
+/// `template` with its actions stubbed out, byte for byte the same length.
+pub(crate) fn stub(template: &str) -> String {
+    let actions = actions(template);
+    let mut out = template.as_bytes().to_vec();
+
+    for action in &actions {
+        let fill = if action.kind == Kind::Value && !stands_alone(template, &actions, action) {
+            b'_'
+        } else {
+            b' '
+        };
+        blank(&mut out, action.start, action.end, fill);
+    }
+
+    // Keep the first branch of each `if`, and drop named templates.
+    let mut blocks: Vec<Option<usize>> = Vec::new();
+    for action in &actions {
+        match action.kind {
+            Kind::Open => blocks.push(None),
+            Kind::Define => blocks.push(Some(action.end)),
+            Kind::Else => {
+                if let Some(skip @ None) = blocks.last_mut() {
+                    *skip = Some(action.end);
+                }
+            }
+            Kind::End => {
+                if let Some(Some(from)) = blocks.pop() {
+                    blank(&mut out, from, action.start, b' ');
+                }
+            }
+            Kind::Comment | Kind::Value => {}
+        }
+    }
+
+    // Only whole characters were replaced, and only with ASCII.
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Replace `out[from..to]` with `fill`, keeping line breaks.
+fn blank(out: &mut [u8], from: usize, to: usize, fill: u8) {
+    for byte in &mut out[from..to] {
+        if *byte != b'\n' && *byte != b'\r' {
+            *byte = fill;
+        }
+    }
+}
+
+/// Whether `action` shares its lines only with whitespace and other
+/// actions, so it renders a block rather than part of a value.
+fn stands_alone(template: &str, actions: &[Action], action: &Action) -> bool {
+    let line_start = template[..action.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = template[action.end..]
+        .find('\n')
+        .map_or(template.len(), |i| action.end + i);
+    let mut offset = line_start;
+    for other in actions
+        .iter()
+        .filter(|a| a.end > line_start && a.start < line_end)
+    {
+        if !template[offset..other.start].trim().is_empty() {
+            return false;
+        }
+        offset = other.end;
+    }
+    template[offset..line_end].trim().is_empty()
+}
+
+/// The actions of `template`, in order. An action that is never closed
+/// is left as text.
+fn actions(template: &str) -> Vec<Action> {
+    let bytes = template.as_bytes();
+    let mut actions = Vec::new();
+    let mut offset = 0;
+    while let Some(found) = template[offset..].find("{{") {
+        let start = offset + found;
+        let Some(end) = action_end(bytes, start + 2) else {
+            break;
+        };
+        let body = template[start + 2..end - 2]
+            .trim_start_matches('-')
+            .trim_end_matches('-')
+            .trim();
+        actions.push(Action {
+            start,
+            end,
+            kind: kind(body),
+        });
+        offset = end;
+    }
+    actions
+}
+
+/// The offset just past the `}}` closing the action whose body starts at
+/// `from`, skipping strings and comments.
+fn action_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'`' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'`' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'}' if bytes.get(i + 1) == Some(&b'}') => return Some(i + 2),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn kind(body: &str) -> Kind {
+    if body.starts_with("/*") {
+        return Kind::Comment;
+    }
+    let keyword = body
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default();
+    match keyword {
+        "if" | "with" | "range" | "block" => Kind::Open,
+        "define" => Kind::Define,
+        "else" => Kind::Else,
+        "end" => Kind::End,
+        _ => Kind::Value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stubs_actions_without_moving_text() {
+        let template = "\
+{{- /* a comment */ -}}
+apiVersion: apps/v1
+metadata:
+  name: {{ include \"app.fullname\" . }}-web
+  labels:
+    {{- include \"app.labels\" . | nindent 4 }}
+spec:
+  {{- if .Values.autoscaling.enabled }}
+  replicas: 1
+  {{- else }}
+  replicas: {{ .Values.replicas }}
+  {{- end }}
+  image: \"{{ .Values.image }}:{{ .Chart.AppVersion | default \"}}\" }}\"
+{{ define \"unused\" }}not: [yaml{{ end }}
+";
+        let stubbed = stub(template);
+        assert_eq!(stubbed.len(), template.len());
+        assert_eq!(
+            stubbed.lines().map(str::trim_end).collect::<Vec<_>>(),
+            [
+                "",
+                "apiVersion: apps/v1",
+                "metadata:",
+                "  name: ______________________________-web",
+                "  labels:",
+                "",
+                "spec:",
+                "",
+                "  replicas: 1",
+                "",
+                "",
+                "",
+                "  image: \"___________________:______________________________________\"",
+                "",
+            ]
+        );
+        let value: serde_json::Value = serde_yaml::from_str(&stubbed).expect("stub should parse");
+        assert_eq!(value["spec"]["replicas"], 1);
+    }
+
+    #[test]
+    fn unclosed_actions_stay_text() {
+        assert_eq!(stub("a: {{ .b }}\nc: {{ .d\n"), "a: ________\nc: {{ .d\n");
+    }
+
+    #[test]
+    fn chart_templates_and_gotmpl_files_are_templates() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let templates = tmp.path().join("templates");
+        std::fs::create_dir_all(templates.join("web"))?;
+        assert!(!is_template(&templates.join("web/deployment.yaml")));
+
+        std::fs::write(tmp.path().join("Chart.yaml"), "name: app\n")?;
+        assert!(is_template(&templates.join("web/deployment.yaml")));
+        assert!(is_template(&templates.join("service.yml")));
+        assert!(!is_template(&templates.join("_helpers.tpl")));
+        assert!(!is_template(&tmp.path().join("values.yaml")));
+        assert!(is_template(Path::new("helmfile.yaml.gotmpl")));
+        Ok(())
+    }
+}
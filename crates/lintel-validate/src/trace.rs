@@ -0,0 +1,333 @@
+//! `--trace-resolution`: explain how a file's schema was chosen.
+//!
+//! Lists every candidate source in the order it is considered (which depends
+//! on the `[schemas]` mapping's priority), marks the selected one and those
+//! it beat, and points out mapping and catalog patterns that almost matched
+//! the file.
+
+use core::fmt::Write;
+use std::path::Path;
+
+use lintel_config::{Config, MappingPriority};
+use schema_catalog::{CompiledCatalog, FileFormat};
+
+use crate::parsers;
+use crate::parsers::try_parse_all;
+
+/// Extension swaps that commonly explain a pattern not matching.
+const EXTENSION_SWAPS: &[(&str, &str)] = &[
+    ("yaml", "yml"),
+    ("yml", "yaml"),
+    ("json", "jsonc"),
+    ("jsonc", "json"),
+    ("json", "json5"),
+];
+
+/// A place a schema can come from.
+enum Candidate {
+    Inline,
+    Mapping,
+    Catalogs,
+}
+
+/// Describe how the schema for `path` is resolved.
+pub(crate) fn trace_file(
+    path: &Path,
+    content: &str,
+    config: &Config,
+    catalogs: &[CompiledCatalog],
+) -> String {
+    let path_str = path.display().to_string();
+    let path_str = path_str.strip_prefix("./").unwrap_or(&path_str);
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path_str);
+
+    let mapping = config.find_schema_mapping_entry(path_str, file_name);
+    let order = match mapping.map(|(_, m)| m.priority()) {
+        Some(MappingPriority::High) => [Candidate::Mapping, Candidate::Inline, Candidate::Catalogs],
+        Some(MappingPriority::Low) => [Candidate::Inline, Candidate::Catalogs, Candidate::Mapping],
+        _ => [Candidate::Inline, Candidate::Mapping, Candidate::Catalogs],
+    };
+
+    let mut out = format!("resolution: {path_str}\n");
+    let mut selected: Option<String> = None;
+    let mut verdict = |uri: &str| {
+        if selected.is_some() {
+            "rejected, lower priority"
+        } else {
+            selected = Some(uri.to_string());
+            "selected"
+        }
+    };
+    for candidate in order {
+        match candidate {
+            Candidate::Inline => {
+                let inline = inline_schema(path, content, path_str);
+                match &inline {
+                    Some(uri) => {
+                        let _ = writeln!(out, "  inline $schema: {uri} ({})", verdict(uri));
+                    }
+                    None => out.push_str("  inline $schema: none\n"),
+                }
+                if let Some(sidecar) = lintel_config::sidecar::find_sidecar_schema(path) {
+                    let status = if inline.is_some() {
+                        "rejected, lower priority"
+                    } else {
+                        verdict(&sidecar.uri)
+                    };
+                    let _ = writeln!(
+                        out,
+                        "  sidecar {}: {} ({status})",
+                        sidecar.sidecar.display(),
+                        sidecar.uri
+                    );
+                }
+            }
+            Candidate::Mapping => {
+                trace_mappings(&mut out, config, path_str, file_name, &mut verdict);
+            }
+            Candidate::Catalogs => {
+                if let Some(uri) = crate::helm::values_schema(path) {
+                    let _ = writeln!(out, "  helm values schema: {uri} ({})", verdict(&uri));
+                }
+                if let Some(uri) = kubernetes_schema(path, content, path_str, config) {
+                    let _ = writeln!(out, "  kubernetes: {uri} ({})", verdict(&uri));
+                }
+                trace_catalogs(&mut out, catalogs, path_str, file_name, &mut verdict);
+                if let Some(uri) = crate::helm::chart_schema(path) {
+                    let _ = writeln!(out, "  helm chart: {uri} ({})", verdict(&uri));
+                }
+            }
+        }
+    }
+    match &selected {
+        Some(uri) => {
+            let _ = writeln!(out, "  -> {uri}");
+        }
+        None => out.push_str("  -> no schema, file is skipped\n"),
+    }
+    out
+}
+
+fn inline_schema(path: &Path, content: &str, path_str: &str) -> Option<String> {
+    match parsers::detect_format(path) {
+        Some(FileFormat::Jsonl) => content
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .and_then(|line| serde_json::from_str(line).ok())
+            .and_then(|value| parsers::jsonl::extract_schema_uri(&value)),
+        Some(format) => {
+            let parser = parsers::parser_for(format);
+            let value = parser.parse(content, path_str).ok()?;
+            parser.extract_schema_uri(content, &value)
+        }
+        None => {
+            let (format, value) = try_parse_all(content, path_str)?;
+            parsers::parser_for(format).extract_schema_uri(content, &value)
+        }
+    }
+}
+
+/// The schema `[kubernetes]` resolution picks from the file's `apiVersion`
+/// and `kind`, if enabled.
+fn kubernetes_schema(
+    path: &Path,
+    content: &str,
+    path_str: &str,
+    config: &Config,
+) -> Option<String> {
+    if config.kubernetes.enabled != Some(true) {
+        return None;
+    }
+    let value = match parsers::detect_format(path) {
+        Some(FileFormat::Jsonl) => return None,
+        Some(format) => parsers::parser_for(format).parse(content, path_str).ok()?,
+        None => try_parse_all(content, path_str)?.1,
+    };
+    crate::kubernetes::schema_uri(&config.kubernetes, &value)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn trace_mappings(
+    out: &mut String,
+    config: &Config,
+    path: &str,
+    file_name: &str,
+    verdict: &mut impl FnMut(&str) -> &'static str,
+) {
+    if config.schemas.is_empty() {
+        out.push_str("  [schemas]: no mappings\n");
+        return;
+    }
+    let mut patterns: Vec<_> = config.schemas.iter().collect();
+    patterns.sort_by_key(|(pattern, _)| pattern.as_str());
+    let matched = config.find_schema_mapping_entry(path, file_name);
+    for (pattern, mapping) in patterns {
+        if matched.is_some_and(|(p, _)| p == pattern) {
+            let _ = writeln!(
+                out,
+                "  [schemas] {pattern:?}: {} ({})",
+                mapping.url(),
+                verdict(mapping.url())
+            );
+        } else if let Some(reason) = near_miss(pattern, path, file_name, false) {
+            let _ = writeln!(out, "  [schemas] {pattern:?}: near miss, {reason}");
+        } else {
+            let _ = writeln!(out, "  [schemas] {pattern:?}: no match");
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn trace_catalogs(
+    out: &mut String,
+    catalogs: &[CompiledCatalog],
+    path: &str,
+    file_name: &str,
+    verdict: &mut impl FnMut(&str) -> &'static str,
+) {
+    if catalogs.is_empty() {
+        out.push_str("  catalogs: none loaded\n");
+        return;
+    }
+    for (i, catalog) in catalogs.iter().enumerate() {
+        let n = i + 1;
+        match catalog.find_schema_detailed(path, file_name) {
+            Some(m) => {
+                let _ = writeln!(
+                    out,
+                    "  catalog #{n}: {} via {:?}: {} ({})",
+                    m.name,
+                    m.matched_pattern,
+                    m.url,
+                    verdict(m.url)
+                );
+            }
+            None => {
+                let _ = writeln!(out, "  catalog #{n}: no match");
+            }
+        }
+        for entry in catalog.entries() {
+            for pattern in entry.file_match {
+                if let Some(reason) = near_miss(pattern, path, file_name, true) {
+                    let _ = writeln!(
+                        out,
+                        "  catalog #{n}: {} {pattern:?}: near miss, {reason}",
+                        entry.name
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Why `pattern` almost matches the file, if it does not match but a small
+/// change to the path would make it.
+///
+/// Catalog patterns without a `/` match at any depth, like in
+/// [`CompiledCatalog::compile`]; mapping patterns are tried against both
+/// the path and the file name.
+fn near_miss(pattern: &str, path: &str, file_name: &str, catalog: bool) -> Option<String> {
+    if pattern.starts_with('!') {
+        return None;
+    }
+    let normalized = if catalog && !pattern.contains('/') {
+        format!("**/{pattern}")
+    } else {
+        pattern.to_string()
+    };
+    let matches = |glob: &str, path: &str, file_name: &str| {
+        glob_matcher::glob_match(glob, path)
+            || (!catalog && glob_matcher::glob_match(glob, file_name))
+    };
+    if matches(&normalized, path, file_name) {
+        return None;
+    }
+
+    if let Some((_, last)) = pattern.rsplit_once('/')
+        && !last.contains("**")
+        && glob_matcher::glob_match(last, file_name)
+    {
+        return Some("the file name matches but the directory does not".to_string());
+    }
+    if let (Some((stem, ext)), Some((name_stem, _))) =
+        (path.rsplit_once('.'), file_name.rsplit_once('.'))
+    {
+        for (from, to) in EXTENSION_SWAPS {
+            if ext == *from
+                && matches(
+                    &normalized,
+                    &format!("{stem}.{to}"),
+                    &format!("{name_stem}.{to}"),
+                )
+            {
+                return Some(format!("it would match with a .{to} extension"));
+            }
+        }
+    }
+    if matches(
+        &normalized.to_lowercase(),
+        &path.to_lowercase(),
+        &file_name.to_lowercase(),
+    ) {
+        return Some("it differs only in letter case".to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_miss_explains_directory_extension_and_case() {
+        assert_eq!(
+            near_miss(".github/workflows/*.yml", "ci/build.yml", "build.yml", true).as_deref(),
+            Some("the file name matches but the directory does not")
+        );
+        assert_eq!(
+            near_miss("deploy/*.yml", "deploy/app.yaml", "app.yaml", false).as_deref(),
+            Some("it would match with a .yml extension")
+        );
+        assert_eq!(
+            near_miss(
+                "Dockerfile.json",
+                "dockerfile.json",
+                "dockerfile.json",
+                true
+            )
+            .as_deref(),
+            Some("it differs only in letter case")
+        );
+        assert_eq!(near_miss("*.toml", "a.json", "a.json", true), None);
+        assert_eq!(near_miss("*.json", "a.json", "a.json", true), None);
+    }
+
+    #[test]
+    fn traces_candidates_in_priority_order() -> anyhow::Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[schemas]
+"*.json" = { url = "https://example.com/mapped.json", priority = "high" }
+"#,
+        )?;
+        let out = trace_file(
+            Path::new("data.json"),
+            r#"{"$schema":"https://example.com/inline.json"}"#,
+            &config,
+            &[],
+        );
+        assert_eq!(
+            out,
+            "resolution: data.json
+  [schemas] \"*.json\": https://example.com/mapped.json (selected)
+  inline $schema: https://example.com/inline.json (rejected, lower priority)
+  catalogs: none loaded
+  -> https://example.com/mapped.json
+"
+        );
+        Ok(())
+    }
+}
@@ -6,33 +6,48 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use serde_json::Value;
 
-use lintel_diagnostics::reporter::{CheckResult, CheckedFile};
-use lintel_diagnostics::{
-    DEFAULT_LABEL, LintelDiagnostic, ValidationDiagnostic, find_instance_path_span, format_label,
-};
+use lintel_config::schema_index::LazySchemaIndex;
+use lintel_config::{MappingPriority, SchemaMapping};
+use lintel_diagnostics::reporter::{CheckResult, CheckedFile, Timings};
+use lintel_diagnostics::{DEFAULT_LABEL, LintelDiagnostic};
 use lintel_schema_cache::{CacheStatus, SchemaCache};
-use lintel_validation_cache::{ValidationCacheStatus, ValidationError, ValidationErrorKind};
+use lintel_validation_cache::{ValidationCacheStatus, ValidationError};
 use schema_catalog::{CompiledCatalog, FileFormat};
 
 use crate::catalog;
+use crate::describe;
+use crate::document::{DiagnosticSource, document_errors, error_keyword};
 use crate::parsers::{self, Parser};
 use crate::registry;
-use crate::suggest;
 
 /// Conservative limit for concurrent file reads to avoid exhausting file
 /// descriptors. 128 is well below the default soft limit on macOS (256) and
 /// Linux (1024) while still providing good throughput.
 const FD_CONCURRENCY_LIMIT: usize = 128;
 
-/// Composite retriever that dispatches `file://` URIs to local disk reads
-/// and everything else to the HTTP-backed [`SchemaCache`].
+/// JSON and YAML documents at least this large are parsed in full only
+/// when they are first needed, and JSON ones are only outlined for schema
+/// resolution. A validation cache hit without errors never parses them in
+/// full.
+const LARGE_DOCUMENT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Composite retriever that dispatches `file://` URIs, remote URIs that
+/// are the `$id` of a workspace schema, and remote URIs that a `[rewrite]`
+/// rule maps to a local path, to disk reads and everything else to the
+/// HTTP-backed [`SchemaCache`].
 struct LocalRetriever {
     http: SchemaCache,
+    rewrite: HashMap<String, String>,
+    config_dir: PathBuf,
+    schema_index: alloc::sync::Arc<LazySchemaIndex>,
+    /// Keywords removed from every retrieved schema (`ignore_keywords`)
+    ignored: Vec<String>,
+    /// `lintel.lock`, checked for every remote schema `$ref`s reach
+    lock: Option<alloc::sync::Arc<crate::lock::SchemaLock>>,
 }
 
-#[async_trait::async_trait]
-impl jsonschema::AsyncRetrieve for LocalRetriever {
-    async fn retrieve(
+impl LocalRetriever {
+    async fn fetch(
         &self,
         uri: &jsonschema::Uri<String>,
     ) -> Result<Value, Box<dyn core::error::Error + Send + Sync>> {
@@ -40,13 +55,42 @@ impl jsonschema::AsyncRetrieve for LocalRetriever {
         if let Some(raw) = s.strip_prefix("file://") {
             let path = percent_encoding::percent_decode_str(raw).decode_utf8()?;
             let content = tokio::fs::read_to_string(path.as_ref()).await?;
-            Ok(serde_json::from_str(&content)?)
-        } else {
-            self.http.retrieve(uri).await
+            return Ok(serde_json::from_str(&content)?);
+        }
+        if let Some(path) = self.schema_index.resolve(s) {
+            let content = tokio::fs::read_to_string(path).await?;
+            return Ok(serde_json::from_str(&content)?);
+        }
+        let rewritten = lintel_config::apply_rewrites(s, &self.rewrite);
+        let rewritten = lintel_config::resolve_double_slash(&rewritten, &self.config_dir);
+        if rewritten != s && !rewritten.starts_with("http://") && !rewritten.starts_with("https://")
+        {
+            let content = tokio::fs::read_to_string(&rewritten).await?;
+            return Ok(serde_json::from_str(&content)?);
         }
+        let schema = jsonschema::AsyncRetrieve::retrieve(&self.http, uri).await?;
+        if let Some(lock) = &self.lock
+            && let Some(hash) = self.http.content_hash(s)
+        {
+            lock.check(s, &hash)?;
+        }
+        Ok(schema)
+    }
+}
+
+#[async_trait::async_trait]
+impl jsonschema::AsyncRetrieve for LocalRetriever {
+    async fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn core::error::Error + Send + Sync>> {
+        let mut schema = self.fetch(uri).await?;
+        crate::ignore::strip(&mut schema, &self.ignored);
+        Ok(schema)
     }
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct ValidateArgs {
     /// Glob patterns to find files (empty = auto-discover)
     pub globs: Vec<String>,
@@ -71,6 +115,43 @@ pub struct ValidateArgs {
 
     /// TTL for cached schemas. `None` means no expiry.
     pub schema_cache_ttl: Option<core::time::Duration>,
+
+    /// Time limit for each schema download; overrides `[network] timeout`
+    pub fetch_timeout: Option<core::time::Duration>,
+
+    /// Retries for failed schema downloads; overrides `[network] retries`
+    pub fetch_retries: Option<u32>,
+
+    /// Delay before the first retry; overrides `[network] retry-backoff`
+    pub fetch_retry_backoff: Option<core::time::Duration>,
+
+    /// Print how each file's schema was resolved to stderr
+    pub trace_resolution: bool,
+
+    /// How `lintel.lock` is checked and updated
+    pub lock: crate::lock::LockMode,
+    /// Validate the examples and defaults of OpenAPI documents against
+    /// their schemas
+    pub openapi_examples: bool,
+    /// Decode `contentEncoding` values and check embedded JSON documents
+    /// against `contentMediaType` and `contentSchema`
+    pub validate_content: bool,
+    /// Warn about properties the schema allows only because it omits
+    /// `additionalProperties`
+    pub strict_instances: bool,
+    /// Warn about `lintel-ignore` comments that silence no errors
+    pub report_unused_suppressions: bool,
+    /// Stop validating after the first file that fails
+    pub fail_fast: bool,
+    /// Report at most this many diagnostics per file, followed by a count of
+    /// the rest
+    pub max_errors: Option<usize>,
+    /// Give up on validating a file after this long and report a timeout
+    /// instead. The validation itself runs on until it finishes.
+    pub timeout_per_file: Option<core::time::Duration>,
+    /// Validators for custom `format` values, on top of `[formats]` in
+    /// `lintel.toml`
+    pub formats: crate::formats::FormatRegistry,
 }
 
 // ---------------------------------------------------------------------------
@@ -81,9 +162,53 @@ pub struct ValidateArgs {
 struct ParsedFile {
     path: String,
     content: String,
-    instance: Value,
+    instance: Instance,
     /// Original schema URI before rewrites (for override matching).
     original_schema_uri: String,
+    /// JSON pointer of `instance` within the parsed document, when an
+    /// override's `extract` selected a fragment of it; empty otherwise.
+    instance_prefix: String,
+    /// Set when `content` is embedded in another file, such as a Markdown
+    /// code fence, so diagnostics point into that file.
+    embedded_in: Option<EmbeddedSource>,
+}
+
+/// The document of a [`ParsedFile`].
+enum Instance {
+    Parsed(Value),
+    /// A large document, parsed from `content` with the parser for this
+    /// format the first time it is needed and kept from then on; see
+    /// [`LARGE_DOCUMENT_BYTES`].
+    Deferred(FileFormat, std::sync::OnceLock<Value>),
+}
+
+impl ParsedFile {
+    /// The document, parsed on first use if it was deferred.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error of a deferred document.
+    #[allow(clippy::result_large_err)]
+    fn instance(&self) -> Result<&Value, LintelDiagnostic> {
+        match &self.instance {
+            Instance::Parsed(value) => Ok(value),
+            Instance::Deferred(format, parsed) => {
+                if let Some(value) = parsed.get() {
+                    return Ok(value);
+                }
+                let value = parsers::parser_for(*format).parse(&self.content, &self.path)?;
+                Ok(parsed.get_or_init(|| value))
+            }
+        }
+    }
+}
+
+/// The file a [`ParsedFile`]'s content was extracted from.
+struct EmbeddedSource {
+    path: String,
+    content: String,
+    /// Byte offset of the embedded content in `content`.
+    offset: usize,
 }
 
 // ---------------------------------------------------------------------------
@@ -129,35 +254,131 @@ pub fn collect_files(globs: &[String], exclude: &[String]) -> Result<Vec<PathBuf
     lintel_config::discover::collect_files(globs, exclude, |p| parsers::detect_format(p).is_some())
 }
 
-// ---------------------------------------------------------------------------
-// Phase 1: Parse files and resolve schema URIs
-// ---------------------------------------------------------------------------
+/// Like [`collect_files`], but only the files git reports as changed since
+/// the revision `since`, including uncommitted and untracked ones.
+/// `diff_filter` selects which changes count, as for `git diff --diff-filter`.
+///
+/// # Errors
+///
+/// Returns an error if git fails, e.g. outside a repository or for an
+/// unknown revision.
+pub fn collect_changed_files(
+    globs: &[String],
+    exclude: &[String],
+    since: &str,
+    diff_filter: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    let changed = lintel_config::discover::changed_files(".", since, diff_filter)?;
+    Ok(lintel_config::discover::filter_files(
+        changed,
+        globs,
+        exclude,
+        |p| parsers::detect_format(p).is_some(),
+    ))
+}
+
+/// Whether a positional path is an `http://` or `https://` URL to download
+/// rather than a local path or glob.
+pub fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
 
-/// Try parsing content with each known format, returning the first success.
+/// Read the documents named by `args.globs`: URLs are downloaded, and the
+/// remaining paths and globs are collected and read from disk. Without any
+/// URLs this is [`collect_files`] followed by [`read_files`].
+///
+/// Download and read failures are pushed as `LintelDiagnostic::Io`.
+///
+/// # Errors
 ///
-/// JSONC is tried first (superset of JSON, handles comments), then YAML and
-/// TOML which cover the most common config formats, followed by the rest.
-pub fn try_parse_all(content: &str, file_name: &str) -> Option<(FileFormat, Value)> {
-    use FileFormat::{Json, Json5, Jsonc, Markdown, Toml, Yaml};
-    const FORMATS: [FileFormat; 6] = [Jsonc, Yaml, Toml, Json, Json5, Markdown];
+/// Returns an error if a glob pattern is invalid or a directory cannot be walked.
+pub async fn read_documents(
+    args: &ValidateArgs,
+    errors: &mut Vec<LintelDiagnostic>,
+) -> Result<Vec<(PathBuf, String)>> {
+    let (urls, globs): (Vec<String>, Vec<String>) =
+        args.globs.iter().cloned().partition(|g| is_url(g));
+    let mut documents = fetch_documents(&urls, args, errors).await;
+    // Only URLs given: don't fall back to discovering every file in cwd.
+    if urls.is_empty() || globs.iter().any(|g| !g.starts_with('!')) {
+        let files = collect_files(&globs, &args.exclude)?;
+        documents.extend(read_files(&files, errors).await);
+    }
+    Ok(documents)
+}
 
-    for fmt in FORMATS {
-        let parser = parsers::parser_for(fmt);
-        if let Ok(val) = parser.parse(content, file_name) {
-            return Some((fmt, val));
+/// Download the documents at `urls` concurrently, retrying like schema
+/// downloads do. Each is paired with its URL, minus any query string or
+/// fragment, as its path, so catalog patterns such as
+/// `**/.github/workflows/*.yml` match it and relative `$schema` references
+/// resolve against it.
+async fn fetch_documents(
+    urls: &[String],
+    args: &ValidateArgs,
+    errors: &mut Vec<LintelDiagnostic>,
+) -> Vec<(PathBuf, String)> {
+    use lintel_schema_cache::{CacheValidators, HttpRetriever, SchemaRetriever};
+
+    if urls.is_empty() {
+        return Vec::new();
+    }
+    let mut retriever = HttpRetriever::default();
+    if let Some(timeout) = args.fetch_timeout {
+        retriever = retriever.timeout(timeout);
+    }
+    if let Some(retries) = args.fetch_retries {
+        retriever = retriever.retries(retries);
+    }
+    if let Some(backoff) = args.fetch_retry_backoff {
+        retriever = retriever.retry_backoff(backoff);
+    }
+    let retriever = alloc::sync::Arc::new(retriever);
+
+    let mut fetch_set = tokio::task::JoinSet::new();
+    for url in urls {
+        let url = url.clone();
+        let retriever = retriever.clone();
+        fetch_set.spawn(async move {
+            let result = retriever.fetch(&url, &CacheValidators::default()).await;
+            (url, result)
+        });
+    }
+
+    let mut documents = Vec::with_capacity(urls.len());
+    while let Some(result) = fetch_set.join_next().await {
+        match result {
+            Ok((url, Ok(response))) => {
+                let path = url.split(['?', '#']).next().unwrap_or(&url);
+                documents.push((PathBuf::from(path), response.body.unwrap_or_default()));
+            }
+            Ok((url, Err(e))) => {
+                errors.push(LintelDiagnostic::Io {
+                    path: url,
+                    message: format!("failed to fetch: {e}"),
+                });
+            }
+            Err(e) => tracing::warn!("document fetch task panicked: {e}"),
         }
     }
-    None
+    documents
 }
 
+// ---------------------------------------------------------------------------
+// Phase 1: Parse files and resolve schema URIs
+// ---------------------------------------------------------------------------
+
+pub use crate::parsers::try_parse_all;
+
 /// Result of processing a single file: either a parsed file with its schema URI,
-/// a lint error, or nothing (file was skipped).
+/// a lint error, a document no schema was found for, or nothing (file was
+/// skipped).
 enum FileResult {
     Parsed {
         schema_uri: String,
         parsed: ParsedFile,
     },
     Error(LintelDiagnostic),
+    NoSchema,
     Skip,
 }
 
@@ -170,6 +391,10 @@ fn resolve_local_schema_path(schema_uri: &str, base_dir: Option<&Path>) -> Strin
     if schema_uri.starts_with("http://") || schema_uri.starts_with("https://") {
         return schema_uri.to_string();
     }
+    // A document downloaded from a URL: resolve against the URL instead.
+    if let Some(base) = base_dir.and_then(Path::to_str).filter(|b| is_url(b)) {
+        return format!("{base}/{schema_uri}");
+    }
     if let Some(dir) = base_dir {
         normalize_path(&dir.join(schema_uri))
             .to_string_lossy()
@@ -197,7 +422,8 @@ fn normalize_path(path: &Path) -> PathBuf {
 
 /// Process a single file's already-read content: parse and resolve schema URI.
 ///
-/// Returns a `Vec` because JSONL files expand to one result per non-empty line.
+/// Returns a `Vec` because JSONL files expand to one result per non-empty line
+/// and Markdown files to one per validated code fence.
 #[allow(clippy::too_many_arguments)]
 fn process_one_file(
     path: &Path,
@@ -205,12 +431,19 @@ fn process_one_file(
     config: &lintel_config::Config,
     config_dir: &Path,
     compiled_catalogs: &[CompiledCatalog],
+    plugin_schemas: &HashMap<String, String>,
 ) -> Vec<FileResult> {
     let path_str = path.display().to_string();
     let file_name = path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or(&path_str);
+    let match_path = crate::archive::member_path(&path_str);
+
+    // Go templates are checked for their YAML structure only.
+    if crate::template::is_template(path) {
+        return process_template(&path_str, &content);
+    }
 
     let detected_format = parsers::detect_format(path);
 
@@ -224,63 +457,155 @@ fn process_one_file(
             config,
             config_dir,
             compiled_catalogs,
+            plugin_schemas,
         );
     }
 
-    // For unrecognized extensions, only proceed if a catalog or config mapping matches.
-    if detected_format.is_none() {
-        let has_match = config.find_schema_mapping(&path_str, file_name).is_some()
+    // For unrecognized extensions and formats that are only validated when
+    // mapped (XML, HCL, Jsonnet, CUE), only proceed if a catalog or config
+    // mapping matches.
+    if matches!(
+        detected_format,
+        None | Some(FileFormat::Xml | FileFormat::Hcl | FileFormat::Jsonnet | FileFormat::Cue)
+    ) {
+        let has_match = config.find_schema_mapping(match_path, file_name).is_some()
             || compiled_catalogs
                 .iter()
-                .any(|cat| cat.find_schema(&path_str, file_name).is_some());
+                .any(|cat| cat.find_schema(match_path, file_name).is_some());
         if !has_match {
-            return vec![FileResult::Skip];
+            return vec![FileResult::NoSchema];
+        }
+    }
+
+    // Markdown files validate their front matter and schema-tagged code
+    // fences separately.
+    if detected_format == Some(FileFormat::Markdown) {
+        let mut results = process_code_fences(path, &path_str, &content, config, config_dir);
+        if let Some(front_matter) = process_document(
+            path,
+            &path_str,
+            file_name,
+            content,
+            detected_format,
+            config,
+            config_dir,
+            compiled_catalogs,
+            plugin_schemas,
+        ) {
+            results.insert(0, front_matter);
+        }
+        return or_skip(results);
+    }
+
+    // Multi-document YAML streams validate each document separately.
+    if detected_format == Some(FileFormat::Yaml) {
+        let documents = parsers::yaml::split_documents(&content);
+        if documents.len() > 1 {
+            return process_yaml_documents(
+                path,
+                &path_str,
+                file_name,
+                &content,
+                &documents,
+                config,
+                config_dir,
+                compiled_catalogs,
+                plugin_schemas,
+            );
         }
     }
 
+    process_document(
+        path,
+        &path_str,
+        file_name,
+        content,
+        detected_format,
+        config,
+        config_dir,
+        compiled_catalogs,
+        plugin_schemas,
+    )
+    .map_or_else(|| vec![FileResult::Skip], |result| vec![result])
+}
+
+/// Parse a single-document file and resolve its schema URI. Returns `None`
+/// when the file should be skipped.
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+fn process_document(
+    path: &Path,
+    path_str: &str,
+    file_name: &str,
+    content: String,
+    detected_format: Option<FileFormat>,
+    config: &lintel_config::Config,
+    config_dir: &Path,
+    compiled_catalogs: &[CompiledCatalog],
+    plugin_schemas: &HashMap<String, String>,
+) -> Option<FileResult> {
+    let match_path = crate::archive::member_path(path_str);
+    let deferred = detected_format.filter(|fmt| {
+        matches!(fmt, FileFormat::Json | FileFormat::Yaml) && content.len() >= LARGE_DOCUMENT_BYTES
+    });
+
     // Parse the file content.
     let (parser, instance): (Box<dyn Parser>, Value) = if let Some(fmt) = detected_format {
         let parser = parsers::parser_for(fmt);
-        match parser.parse(&content, &path_str) {
+        let parsed = if deferred == Some(FileFormat::Json) {
+            parsers::parse_outline(&content, path_str)
+        } else {
+            parser.parse(&content, path_str)
+        };
+        match parsed {
             Ok(val) => (parser, val),
-            Err(parse_err) => return vec![FileResult::Error(parse_err)],
+            Err(parse_err) => return Some(FileResult::Error(parse_err)),
         }
     } else {
-        match try_parse_all(&content, &path_str) {
-            Some((fmt, val)) => (parsers::parser_for(fmt), val),
-            None => return vec![FileResult::Skip],
-        }
+        let (fmt, val) = try_parse_all(&content, path_str)?;
+        (parsers::parser_for(fmt), val)
     };
 
     // Skip markdown files with no frontmatter
     if instance.is_null() {
-        return vec![FileResult::Skip];
+        return None;
     }
 
-    // Schema resolution priority:
-    // 1. Inline $schema / YAML modeline (always wins)
+    // Schema resolution priority (see `choose_schema_uri`):
+    // 1. Inline $schema / YAML modeline, else a `.lintel-schema` sidecar
     // 2. Custom schema mappings from lintel.toml [schemas]
-    // 3. Catalog matching (custom registries > Lintel catalog > SchemaStore)
+    // 3. `resolve` plugins from lintel.toml [plugins]
+    // 4. A Helm chart's values.schema.json, for its values files
+    // 5. Kubernetes apiVersion/kind, when [kubernetes] is enabled
+    // 6. OpenAPI meta-schema, for documents with an `openapi` version
+    // 7. Catalog matching (custom registries > Lintel catalog > SchemaStore)
+    // 8. The SchemaStore chart schema, for a Helm chart's Chart.yaml
     //
     // Track whether the URI came from inline $schema (resolve relative to file)
     // or from config/catalog (resolve relative to config dir).
-    let inline_uri = parser.extract_schema_uri(&content, &instance);
-    let from_inline = inline_uri.is_some();
-    let schema_uri = inline_uri
-        .or_else(|| {
-            config
-                .find_schema_mapping(&path_str, file_name)
-                .map(str::to_string)
-        })
-        .or_else(|| {
-            compiled_catalogs
-                .iter()
-                .find_map(|cat| cat.find_schema(&path_str, file_name))
-                .map(str::to_string)
-        });
-
-    let Some(schema_uri) = schema_uri else {
-        return vec![FileResult::Skip];
+    let Some((schema_uri, from_inline)) = choose_schema_uri(
+        parser
+            .extract_schema_uri(&content, &instance)
+            .or_else(|| lintel_config::sidecar::find_sidecar_schema(path).map(|s| s.uri)),
+        config
+            .find_schema_mapping_entry(match_path, file_name)
+            .map(|(_, m)| m),
+        || {
+            plugin_schemas
+                .get(path_str)
+                .cloned()
+                .or_else(|| crate::helm::values_schema(path))
+                .or_else(|| crate::kubernetes::schema_uri(&config.kubernetes, &instance))
+                .or_else(|| crate::openapi::schema_uri(&instance))
+                .or_else(|| {
+                    compiled_catalogs
+                        .iter()
+                        .find_map(|cat| cat.find_schema(match_path, file_name))
+                        .map(str::to_string)
+                })
+                .or_else(|| crate::helm::chart_schema(path))
+        },
+    ) else {
+        return Some(FileResult::NoSchema);
     };
 
     // Keep original URI for override matching (before rewrites)
@@ -302,15 +627,90 @@ fn process_one_file(
         },
     );
 
-    vec![FileResult::Parsed {
+    // An override's `extract` validates only a fragment of the document,
+    // which is kept however large the document is.
+    let (instance, instance_prefix) = match config.extract_pointer(
+        match_path,
+        &[original_schema_uri.as_str(), schema_uri.as_str()],
+    ) {
+        Some(pointer) => {
+            let document = if deferred == Some(FileFormat::Json) {
+                parser.parse(&content, path_str).ok()?
+            } else {
+                instance
+            };
+            (
+                Instance::Parsed(document.pointer(&pointer)?.clone()),
+                pointer,
+            )
+        }
+        None => (
+            deferred.map_or(Instance::Parsed(instance), |format| {
+                Instance::Deferred(format, std::sync::OnceLock::new())
+            }),
+            String::new(),
+        ),
+    };
+
+    // Markdown: validate only the front matter, with diagnostics offset into
+    // the file.
+    let (content, embedded_in) = if detected_format == Some(FileFormat::Markdown) {
+        front_matter_source(path_str, content)
+    } else {
+        (content, None)
+    };
+
+    Some(FileResult::Parsed {
         schema_uri,
         parsed: ParsedFile {
-            path: path_str,
+            path: path_str.to_string(),
             content,
             instance,
             original_schema_uri,
+            instance_prefix,
+            embedded_in,
         },
-    }]
+    })
+}
+
+/// Split a Markdown file into its front matter and the [`EmbeddedSource`]
+/// locating it in the file.
+fn front_matter_source(path_str: &str, content: String) -> (String, Option<EmbeddedSource>) {
+    let Some((front, offset)) = parsers::markdown::front_matter(&content) else {
+        return (content, None);
+    };
+    let front = front.to_string();
+    let source = EmbeddedSource {
+        path: path_str.to_string(),
+        content,
+        offset,
+    };
+    (front, Some(source))
+}
+
+/// Pick a file's schema URI from its inline declaration, its `[schemas]`
+/// mapping and its catalog match, honouring the mapping's priority.
+///
+/// Returns the URI and whether it came from the inline declaration.
+fn choose_schema_uri(
+    inline: Option<String>,
+    mapping: Option<&SchemaMapping>,
+    catalog: impl FnOnce() -> Option<String>,
+) -> Option<(String, bool)> {
+    let mapped = || mapping.map(|m| m.url().to_string());
+    let priority = mapping.map(SchemaMapping::priority);
+    if priority == Some(MappingPriority::High) {
+        return mapped().map(|uri| (uri, false));
+    }
+    if let Some(uri) = inline {
+        return Some((uri, true));
+    }
+    let uri = if priority == Some(MappingPriority::Low) {
+        catalog().or_else(mapped)
+    } else {
+        mapped().or_else(catalog)
+    };
+    uri.map(|uri| (uri, false))
 }
 
 /// Process a JSONL file: parse each line independently and resolve schemas.
@@ -329,6 +729,7 @@ fn process_jsonl_file(
     config: &lintel_config::Config,
     config_dir: &Path,
     compiled_catalogs: &[CompiledCatalog],
+    plugin_schemas: &HashMap<String, String>,
 ) -> Vec<FileResult> {
     let lines = match parsers::jsonl::parse_jsonl(content, path_str) {
         Ok(lines) => lines,
@@ -339,6 +740,8 @@ fn process_jsonl_file(
         return vec![FileResult::Skip];
     }
 
+    // Line offsets are relative to the content without its BOM.
+    let source = content.strip_prefix('\u{feff}').unwrap_or(content);
     let mut results = Vec::with_capacity(lines.len());
 
     // Check schema consistency before consuming lines.
@@ -352,25 +755,26 @@ fn process_jsonl_file(
         }
     }
 
+    let match_path = crate::archive::member_path(path_str);
+    let sidecar = lintel_config::sidecar::find_sidecar_schema(path).map(|s| s.uri);
     for line in lines {
-        // Schema resolution: inline $schema on line > config > catalog
-        // Track source to resolve relative paths correctly.
-        let inline_uri = parsers::jsonl::extract_schema_uri(&line.value);
-        let from_inline = inline_uri.is_some();
-        let schema_uri = inline_uri
-            .or_else(|| {
-                config
-                    .find_schema_mapping(path_str, file_name)
-                    .map(str::to_string)
-            })
-            .or_else(|| {
-                compiled_catalogs
-                    .iter()
-                    .find_map(|cat| cat.find_schema(path_str, file_name))
-                    .map(str::to_string)
-            });
-
-        let Some(schema_uri) = schema_uri else {
+        // Schema resolution: inline $schema on line (else the sidecar's) >
+        // config > catalog, subject to the mapping's priority. Track source
+        // to resolve relative paths correctly.
+        let Some((schema_uri, from_inline)) = choose_schema_uri(
+            parsers::jsonl::extract_schema_uri(&line.value).or_else(|| sidecar.clone()),
+            config
+                .find_schema_mapping_entry(match_path, file_name)
+                .map(|(_, m)| m),
+            || {
+                plugin_schemas.get(path_str).cloned().or_else(|| {
+                    compiled_catalogs
+                        .iter()
+                        .find_map(|cat| cat.find_schema(match_path, file_name))
+                        .map(str::to_string)
+                })
+            },
+        ) else {
             continue;
         };
 
@@ -396,12 +800,198 @@ fn process_jsonl_file(
             parsed: ParsedFile {
                 path: line_path,
                 content: line.raw,
-                instance: line.value,
+                instance: Instance::Parsed(line.value),
+                original_schema_uri,
+                instance_prefix: String::new(),
+                // Point diagnostics at the line within the whole file.
+                embedded_in: Some(EmbeddedSource {
+                    path: path_str.to_string(),
+                    content: source.to_string(),
+                    offset: line.byte_offset,
+                }),
+            },
+        });
+    }
+
+    if results.is_empty() {
+        return vec![FileResult::NoSchema];
+    }
+    results
+}
+
+/// Process the documents of a multi-document YAML stream.
+///
+/// Each document becomes its own [`FileResult::Parsed`] named `file.yaml:LINE`
+/// after its first line, with diagnostics pointing into the whole file.
+/// Schema resolution per document follows [`process_document`]: the
+/// document's own modeline > its inline `$schema` key > a `.lintel-schema`
+/// sidecar > config mapping > Kubernetes, OpenAPI and catalog matching.
+#[allow(clippy::too_many_arguments)]
+fn process_yaml_documents(
+    path: &Path,
+    path_str: &str,
+    file_name: &str,
+    content: &str,
+    documents: &[(usize, &str)],
+    config: &lintel_config::Config,
+    config_dir: &Path,
+    compiled_catalogs: &[CompiledCatalog],
+    plugin_schemas: &HashMap<String, String>,
+) -> Vec<FileResult> {
+    let mut results = Vec::with_capacity(documents.len());
+    for &(offset, text) in documents {
+        let Some(result) = process_document(
+            path,
+            path_str,
+            file_name,
+            text.to_string(),
+            Some(FileFormat::Yaml),
+            config,
+            config_dir,
+            compiled_catalogs,
+            plugin_schemas,
+        ) else {
+            continue;
+        };
+        let line_number = content[..offset].lines().count() + 1;
+        results.push(match result {
+            FileResult::Parsed { schema_uri, parsed } => FileResult::Parsed {
+                schema_uri,
+                parsed: ParsedFile {
+                    path: format!("{path_str}:{line_number}"),
+                    embedded_in: Some(EmbeddedSource {
+                        path: path_str.to_string(),
+                        content: content.to_string(),
+                        offset,
+                    }),
+                    ..parsed
+                },
+            },
+            FileResult::Error(LintelDiagnostic::Parse { span, message, .. }) => {
+                FileResult::Error(LintelDiagnostic::Parse {
+                    src: miette::NamedSource::new(path_str, content.to_string()),
+                    span: (offset + span.offset(), span.len()).into(),
+                    message,
+                })
+            }
+            other => other,
+        });
+    }
+    or_skip(results)
+}
+
+/// Check a Go template, such as a Helm chart template, for the YAML syntax
+/// errors left once its actions are stubbed out (see [`crate::template`]).
+/// Templates are not validated against a schema: their values are only
+/// known once rendered.
+fn process_template(path_str: &str, content: &str) -> Vec<FileResult> {
+    let stubbed = crate::template::stub(content);
+    let mut results = Vec::new();
+    for (offset, text) in parsers::yaml::split_documents(&stubbed) {
+        if let Err(LintelDiagnostic::Parse { span, message, .. }) =
+            parsers::YamlParser.parse(text, path_str)
+        {
+            results.push(FileResult::Error(LintelDiagnostic::Parse {
+                src: miette::NamedSource::new(path_str, content.to_string()),
+                span: (offset + span.offset(), span.len()).into(),
+                message,
+            }));
+        }
+    }
+    or_skip(results)
+}
+
+/// Process the code fences of a Markdown file that have a schema.
+///
+/// Each fence becomes its own [`FileResult::Parsed`] named `file.md:LINE`
+/// after its opening line, with diagnostics pointing into the Markdown file.
+/// Schema resolution per fence: `schema=` in the info string > inline
+/// `$schema` in the fence > the front matter's `fence-schemas` entry for the
+/// fence's language. Fences without a schema are skipped, including ones that
+/// do not parse.
+#[allow(clippy::too_many_arguments)]
+fn process_code_fences(
+    path: &Path,
+    path_str: &str,
+    content: &str,
+    config: &lintel_config::Config,
+    config_dir: &Path,
+) -> Vec<FileResult> {
+    let fences = parsers::markdown::code_fences(content);
+    if fences.is_empty() {
+        return Vec::new();
+    }
+    let front_matter = parsers::MarkdownParser
+        .parse(content, path_str)
+        .unwrap_or(Value::Null);
+
+    let mut results = Vec::new();
+    for fence in fences {
+        let Some(format) = parsers::markdown::fence_format(fence.lang) else {
+            continue;
+        };
+        let declared = fence.schema.map(str::to_string).or_else(|| {
+            front_matter
+                .get(parsers::markdown::FENCE_SCHEMAS_KEY)
+                .and_then(|schemas| schemas.get(fence.lang))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+
+        let parser = parsers::parser_for(format);
+        let instance = match parser.parse(fence.body, path_str) {
+            Ok(instance) => instance,
+            Err(_) if declared.is_none() => continue,
+            Err(LintelDiagnostic::Parse { span, message, .. }) => {
+                results.push(FileResult::Error(LintelDiagnostic::Parse {
+                    src: miette::NamedSource::new(path_str, content.to_string()),
+                    span: (fence.offset + span.offset(), span.len()).into(),
+                    message,
+                }));
+                continue;
+            }
+            Err(other) => {
+                results.push(FileResult::Error(other));
+                continue;
+            }
+        };
+        let Some(schema_uri) = fence
+            .schema
+            .map(str::to_string)
+            .or_else(|| parser.extract_schema_uri(fence.body, &instance))
+            .or(declared)
+        else {
+            continue;
+        };
+
+        let original_schema_uri = schema_uri.clone();
+
+        let schema_uri = lintel_config::apply_rewrites(&schema_uri, &config.rewrite);
+        let schema_uri = lintel_config::resolve_double_slash(&schema_uri, config_dir);
+        // Declared in the Markdown file, so relative to its directory.
+        let schema_uri = resolve_local_schema_path(&schema_uri, path.parent());
+
+        results.push(FileResult::Parsed {
+            schema_uri,
+            parsed: ParsedFile {
+                path: format!("{path_str}:{}", fence.line_number),
+                content: fence.body.to_string(),
+                instance: Instance::Parsed(instance),
                 original_schema_uri,
+                instance_prefix: String::new(),
+                embedded_in: Some(EmbeddedSource {
+                    path: path_str.to_string(),
+                    content: content.to_string(),
+                    offset: fence.offset,
+                }),
             },
         });
     }
+    results
+}
 
+/// A single [`FileResult::Skip`] when there are no results.
+fn or_skip(results: Vec<FileResult>) -> Vec<FileResult> {
     if results.is_empty() {
         vec![FileResult::Skip]
     } else {
@@ -410,7 +1000,8 @@ fn process_jsonl_file(
 }
 
 /// Read files concurrently with tokio, using a semaphore to avoid exhausting
-/// file descriptors. I/O errors are pushed as `LintelDiagnostic::Io`.
+/// file descriptors. Tar and zip archives are unpacked into their members,
+/// named `<archive>!<member>`. I/O errors are pushed as `LintelDiagnostic::Io`.
 ///
 /// # Panics
 ///
@@ -427,7 +1018,15 @@ pub async fn read_files(
         let sem = semaphore.clone();
         read_set.spawn(async move {
             let _permit = sem.acquire().await.expect("semaphore closed");
-            let result = tokio::fs::read_to_string(&path).await;
+            let result = if crate::archive::is_archive(&path) {
+                tokio::fs::read(&path)
+                    .await
+                    .and_then(|bytes| crate::archive::read_archive(&path, &bytes))
+            } else {
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .map(|content| vec![(path.clone(), content)])
+            };
             (path, result)
         });
     }
@@ -435,7 +1034,7 @@ pub async fn read_files(
     let mut file_contents = Vec::with_capacity(files.len());
     while let Some(result) = read_set.join_next().await {
         match result {
-            Ok((path, Ok(content))) => file_contents.push((path, content)),
+            Ok((_, Ok(documents))) => file_contents.extend(documents),
             Ok((path, Err(e))) => {
                 errors.push(LintelDiagnostic::Io {
                     path: path.display().to_string(),
@@ -450,7 +1049,9 @@ pub async fn read_files(
 }
 
 /// Parse pre-read file contents, extract schema URIs, apply rewrites, and
-/// group by resolved schema URI.
+/// group by resolved schema URI. URIs that are the `$id` of a workspace
+/// schema resolve to its file. Files none of whose documents have a schema
+/// are pushed to `without_schema`.
 #[tracing::instrument(skip_all, fields(file_count = file_contents.len()))]
 #[allow(clippy::too_many_arguments)]
 fn parse_and_group_contents(
@@ -458,18 +1059,38 @@ fn parse_and_group_contents(
     config: &lintel_config::Config,
     config_dir: &Path,
     compiled_catalogs: &[CompiledCatalog],
+    plugin_schemas: &HashMap<String, String>,
+    schema_index: &LazySchemaIndex,
     errors: &mut Vec<LintelDiagnostic>,
+    without_schema: &mut Vec<String>,
 ) -> BTreeMap<String, Vec<ParsedFile>> {
     let mut schema_groups: BTreeMap<String, Vec<ParsedFile>> = BTreeMap::new();
     for (path, content) in file_contents {
-        let results = process_one_file(&path, content, config, config_dir, compiled_catalogs);
+        let results = process_one_file(
+            &path,
+            content,
+            config,
+            config_dir,
+            compiled_catalogs,
+            plugin_schemas,
+        );
+        if results.iter().any(|r| matches!(r, FileResult::NoSchema))
+            && results
+                .iter()
+                .all(|r| matches!(r, FileResult::NoSchema | FileResult::Skip))
+        {
+            without_schema.push(path.display().to_string());
+        }
         for result in results {
             match result {
                 FileResult::Parsed { schema_uri, parsed } => {
+                    let schema_uri = schema_index
+                        .resolve(&schema_uri)
+                        .map_or(schema_uri, |path| path.to_string_lossy().to_string());
                     schema_groups.entry(schema_uri).or_default().push(parsed);
                 }
                 FileResult::Error(e) => errors.push(e),
-                FileResult::Skip => {}
+                FileResult::NoSchema | FileResult::Skip => {}
             }
         }
     }
@@ -500,7 +1121,7 @@ async fn fetch_schema_from_prefetched(
     let result: Result<(Value, Option<CacheStatus>), String> = if is_remote {
         match prefetched.get(schema_uri) {
             Some(Ok((v, status))) => Ok((v.clone(), Some(*status))),
-            Some(Err(e)) => Err(format!("failed to fetch schema: {schema_uri}: {e}")),
+            Some(Err(message)) => Err(message.clone()),
             None => Err(format!("schema not prefetched: {schema_uri}")),
         }
     } else if let Some(cached) = local_cache.get(schema_uri) {
@@ -557,6 +1178,7 @@ fn report_group_error<P: alloc::borrow::Borrow<ParsedFile>>(
             schema: schema_uri.to_string(),
             cache_status,
             validation_cache_status: None,
+            duration: None,
         };
         on_check(&cf);
         checked.push(cf);
@@ -581,6 +1203,7 @@ fn mark_group_checked<P: alloc::borrow::Borrow<ParsedFile>>(
             schema: schema_uri.to_string(),
             cache_status,
             validation_cache_status,
+            duration: None,
         };
         on_check(&cf);
         checked.push(cf);
@@ -596,214 +1219,436 @@ fn push_validation_errors(
     errors: &mut Vec<LintelDiagnostic>,
     schema: Option<&Value>,
 ) {
-    for ve in validation_errors {
-        let instance_path = if ve.instance_path.is_empty() {
-            DEFAULT_LABEL.to_string()
-        } else {
-            ve.instance_path.clone()
-        };
-        let label = format_label(&instance_path, &ve.schema_path);
-        let source_span: miette::SourceSpan = ve.span.into();
-        let mut message = ve.kind.message();
-        if let ValidationErrorKind::AdditionalProperty { ref property } = ve.kind
-            && let Some(s) = schema
-            && let Some(suggestion) = suggest::suggest_property(property, &ve.schema_path, s)
+    if validation_errors.is_empty() {
+        return;
+    }
+    let source = match &pf.embedded_in {
+        Some(e) => DiagnosticSource {
+            path: &pf.path,
+            file: &e.path,
+            content: &e.content,
+            offset: e.offset,
+        },
+        None => DiagnosticSource {
+            path: &pf.path,
+            file: &pf.path,
+            content: &pf.content,
+            offset: 0,
+        },
+    };
+    let instance = match pf.instance() {
+        Ok(instance) => instance,
+        Err(parse_error) => {
+            errors.push(parse_error);
+            return;
+        }
+    };
+    crate::document::push_validation_errors(
+        &source,
+        instance,
+        &pf.instance_prefix,
+        schema_url,
+        validation_errors,
+        errors,
+        schema,
+    );
+}
+
+/// Validate the examples and defaults of every OpenAPI document against the
+/// schemas they belong to. Diagnostics name the schema as `file#pointer`.
+fn check_openapi_examples(
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+    errors: &mut Vec<LintelDiagnostic>,
+) {
+    for pf in schema_groups.values().flatten() {
+        let instance = match pf.instance() {
+            Ok(instance) => instance,
+            Err(parse_error) => {
+                errors.push(parse_error);
+                continue;
+            }
+        };
+        for (schema_pointer, example_errors) in
+            crate::openapi::check_examples(instance, &pf.content)
         {
-            message = format!("{message}; did you mean '{suggestion}'?");
+            let schema_url = format!("{}#{schema_pointer}", pf.path);
+            push_validation_errors(pf, &schema_url, &example_errors, errors, None);
         }
-        errors.push(LintelDiagnostic::Validation(ValidationDiagnostic {
-            src: miette::NamedSource::new(&pf.path, pf.content.clone()),
-            span: source_span,
-            schema_span: source_span,
-            path: pf.path.clone(),
-            instance_path,
-            label,
-            message,
-            schema_url: schema_url.to_string(),
-            schema_path: ve.schema_path.clone(),
-            validation_code: format!("validation({})", ve.kind.as_ref()),
-        }));
     }
 }
 
-/// Map a `jsonschema::error::ValidationErrorKind` to our serializable
-/// [`ValidationErrorKind`]. `AdditionalProperties` is handled separately
-/// in [`convert_error`].
-fn convert_kind(kind: &jsonschema::error::ValidationErrorKind) -> ValidationErrorKind {
-    use jsonschema::error::{TypeKind, ValidationErrorKind as JK};
-
-    match kind {
-        JK::AdditionalItems { limit } => ValidationErrorKind::AdditionalItems { limit: *limit },
-        JK::AdditionalProperties { .. } => unreachable!("handled in convert_error"),
-        JK::AnyOf { .. } => ValidationErrorKind::AnyOf,
-        JK::BacktrackLimitExceeded { error } => ValidationErrorKind::BacktrackLimitExceeded {
-            message: error.to_string(),
-        },
-        JK::Constant { expected_value } => ValidationErrorKind::Constant {
-            expected_value: expected_value.clone(),
-        },
-        JK::Contains => ValidationErrorKind::Contains,
-        JK::ContentEncoding { content_encoding } => ValidationErrorKind::ContentEncoding {
-            content_encoding: content_encoding.clone(),
-        },
-        JK::ContentMediaType { content_media_type } => ValidationErrorKind::ContentMediaType {
-            content_media_type: content_media_type.clone(),
-        },
-        JK::Custom { keyword, message } => ValidationErrorKind::Custom {
-            keyword: keyword.clone(),
-            message: message.clone(),
-        },
-        JK::Enum { options } => ValidationErrorKind::Enum {
-            options: options.clone(),
-        },
-        JK::ExclusiveMaximum { limit } => ValidationErrorKind::ExclusiveMaximum {
-            limit: limit.clone(),
-        },
-        JK::ExclusiveMinimum { limit } => ValidationErrorKind::ExclusiveMinimum {
-            limit: limit.clone(),
-        },
-        JK::FalseSchema => ValidationErrorKind::FalseSchema,
-        JK::Format { format } => ValidationErrorKind::Format {
-            format: format.clone(),
-        },
-        JK::FromUtf8 { error } => ValidationErrorKind::FromUtf8 {
-            message: error.to_string(),
-        },
-        JK::MaxItems { limit } => ValidationErrorKind::MaxItems { limit: *limit },
-        JK::Maximum { limit } => ValidationErrorKind::Maximum {
-            limit: limit.clone(),
-        },
-        JK::MaxLength { limit } => ValidationErrorKind::MaxLength { limit: *limit },
-        JK::MaxProperties { limit } => ValidationErrorKind::MaxProperties { limit: *limit },
-        JK::MinItems { limit } => ValidationErrorKind::MinItems { limit: *limit },
-        JK::Minimum { limit } => ValidationErrorKind::Minimum {
-            limit: limit.clone(),
-        },
-        JK::MinLength { limit } => ValidationErrorKind::MinLength { limit: *limit },
-        JK::MinProperties { limit } => ValidationErrorKind::MinProperties { limit: *limit },
-        JK::MultipleOf { multiple_of } => ValidationErrorKind::MultipleOf {
-            multiple_of: *multiple_of,
-        },
-        JK::Not { .. } => ValidationErrorKind::Not,
-        JK::OneOfMultipleValid { .. } => ValidationErrorKind::OneOfMultipleValid,
-        JK::OneOfNotValid { .. } => ValidationErrorKind::OneOfNotValid,
-        JK::Pattern { pattern } => ValidationErrorKind::Pattern {
-            pattern: pattern.clone(),
-        },
-        JK::PropertyNames { error } => ValidationErrorKind::PropertyNames {
-            message: error.to_string(),
-        },
-        JK::Required { property } => ValidationErrorKind::Required {
-            property: match property {
-                Value::String(s) => format!("\"{s}\""),
-                other => other.to_string(),
-            },
-        },
-        JK::Type { kind } => {
-            let expected = match kind {
-                TypeKind::Single(t) => t.to_string(),
-                TypeKind::Multiple(ts) => {
-                    let parts: Vec<String> = ts.iter().map(|t| t.to_string()).collect();
-                    parts.join(", ")
+/// Check the `${{ }}` expressions of GitHub workflows and actions for
+/// unknown contexts and functions.
+fn check_github_expressions(
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+    errors: &mut Vec<LintelDiagnostic>,
+) {
+    for (schema_uri, group) in schema_groups {
+        for pf in group.iter().filter(|pf| {
+            crate::expressions::applies_to(schema_uri)
+                || crate::expressions::applies_to(&pf.original_schema_uri)
+        }) {
+            let instance = match pf.instance() {
+                Ok(instance) => instance,
+                Err(parse_error) => {
+                    errors.push(parse_error);
+                    continue;
                 }
             };
-            ValidationErrorKind::Type { expected }
+            let expression_errors =
+                crate::expressions::check(instance, &pf.content, &pf.instance_prefix);
+            push_validation_errors(pf, schema_uri, &expression_errors, errors, None);
         }
-        JK::UnevaluatedItems { unexpected } => ValidationErrorKind::UnevaluatedItems {
-            unexpected: unexpected.clone(),
-        },
-        JK::UnevaluatedProperties { unexpected } => ValidationErrorKind::UnevaluatedProperties {
-            unexpected: unexpected.clone(),
-        },
-        JK::UniqueItems => ValidationErrorKind::UniqueItems,
-        JK::Referencing(err) => ValidationErrorKind::Referencing {
-            message: err.to_string(),
-        },
     }
 }
 
-/// Convert a single `jsonschema::ValidationError` into one or more typed
-/// [`ValidationError`]s with pre-computed spans.
-///
-/// `AdditionalProperties` errors are split into one per unexpected property.
-fn convert_error(error: &jsonschema::ValidationError<'_>, content: &str) -> Vec<ValidationError> {
-    use jsonschema::error::ValidationErrorKind as JK;
+/// Send the validated documents a `validate` plugin of `[plugins]` matches
+/// to it and report their diagnostics. Plugins match the file a document is
+/// in, so every document of a JSON Lines file or Markdown file is sent.
+async fn run_validate_plugins(
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+    config: &lintel_config::Config,
+    config_dir: &Path,
+    errors: &mut Vec<LintelDiagnostic>,
+) -> Result<()> {
+    let mut parsed: Vec<(&String, &ParsedFile)> = Vec::new();
+    let mut files: Vec<crate::plugin::PluginFile<'_>> = Vec::new();
+    let matched = schema_groups
+        .iter()
+        .flat_map(|(schema_uri, group)| group.iter().map(move |pf| (schema_uri, pf)))
+        .map(|(schema_uri, pf)| {
+            let file = pf.embedded_in.as_ref().map_or(&pf.path, |e| &e.path);
+            (schema_uri, pf, file)
+        })
+        .filter(|(_, _, file)| crate::plugin::validates(config, file));
+    for (schema_uri, pf, file) in matched {
+        match pf.instance() {
+            Ok(document) => {
+                parsed.push((schema_uri, pf));
+                files.push(crate::plugin::PluginFile {
+                    path: file,
+                    schema: schema_uri,
+                    document,
+                    content: &pf.content,
+                    instance_prefix: &pf.instance_prefix,
+                });
+            }
+            Err(parse_error) => errors.push(parse_error),
+        }
+    }
+    if files.is_empty() {
+        return Ok(());
+    }
+    for (index, error) in crate::plugin::validate(config, config_dir, &files).await? {
+        let (schema_uri, pf) = parsed[index];
+        push_validation_errors(pf, schema_uri, &[error], errors, None);
+    }
+    Ok(())
+}
+
+/// Drop validation errors silenced by `lintel-ignore` comments. With
+/// `report_unused`, comments that silenced nothing are reported as warnings.
+fn apply_suppressions(
+    errors: &mut Vec<LintelDiagnostic>,
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+    report_unused: bool,
+) {
+    // Embedded documents share their file's comments, so visit files once.
+    let sources: BTreeMap<&str, &str> = schema_groups
+        .values()
+        .flatten()
+        .map(|pf| match &pf.embedded_in {
+            Some(e) => (e.path.as_str(), e.content.as_str()),
+            None => (pf.path.as_str(), pf.content.as_str()),
+        })
+        .collect();
+    for (path, content) in sources {
+        let mut suppressions = crate::suppress::parse(content);
+        if suppressions.is_empty() {
+            continue;
+        }
+        crate::suppress::apply(errors, path, &mut suppressions);
+        if !report_unused {
+            continue;
+        }
+        for suppression in suppressions.iter().filter(|s| !s.used) {
+            errors.push(LintelDiagnostic::UnusedSuppression {
+                src: miette::NamedSource::new(path, content.to_string()),
+                span: (suppression.offset, suppression.len).into(),
+                message: "lintel-ignore comment does not silence any error".to_string(),
+            });
+        }
+    }
+}
 
-    let schema_path = error.schema_path().to_string();
-    let base_instance_path = error.instance_path().to_string();
+/// Apply `ignore_keywords`, `messages` and `severity` from `[[override]]`
+/// blocks to validation diagnostics: drop errors from ignored keywords and
+/// from files turned `off`, replace messages, and mark downgraded ones as
+/// warnings.
+fn apply_overrides(
+    errors: &mut Vec<LintelDiagnostic>,
+    config: &lintel_config::Config,
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+) {
+    use lintel_config::Severity;
 
-    if let JK::AdditionalProperties { unexpected } = error.kind() {
-        return unexpected
-            .iter()
-            .map(|prop| {
-                let instance_path = format!("{base_instance_path}/{prop}");
-                let span = find_instance_path_span(content, &instance_path);
-                ValidationError {
-                    instance_path,
-                    schema_path: schema_path.clone(),
-                    kind: ValidationErrorKind::AdditionalProperty {
-                        property: prop.clone(),
-                    },
-                    span,
-                }
-            })
-            .collect();
+    let original_uris: HashMap<&str, &str> = schema_groups
+        .values()
+        .flatten()
+        .map(|pf| (pf.path.as_str(), pf.original_schema_uri.as_str()))
+        .collect();
+    errors.retain_mut(|error| {
+        let LintelDiagnostic::Validation(v) = error else {
+            return true;
+        };
+        let original = original_uris.get(v.path.as_str()).copied().unwrap_or("");
+        let schema_uris = [original, v.schema_url.as_str()];
+        // Overrides match the file, also for documents embedded in it
+        // (JSONL lines, Markdown code fences) that report as `file:line`.
+        let path = v.src.name().to_string();
+        if config
+            .ignored_keywords(&path, &schema_uris)
+            .contains(&error_keyword(&v.schema_path))
+        {
+            return false;
+        }
+        if let Some(template) = config.error_message(&path, &schema_uris, &v.schema_path) {
+            let instance_path = v.instance_path.as_str();
+            let instance_path = if instance_path == DEFAULT_LABEL {
+                ""
+            } else {
+                instance_path
+            };
+            v.message = describe::fill_message_template(template, &v.message, instance_path);
+        }
+        match config.severity_for(&path, &schema_uris) {
+            Severity::Error => true,
+            Severity::Warning => {
+                v.severity = Some(miette::Severity::Warning);
+                true
+            }
+            Severity::Off => false,
+        }
+    });
+}
+
+/// Whether `diagnostics` of `pf` fail the run once `lintel-ignore` comments
+/// and overrides are applied, for `--fail-fast`.
+fn file_fails(
+    pf: &ParsedFile,
+    diagnostics: &[LintelDiagnostic],
+    config: &lintel_config::Config,
+) -> bool {
+    let (path, content) = pf
+        .embedded_in
+        .as_ref()
+        .map_or((&pf.path, &pf.content), |e| (&e.path, &e.content));
+    let suppressions = crate::suppress::parse(content);
+    diagnostics.iter().any(|error| {
+        let LintelDiagnostic::Validation(v) = error else {
+            return !error.is_warning();
+        };
+        let schema_uris = [pf.original_schema_uri.as_str(), v.schema_url.as_str()];
+        !error.is_warning()
+            && !suppressions.iter().any(|s| s.silences(v))
+            && !config
+                .ignored_keywords(path, &schema_uris)
+                .contains(&error_keyword(&v.schema_path))
+            && config.severity_for(path, &schema_uris) == lintel_config::Severity::Error
+    })
+}
+
+/// Sort errors for deterministic output (by path, then by span offset) and
+/// apply `--max-errors`.
+fn sort_errors(errors: &mut Vec<LintelDiagnostic>, max_errors: Option<usize>) {
+    errors.sort_by(|a, b| {
+        a.path()
+            .cmp(b.path())
+            .then_with(|| a.offset().cmp(&b.offset()))
+    });
+    if let Some(max) = max_errors {
+        limit_errors(errors, max);
     }
+}
 
-    let span = find_instance_path_span(content, &base_instance_path);
-    vec![ValidationError {
-        instance_path: base_instance_path,
-        schema_path,
-        kind: convert_kind(error.kind()),
-        span,
-    }]
+/// Keep the first `max` diagnostics of each file and replace the rest with
+/// a [`LintelDiagnostic::Truncated`] count. `errors` must be sorted by path.
+fn limit_errors(errors: &mut Vec<LintelDiagnostic>, max: usize) {
+    let mut limited = Vec::with_capacity(errors.len());
+    let mut rest = core::mem::take(errors).into_iter().peekable();
+    while let Some(first) = rest.next() {
+        let path = first.path().to_string();
+        let mut file = vec![first];
+        while let Some(next) = rest.next_if(|e| e.path() == path) {
+            file.push(next);
+        }
+        let hidden = file.split_off(max.min(file.len()));
+        limited.extend(file);
+        if !hidden.is_empty() {
+            limited.push(LintelDiagnostic::Truncated {
+                message: format!("+{} more", hidden.len()),
+                warning: hidden.iter().all(LintelDiagnostic::is_warning),
+                path,
+            });
+        }
+    }
+    *errors = limited;
 }
 
 /// Validate all files in a group against an already-compiled validator and store
-/// results in the validation cache.
+/// results in the validation cache. Returns `true` when `stop_after` stopped
+/// validation after a file.
 #[tracing::instrument(skip_all, fields(schema_uri, file_count = group.len()))]
 #[allow(clippy::too_many_arguments)]
 async fn validate_group<P: alloc::borrow::Borrow<ParsedFile>>(
-    validator: &jsonschema::Validator,
+    validator: &alloc::sync::Arc<jsonschema::Validator>,
     schema_uri: &str,
     schema_hash: &str,
+    config_hash: &str,
     validate_formats: bool,
+    strict_instances: bool,
+    timeout: Option<core::time::Duration>,
     cache_status: Option<CacheStatus>,
     group: &[P],
-    schema_value: &Value,
+    schema_value: &alloc::sync::Arc<Value>,
     vcache: &lintel_validation_cache::ValidationCache,
     errors: &mut Vec<LintelDiagnostic>,
     checked: &mut Vec<CheckedFile>,
     on_check: &mut impl FnMut(&CheckedFile),
-) {
+    stop_after: &impl Fn(&ParsedFile, &[LintelDiagnostic]) -> bool,
+) -> bool {
+    let deprecations = crate::deprecated::marks_deprecated(schema_value);
     for item in group {
         let pf = item.borrow();
-        let file_errors: Vec<ValidationError> = validator
-            .iter_errors(&pf.instance)
-            .flat_map(|error| convert_error(&error, &pf.content))
-            .collect();
+        let start = std::time::Instant::now();
+        // A parse error or a timeout takes the place of the file's result.
+        let file_errors = match (pf.instance(), timeout) {
+            (Err(parse_error), _) => Err(parse_error),
+            (Ok(instance), Some(timeout)) => document_errors_within(
+                timeout,
+                validator,
+                schema_value,
+                pf,
+                instance,
+                strict_instances,
+                deprecations,
+            )
+            .ok_or_else(|| LintelDiagnostic::Timeout {
+                path: pf.path.clone(),
+                message: format!(
+                    "validation did not finish within {}",
+                    humantime::format_duration(timeout)
+                ),
+            }),
+            (Ok(instance), None) => Ok(document_errors(
+                validator,
+                schema_value,
+                instance,
+                &pf.content,
+                &pf.instance_prefix,
+                strict_instances,
+                deprecations,
+            )),
+        };
+        let duration = start.elapsed();
+        let before = errors.len();
+        let file_errors = match file_errors {
+            Ok(file_errors) => file_errors,
+            Err(diagnostic) => {
+                errors.push(diagnostic);
+                let cf = CheckedFile {
+                    path: pf.path.clone(),
+                    schema: schema_uri.to_string(),
+                    cache_status,
+                    validation_cache_status: None,
+                    duration: Some(duration),
+                };
+                on_check(&cf);
+                checked.push(cf);
+                if stop_after(pf, &errors[before..]) {
+                    return true;
+                }
+                continue;
+            }
+        };
 
         vcache
             .store(
                 &lintel_validation_cache::CacheKey {
                     file_content: &pf.content,
+                    instance_prefix: &pf.instance_prefix,
                     schema_hash,
+                    config_hash,
                     validate_formats,
                 },
                 &file_errors,
             )
             .await;
-        push_validation_errors(pf, schema_uri, &file_errors, errors, Some(schema_value));
+        push_validation_errors(pf, schema_uri, &file_errors, errors, Some(&**schema_value));
 
         let cf = CheckedFile {
             path: pf.path.clone(),
             schema: schema_uri.to_string(),
             cache_status,
             validation_cache_status: Some(ValidationCacheStatus::Miss),
+            duration: Some(duration),
         };
         on_check(&cf);
         checked.push(cf);
+        if stop_after(pf, &errors[before..]) {
+            return true;
+        }
+    }
+    false
+}
+
+/// [`document_errors`] on a thread of its own, given up on after `timeout`.
+/// `None` when it did not finish in time. Validation cannot be interrupted,
+/// so the thread is left to run until it finishes or the process exits.
+#[allow(clippy::too_many_arguments)]
+fn document_errors_within(
+    timeout: core::time::Duration,
+    validator: &alloc::sync::Arc<jsonschema::Validator>,
+    schema_value: &alloc::sync::Arc<Value>,
+    pf: &ParsedFile,
+    instance: &Value,
+    strict_instances: bool,
+    deprecations: bool,
+) -> Option<Vec<ValidationError>> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let spawned = {
+        let validator = alloc::sync::Arc::clone(validator);
+        let schema_value = alloc::sync::Arc::clone(schema_value);
+        let instance = instance.clone();
+        let content = pf.content.clone();
+        let instance_prefix = pf.instance_prefix.clone();
+        std::thread::Builder::new()
+            .name("lintel-validate".to_string())
+            .spawn(move || {
+                let _ = tx.send(document_errors(
+                    &validator,
+                    &schema_value,
+                    &instance,
+                    &content,
+                    &instance_prefix,
+                    strict_instances,
+                    deprecations,
+                ));
+            })
+    };
+    if spawned.is_err() {
+        // Out of threads: validate here, without a time limit.
+        return Some(document_errors(
+            validator,
+            schema_value,
+            instance,
+            &pf.content,
+            &pf.instance_prefix,
+            strict_instances,
+            deprecations,
+        ));
     }
+    rx.recv_timeout(timeout).ok()
 }
 
 // ---------------------------------------------------------------------------
@@ -816,6 +1661,7 @@ async fn validate_group<P: alloc::borrow::Borrow<ParsedFile>>(
 pub async fn fetch_compiled_catalogs(
     retriever: &SchemaCache,
     config: &lintel_config::Config,
+    config_dir: &Path,
     no_catalog: bool,
 ) -> Vec<CompiledCatalog> {
     let mut compiled_catalogs = Vec::new();
@@ -834,16 +1680,19 @@ pub async fn fetch_compiled_catalogs(
         );
         let mut catalog_tasks: tokio::task::JoinSet<CatalogResult> = tokio::task::JoinSet::new();
 
-        // Custom registries from lintel.toml (highest precedence among catalogs)
+        // Custom registries from lintel.toml (highest precedence among catalogs).
+        // `//`-prefixed registries are local catalog files or schema directories.
         for (i, registry_url) in config.registries.iter().enumerate() {
             let r = retriever.clone();
             let url = registry_url.clone();
             let label = format!("registry {url}");
+            let local = registry::local_path(&url, config_dir);
             catalog_tasks.spawn(async move {
-                let result = registry::fetch(&r, &url)
-                    .await
-                    .map(|cat| CompiledCatalog::compile(&cat));
-                (i, label, result)
+                let result = match local {
+                    Some(path) => registry::load_local(&r, &path),
+                    None => registry::fetch(&r, &url).await,
+                };
+                (i, label, result.map(|cat| CompiledCatalog::compile(&cat)))
             });
         }
 
@@ -905,8 +1754,15 @@ pub async fn run_with(
     cache: Option<SchemaCache>,
     on_check: impl FnMut(&CheckedFile),
 ) -> Result<CheckResult> {
-    let files = collect_files(&args.globs, &args.exclude)?;
-    run_with_files(args, cache, files, on_check).await
+    if !args.globs.iter().any(|g| is_url(g)) {
+        let files = collect_files(&args.globs, &args.exclude)?;
+        return run_with_files(args, cache, files, on_check).await;
+    }
+    let mut read_errors = Vec::new();
+    let documents = read_documents(args, &mut read_errors).await?;
+    let mut result = run_with_contents(args, documents, cache, on_check).await?;
+    result.errors.extend(read_errors);
+    Ok(result)
 }
 
 /// Like [`run_with`] but operates on a pre-discovered file list.
@@ -925,11 +1781,12 @@ pub async fn run_with_files(
     files: Vec<PathBuf>,
     mut on_check: impl FnMut(&CheckedFile),
 ) -> Result<CheckResult> {
-    let retriever = build_retriever(args, cache);
     let (config, config_dir, _config_path) = load_config(args.config_dir.as_deref());
+    let retriever = cache.unwrap_or_else(|| build_retriever(args, &config));
     tracing::info!(file_count = files.len(), "collected files");
 
-    let compiled_catalogs = fetch_compiled_catalogs(&retriever, &config, args.no_catalog).await;
+    let compiled_catalogs =
+        fetch_compiled_catalogs(&retriever, &config, &config_dir, args.no_catalog).await;
 
     let mut errors: Vec<LintelDiagnostic> = Vec::new();
     let file_contents = read_files(&files, &mut errors).await;
@@ -938,7 +1795,7 @@ pub async fn run_with_files(
         file_contents,
         args,
         retriever,
-        config,
+        &config,
         &config_dir,
         compiled_catalogs,
         errors,
@@ -958,11 +1815,31 @@ pub async fn run_with_contents(
     args: &ValidateArgs,
     file_contents: Vec<(PathBuf, String)>,
     cache: Option<SchemaCache>,
-    mut on_check: impl FnMut(&CheckedFile),
+    on_check: impl FnMut(&CheckedFile),
 ) -> Result<CheckResult> {
-    let retriever = build_retriever(args, cache);
     let (config, config_dir, _config_path) = load_config(args.config_dir.as_deref());
-    let compiled_catalogs = fetch_compiled_catalogs(&retriever, &config, args.no_catalog).await;
+    run_with_config(args, &config, &config_dir, file_contents, cache, on_check).await
+}
+
+/// Like [`run_with_contents`], but with a config the caller loaded or built
+/// instead of the `lintel.toml` found from `args.config_dir`. `config_dir`
+/// is the directory `//` paths and mapped local schemas resolve against.
+///
+/// # Errors
+///
+/// Returns an error if schema validation encounters an I/O or network error.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_with_config(
+    args: &ValidateArgs,
+    config: &lintel_config::Config,
+    config_dir: &Path,
+    file_contents: Vec<(PathBuf, String)>,
+    cache: Option<SchemaCache>,
+    mut on_check: impl FnMut(&CheckedFile),
+) -> Result<CheckResult> {
+    let retriever = cache.unwrap_or_else(|| build_retriever(args, config));
+    let compiled_catalogs =
+        fetch_compiled_catalogs(&retriever, config, config_dir, args.no_catalog).await;
     let errors: Vec<LintelDiagnostic> = Vec::new();
 
     run_with_contents_inner(
@@ -970,7 +1847,7 @@ pub async fn run_with_contents(
         args,
         retriever,
         config,
-        &config_dir,
+        config_dir,
         compiled_catalogs,
         errors,
         &mut on_check,
@@ -978,10 +1855,11 @@ pub async fn run_with_contents(
     .await
 }
 
-fn build_retriever(args: &ValidateArgs, cache: Option<SchemaCache>) -> SchemaCache {
-    if let Some(c) = cache {
-        return c;
-    }
+/// Build the schema cache for a validation run from the CLI arguments,
+/// falling back to the `cache-max-size` and `[network]` settings from
+/// `lintel.toml`.
+pub fn build_retriever(args: &ValidateArgs, config: &lintel_config::Config) -> SchemaCache {
+    let network = &config.network;
     let mut builder = SchemaCache::builder().force_fetch(args.force_schema_fetch);
     if let Some(dir) = &args.cache_dir {
         let path = PathBuf::from(dir);
@@ -991,30 +1869,276 @@ fn build_retriever(args: &ValidateArgs, cache: Option<SchemaCache>) -> SchemaCac
     if let Some(ttl) = args.schema_cache_ttl {
         builder = builder.ttl(ttl);
     }
+    if let Some(max_size) = config.cache_max_size {
+        builder = builder.max_size(max_size);
+    }
+    if let Some(timeout) = args.fetch_timeout.or(network.timeout) {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(retries) = args.fetch_retries.or(network.retries) {
+        builder = builder.retries(retries);
+    }
+    if let Some(backoff) = args.fetch_retry_backoff.or(network.retry_backoff) {
+        builder = builder.retry_backoff(backoff);
+    }
     builder.build()
 }
 
+/// `ignore_keywords` of the `[[override]]` blocks matching `pf`, sorted so
+/// files ignoring the same keywords share a validator.
+fn ignored_keywords(
+    config: &lintel_config::Config,
+    pf: &ParsedFile,
+    schema_uri: &str,
+) -> Vec<String> {
+    let schema_uris = [pf.original_schema_uri.as_str(), schema_uri];
+    let mut ignored: Vec<String> = config
+        .ignored_keywords(&pf.path, &schema_uris)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    ignored.sort_unstable();
+    ignored
+}
+
+/// The validation cache's schema hash for a validator ignoring `ignored`.
+fn with_ignored(schema_hash: &str, ignored: &[String]) -> String {
+    if ignored.is_empty() {
+        schema_hash.to_string()
+    } else {
+        format!("{schema_hash}:ignore={}", ignored.join(","))
+    }
+}
+
+/// Compile the schema of one group with the options of this run, without
+/// the keywords the retriever ignores.
+#[allow(clippy::too_many_arguments)]
+async fn compile_validator(
+    schema_uri: &str,
+    schema_value: &Value,
+    retriever: LocalRetriever,
+    formats: &crate::formats::FormatRegistry,
+    validate_formats: bool,
+    validate_content: bool,
+) -> Result<jsonschema::Validator, jsonschema::ValidationError<'static>> {
+    let mut schema_value = alloc::borrow::Cow::Borrowed(schema_value);
+    if !retriever.ignored.is_empty() {
+        crate::ignore::strip(schema_value.to_mut(), &retriever.ignored);
+    }
+    let opts = crate::patterns::apply(
+        formats.apply(
+            jsonschema::async_options()
+                .with_retriever(retriever)
+                .should_validate_formats(validate_formats),
+        ),
+    );
+    let opts = if validate_content {
+        crate::content::apply(opts, &schema_value)
+    } else {
+        opts
+    };
+    // Set base URI so relative $ref values (e.g. "./rule.json") resolve
+    // correctly. Remote schemas use the HTTP URI directly; local schemas
+    // get a file:// URI derived from the canonical absolute path.
+    let is_remote_schema = schema_uri.starts_with("http://") || schema_uri.starts_with("https://");
+    let base_uri = if is_remote_schema {
+        // Strip fragment (e.g. "#") — base URIs must not contain fragments.
+        Some(
+            schema_uri
+                .split('#')
+                .next()
+                .unwrap_or(schema_uri)
+                .to_string(),
+        )
+    } else {
+        std::fs::canonicalize(schema_uri)
+            .ok()
+            .map(|p| format!("file://{}", p.display()))
+    };
+    let opts = if let Some(uri) = base_uri {
+        opts.with_base_uri(uri)
+    } else {
+        opts
+    };
+    opts.build(&schema_value).await
+}
+
+/// Fetch every remote schema of `schema_groups` in parallel, keyed by URI.
+async fn prefetch_schemas(
+    retriever: &SchemaCache,
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+) -> HashMap<String, Result<(Value, CacheStatus), String>> {
+    let remote_uris: Vec<&String> = schema_groups
+        .keys()
+        .filter(|uri| uri.starts_with("http://") || uri.starts_with("https://"))
+        .collect();
+    let _prefetch_span =
+        tracing::info_span!("prefetch_schemas", count = remote_uris.len()).entered();
+
+    let mut schema_tasks = tokio::task::JoinSet::new();
+    for uri in remote_uris {
+        let r = retriever.clone();
+        let u = uri.clone();
+        schema_tasks.spawn(async move {
+            let result = r.fetch(&u).await;
+            (u, result)
+        });
+    }
+
+    let mut prefetched = HashMap::new();
+    while let Some(result) = schema_tasks.join_next().await {
+        match result {
+            Ok((uri, fetch_result)) => {
+                let fetch_result =
+                    fetch_result.map_err(|e| format!("failed to fetch schema: {uri}: {e}"));
+                prefetched.insert(uri, fetch_result);
+            }
+            Err(e) => eprintln!("warning: schema prefetch task failed: {e}"),
+        }
+    }
+    prefetched
+}
+
+/// Report a schema that failed to compile for every file in `group`.
+#[allow(clippy::too_many_arguments)]
+fn report_compile_error(
+    error: &jsonschema::ValidationError<'_>,
+    validate_formats: bool,
+    schema_uri: &str,
+    cache_status: Option<CacheStatus>,
+    group: &[&ParsedFile],
+    errors: &mut Vec<LintelDiagnostic>,
+    checked: &mut Vec<CheckedFile>,
+    on_check: &mut impl FnMut(&CheckedFile),
+) {
+    // When format validation is disabled and the compilation error
+    // is a uri-reference issue (e.g. Rust-style $ref paths in
+    // vector.json), skip validation silently.
+    if !validate_formats && error.to_string().contains("uri-reference") {
+        mark_group_checked(
+            schema_uri,
+            cache_status,
+            Some(ValidationCacheStatus::Miss),
+            group,
+            checked,
+            on_check,
+        );
+        return;
+    }
+    let msg = format!("failed to compile schema: {error}");
+    report_group_error(
+        |path| LintelDiagnostic::SchemaCompile {
+            path: path.to_string(),
+            message: msg.clone(),
+        },
+        schema_uri,
+        cache_status,
+        group,
+        errors,
+        checked,
+        on_check,
+    );
+}
+
+/// Print how the schema of each file is resolved (`--trace-resolution`).
+fn trace_resolution(
+    file_contents: &[(PathBuf, String)],
+    config: &lintel_config::Config,
+    catalogs: &[CompiledCatalog],
+) {
+    for (path, content) in file_contents {
+        eprint!(
+            "{}",
+            crate::trace::trace_file(path, content, config, catalogs)
+        );
+    }
+}
+
+/// The schema hash validation results are cached under: the schema content
+/// plus the options that change what validating against it reports.
+fn validation_schema_hash(
+    content_hash: &str,
+    formats: &crate::formats::FormatRegistry,
+    args: &ValidateArgs,
+) -> String {
+    let mut schema_hash = content_hash.to_string();
+    if !formats.is_empty() {
+        schema_hash = format!("{schema_hash}:{}", formats.cache_key());
+    }
+    if args.validate_content {
+        schema_hash.push_str(":content");
+    }
+    if args.strict_instances {
+        schema_hash.push_str(":strict");
+    }
+    schema_hash
+}
+
+/// Checks that run on whole documents after schema validation: `OpenAPI`
+/// examples, GitHub Actions expressions and `validate` plugins.
+#[allow(clippy::too_many_arguments)]
+async fn run_document_checks(
+    args: &ValidateArgs,
+    config: &lintel_config::Config,
+    config_dir: &Path,
+    schema_groups: &BTreeMap<String, Vec<ParsedFile>>,
+    errors: &mut Vec<LintelDiagnostic>,
+) -> Result<()> {
+    if args.openapi_examples {
+        check_openapi_examples(schema_groups, errors);
+    }
+    if config.github_actions.expressions == Some(true) {
+        check_github_expressions(schema_groups, errors);
+    }
+    run_validate_plugins(schema_groups, config, config_dir, errors).await
+}
+
+/// The workspace schema index, unless the config sets `no-schema-index`.
+fn schema_index(
+    config: &lintel_config::Config,
+    config_dir: &Path,
+) -> alloc::sync::Arc<LazySchemaIndex> {
+    alloc::sync::Arc::new(if config.no_schema_index {
+        LazySchemaIndex::disabled()
+    } else {
+        LazySchemaIndex::new(config_dir, &config.exclude)
+    })
+}
+
 #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 async fn run_with_contents_inner(
     file_contents: Vec<(PathBuf, String)>,
     args: &ValidateArgs,
     retriever: SchemaCache,
-    config: lintel_config::Config,
+    config: &lintel_config::Config,
     config_dir: &Path,
     compiled_catalogs: Vec<CompiledCatalog>,
     mut errors: Vec<LintelDiagnostic>,
     on_check: &mut impl FnMut(&CheckedFile),
 ) -> Result<CheckResult> {
     let mut checked: Vec<CheckedFile> = Vec::new();
+    let mut schema_hashes: BTreeMap<String, String> = BTreeMap::new();
+    let formats = args.formats.with_config(&config.formats)?;
+
+    if args.trace_resolution {
+        trace_resolution(&file_contents, config, &compiled_catalogs);
+    }
 
     // Phase 1: Parse files and resolve schema URIs
+    let schema_index = schema_index(config, config_dir);
+    let plugin_schemas = crate::plugin::resolve_schemas(config, config_dir, &file_contents).await?;
+    let mut without_schema = Vec::new();
     let schema_groups = parse_and_group_contents(
         file_contents,
-        &config,
+        config,
         config_dir,
         &compiled_catalogs,
+        &plugin_schemas,
+        &schema_index,
         &mut errors,
+        &mut without_schema,
     );
+    without_schema.sort();
     tracing::info!(
         schema_count = schema_groups.len(),
         total_files = schema_groups.values().map(Vec::len).sum::<usize>(),
@@ -1026,49 +2150,39 @@ async fn run_with_contents_inner(
         lintel_validation_cache::ensure_cache_dir(),
         args.force_validation,
     );
+    let config_hash = lintel_validation_cache::config_hash(&config.rewrite);
 
     // Prefetch all remote schemas in parallel
-    let remote_uris: Vec<&String> = schema_groups
-        .keys()
-        .filter(|uri| uri.starts_with("http://") || uri.starts_with("https://"))
-        .collect();
-
-    let prefetched = {
-        let _prefetch_span =
-            tracing::info_span!("prefetch_schemas", count = remote_uris.len()).entered();
-
-        let mut schema_tasks = tokio::task::JoinSet::new();
-        for uri in remote_uris {
-            let r = retriever.clone();
-            let u = uri.clone();
-            schema_tasks.spawn(async move {
-                let result = r.fetch(&u).await;
-                (u, result)
-            });
-        }
-
-        let mut prefetched: HashMap<String, Result<(Value, CacheStatus), String>> = HashMap::new();
-        while let Some(result) = schema_tasks.join_next().await {
-            match result {
-                Ok((uri, fetch_result)) => {
-                    prefetched.insert(uri, fetch_result.map_err(|e| e.to_string()));
-                }
-                Err(e) => eprintln!("warning: schema prefetch task failed: {e}"),
-            }
-        }
-
-        prefetched
-    };
+    let prefetch_start = std::time::Instant::now();
+    let mut prefetched = prefetch_schemas(&retriever, &schema_groups).await;
+    let schema_lock =
+        crate::lock::SchemaLock::load(args.lock, config_dir)?.map(alloc::sync::Arc::new);
+    if let Some(lock) = &schema_lock {
+        crate::lock::apply(lock, |uri| retriever.content_hash(uri), &mut prefetched);
+    }
 
     // Phase 2: Compile each schema once and validate all matching files
     let mut local_schema_cache: HashMap<String, Value> = HashMap::new();
-    let mut fetch_time = core::time::Duration::ZERO;
+    let mut fetch_time = prefetch_start.elapsed();
     let mut hash_time = core::time::Duration::ZERO;
     let mut vcache_time = core::time::Duration::ZERO;
     let mut compile_time = core::time::Duration::ZERO;
     let mut validate_time = core::time::Duration::ZERO;
+    // `--fail-fast`: stop after the first file that fails the run.
+    let stop_after = |pf: &ParsedFile, diagnostics: &[LintelDiagnostic]| {
+        args.fail_fast && file_fails(pf, diagnostics, config)
+    };
+    let mut stopped = false;
 
     for (schema_uri, group) in &schema_groups {
+        // Parse, fetch and compile errors are not affected by suppressions.
+        stopped |= args.fail_fast
+            && errors
+                .iter()
+                .any(|e| !matches!(e, LintelDiagnostic::Validation(_)) && !e.is_warning());
+        if stopped {
+            break;
+        }
         let _group_span = tracing::debug_span!(
             "schema_group",
             schema = schema_uri.as_str(),
@@ -1104,23 +2218,30 @@ async fn run_with_contents_inner(
 
         // Pre-compute schema hash once for the entire group.
         let t = std::time::Instant::now();
-        let schema_hash = lintel_validation_cache::schema_hash(&schema_value);
+        let content_hash = lintel_validation_cache::schema_hash(&schema_value);
+        let schema_hash = validation_schema_hash(&content_hash, &formats, args);
+        schema_hashes.insert(schema_uri.clone(), content_hash);
         hash_time += t.elapsed();
 
-        // Split the group into validation cache hits and misses.
-        let mut cache_misses: Vec<&ParsedFile> = Vec::new();
+        // Split the group into validation cache hits and misses. Files with
+        // different `ignore_keywords` are validated by validators of their own.
+        let mut cache_misses: BTreeMap<Vec<String>, Vec<&ParsedFile>> = BTreeMap::new();
 
         let t = std::time::Instant::now();
         for pf in group {
+            let ignored = ignored_keywords(config, pf, schema_uri);
             let (cached, vcache_status) = vcache
                 .lookup(&lintel_validation_cache::CacheKey {
                     file_content: &pf.content,
-                    schema_hash: &schema_hash,
+                    instance_prefix: &pf.instance_prefix,
+                    schema_hash: &with_ignored(&schema_hash, &ignored),
+                    config_hash: &config_hash,
                     validate_formats,
                 })
                 .await;
 
             if let Some(cached_errors) = cached {
+                let before = errors.len();
                 push_validation_errors(
                     pf,
                     schema_uri,
@@ -1133,110 +2254,98 @@ async fn run_with_contents_inner(
                     schema: schema_uri.clone(),
                     cache_status,
                     validation_cache_status: Some(vcache_status),
+                    duration: None,
                 };
                 on_check(&cf);
                 checked.push(cf);
+                if stop_after(pf, &errors[before..]) {
+                    stopped = true;
+                    break;
+                }
             } else {
-                cache_misses.push(pf);
+                cache_misses.entry(ignored).or_default().push(pf);
             }
         }
         vcache_time += t.elapsed();
+        if stopped {
+            break;
+        }
 
+        let miss_count = cache_misses.values().map(Vec::len).sum::<usize>();
         tracing::debug!(
-            cache_hits = group.len() - cache_misses.len(),
-            cache_misses = cache_misses.len(),
+            cache_hits = group.len() - miss_count,
+            cache_misses = miss_count,
             "validation cache"
         );
 
-        // If all files hit the validation cache, skip schema compilation entirely.
-        if cache_misses.is_empty() {
-            continue;
-        }
-
-        // Compile the schema for cache misses.
-        let t = std::time::Instant::now();
-        let validator = {
-            // Set base URI so relative $ref values (e.g. "./rule.json") resolve
-            // correctly. Remote schemas use the HTTP URI directly; local schemas
-            // get a file:// URI derived from the canonical absolute path.
-            let is_remote_schema =
-                schema_uri.starts_with("http://") || schema_uri.starts_with("https://");
+        // Compile the schema for cache misses, once per set of ignored keywords;
+        // if all files hit the validation cache there is nothing to compile.
+        let schema_value = alloc::sync::Arc::new(schema_value);
+        for (ignored, cache_misses) in &cache_misses {
+            let t = std::time::Instant::now();
             let local_retriever = LocalRetriever {
                 http: retriever.clone(),
+                rewrite: config.rewrite.clone(),
+                config_dir: config_dir.to_path_buf(),
+                schema_index: schema_index.clone(),
+                ignored: ignored.clone(),
+                lock: schema_lock.clone(),
             };
-            let opts = jsonschema::async_options()
-                .with_retriever(local_retriever)
-                .should_validate_formats(validate_formats);
-            let base_uri = if is_remote_schema {
-                // Strip fragment (e.g. "#") — base URIs must not contain fragments.
-                let uri = match schema_uri.find('#') {
-                    Some(pos) => schema_uri[..pos].to_string(),
-                    None => schema_uri.clone(),
-                };
-                Some(uri)
-            } else {
-                std::fs::canonicalize(schema_uri)
-                    .ok()
-                    .map(|p| format!("file://{}", p.display()))
-            };
-            let opts = if let Some(uri) = base_uri {
-                opts.with_base_uri(uri)
-            } else {
-                opts
-            };
-            match opts.build(&schema_value).await {
+            let compiled = compile_validator(
+                schema_uri,
+                &schema_value,
+                local_retriever,
+                &formats,
+                validate_formats,
+                args.validate_content,
+            )
+            .await;
+            compile_time += t.elapsed();
+            let validator = match compiled {
                 Ok(v) => v,
                 Err(e) => {
-                    compile_time += t.elapsed();
-                    // When format validation is disabled and the compilation error
-                    // is a uri-reference issue (e.g. Rust-style $ref paths in
-                    // vector.json), skip validation silently.
-                    if !validate_formats && e.to_string().contains("uri-reference") {
-                        mark_group_checked(
-                            schema_uri,
-                            cache_status,
-                            Some(ValidationCacheStatus::Miss),
-                            &cache_misses,
-                            &mut checked,
-                            on_check,
-                        );
-                        continue;
-                    }
-                    let msg = format!("failed to compile schema: {e}");
-                    report_group_error(
-                        |path| LintelDiagnostic::SchemaCompile {
-                            path: path.to_string(),
-                            message: msg.clone(),
-                        },
+                    report_compile_error(
+                        &e,
+                        validate_formats,
                         schema_uri,
                         cache_status,
-                        &cache_misses,
+                        cache_misses,
                         &mut errors,
                         &mut checked,
                         on_check,
                     );
                     continue;
                 }
-            }
-        };
-        compile_time += t.elapsed();
+            };
 
-        let t = std::time::Instant::now();
-        validate_group(
-            &validator,
-            schema_uri,
-            &schema_hash,
-            validate_formats,
-            cache_status,
-            &cache_misses,
-            &schema_value,
-            &vcache,
-            &mut errors,
-            &mut checked,
-            on_check,
-        )
-        .await;
-        validate_time += t.elapsed();
+            let t = std::time::Instant::now();
+            stopped = validate_group(
+                &alloc::sync::Arc::new(validator),
+                schema_uri,
+                &with_ignored(&schema_hash, ignored),
+                &config_hash,
+                validate_formats,
+                args.strict_instances,
+                args.timeout_per_file,
+                cache_status,
+                cache_misses,
+                &schema_value,
+                &vcache,
+                &mut errors,
+                &mut checked,
+                on_check,
+                &stop_after,
+            )
+            .await;
+            validate_time += t.elapsed();
+            if stopped {
+                break;
+            }
+        }
+    }
+
+    if let Some(lock) = &schema_lock {
+        errors.extend(lock.finish()?);
     }
 
     #[allow(clippy::cast_possible_truncation)]
@@ -1251,19 +2360,37 @@ async fn run_with_contents_inner(
         );
     }
 
-    // Sort errors for deterministic output (by path, then by span offset)
-    errors.sort_by(|a, b| {
-        a.path()
-            .cmp(b.path())
-            .then_with(|| a.offset().cmp(&b.offset()))
-    });
+    if !stopped {
+        run_document_checks(args, config, config_dir, &schema_groups, &mut errors).await?;
+    }
+
+    // Files skipped by `--fail-fast` would report all their comments unused.
+    apply_suppressions(
+        &mut errors,
+        &schema_groups,
+        args.report_unused_suppressions && !stopped,
+    );
+    apply_overrides(&mut errors, config, &schema_groups);
 
-    Ok(CheckResult { errors, checked })
+    sort_errors(&mut errors, args.max_errors);
+
+    Ok(CheckResult {
+        errors,
+        checked,
+        timings: Timings {
+            fetch: fetch_time,
+            compile: compile_time,
+            validate: vcache_time + validate_time,
+        },
+        schema_hashes,
+        without_schema,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::document::convert_error;
     use lintel_schema_cache::SchemaCache;
     use std::path::Path;
 
@@ -1309,6 +2436,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         }
     }
 
@@ -1334,6 +2474,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1394,6 +2547,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(schema_mock()), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1417,6 +2583,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(schema_mock()), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1440,6 +2619,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(schema_mock()), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1458,6 +2650,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(result.has_errors());
@@ -1476,6 +2681,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(result.has_errors());
@@ -1500,6 +2718,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(schema_mock()), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1519,6 +2750,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(schema_mock()), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1552,6 +2796,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1583,6 +2840,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1605,6 +2875,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(result.has_errors());
@@ -1642,6 +2925,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1677,6 +2973,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1739,6 +3048,19 @@ mod tests {
             no_catalog: false,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1773,6 +3095,19 @@ mod tests {
             no_catalog: false,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(result.has_errors());
@@ -1809,6 +3144,19 @@ mod tests {
             no_catalog: false,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
 
         let orig_dir = std::env::current_dir()?;
@@ -1847,6 +3195,19 @@ mod tests {
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -1887,6 +3248,19 @@ mod tests {
             no_catalog: true,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
 
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
@@ -1910,9 +3284,890 @@ mod tests {
         let f = sub.join("config.json");
         fs::write(&f, r#"{"$schema":"//schemas/test.json","name":"hello"}"#)?;
 
-        let pattern = sub.join("*.json").to_string_lossy().to_string();
+        let pattern = sub.join("*.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(!result.has_errors());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn downloaded_document_resolves_relative_schema_against_its_url() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let mut c = args_for_dirs(&[]);
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let document = (
+            PathBuf::from("https://example.com/configs/app.json"),
+            r#"{"$schema":"schema.json","name":42}"#.to_string(),
+        );
+        let cache = mock(&[("https://example.com/configs/schema.json", SCHEMA)]);
+
+        let result = run_with_contents(&c, vec![document], Some(cache), |_| {}).await?;
+        assert_eq!(result.files_checked(), 1);
+        assert!(result.has_errors());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn helm_chart_values_and_chart_yaml_are_validated() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("Chart.yaml"), "apiVersion: v2\n")?;
+        fs::write(
+            tmp.path().join("values.schema.json"),
+            r#"{"properties":{"replicas":{"type":"integer"}}}"#,
+        )?;
+        fs::write(tmp.path().join("values.yaml"), "replicas: 1\n")?;
+        fs::write(tmp.path().join("values.prod.yaml"), "replicas: many\n")?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.yaml").to_string_lossy().to_string()];
+        let cache = mock(&[(crate::helm::CHART_SCHEMA_URL, SCHEMA)]);
+
+        let result = run_with(&c, Some(cache), |_| {}).await?;
+        assert_eq!(result.files_checked(), 3);
+        let failed: Vec<_> = result
+            .errors
+            .iter()
+            .filter_map(|e| Path::new(e.path()).file_name()?.to_str())
+            .collect();
+        // values.prod.yaml has a string for replicas; Chart.yaml lacks `name`.
+        assert_eq!(failed, ["Chart.yaml", "values.prod.yaml"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn helm_templates_report_only_yaml_syntax_errors() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let templates = tmp.path().join("templates");
+        fs::create_dir_all(&templates)?;
+        fs::write(tmp.path().join("Chart.yaml"), "apiVersion: v2\n")?;
+        fs::write(
+            templates.join("good.yaml"),
+            "kind: Service\nmetadata:\n  name: {{ .Release.Name }}\n  labels:\n    \
+             {{- include \"labels\" . | nindent 4 }}\n",
+        )?;
+        let bad = "kind: Service\n---\nmetadata:\n  name: {{ .Release.Name }}\n  \
+                   ports: [80\n";
+        fs::write(templates.join("bad.yaml"), bad)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![templates.join("*.yaml").to_string_lossy().to_string()];
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert!(matches!(error, LintelDiagnostic::Parse { .. }));
+        assert!(error.path().ends_with("bad.yaml"));
+        // The span points into the template, past the `name:` action.
+        assert!(error.offset() > bad.find("{{").unwrap_or_default());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn plugins_resolve_schemas_and_report_diagnostics() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(
+            tmp.path().join("schema.json"),
+            r#"{"properties":{"replicas":{"type":"integer"}}}"#,
+        )?;
+        fs::write(tmp.path().join("app.yaml"), "replicas: 50\n")?;
+        // Resolves its one file to schema.json, then warns about it.
+        fs::write(
+            tmp.path().join("plugin.sh"),
+            r#"request=$(cat)
+path=$(printf '%s' "$request" | sed -n 's/.*"path":"\([^"]*\)".*/\1/p')
+case "$request" in
+  *'"hook":"resolve"'*) printf '{"schemas": {"%s": "//schema.json"}}' "$path" ;;
+  *) printf '{"diagnostics": [{"id": 0, "instance_path": "/replicas", "message": "at most 10 replicas", "severity": "warning"}]}' ;;
+esac
+"#,
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[plugins.replicas]\ncommand = [\"sh\", \"plugin.sh\"]\nresolve = true\nvalidate = true\n",
+        )?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.yaml").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].is_warning());
+        assert!(result.errors[0].to_string().contains("at most 10 replicas"));
+        Ok(())
+    }
+
+    // --- Format validation override ---
+
+    const FORMAT_SCHEMA: &str = r#"{
+        "type": "object",
+        "properties": {
+            "link": { "type": "string", "format": "uri-reference" }
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn custom_formats_from_config_and_rust() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"properties":{"size":{"type":"string","format":"quantity"},"tag":{"type":"string","format":"tag"}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[formats]\nquantity = \"k8s-quantity\"\n",
+        )?;
+        fs::write(
+            tmp.path().join("good.json"),
+            r#"{"$schema":"//schemas/test.json","size":"1Gi","tag":"v1"}"#,
+        )?;
+        fs::write(
+            tmp.path().join("bad.json"),
+            r#"{"$schema":"//schemas/test.json","size":"1GB","tag":"1"}"#,
+        )?;
+
+        let mut formats = crate::formats::FormatRegistry::default();
+        formats.register("tag", |s: &str| s.starts_with('v'));
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats,
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.files_checked(), 2);
+        let failed: Vec<&str> = result.errors.iter().map(LintelDiagnostic::path).collect();
+        assert_eq!(failed.len(), 2);
+        assert!(failed.iter().all(|path| path.ends_with("bad.json")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_content_checks_embedded_json() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"properties":{"config":{"type":"string","contentEncoding":"base64","contentMediaType":"application/json","contentSchema":{"properties":{"port":{"type":"integer"}}}}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("good.json"),
+            r#"{"$schema":"//schemas/test.json","config":"eyJwb3J0Ijo4MH0="}"#,
+        )?;
+        fs::write(
+            tmp.path().join("bad.json"),
+            r#"{"$schema":"//schemas/test.json","config":"eyJwb3J0IjoiODAifQ=="}"#,
+        )?;
+
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let mut c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.files_checked(), 2);
+        assert!(!result.has_errors());
+
+        c.validate_content = true;
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        let failed: Vec<&str> = result.errors.iter().map(LintelDiagnostic::path).collect();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].ends_with("bad.json"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn strict_instances_warns_on_undescribed_properties() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"properties":{"name":{"type":"string"},"port":{"type":"integer"}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("app.json"),
+            r#"{"$schema":"//schemas/test.json","name":"web","nmae":"web"}"#,
+        )?;
+
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let mut c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(result.errors.is_empty());
+
+        c.strict_instances = true;
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(!result.has_errors());
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].is_warning());
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation diagnostic");
+        };
+        assert_eq!(v.instance_path, "/nmae");
+        assert_eq!(
+            v.message,
+            "'nmae' is not in the schema; did you mean 'name'?"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unevaluated_property_suggests_a_fix() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"allOf":[{"properties":{"replicas":{"type":"integer"}}}],"unevaluatedProperties":false}"#,
+        )?;
+        // Mapped in lintel.toml: `unevaluatedProperties` would reject `$schema`.
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schemas]\n\"app.json\" = \"//schemas/test.json\"\n",
+        )?;
+        fs::write(tmp.path().join("app.json"), r#"{"replicass":3}"#)?;
+
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation diagnostic");
+        };
+        assert_eq!(v.instance_path, "/replicass");
+        assert_eq!(v.validation_code, "validation(unevaluated_property)");
+        assert!(v.message.ends_with("; did you mean 'replicas'?"));
+        let (span, replacement) = v.suggested_fix().expect("a suggested fix");
+        assert_eq!(span.offset(), 1);
+        assert_eq!(replacement, r#""replicas""#);
+        Ok(())
+    }
+
+    #[test]
+    fn one_of_reports_the_discriminated_branch() -> anyhow::Result<()> {
+        let schema = serde_json::json!({"oneOf": [
+            {"properties": {"kind": {"const": "Deployment"}, "replicas": {"type": "integer"}}},
+            {"properties": {"kind": {"const": "Service"}, "port": {"type": "integer"}}}
+        ]});
+        let validator = jsonschema::validator_for(&schema)?;
+        let instance = serde_json::json!({"kind": "Service", "port": "80"});
+        let errors: Vec<_> = validator
+            .iter_errors(&instance)
+            .flat_map(|e| convert_error(&e, "", ""))
+            .map(|e| (e.instance_path, e.schema_path))
+            .collect();
+        assert_eq!(
+            errors,
+            [(
+                "/port".to_string(),
+                "/oneOf/1/properties/port/type".to_string()
+            )]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn enum_failure_suggests_closest_value() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"properties":{"level":{"enum":["debug","info","warn"]}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("app.json"),
+            r#"{"$schema":"//schemas/test.json","level":"wran"}"#,
+        )?;
+
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation diagnostic");
+        };
+        assert_eq!(
+            v.message,
+            r#"value is not one of: "debug", "info", "warn"; did you mean "warn"?"#
+        );
+        assert_eq!(v.allowed_values, ["debug", "info", "warn"]);
+        assert_eq!(v.suggestion, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deprecated_properties_are_warnings() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas_dir = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas_dir)?;
+        fs::write(
+            schemas_dir.join("test.json"),
+            r#"{"properties":{"port":{"type":"integer"},"listen":{"deprecated":true,"description":"Use `port` instead."}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("app.json"),
+            r#"{"$schema":"//schemas/test.json","listen":8080}"#,
+        )?;
+
+        let pattern = tmp.path().join("*.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(!result.has_errors());
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].is_warning());
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation diagnostic");
+        };
+        assert_eq!(v.instance_path, "/listen");
+        assert_eq!(v.message, "'listen' is deprecated: Use `port` instead.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn format_errors_reported_without_override() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(&schema_path, FORMAT_SCHEMA)?;
+
+        let f = tmp.path().join("data.json");
+        fs::write(
+            &f,
+            format!(
+                r#"{{"$schema":"{}","link":"not a valid {{uri}}"}}"#,
+                schema_path.to_string_lossy()
+            ),
+        )?;
+
+        let pattern = tmp.path().join("data.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(
+            result.has_errors(),
+            "expected format error without override"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn format_errors_suppressed_with_override() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(&schema_path, FORMAT_SCHEMA)?;
+
+        let f = tmp.path().join("data.json");
+        fs::write(
+            &f,
+            format!(
+                r#"{{"$schema":"{}","link":"not a valid {{uri}}"}}"#,
+                schema_path.to_string_lossy()
+            ),
+        )?;
+
+        // Use **/data.json to match the absolute path from the tempdir.
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            r#"
+[[override]]
+files = ["**/data.json"]
+validate_formats = false
+"#,
+        )?;
+
+        let pattern = tmp.path().join("data.json").to_string_lossy().to_string();
+        let c = ValidateArgs {
+            globs: vec![pattern],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert!(
+            !result.has_errors(),
+            "expected no errors with validate_formats = false override"
+        );
+        Ok(())
+    }
+
+    // --- Severity override ---
+
+    async fn run_with_severity(severity: &str) -> anyhow::Result<CheckResult> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(&schema_path, SCHEMA)?;
+        fs::write(
+            tmp.path().join("legacy.json"),
+            format!(r#"{{"$schema":"{}"}}"#, schema_path.to_string_lossy()),
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            format!("[[override]]\nfiles = [\"**/legacy.json\"]\nseverity = \"{severity}\"\n"),
+        )?;
+
+        let c = ValidateArgs {
+            globs: vec![tmp.path().join("*.json").to_string_lossy().to_string()],
+            exclude: vec!["**/schema.json".to_string()],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        run_with(&c, Some(mock(&[])), |_| {}).await
+    }
+
+    #[tokio::test]
+    async fn severity_override_downgrades_to_warning() -> anyhow::Result<()> {
+        let result = run_with_severity("warning").await?;
+        assert!(!result.has_errors());
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.errors[0].is_warning());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn severity_override_off_hides_errors() -> anyhow::Result<()> {
+        let result = run_with_severity("off").await?;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.files_checked(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn severity_override_error_keeps_errors() -> anyhow::Result<()> {
+        let result = run_with_severity("error").await?;
+        assert!(result.has_errors());
+        assert_eq!(result.error_count(), 1);
+        Ok(())
+    }
+
+    // --- Ignored keywords ---
+
+    #[test]
+    fn error_keyword_is_last_schema_path_segment() {
+        assert_eq!(error_keyword("/properties/port/type"), "type");
+        assert_eq!(
+            error_keyword("/additionalProperties"),
+            "additionalProperties"
+        );
+        assert_eq!(error_keyword(""), "");
+    }
+
+    #[tokio::test]
+    async fn ignore_keywords_override_skips_matching_errors() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(
+            &schema_path,
+            r#"{"type":"object","properties":{"port":{"type":"integer"}},"required":["name"],"additionalProperties":false}"#,
+        )?;
+        fs::write(
+            tmp.path().join("data.json"),
+            format!(
+                r#"{{"$schema":"{}","port":"80","extra":true}}"#,
+                schema_path.to_string_lossy()
+            ),
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            r#"
+[[override]]
+files = ["**/data.json"]
+ignore_keywords = ["additionalProperties", "required"]
+"#,
+        )?;
+
+        let c = ValidateArgs {
+            globs: vec![tmp.path().join("data.json").to_string_lossy().to_string()],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.errors.len(), 1, "only the type error should remain");
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(v.schema_path, "/properties/port/type");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ignore_keywords_apply_inside_any_of() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(
+            &schema_path,
+            r#"{"anyOf":[{"type":"object","properties":{"name":{"type":"string"}},"additionalProperties":false},{"type":"string"}]}"#,
+        )?;
+        for name in ["lenient.json", "strict.json"] {
+            fs::write(
+                tmp.path().join(name),
+                format!(
+                    r#"{{"$schema":"{}","name":"a","extra":true}}"#,
+                    schema_path.to_string_lossy()
+                ),
+            )?;
+        }
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            r#"
+[[override]]
+files = ["**/lenient.json"]
+ignore_keywords = ["additionalProperties"]
+"#,
+        )?;
+
+        let c = ValidateArgs {
+            globs: vec![tmp.path().join("*.json").to_string_lossy().to_string()],
+            exclude: vec!["**/schema.json".to_string()],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.files_checked(), 2);
+        assert!(
+            result
+                .errors
+                .iter()
+                .all(|e| e.path().ends_with("strict.json")),
+            "the ignored keyword fails the anyOf: {:?}",
+            result.errors
+        );
+        assert!(!result.errors.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_error_messages_from_schema_and_config() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(
+            &schema_path,
+            r#"{"type":"object","properties":{"port":{"type":"integer","errorMessage":{"type":"ports are numbers ({message})"}},"name":{"pattern":"^[a-z-]+$"}}}"#,
+        )?;
+        fs::write(
+            tmp.path().join("data.json"),
+            format!(
+                r#"{{"$schema":"{}","port":"80","name":"My App"}}"#,
+                schema_path.to_string_lossy()
+            ),
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            r#"
+[[override]]
+files = ["**/data.json"]
+messages = { pattern = "{instance_path} must be kebab-case" }
+"#,
+        )?;
+
+        let c = ValidateArgs {
+            globs: vec![tmp.path().join("data.json").to_string_lossy().to_string()],
+            exclude: vec![],
+            cache_dir: None,
+            force_schema_fetch: true,
+            force_validation: true,
+            no_catalog: true,
+            config_dir: Some(tmp.path().to_path_buf()),
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
+        };
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        let mut messages: Vec<&str> = result
+            .errors
+            .iter()
+            .map(LintelDiagnostic::message)
+            .collect();
+        messages.sort_unstable();
+        assert_eq!(
+            messages,
+            [
+                "/name must be kebab-case",
+                r#"ports are numbers (value is not of type "integer")"#,
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lintel_ignore_comments_silence_the_next_node() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(
+            &schema_path,
+            r#"{"type":"object","properties":{"port":{"type":"integer"},"name":{"type":"string"}},"additionalProperties":false}"#,
+        )?;
+        fs::write(
+            tmp.path().join("app.yaml"),
+            format!(
+                "# yaml-language-server: $schema={}\n# lintel-ignore type -- legacy string port\nport: \"80\"\n# lintel-ignore additionalProperties\nname: demo\nextra: true\n",
+                schema_path.to_string_lossy()
+            ),
+        )?;
+
         let c = ValidateArgs {
-            globs: vec![pattern],
+            globs: vec![tmp.path().join("app.yaml").to_string_lossy().to_string()],
             exclude: vec![],
             cache_dir: None,
             force_schema_fetch: true,
@@ -1920,40 +4175,70 @@ mod tests {
             no_catalog: true,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: true,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
-
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
-        assert!(!result.has_errors());
+        let validation: Vec<&str> = result
+            .errors
+            .iter()
+            .filter_map(|e| match e {
+                LintelDiagnostic::Validation(v) => Some(v.schema_path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(validation, ["/additionalProperties"]);
+
+        // The comment above `name` silences nothing: `extra` is on the
+        // line after it.
+        let unused: Vec<_> = result
+            .errors
+            .iter()
+            .filter(|e| matches!(e, LintelDiagnostic::UnusedSuppression { .. }))
+            .map(LintelDiagnostic::line_col)
+            .collect();
+        assert_eq!(unused, [Some((4, 1))]);
+        assert_eq!(result.warning_count(), 1);
         Ok(())
     }
 
-    // --- Format validation override ---
-
-    const FORMAT_SCHEMA: &str = r#"{
-        "type": "object",
-        "properties": {
-            "link": { "type": "string", "format": "uri-reference" }
-        }
-    }"#;
-
     #[tokio::test]
-    async fn format_errors_reported_without_override() -> anyhow::Result<()> {
+    async fn fail_fast_and_max_errors_limit_the_report() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
         let schema_path = tmp.path().join("schema.json");
-        fs::write(&schema_path, FORMAT_SCHEMA)?;
-
-        let f = tmp.path().join("data.json");
         fs::write(
-            &f,
-            format!(
-                r#"{{"$schema":"{}","link":"not a valid {{uri}}"}}"#,
-                schema_path.to_string_lossy()
-            ),
+            &schema_path,
+            r#"{"type":"object","additionalProperties":{"type":"integer"}}"#,
+        )?;
+        let header = format!(
+            "# yaml-language-server: $schema={}\n",
+            schema_path.to_string_lossy()
+        );
+        // Suppressed errors do not stop the run.
+        fs::write(
+            tmp.path().join("a.yaml"),
+            format!("{header}# lintel-ignore\na: x\n"),
         )?;
+        for name in ["b.yaml", "c.yaml"] {
+            fs::write(tmp.path().join(name), format!("{header}a: x\nb: x\nc: x\n"))?;
+        }
 
-        let pattern = tmp.path().join("data.json").to_string_lossy().to_string();
-        let c = ValidateArgs {
-            globs: vec![pattern],
+        let mut c = ValidateArgs {
+            globs: ["a.yaml", "b.yaml", "c.yaml"]
+                .iter()
+                .map(|name| tmp.path().join(name).to_string_lossy().to_string())
+                .collect(),
             exclude: vec![],
             cache_dir: None,
             force_schema_fetch: true,
@@ -1961,59 +4246,257 @@ mod tests {
             no_catalog: true,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: Some(1),
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
-        assert!(
-            result.has_errors(),
-            "expected format error without override"
+        let messages: Vec<&str> = result
+            .errors
+            .iter()
+            .map(LintelDiagnostic::message)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                r#"value is not of type "integer""#,
+                "+2 more",
+                r#"value is not of type "integer""#,
+                "+2 more",
+            ]
+        );
+        assert_eq!(result.files_checked(), 3);
+
+        c.fail_fast = true;
+        c.max_errors = None;
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.error_count(), 3);
+        assert_eq!(
+            result.errors.iter().map(LintelDiagnostic::path).min(),
+            result.errors.iter().map(LintelDiagnostic::path).max()
+        );
+        assert!(result.files_checked() < 3);
+        Ok(())
+    }
+
+    #[test]
+    fn deferred_instances_are_parsed_when_needed() {
+        let pf = ParsedFile {
+            path: "big.yaml".to_string(),
+            content: "a: [1, 2]\n".to_string(),
+            instance: Instance::Deferred(FileFormat::Yaml, std::sync::OnceLock::new()),
+            original_schema_uri: String::new(),
+            instance_prefix: String::new(),
+            embedded_in: None,
+        };
+        let first: *const Value = pf.instance().expect("valid YAML");
+        assert_eq!(
+            pf.instance().expect("valid YAML"),
+            &serde_json::json!({"a": [1, 2]})
+        );
+        assert!(core::ptr::eq(first, pf.instance().expect("valid YAML")));
+
+        let broken = ParsedFile {
+            content: "a: [1, 2\n".to_string(),
+            instance: Instance::Deferred(FileFormat::Yaml, std::sync::OnceLock::new()),
+            ..pf
+        };
+        assert!(matches!(
+            broken.instance(),
+            Err(LintelDiagnostic::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn slow_validation_times_out() -> anyhow::Result<()> {
+        let schema = serde_json::json!({"items": {"type": "string", "pattern": "^[a-z]+$"}});
+        let validator = alloc::sync::Arc::new(jsonschema::validator_for(&schema)?);
+        let schema = alloc::sync::Arc::new(schema);
+        let instance = Value::Array(vec![Value::from("abc"); 200_000]);
+        let pf = ParsedFile {
+            path: "big.json".to_string(),
+            content: instance.to_string(),
+            instance: Instance::Parsed(instance),
+            original_schema_uri: String::new(),
+            instance_prefix: String::new(),
+            embedded_in: None,
+        };
+
+        let instance = pf.instance().expect("parsed instance");
+        let within = |timeout| {
+            document_errors_within(timeout, &validator, &schema, &pf, instance, false, false)
+        };
+        assert!(within(core::time::Duration::from_nanos(1)).is_none());
+        assert_eq!(
+            within(core::time::Duration::from_secs(60)).map(|e| e.len()),
+            Some(0)
         );
         Ok(())
     }
 
     #[tokio::test]
-    async fn format_errors_suppressed_with_override() -> anyhow::Result<()> {
+    async fn sidecar_assigns_schema_to_files_beneath_it() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
-        let schema_path = tmp.path().join("schema.json");
-        fs::write(&schema_path, FORMAT_SCHEMA)?;
+        fs::write(tmp.path().join("schema.json"), SCHEMA)?;
+        let resources = tmp.path().join("resources");
+        fs::create_dir_all(&resources)?;
+        fs::write(
+            resources.join(lintel_config::sidecar::SIDECAR_FILENAME),
+            "schema = \"../schema.json\"\nfiles = [\"*.yaml\"]\n",
+        )?;
+        fs::write(resources.join("a.yaml"), "name: 1\n")?;
+        fs::write(resources.join("b.yml"), "name: 1\n")?;
 
-        let f = tmp.path().join("data.json");
+        let mut c = args_for_dirs(&[]);
+        c.globs = ["a.yaml", "b.yml"]
+            .iter()
+            .map(|name| resources.join(name).to_string_lossy().to_string())
+            .collect();
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.files_checked(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation error");
+        };
+        assert!(v.path.ends_with("a.yaml"));
+        assert!(v.schema_url.ends_with("schema.json"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn multi_document_yaml_uses_a_schema_per_document() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("a.json"), SCHEMA)?;
         fs::write(
-            &f,
-            format!(
-                r#"{{"$schema":"{}","link":"not a valid {{uri}}"}}"#,
-                schema_path.to_string_lossy()
-            ),
+            tmp.path().join("b.json"),
+            r#"{"type":"object","properties":{"port":{"type":"integer"}}}"#,
         )?;
+        let content = "\
+# yaml-language-server: $schema=a.json
+---
+name: 1
+---
+$schema: ./b.json
+port: eighty
+---
+kind: Unknown
+";
+        let file = tmp.path().join("mixed.yaml");
+        fs::write(&file, content)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![file.to_string_lossy().to_string()];
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        let errors: Vec<_> = result
+            .errors
+            .iter()
+            .map(|e| {
+                let LintelDiagnostic::Validation(v) = e else {
+                    panic!("expected a validation error, got {e:?}");
+                };
+                (
+                    v.line_col().0,
+                    v.schema_url.rsplit('/').next().map(str::to_string),
+                )
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (3, Some("a.json".to_string())),
+                (6, Some("b.json".to_string()))
+            ]
+        );
+        Ok(())
+    }
 
-        // Use **/data.json to match the absolute path from the tempdir.
+    #[tokio::test]
+    async fn workspace_schemas_are_resolved_by_id() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schemas = tmp.path().join("schemas");
+        fs::create_dir_all(&schemas)?;
         fs::write(
-            tmp.path().join("lintel.toml"),
-            r#"
-[[override]]
-files = ["**/data.json"]
-validate_formats = false
-"#,
+            schemas.join("app.schema.json"),
+            r#"{
+                "$id": "https://example.com/app.json",
+                "type": "object",
+                "properties": {"name": {"$ref": "https://example.com/name.json"}}
+            }"#,
+        )?;
+        fs::write(
+            schemas.join("name.schema.json"),
+            r#"{"$id": "https://example.com/name.json", "type": "string"}"#,
+        )?;
+        let file = tmp.path().join("app.json");
+        fs::write(
+            &file,
+            r#"{"$schema": "https://example.com/app.json", "name": 1}"#,
         )?;
 
-        let pattern = tmp.path().join("data.json").to_string_lossy().to_string();
-        let c = ValidateArgs {
-            globs: vec![pattern],
-            exclude: vec![],
-            cache_dir: None,
-            force_schema_fetch: true,
-            force_validation: true,
-            no_catalog: true,
-            config_dir: Some(tmp.path().to_path_buf()),
-            schema_cache_ttl: None,
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![file.to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            panic!("expected a validation error, got {:?}", result.errors[0]);
         };
+        assert_eq!(v.instance_path, "/name");
+        assert!(v.schema_url.ends_with("app.schema.json"));
+
+        fs::write(tmp.path().join("lintel.toml"), "no-schema-index = true\n")?;
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
-        assert!(
-            !result.has_errors(),
-            "expected no errors with validate_formats = false override"
-        );
+        assert!(matches!(
+            result.errors[..],
+            [LintelDiagnostic::SchemaFetch { .. }]
+        ));
         Ok(())
     }
 
+    // --- Schema mapping priority ---
+
+    #[test]
+    fn mapping_priority_orders_schema_sources() {
+        let mapping = |priority| {
+            SchemaMapping::Detailed(lintel_config::DetailedSchemaMapping {
+                url: "map".to_string(),
+                priority,
+            })
+        };
+        let choose = |inline: Option<&str>, m: Option<&SchemaMapping>, catalog: Option<&str>| {
+            choose_schema_uri(inline.map(str::to_string), m, || {
+                catalog.map(str::to_string)
+            })
+        };
+        let pick = |uri: &str, from_inline: bool| Some((uri.to_string(), from_inline));
+
+        let high = mapping(MappingPriority::High);
+        let normal = SchemaMapping::from("map");
+        let low = mapping(MappingPriority::Low);
+        assert_eq!(
+            choose(Some("inline"), Some(&high), Some("cat")),
+            pick("map", false)
+        );
+        assert_eq!(
+            choose(Some("inline"), Some(&normal), Some("cat")),
+            pick("inline", true)
+        );
+        assert_eq!(choose(None, Some(&normal), Some("cat")), pick("map", false));
+        assert_eq!(choose(None, Some(&low), Some("cat")), pick("cat", false));
+        assert_eq!(choose(None, Some(&low), None), pick("map", false));
+        assert_eq!(choose(None, None, Some("cat")), pick("cat", false));
+        assert_eq!(choose(None, None, None), None);
+    }
+
     // --- Unrecognized extension handling ---
 
     #[tokio::test]
@@ -2031,6 +4514,19 @@ validate_formats = false
             no_catalog: true,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
         assert!(!result.has_errors());
@@ -2074,6 +4570,19 @@ validate_formats = false
             no_catalog: false,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(!result.has_errors());
@@ -2112,6 +4621,19 @@ validate_formats = false
             no_catalog: false,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(!result.has_errors());
@@ -2152,6 +4674,19 @@ validate_formats = false
             no_catalog: false,
             config_dir: Some(tmp.path().to_path_buf()),
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(result.has_errors());
@@ -2188,6 +4723,19 @@ validate_formats = false
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let mut first_statuses = Vec::new();
         let result = run_with(&c, Some(mock(&[])), |cf| {
@@ -2257,6 +4805,19 @@ validate_formats = false
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&c, Some(client), |_| {}).await?;
         assert!(
@@ -2310,6 +4871,19 @@ validate_formats = false
             no_catalog: true,
             config_dir: None,
             schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: crate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: None,
+            timeout_per_file: None,
+            formats: crate::formats::FormatRegistry::default(),
         };
         let result = run_with(&args, Some(mock(&[])), |_| {}).await?;
 
@@ -2319,4 +4893,287 @@ validate_formats = false
         assert_eq!(result.errors.len(), 1);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn markdown_code_fences_validated_in_place() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("schema.json"), SCHEMA)?;
+        let content = "---\nfence-schemas:\n  json: ./schema.json\n---\n# Examples\n\n\
+                       ```yaml schema=./schema.json\nname: 42\n```\n\n\
+                       ```json\n{\"name\": \"ok\"}\n```\n\n\
+                       ```yaml\nnot: [valid\n```\n";
+        fs::write(tmp.path().join("README.md"), content)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.md").to_string_lossy().to_string()];
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 2);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert!(v.path.ends_with("README.md:7"), "path: {}", v.path);
+        assert_eq!(v.line_col().0, 8);
+        let offset = v.span.offset();
+        assert_eq!(&content[offset..offset + 4], "name");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn markdown_front_matter_validated_via_mapping() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("schema.json"), SCHEMA)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schemas]\n\"*.md\" = \"//schema.json\"\n",
+        )?;
+        let content = "---\ntitle: Post\nname: 42\n---\n# Post\n\nname: in the body\n";
+        fs::write(tmp.path().join("post.md"), content)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.md").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert_eq!(
+            v.span.offset(),
+            content.find("name: 42").unwrap_or_default()
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "xml")]
+    #[tokio::test]
+    async fn xml_validated_only_when_mapped() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(
+            tmp.path().join("pom.schema.json"),
+            r#"{
+                "type": "object",
+                "properties": {
+                    "project": {
+                        "type": "object",
+                        "required": ["artifactId"],
+                        "properties": { "packaging": { "enum": ["jar", "pom"] } }
+                    }
+                }
+            }"#,
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schemas]\n\"pom.xml\" = \"//pom.schema.json\"\n",
+        )?;
+        let content = "<project>\n  <artifactId>demo</artifactId>\n  <packaging>war</packaging>\n</project>\n";
+        fs::write(tmp.path().join("pom.xml"), content)?;
+        fs::write(
+            tmp.path().join("build.xml"),
+            "<project><target></project>\n",
+        )?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.xml").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert_eq!(v.instance_path, "/project/packaging");
+        assert_eq!(v.line_col(), (3, 4));
+        Ok(())
+    }
+
+    #[cfg(feature = "hcl")]
+    #[tokio::test]
+    async fn terraform_validated_via_mapping() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(
+            tmp.path().join("tf.schema.json"),
+            r#"{
+                "type": "object",
+                "properties": {
+                    "variable": {
+                        "additionalProperties": { "required": ["description"] }
+                    }
+                }
+            }"#,
+        )?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schemas]\n\"*.tf\" = \"//tf.schema.json\"\n",
+        )?;
+        let content = "variable \"region\" {\n  description = \"AWS region\"\n}\n\nvariable \"zone\" {\n  default = var.region\n}\n";
+        fs::write(tmp.path().join("variables.tf"), content)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.tf").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert_eq!(v.instance_path, "/variable/zone");
+        assert_eq!(v.line_col(), (5, 11));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn override_extract_validates_fragment() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        fs::write(tmp.path().join("schema.json"), SCHEMA)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            r#"
+[schemas]
+"*.yaml" = "//schema.json"
+
+[[override]]
+schemas = ["//schema.json"]
+extract = "$.spec.template"
+"#,
+        )?;
+        let content = "kind: Job\nspec:\n  template:\n    name: 42\n";
+        fs::write(tmp.path().join("job.yaml"), content)?;
+        fs::write(tmp.path().join("other.yaml"), "kind: Service\n")?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.yaml").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        // other.yaml has no spec.template and is skipped.
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert_eq!(v.instance_path, "/spec/template/name");
+        assert_eq!(v.span.offset(), content.find("name").unwrap_or_default());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn jsonl_errors_point_at_their_line() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let schema_path = tmp.path().join("schema.json");
+        fs::write(&schema_path, SCHEMA)?;
+        fs::write(
+            tmp.path().join("lintel.toml"),
+            "[schemas]\n\"*.ndjson\" = \"//schema.json\"\n",
+        )?;
+        let content = "{\"name\":\"a\"}\n\n{\"name\":1}\n{\"name\":\"c\"}\n";
+        fs::write(tmp.path().join("events.ndjson"), content)?;
+
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("*.ndjson").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        let result = run_with(&c, Some(mock(&[])), |_| {}).await?;
+
+        assert_eq!(result.files_checked(), 3);
+        assert_eq!(result.errors.len(), 1);
+        let LintelDiagnostic::Validation(v) = &result.errors[0] else {
+            anyhow::bail!("expected a validation error, got {:?}", result.errors[0]);
+        };
+        assert!(v.path.ends_with("events.ndjson:3"), "path: {}", v.path);
+        assert_eq!(v.line_col().0, 3);
+        Ok(())
+    }
+
+    /// Serves schemas from a map that tests can change between runs.
+    #[derive(Clone, Default)]
+    struct MapRetriever(alloc::sync::Arc<std::sync::Mutex<HashMap<String, String>>>);
+
+    impl MapRetriever {
+        fn serve(&self, uri: &str, body: &str) {
+            self.0
+                .lock()
+                .expect("schemas poisoned")
+                .insert(uri.to_string(), body.to_string());
+        }
+
+        fn cache(&self, dir: &Path) -> SchemaCache {
+            SchemaCache::builder()
+                .cache_dir(dir.join("cache"))
+                .force_fetch(true)
+                .retriever(self.clone())
+                .build()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl lintel_schema_cache::SchemaRetriever for MapRetriever {
+        async fn fetch(
+            &self,
+            uri: &str,
+            _cached: &lintel_schema_cache::CacheValidators,
+        ) -> Result<lintel_schema_cache::FetchResponse, Box<dyn core::error::Error + Send + Sync>>
+        {
+            let body = self.0.lock().expect("schemas poisoned").get(uri).cloned();
+            Ok(lintel_schema_cache::FetchResponse {
+                body: Some(body.ok_or_else(|| format!("no schema at {uri}"))?),
+                validators: lintel_schema_cache::CacheValidators::default(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn lockfile_covers_referenced_schemas() -> anyhow::Result<()> {
+        let root = "https://example.com/root.json";
+        let defs = "https://example.com/defs.json";
+        let schemas = MapRetriever::default();
+        schemas.serve(
+            root,
+            r#"{"type":"object","properties":{"name":{"$ref":"defs.json"}}}"#,
+        );
+        schemas.serve(defs, r#"{"type":"string"}"#);
+
+        let tmp = tempfile::tempdir()?;
+        fs::write(
+            tmp.path().join("data.json"),
+            format!(r#"{{"$schema":"{root}","name":"a"}}"#),
+        )?;
+        let mut c = args_for_dirs(&[]);
+        c.globs = vec![tmp.path().join("data.json").to_string_lossy().to_string()];
+        c.config_dir = Some(tmp.path().to_path_buf());
+        c.force_validation = true;
+
+        c.lock = crate::lock::LockMode::Update;
+        let result = run_with(&c, Some(schemas.cache(tmp.path())), |_| {}).await?;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let lock = crate::lock::Lockfile::load(&tmp.path().join(crate::lock::LOCK_FILENAME))?
+            .expect("lockfile written");
+        assert!(lock.get(root).is_some());
+        assert!(lock.get(defs).is_some());
+
+        // Only the referenced schema changes upstream
+        schemas.serve(defs, r#"{"type":"integer"}"#);
+
+        c.lock = crate::lock::LockMode::Frozen;
+        let result = run_with(&c, Some(schemas.cache(tmp.path())), |_| {}).await?;
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert!(matches!(
+            &result.errors[0],
+            LintelDiagnostic::SchemaCompile { message, .. }
+                if message.contains(&format!("schema {defs} changed since it was locked"))
+        ));
+
+        c.lock = crate::lock::LockMode::Auto;
+        let result = run_with(&c, Some(schemas.cache(tmp.path())), |_| {}).await?;
+        assert!(result.errors.iter().any(|e| matches!(
+            e,
+            LintelDiagnostic::SchemaChanged { message, .. } if message.contains(defs)
+        )));
+        Ok(())
+    }
 }
@@ -0,0 +1,262 @@
+//! WebAssembly plugins: `[plugins]` entries that name a `wasm` module
+//! instead of a `command`.
+//!
+//! A module gets the same requests and writes the same responses as a
+//! command plugin, but runs inside Lintel without any imports, so it cannot
+//! read files, reach the network or see the environment, and it behaves the
+//! same on every machine. Each call gets a fresh instance with a fuel and
+//! memory budget, so a runaway plugin fails instead of hanging CI. The fuel
+//! budget follows the plugin's `timeout`: [`FUEL_PER_SECOND`] for each
+//! second of it.
+//!
+//! The guest API:
+//!
+//! - `memory`: the module's exported linear memory.
+//! - `alloc(len: i32) -> i32`: reserve `len` bytes for the request and
+//!   return their address.
+//! - `resolve(ptr: i32, len: i32) -> i64` and
+//!   `validate(ptr: i32, len: i32) -> i64`: handle the request of `len`
+//!   bytes at `ptr` and return the address of the response in the high 32
+//!   bits and its length in the low 32 bits. Only the hooks the plugin
+//!   enables need to be exported.
+
+use core::time::Duration;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail};
+use wasmi::core::TrapCode;
+use wasmi::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Fuel, roughly instructions, a plugin may use per second of its `timeout`.
+/// wasmi runs more instructions than this a second on current hardware, so
+/// a looping module runs out of fuel before its timeout passes.
+const FUEL_PER_SECOND: u64 = 100_000_000;
+
+/// Bytes of linear memory a plugin may grow to.
+const MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// Bytes a plugin's response may take.
+const OUTPUT_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Run the `hook` export of the module at `path` with `input` and return
+/// its output, with the fuel budget of `timeout`.
+///
+/// # Errors
+///
+/// Returns an error if the module cannot be read or loaded, imports
+/// anything, lacks the guest API, traps or runs out of fuel.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn call(
+    name: &str,
+    path: &Path,
+    hook: &str,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<Vec<u8>> {
+    let wasm = std::fs::read(path)
+        .with_context(|| format!("failed to read plugin `{name}` ({})", path.display()))?;
+    let mut config = wasmi::Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm)
+        .with_context(|| format!("plugin `{name}` is not a valid WebAssembly module"))?;
+    if let Some(import) = module.imports().next() {
+        bail!(
+            "plugin `{name}` imports `{}::{}`, but WebAssembly plugins cannot import anything",
+            import.module(),
+            import.name()
+        );
+    }
+
+    let limits = StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits: &mut StoreLimits| limits);
+    store.set_fuel(fuel(timeout)).map_err(wasmi::Error::from)?;
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .and_then(|instance| instance.start(&mut store))
+        .with_context(|| format!("failed to start plugin `{name}`"))?;
+    let memory = instance
+        .get_memory(&store, "memory")
+        .with_context(|| format!("plugin `{name}` does not export `memory`"))?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&store, "alloc")
+        .with_context(|| format!("plugin `{name}` does not export `alloc(i32) -> i32`"))?;
+    let run = instance
+        .get_typed_func::<(i32, i32), i64>(&store, hook)
+        .with_context(|| format!("plugin `{name}` does not export `{hook}(i32, i32) -> i64`"))?;
+
+    let len = i32::try_from(input.len()).context("plugin request is too large")?;
+    let trapped = |error: wasmi::Error| match error.as_trap_code() {
+        Some(TrapCode::OutOfFuel) => {
+            anyhow!("plugin `{name}` ran out of the fuel of its {timeout:?} timeout")
+        }
+        _ => anyhow::Error::from(error).context(format!("plugin `{name}` failed")),
+    };
+    let ptr = alloc.call(&mut store, len).map_err(trapped)?;
+    memory
+        .write(&mut store, guest_offset(ptr), input)
+        .map_err(|error| trapped(error.into()))?;
+    let packed = run.call(&mut store, (ptr, len)).map_err(trapped)?;
+    let packed = packed.cast_unsigned();
+    let out_ptr = usize::try_from(packed >> 32)?;
+    let out_len = usize::try_from(packed & u64::from(u32::MAX))?;
+    if out_len > OUTPUT_LIMIT {
+        bail!(
+            "plugin `{name}` returned a response of {out_len} bytes, over the limit of {OUTPUT_LIMIT}"
+        );
+    }
+    // Check the range against the guest's memory before copying, so a bogus
+    // length cannot make Lintel allocate for it.
+    let output = out_ptr
+        .checked_add(out_len)
+        .and_then(|end| memory.data(&store).get(out_ptr..end))
+        .with_context(|| format!("plugin `{name}` returned a response outside its memory"))?;
+    Ok(output.to_vec())
+}
+
+/// The fuel budget of a plugin with `timeout`.
+fn fuel(timeout: Duration) -> u64 {
+    u64::try_from(timeout.as_millis())
+        .unwrap_or(u64::MAX)
+        .saturating_mul(FUEL_PER_SECOND / 1000)
+}
+
+/// A guest address as an offset into its memory.
+fn guest_offset(ptr: i32) -> usize {
+    ptr.cast_unsigned() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// A plugin whose `validate` echoes its request back.
+    const ECHO: &str = r#"(module
+      (memory (export "memory") 1)
+      (func (export "alloc") (param i32) (result i32) i32.const 16)
+      (func (export "validate") (param i32 i32) (result i64)
+        (i64.or
+          (i64.shl (i64.extend_i32_u (local.get 0)) (i64.const 32))
+          (i64.extend_i32_u (local.get 1)))))"#;
+
+    fn write_module(dir: &Path, wat: &str) -> anyhow::Result<std::path::PathBuf> {
+        let path = dir.join("plugin.wasm");
+        std::fs::write(&path, wat::parse_str(wat)?)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn calls_the_hook_export_with_the_request() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = write_module(tmp.path(), ECHO)?;
+        assert_eq!(
+            call("echo", &path, "validate", b"{\"a\": 1}", TIMEOUT)?,
+            b"{\"a\": 1}"
+        );
+
+        let error =
+            call("echo", &path, "resolve", b"{}", TIMEOUT).expect_err("resolve is not exported");
+        assert!(error.to_string().contains("does not export `resolve"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_plugins_can_be_modules() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let response =
+            r#"{"diagnostics": [{"id": 0, "instance_path": "/replicas", "message": "too many"}]}"#;
+        std::fs::write(
+            tmp.path().join("policy.wasm"),
+            wat::parse_str(format!(
+                r#"(module
+                  (memory (export "memory") 1)
+                  (data (i32.const 1024) "{}")
+                  (func (export "alloc") (param i32) (result i32) i32.const 16)
+                  (func (export "validate") (param i32 i32) (result i64)
+                    i64.const {}))"#,
+                response.replace('"', "\\\""),
+                (1024_i64 << 32) | i64::try_from(response.len())?
+            ))?,
+        )?;
+        let config: lintel_config::Config =
+            toml::from_str("[plugins.policy]\nwasm = \"policy.wasm\"\nvalidate = true\n")?;
+        let document = serde_json::json!({"replicas": 50});
+        let files = [crate::plugin::PluginFile {
+            path: "app.json",
+            schema: "https://example.com/app.json",
+            document: &document,
+            content: "{\"replicas\": 50}",
+            instance_prefix: "",
+        }];
+
+        let errors = crate::plugin::validate(&config, tmp.path(), &files).await?;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].1.instance_path, "/replicas");
+        assert_eq!(errors[0].1.schema_path, "/plugins/policy");
+        Ok(())
+    }
+
+    /// A plugin whose `validate` returns the packed pointer and length
+    /// `packed`.
+    fn returning(packed: i64) -> String {
+        format!(
+            r#"(module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32) i32.const 16)
+              (func (export "validate") (param i32 i32) (result i64) i64.const {packed}))"#
+        )
+    }
+
+    #[test]
+    fn responses_must_fit_in_memory_and_the_limit() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = write_module(tmp.path(), &returning((65_500_i64 << 32) | 0x64))?;
+        let error =
+            call("bad", &path, "validate", b"{}", TIMEOUT).expect_err("response is out of bounds");
+        assert!(error.to_string().contains("outside its memory"), "{error}");
+
+        let path = write_module(tmp.path(), &returning(i64::from(u32::MAX)))?;
+        let error =
+            call("bad", &path, "validate", b"{}", TIMEOUT).expect_err("response is too large");
+        assert!(error.to_string().contains("over the limit"), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn modules_cannot_import_anything() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = write_module(
+            tmp.path(),
+            r#"(module (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32))))"#,
+        )?;
+        let error =
+            call("io", &path, "validate", b"{}", TIMEOUT).expect_err("imports are rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("imports `wasi_snapshot_preview1::fd_write`")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn looping_modules_run_out_of_the_fuel_of_their_timeout() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = write_module(
+            tmp.path(),
+            r#"(module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32) i32.const 16)
+              (func (export "validate") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                i64.const 0))"#,
+        )?;
+        let error = call("spin", &path, "validate", b"{}", Duration::from_millis(10))
+            .expect_err("the loop never ends");
+        assert!(error.to_string().contains("ran out of the fuel"), "{error}");
+        Ok(())
+    }
+}
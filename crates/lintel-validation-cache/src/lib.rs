@@ -3,12 +3,18 @@
 pub mod validation_error;
 pub use validation_error::*;
 
+#[cfg(feature = "disk")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 
+/// Distinguishes temporary files written concurrently by one process.
+#[cfg(feature = "disk")]
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 /// Whether a validation result was served from the disk cache or freshly computed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValidationCacheStatus {
@@ -18,26 +24,36 @@ pub enum ValidationCacheStatus {
     Miss,
 }
 
-/// The cache lookup/store key: file content, schema hash, and format-validation flag.
+/// The cache lookup/store key: file content, the validated fragment, schema
+/// hash, config hash, and format-validation flag.
 pub struct CacheKey<'a> {
     /// The raw file content being validated.
     pub file_content: &'a str,
+    /// JSON pointer of the fragment of the file that was validated, selected
+    /// by an override's `extract`; empty for the whole document.
+    pub instance_prefix: &'a str,
     /// Pre-computed SHA-256 hash of the schema (see [`schema_hash`]).
     pub schema_hash: &'a str,
+    /// Pre-computed SHA-256 hash of the configuration that changes results
+    /// beyond the schema itself (see [`config_hash`]).
+    pub config_hash: &'a str,
     /// Whether format validation was enabled.
     pub validate_formats: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[cfg(feature = "disk")]
+#[derive(serde::Serialize, serde::Deserialize)]
 struct CachedResult {
     errors: Vec<ValidationError>,
 }
 
 /// A disk-backed cache for JSON Schema validation results.
 ///
-/// Results are keyed by `SHA-256(crate_version + file_content + schema_json + validate_formats_byte)`.
+/// Results are keyed by `SHA-256(crate_version + file_content + instance_prefix + schema_hash +
+/// config_hash + validate_formats_byte)`.
 /// Cache files are stored as `<cache_dir>/<sha256-hex>.json`.
 #[derive(Clone)]
+#[cfg_attr(not(feature = "disk"), allow(dead_code))]
 pub struct ValidationCache {
     cache_dir: PathBuf,
     skip_read: bool,
@@ -58,6 +74,7 @@ impl ValidationCache {
     ///
     /// `key.schema_hash` should be obtained from [`schema_hash`] — pass the same
     /// value for all files in a schema group to avoid redundant serialization.
+    #[cfg(feature = "disk")]
     pub async fn lookup(
         &self,
         key: &CacheKey<'_>,
@@ -87,6 +104,7 @@ impl ValidationCache {
     ///
     /// `key.schema_hash` should be obtained from [`schema_hash`] — pass the same
     /// value for all files in a schema group to avoid redundant serialization.
+    #[cfg(feature = "disk")]
     pub async fn store(&self, key: &CacheKey<'_>, errors: &[ValidationError]) {
         let hash = Self::cache_key(key);
         let cache_path = self.cache_dir.join(format!("{hash}.json"));
@@ -99,8 +117,18 @@ impl ValidationCache {
             return;
         };
 
-        if tokio::fs::create_dir_all(&self.cache_dir).await.is_ok() {
-            let _ = tokio::fs::write(&cache_path, json).await;
+        // Write to a temporary file and rename it into place, so concurrent
+        // runs never read a partly written result.
+        let tmp = cache_path.with_extension(format!(
+            "tmp.{}.{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if tokio::fs::create_dir_all(&self.cache_dir).await.is_ok()
+            && tokio::fs::write(&tmp, json).await.is_ok()
+            && tokio::fs::rename(&tmp, &cache_path).await.is_err()
+        {
+            let _ = tokio::fs::remove_file(&tmp).await;
         }
     }
 
@@ -111,8 +139,15 @@ impl ValidationCache {
     pub fn cache_key(key: &CacheKey<'_>) -> String {
         let mut hasher = Sha256::new();
         hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
-        hasher.update(key.file_content.as_bytes());
-        hasher.update(key.schema_hash.as_bytes());
+        for part in [
+            key.file_content,
+            key.instance_prefix,
+            key.schema_hash,
+            key.config_hash,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
         hasher.update([u8::from(key.validate_formats)]);
         format!("{:x}", hasher.finalize())
     }
@@ -128,6 +163,25 @@ pub fn schema_hash(schema: &Value) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Compute a SHA-256 hash of the `[rewrite]` rules, which decide where a
+/// schema's `$ref`s are loaded from, so that changing them invalidates the
+/// results validated with the old ones.
+///
+/// Call this once per run and pass the result to [`ValidationCache::lookup`]
+/// and [`ValidationCache::store`].
+pub fn config_hash<S: core::hash::BuildHasher>(rewrite: &HashMap<String, String, S>) -> String {
+    let mut rules: Vec<(&String, &String)> = rewrite.iter().collect();
+    rules.sort();
+    let mut hasher = Sha256::new();
+    for (from, to) in rules {
+        hasher.update(from.as_bytes());
+        hasher.update([0]);
+        hasher.update(to.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
 /// Return a usable cache directory for validation results, creating it if necessary.
 ///
 /// Tries `<system_cache>/lintel/validations` first, falling back to
@@ -158,7 +212,9 @@ mod tests {
         let hash = schema_hash(&sample_schema());
         let key = CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         };
         let a = ValidationCache::cache_key(&key);
@@ -171,12 +227,16 @@ mod tests {
         let hash = schema_hash(&sample_schema());
         let a = ValidationCache::cache_key(&CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         });
         let b = ValidationCache::cache_key(&CacheKey {
             file_content: "world",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         });
         assert_ne!(a, b);
@@ -188,12 +248,16 @@ mod tests {
         let hash_b = schema_hash(&serde_json::json!({"type": "string"}));
         let a = ValidationCache::cache_key(&CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash_a,
+            config_hash: "",
             validate_formats: true,
         });
         let b = ValidationCache::cache_key(&CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash_b,
+            config_hash: "",
             validate_formats: true,
         });
         assert_ne!(a, b);
@@ -204,17 +268,57 @@ mod tests {
         let hash = schema_hash(&sample_schema());
         let a = ValidationCache::cache_key(&CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         });
         let b = ValidationCache::cache_key(&CacheKey {
             file_content: "hello",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: false,
         });
         assert_ne!(a, b);
     }
 
+    #[test]
+    fn cache_key_differs_on_fragment_and_config() {
+        let hash = schema_hash(&sample_schema());
+        let key = |instance_prefix, config_hash| {
+            ValidationCache::cache_key(&CacheKey {
+                file_content: "hello",
+                instance_prefix,
+                schema_hash: &hash,
+                config_hash,
+                validate_formats: true,
+            })
+        };
+        assert_ne!(key("", ""), key("/spec", ""));
+        assert_ne!(key("", ""), key("", "abc"));
+        assert_ne!(key("/a", "b"), key("", "/ab"));
+    }
+
+    #[test]
+    fn config_hash_ignores_rule_order() {
+        let rules = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(from, to)| ((*from).to_string(), (*to).to_string()))
+                .collect()
+        };
+        let a = rules(&[("http://a/", "//a/"), ("http://b/", "//b/")]);
+        let b = rules(&[("http://b/", "//b/"), ("http://a/", "//a/")]);
+        assert_eq!(config_hash(&a), config_hash(&b));
+        assert_ne!(
+            config_hash(&a),
+            config_hash(&rules(&[("http://a/", "//a/")]))
+        );
+        assert_ne!(config_hash(&a), config_hash(&HashMap::new()));
+    }
+
+    #[cfg(feature = "disk")]
     #[tokio::test]
     async fn store_and_lookup() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
@@ -231,7 +335,9 @@ mod tests {
         }];
         let key = CacheKey {
             file_content: "content",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         };
         cache.store(&key, &errors).await;
@@ -245,6 +351,7 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "disk")]
     #[tokio::test]
     async fn lookup_miss() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
@@ -253,7 +360,9 @@ mod tests {
 
         let key = CacheKey {
             file_content: "content",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         };
         let (result, status) = cache.lookup(&key).await;
@@ -262,6 +371,7 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "disk")]
     #[tokio::test]
     async fn skip_read_forces_miss() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
@@ -272,7 +382,9 @@ mod tests {
         // Store a result
         let key = CacheKey {
             file_content: "content",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         };
         cache_write.store(&key, &[]).await;
@@ -285,7 +397,9 @@ mod tests {
         // But store still writes (verify by reading with non-skip cache)
         let key_other = CacheKey {
             file_content: "other",
+            instance_prefix: "",
             schema_hash: &hash,
+            config_hash: "",
             validate_formats: true,
         };
         cache_skip
@@ -49,6 +49,12 @@ pub enum ValidationErrorKind {
         keyword: String,
         message: String,
     },
+    /// A property whose schema is marked `deprecated: true`, with the
+    /// schema's `description` (which usually names the replacement).
+    Deprecated {
+        property: String,
+        description: Option<String>,
+    },
     Enum {
         options: Value,
     },
@@ -58,6 +64,11 @@ pub enum ValidationErrorKind {
     ExclusiveMinimum {
         limit: Value,
     },
+    /// A GitHub Actions `${{ }}` expression that uses an unknown context or
+    /// function.
+    Expression {
+        message: String,
+    },
     FalseSchema,
     Format {
         format: String,
@@ -98,6 +109,11 @@ pub enum ValidationErrorKind {
     Pattern {
         pattern: String,
     },
+    /// A diagnostic from a `[plugins]` program's validate hook.
+    Plugin {
+        message: String,
+        warning: bool,
+    },
     PropertyNames {
         message: String,
     },
@@ -107,11 +123,18 @@ pub enum ValidationErrorKind {
     Type {
         expected: String,
     },
+    /// A property the schema allows only because it omits
+    /// `additionalProperties`, reported with `--strict-instances`.
+    UndescribedProperty {
+        property: String,
+    },
     UnevaluatedItems {
         unexpected: Vec<String>,
     },
-    UnevaluatedProperties {
-        unexpected: Vec<String>,
+    /// A single unevaluated property — split from `UnevaluatedProperties`
+    /// (plural).
+    UnevaluatedProperty {
+        property: String,
     },
     UniqueItems,
     Referencing {
@@ -120,6 +143,20 @@ pub enum ValidationErrorKind {
 }
 
 impl ValidationErrorKind {
+    /// Every value the schema allows, for `enum` and `const` errors.
+    pub fn allowed_values(&self) -> Option<Vec<Value>> {
+        match self {
+            Self::Enum {
+                options: Value::Array(options),
+            } => Some(options.clone()),
+            Self::Enum { options: value }
+            | Self::Constant {
+                expected_value: value,
+            } => Some(vec![value.clone()]),
+            _ => None,
+        }
+    }
+
     /// Produce a human-readable error message from the structured fields.
     #[allow(clippy::match_same_arms)]
     pub fn message(&self) -> String {
@@ -135,6 +172,8 @@ impl ValidationErrorKind {
             }
             Self::BacktrackLimitExceeded { message }
             | Self::Custom { message, .. }
+            | Self::Expression { message }
+            | Self::Plugin { message, .. }
             | Self::FromUtf8 { message }
             | Self::PropertyNames { message }
             | Self::Referencing { message } => message.clone(),
@@ -146,21 +185,14 @@ impl ValidationErrorKind {
             Self::ContentMediaType { content_media_type } => {
                 format!(r#"not compliant with "{content_media_type}" media type"#)
             }
-            Self::Enum { options } => {
-                let mut msg = String::new();
-                if let Value::Array(arr) = options {
-                    let _ = write!(msg, "value is not one of: ");
-                    for (i, opt) in arr.iter().enumerate() {
-                        if i > 0 {
-                            let _ = write!(msg, ", ");
-                        }
-                        let _ = write!(msg, "{opt}");
-                    }
-                } else {
-                    let _ = write!(msg, "{options} was expected");
-                }
-                msg
-            }
+            Self::Deprecated {
+                property,
+                description,
+            } => match description {
+                Some(description) => format!("'{property}' is deprecated: {description}"),
+                None => format!("'{property}' is deprecated"),
+            },
+            Self::Enum { options } => enum_message(options),
             Self::ExclusiveMaximum { limit } => {
                 format!("value is greater than or equal to the maximum of {limit}")
             }
@@ -208,23 +240,43 @@ impl ValidationErrorKind {
             Self::Pattern { pattern } => format!(r#"value does not match "{pattern}""#),
             Self::Required { property } => format!("{property} is a required property"),
             Self::Type { expected } => format!(r#"value is not of type "{expected}""#),
+            Self::UndescribedProperty { property } => format!("'{property}' is not in the schema"),
             Self::UnevaluatedItems { unexpected } => {
                 let mut msg = "Unevaluated items are not allowed (".to_string();
                 write_quoted_list(&mut msg, unexpected);
                 write_unexpected_suffix(&mut msg, unexpected.len());
                 msg
             }
-            Self::UnevaluatedProperties { unexpected } => {
-                let mut msg = "Unevaluated properties are not allowed (".to_string();
-                write_quoted_list(&mut msg, unexpected);
-                write_unexpected_suffix(&mut msg, unexpected.len());
-                msg
+            Self::UnevaluatedProperty { property } => {
+                format!("Unevaluated properties are not allowed ('{property}' was unexpected)")
             }
             Self::UniqueItems => "array has non-unique elements".to_string(),
         }
     }
 }
 
+/// Enum options listed in a message; longer lists end with "and N more".
+const ENUM_MESSAGE_LIMIT: usize = 10;
+
+fn enum_message(options: &Value) -> String {
+    let mut msg = String::new();
+    if let Value::Array(arr) = options {
+        let _ = write!(msg, "value is not one of: ");
+        for (i, opt) in arr.iter().take(ENUM_MESSAGE_LIMIT).enumerate() {
+            if i > 0 {
+                let _ = write!(msg, ", ");
+            }
+            let _ = write!(msg, "{opt}");
+        }
+        if arr.len() > ENUM_MESSAGE_LIMIT {
+            let _ = write!(msg, " (and {} more)", arr.len() - ENUM_MESSAGE_LIMIT);
+        }
+    } else {
+        let _ = write!(msg, "{options} was expected");
+    }
+    msg
+}
+
 fn write_quoted_list(buf: &mut String, items: &[String]) {
     for (i, item) in items.iter().enumerate() {
         if i > 0 {
@@ -259,6 +311,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unevaluated_property_message() {
+        let kind = ValidationErrorKind::UnevaluatedProperty {
+            property: "foo".to_string(),
+        };
+        assert_eq!(
+            kind.message(),
+            "Unevaluated properties are not allowed ('foo' was unexpected)"
+        );
+    }
+
     #[test]
     fn required_message() {
         let kind = ValidationErrorKind::Required {
@@ -283,6 +346,23 @@ mod tests {
         assert_eq!(kind.message(), r#"value is not one of: "a", "b", "c""#);
     }
 
+    #[test]
+    fn long_enum_message_is_truncated() {
+        let options: Vec<Value> = (1..=12).map(Value::from).collect();
+        let kind = ValidationErrorKind::Enum {
+            options: Value::Array(options.clone()),
+        };
+        assert_eq!(
+            kind.message(),
+            "value is not one of: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 (and 2 more)"
+        );
+        assert_eq!(kind.allowed_values(), Some(options));
+        let kind = ValidationErrorKind::Constant {
+            expected_value: json!("v1"),
+        };
+        assert_eq!(kind.allowed_values(), Some(vec![json!("v1")]));
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let error = ValidationError {
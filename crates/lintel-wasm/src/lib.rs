@@ -0,0 +1,157 @@
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use std::collections::HashMap;
+
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use lintel_diagnostics::reporter::{CheckResult, CheckedFile};
+
+/// The schemas `$ref`s may point to, by URI, supplied by the caller in
+/// place of the filesystem and network.
+struct Resources(HashMap<String, Value>);
+
+impl jsonschema::Retrieve for Resources {
+    fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> Result<Value, Box<dyn core::error::Error + Send + Sync>> {
+        let uri = uri.as_str();
+        self.0
+            .get(uri)
+            .or_else(|| self.0.get(uri.strip_suffix('#')?))
+            .cloned()
+            .ok_or_else(|| format!("'{uri}' is not among the resources").into())
+    }
+}
+
+fn parse_json(text: &str, what: &str) -> Result<Value, String> {
+    serde_json::from_str(text).map_err(|e| format!("{what} is not valid JSON: {e}"))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn validate_document(
+    path: &str,
+    content: &str,
+    schema: &str,
+    schema_url: &str,
+    resources: &str,
+) -> Result<String, String> {
+    let schema = parse_json(schema, "schema")?;
+    let resources: HashMap<String, Value> = serde_json::from_str(resources)
+        .map_err(|e| format!("resources is not a JSON object of schemas: {e}"))?;
+    let errors = lintel_validate::document::validate_document(
+        path,
+        content,
+        &schema,
+        schema_url,
+        Resources(resources),
+    );
+    let result = CheckResult {
+        errors,
+        checked: vec![CheckedFile {
+            path: path.to_string(),
+            schema: schema_url.to_string(),
+            cache_status: None,
+            validation_cache_status: None,
+            duration: None,
+        }],
+        timings: lintel_diagnostics::reporter::Timings::default(),
+        schema_hashes: BTreeMap::new(),
+        without_schema: Vec::new(),
+    };
+    Ok(lintel_reporters::reporters::json::report_json(&result).to_string())
+}
+
+fn explain_schema(path: &str, schema: &str) -> Result<String, String> {
+    let schema = jsonschema_migrate::migrate(parse_json(schema, "schema")?)
+        .map_err(|e| format!("failed to read schema: {e}"))?;
+    let opts = jsonschema_explain::ExplainOptions {
+        color: false,
+        syntax_highlight: false,
+        width: 80,
+        validation_errors: vec![],
+        extended: false,
+        max_depth: Some(jsonschema_explain::DEFAULT_MAX_DEPTH),
+        example_format: jsonschema_explain::ExampleFormat::for_path(path),
+        annotations: vec![],
+        required_only: false,
+        property_filter: None,
+        exclude_deprecated: false,
+    };
+    Ok(jsonschema_explain::explain(
+        &jsonschema_schema::SchemaValue::Schema(Box::new(schema)),
+        path,
+        &opts,
+    ))
+}
+
+/// Validate `content` as if it were the file at `path` against `schema`,
+/// returning the JSON report of `lintel ci --output json`.
+///
+/// `schema_url` names the schema in diagnostics and resolves its relative
+/// `$ref`s, and `resources` is a JSON object of the schemas `$ref`s may
+/// point to, by URI.
+///
+/// # Errors
+///
+/// Throws if `schema` or `resources` is not valid JSON.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn validate(
+    path: &str,
+    content: &str,
+    schema: &str,
+    schema_url: &str,
+    resources: &str,
+) -> Result<String, JsError> {
+    validate_document(path, content, schema, schema_url, resources).map_err(|e| JsError::new(&e))
+}
+
+/// Render the documentation of `schema` as plain text, with examples in the
+/// format of the data file at `path`.
+///
+/// # Errors
+///
+/// Throws if `schema` is not a valid JSON Schema document.
+#[wasm_bindgen]
+pub fn explain(path: &str, schema: &str) -> Result<String, JsError> {
+    explain_schema(path, schema).map_err(|e| JsError::new(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_against_schemas_from_resources() -> Result<(), String> {
+        let schema = r#"{"properties": {"port": {"$ref": "defs.json#/$defs/port"}}}"#;
+        let resources =
+            r#"{"https://example.com/defs.json": {"$defs": {"port": {"type": "integer"}}}}"#;
+        let report = validate_document(
+            "app.yaml",
+            "port: http\n",
+            schema,
+            "https://example.com/app.json",
+            resources,
+        )?;
+        let report = parse_json(&report, "report")?;
+        assert_eq!(report["files_checked"], 1);
+        assert_eq!(report["error_count"], 1);
+
+        let report = validate_document("app.yaml", "port: 1\n", schema, "app.json", "{}")?;
+        let report = parse_json(&report, "report")?;
+        assert_eq!(report["error_count"], 1, "unresolvable $ref is reported");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_json_arguments() {
+        assert!(validate_document("app.yaml", "", "{", "app.json", "{}").is_err());
+        assert!(validate_document("app.yaml", "", "{}", "app.json", "[]").is_err());
+        assert!(explain_schema("app.yaml", "[").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use core::time::Duration;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -5,16 +6,33 @@ use anyhow::{Context, Result, bail};
 use bpaf::{Bpaf, ShellComp};
 use lintel_cli_common::CLIGlobalOptions;
 
-use lintel_schema_cache::{CacheStatus, SchemaCache};
+use lintel_config::{MappingPriority, SchemaMapping};
+use lintel_schema_cache::{CacheEntry, CacheStatus, SchemaCache, Verification};
 use lintel_validate::parsers;
 use lintel_validate::validate;
 use schema_catalog::FileFormat;
 
 #[derive(Debug, Clone, Bpaf)]
 pub enum CacheCommand {
-    #[bpaf(command("inspect-schema"))]
+    #[bpaf(command("ls"))]
+    /// List the cached schemas with their size and age
+    Ls(#[bpaf(external(cache_dir_args))] CacheDirArgs),
+
+    #[bpaf(command("info"))]
     /// Show cache file info for a schema URL
-    InspectSchema(#[bpaf(external(inspect_schema_args))] InspectSchemaArgs),
+    Info(#[bpaf(external(info_args))] InfoArgs),
+
+    #[bpaf(command("clear"))]
+    /// Delete every cached schema
+    Clear(#[bpaf(external(cache_dir_args))] CacheDirArgs),
+
+    #[bpaf(command("prune"))]
+    /// Delete cached schemas not downloaded or revalidated recently
+    Prune(#[bpaf(external(prune_args))] PruneArgs),
+
+    #[bpaf(command("verify"))]
+    /// Recheck cached schemas against the content hashes recorded when they were downloaded
+    Verify(#[bpaf(external(cache_dir_args))] CacheDirArgs),
 
     #[bpaf(command("trace"))]
     /// Trace cache involvement for a file's validation
@@ -22,7 +40,13 @@ pub enum CacheCommand {
 }
 
 #[derive(Debug, Clone, Bpaf)]
-pub struct InspectSchemaArgs {
+pub struct CacheDirArgs {
+    #[bpaf(long("cache-dir"), argument("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
+    pub cache_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct InfoArgs {
     #[bpaf(long("cache-dir"), argument("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
     pub cache_dir: Option<String>,
 
@@ -31,6 +55,16 @@ pub struct InspectSchemaArgs {
     pub url: String,
 }
 
+#[derive(Debug, Clone, Bpaf)]
+pub struct PruneArgs {
+    #[bpaf(long("cache-dir"), argument("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
+    pub cache_dir: Option<String>,
+
+    /// Delete schemas last downloaded or revalidated longer ago than this (e.g. "30d")
+    #[bpaf(long("older-than"), argument::<String>("DURATION"), parse(lintel_cli_common::parse_duration))]
+    pub older_than: Duration,
+}
+
 #[derive(Debug, Clone, Bpaf)]
 pub struct TraceArgs {
     #[bpaf(long("cache-dir"), argument("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
@@ -50,10 +84,25 @@ pub struct TraceArgs {
 
 pub async fn run(cmd: CacheCommand, _global: &CLIGlobalOptions) -> Result<bool> {
     match cmd {
-        CacheCommand::InspectSchema(args) => {
-            inspect_schema(args)?;
+        CacheCommand::Ls(args) => {
+            list(&cache_dir(args.cache_dir))?;
+            Ok(false)
+        }
+        CacheCommand::Info(args) => {
+            info(&cache_dir(args.cache_dir), &args.url)?;
             Ok(false)
         }
+        CacheCommand::Clear(args) => {
+            remove(&cache_dir(args.cache_dir), |_| true)?;
+            Ok(false)
+        }
+        CacheCommand::Prune(args) => {
+            remove(&cache_dir(args.cache_dir), |entry| {
+                entry.age().is_some_and(|age| age > args.older_than)
+            })?;
+            Ok(false)
+        }
+        CacheCommand::Verify(args) => verify(&cache_dir(args.cache_dir)),
         CacheCommand::Trace(args) => {
             trace(args).await?;
             Ok(false)
@@ -61,28 +110,117 @@ pub async fn run(cmd: CacheCommand, _global: &CLIGlobalOptions) -> Result<bool>
     }
 }
 
-fn inspect_schema(args: InspectSchemaArgs) -> Result<()> {
-    let hash = SchemaCache::hash_uri(&args.url);
-    let cache_dir = args
-        .cache_dir
-        .map_or_else(lintel_schema_cache::ensure_cache_dir, PathBuf::from);
-    let cache_path = cache_dir.join(format!("{hash}.json"));
+/// The directory given with `--cache-dir`, or the default schema cache.
+fn cache_dir(arg: Option<String>) -> PathBuf {
+    arg.map_or_else(lintel_schema_cache::ensure_cache_dir, PathBuf::from)
+}
+
+/// How an entry is named in listings: its URL, or its hash when the URL was
+/// not recorded.
+fn entry_name(entry: &CacheEntry) -> &str {
+    entry.url.as_deref().unwrap_or(&entry.hash)
+}
+
+fn format_age(entry: &CacheEntry) -> String {
+    entry.age().map_or_else(
+        || "?".to_string(),
+        |age| humantime::format_duration(Duration::from_secs(age.as_secs())).to_string(),
+    )
+}
 
-    println!("URL:        {}", args.url);
-    println!("Hash:       {hash}");
-    println!("Cache file: {}", cache_path.display());
+fn list(cache_dir: &Path) -> Result<()> {
+    let entries = CacheEntry::list(cache_dir)
+        .with_context(|| format!("failed to read {}", cache_dir.display()))?;
+    for entry in &entries {
+        println!(
+            "{:>10}  {:>12}  {}",
+            entry.size,
+            format_age(entry),
+            entry_name(entry)
+        );
+    }
+    // Schemas served by several URLs are stored once.
+    let mut seen = std::collections::HashSet::new();
+    let total: u64 = entries
+        .iter()
+        .filter(|e| seen.insert(&e.content_hash))
+        .map(|e| e.size)
+        .sum();
+    println!(
+        "{} schemas, {total} bytes in {}",
+        entries.len(),
+        cache_dir.display()
+    );
+    Ok(())
+}
+
+/// Delete the entries `select` picks and whatever no remaining entry needs,
+/// reporting what was freed.
+fn remove(cache_dir: &Path, select: impl Fn(&CacheEntry) -> bool) -> Result<()> {
+    let entries = CacheEntry::list(cache_dir)
+        .with_context(|| format!("failed to read {}", cache_dir.display()))?;
+    let mut count = 0;
+    for entry in entries.iter().filter(|e| select(e)) {
+        entry
+            .remove()
+            .with_context(|| format!("failed to delete {}", entry_name(entry)))?;
+        count += 1;
+    }
+    let bytes = lintel_schema_cache::collect_garbage(cache_dir)
+        .with_context(|| format!("failed to read {}", cache_dir.display()))?;
+    println!(
+        "removed {count} schemas ({bytes} bytes) from {}",
+        cache_dir.display()
+    );
+    Ok(())
+}
 
-    if !cache_path.exists() {
+/// Recheck every entry, returning whether any is corrupt.
+fn verify(cache_dir: &Path) -> Result<bool> {
+    let entries = CacheEntry::list(cache_dir)
+        .with_context(|| format!("failed to read {}", cache_dir.display()))?;
+    let mut corrupt = 0;
+    for entry in &entries {
+        if let Verification::Corrupt(reason) = entry.verify() {
+            corrupt += 1;
+            println!("{}: {reason}", entry_name(entry));
+        }
+    }
+    println!("{} schemas checked, {corrupt} corrupt", entries.len());
+    if corrupt > 0 {
+        println!("run `lintel cache clear` or delete the corrupt entries to download them again");
+    }
+    Ok(corrupt > 0)
+}
+
+fn info(cache_dir: &Path, url: &str) -> Result<()> {
+    println!("URL:        {url}");
+    println!("Hash:       {}", SchemaCache::hash_uri(url));
+
+    let Some(entry) = CacheEntry::find(cache_dir, url) else {
         println!("Status:     not cached");
         return Ok(());
-    }
+    };
 
-    let meta = fs::metadata(&cache_path)?;
-    println!("Size:       {} bytes", meta.len());
-    print_modified_age(&meta);
+    println!("Cache file: {}", entry.path.display());
+    println!("Size:       {} bytes", entry.size);
+    if entry.age().is_some() {
+        println!("Modified:   {} ago", format_age(&entry));
+    }
+    let validators = entry.validators();
+    if let Some(etag) = validators.etag {
+        println!("ETag:       {etag}");
+    }
+    if let Some(last_modified) = validators.last_modified {
+        println!("Last-Mod:   {last_modified}");
+    }
+    match entry.verify() {
+        Verification::Ok => println!("Integrity:  ok"),
+        Verification::Corrupt(reason) => println!("Integrity:  corrupt ({reason})"),
+    }
 
-    let content = fs::read_to_string(&cache_path)
-        .with_context(|| format!("failed to read cache file: {}", cache_path.display()))?;
+    let content = fs::read_to_string(&entry.path)
+        .with_context(|| format!("failed to read cache file: {}", entry.path.display()))?;
 
     if let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) {
         let preview = format_json_preview(&value);
@@ -135,8 +273,14 @@ async fn trace(args: TraceArgs) -> Result<()> {
     let config_search_dir = file_path.parent().map(Path::to_path_buf);
     let (cfg, config_dir, _config_path) = validate::load_config(config_search_dir.as_deref());
 
-    let compiled_catalogs =
-        trace_catalog(&retriever, &cfg, args.no_catalog, &schema_cache_dir).await;
+    let compiled_catalogs = trace_catalog(
+        &retriever,
+        &cfg,
+        &config_dir,
+        args.no_catalog,
+        &schema_cache_dir,
+    )
+    .await;
 
     // Parse file and resolve schema
     let detected_format = parsers::detect_format(file_path);
@@ -170,25 +314,26 @@ async fn trace(args: TraceArgs) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn trace_catalog(
     retriever: &SchemaCache,
     cfg: &lintel_config::Config,
+    config_dir: &Path,
     no_catalog: bool,
     schema_cache_dir: &Path,
 ) -> Vec<schema_catalog::CompiledCatalog> {
     println!();
     println!("catalog:");
-    let compiled_catalogs = validate::fetch_compiled_catalogs(retriever, cfg, no_catalog).await;
+    let compiled_catalogs =
+        validate::fetch_compiled_catalogs(retriever, cfg, config_dir, no_catalog).await;
     if no_catalog {
         println!("  status: disabled (--no-catalog)");
     } else {
         let catalog_url = lintel_validate::catalog::SCHEMASTORE_CATALOG_URL;
-        let catalog_hash = SchemaCache::hash_uri(catalog_url);
-        let catalog_cache_path = schema_cache_dir.join(format!("{catalog_hash}.json"));
         println!("  url: {catalog_url}");
-        println!("  hash: {catalog_hash}");
-        if catalog_cache_path.exists() {
-            print_cache_file_info(&catalog_cache_path, "  ");
+        println!("  hash: {}", SchemaCache::hash_uri(catalog_url));
+        if let Some(entry) = CacheEntry::find(schema_cache_dir, catalog_url) {
+            print_cache_entry_info(&entry, "  ");
         } else {
             println!("  cache: miss (not on disk)");
         }
@@ -210,32 +355,41 @@ fn trace_schema_resolution(
 ) -> Option<(String, bool)> {
     println!();
     println!("schema resolution:");
-    let schema_uri = if let Some(uri) = parser.extract_schema_uri(content, instance) {
-        println!("  source: inline ($schema / modeline)");
-        uri
-    } else if let Some((pattern, url)) = cfg
-        .schemas
-        .iter()
-        .find(|(pattern, _)| {
-            let p = path_str.strip_prefix("./").unwrap_or(path_str);
-            glob_matcher::glob_match(pattern, p) || glob_matcher::glob_match(pattern, file_name)
-        })
-        .map(|(pattern, url)| (pattern.as_str(), url.as_str()))
-    {
+    let print_mapping = |(pattern, mapping): (&str, &SchemaMapping)| {
         println!("  source: config mapping");
         println!("  pattern: {pattern}");
-        url.to_string()
-    } else if let Some(schema_match) = compiled_catalogs
-        .iter()
-        .find_map(|cat| cat.find_schema_detailed(path_str, file_name))
-    {
-        println!("  source: catalog");
-        println!("  matched: {}", schema_match.matched_pattern);
-        println!("  name: {}", schema_match.name);
-        schema_match.url.to_string()
-    } else {
-        println!("  result: no schema found");
-        return None;
+        match mapping.priority() {
+            MappingPriority::High => println!("  priority: high (overrides inline $schema)"),
+            MappingPriority::Low => println!("  priority: low (after catalogs)"),
+            MappingPriority::Normal => {}
+        }
+        mapping.url().to_string()
+    };
+    let mapping = cfg.find_schema_mapping_entry(path_str, file_name);
+    let priority = mapping.map(|(_, m)| m.priority());
+    let schema_uri = match (parser.extract_schema_uri(content, instance), mapping) {
+        (_, Some(m)) if priority == Some(MappingPriority::High) => print_mapping(m),
+        (Some(uri), _) => {
+            println!("  source: inline ($schema / modeline)");
+            uri
+        }
+        (None, Some(m)) if priority == Some(MappingPriority::Normal) => print_mapping(m),
+        (None, low_mapping) => {
+            if let Some(schema_match) = compiled_catalogs
+                .iter()
+                .find_map(|cat| cat.find_schema_detailed(path_str, file_name))
+            {
+                println!("  source: catalog");
+                println!("  matched: {}", schema_match.matched_pattern);
+                println!("  name: {}", schema_match.name);
+                schema_match.url.to_string()
+            } else if let Some(m) = low_mapping {
+                print_mapping(m)
+            } else {
+                println!("  result: no schema found");
+                return None;
+            }
+        }
     };
 
     // Apply rewrites
@@ -264,12 +418,10 @@ async fn trace_schema_cache(
     println!();
     println!("schema cache:");
     if is_remote {
-        let schema_hash = SchemaCache::hash_uri(schema_uri);
-        let schema_cache_path = schema_cache_dir.join(format!("{schema_hash}.json"));
-        println!("  hash: {schema_hash}");
-        println!("  path: {}", schema_cache_path.display());
-        if schema_cache_path.exists() {
-            print_cache_file_info(&schema_cache_path, "  ");
+        println!("  hash: {}", SchemaCache::hash_uri(schema_uri));
+        if let Some(entry) = CacheEntry::find(schema_cache_dir, schema_uri) {
+            println!("  path: {}", entry.path.display());
+            print_cache_entry_info(&entry, "  ");
         } else {
             println!("  cache: miss (not on disk)");
         }
@@ -326,9 +478,15 @@ async fn trace_validation_cache(
         let schema_hash = lintel_validation_cache::schema_hash(&schema_value);
         let vcache = lintel_validation_cache::ValidationCache::new(vcache_dir, false);
         let validate_formats = cfg.should_validate_formats(path_str, &[schema_uri]);
+        let instance_prefix = cfg
+            .extract_pointer(path_str, &[schema_uri])
+            .unwrap_or_default();
+        let config_hash = lintel_validation_cache::config_hash(&cfg.rewrite);
         let ck = lintel_validation_cache::CacheKey {
             file_content: content,
+            instance_prefix: &instance_prefix,
             schema_hash: &schema_hash,
+            config_hash: &config_hash,
             validate_formats,
         };
         let cache_key = lintel_validation_cache::ValidationCache::cache_key(&ck);
@@ -368,25 +526,10 @@ fn format_json_preview(value: &serde_json::Value) -> String {
     }
 }
 
-fn print_modified_age(meta: &fs::Metadata) {
-    if let Ok(modified) = meta.modified()
-        && let Ok(age) = modified.elapsed()
-    {
-        let duration = humantime::format_duration(core::time::Duration::from_secs(age.as_secs()));
-        println!("Modified:   {duration} ago");
-    }
-}
-
-fn print_cache_file_info(path: &Path, indent: &str) {
-    if let Ok(meta) = fs::metadata(path) {
-        println!("{indent}size: {} bytes", meta.len());
-        if let Ok(modified) = meta.modified()
-            && let Ok(age) = modified.elapsed()
-        {
-            let duration =
-                humantime::format_duration(core::time::Duration::from_secs(age.as_secs()));
-            println!("{indent}modified: {duration} ago");
-        }
+fn print_cache_entry_info(entry: &CacheEntry, indent: &str) {
+    println!("{indent}size: {} bytes", entry.size);
+    if entry.age().is_some() {
+        println!("{indent}modified: {} ago", format_age(entry));
     }
 }
 
@@ -0,0 +1,232 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bpaf::{Bpaf, ShellComp};
+use lintel_validate::registry;
+use schema_catalog::Catalog;
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct BuildArgs {
+    /// Write the catalog to FILE instead of stdout
+    #[bpaf(short('o'), long("output"), argument("FILE"))]
+    pub output: Option<PathBuf>,
+
+    /// URL the schemas are published under; entry URLs become URL/<path in DIR>
+    #[bpaf(long("base-url"), argument("URL"))]
+    pub base_url: Option<String>,
+
+    /// Add a fileMatch glob to a schema, given by its path in DIR (repeatable)
+    #[bpaf(long("file-match"), argument("SCHEMA=GLOB"))]
+    pub file_match: Vec<String>,
+
+    /// Catalog title
+    #[bpaf(long("title"), argument("TITLE"))]
+    pub title: Option<String>,
+
+    /// Directory containing the JSON Schemas
+    #[bpaf(positional("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
+    pub dir: PathBuf,
+}
+
+/// Run `lintel catalog build`.
+pub fn run(args: &BuildArgs) -> Result<()> {
+    let catalog = build_catalog(args)?;
+    for entry in catalog.schemas.iter().filter(|e| e.file_match.is_empty()) {
+        eprintln!(
+            "warning: {} has no fileMatch patterns and will not be matched to files",
+            entry.url
+        );
+    }
+    let json = serde_json::to_string_pretty(&catalog)? + "\n";
+    match &args.output {
+        Some(path) => {
+            fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!(
+                "Wrote {} schemas to {}",
+                catalog.schemas.len(),
+                path.display()
+            );
+        }
+        None => print!("{json}"),
+    }
+    Ok(())
+}
+
+/// Scan `args.dir` and build the catalog.
+///
+/// Entry URLs are, in order of preference: `--base-url` joined with the
+/// schema's path in the directory, the schema's `$id`, or its path relative
+/// to the output file.
+fn build_catalog(args: &BuildArgs) -> Result<Catalog> {
+    let schemas = registry::read_schema_dir(&args.dir)
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("failed to read schemas from {}", args.dir.display()))?;
+    let extra = parse_file_matches(&args.file_match)?;
+    let output_dir = args
+        .output
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""));
+
+    let mut entries = Vec::with_capacity(schemas.len());
+    for (file, value) in &schemas {
+        let rel = relative_path(file, &args.dir);
+        let mut entry = registry::schema_entry(file, value);
+        entry.url = match (&args.base_url, &entry.source_url) {
+            (Some(base), _) => format!("{}/{rel}", base.trim_end_matches('/')),
+            (None, Some(id)) => id.clone(),
+            (None, None) => relative_to(file, output_dir),
+        };
+        if entry.source_url.as_ref() == Some(&entry.url) {
+            entry.source_url = None;
+        }
+        for (_, glob) in extra.iter().filter(|(schema, _)| *schema == rel) {
+            if !entry.file_match.iter().any(|g| g == glob) {
+                entry.file_match.push((*glob).to_string());
+            }
+        }
+        entries.push(entry);
+    }
+
+    for (schema, _) in &extra {
+        if !schemas
+            .iter()
+            .any(|(file, _)| relative_path(file, &args.dir) == *schema)
+        {
+            bail!("--file-match: no schema {schema} in {}", args.dir.display());
+        }
+    }
+
+    Ok(Catalog {
+        version: 1,
+        title: args.title.clone(),
+        schemas: entries,
+        ..Catalog::default()
+    })
+}
+
+/// Split `SCHEMA=GLOB` arguments.
+fn parse_file_matches(args: &[String]) -> Result<Vec<(&str, &str)>> {
+    args.iter()
+        .map(|arg| match arg.split_once('=') {
+            Some((schema, glob)) if !schema.is_empty() && !glob.is_empty() => {
+                Ok((schema.trim_start_matches("./"), glob))
+            }
+            _ => bail!("--file-match expects SCHEMA=GLOB, got {arg:?}"),
+        })
+        .collect()
+}
+
+/// `path` relative to `dir`, with `/` separators.
+fn relative_path(path: &Path, dir: &Path) -> String {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// `path` relative to `dir` when both are relative (or share a prefix),
+/// walking up with `..` as needed.
+fn relative_to(path: &Path, dir: &Path) -> String {
+    let path: Vec<_> = path.components().collect();
+    let dir: Vec<_> = dir.components().collect();
+    let common = path.iter().zip(&dir).take_while(|(a, b)| a == b).count();
+    let mut out = PathBuf::new();
+    for _ in common..dir.len() {
+        out.push("..");
+    }
+    out.extend(&path[common..]);
+    out.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(dir: &Path) -> BuildArgs {
+        BuildArgs {
+            output: None,
+            base_url: None,
+            file_match: Vec::new(),
+            title: None,
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn write_schemas(dir: &Path) -> Result<()> {
+        fs::create_dir(dir.join("ci"))?;
+        fs::write(
+            dir.join("deploy.json"),
+            r#"{"$id":"https://schemas.internal/deploy.json","title":"Deploy","description":"Deploy config","fileMatch":["deploy.yaml"]}"#,
+        )?;
+        fs::write(dir.join("ci/pipeline.json"), r#"{"title":"Pipeline"}"#)?;
+        Ok(())
+    }
+
+    #[test]
+    fn builds_entries_from_schema_metadata() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        write_schemas(tmp.path())?;
+        let mut args = args(tmp.path());
+        args.title = Some("Internal".into());
+        args.file_match = vec!["ci/pipeline.json=ci/*.yml".into()];
+
+        let catalog = build_catalog(&args)?;
+        assert_eq!(catalog.title.as_deref(), Some("Internal"));
+        let [pipeline, deploy] = catalog.schemas.as_slice() else {
+            panic!("expected two entries");
+        };
+        assert_eq!(deploy.name, "Deploy");
+        assert_eq!(deploy.description, "Deploy config");
+        assert_eq!(deploy.url, "https://schemas.internal/deploy.json");
+        assert_eq!(deploy.source_url, None);
+        assert_eq!(deploy.file_match, ["deploy.yaml"]);
+        assert_eq!(pipeline.name, "Pipeline");
+        assert_eq!(pipeline.file_match, ["ci/*.yml"]);
+        Ok(())
+    }
+
+    #[test]
+    fn base_url_sets_entry_urls() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        write_schemas(tmp.path())?;
+        let mut args = args(tmp.path());
+        args.base_url = Some("https://example.com/schemas/".into());
+
+        let catalog = build_catalog(&args)?;
+        assert_eq!(
+            catalog.schemas[0].url,
+            "https://example.com/schemas/ci/pipeline.json"
+        );
+        assert_eq!(
+            catalog.schemas[1].source_url.as_deref(),
+            Some("https://schemas.internal/deploy.json")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_file_match() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        write_schemas(tmp.path())?;
+        let mut args = args(tmp.path());
+        args.file_match = vec!["missing.json=*.yml".into()];
+        assert!(build_catalog(&args).is_err());
+        args.file_match = vec!["deploy.json".into()];
+        assert!(build_catalog(&args).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn relative_to_walks_up() {
+        assert_eq!(
+            relative_to(Path::new("schemas/a.json"), Path::new("")),
+            "schemas/a.json"
+        );
+        assert_eq!(
+            relative_to(Path::new("schemas/a.json"), Path::new("dist")),
+            "../schemas/a.json"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use bpaf::Bpaf;
+
+pub mod build;
+pub mod search;
+
+#[derive(Debug, Clone, Bpaf)]
+pub enum CatalogCommand {
+    #[bpaf(command("build"))]
+    /// Generate a SchemaStore-format catalog from a directory of schemas
+    Build(#[bpaf(external(build::build_args))] build::BuildArgs),
+
+    #[bpaf(command("search"))]
+    /// Search the configured catalogs for schemas
+    Search(#[bpaf(external(search::search_args))] search::SearchArgs),
+}
+
+pub async fn run(cmd: &CatalogCommand) -> Result<()> {
+    match cmd {
+        CatalogCommand::Build(args) => build::run(args),
+        CatalogCommand::Search(args) => search::run(args).await,
+    }
+}
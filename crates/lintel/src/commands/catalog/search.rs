@@ -0,0 +1,78 @@
+use core::fmt::Write;
+use std::collections::HashSet;
+
+use anyhow::Result;
+use bpaf::Bpaf;
+use lintel_schema_cache::SchemaCache;
+use lintel_validate::validate;
+use schema_catalog::SearchHit;
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct SearchArgs {
+    /// Text to look for in schema names, fileMatch patterns, descriptions and URLs
+    #[bpaf(positional("TERM"))]
+    pub term: String,
+}
+
+/// Run `lintel catalog search`.
+pub async fn run(args: &SearchArgs) -> Result<()> {
+    let (config, config_dir, _) = validate::load_config(None);
+    let retriever = SchemaCache::builder().build();
+    let catalogs = validate::fetch_compiled_catalogs(&retriever, &config, &config_dir, false).await;
+
+    // Catalogs are in priority order; the first entry for a URL wins.
+    let mut seen = HashSet::new();
+    let hits: Vec<SearchHit<'_>> = catalogs
+        .iter()
+        .flat_map(|cat| cat.search(&args.term))
+        .filter(|hit| seen.insert(hit.url))
+        .collect();
+
+    if hits.is_empty() {
+        eprintln!("No schemas match {:?}.", args.term);
+        return Ok(());
+    }
+    for hit in &hits {
+        print!("{}", render_hit(hit));
+    }
+    eprintln!(
+        "{} schema{} match {:?}.",
+        hits.len(),
+        if hits.len() == 1 { "" } else { "s" },
+        args.term
+    );
+    Ok(())
+}
+
+fn render_hit(hit: &SearchHit<'_>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", hit.name);
+    if let Some(description) = hit.description {
+        let _ = writeln!(out, "  {description}");
+    }
+    if !hit.file_match.is_empty() {
+        let _ = writeln!(out, "  fileMatch: {}", hit.file_match.join(", "));
+    }
+    let _ = writeln!(out, "  url: {}", hit.url);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_name_description_patterns_and_url() {
+        let file_match = ["*.deploy.yaml".to_string()];
+        let hit = SearchHit {
+            url: "https://example.com/deploy.json",
+            name: "Deploy",
+            description: Some("Deployment config"),
+            file_match: &file_match,
+        };
+        assert_eq!(
+            render_hit(&hit),
+            "Deploy\n  Deployment config\n  fileMatch: *.deploy.yaml\n  url: https://example.com/deploy.json\n"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use core::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use bpaf::{Bpaf, ShellComp};
+use lintel_config::ConfigLayer;
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct CheckArgs {
+    /// Directory to search for lintel.toml (defaults to the current directory)
+    #[bpaf(positional("DIR"), complete_shell(ShellComp::Dir { mask: None }))]
+    pub dir: Option<PathBuf>,
+}
+
+/// A config layer parsed as a plain TOML table, for printing.
+struct Layer<'a> {
+    origin: &'a str,
+    table: toml::Table,
+}
+
+/// Run `lintel config check`.
+pub fn run(args: &CheckArgs) -> Result<bool> {
+    let dir = match &args.dir {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir()?,
+    };
+    let layers = lintel_config::find_layers(&dir)?;
+    if layers.is_empty() {
+        eprintln!("No lintel.toml found in {} or its parents.", dir.display());
+        return Ok(false);
+    }
+
+    println!("Config files (highest priority first):");
+    for (i, layer) in layers.iter().enumerate() {
+        println!("  [{}] {}", i + 1, layer.origin);
+    }
+
+    let errors = schema_errors(&layers)?;
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("error: {error}");
+        }
+        eprintln!(
+            "{} error{} found.",
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" }
+        );
+        return Ok(true);
+    }
+
+    let tables = layers
+        .iter()
+        .map(|layer| -> Result<Layer<'_>> {
+            Ok(Layer {
+                origin: &layer.origin,
+                table: layer.content.parse()?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    println!();
+    println!("Effective configuration:");
+    println!();
+    print!("{}", render_effective(&tables));
+
+    let config = lintel_config::find_and_load(&dir)?.unwrap_or_default();
+    let origins = override_origins(&tables);
+    for (i, reason) in config.unreachable_overrides() {
+        let origin = origins.get(i).copied().unwrap_or("?");
+        eprintln!("warning: override #{} ({origin}) {reason}", i + 1);
+    }
+    Ok(false)
+}
+
+/// Validate every layer against the `lintel.toml` JSON Schema.
+fn schema_errors(layers: &[ConfigLayer]) -> Result<Vec<String>> {
+    let schema = lintel_config::schema();
+    let validator = jsonschema::validator_for(&schema)
+        .map_err(|e| anyhow::anyhow!("invalid lintel.toml schema: {e}"))?;
+    let mut errors = Vec::new();
+    for layer in layers {
+        let instance: serde_json::Value = toml::from_str(&layer.content)
+            .with_context(|| format!("failed to parse {}", layer.origin))?;
+        for error in validator.iter_errors(&instance) {
+            let path = error.instance_path().to_string();
+            let at = if path.is_empty() {
+                String::new()
+            } else {
+                format!(" (at {path})")
+            };
+            errors.push(format!("{}: {error}{at}", layer.origin));
+        }
+    }
+    Ok(errors)
+}
+
+/// Render the merged config as TOML, with a `# [n]` comment after each
+/// setting naming the layer it came from. Follows the merge rules of
+/// `lintel_config::Config::merge_parent`.
+fn render_effective(layers: &[Layer<'_>]) -> String {
+    let mut out = String::new();
+    let Some(child) = layers.first() else {
+        return out;
+    };
+    // `root`, `no-default-catalog` and `no-schema-index` are never inherited.
+    for key in ["root", "no-default-catalog", "no-schema-index"] {
+        if let Some(value) = child.table.get(key) {
+            let _ = writeln!(out, "{key} = {value}  # [1]");
+        }
+    }
+    render_list(&mut out, layers, "exclude", false);
+    render_list(&mut out, layers, "registries", true);
+    render_map(&mut out, layers, "schemas");
+    render_map(&mut out, layers, "rewrite");
+    render_map(&mut out, layers, "network");
+    render_map(&mut out, layers, "kubernetes");
+    render_map(&mut out, layers, "schema-lint");
+
+    for (i, layer) in layers.iter().enumerate() {
+        let overrides = layer.table.get("override").and_then(toml::Value::as_array);
+        for ov in overrides.into_iter().flatten() {
+            let mut wrapper = toml::Table::new();
+            wrapper.insert("override".into(), toml::Value::Array(vec![ov.clone()]));
+            render_section(&mut out, &wrapper, i);
+        }
+    }
+    if let Some((i, format)) = layers
+        .iter()
+        .enumerate()
+        .find_map(|(i, layer)| layer.table.get("format").map(|f| (i, f)))
+    {
+        let mut wrapper = toml::Table::new();
+        wrapper.insert("format".into(), format.clone());
+        render_section(&mut out, &wrapper, i);
+    }
+    out
+}
+
+/// A list whose entries from every layer are concatenated.
+fn render_list(out: &mut String, layers: &[Layer<'_>], key: &str, dedup: bool) {
+    let mut seen: Vec<&toml::Value> = Vec::new();
+    let mut lines = String::new();
+    for (i, layer) in layers.iter().enumerate() {
+        let entries = layer.table.get(key).and_then(toml::Value::as_array);
+        for entry in entries.into_iter().flatten() {
+            if dedup && seen.contains(&entry) {
+                continue;
+            }
+            seen.push(entry);
+            let _ = writeln!(lines, "  {entry},  # [{}]", i + 1);
+        }
+    }
+    if !lines.is_empty() {
+        let _ = write!(out, "{key} = [\n{lines}]\n");
+    }
+}
+
+/// A table whose keys are taken from the first layer that sets them.
+fn render_map(out: &mut String, layers: &[Layer<'_>], key: &str) {
+    let mut seen: Vec<&str> = Vec::new();
+    let mut lines = String::new();
+    for (i, layer) in layers.iter().enumerate() {
+        let entries = layer.table.get(key).and_then(toml::Value::as_table);
+        for (k, v) in entries.into_iter().flatten() {
+            if seen.contains(&k.as_str()) {
+                continue;
+            }
+            seen.push(k);
+            let quoted = toml::Value::String(k.clone());
+            let _ = writeln!(lines, "{quoted} = {v}  # [{}]", i + 1);
+        }
+    }
+    if !lines.is_empty() {
+        let _ = write!(out, "\n[{key}]\n{lines}");
+    }
+}
+
+/// A `[section]` or `[[section]]` from one layer, serialized as TOML.
+fn render_section(out: &mut String, wrapper: &toml::Table, layer: usize) {
+    let Ok(text) = toml::to_string(wrapper) else {
+        return;
+    };
+    let mut lines = text.lines();
+    if let Some(header) = lines.next() {
+        let _ = writeln!(out, "\n{header}  # [{}]", layer + 1);
+    }
+    for line in lines {
+        let _ = writeln!(out, "{line}");
+    }
+}
+
+/// The origin of each merged override, in merge order.
+fn override_origins<'a>(layers: &[Layer<'a>]) -> Vec<&'a str> {
+    layers
+        .iter()
+        .flat_map(|layer| {
+            let count = layer
+                .table
+                .get("override")
+                .and_then(toml::Value::as_array)
+                .map_or(0, Vec::len);
+            core::iter::repeat_n(layer.origin, count)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer<'a>(origin: &'a str, content: &str) -> anyhow::Result<Layer<'a>> {
+        Ok(Layer {
+            origin,
+            table: content.parse()?,
+        })
+    }
+
+    #[test]
+    fn renders_merged_settings_with_provenance() -> anyhow::Result<()> {
+        let layers = [
+            layer(
+                "lintel.toml",
+                "root = true\nexclude = [\"own/**\"]\nregistries = [\"r\"]\n[schemas]\n\"a.json\" = \"own\"\n[[override]]\nfiles = [\"x/**\"]\nseverity = \"warning\"\n",
+            )?,
+            layer(
+                "base.toml",
+                "root = true\nexclude = [\"base/**\"]\nregistries = [\"r\"]\n[schemas]\n\"a.json\" = \"base\"\n\"b.json\" = \"base\"\n[format]\ntoml_style = \"auto\"\n",
+            )?,
+        ];
+        let out = render_effective(&layers);
+        assert_eq!(
+            out,
+            r#"root = true  # [1]
+exclude = [
+  "own/**",  # [1]
+  "base/**",  # [2]
+]
+registries = [
+  "r",  # [1]
+]
+
+[schemas]
+"a.json" = "own"  # [1]
+"b.json" = "base"  # [2]
+
+[[override]]  # [1]
+files = ["x/**"]
+severity = "warning"
+
+[format]  # [2]
+toml_style = "auto"
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn override_origins_follow_merge_order() -> anyhow::Result<()> {
+        let layers = [
+            layer("a", "[[override]]\nfiles = [\"x\"]\n")?,
+            layer("b", "")?,
+            layer(
+                "c",
+                "[[override]]\nfiles = [\"y\"]\n[[override]]\nfiles = [\"z\"]\n",
+            )?,
+        ];
+        assert_eq!(override_origins(&layers), ["a", "c", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_schema_errors_per_layer() -> anyhow::Result<()> {
+        let layers = [ConfigLayer {
+            origin: "lintel.toml".into(),
+            content: "exclude = \"vendor/**\"\n".into(),
+            remote: false,
+        }];
+        let errors = schema_errors(&layers)?;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("lintel.toml: "), "{}", errors[0]);
+        assert!(errors[0].ends_with("(at /exclude)"), "{}", errors[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use alloc::collections::BTreeMap;
+use core::fmt::Write as _;
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write as _};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use bpaf::Bpaf;
+use lintel_schema_cache::SchemaCache;
+use lintel_validate::{parsers, validate};
+use schema_catalog::{CompiledCatalog, FileFormat};
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct InitArgs {
+    /// Accept every suggestion without prompting
+    #[bpaf(long("yes"), switch)]
+    pub yes: bool,
+
+    /// Skip catalog lookups when detecting schemas
+    #[bpaf(long("no-catalog"), switch)]
+    pub no_catalog: bool,
+}
+
+/// Directory names whose contents are usually vendored or generated and
+/// should not be validated.
+const VENDORED_DIRS: &[&str] = &[
+    "vendor",
+    "third_party",
+    "third-party",
+    "node_modules",
+    "testdata",
+    "fixtures",
+    "__fixtures__",
+    "dist",
+];
+
+/// How many files without a schema are listed as mapping stubs.
+const MAX_UNMATCHED_STUBS: usize = 10;
+
+/// How a scanned file gets its schema today.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Detected {
+    /// `$schema` property or modeline in the file.
+    Inline(String),
+    /// Catalog match, with the schema's name.
+    Catalog(String),
+    None,
+}
+
+#[derive(Debug)]
+struct ScannedFile {
+    path: String,
+    format: FileFormat,
+    schema: Detected,
+}
+
+/// Run `lintel config init`.
+pub async fn run(args: &InitArgs) -> Result<()> {
+    let config_path = Path::new("lintel.toml");
+    if config_path.exists() {
+        bail!("lintel.toml already exists");
+    }
+
+    let paths =
+        lintel_config::discover::discover_files(".", &[], |p| parsers::detect_format(p).is_some())?;
+    let excludes = vendored_excludes(&paths);
+    let retriever = SchemaCache::builder().build();
+    let catalogs = validate::fetch_compiled_catalogs(
+        &retriever,
+        &lintel_config::Config::default(),
+        Path::new("."),
+        args.no_catalog,
+    )
+    .await;
+    let files: Vec<ScannedFile> = paths
+        .iter()
+        .filter(|p| !lintel_config::discover::is_excluded(p, &excludes))
+        .filter_map(|p| scan_file(p, &catalogs))
+        .collect();
+
+    print_summary(&files);
+
+    let interactive = !args.yes && std::io::stdin().is_terminal();
+    let mut accepted_excludes = Vec::new();
+    for pattern in excludes {
+        if !interactive || confirm(&format!("Exclude {pattern}?"))? {
+            accepted_excludes.push(pattern);
+        }
+    }
+    let mut accepted_mappings = Vec::new();
+    for (pattern, url) in suggest_mappings(&files) {
+        if !interactive || confirm(&format!("Map {pattern} to {url}?"))? {
+            accepted_mappings.push((pattern, url));
+        }
+    }
+    let unmatched: Vec<&str> = files
+        .iter()
+        .filter(|f| f.schema == Detected::None)
+        .filter(|f| {
+            !accepted_mappings
+                .iter()
+                .any(|(p, _)| glob_matcher::glob_match(p, &f.path))
+        })
+        .map(|f| f.path.as_str())
+        .collect();
+
+    fs::write(
+        config_path,
+        render_config(&accepted_excludes, &accepted_mappings, &unmatched),
+    )?;
+    eprintln!("Created lintel.toml");
+    Ok(())
+}
+
+/// Parse a file and work out where its schema comes from. Returns `None`
+/// for files that cannot be read or parsed.
+fn scan_file(path: &Path, catalogs: &[CompiledCatalog]) -> Option<ScannedFile> {
+    let format = parsers::detect_format(path)?;
+    let content = fs::read_to_string(path).ok()?;
+    let path_str = path.to_string_lossy();
+    let path_str = path_str.strip_prefix("./").unwrap_or(&path_str).to_string();
+    let file_name = path.file_name()?.to_string_lossy();
+
+    let parser = parsers::parser_for(format);
+    let value = parser.parse(&content, &path_str).ok()?;
+    let schema = if let Some(uri) = parser.extract_schema_uri(&content, &value) {
+        Detected::Inline(uri)
+    } else if let Some(m) = catalogs
+        .iter()
+        .find_map(|cat| cat.find_schema_detailed(&path_str, &file_name))
+    {
+        Detected::Catalog(m.name.to_string())
+    } else {
+        Detected::None
+    };
+    Some(ScannedFile {
+        path: path_str,
+        format,
+        schema,
+    })
+}
+
+fn format_label(format: FileFormat) -> &'static str {
+    match format {
+        FileFormat::Json | FileFormat::Jsonc => "JSON",
+        FileFormat::Json5 => "JSON5",
+        FileFormat::Jsonl => "JSON Lines",
+        FileFormat::Toml => "TOML",
+        FileFormat::Yaml => "YAML",
+        FileFormat::Markdown => "Markdown",
+        FileFormat::Xml => "XML",
+        FileFormat::Hcl => "HCL",
+        FileFormat::Jsonnet => "Jsonnet",
+        FileFormat::Cue => "CUE",
+    }
+}
+
+fn print_summary(files: &[ScannedFile]) {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for file in files {
+        *counts.entry(format_label(file.format)).or_default() += 1;
+    }
+    let types: Vec<String> = counts.iter().map(|(k, n)| format!("{n} {k}")).collect();
+    eprintln!("Found {} files ({}).", files.len(), types.join(", "));
+
+    let inline = files
+        .iter()
+        .filter(|f| matches!(f.schema, Detected::Inline(_)))
+        .count();
+    eprintln!("  {inline} declare a schema inline");
+    for file in files {
+        if let Detected::Catalog(name) = &file.schema {
+            eprintln!("  {} -> {name} (catalog)", file.path);
+        }
+    }
+}
+
+/// Ask a yes/no question on stderr; an empty answer means yes.
+fn confirm(question: &str) -> Result<bool> {
+    eprint!("{question} [Y/n] ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim().to_ascii_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+/// `dir/**` excludes for the outermost vendored directories containing
+/// any of `paths`.
+fn vendored_excludes(paths: &[PathBuf]) -> Vec<String> {
+    let mut excludes: Vec<String> = Vec::new();
+    for path in paths {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let Some(parent) = path.parent() else {
+            continue;
+        };
+        let mut prefix = PathBuf::new();
+        for component in parent.components() {
+            prefix.push(component);
+            let name = component.as_os_str().to_string_lossy();
+            if VENDORED_DIRS.contains(&name.as_ref()) {
+                let pattern = format!("{}/**", prefix.to_string_lossy().replace('\\', "/"));
+                if !excludes.contains(&pattern) {
+                    excludes.push(pattern);
+                }
+                break;
+            }
+        }
+    }
+    excludes
+}
+
+/// Suggest `dir/*.ext` mappings for directories where some files declare
+/// a schema inline and the rest of the same type have no schema at all.
+fn suggest_mappings(files: &[ScannedFile]) -> Vec<(String, String)> {
+    let mut groups: BTreeMap<(&str, &str), Vec<&ScannedFile>> = BTreeMap::new();
+    for file in files {
+        let (dir, name) = file.path.rsplit_once('/').unwrap_or(("", &file.path));
+        let Some((_, ext)) = name.rsplit_once('.') else {
+            continue;
+        };
+        groups.entry((dir, ext)).or_default().push(file);
+    }
+
+    let mut mappings = Vec::new();
+    for ((dir, ext), group) in groups {
+        let mut urls = group.iter().filter_map(|f| match &f.schema {
+            Detected::Inline(url) => Some(url.as_str()),
+            _ => None,
+        });
+        let Some(url) = urls.next() else {
+            continue;
+        };
+        // Relative schema paths would resolve differently from lintel.toml.
+        let is_remote = url.starts_with("http://") || url.starts_with("https://");
+        let single_schema = urls.all(|u| u == url);
+        let has_unmatched = group.iter().any(|f| f.schema == Detected::None);
+        if is_remote && single_schema && has_unmatched {
+            let pattern = if dir.is_empty() {
+                format!("*.{ext}")
+            } else {
+                format!("{dir}/*.{ext}")
+            };
+            mappings.push((pattern, url.to_string()));
+        }
+    }
+    mappings
+}
+
+fn render_config(excludes: &[String], mappings: &[(String, String)], unmatched: &[&str]) -> String {
+    let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+    let mut out = String::from("# Lintel configuration\n# https://github.com/lintel-rs/lintel\n");
+
+    if excludes.is_empty() {
+        out.push_str("\nexclude = []\n");
+    } else {
+        out.push_str("\nexclude = [\n");
+        for pattern in excludes {
+            let _ = writeln!(out, "  {},", quote(pattern));
+        }
+        out.push_str("]\n");
+    }
+
+    if !mappings.is_empty() || !unmatched.is_empty() {
+        out.push_str("\n# Custom schema mappings: glob pattern -> schema URL\n[schemas]\n");
+        for (pattern, url) in mappings {
+            let _ = writeln!(out, "{} = {}", quote(pattern), quote(url));
+        }
+        if !unmatched.is_empty() {
+            out.push_str("# Files without a detected schema:\n");
+            for path in unmatched.iter().take(MAX_UNMATCHED_STUBS) {
+                let _ = writeln!(out, "# {} = \"\"", quote(path));
+            }
+            if unmatched.len() > MAX_UNMATCHED_STUBS {
+                let _ = writeln!(
+                    out,
+                    "# ...and {} more",
+                    unmatched.len() - MAX_UNMATCHED_STUBS
+                );
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, schema: Detected) -> ScannedFile {
+        ScannedFile {
+            path: path.to_string(),
+            format: FileFormat::Yaml,
+            schema,
+        }
+    }
+
+    #[test]
+    fn excludes_outermost_vendored_dirs() {
+        let paths: Vec<PathBuf> = [
+            "./vendor/a/b.json",
+            "./vendor/c/testdata/d.json",
+            "./pkg/testdata/e.yaml",
+            "./src/config.json",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(vendored_excludes(&paths), ["vendor/**", "pkg/testdata/**"]);
+    }
+
+    #[test]
+    fn suggests_mappings_from_sibling_inline_schemas() {
+        let url = "https://example.com/deploy.json";
+        let files = [
+            file("deploy/a.yaml", Detected::Inline(url.into())),
+            file("deploy/b.yaml", Detected::None),
+            file("ci/a.yaml", Detected::Inline(url.into())),
+            file("ci/b.yaml", Detected::Catalog("CI".into())),
+            file("mixed/a.yaml", Detected::Inline(url.into())),
+            file("mixed/b.yaml", Detected::Inline("https://other".into())),
+            file("mixed/c.yaml", Detected::None),
+            file("local/a.yaml", Detected::Inline("./schema.json".into())),
+            file("local/b.yaml", Detected::None),
+        ];
+        assert_eq!(
+            suggest_mappings(&files),
+            [("deploy/*.yaml".to_string(), url.to_string())]
+        );
+    }
+
+    #[test]
+    fn renders_excludes_mappings_and_stubs() {
+        let out = render_config(
+            &["vendor/**".into()],
+            &[("deploy/*.yaml".into(), "https://example.com/s.json".into())],
+            &["misc/data.json"],
+        );
+        assert_eq!(
+            out,
+            r#"# Lintel configuration
+# https://github.com/lintel-rs/lintel
+
+exclude = [
+  "vendor/**",
+]
+
+# Custom schema mappings: glob pattern -> schema URL
+[schemas]
+"deploy/*.yaml" = "https://example.com/s.json"
+# Files without a detected schema:
+# "misc/data.json" = ""
+"#
+        );
+        let config: lintel_config::Config = toml::from_str(&out).unwrap_or_default();
+        assert_eq!(config.exclude, ["vendor/**"]);
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use bpaf::Bpaf;
+
+pub mod check;
+pub mod init;
+
+#[derive(Debug, Clone, Bpaf)]
+pub enum ConfigCommand {
+    #[bpaf(command("check"))]
+    /// Validate lintel.toml files and show the effective merged config
+    Check(#[bpaf(external(check::check_args))] check::CheckArgs),
+
+    #[bpaf(command("init"))]
+    /// Inspect the repository and write a starter lintel.toml
+    Init(#[bpaf(external(init::init_args))] init::InitArgs),
+}
+
+pub async fn run(cmd: &ConfigCommand) -> Result<bool> {
+    match cmd {
+        ConfigCommand::Check(args) => check::run(args),
+        ConfigCommand::Init(args) => init::run(args).await.map(|()| false),
+    }
+}
@@ -1,13 +1,27 @@
+use std::collections::HashMap;
 use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use ansi_term_styles::{BOLD, DIM, RESET};
-use anyhow::Result;
+use anyhow::{Context, Result};
+use lintel_cli_common::CliCacheOptions;
 
-/// Run the `format` command: format files in place, or check with `--check`.
-pub fn run(args: &lintel_format::FormatArgs, verbose: bool) -> Result<bool> {
+/// Run the `format` command: format files in place, or check with `--check`
+/// (and print a patch with `--diff`).
+pub async fn run(
+    args: &lintel_format::FormatArgs,
+    cache: &CliCacheOptions,
+    verbose: bool,
+) -> Result<bool> {
+    if let Some(path) = &args.stdin_filepath {
+        format_stdin(args, cache, path).await?;
+        return Ok(false);
+    }
     let start = Instant::now();
-    let result = lintel_format::run(args)?;
+    let check = args.check || args.diff;
+    let schemas = resolve_schemas(args, cache).await?;
+    let result = lintel_format::run_with_schemas(args, &schemas)?;
     let had_errors = !result.errors.is_empty();
 
     if verbose {
@@ -17,7 +31,7 @@ pub fn run(args: &lintel_format::FormatArgs, verbose: bool) -> Result<bool> {
     }
 
     // In non-check mode, report write errors
-    if !args.check {
+    if !check {
         for (path, err) in &result.errors {
             eprintln!("error: {path}: {err}");
         }
@@ -28,7 +42,7 @@ pub fn run(args: &lintel_format::FormatArgs, verbose: bool) -> Result<bool> {
     let skipped = result.skipped;
     let ms = start.elapsed().as_millis();
 
-    if args.check {
+    if check {
         let error_count = result.errors.len();
         if std::io::stderr().is_terminal() {
             eprintln!(
@@ -51,3 +65,47 @@ pub fn run(args: &lintel_format::FormatArgs, verbose: bool) -> Result<bool> {
 
     Ok(had_errors)
 }
+
+/// Resolve the schema of every file whose formatting uses it
+/// (`--sort-keys schema`, `toml_style = "auto"`). Files without a schema, or
+/// whose schema fails to load, are left out and are formatted without one.
+async fn resolve_schemas(
+    args: &lintel_format::FormatArgs,
+    cache: &CliCacheOptions,
+) -> Result<HashMap<PathBuf, jsonschema_schema::SchemaValue>> {
+    let paths = lintel_format::files_needing_schema(args)?;
+    if paths.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Ok(lintel_explain::load_schemas_for_files(paths, cache).await)
+}
+
+/// `--stdin-filepath`: format stdin to stdout without touching disk.
+async fn format_stdin(
+    args: &lintel_format::FormatArgs,
+    cache: &CliCacheOptions,
+    path: &Path,
+) -> Result<()> {
+    use std::io::{Read, Write};
+
+    let mut content = String::new();
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .context("failed to read stdin")?;
+
+    let schema = if lintel_format::files_needing_schema(args)?.is_empty() {
+        None
+    } else {
+        lintel_explain::load_schema_for_content(&content, path, cache)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::debug!("no schema for {}: {e}", path.display());
+                None
+            })
+    };
+
+    let formatted = lintel_format::format_stdin(path, &content, args, schema.as_ref())?;
+    std::io::stdout()
+        .write_all(formatted.as_bytes())
+        .context("failed to write stdout")
+}
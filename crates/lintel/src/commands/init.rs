@@ -22,6 +22,7 @@ exclude = []
 # Custom schema mappings: glob pattern -> schema URL
 # [schemas]
 # "my-config.json" = "https://example.com/schema.json"
+# "vendor/*.json" = { url = "https://example.com/fixed.json", priority = "high" }
 
 # Additional schema catalog registries (SchemaStore format)
 # registries = []
@@ -34,6 +35,8 @@ exclude = []
 # [[override]]
 # files = ["vendor/**"]
 # validate_formats = false
+# severity = "warning"  # "error", "warning" or "off"
+# priority = 0          # higher-priority overrides are consulted first
 "#;
 
     fs::write(config_path, content)?;
@@ -0,0 +1,208 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail};
+use bpaf::{Bpaf, ShellComp};
+use lintel_format::FormatConfig;
+use lintel_validate::{parsers, validate};
+use schema_catalog::FileFormat;
+use serde_json::Value;
+
+/// JSON Schema draft that `lintel migrate` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Draft2020_12,
+    Draft07,
+}
+
+impl core::str::FromStr for Target {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2020-12" => Ok(Self::Draft2020_12),
+            "draft-07" => Ok(Self::Draft07),
+            _ => Err(format!("unknown target '{s}', expected: 2020-12, draft-07")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(generate(migrate_args_inner))]
+pub struct MigrateArgs {
+    /// Draft to migrate to
+    #[bpaf(
+        long("target"),
+        argument("2020-12|draft-07"),
+        fallback(Target::Draft2020_12)
+    )]
+    pub target: Target,
+
+    /// Rewrite the files in place
+    #[bpaf(long("in-place"), switch)]
+    pub in_place: bool,
+
+    /// Print the migrated schema to stdout (the default, for a single file)
+    #[bpaf(long("stdout"), switch)]
+    pub stdout: bool,
+
+    #[bpaf(long("exclude"), argument("PATTERN"))]
+    pub exclude: Vec<String>,
+
+    /// Schema files or globs (JSON, JSONC, JSON5, YAML or TOML)
+    #[bpaf(
+        positional("PATH"),
+        complete_shell(ShellComp::File { mask: None }),
+        some("at least one schema file is required")
+    )]
+    pub globs: Vec<String>,
+}
+
+/// Construct the bpaf parser for `MigrateArgs`.
+pub fn migrate_args() -> impl bpaf::Parser<MigrateArgs> {
+    migrate_args_inner()
+}
+
+/// Run `lintel migrate`.
+///
+/// Files are found like `lintel check` finds them, with the excludes from
+/// `lintel.toml`. Only documents with a JSON Schema `$schema` are migrated.
+pub fn run(args: &MigrateArgs) -> Result<()> {
+    if args.in_place && args.stdout {
+        bail!("--in-place and --stdout cannot be used together");
+    }
+    let (config, _, _) = validate::load_config(None);
+    let mut exclude = config.exclude.clone();
+    exclude.extend(args.exclude.iter().cloned());
+    let format_config = lintel_format::format_config_from_lintel(&config);
+
+    let files = validate::collect_files(&args.globs, &exclude)?;
+    if !args.in_place && files.len() > 1 {
+        bail!(
+            "{} files match; use --in-place to migrate more than one file",
+            files.len()
+        );
+    }
+
+    let mut migrated = 0;
+    for path in &files {
+        let display = path.display();
+        let content =
+            fs::read_to_string(path).with_context(|| format!("failed to read {display}"))?;
+        let Some(output) = migrate_file(path, &content, args.target, &format_config)? else {
+            eprintln!("skipping {display}: no JSON Schema $schema");
+            continue;
+        };
+        if !args.in_place {
+            print!("{output}");
+        } else if output != content {
+            fs::write(path, output).with_context(|| format!("failed to write {display}"))?;
+            eprintln!("migrated {display}");
+            migrated += 1;
+        }
+    }
+    if args.in_place {
+        eprintln!("Migrated {migrated} of {} files", files.len());
+    }
+    Ok(())
+}
+
+/// Migrate the schema in `content`, returning the new file content, or
+/// `None` if the document has no JSON Schema `$schema`.
+fn migrate_file(
+    path: &Path,
+    content: &str,
+    target: Target,
+    format_config: &FormatConfig,
+) -> Result<Option<String>> {
+    let file_name = path.display().to_string();
+    let format = parsers::detect_format(path).unwrap_or(FileFormat::Json);
+    let mut schema = parsers::parser_for(format)
+        .parse(content, &file_name)
+        .map_err(|e| anyhow!("failed to parse {file_name}: {}", e.message()))?;
+    if jsonschema_migrate::detect_draft(&schema).is_none() {
+        return Ok(None);
+    }
+    match target {
+        Target::Draft2020_12 => jsonschema_migrate::migrate_to_2020_12(&mut schema),
+        Target::Draft07 => jsonschema_migrate::migrate_to_draft_07(&mut schema),
+    }
+
+    let output = serialize(&schema, format, content)?;
+    // Files kept in `lintel format` style stay that way.
+    let formatted = matches!(
+        lintel_format::format_content(path, content, format_config),
+        Ok(None)
+    );
+    if formatted
+        && let Ok(Some(reformatted)) = lintel_format::format_content(path, &output, format_config)
+    {
+        return Ok(Some(reformatted));
+    }
+    Ok(Some(output))
+}
+
+/// Write `schema` in `format`, indented like `original`.
+fn serialize(schema: &Value, format: FileFormat, original: &str) -> Result<String> {
+    match format {
+        FileFormat::Yaml => serde_yaml::to_string(schema).context("failed to serialize YAML"),
+        FileFormat::Toml => {
+            let value: toml::Value = serde_json::from_value(schema.clone())
+                .context("schema cannot be represented as TOML")?;
+            toml::to_string_pretty(&value).context("failed to serialize TOML")
+        }
+        _ => {
+            let indent = original
+                .lines()
+                .map(|line| &line[..line.len() - line.trim_start().len()])
+                .find(|indent| !indent.is_empty())
+                .unwrap_or("  ");
+            // Pretty JSON indents with two spaces per level, and strings
+            // never span lines.
+            let pretty =
+                serde_json::to_string_pretty(schema).context("failed to serialize JSON")?;
+            let mut out = String::with_capacity(pretty.len());
+            for line in pretty.lines() {
+                let trimmed = line.trim_start_matches(' ');
+                out.push_str(&indent.repeat((line.len() - trimmed.len()) / 2));
+                out.push_str(trimmed);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_indentation_of_json_files() -> anyhow::Result<()> {
+        let content = "{\n    \"$schema\": \"http://json-schema.org/draft-04/schema#\",\n    \"definitions\": {\n        \"name\": { \"type\": \"string\" }\n    }\n}\n";
+        let output = migrate_file(
+            Path::new("schema.json5"),
+            content,
+            Target::Draft2020_12,
+            &FormatConfig::default(),
+        )?;
+        assert_eq!(
+            output.as_deref(),
+            Some(
+                "{\n    \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n    \"$defs\": {\n        \"name\": {\n            \"type\": \"string\"\n        }\n    }\n}\n"
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn skips_documents_that_are_not_schemas() -> anyhow::Result<()> {
+        let output = migrate_file(
+            Path::new("config.yaml"),
+            "name: demo\n",
+            Target::Draft07,
+            &FormatConfig::default(),
+        )?;
+        assert_eq!(output, None);
+        Ok(())
+    }
+}
@@ -1,6 +1,12 @@
 pub mod annotate;
 pub mod cache;
+pub mod catalog;
+pub mod config;
 pub mod convert;
 pub mod format;
 pub mod github_action;
 pub mod init;
+pub mod migrate;
+pub mod new;
+pub mod schema_lint;
+pub mod vendor;
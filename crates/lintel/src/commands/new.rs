@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bpaf::Bpaf;
+use jsonschema_explain::ExampleFormat;
+use lintel_cli_common::CliCacheOptions;
+use lintel_validate::validate;
+use schema_catalog::{CompiledCatalog, SearchHit};
+
+#[derive(Debug, Clone, Bpaf)]
+pub struct NewArgs {
+    /// Write to PATH instead of the file name the catalog gives the schema
+    #[bpaf(long("output"), short('o'), argument("PATH"))]
+    pub output: Option<PathBuf>,
+
+    /// Overwrite the file if it exists
+    #[bpaf(long("force"), switch)]
+    pub force: bool,
+
+    #[bpaf(external(lintel_cli_common::cli_cache_options))]
+    pub cache: CliCacheOptions,
+
+    /// Catalog name of the schema, such as `tsconfig` or `github-workflow`
+    #[bpaf(positional("NAME"))]
+    pub name: String,
+}
+
+/// Run `lintel new`: write a starter file for the catalog schema `name`,
+/// with its required properties and their descriptions as comments.
+pub async fn run(args: &NewArgs) -> Result<()> {
+    let (config, config_dir, _) = validate::load_config(None);
+    let retriever = lintel_explain::build_retriever(&args.cache);
+    let catalogs = validate::fetch_compiled_catalogs(&retriever, &config, &config_dir, false).await;
+    let entries: Vec<SearchHit<'_>> = catalogs.iter().flat_map(CompiledCatalog::entries).collect();
+    let Some(entry) = find_entry(&entries, &args.name) else {
+        bail!(
+            "no catalog schema is named {:?}; try `lintel catalog search {}`",
+            args.name,
+            args.name
+        );
+    };
+
+    let path = match &args.output {
+        Some(path) => path.clone(),
+        None => canonical_file_name(entry.file_match)
+            .map(PathBuf::from)
+            .with_context(|| {
+                format!("{} has no file name to write to; pass --output", entry.name)
+            })?,
+    };
+    if path.exists() && !args.force {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+
+    let is_remote = entry.url.starts_with("http://") || entry.url.starts_with("https://");
+    let schema = lintel_explain::fetch_schema(entry.url, is_remote, &args.cache).await?;
+    let schema = jsonschema_schema::SchemaValue::Schema(Box::new(schema));
+    let format = ExampleFormat::for_path(&path.to_string_lossy());
+    let document = jsonschema_explain::commented_example_document(&schema, &schema, format);
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, document).with_context(|| format!("failed to write {}", path.display()))?;
+    eprintln!("Created {} from the {} schema", path.display(), entry.name);
+    Ok(())
+}
+
+/// The entry `name` refers to: the first whose name is `name`, ignoring case
+/// and punctuation, else the first whose name or file name is `name` without
+/// its extension, as `tsconfig` is for `tsconfig.json`.
+fn find_entry<'a, 'e>(entries: &'e [SearchHit<'a>], name: &str) -> Option<&'e SearchHit<'a>> {
+    let wanted = normalize(name);
+    entries
+        .iter()
+        .find(|entry| normalize(entry.name) == wanted)
+        .or_else(|| {
+            entries.iter().find(|entry| {
+                normalize(stem(entry.name)) == wanted
+                    || canonical_file_name(entry.file_match)
+                        .is_some_and(|file| normalize(stem(&file)) == wanted)
+            })
+        })
+}
+
+/// `name` in lowercase without punctuation or spaces.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The file name of `path` without its extension.
+fn stem(path: &str) -> &str {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => name,
+    }
+}
+
+/// The file to create for a schema: its first `fileMatch` pattern without
+/// glob syntax, else the first pattern that can be filled in, with `**/`
+/// dropped, `*` spelled `example` and a `{a,b}` group taking its first
+/// alternative.
+fn canonical_file_name(file_match: &[String]) -> Option<String> {
+    let mut patterns = file_match.iter().filter(|p| !p.starts_with('!'));
+    let literal = patterns
+        .clone()
+        .find(|p| !p.contains(['*', '?', '[', '{', '\\']));
+    if let Some(literal) = literal {
+        return Some(literal.clone());
+    }
+    patterns.find_map(|p| fill_in(p))
+}
+
+fn fill_in(pattern: &str) -> Option<String> {
+    let mut pattern = pattern;
+    while let Some(rest) = pattern.strip_prefix("**/") {
+        pattern = rest;
+    }
+    let pattern = pattern.replace("/**/", "/");
+    let mut file = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                while chars.next_if_eq(&'*').is_some() {}
+                file.push_str("example");
+            }
+            '{' => {
+                let group: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                file.push_str(group.split(',').next().unwrap_or_default());
+            }
+            '?' | '[' | '\\' => return None,
+            c => file.push(c),
+        }
+    }
+    Some(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit<'a>(name: &'a str, file_match: &'a [String]) -> SearchHit<'a> {
+        SearchHit {
+            url: "https://example.com/schema.json",
+            name,
+            description: None,
+            file_match,
+        }
+    }
+
+    #[test]
+    fn finds_entries_by_name_or_file_name() {
+        let tsconfig = ["tsconfig.json".to_string(), "tsconfig.*.json".to_string()];
+        let workflow = ["**/.github/workflows/*.yml".to_string()];
+        let entries = [
+            hit("tsconfig.json", &tsconfig),
+            hit("GitHub Workflow", &workflow),
+        ];
+        let found = |name| find_entry(&entries, name).map(|entry| entry.name);
+        assert_eq!(found("tsconfig"), Some("tsconfig.json"));
+        assert_eq!(found("TSConfig.json"), Some("tsconfig.json"));
+        assert_eq!(found("github-workflow"), Some("GitHub Workflow"));
+        assert_eq!(found("workflow"), None);
+    }
+
+    #[test]
+    fn canonical_file_names() {
+        let name = |patterns: &[&str]| {
+            let patterns: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+            canonical_file_name(&patterns)
+        };
+        assert_eq!(
+            name(&["tsconfig.*.json", "tsconfig.json"]).as_deref(),
+            Some("tsconfig.json")
+        );
+        assert_eq!(
+            name(&["**/.github/workflows/*.yml"]).as_deref(),
+            Some(".github/workflows/example.yml")
+        );
+        assert_eq!(
+            name(&["**/{docker-,}compose.{yml,yaml}"]).as_deref(),
+            Some("docker-compose.yml")
+        );
+        assert_eq!(
+            name(&["config/**/*.json"]).as_deref(),
+            Some("config/example.json")
+        );
+        assert_eq!(name(&["file?.json"]), None);
+    }
+}
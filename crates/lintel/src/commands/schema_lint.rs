@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use bpaf::{Bpaf, ShellComp};
+use lintel_config::Severity;
+use lintel_diagnostics::{find_instance_path_span, offset_to_line_col};
+use lintel_schema::lint::{self, Finding, Suppressions, lint};
+use lintel_validate::{parsers, validate};
+use schema_catalog::FileFormat;
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(generate(schema_lint_args_inner))]
+pub struct SchemaLintArgs {
+    /// Schema files to lint (JSON, JSONC, JSON5, YAML or TOML)
+    #[bpaf(
+        positional("FILE"),
+        complete_shell(ShellComp::File { mask: None }),
+        some("at least one schema file is required")
+    )]
+    pub files: Vec<String>,
+}
+
+/// Construct the bpaf parser for `SchemaLintArgs`.
+pub fn schema_lint_args() -> impl bpaf::Parser<SchemaLintArgs> {
+    schema_lint_args_inner()
+}
+
+/// Run `lintel schema-lint`. Rule severities come from the `[schema-lint]`
+/// table of `lintel.toml`, custom keywords from `annotations` and
+/// `[vocabularies]`. Returns `true` if any finding is an error.
+pub fn run(args: &SchemaLintArgs) -> Result<bool> {
+    let (config, _, _) = validate::load_config(None);
+    let mut ids: Vec<&String> = config.schema_lint.keys().collect();
+    ids.sort();
+    for id in ids {
+        if lint::rule(id).is_none() {
+            eprintln!("warning: unknown rule {id} in [schema-lint]");
+        }
+    }
+
+    let (mut errors, mut warnings) = (0, 0);
+    for file in &args.files {
+        let path = Path::new(file);
+        let content = fs::read_to_string(path).with_context(|| format!("failed to read {file}"))?;
+        let format = parsers::detect_format(path).unwrap_or(FileFormat::Json);
+        let schema = parsers::parser_for(format)
+            .parse(&content, file)
+            .map_err(|e| anyhow!("failed to parse {file}: {}", e.message()))?;
+
+        let vocabularies = schema
+            .get("$vocabulary")
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flat_map(|vocabularies| vocabularies.keys().map(String::as_str));
+        let annotations = config.annotation_keywords(vocabularies);
+        let findings = lint(&schema, &config.schema_lint, &annotations);
+
+        let suppressions = Suppressions::parse(&content);
+        for ((line, col), finding) in locate(&content, findings) {
+            if suppressions.silences(line, finding.rule) {
+                continue;
+            }
+            let level = if finding.severity == Severity::Error {
+                errors += 1;
+                "error"
+            } else {
+                warnings += 1;
+                "warning"
+            };
+            println!(
+                "{file}:{line}:{col}: {level}: {} [{}]",
+                finding.message, finding.rule
+            );
+        }
+    }
+    if errors + warnings > 0 {
+        eprintln!("{errors} errors and {warnings} warnings");
+    }
+    Ok(errors > 0)
+}
+
+/// Pair each finding with its 1-based line and column in `content`, sorted
+/// by position.
+fn locate(content: &str, findings: Vec<Finding>) -> Vec<((usize, usize), Finding)> {
+    let mut located: Vec<_> = findings
+        .into_iter()
+        .map(|finding| {
+            let (offset, _) = find_instance_path_span(content, &finding.pointer);
+            (offset_to_line_col(content, offset), finding)
+        })
+        .collect();
+    located.sort_by_key(|(position, _)| *position);
+    located
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn locates_findings_in_the_schema_source() {
+        let content = r#"{
+  "$defs": {
+    "unused": { "type": "string" }
+  },
+  "properties": {
+    "name": { "type": "string" }
+  },
+  "additionalProperties": false
+}"#;
+        let schema: serde_json::Value = serde_json::from_str(content).unwrap_or_default();
+        let located: Vec<_> = locate(content, lint(&schema, &HashMap::new(), &[]))
+            .into_iter()
+            .map(|(position, finding)| (position, finding.rule))
+            .collect();
+        assert_eq!(
+            located,
+            [((3, 5), "unreachable-def"), ((6, 5), "missing-description")]
+        );
+    }
+}
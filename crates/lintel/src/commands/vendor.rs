@@ -0,0 +1,267 @@
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use bpaf::Bpaf;
+use lintel_schema_cache::SchemaCache;
+use lintel_validate::validate;
+use serde_json::Value;
+use url::Url;
+
+/// Where schemas are written when `--dir` is not given.
+const DEFAULT_VENDOR_DIR: &str = ".lintel/schemas";
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(generate(vendor_args_inner))]
+pub struct VendorArgs {
+    /// Directory to write schemas to, relative to lintel.toml
+    #[bpaf(long("dir"), argument("DIR"), fallback(DEFAULT_VENDOR_DIR.to_string()), display_fallback)]
+    pub dir: String,
+
+    #[bpaf(external(lintel_validate::validate_args))]
+    pub validate: lintel_validate::ValidateArgs,
+}
+
+/// Construct the bpaf parser for `VendorArgs`.
+pub fn vendor_args() -> impl bpaf::Parser<VendorArgs> {
+    vendor_args_inner()
+}
+
+/// Run `lintel vendor`.
+///
+/// Resolves the schema of every file the way `lintel check` does, downloads
+/// each remote schema and the remote schemas it references into `args.dir`,
+/// and adds a `[rewrite]` rule per schema to `lintel.toml` pointing at the
+/// local copy.
+pub async fn run(args: &mut VendorArgs) -> Result<()> {
+    lintel_validate::merge_config(&mut args.validate);
+    let lib_args = validate::ValidateArgs::from(&args.validate);
+    let (config, config_dir, config_path) = validate::load_config(lib_args.config_dir.as_deref());
+    let retriever = validate::build_retriever(&lib_args, &config);
+
+    let result = validate::run_with(&lib_args, Some(retriever.clone()), |_| {}).await?;
+    let roots: BTreeSet<String> = result
+        .checked
+        .iter()
+        .filter(|f| is_remote(&f.schema))
+        .map(|f| strip_fragment(&f.schema).to_string())
+        .collect();
+    if roots.is_empty() {
+        eprintln!("No remote schemas to vendor");
+        return Ok(());
+    }
+
+    let schemas = download(&retriever, roots).await;
+    let dir = args.dir.trim_end_matches('/');
+    let mut rewrites: BTreeMap<String, String> = BTreeMap::new();
+    for (uri, schema) in &schemas {
+        let Some(rel) = vendored_path(uri) else {
+            continue;
+        };
+        let path = config_dir.join(dir).join(&rel);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(schema)? + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        rewrites.insert(uri.clone(), format!("//{dir}/{rel}"));
+    }
+
+    let config_path = config_path.unwrap_or_else(|| config_dir.join("lintel.toml"));
+    let existing = match fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("failed to read {}", config_path.display()));
+        }
+    };
+    fs::write(&config_path, add_rewrites(&existing, &rewrites)?)
+        .with_context(|| format!("failed to write {}", config_path.display()))?;
+
+    eprintln!(
+        "Vendored {} schemas into {}",
+        rewrites.len(),
+        display_dir(&config_dir, dir)
+    );
+    eprintln!("Updated [rewrite] in {}", config_path.display());
+    Ok(())
+}
+
+fn is_remote(uri: &str) -> bool {
+    uri.starts_with("http://") || uri.starts_with("https://")
+}
+
+fn strip_fragment(uri: &str) -> &str {
+    uri.split_once('#').map_or(uri, |(base, _)| base)
+}
+
+fn display_dir(config_dir: &Path, dir: &str) -> String {
+    if config_dir == Path::new(".") {
+        dir.to_string()
+    } else {
+        config_dir.join(dir).display().to_string()
+    }
+}
+
+/// Fetch `roots` and every remote schema they reference, following `$ref`s
+/// transitively. Schemas that cannot be downloaded are reported and skipped.
+async fn download(retriever: &SchemaCache, roots: BTreeSet<String>) -> BTreeMap<String, Value> {
+    let mut seen: HashSet<String> = roots.iter().cloned().collect();
+    let mut queue: VecDeque<String> = roots.into_iter().collect();
+    let mut schemas = BTreeMap::new();
+    while let Some(uri) = queue.pop_front() {
+        let schema = match retriever.fetch(&uri).await {
+            Ok((schema, _)) => schema,
+            Err(e) => {
+                eprintln!("warning: failed to download {uri}: {e}");
+                continue;
+            }
+        };
+        for reference in remote_refs(&uri, &schema) {
+            if seen.insert(reference.clone()) {
+                queue.push_back(reference);
+            }
+        }
+        schemas.insert(uri, schema);
+    }
+    schemas
+}
+
+/// The remote documents `schema`'s `$ref`s point to, resolved against
+/// `base`, without fragments. References within the document are skipped.
+fn remote_refs(base: &str, schema: &Value) -> Vec<String> {
+    let Ok(base) = Url::parse(base) else {
+        return Vec::new();
+    };
+    let mut refs = Vec::new();
+    collect_refs(schema, &mut refs);
+    let mut out: Vec<String> = Vec::new();
+    for reference in refs {
+        let Ok(mut url) = base.join(reference) else {
+            continue;
+        };
+        url.set_fragment(None);
+        let url = url.to_string();
+        if url != base.as_str() && is_remote(&url) && !out.contains(&url) {
+            out.push(url);
+        }
+    }
+    out
+}
+
+fn collect_refs<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value.as_str() {
+                    Some(reference) if key == "$ref" || key == "$dynamicRef" => {
+                        out.push(reference);
+                    }
+                    _ => collect_refs(value, out),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Path of the local copy of `uri` inside the vendor directory: the host
+/// (with the port, if any) followed by the URL path, so relative `$ref`s
+/// between vendored schemas keep resolving.
+fn vendored_path(uri: &str) -> Option<String> {
+    let url = Url::parse(uri).ok()?;
+    let host = match url.port() {
+        Some(port) => format!("{}_{port}", url.host_str()?),
+        None => url.host_str()?.to_string(),
+    };
+    let path = url.path();
+    if path.ends_with('/') {
+        Some(format!("{host}{path}index.json"))
+    } else {
+        Some(format!("{host}{path}"))
+    }
+}
+
+/// Add `rewrites` to the `[rewrite]` table of a `lintel.toml`, keeping the
+/// rest of the file as written.
+fn add_rewrites(config: &str, rewrites: &BTreeMap<String, String>) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = config.parse().context("failed to parse lintel.toml")?;
+    let table = doc
+        .entry("rewrite")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .context("`rewrite` in lintel.toml is not a table")?;
+    for (from, to) in rewrites {
+        table.insert(from, toml_edit::value(to.as_str()));
+    }
+    Ok(doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn vendored_paths_mirror_the_url() {
+        assert_eq!(
+            vendored_path("https://json.schemastore.org/github-workflow.json").as_deref(),
+            Some("json.schemastore.org/github-workflow.json")
+        );
+        assert_eq!(
+            vendored_path("http://localhost:8000/schemas/").as_deref(),
+            Some("localhost_8000/schemas/index.json")
+        );
+    }
+
+    #[test]
+    fn finds_remote_refs_relative_to_the_schema() {
+        let schema = json!({
+            "$defs": { "a": { "$ref": "#/$defs/b" } },
+            "properties": {
+                "$ref": { "$ref": "./common.json#/$defs/name" },
+                "other": { "$ref": "https://example.org/other.json" },
+                "again": { "$ref": "common.json" }
+            }
+        });
+        let mut refs = remote_refs("https://example.com/schemas/main.json", &schema);
+        refs.sort();
+        assert_eq!(
+            refs,
+            [
+                "https://example.com/schemas/common.json",
+                "https://example.org/other.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn adds_rewrite_rules_keeping_the_config() -> anyhow::Result<()> {
+        let rewrites = BTreeMap::from([(
+            "https://example.com/s.json".to_string(),
+            "//.lintel/schemas/example.com/s.json".to_string(),
+        )]);
+        let out = add_rewrites(
+            "# project config\nexclude = [\"vendor/**\"]\n\n[rewrite]\n\"http://localhost/\" = \"//schemas/\"\n",
+            &rewrites,
+        )?;
+        assert_eq!(
+            out,
+            "# project config\nexclude = [\"vendor/**\"]\n\n[rewrite]\n\"http://localhost/\" = \"//schemas/\"\n\"https://example.com/s.json\" = \"//.lintel/schemas/example.com/s.json\"\n"
+        );
+        let config: lintel_config::Config = toml::from_str(&add_rewrites("", &rewrites)?)?;
+        assert_eq!(
+            config.rewrite["https://example.com/s.json"],
+            "//.lintel/schemas/example.com/s.json"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+#![doc = include_str!("../README.md")]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use lintel_schema_cache::SchemaCache;
+use lintel_validate::validate;
+
+pub use lintel_config::Config;
+pub use lintel_diagnostics::LintelDiagnostic;
+pub use lintel_diagnostics::reporter::{CheckResult, CheckedFile, ExitStatus};
+
+/// Validates files and documents against their schemas, resolving each
+/// schema the way `lintel check` does.
+///
+/// The config is loaded once, when the validator is built. Schemas are
+/// cached in memory for the lifetime of the validator, and on disk like the
+/// CLI's, so repeated calls only download a schema once.
+pub struct Validator {
+    args: validate::ValidateArgs,
+    config: Config,
+    config_dir: PathBuf,
+    cache: SchemaCache,
+}
+
+/// Builder for a [`Validator`].
+#[must_use]
+#[derive(Default)]
+pub struct ValidatorBuilder {
+    config: Option<Config>,
+    config_dir: Option<PathBuf>,
+    cache: Option<SchemaCache>,
+    cache_dir: Option<PathBuf>,
+    no_catalog: bool,
+    max_errors: Option<usize>,
+}
+
+impl ValidatorBuilder {
+    /// Use `config` instead of loading `lintel.toml`.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Directory to search for `lintel.toml`, or with [`config`](Self::config)
+    /// the directory its `//` paths and mapped local schemas resolve
+    /// against. Defaults to the working directory.
+    pub fn config_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config_dir = Some(dir.into());
+        self
+    }
+
+    /// Fetch schemas through `cache`, for example to share it between
+    /// validators.
+    pub fn cache(mut self, cache: SchemaCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Override the directory remote schemas are cached in.
+    pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// When `true`, do not match files against the schema catalogs.
+    pub fn no_catalog(mut self, no_catalog: bool) -> Self {
+        self.no_catalog = no_catalog;
+        self
+    }
+
+    /// Report at most `max` diagnostics per file, followed by a count of the
+    /// rest.
+    pub fn max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Build the [`Validator`].
+    pub fn build(self) -> Validator {
+        let args = validate::ValidateArgs {
+            globs: Vec::new(),
+            exclude: Vec::new(),
+            cache_dir: self.cache_dir.map(|dir| dir.to_string_lossy().to_string()),
+            force_schema_fetch: false,
+            force_validation: false,
+            no_catalog: self.no_catalog,
+            config_dir: self.config_dir,
+            schema_cache_ttl: None,
+            fetch_timeout: None,
+            fetch_retries: None,
+            fetch_retry_backoff: None,
+            trace_resolution: false,
+            lock: lintel_validate::lock::LockMode::Auto,
+            openapi_examples: false,
+            validate_content: false,
+            strict_instances: false,
+            report_unused_suppressions: false,
+            fail_fast: false,
+            max_errors: self.max_errors,
+            timeout_per_file: None,
+            formats: lintel_validate::formats::FormatRegistry::default(),
+        };
+        let (config, config_dir) = if let Some(config) = self.config {
+            let dir = args
+                .config_dir
+                .clone()
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_else(|| PathBuf::from("."));
+            (config, dir)
+        } else {
+            let (config, dir, _) = validate::load_config(args.config_dir.as_deref());
+            (config, dir)
+        };
+        let cache = self
+            .cache
+            .unwrap_or_else(|| validate::build_retriever(&args, &config));
+        Validator {
+            args,
+            config,
+            config_dir,
+            cache,
+        }
+    }
+}
+
+impl Validator {
+    /// Start building a [`Validator`].
+    pub fn builder() -> ValidatorBuilder {
+        ValidatorBuilder::default()
+    }
+
+    /// Validate the file at `path`, or every supported file beneath it when
+    /// it is a directory.
+    ///
+    /// Files that cannot be read are reported as [`LintelDiagnostic::Io`]
+    /// in the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be walked or a schema
+    /// cannot be compiled into a validator for reasons other than the
+    /// schema itself.
+    pub async fn validate_path(&self, path: impl AsRef<Path>) -> Result<CheckResult> {
+        let files = validate::collect_files(
+            &[path.as_ref().to_string_lossy().to_string()],
+            &self.config.exclude,
+        )?;
+        let mut errors = Vec::new();
+        let contents = validate::read_files(&files, &mut errors).await;
+        let mut result = self.run(contents).await?;
+        result.errors.splice(0..0, errors);
+        Ok(result)
+    }
+
+    /// Validate `content` as if it were the file at `path`, which decides
+    /// its format and schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a schema cannot be compiled into a validator for
+    /// reasons other than the schema itself.
+    pub async fn validate_str(
+        &self,
+        path: impl AsRef<Path>,
+        content: impl Into<String>,
+    ) -> Result<CheckResult> {
+        let contents = vec![(path.as_ref().to_path_buf(), content.into())];
+        self.run(contents).await
+    }
+
+    async fn run(&self, contents: Vec<(PathBuf, String)>) -> Result<CheckResult> {
+        validate::run_with_config(
+            &self.args,
+            &self.config,
+            &self.config_dir,
+            contents,
+            Some(self.cache.clone()),
+            |_| {},
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn validates_paths_and_strings() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        std::fs::write(
+            tmp.path().join("schema.json"),
+            r#"{"type":"object","properties":{"name":{"type":"string"}}}"#,
+        )?;
+        let config: Config = toml::from_str("[schemas]\n\"**/*.yaml\" = \"//schema.json\"\n")?;
+        let validator = Validator::builder()
+            .config(config)
+            .config_dir(tmp.path())
+            .cache(SchemaCache::memory())
+            .no_catalog(true)
+            .build();
+
+        let file = tmp.path().join("app.yaml");
+        std::fs::write(&file, "name: 1\n")?;
+        let result = validator.validate_path(&file).await?;
+        assert_eq!(result.files_checked(), 1);
+        assert_eq!(result.error_count(), 1);
+        assert!(matches!(
+            result.errors[0],
+            LintelDiagnostic::Validation(ref v) if v.instance_path == "/name"
+        ));
+
+        let result = validator.validate_str(&file, "name: demo\n").await?;
+        assert_eq!(result.files_checked(), 1);
+        assert!(result.errors.is_empty());
+
+        let result = validator.validate_path(tmp.path().join("missing")).await?;
+        assert_eq!(result.files_checked(), 0);
+        Ok(())
+    }
+}
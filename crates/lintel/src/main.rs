@@ -1,5 +1,7 @@
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
 use std::process::ExitCode;
 
 use bpaf::{Bpaf, ShellComp};
@@ -8,6 +10,7 @@ use tracing_subscriber::prelude::*;
 
 use lintel_annotate::annotate_args;
 use lintel_check::{CheckArgs, check_args};
+use lintel_diagnostics::reporter::ExitStatus;
 use lintel_explain::explain_args;
 use lintel_format::{FormatArgs, format_args};
 use lintel_github_action::github_action_args;
@@ -68,7 +71,8 @@ enum Commands {
         /// Output format
         #[bpaf(
             long("reporter"),
-            argument("pretty|text|github"),
+            long("output"),
+            argument("pretty|text|github|json|sarif"),
             fallback(ReporterKind::Pretty)
         )]
         ReporterKind,
@@ -82,7 +86,8 @@ enum Commands {
         /// Output format
         #[bpaf(
             long("reporter"),
-            argument("pretty|text|github"),
+            long("output"),
+            argument("pretty|text|github|json|sarif"),
             fallback(ReporterKind::Text)
         )]
         ReporterKind,
@@ -96,7 +101,8 @@ enum Commands {
         /// Output format
         #[bpaf(
             long("reporter"),
-            argument("pretty|text|github"),
+            long("output"),
+            argument("pretty|text|github|json|sarif"),
             fallback(ReporterKind::Pretty)
         )]
         ReporterKind,
@@ -117,10 +123,38 @@ enum Commands {
         #[bpaf(external(explain_args))] lintel_explain::ExplainArgs,
     ),
 
+    #[bpaf(command("config"), fallback_to_usage)]
+    /// Inspect lintel.toml configuration
+    Config(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::config::config_command))] commands::config::ConfigCommand,
+    ),
+
+    #[bpaf(command("catalog"), fallback_to_usage)]
+    /// Schema catalog tools
+    Catalog(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::catalog::catalog_command))] commands::catalog::CatalogCommand,
+    ),
+
+    #[bpaf(command("vendor"))]
+    /// Download the project's remote schemas and rewrite them to local copies
+    Vendor(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::vendor::vendor_args))] commands::vendor::VendorArgs,
+    ),
+
     #[bpaf(command("init"))]
     /// Create a lintel.toml configuration file
     Init(#[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions),
 
+    #[bpaf(command("new"))]
+    /// Create a starter file from a catalog schema
+    New(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::new::new_args))] commands::new::NewArgs,
+    ),
+
     #[bpaf(command("convert"))]
     /// Convert between JSON, YAML, and TOML formats
     Convert(
@@ -128,6 +162,13 @@ enum Commands {
         #[bpaf(external(convert_args))] ConvertArgs,
     ),
 
+    #[bpaf(command("migrate"))]
+    /// Migrate JSON Schema documents to draft 2020-12 or draft-07
+    Migrate(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::migrate::migrate_args))] commands::migrate::MigrateArgs,
+    ),
+
     #[bpaf(command("annotate"))]
     /// Add schema annotations to files
     Annotate(
@@ -136,9 +177,10 @@ enum Commands {
     ),
 
     #[bpaf(command("format"))]
-    /// Format JSON, YAML, TOML, and Markdown files
+    /// Format JSON, YAML, TOML, Markdown, INI and .env files
     Format(
         #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(lintel_cli_common::cli_cache_options))] lintel_cli_common::CliCacheOptions,
         #[bpaf(external(format_args))] FormatArgs,
     ),
 
@@ -156,8 +198,16 @@ enum Commands {
         #[bpaf(external(schema_command))] lintel_schema::SchemaCommand,
     ),
 
-    #[bpaf(command("cache"), hide, fallback_to_usage)]
-    /// Cache debugging tools
+    #[bpaf(command("schema-lint"))]
+    /// Lint JSON Schema documents for common authoring mistakes
+    SchemaLint(
+        #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
+        #[bpaf(external(commands::schema_lint::schema_lint_args))]
+        commands::schema_lint::SchemaLintArgs,
+    ),
+
+    #[bpaf(command("cache"), fallback_to_usage)]
+    /// Inspect and maintain the schema cache
     Cache(
         #[bpaf(external(lintel_cli_common::cli_global_options), hide_usage)] CLIGlobalOptions,
         #[bpaf(external(commands::cache::cache_command))] commands::cache::CacheCommand,
@@ -229,6 +279,7 @@ fn setup_miette(global: &CLIGlobalOptions) {
 }
 
 #[tokio::main]
+#[allow(clippy::too_many_lines)]
 async fn main() -> ExitCode {
     let opts = cli().run();
 
@@ -236,15 +287,15 @@ async fn main() -> ExitCode {
         Commands::Check(global, reporter_kind, mut args) => {
             setup_tracing(&global);
             setup_miette(&global);
-            let mut reporter = make_reporter(reporter_kind, global.verbose);
-            lintel_check::run(&mut args, reporter.as_mut()).await
+            let mut reporter = make_reporter(reporter_kind, global.verbose, global.quiet);
+            return exit_code(lintel_check::run(&mut args, reporter.as_mut()).await);
         }
         Commands::CI(global, reporter_kind, mut args)
         | Commands::Validate(global, reporter_kind, mut args) => {
             setup_tracing(&global);
             setup_miette(&global);
-            let mut reporter = make_reporter(reporter_kind, global.verbose);
-            lintel_validate::run(&mut args, reporter.as_mut()).await
+            let mut reporter = make_reporter(reporter_kind, global.verbose, global.quiet);
+            return exit_code(lintel_validate::run(&mut args, reporter.as_mut()).await);
         }
         Commands::Identify(global, args) => {
             setup_tracing(&global);
@@ -260,18 +311,44 @@ async fn main() -> ExitCode {
             setup_tracing(&global);
             commands::annotate::run(&args, global.verbose).await
         }
-        Commands::Format(global, args) => {
+        Commands::Format(global, cache, args) => {
+            setup_tracing(&global);
+            commands::format::run(&args, &cache, global.verbose).await
+        }
+        Commands::Config(global, cmd) => {
+            setup_tracing(&global);
+            commands::config::run(&cmd).await
+        }
+        Commands::Catalog(_global, cmd) => match commands::catalog::run(&cmd).await {
+            Ok(()) => return ExitCode::SUCCESS,
+            Err(e) => Err(e),
+        },
+        Commands::Vendor(global, mut args) => {
             setup_tracing(&global);
-            commands::format::run(&args, global.verbose)
+            match commands::vendor::run(&mut args).await {
+                Ok(()) => return ExitCode::SUCCESS,
+                Err(e) => Err(e),
+            }
         }
         Commands::Init(_global) => match commands::init::run() {
             Ok(()) => return ExitCode::SUCCESS,
             Err(e) => Err(e),
         },
+        Commands::New(global, args) => {
+            setup_tracing(&global);
+            match commands::new::run(&args).await {
+                Ok(()) => return ExitCode::SUCCESS,
+                Err(e) => Err(e),
+            }
+        }
         Commands::Convert(_global, args) => match commands::convert::run(&args) {
             Ok(()) => return ExitCode::SUCCESS,
             Err(e) => Err(e),
         },
+        Commands::Migrate(_global, args) => match commands::migrate::run(&args) {
+            Ok(()) => return ExitCode::SUCCESS,
+            Err(e) => Err(e),
+        },
         Commands::GithubAction(global, mut args) => {
             setup_tracing(&global);
             commands::github_action::run(&mut args).await
@@ -280,6 +357,10 @@ async fn main() -> ExitCode {
             setup_tracing(&global);
             lintel_schema::run(cmd).await.map(|()| false)
         }
+        Commands::SchemaLint(global, args) => {
+            setup_tracing(&global);
+            commands::schema_lint::run(&args)
+        }
         Commands::Cache(global, cmd) => {
             setup_tracing(&global);
             commands::cache::run(cmd, &global).await
@@ -301,17 +382,23 @@ async fn main() -> ExitCode {
         }
     };
 
-    match result {
-        Ok(had_errors) => {
-            if had_errors {
-                ExitCode::from(1)
-            } else {
-                ExitCode::SUCCESS
-            }
+    exit_code(result.map(|had_errors| {
+        if had_errors {
+            ExitStatus::Invalid
+        } else {
+            ExitStatus::Success
         }
+    }))
+}
+
+/// Exit with `status`, or with [`ExitStatus::Config`] after printing the
+/// error of a command that could not run.
+fn exit_code(result: anyhow::Result<ExitStatus>) -> ExitCode {
+    match result {
+        Ok(status) => ExitCode::from(u8::from(status)),
         Err(e) => {
             eprintln!("Error: {e:#}");
-            ExitCode::from(2)
+            ExitCode::from(u8::from(ExitStatus::Config))
         }
     }
 }
@@ -421,6 +508,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn cli_parses_validate_stdin() -> anyhow::Result<()> {
+        let cli = cli()
+            .run_inner(&["validate", "--stdin", "--stdin-filepath", "src/app.yaml"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match cli.command {
+            Commands::Validate(_, _, args) => {
+                assert!(args.stdin);
+                assert_eq!(args.stdin_filepath.as_deref(), Some("src/app.yaml"));
+                let lib_args = lintel_validate::validate::ValidateArgs::from(&args);
+                assert_eq!(lib_args.config_dir, Some("src".into()));
+            }
+            _ => panic!("expected Validate"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_validate_since() -> anyhow::Result<()> {
+        let cli = cli()
+            .run_inner(&["validate", "--since", "origin/main", "--diff-filter", "AM"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match cli.command {
+            Commands::Validate(_, _, args) => {
+                assert_eq!(args.since.as_deref(), Some("origin/main"));
+                assert_eq!(args.diff_filter.as_deref(), Some("AM"));
+            }
+            _ => panic!("expected Validate"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_validate_compose_merge() -> anyhow::Result<()> {
+        let cli = cli()
+            .run_inner(&["validate", "--compose-merge", "base.yml", "override.yml"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match cli.command {
+            Commands::Validate(_, _, args) => {
+                assert!(args.compose_merge);
+                assert_eq!(args.globs, ["base.yml", "override.yml"]);
+            }
+            _ => panic!("expected Validate"),
+        }
+        Ok(())
+    }
+
     #[test]
     fn cli_check_default_reporter_is_pretty() -> anyhow::Result<()> {
         let parsed = cli()
@@ -477,6 +611,90 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn cli_ci_output_github() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["ci", "--output", "github"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::CI(_, reporter_kind, _) => {
+                assert_eq!(reporter_kind, ReporterKind::Github);
+            }
+            _ => panic!("expected CI"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_validate_output_github() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["validate", "--output", "github", "*.json"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Validate(_, reporter_kind, args) => {
+                assert_eq!(reporter_kind, ReporterKind::Github);
+                assert_eq!(args.globs, vec!["*.json"]);
+            }
+            _ => panic!("expected Validate"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_check_reporter_sarif() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["check", "--reporter", "sarif"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Check(_, reporter_kind, _) => {
+                assert_eq!(reporter_kind, ReporterKind::Sarif);
+            }
+            _ => panic!("expected Check"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_migrate() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&[
+                "migrate",
+                "--in-place",
+                "--target",
+                "draft-07",
+                "schemas/*.json",
+            ])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Migrate(_, args) => {
+                assert!(args.in_place);
+                assert_eq!(args.target, commands::migrate::Target::Draft07);
+                assert_eq!(args.globs, vec!["schemas/*.json"]);
+            }
+            _ => panic!("expected Migrate"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_new() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["new", "tsconfig", "-o", "app/tsconfig.json", "--force"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::New(_, args) => {
+                assert_eq!(args.name, "tsconfig");
+                assert_eq!(
+                    args.output,
+                    Some(std::path::PathBuf::from("app/tsconfig.json"))
+                );
+                assert!(args.force);
+            }
+            _ => panic!("expected New"),
+        }
+        Ok(())
+    }
+
     // --- explain subcommand ---
 
     #[test]
@@ -650,4 +868,113 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn cli_parses_format_sort_keys() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["format", "--no-catalog", "--sort-keys", "schema", "."])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Format(_, cache, args) => {
+                assert!(cache.no_catalog);
+                assert_eq!(args.sort_keys, lintel_format::SortKeys::Schema);
+                assert_eq!(args.globs, vec!["."]);
+            }
+            _ => panic!("expected Format"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_format_check_diff() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["format", "--check", "--diff", "src"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Format(_, _, args) => {
+                assert!(args.check);
+                assert!(args.diff);
+                assert_eq!(args.globs, vec!["src"]);
+            }
+            _ => panic!("expected Format"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_cache_prune() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&[
+                "cache",
+                "prune",
+                "--older-than",
+                "30d",
+                "--cache-dir",
+                "/tmp/c",
+            ])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Cache(_, commands::cache::CacheCommand::Prune(args)) => {
+                assert_eq!(args.older_than, core::time::Duration::from_secs(30 * 86400));
+                assert_eq!(args.cache_dir.as_deref(), Some("/tmp/c"));
+            }
+            _ => panic!("expected Cache Prune"),
+        }
+        assert!(cli().run_inner(&["cache", "prune"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_config_check() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["config", "check", "sub"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Config(_, commands::config::ConfigCommand::Check(args)) => {
+                assert_eq!(args.dir, Some(std::path::PathBuf::from("sub")));
+            }
+            _ => panic!("expected Config"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_catalog_build() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&[
+                "catalog",
+                "build",
+                "schemas",
+                "-o",
+                "catalog.json",
+                "--file-match",
+                "a.json=*.a.yaml",
+            ])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Catalog(_, commands::catalog::CatalogCommand::Build(args)) => {
+                assert_eq!(args.dir, std::path::PathBuf::from("schemas"));
+                assert_eq!(args.output, Some(std::path::PathBuf::from("catalog.json")));
+                assert_eq!(args.file_match, ["a.json=*.a.yaml"]);
+            }
+            _ => panic!("expected Catalog"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_vendor() -> anyhow::Result<()> {
+        let parsed = cli()
+            .run_inner(&["vendor", "configs", "--exclude", "tmp/**"])
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        match parsed.command {
+            Commands::Vendor(_, args) => {
+                assert_eq!(args.dir, ".lintel/schemas");
+                assert_eq!(args.validate.globs, ["configs"]);
+                assert_eq!(args.validate.exclude, ["tmp/**"]);
+            }
+            _ => panic!("expected Vendor"),
+        }
+        Ok(())
+    }
 }
@@ -8,30 +8,40 @@ fn cases_root() -> PathBuf {
         .expect("e2e-tests/cases directory must exist")
 }
 
-fn run_lintel_ci(case_name: &str) -> Output {
+fn run_lintel(case_name: &str, args: &[&str]) -> Output {
     let case_dir = cases_root().join(case_name);
     assert!(case_dir.is_dir(), "case directory not found: {case_name}");
 
     Command::new(env!("CARGO_BIN_EXE_lintel"))
-        .args(["ci", "--no-catalog"])
+        .args(args)
         .current_dir(&case_dir)
         .output()
         .expect("failed to execute lintel")
 }
 
 fn snapshot_output(output: &Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     let code = output.status.code().unwrap_or(-1);
-    format!("{stderr}exit code: {code}\n")
+    format!("{stdout}{stderr}exit code: {code}\n")
 }
 
+/// `e2e_test!(name)` runs `lintel ci --no-catalog` in the case directory
+/// named after the test; `e2e_test!(name, "case", [args...])` runs the given
+/// command in the given case directory.
 macro_rules! e2e_test {
     ($name:ident) => {
+        e2e_test!(
+            $name,
+            &stringify!($name).replace('_', "-"),
+            ["ci", "--no-catalog"]
+        );
+    };
+    ($name:ident, $case:expr, [$($arg:expr),* $(,)?]) => {
         #[test]
         #[ignore = "requires built binary and e2e fixtures"]
         fn $name() {
-            let case = stringify!($name).replace('_', "-");
-            let output = run_lintel_ci(&case);
+            let output = run_lintel($case, &[$($arg),*]);
             let text = snapshot_output(&output);
 
             let mut settings = insta::Settings::clone_current();
@@ -53,3 +63,21 @@ e2e_test!(jsonl_validation);
 e2e_test!(jsonl_schema_mismatch);
 
 e2e_test!(schemastore);
+
+e2e_test!(
+    timeout_per_file,
+    "timeout-per-file",
+    ["ci", "--no-catalog", "--timeout-per-file", "1ns"]
+);
+
+e2e_test!(
+    explain_jsonl_validation_schema,
+    "jsonl-validation",
+    ["explain", "--schema", "schema.json", "--no-pager"]
+);
+
+e2e_test!(
+    format_check_malformed_json,
+    "malformed-json",
+    ["format", "--check"]
+);
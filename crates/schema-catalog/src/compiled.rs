@@ -28,6 +28,20 @@ pub struct SchemaMatch<'a> {
     pub description: Option<&'a str>,
 }
 
+/// A catalog entry returned by [`CompiledCatalog::entries`] and
+/// [`CompiledCatalog::search`].
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    /// The schema URL.
+    pub url: &'a str,
+    /// Human-readable schema name from the catalog.
+    pub name: &'a str,
+    /// Description from the catalog entry, if present.
+    pub description: Option<&'a str>,
+    /// All `fileMatch` globs from the catalog entry.
+    pub file_match: &'a [String],
+}
+
 /// A glob entry stored in the `GlobMap`, carrying the schema URL and the original pattern.
 struct GlobEntry {
     url: String,
@@ -138,6 +152,43 @@ impl CompiledCatalog {
     pub fn schema_name(&self, url: &str) -> Option<&str> {
         self.url_to_entry.get(url).map(|e| e.name.as_str())
     }
+
+    /// All entries, in URL order.
+    pub fn entries(&self) -> impl Iterator<Item = SearchHit<'_>> {
+        self.url_to_entry.iter().map(|(url, info)| SearchHit {
+            url,
+            name: &info.name,
+            description: info.description.as_deref(),
+            file_match: &info.file_match,
+        })
+    }
+
+    /// Entries whose name, `fileMatch` patterns, description or URL contain
+    /// `term`, ignoring case.
+    ///
+    /// Name matches come first, then `fileMatch` matches, then the rest;
+    /// each group is sorted by URL.
+    pub fn search(&self, term: &str) -> Vec<SearchHit<'_>> {
+        let term = term.to_lowercase();
+        let contains = |s: &str| s.to_lowercase().contains(&term);
+        let mut ranked: Vec<(u8, SearchHit<'_>)> = self
+            .entries()
+            .filter_map(|hit| {
+                let rank = if contains(hit.name) {
+                    0
+                } else if hit.file_match.iter().any(|p| contains(p)) {
+                    1
+                } else if hit.description.is_some_and(contains) || contains(hit.url) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, hit))
+            })
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, hit)| hit).collect()
+    }
 }
 
 #[cfg(test)]
@@ -332,4 +383,21 @@ mod tests {
             .expect("should match");
         assert_eq!(m.description, Some("A test schema"));
     }
+
+    #[test]
+    fn search_ranks_name_before_file_match_and_description() {
+        let mut catalog = test_catalog();
+        catalog.schemas[2].description = "Not a tsconfig".into();
+        let compiled = CompiledCatalog::compile(&catalog);
+
+        let hits = compiled.search("TSCONFIG");
+        let names: Vec<&str> = hits.iter().map(|h| h.name).collect();
+        assert_eq!(names, ["tsconfig", "no-match"]);
+        assert_eq!(hits[0].file_match, ["tsconfig.json", "tsconfig.*.json"]);
+
+        let hits = compiled.search("package.json");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].url, "https://json.schemastore.org/package.json");
+        assert!(compiled.search("nothing-like-this").is_empty());
+    }
 }
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 mod compiled;
-pub use compiled::{CompiledCatalog, SchemaMatch};
+pub use compiled::{CompiledCatalog, SchemaMatch, SearchHit};
 
 /// The default `$schema` URL for Lintel catalogs.
 pub const DEFAULT_SCHEMA_URL: &str =
@@ -155,6 +155,10 @@ pub enum FileFormat {
     Toml,
     Yaml,
     Markdown,
+    Xml,
+    Hcl,
+    Jsonnet,
+    Cue,
 }
 
 /// Generate the JSON Schema for the [`Catalog`] type.